@@ -0,0 +1,198 @@
+extern crate bincode_next as bincode;
+use bincode::{config, Decode, Encode};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use std::hint::black_box;
+
+const LEN: usize = 1024 * 1024;
+
+/// A byte that is encoded like a `u8`, but is not a `u8`, so `Vec<Byte>` is encoded and decoded element by element
+#[derive(Encode, Decode, Clone, Copy)]
+struct Byte(u8);
+
+fn bytes() -> Vec<u8> {
+    (0..LEN).map(|i| i as u8).collect()
+}
+
+fn encode_bytes(c: &mut Criterion) {
+    let config = config::standard();
+    let bulk = bytes();
+    let element_wise: Vec<Byte> = bulk.iter().copied().map(Byte).collect();
+    let mut buffer = vec![0u8; LEN + 16];
+
+    c.bench_function("encode_vec_u8_1mb", |b| {
+        b.iter(|| bincode::encode_into_slice(black_box(&bulk), &mut buffer, config).unwrap())
+    });
+    c.bench_function("encode_vec_byte_1mb", |b| {
+        b.iter(|| {
+            bincode::encode_into_slice(black_box(&element_wise), &mut buffer, config).unwrap()
+        })
+    });
+}
+
+fn decode_bytes_slice_reader(c: &mut Criterion) {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(bytes(), config).unwrap();
+    let string = bincode::encode_to_vec("a".repeat(LEN), config).unwrap();
+
+    c.bench_function("slice_reader_vec_u8_1mb", |b| {
+        b.iter(|| bincode::decode_from_slice::<Vec<u8>, _>(black_box(&encoded), config).unwrap())
+    });
+    c.bench_function("slice_reader_vec_byte_1mb", |b| {
+        b.iter(|| bincode::decode_from_slice::<Vec<Byte>, _>(black_box(&encoded), config).unwrap())
+    });
+    c.bench_function("slice_reader_string_1mb", |b| {
+        b.iter(|| bincode::decode_from_slice::<String, _>(black_box(&string), config).unwrap())
+    });
+}
+
+fn decode_bytes_io_reader(c: &mut Criterion) {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(bytes(), config).unwrap();
+    let string = bincode::encode_to_vec("a".repeat(LEN), config).unwrap();
+
+    c.bench_function("io_reader_vec_u8_1mb", |b| {
+        b.iter(|| {
+            bincode::decode_from_std_read::<Vec<u8>, _, _>(&mut black_box(&encoded[..]), config)
+                .unwrap()
+        })
+    });
+    c.bench_function("io_reader_vec_byte_1mb", |b| {
+        b.iter(|| {
+            bincode::decode_from_std_read::<Vec<Byte>, _, _>(&mut black_box(&encoded[..]), config)
+                .unwrap()
+        })
+    });
+    c.bench_function("io_reader_string_1mb", |b| {
+        b.iter(|| {
+            bincode::decode_from_std_read::<String, _, _>(&mut black_box(&string[..]), config)
+                .unwrap()
+        })
+    });
+}
+
+/// An `f32` that is not an `f32`, so `Vec<Float>` is encoded and decoded element by element
+#[derive(Encode, Decode, Clone, Copy)]
+struct Float(f32);
+
+fn floats_fixint(c: &mut Criterion) {
+    let config = config::legacy();
+    let floats: Vec<f32> = (0..LEN / 4).map(|i| i as f32 * 0.5).collect();
+    let element_wise: Vec<Float> = floats.iter().copied().map(Float).collect();
+    let encoded = bincode::encode_to_vec(&floats, config).unwrap();
+    let mut buffer = vec![0u8; encoded.len()];
+
+    c.bench_function("encode_vec_f32_1mb", |b| {
+        b.iter(|| bincode::encode_into_slice(black_box(&floats), &mut buffer, config).unwrap())
+    });
+    c.bench_function("encode_vec_float_1mb", |b| {
+        b.iter(|| {
+            bincode::encode_into_slice(black_box(&element_wise), &mut buffer, config).unwrap()
+        })
+    });
+    c.bench_function("decode_vec_f32_1mb", |b| {
+        b.iter(|| bincode::decode_from_slice::<Vec<f32>, _>(black_box(&encoded), config).unwrap())
+    });
+    c.bench_function("decode_vec_float_1mb", |b| {
+        b.iter(|| bincode::decode_from_slice::<Vec<Float>, _>(black_box(&encoded), config).unwrap())
+    });
+}
+
+fn deque_bytes(c: &mut Criterion) {
+    let config = config::standard();
+    // wrap around, so the deque consists of two slices
+    let mut deque = VecDeque::with_capacity(LEN);
+    for _ in 0..deque.capacity() - LEN / 2 {
+        deque.push_back(0u8);
+        deque.pop_front();
+    }
+    deque.extend(bytes());
+    let encoded = bincode::encode_to_vec(&deque, config).unwrap();
+    let mut buffer = vec![0u8; encoded.len()];
+
+    c.bench_function("encode_vec_deque_u8_1mb", |b| {
+        b.iter(|| bincode::encode_into_slice(black_box(&deque), &mut buffer, config).unwrap())
+    });
+    c.bench_function("decode_vec_deque_u8_1mb", |b| {
+        b.iter(|| {
+            bincode::decode_from_slice::<VecDeque<u8>, _>(black_box(&encoded), config).unwrap()
+        })
+    });
+    c.bench_function("decode_binary_heap_u8_1mb", |b| {
+        b.iter(|| {
+            bincode::decode_from_slice::<BinaryHeap<u8>, _>(black_box(&encoded), config).unwrap()
+        })
+    });
+}
+
+fn btree_map(c: &mut Criterion) {
+    let config = config::standard();
+    let map: BTreeMap<u32, u32> = (0..LEN as u32).map(|i| (i, i / 2)).collect();
+    let sorted = bincode::encode_to_vec(&map, config).unwrap();
+    // the same entries in descending order, which are inserted one by one
+    let reversed: Vec<(u32, u32)> = map.iter().rev().map(|(&k, &v)| (k, v)).collect();
+    let reversed = bincode::encode_to_vec(reversed, config).unwrap();
+
+    c.bench_function("decode_btree_map_sorted_1m", |b| {
+        b.iter(|| {
+            bincode::decode_from_slice::<BTreeMap<u32, u32>, _>(black_box(&sorted), config).unwrap()
+        })
+    });
+    c.bench_function("decode_btree_map_sorted_strict_1m", |b| {
+        let config = config.with_strict_map_order();
+        b.iter(|| {
+            bincode::decode_from_slice::<BTreeMap<u32, u32>, _>(black_box(&sorted), config).unwrap()
+        })
+    });
+    c.bench_function("decode_btree_map_reversed_1m", |b| {
+        b.iter(|| {
+            bincode::decode_from_slice::<BTreeMap<u32, u32>, _>(black_box(&reversed), config)
+                .unwrap()
+        })
+    });
+}
+
+/// A reader that only implements `read`, so it zeroes every buffer before it reads into it, as every reader did before `Reader::read_uninit`
+struct ZeroingReader<'a>(bincode::de::read::SliceReader<'a>);
+
+impl bincode::de::read::Reader for ZeroingReader<'_> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), bincode::error::DecodeError> {
+        self.0.read(bytes)
+    }
+}
+
+fn decode_bytes_100mb(c: &mut Criterion) {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(vec![7u8; 100 * LEN], config).unwrap();
+
+    let mut group = c.benchmark_group("vec_u8_100mb");
+    group.sample_size(10);
+    group.bench_function("slice_reader", |b| {
+        b.iter(|| bincode::decode_from_slice::<Vec<u8>, _>(black_box(&encoded), config).unwrap())
+    });
+    group.bench_function("io_reader", |b| {
+        b.iter(|| {
+            bincode::decode_from_std_read::<Vec<u8>, _, _>(&mut black_box(&encoded[..]), config)
+                .unwrap()
+        })
+    });
+    group.bench_function("zeroing_reader", |b| {
+        b.iter(|| {
+            let reader = ZeroingReader(bincode::de::read::SliceReader::new(black_box(&encoded)));
+            bincode::decode_from_reader::<Vec<u8>, _, _>(reader, config).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    floats_fixint,
+    deque_bytes,
+    btree_map,
+    encode_bytes,
+    decode_bytes_slice_reader,
+    decode_bytes_io_reader,
+    decode_bytes_100mb
+);
+criterion_main!(benches);
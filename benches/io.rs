@@ -0,0 +1,141 @@
+extern crate bincode_next as bincode;
+use bincode::{config, enc::write::BufferedWriter};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::io::{Seek, SeekFrom};
+
+fn file_with_u64s() -> std::fs::File {
+    // a mix of 1, 3, 5 and 9 byte varints
+    let input: Vec<u64> = (0..10_000u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (i % 4 * 16))
+        .collect();
+    let mut file = tempfile::tempfile().unwrap();
+    bincode::encode_into_std_write(input, &mut file, config::standard()).unwrap();
+    file
+}
+
+fn file_io_reader_u64(c: &mut Criterion) {
+    let mut file = file_with_u64s();
+    let config = config::standard();
+
+    c.bench_function("file_io_reader_u64", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            let _: Vec<u64> = bincode::decode_from_std_read(&mut file, config).unwrap();
+        })
+    });
+}
+
+fn file_buffered_io_reader_u64(c: &mut Criterion) {
+    let mut file = file_with_u64s();
+    let config = config::standard();
+
+    c.bench_function("file_buffered_io_reader_u64", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            let reader = bincode::BufferedIoReader::new(&mut file);
+            let _: Vec<u64> = bincode::decode_from_reader(reader, config).unwrap();
+        })
+    });
+}
+
+/// A few large blobs between many small values
+fn blobs() -> Vec<(u32, String, Vec<u8>)> {
+    (0..100u32)
+        .map(|i| (i, i.to_string(), vec![i as u8; 16 * 1024]))
+        .collect()
+}
+
+fn file_io_writer_blobs(c: &mut Criterion) {
+    let mut file = tempfile::tempfile().unwrap();
+    let input = blobs();
+    let config = config::standard();
+
+    c.bench_function("file_io_writer_blobs", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            bincode::encode_into_std_write(&input, &mut file, config).unwrap();
+        })
+    });
+}
+
+fn file_buffered_io_writer_blobs(c: &mut Criterion) {
+    let mut file = tempfile::tempfile().unwrap();
+    let input = blobs();
+    let config = config::standard();
+
+    c.bench_function("file_buffered_io_writer_blobs", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            let mut writer = bincode::BufferedIoWriter::new(&mut file);
+            bincode::encode_into_writer(&input, &mut writer, config).unwrap();
+            writer.flush().unwrap();
+        })
+    });
+}
+
+#[derive(bincode::Encode)]
+struct Record {
+    id: u32,
+    kind: u8,
+    flags: u16,
+    enabled: bool,
+    score: f32,
+    position: (i16, i16, i16),
+    parent: Option<u32>,
+    name: String,
+}
+
+/// Many structs with small fields, so most writes are only a few bytes
+fn records() -> Vec<Record> {
+    (0..1_000u32)
+        .map(|i| Record {
+            id: i,
+            kind: (i % 7) as u8,
+            flags: (i * 31) as u16,
+            enabled: i % 3 == 0,
+            score: i as f32 / 3.0,
+            position: (i as i16, -(i as i16), 5),
+            parent: i.checked_sub(1),
+            name: format!("record {i}"),
+        })
+        .collect()
+}
+
+fn file_io_writer_records(c: &mut Criterion) {
+    let mut file = tempfile::tempfile().unwrap();
+    let input = records();
+    let config = config::standard();
+
+    c.bench_function("file_io_writer_records", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            bincode::encode_into_std_write(&input, &mut file, config).unwrap();
+        })
+    });
+}
+
+fn file_buffered_writer_records(c: &mut Criterion) {
+    let mut file = tempfile::tempfile().unwrap();
+    let input = records();
+    let config = config::standard();
+
+    c.bench_function("file_buffered_writer_records", |b| {
+        b.iter(|| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+            let mut writer = BufferedWriter::new(bincode::IoWriter::new(&mut file));
+            bincode::encode_into_writer(&input, &mut writer, config).unwrap();
+            writer.finish().unwrap();
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    file_io_reader_u64,
+    file_buffered_io_reader_u64,
+    file_io_writer_blobs,
+    file_buffered_io_writer_blobs,
+    file_io_writer_records,
+    file_buffered_writer_records
+);
+criterion_main!(benches);
@@ -71,5 +71,33 @@ fn index_item_decode(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, index_item_decode);
+/// Decoding long strings is dominated by UTF-8 validation, compare with `--features simdutf8`
+fn long_string_decode(c: &mut Criterion) {
+    let config = bincode::config::standard();
+    for (name, string) in [
+        ("ascii", "a".repeat(64 * 1024)),
+        ("multibyte", "日本語 text ".repeat(4 * 1024)),
+    ] {
+        let encoded = bincode::encode_to_vec(&string, config).unwrap();
+
+        c.bench_function(&format!("decode String ({name})"), |b| {
+            b.iter(|| {
+                let _: (String, _) =
+                    black_box(bincode::decode_from_slice(black_box(&encoded), config)).unwrap();
+            });
+        });
+
+        c.bench_function(&format!("borrow decode &str ({name})"), |b| {
+            b.iter(|| {
+                let _: (&str, _) = black_box(bincode::borrow_decode_from_slice(
+                    black_box(&encoded),
+                    config,
+                ))
+                .unwrap();
+            });
+        });
+    }
+}
+
+criterion_group!(benches, index_item_decode, long_string_decode);
 criterion_main!(benches);
@@ -8,6 +8,184 @@ pub struct ContainerAttributes {
     pub decode_context: Option<(String, Literal)>,
     pub borrow_decode_bounds: Option<(String, Literal)>,
     pub encode_bounds: Option<(String, Literal)>,
+    pub tag_type: Option<(TagType, Literal)>,
+    pub prefix: Option<(Prefix, Literal)>,
+    /// The `pad_struct_to` of a struct, if it is encoded into a fixed amount of bytes
+    pub pad_struct_to: Option<Padding>,
+    /// The `option_bitmap` tag, if the options of a struct are marked in a bitmap instead of a byte each
+    pub option_bitmap: Option<Ident>,
+    /// The `length_prefixed` tag, if a struct or enum is encoded with its length in bytes in front of it
+    pub length_prefixed: Option<Ident>,
+    /// The `string_tags` tag, if the variants of an enum are identified by their names instead of a number
+    pub string_tags: Option<Ident>,
+    /// The `encode_only` tag, if the type can only derive `Encode` and `ConstEncode`
+    pub encode_only: Option<Ident>,
+    /// The `decode_only` tag, if the type can only derive `Decode`, `BorrowDecode` and `SkipDecode`
+    pub decode_only: Option<Ident>,
+}
+
+/// The `pad_to` of a field or the `pad_struct_to` of a struct: the amount of bytes it is padded to, and if the padding must be zero when decoding.
+pub struct Padding {
+    pub size: usize,
+    pub lit: Literal,
+    pub strict: bool,
+}
+
+impl Padding {
+    fn parse(lit: Literal) -> Result<Self> {
+        Ok(Self {
+            size: parse_usize(&lit)?,
+            lit,
+            strict: false,
+        })
+    }
+
+    /// Wraps `expr`, which encodes a value with `encoder`, so that it writes exactly `size` bytes.
+    pub fn encode(&self, crate_name: &str, expr: &str) -> String {
+        format!(
+            "{}::enc::Encoder::with_padding(encoder, {}, |encoder| {})",
+            crate_name, self.size, expr
+        )
+    }
+
+    /// The path of the function that decodes a value from exactly `size` bytes. With `borrow`, the bytes are borrowed from a `BorrowDecoder`.
+    pub fn decode_fn(&self, crate_name: &str, borrow: bool) -> String {
+        let (decoder, function) = if borrow {
+            ("BorrowDecoder", "borrow_with_padding")
+        } else {
+            ("Decoder", "with_padding")
+        };
+        format!(
+            "{}::de::{}::{}::<{}, _>",
+            crate_name, decoder, function, self.size
+        )
+    }
+
+    /// Wraps `expr`, which decodes a value with `decoder`, so that it reads exactly `size` bytes.
+    pub fn decode(&self, crate_name: &str, expr: &str, borrow: bool) -> String {
+        format!(
+            "{}(decoder, {}, |decoder| {})",
+            self.decode_fn(crate_name, borrow),
+            self.strict,
+            expr
+        )
+    }
+}
+
+/// Parses an integer literal like `16` or `16usize`.
+fn parse_usize(lit: &Literal) -> Result<usize> {
+    let val_string = lit.to_string().replace('_', "");
+    val_string
+        .strip_suffix("usize")
+        .unwrap_or(&val_string)
+        .parse()
+        .map_err(|_| Error::custom_at("Should be an integer literal", lit.span()))
+}
+
+/// Parses an integer literal like `7`, `0xFFFF_0000_0000` or `7u64`.
+fn parse_u64(lit: &Literal) -> Result<u64> {
+    let val_string = lit.to_string().replace('_', "");
+    let val_string = val_string.strip_suffix("u64").unwrap_or(&val_string);
+    let (digits, radix) = [("0x", 16), ("0o", 8), ("0b", 2)]
+        .into_iter()
+        .find_map(|(prefix, radix)| Some((val_string.strip_prefix(prefix)?, radix)))
+        .unwrap_or((val_string, 10));
+    u64::from_str_radix(digits, radix).map_err(|_| {
+        Error::custom_at(
+            "Should be an integer literal that fits in a u64",
+            lit.span(),
+        )
+    })
+}
+
+/// Applies the `strict_padding` tag to the `padding` it belongs to, which must be set.
+fn set_strict_padding(padding: Option<&mut Padding>, tag: &Ident, property: &str) -> Result<()> {
+    match padding {
+        Some(padding) => {
+            padding.strict = true;
+            Ok(())
+        }
+        None => Err(Error::custom_at(
+            format!("strict_padding can only be used with {}", property),
+            tag.span(),
+        )),
+    }
+}
+
+/// The `prefix` of a struct: the name of a struct to generate with the first `fields` of the struct.
+pub struct Prefix {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+impl Prefix {
+    /// Parses `"Name: field_a, field_b"`.
+    fn parse(val: &str) -> Option<Self> {
+        let is_ident = |s: &str| {
+            let s = s.strip_prefix("r#").unwrap_or(s);
+            s.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+        };
+        let (name, fields) = val.split_once(':')?;
+        let name = name.trim();
+        let fields: Vec<String> = fields.split(',').map(|f| f.trim().to_string()).collect();
+        if !is_ident(name) || !fields.iter().all(|f| is_ident(f)) {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            fields,
+        })
+    }
+}
+
+/// The `tag_type` of an enum, see `bincode::config::TagType`.
+#[derive(Clone, Copy)]
+pub enum TagType {
+    U8,
+    U16,
+    U32,
+    Varint,
+}
+
+impl TagType {
+    fn parse(val: &str) -> Option<Self> {
+        match val {
+            "u8" => Some(Self::U8),
+            "u16" => Some(Self::U16),
+            "u32" => Some(Self::U32),
+            "varint" => Some(Self::Varint),
+            _ => None,
+        }
+    }
+
+    /// The name of the `bincode::config::TagType` variant
+    pub fn variant_name(self) -> &'static str {
+        match self {
+            Self::U8 => "U8",
+            Self::U16 => "U16",
+            Self::U32 => "U32",
+            Self::Varint => "Varint",
+        }
+    }
+
+    /// The amount of variants that can be told apart with this tag type, or `None` if it can count every variant
+    pub fn max_variants(self) -> Option<usize> {
+        match self {
+            Self::U8 => Some(1 << 8),
+            Self::U16 => Some(1 << 16),
+            Self::U32 | Self::Varint => None,
+        }
+    }
+
+    /// The largest tag that fits in this tag type
+    pub fn max_tag(self) -> u64 {
+        match self {
+            Self::U8 => u8::MAX.into(),
+            Self::U16 => u16::MAX.into(),
+            Self::U32 | Self::Varint => u32::MAX.into(),
+        }
+    }
 }
 
 impl Default for ContainerAttributes {
@@ -19,6 +197,14 @@ impl Default for ContainerAttributes {
             decode_context: None,
             encode_bounds: None,
             borrow_decode_bounds: None,
+            tag_type: None,
+            prefix: None,
+            pad_struct_to: None,
+            option_bitmap: None,
+            length_prefixed: None,
+            string_tags: None,
+            encode_only: None,
+            decode_only: None,
         }
     }
 }
@@ -30,6 +216,7 @@ impl FromAttribute for ContainerAttributes {
             None => return Ok(None),
         };
         let mut result = Self::default();
+        let mut strict_padding = None;
         for attribute in attributes {
             match attribute {
                 ParsedAttribute::Property(key, val) if key.to_string() == "crate" => {
@@ -87,6 +274,59 @@ impl FromAttribute for ContainerAttributes {
                         return Err(Error::custom_at("Should be a literal str", val.span()));
                     }
                 }
+                ParsedAttribute::Property(key, val) if key.to_string() == "tag_type" => {
+                    let val_string = val.to_string();
+                    let tag_type = val_string
+                        .strip_prefix('"')
+                        .and_then(|val| val.strip_suffix('"'))
+                        .and_then(TagType::parse);
+                    match tag_type {
+                        Some(tag_type) => result.tag_type = Some((tag_type, val)),
+                        None => {
+                            return Err(Error::custom_at(
+                                "Should be one of \"u8\", \"u16\", \"u32\" or \"varint\"",
+                                val.span(),
+                            ))
+                        }
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "prefix" => {
+                    let val_string = val.to_string();
+                    let prefix = val_string
+                        .strip_prefix('"')
+                        .and_then(|val| val.strip_suffix('"'))
+                        .and_then(Prefix::parse);
+                    match prefix {
+                        Some(prefix) => result.prefix = Some((prefix, val)),
+                        None => {
+                            return Err(Error::custom_at(
+                                "Should be the name of a struct and its fields, like \"Header: version, kind\"",
+                                val.span(),
+                            ))
+                        }
+                    }
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "pad_struct_to" => {
+                    result.pad_struct_to = Some(Padding::parse(val)?);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "strict_padding" => {
+                    strict_padding = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "string_tags" => {
+                    result.string_tags = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "option_bitmap" => {
+                    result.option_bitmap = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "length_prefixed" => {
+                    result.length_prefixed = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "encode_only" => {
+                    result.encode_only = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "decode_only" => {
+                    result.decode_only = Some(i);
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -96,13 +336,116 @@ impl FromAttribute for ContainerAttributes {
                 _ => {}
             }
         }
+        if let Some(tag) = strict_padding {
+            set_strict_padding(result.pad_struct_to.as_mut(), &tag, "pad_struct_to")?;
+        }
+        result.check_direction_attributes()?;
         Ok(Some(result))
     }
 }
 
+impl ContainerAttributes {
+    /// Checks that `encode_only` and `decode_only` are not combined with each other, or with attributes that only affect the other direction.
+    fn check_direction_attributes(&self) -> Result<()> {
+        if let (Some(_), Some(decode_only)) = (&self.encode_only, &self.decode_only) {
+            return Err(Error::custom_at(
+                "encode_only and decode_only can not be combined",
+                decode_only.span(),
+            ));
+        }
+        if let Some(encode_only) = &self.encode_only {
+            if self.decode_bounds.is_some()
+                || self.borrow_decode_bounds.is_some()
+                || self.decode_context.is_some()
+                || self.prefix.is_some()
+            {
+                return Err(Error::custom_at(
+                    "encode_only can not be combined with decode_bounds, borrow_decode_bounds, decode_context or prefix, as the type is never decoded",
+                    encode_only.span(),
+                ));
+            }
+        }
+        if let (Some(decode_only), Some(_)) = (&self.decode_only, &self.encode_bounds) {
+            return Err(Error::custom_at(
+                "decode_only can not be combined with encode_bounds, as the type is never encoded",
+                decode_only.span(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the type is not `encode_only` if `derive` decodes it, or `decode_only` if `derive` encodes it.
+    pub fn check_direction(&self, derive: &str, encodes: bool) -> Result<()> {
+        let (tag, direction) = if encodes {
+            (&self.decode_only, "decode_only")
+        } else {
+            (&self.encode_only, "encode_only")
+        };
+        match tag {
+            Some(tag) => Err(Error::custom_at(
+                format!(
+                    "{} can not be derived for a type with #[bincode({})]. Remove the attribute if the type should implement {} after all",
+                    derive, direction, derive
+                ),
+                tag.span(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct FieldAttributes {
     pub with_serde: bool,
+    /// The `remaining_bytes` tag, if the field is decoded from all remaining bytes of the input. This is also set for a field with `unknown_tail`.
+    pub remaining_bytes: Option<Ident>,
+    /// The `unknown_tail` tag, if the field holds the bytes after the known fields of a struct with `length_prefixed`
+    pub unknown_tail: Option<Ident>,
+    /// The `borrow` tag, if the field is always decoded with `BorrowDecode`
+    pub borrow: Option<Ident>,
+    /// The `owned` tag, if the field is always decoded with `Decode`, even by `BorrowDecode`
+    pub owned: Option<Ident>,
+    /// Whether the field is a `Cow` that can not borrow from the input, which is decoded with `Decode` like an `owned` field. This is set by `field_attributes`, not by an attribute.
+    pub owned_cow: bool,
+    /// The `endian` of the field, as the name of the `bincode::config::Endianness` variant, if it is encoded with that instead of the endianness of the config
+    pub endian: Option<(&'static str, Literal)>,
+    /// The `pad_to` of the field, if it is encoded into a fixed amount of bytes
+    pub pad_to: Option<Padding>,
+    /// The `len` of the field, if it is encoded as exactly that many bytes without a length in front of them
+    pub len: Option<(usize, Literal)>,
+    /// The `pad` of a field with `len`, as the name of the `bincode::config::FixedLenPadding` variant
+    pub pad: Option<(&'static str, Literal)>,
+}
+
+impl FieldAttributes {
+    /// Whether the field can be in the option bitmap of a struct with `#[bincode(option_bitmap)]`: it has no attributes that change how it is encoded.
+    pub fn is_plain(&self) -> bool {
+        !self.with_serde
+            && self.remaining_bytes.is_none()
+            && self.endian.is_none()
+            && self.pad_to.is_none()
+            && self.len.is_none()
+    }
+
+    /// Whether the field is decoded with `Decode` by a derived `BorrowDecode`, because it has the `owned` tag or is a `Cow` that can not borrow from the input.
+    pub fn is_owned(&self) -> bool {
+        self.owned.is_some() || self.owned_cow
+    }
+
+    /// Whether the field is encoded and decoded with the traits of its type, so its type needs a bound of the derived trait. `with_serde`, `remaining_bytes` and `len` use other traits.
+    pub fn uses_traits(&self) -> bool {
+        !self.with_serde && self.remaining_bytes.is_none() && self.len.is_none()
+    }
+
+    /// The `len` and `pad` arguments of `encode_fixed_len` and `decode_fixed_len`, if the field has a `len`.
+    pub fn fixed_len_args(&self, crate_name: &str) -> Option<String> {
+        let (len, _) = self.len.as_ref()?;
+        let pad = self.pad.as_ref().map_or("Zero", |(pad, _)| pad);
+        Some(format!(
+            "{}, {}::config::FixedLenPadding::{}",
+            len, crate_name, pad
+        ))
+    }
 }
 
 impl FromAttribute for FieldAttributes {
@@ -112,11 +455,60 @@ impl FromAttribute for FieldAttributes {
             None => return Ok(None),
         };
         let mut result = Self::default();
+        let mut strict_padding = None;
         for attribute in attributes {
             match attribute {
                 ParsedAttribute::Tag(i) if i.to_string() == "with_serde" => {
                     result.with_serde = true;
                 }
+                ParsedAttribute::Tag(i) if i.to_string() == "remaining_bytes" => {
+                    result.remaining_bytes = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "unknown_tail" => {
+                    result.unknown_tail = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "borrow" => {
+                    result.borrow = Some(i);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "owned" => {
+                    result.owned = Some(i);
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "endian" => {
+                    let endian = match val.to_string().as_str() {
+                        "\"big\"" => "Big",
+                        "\"little\"" => "Little",
+                        _ => {
+                            return Err(Error::custom_at(
+                                "Should be \"big\" or \"little\"",
+                                val.span(),
+                            ))
+                        }
+                    };
+                    result.endian = Some((endian, val));
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "pad_to" => {
+                    result.pad_to = Some(Padding::parse(val)?);
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "len" => {
+                    result.len = Some((parse_usize(&val)?, val));
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "pad" => {
+                    let pad = match val.to_string().as_str() {
+                        "\"zero\"" => "Zero",
+                        "\"space\"" => "Space",
+                        "\"none\"" => "None",
+                        _ => {
+                            return Err(Error::custom_at(
+                                "Should be \"zero\", \"space\" or \"none\"",
+                                val.span(),
+                            ))
+                        }
+                    };
+                    result.pad = Some((pad, val));
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "strict_padding" => {
+                    strict_padding = Some(i);
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -126,6 +518,140 @@ impl FromAttribute for FieldAttributes {
                 _ => {}
             }
         }
+        if let Some(tag) = strict_padding {
+            set_strict_padding(result.pad_to.as_mut(), &tag, "pad_to")?;
+        }
+        if let (None, Some((_, pad))) = (&result.len, &result.pad) {
+            return Err(Error::custom_at(
+                "pad can only be used with len",
+                pad.span(),
+            ));
+        }
+        if let Some((_, len)) = &result.len {
+            if result.with_serde
+                || result.remaining_bytes.is_some()
+                || result.endian.is_some()
+                || result.borrow.is_some()
+                || result.owned.is_some()
+            {
+                return Err(Error::custom_at(
+                    "len can not be combined with with_serde, remaining_bytes, endian, borrow or owned",
+                    len.span(),
+                ));
+            }
+        }
+        if let (Some(_), Some(remaining_bytes)) = (&result.pad_to, &result.remaining_bytes) {
+            return Err(Error::custom_at(
+                "remaining_bytes can not be combined with pad_to",
+                remaining_bytes.span(),
+            ));
+        }
+        if let (true, Some(remaining_bytes)) = (result.with_serde, &result.remaining_bytes) {
+            return Err(Error::custom_at(
+                "remaining_bytes can not be combined with with_serde",
+                remaining_bytes.span(),
+            ));
+        }
+        if let (Some(_), Some(owned)) = (&result.borrow, &result.owned) {
+            return Err(Error::custom_at(
+                "borrow and owned can not be combined",
+                owned.span(),
+            ));
+        }
+        if let Some((_, endian)) = &result.endian {
+            if result.with_serde
+                || result.remaining_bytes.is_some()
+                || result.borrow.is_some()
+                || result.owned.is_some()
+            {
+                return Err(Error::custom_at(
+                    "endian can not be combined with with_serde, remaining_bytes, borrow or owned",
+                    endian.span(),
+                ));
+            }
+        }
+        if let Some(tag) = result.borrow.as_ref().or(result.owned.as_ref()) {
+            if result.with_serde || result.remaining_bytes.is_some() {
+                return Err(Error::custom_at(
+                    format!(
+                        "{} can not be combined with with_serde or remaining_bytes",
+                        tag
+                    ),
+                    tag.span(),
+                ));
+            }
+        }
+        if let Some(unknown_tail) = &result.unknown_tail {
+            if result.with_serde
+                || result.remaining_bytes.is_some()
+                || result.endian.is_some()
+                || result.borrow.is_some()
+                || result.owned.is_some()
+                || result.pad_to.is_some()
+                || result.len.is_some()
+            {
+                return Err(Error::custom_at(
+                    "unknown_tail can not be combined with other field attributes",
+                    unknown_tail.span(),
+                ));
+            }
+            // the tail is the rest of the section of the struct, so it is encoded and decoded like a field with `remaining_bytes`
+            result.remaining_bytes = Some(unknown_tail.clone());
+        }
+        Ok(Some(result))
+    }
+}
+
+#[derive(Default)]
+pub struct VariantAttributes {
+    /// The `discriminant` of the variant, if its tag is set instead of following the tag of the variant before it
+    pub discriminant: Option<(u64, Literal)>,
+    /// The `other` tag, if the variant is decoded from every tag that is not the tag of another variant
+    pub other: Option<Ident>,
+}
+
+impl FromAttribute for VariantAttributes {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "bincode")? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        let mut result = Self::default();
+        for attribute in attributes {
+            match attribute {
+                ParsedAttribute::Property(key, val) if key.to_string() == "discriminant" => {
+                    result.discriminant = Some((parse_u64(&val)?, val));
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "other" => {
+                    result.other = Some(i);
+                }
+                ParsedAttribute::Tag(i) => {
+                    return Err(Error::custom_at("Unknown variant attribute", i.span()))
+                }
+                ParsedAttribute::Property(key, _) => {
+                    return Err(Error::custom_at("Unknown variant attribute", key.span()))
+                }
+                _ => {}
+            }
+        }
         Ok(Some(result))
     }
 }
+
+/// The `#[repr(..)]` of a type, which `TransparentWire` checks for `transparent`.
+pub struct Repr {
+    pub transparent: bool,
+}
+
+impl FromAttribute for Repr {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        // reprs like `align(8)` are not understood by `parse_tagged_attribute`, but are not `transparent` either
+        let Ok(Some(attributes)) = parse_tagged_attribute(group, "repr") else {
+            return Ok(None);
+        };
+        let transparent = attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ParsedAttribute::Tag(i) if i.to_string() == "transparent"));
+        Ok(Some(Self { transparent }))
+    }
+}
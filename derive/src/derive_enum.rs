@@ -1,4 +1,11 @@
-use crate::attribute::{ContainerAttributes, FieldAttributes};
+use crate::attribute::{ContainerAttributes, FieldAttributes, TagType, VariantAttributes};
+use crate::{
+    borrow_decode_trait, check_borrow_attributes, check_borrowed_references,
+    check_endian_attributes, check_remaining_bytes, check_trait_objects, field_attributes,
+    field_error_context, fields_layout, find_reference, generate_borrow_decode_with_decode,
+    is_owned_lifetime, push_field_bounds, push_struct_decode, skip_field, stats_markers,
+    unnamed_fields,
+};
 use virtue::prelude::*;
 
 const TUPLE_FIELD_PREFIX: &str = "field_";
@@ -9,15 +16,284 @@ pub(crate) struct DeriveEnum {
 }
 
 impl DeriveEnum {
-    fn iter_fields(&self) -> EnumVariantIterator<'_> {
-        EnumVariantIterator {
-            idx: 0,
-            variants: &self.variants,
+    /// Returns the fields of every variant.
+    fn all_fields(&self) -> impl Iterator<Item = &UnnamedField> {
+        self.variants
+            .iter()
+            .flat_map(|variant| unnamed_fields(variant.fields.as_ref()))
+    }
+
+    /// Returns the tag of every variant: its `#[bincode(discriminant = N)]`, or one more than the tag of the variant before it, like the discriminants of Rust. The tag of the first variant is 0 by default.
+    fn variant_tags(&self) -> Result<Vec<u64>> {
+        let mut tags: Vec<u64> = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            let attributes = variant
+                .attributes
+                .get_attribute::<VariantAttributes>()?
+                .unwrap_or_default();
+            let (tag, span) = match &attributes.discriminant {
+                Some((tag, lit)) => (*tag, lit.span()),
+                None => {
+                    let tag = match tags.last() {
+                        Some(last) => last.checked_add(1).ok_or_else(|| {
+                            Error::custom_at(
+                                "The tag of this variant does not fit in a u64, give it a discriminant",
+                                variant.name.span(),
+                            )
+                        })?,
+                        None => 0,
+                    };
+                    (tag, variant.name.span())
+                }
+            };
+            if let Some(idx) = tags.iter().position(|&t| t == tag) {
+                return Err(Error::custom_at(
+                    format!(
+                        "The tag of variant {} is {}, which is already the tag of variant {}",
+                        variant.name, tag, self.variants[idx].name
+                    ),
+                    span,
+                ));
+            }
+            tags.push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Returns the variant with `#[bincode(other)]`, which unknown tags are decoded into, after checking that there is at most one, that it can hold the tag, and that the fields of an unknown variant can be skipped.
+    fn other_variant(&self) -> Result<Option<&EnumVariant>> {
+        let mut other: Option<(&EnumVariant, Ident)> = None;
+        for variant in &self.variants {
+            let attributes = variant
+                .attributes
+                .get_attribute::<VariantAttributes>()?
+                .unwrap_or_default();
+            let Some(tag) = attributes.other else {
+                continue;
+            };
+            if other.is_some() {
+                return Err(Error::custom_at(
+                    "Only one variant can have #[bincode(other)]",
+                    tag.span(),
+                ));
+            }
+            if self.attributes.string_tags.is_some() {
+                return Err(Error::custom_at(
+                    "other can not be used on the variants of an enum with string_tags",
+                    tag.span(),
+                ));
+            }
+            let fields = unnamed_fields(variant.fields.as_ref());
+            let mut plain = fields.len() <= 1;
+            for field in &fields {
+                plain &= field
+                    .attributes
+                    .get_attribute::<FieldAttributes>()?
+                    .is_none();
+            }
+            if !plain {
+                return Err(Error::custom_at(
+                    "A variant with #[bincode(other)] can only have no fields, or a single field without attributes that holds the unknown tag, like Unknown(u32)",
+                    tag.span(),
+                ));
+            }
+            other = Some((variant, tag));
+        }
+        let Some((other, tag)) = other else {
+            return Ok(None);
+        };
+        let has_fields = self.variants.iter().any(|variant| {
+            !std::ptr::eq(variant, other) && !unnamed_fields(variant.fields.as_ref()).is_empty()
+        });
+        if has_fields && self.attributes.length_prefixed.is_none() {
+            return Err(Error::custom_at(
+                "#[bincode(other)] can only be used in an enum with fields if the enum has #[bincode(length_prefixed)], as the fields of an unknown variant can not be skipped otherwise",
+                tag.span(),
+            ));
+        }
+        Ok(Some(other))
+    }
+
+    /// Returns the name of the field that holds the tag if `variant` is the `other` variant and has a field, as it is named in a struct expression (`0` for a tuple variant) and as it is bound in the `match` of `encode`.
+    fn tag_field(&self, variant: &EnumVariant) -> Result<Option<(String, String)>> {
+        let is_other = self
+            .other_variant()?
+            .is_some_and(|other| std::ptr::eq(other, variant));
+        let Some(fields) = variant.fields.as_ref().filter(|_| is_other) else {
+            return Ok(None);
+        };
+        let Some(field) = fields.names().into_iter().next() else {
+            return Ok(None);
+        };
+        let name = if matches!(fields, Fields::Tuple(_)) {
+            String::from("0")
+        } else {
+            field.unwrap_ident().to_string()
+        };
+        Ok(Some((
+            name,
+            field.to_string_with_prefix(TUPLE_FIELD_PREFIX),
+        )))
+    }
+
+    /// Returns every variant with its tag, as a literal of the type the tag is encoded as: a `u64` if the enum has no `tag_type` and a tag does not fit in a `u32`, and a `u32` otherwise. With `string_tags`, the tag is the index of the variant as a `usize`.
+    fn iter_fields(&self) -> Result<Vec<(TokenTree, &EnumVariant)>> {
+        if self.attributes.string_tags.is_some() {
+            self.tag_type()?;
+            return Ok(self
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(idx, variant)| (TokenTree::Literal(Literal::usize_suffixed(idx)), variant))
+                .collect());
+        }
+        let wide = self.wide_tags()?;
+        Ok(self
+            .variant_tags()?
+            .into_iter()
+            .zip(&self.variants)
+            .map(|(tag, variant)| {
+                let literal = if wide {
+                    Literal::u64_suffixed(tag)
+                } else {
+                    Literal::u32_suffixed(tag as u32)
+                };
+                (TokenTree::Literal(literal), variant)
+            })
+            .collect())
+    }
+
+    /// Returns the `tag_type` of the enum, after checking that every variant fits in it and that the fields of every variant are valid.
+    fn tag_type(&self) -> Result<Option<TagType>> {
+        if let Some((_, lit)) = &self.attributes.prefix {
+            return Err(Error::custom_at(
+                "prefix can only be used on structs",
+                lit.span(),
+            ));
+        }
+        if let Some(option_bitmap) = &self.attributes.option_bitmap {
+            return Err(Error::custom_at(
+                "option_bitmap can only be used on structs",
+                option_bitmap.span(),
+            ));
+        }
+        if let Some(padding) = &self.attributes.pad_struct_to {
+            return Err(Error::custom_at(
+                "pad_struct_to can only be used on structs, use pad_to on the fields of a variant instead",
+                padding.lit.span(),
+            ));
         }
+        self.other_variant()?;
+        for variant in &self.variants {
+            check_remaining_bytes(variant.fields.as_ref(), false)?;
+            check_endian_attributes(variant.fields.as_ref())?;
+            check_borrow_attributes(
+                variant.fields.as_ref(),
+                false,
+                self.attributes.decode_context.is_some(),
+            )?;
+        }
+        if self.attributes.string_tags.is_some() {
+            if let Some((_, lit)) = &self.attributes.tag_type {
+                return Err(Error::custom_at(
+                    "string_tags can not be combined with tag_type",
+                    lit.span(),
+                ));
+            }
+            for variant in &self.variants {
+                let attributes = variant
+                    .attributes
+                    .get_attribute::<VariantAttributes>()?
+                    .unwrap_or_default();
+                if let Some((_, lit)) = &attributes.discriminant {
+                    return Err(Error::custom_at(
+                        "discriminant can not be used on the variants of an enum with string_tags",
+                        lit.span(),
+                    ));
+                }
+            }
+        }
+        let Some((tag_type, lit)) = &self.attributes.tag_type else {
+            return Ok(None);
+        };
+        if let Some(max) = tag_type.max_variants() {
+            if self.variants.len() > max {
+                return Err(Error::custom_at(
+                    format!(
+                        "This enum has {} variants, but a tag of type {} can only tell {} variants apart",
+                        self.variants.len(),
+                        lit,
+                        max
+                    ),
+                    lit.span(),
+                ));
+            }
+        }
+        let tags = self.variant_tags()?;
+        if let Some((tag, variant)) = tags
+            .iter()
+            .zip(&self.variants)
+            .find(|(&tag, _)| tag > tag_type.max_tag())
+        {
+            return Err(Error::custom_at(
+                format!(
+                    "The tag {} of variant {} does not fit in a tag of type {}",
+                    tag, variant.name, lit
+                ),
+                lit.span(),
+            ));
+        }
+        Ok(Some(*tag_type))
+    }
+
+    /// Returns the name of every variant as a string literal, which is the tag of the variant with `string_tags`.
+    fn variant_names(&self) -> Vec<String> {
+        self.variants
+            .iter()
+            .map(|variant| format!("{:?}", variant.name.to_string().trim_start_matches("r#")))
+            .collect()
+    }
+
+    /// Whether the tags are encoded as a `u64`, because the enum has no `tag_type` and a tag does not fit in a `u32`.
+    fn wide_tags(&self) -> Result<bool> {
+        Ok(self.tag_type()?.is_none()
+            && self.attributes.string_tags.is_none()
+            && self
+                .variant_tags()?
+                .iter()
+                .any(|&tag| tag > u32::MAX.into()))
+    }
+
+    /// Generates the statement that decodes the tag of the variant into `variant_index`. With `string_tags`, this is the index of the name of the variant, which `borrow` compares in the input.
+    fn decode_tag(&self, enum_name: &str, borrow: bool) -> Result<String> {
+        let crate_name = self.attributes.crate_name.as_str();
+        if self.attributes.string_tags.is_some() {
+            self.tag_type()?;
+            return Ok(format!(
+                "let variant_index = {}::de::{}decode_variant_name(decoder, {:?}, &[{}])?;",
+                crate_name,
+                if borrow { "borrow_" } else { "" },
+                enum_name,
+                self.variant_names().join(", ")
+            ));
+        }
+        Ok(match self.tag_type()? {
+            Some(tag_type) => format!(
+                "let variant_index = {0}::de::decode_variant_tag(decoder, {0}::config::TagType::{1})?;",
+                crate_name,
+                tag_type.variant_name()
+            ),
+            None => format!(
+                "let variant_index = <{} as {}::Decode::<__D::Context>>::decode(decoder)?;",
+                if self.wide_tags()? { "u64" } else { "u32" },
+                crate_name
+            ),
+        })
     }
 
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
         let crate_name = self.attributes.crate_name.as_str();
+        let enum_name = generator.target_name().to_string();
         generator
             .impl_for(format!("{}::Encode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
@@ -29,11 +305,17 @@ impl DeriveEnum {
                         .push_parsed_constraint(bounds)
                         .map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints
-                            .push_constraint(g, format!("{}::Encode", crate_name))
-                            .unwrap();
-                    }
+                    push_field_bounds(
+                        generics,
+                        where_constraints,
+                        self.all_fields(),
+                        &enum_name,
+                        |attributes| {
+                            attributes
+                                .uses_traits()
+                                .then(|| format!("{}::Encode", crate_name))
+                        },
+                    )?;
                 }
                 Ok(())
             })?
@@ -46,91 +328,236 @@ impl DeriveEnum {
                 crate_name
             ))
             .body(|fn_body| {
-                fn_body.ident_str("match");
-                fn_body.ident_str("self");
-                fn_body.group(Delimiter::Brace, |match_body| {
-                    if self.variants.is_empty() {
-                        self.encode_empty_enum_case(match_body)?;
-                    }
-                    for (variant_index, variant) in self.iter_fields() {
-                        // Self::Variant
-                        match_body.ident_str("Self");
-                        match_body.puncts("::");
-                        match_body.ident(variant.name.clone());
-
-                        // if we have any fields, declare them here
-                        // Self::Variant { a, b, c }
-                        if let Some(fields) = variant.fields.as_ref() {
-                            let delimiter = fields.delimiter();
-                            match_body.group(delimiter, |field_body| {
-                                for (idx, field_name) in fields.names().into_iter().enumerate() {
-                                    if idx != 0 {
-                                        field_body.punct(',');
-                                    }
-                                    field_body.push(
-                                        field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
-                                    );
-                                }
-                                Ok(())
-                            })?;
+                if self.attributes.length_prefixed.is_some() {
+                    fn_body
+                        .push_parsed(format!("{}::enc::Encoder::with_length_prefix", crate_name))?;
+                    fn_body.group(Delimiter::Parenthesis, |args| {
+                        args.push_parsed("encoder, |encoder|")?;
+                        args.group(Delimiter::Brace, |body| {
+                            self.encode_variants(&enum_name, body)
+                        })?;
+                        Ok(())
+                    })?;
+                } else {
+                    self.encode_variants(&enum_name, fn_body)?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Generates the `match` that encodes the variant of `self`, in the `encode` function of the enum `enum_name`.
+    fn encode_variants(&self, enum_name: &str, fn_body: &mut StreamBuilder) -> Result<()> {
+        let crate_name = self.attributes.crate_name.as_str();
+        let tag_type = self.tag_type()?;
+        let tag_int = if self.wide_tags()? { "u64" } else { "u32" };
+        fn_body.ident_str("match");
+        fn_body.ident_str("self");
+        fn_body.group(Delimiter::Brace, |match_body| {
+            if self.variants.is_empty() {
+                self.encode_empty_enum_case(match_body)?;
+            }
+            for (variant_index, variant) in self.iter_fields()? {
+                // Self::Variant
+                match_body.ident_str("Self");
+                match_body.puncts("::");
+                match_body.ident(variant.name.clone());
+
+                // if we have any fields, declare them here
+                // Self::Variant { a, b, c }
+                if let Some(fields) = variant.fields.as_ref() {
+                    let delimiter = fields.delimiter();
+                    match_body.group(delimiter, |field_body| {
+                        for (idx, field_name) in fields.names().into_iter().enumerate() {
+                            if idx != 0 {
+                                field_body.punct(',');
+                            }
+                            field_body.push(
+                                field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
+                            );
                         }
+                        Ok(())
+                    })?;
+                }
 
-                        // Arrow
-                        // Self::Variant { a, b, c } =>
-                        match_body.puncts("=>");
-
-                        // Body of this variant
-                        // Note that the fields are available as locals because of the match destructuring above
-                        // {
-                        //      encoder.encode_u32(n)?;
-                        //      bincode::Encode::encode(a, encoder)?;
-                        //      bincode::Encode::encode(b, encoder)?;
-                        //      bincode::Encode::encode(c, encoder)?;
-                        // }
-                        match_body.group(Delimiter::Brace, |body| {
-                            // variant index
-                            body.push_parsed(format!("<u32 as {}::Encode>::encode", crate_name))?;
-                            body.group(Delimiter::Parenthesis, |args| {
-                                args.punct('&');
-                                args.group(Delimiter::Parenthesis, |num| {
-                                    num.extend(variant_index);
-                                    Ok(())
-                                })?;
-                                args.punct(',');
-                                args.push_parsed("encoder")?;
+                // Arrow
+                // Self::Variant { a, b, c } =>
+                match_body.puncts("=>");
+
+                // Body of this variant
+                // Note that the fields are available as locals because of the match destructuring above
+                // {
+                //      encoder.encode_u32(n)?;
+                //      bincode::Encode::encode(a, encoder)?;
+                //      bincode::Encode::encode(b, encoder)?;
+                //      bincode::Encode::encode(c, encoder)?;
+                // }
+                // the `other` variant writes the tag that it holds instead of its own
+                let tag_field = self.tag_field(variant)?;
+                let tag = match &tag_field {
+                    Some((_, field_name)) => format!("*{}", field_name),
+                    None => variant_index.to_string(),
+                };
+                match_body.group(Delimiter::Brace, |body| {
+                    // variant index
+                    if self.attributes.string_tags.is_some() {
+                        body.push_parsed(format!(
+                            "<str as {}::Encode>::encode({:?}, encoder)",
+                            crate_name,
+                            variant.name.to_string().trim_start_matches("r#")
+                        ))?;
+                    } else if let Some(tag_type) = tag_type {
+                        body.push_parsed(format!(
+                            "{}::enc::encode_variant_tag",
+                            crate_name
+                        ))?;
+                        body.group(Delimiter::Parenthesis, |args| {
+                            args.push_parsed("encoder")?;
+                            args.punct(',');
+                            args.push_parsed(&tag)?;
+                            args.punct(',');
+                            args.push_parsed(format!(
+                                "{}::config::TagType::{}",
+                                crate_name,
+                                tag_type.variant_name()
+                            ))?;
+                            Ok(())
+                        })?;
+                    } else {
+                        body.push_parsed(format!(
+                            "<{} as {}::Encode>::encode",
+                            tag_int, crate_name
+                        ))?;
+                        body.group(Delimiter::Parenthesis, |args| {
+                            args.punct('&');
+                            args.group(Delimiter::Parenthesis, |num| {
+                                num.push_parsed(&tag)?;
                                 Ok(())
                             })?;
-                            body.punct('?');
-                            body.punct(';');
-                            // If we have any fields, encode them all one by one
-                            if let Some(fields) = variant.fields.as_ref() {
-                                for field_name in fields.names() {
-                                    let attributes = field_name
-                                        .attributes()
-                                        .get_attribute::<FieldAttributes>()?
-                                        .unwrap_or_default();
-                                    if attributes.with_serde {
-                                        body.push_parsed(format!(
-                                        "{0}::Encode::encode(&{0}::serde::Compat({1}), encoder)?;",
-                                        crate_name,
-                                        field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                    ))?;
-                                    } else {
-                                        body.push_parsed(format!(
-                                            "{0}::Encode::encode({1}, encoder)?;",
-                                            crate_name,
-                                            field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                        ))?;
-                                    }
-                                }
-                            }
-                            body.push_parsed("core::result::Result::Ok(())")?;
+                            args.punct(',');
+                            args.push_parsed("encoder")?;
                             Ok(())
                         })?;
-                        match_body.punct(',');
                     }
+                    body.punct('?');
+                    body.punct(';');
+                    // If we have any fields, encode them all one by one
+                    if let Some(fields) = variant.fields.as_ref().filter(|_| tag_field.is_none()) {
+                        for field_name in fields.names() {
+                            let attributes = field_name
+                                .attributes()
+                                .get_attribute::<FieldAttributes>()?
+                                .unwrap_or_default();
+                            let marker_name = field_name.to_string();
+                            let field_name = field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX);
+                            let encode = if attributes.remaining_bytes.is_some() {
+                                format!(
+                                    "{0}::enc::write::Writer::write({0}::enc::Encoder::writer(encoder), core::convert::AsRef::<[u8]>::as_ref({1}))",
+                                    crate_name, field_name,
+                                )
+                            } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                                format!(
+                                    "{0}::enc::encode_fixed_len(encoder, core::convert::AsRef::<[u8]>::as_ref({1}), {2})",
+                                    crate_name, field_name, args,
+                                )
+                            } else if attributes.with_serde {
+                                format!(
+                                    "{0}::Encode::encode(&{0}::serde::Compat({1}), encoder)",
+                                    crate_name, field_name,
+                                )
+                            } else if let Some((endian, _)) = attributes.endian {
+                                format!(
+                                    "{0}::enc::encode_with_endian(encoder, {1}, {0}::config::Endianness::{2})",
+                                    crate_name, field_name, endian,
+                                )
+                            } else {
+                                format!("{0}::Encode::encode({1}, encoder)", crate_name, field_name)
+                            };
+                            let encode = match &attributes.pad_to {
+                                Some(padding) => padding.encode(crate_name, &encode),
+                                None => encode,
+                            };
+                            let encode = stats_markers(
+                                crate_name,
+                                "enc::Encoder",
+                                "encoder",
+                                &format!("{}::{}", enum_name, variant.name),
+                                &marker_name,
+                                encode,
+                            );
+                            body.push_parsed(format!("{}?;", encode))?;
+                        }
+                    }
+                    body.push_parsed("core::result::Result::Ok(())")?;
                     Ok(())
                 })?;
+                match_body.punct(',');
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    pub fn generate_layout(self, generator: &mut Generator) -> Result<()> {
+        let crate_name = self.attributes.crate_name.as_str();
+        let enum_name = generator.target_name().to_string();
+        let tag = match self.tag_type()? {
+            Some(tag_type) => format!(
+                "core::option::Option::Some({}::config::TagType::{})",
+                crate_name,
+                tag_type.variant_name()
+            ),
+            None => String::from("core::option::Option::None"),
+        };
+        generator
+            .impl_for(format!("{}::layout::EncodedLayout", crate_name))
+            .modify_generic_constraints(|generics, where_constraints| {
+                for g in generics.iter_generics() {
+                    where_constraints
+                        .push_constraint(g, format!("{}::layout::EncodedLayout", crate_name))
+                        .unwrap();
+                }
+                Ok(())
+            })?
+            .generate_fn("layout")
+            .with_return_type(format!("{}::layout::Layout", crate_name))
+            .body(|fn_body| {
+                // Layout::Enum {
+                //     name: "Enum",
+                //     variants: From::from([
+                //         Variant::new("A", 0, From::from([Field::new("a", <u32 as EncodedLayout>::layout()), ...])),
+                //         ...
+                //     ]),
+                // }
+                let mut variants = String::new();
+                for (variant_tag, variant) in self.variant_tags()?.into_iter().zip(&self.variants) {
+                    variants.push_str(&format!(
+                        "{}::layout::Variant::new({:?}, {}, {}),",
+                        crate_name,
+                        variant.name.to_string().trim_start_matches("r#"),
+                        variant_tag,
+                        fields_layout(crate_name, variant.fields.as_ref(), false)?
+                    ));
+                }
+                let layout = if self.attributes.string_tags.is_some() {
+                    format!(
+                        "{}::layout::Layout::StringTaggedEnum {{ name: {:?}, variants: core::convert::From::from([{}]) }}",
+                        crate_name, enum_name, variants
+                    )
+                } else {
+                    format!(
+                        "{}::layout::Layout::Enum {{ name: {:?}, tag: {}, variants: core::convert::From::from([{}]) }}",
+                        crate_name, enum_name, tag, variants
+                    )
+                };
+                if self.attributes.length_prefixed.is_some() {
+                    fn_body.push_parsed(format!(
+                        "{}::layout::Layout::length_prefixed({})",
+                        crate_name, layout
+                    ))?;
+                } else {
+                    fn_body.push_parsed(layout)?;
+                }
                 Ok(())
             })?;
         Ok(())
@@ -142,24 +569,46 @@ impl DeriveEnum {
         builder.push_parsed("_ => core::unreachable!()").map(|_| ())
     }
 
-    /// Build the catch-all case for an int-to-enum decode implementation
-    fn invalid_variant_case(&self, enum_name: &str, result: &mut StreamBuilder) -> Result {
+    /// Build the catch-all case for an int-to-enum decode implementation, or for the `skip` of `SkipDecode` with `skip`
+    fn invalid_variant_case(
+        &self,
+        enum_name: &str,
+        skip: bool,
+        result: &mut StreamBuilder,
+    ) -> Result {
         let crate_name = self.attributes.crate_name.as_str();
+        if let Some(other) = self.other_variant()? {
+            // an unknown tag is decoded into the `other` variant, which has no fields to skip
+            let case = match self.tag_field(other)? {
+                _ if skip => String::from("_ => core::result::Result::Ok(())"),
+                Some((field, _)) => format!(
+                    "variant => core::result::Result::Ok(Self::{} {{ {}: variant }})",
+                    other.name, field
+                ),
+                None if other.fields.is_none() => {
+                    format!("_ => core::result::Result::Ok(Self::{})", other.name)
+                }
+                None => format!("_ => core::result::Result::Ok(Self::{} {{}})", other.name),
+            };
+            return result.push_parsed(case).map(|_| ());
+        }
+        if self.attributes.string_tags.is_some() {
+            // the index of a variant name is always one of the variants
+            return result.push_parsed("_ => core::unreachable!()").map(|_| ());
+        }
 
         // we'll be generating:
         // variant => Err(
         //    bincode::error::DecodeError::UnexpectedVariant {
-        //        found: variant,
+        //        found: core::convert::From::from(variant),
         //        type_name: <enum_name>
         //        allowed: ...,
         //    }
         // )
         //
-        // Where allowed is either:
-        // - bincode::error::AllowedEnumVariants::Range { min: 0, max: <max> }
-        //   if we have no fixed value variants
-        // - bincode::error::AllowedEnumVariants::Allowed(&[<variant1>, <variant2>, ...])
-        //   if we have fixed value variants
+        // Where allowed is the name and the tag of every variant:
+        // - bincode::error::AllowedEnumVariants::Named(&[("<variant1>", 0), ("<variant2>", 1), ...])
+        let wide_tags = self.wide_tags()?;
         result.ident_str("variant");
         result.puncts("=>");
         result.push_parsed("core::result::Result::Err")?;
@@ -171,7 +620,11 @@ impl DeriveEnum {
             err_inner.group(Delimiter::Brace, |variant_inner| {
                 variant_inner.ident_str("found");
                 variant_inner.punct(':');
-                variant_inner.ident_str("variant");
+                if wide_tags {
+                    variant_inner.ident_str("variant");
+                } else {
+                    variant_inner.push_parsed("core::convert::From::from(variant)")?;
+                }
                 variant_inner.punct(',');
 
                 variant_inner.ident_str("type_name");
@@ -182,33 +635,26 @@ impl DeriveEnum {
                 variant_inner.ident_str("allowed");
                 variant_inner.punct(':');
 
-                if self.variants.iter().any(|i| i.value.is_some()) {
-                    // we have fixed values, implement AllowedEnumVariants::Allowed
-                    variant_inner.push_parsed(format!(
-                        "&{}::error::AllowedEnumVariants::Allowed",
-                        crate_name
-                    ))?;
-                    variant_inner.group(Delimiter::Parenthesis, |allowed_inner| {
-                        allowed_inner.punct('&');
-                        allowed_inner.group(Delimiter::Bracket, |allowed_slice| {
-                            for (idx, (ident, _)) in self.iter_fields().enumerate() {
-                                if idx != 0 {
-                                    allowed_slice.punct(',');
-                                }
-                                allowed_slice.extend(ident);
-                            }
-                            Ok(())
-                        })?;
+                variant_inner.push_parsed(format!(
+                    "&{}::error::AllowedEnumVariants::Named",
+                    crate_name
+                ))?;
+                variant_inner.group(Delimiter::Parenthesis, |allowed_inner| {
+                    allowed_inner.punct('&');
+                    allowed_inner.group(Delimiter::Bracket, |allowed_slice| {
+                        for (tag, variant) in self.variant_tags()?.into_iter().zip(&self.variants) {
+                            allowed_slice.group(Delimiter::Parenthesis, |named| {
+                                named.lit_str(variant.name.to_string().trim_start_matches("r#"));
+                                named.punct(',');
+                                named.push(TokenTree::Literal(Literal::u64_suffixed(tag)));
+                                Ok(())
+                            })?;
+                            allowed_slice.punct(',');
+                        }
                         Ok(())
                     })?;
-                } else {
-                    // no fixed values, implement a range
-                    variant_inner.push_parsed(format!(
-                        "&{0}::error::AllowedEnumVariants::Range {{ min: 0, max: {1} }}",
-                        crate_name,
-                        self.variants.len() - 1
-                    ))?;
-                }
+                    Ok(())
+                })?;
                 Ok(())
             })?;
             Ok(())
@@ -218,6 +664,17 @@ impl DeriveEnum {
 
     pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
         let crate_name = self.attributes.crate_name.as_str();
+        let has_context = self.attributes.decode_context.is_some();
+        let mut has_references = false;
+        for variant in &self.variants {
+            check_borrow_attributes(variant.fields.as_ref(), true, has_context)?;
+            check_trait_objects(variant.fields.as_ref())?;
+            if has_context {
+                has_references |= find_reference(variant.fields.as_ref())?.is_some();
+            } else {
+                check_borrowed_references(variant.fields.as_ref())?;
+            }
+        }
 
         let decode_context = if let Some((decode_context, _)) = &self.attributes.decode_context {
             decode_context.as_str()
@@ -241,9 +698,9 @@ impl DeriveEnum {
                     where_constraints.clear();
                     where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::Decode<__Context>", crate_name))?;
-                    }
+                    push_field_bounds(generics, where_constraints, self.all_fields(), &enum_name, |attributes| {
+                        attributes.uses_traits().then(|| format!("{}::Decode<{}>", crate_name, decode_context))
+                    })?;
                 }
                 Ok(())
             })?
@@ -258,21 +715,17 @@ impl DeriveEnum {
                         crate_name
                     ))?;
                 } else {
-                    fn_builder
-                        .push_parsed(format!(
-                            "let variant_index = <u32 as {}::Decode::<__D::Context>>::decode(decoder)?;",
-                            crate_name
-                        ))?;
-                    fn_builder.push_parsed("match variant_index")?;
-                    fn_builder.group(Delimiter::Brace, |variant_case| {
-                        for (mut variant_index, variant) in self.iter_fields() {
-                            // idx => Ok(..)
-                            if variant_index.len() > 1 {
-                                variant_case.push_parsed("x if x == ")?;
-                                variant_case.extend(variant_index);
-                            } else {
-                                variant_case.push(variant_index.remove(0));
+                    let mut decode = StreamBuilder::new();
+                    decode.push_parsed(self.decode_tag(&enum_name, false)?)?;
+                    decode.push_parsed("match variant_index")?;
+                    decode.group(Delimiter::Brace, |variant_case| {
+                        for (variant_index, variant) in self.iter_fields()? {
+                            // the `other` variant is decoded by the catch-all case with the tag
+                            if self.tag_field(variant)?.is_some() {
+                                continue;
                             }
+                            // idx => Ok(..)
+                            variant_case.push(variant_index);
                             variant_case.puncts("=>");
                             variant_case.push_parsed("core::result::Result::Ok")?;
                             variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
@@ -286,27 +739,35 @@ impl DeriveEnum {
                                 variant_case_body.group(Delimiter::Brace, |variant_body| {
                                     if let Some(fields) = variant.fields.as_ref() {
                                         let is_tuple = matches!(fields, Fields::Tuple(_));
+                                        let type_name = format!("{}::{}", enum_name, variant.name);
                                         for (idx, field) in fields.names().into_iter().enumerate() {
-                                            if is_tuple {
+                                            let field_name = if is_tuple {
                                                 variant_body.lit_usize(idx);
+                                                idx.to_string()
                                             } else {
                                                 variant_body.ident(field.unwrap_ident().clone());
-                                            }
+                                                field.unwrap_ident().to_string()
+                                            };
                                             variant_body.punct(':');
                                             let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                            if attributes.with_serde {
-                                                variant_body
-                                                    .push_parsed(format!(
-                                                        "<{0}::serde::Compat<_> as {0}::Decode::<__D::Context>>::decode(decoder)?.0,",
-                                                        crate_name
-                                                    ))?;
+                                            let context = field_error_context(&type_name, &field_name);
+                                            let (decode, suffix) = if attributes.remaining_bytes.is_some() {
+                                                (format!("{}::de::DecodeRemaining::decode_remaining(decoder)", crate_name), "")
+                                            } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                                                (format!("{}::de::DecodeFixedLen::decode_fixed_len(decoder, {})", crate_name, args), "")
+                                            } else if attributes.with_serde {
+                                                (format!("<{0}::serde::Compat<_> as {0}::Decode::<__D::Context>>::decode(decoder)", crate_name), ".0")
+                                            } else if let Some((endian, _)) = attributes.endian {
+                                                (format!("{0}::de::decode_with_endian(decoder, {0}::config::Endianness::{1})", crate_name, endian), "")
                                             } else {
-                                                variant_body
-                                                    .push_parsed(format!(
-                                                        "{}::Decode::<__D::Context>::decode(decoder)?,",
-                                                        crate_name
-                                                    ))?;
-                                            }
+                                                (format!("{}::Decode::<__D::Context>::decode(decoder)", crate_name), "")
+                                            };
+                                            let decode = match &attributes.pad_to {
+                                                Some(padding) => padding.decode(crate_name, &decode, false),
+                                                None => decode,
+                                            };
+                                            let decode = stats_markers(crate_name, "de::Decoder", "decoder", &type_name, &field_name, format!("{}{}", decode, context));
+                                            variant_body.push_parsed(format!("{}?{},", decode, suffix))?;
                                         }
                                     }
                                     Ok(())
@@ -317,17 +778,25 @@ impl DeriveEnum {
                         }
 
                         // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
+                        self.invalid_variant_case(&enum_name, false, variant_case)
                     })?;
+                    push_struct_decode(fn_builder, crate_name, &self.attributes, false, decode)?;
                 }
                 Ok(())
             })?;
-        self.generate_borrow_decode(generator)?;
+        if has_references {
+            generate_borrow_decode_with_decode(generator, crate_name, decode_context)?;
+        } else {
+            self.generate_borrow_decode(generator)?;
+        }
         Ok(())
     }
 
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
         let crate_name = &self.attributes.crate_name;
+        for variant in &self.variants {
+            check_trait_objects(variant.fields.as_ref())?;
+        }
 
         let decode_context = if let Some((decode_context, _)) = &self.attributes.decode_context {
             decode_context.as_str()
@@ -351,11 +820,13 @@ impl DeriveEnum {
                     where_constraints.clear();
                     where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::de::BorrowDecode<'__de, {}>", crate_name, decode_context)).unwrap();
-                    }
+                    push_field_bounds(generics, where_constraints, self.all_fields(), &enum_name, |attributes| {
+                        attributes.uses_traits().then(|| borrow_decode_trait(crate_name, decode_context, attributes))
+                    })?;
                     for lt in generics.iter_lifetimes() {
-                        where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        if !is_owned_lifetime(self.all_fields(), &lt.ident.to_string())? {
+                            where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        }
                     }
                 }
                 Ok(())
@@ -371,18 +842,17 @@ impl DeriveEnum {
                         crate_name
                     ))?;
                 } else {
-                    fn_builder
-                        .push_parsed(format!("let variant_index = <u32 as {}::Decode::<__D::Context>>::decode(decoder)?;", crate_name))?;
-                    fn_builder.push_parsed("match variant_index")?;
-                    fn_builder.group(Delimiter::Brace, |variant_case| {
-                        for (mut variant_index, variant) in self.iter_fields() {
-                            // idx => Ok(..)
-                            if variant_index.len() > 1 {
-                                variant_case.push_parsed("x if x == ")?;
-                                variant_case.extend(variant_index);
-                            } else {
-                                variant_case.push(variant_index.remove(0));
+                    let mut decode = StreamBuilder::new();
+                    decode.push_parsed(self.decode_tag(&enum_name, true)?)?;
+                    decode.push_parsed("match variant_index")?;
+                    decode.group(Delimiter::Brace, |variant_case| {
+                        for (variant_index, variant) in self.iter_fields()? {
+                            // the `other` variant is decoded by the catch-all case with the tag
+                            if self.tag_field(variant)?.is_some() {
+                                continue;
                             }
+                            // idx => Ok(..)
+                            variant_case.push(variant_index);
                             variant_case.puncts("=>");
                             variant_case.push_parsed("core::result::Result::Ok")?;
                             variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
@@ -396,20 +866,37 @@ impl DeriveEnum {
                                 variant_case_body.group(Delimiter::Brace, |variant_body| {
                                     if let Some(fields) = variant.fields.as_ref() {
                                         let is_tuple = matches!(fields, Fields::Tuple(_));
-                                        for (idx, field) in fields.names().into_iter().enumerate() {
-                                            if is_tuple {
+                                        let type_name = format!("{}::{}", enum_name, variant.name);
+                                        for (idx, (field, ty)) in fields.names().into_iter().zip(unnamed_fields(Some(fields))).enumerate() {
+                                            let field_name = if is_tuple {
                                                 variant_body.lit_usize(idx);
+                                                idx.to_string()
                                             } else {
                                                 variant_body.ident(field.unwrap_ident().clone());
-                                            }
+                                                field.unwrap_ident().to_string()
+                                            };
                                             variant_body.punct(':');
-                                            let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                            if attributes.with_serde {
-                                                variant_body
-                                                    .push_parsed(format!("<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode::<__D::Context>>::borrow_decode(decoder)?.0,", crate_name))?;
+                                            let attributes = field_attributes(ty)?;
+                                            let context = field_error_context(&type_name, &field_name);
+                                            let (decode, suffix) = if attributes.remaining_bytes.is_some() {
+                                                (format!("{}::de::BorrowDecodeRemaining::borrow_decode_remaining(decoder)", crate_name), "")
+                                            } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                                                (format!("{}::de::BorrowDecodeFixedLen::borrow_decode_fixed_len(decoder, {})", crate_name, args), "")
+                                            } else if attributes.is_owned() {
+                                                (format!("{}::Decode::<__D::Context>::decode(decoder)", crate_name), "")
+                                            } else if attributes.with_serde {
+                                                (format!("<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode::<__D::Context>>::borrow_decode(decoder)", crate_name), ".0")
+                                            } else if let Some((endian, _)) = attributes.endian {
+                                                (format!("{0}::de::borrow_decode_with_endian(decoder, {0}::config::Endianness::{1})", crate_name, endian), "")
                                             } else {
-                                                variant_body.push_parsed(format!("{}::BorrowDecode::<__D::Context>::borrow_decode(decoder)?,", crate_name))?;
-                                            }
+                                                (format!("{}::BorrowDecode::<__D::Context>::borrow_decode(decoder)", crate_name), "")
+                                            };
+                                            let decode = match &attributes.pad_to {
+                                                Some(padding) => padding.decode(crate_name, &decode, true),
+                                                None => decode,
+                                            };
+                                            let decode = stats_markers(crate_name, "de::Decoder", "decoder", &type_name, &field_name, format!("{}{}", decode, context));
+                                            variant_body.push_parsed(format!("{}?{},", decode, suffix))?;
                                         }
                                     }
                                     Ok(())
@@ -420,30 +907,86 @@ impl DeriveEnum {
                         }
 
                         // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
+                        self.invalid_variant_case(&enum_name, false, variant_case)
                     })?;
+                    push_struct_decode(fn_builder, crate_name, &self.attributes, true, decode)?;
                 }
                 Ok(())
             })?;
         Ok(())
     }
-}
-
-struct EnumVariantIterator<'a> {
-    variants: &'a [EnumVariant],
-    idx: usize,
-}
-
-impl<'a> Iterator for EnumVariantIterator<'a> {
-    type Item = (Vec<TokenTree>, &'a EnumVariant);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let idx = self.idx;
-        let variant = self.variants.get(self.idx)?;
-        self.idx += 1;
+    pub fn generate_skip_decode(self, generator: &mut Generator) -> Result<()> {
+        let crate_name = self.attributes.crate_name.as_str();
+        for variant in &self.variants {
+            check_trait_objects(variant.fields.as_ref())?;
+        }
+        let enum_name = generator.target_name().to_string();
 
-        let tokens = vec![TokenTree::Literal(Literal::u32_suffixed(idx as u32))];
+        generator
+            .impl_for(format!("{}::de::SkipDecode", crate_name))
+            .modify_generic_constraints(|generics, where_constraints| {
+                if let Some((bounds, lit)) = self.attributes.bounds.as_ref() {
+                    where_constraints.clear();
+                    where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
+                } else {
+                    push_field_bounds(generics, where_constraints, self.all_fields(), &enum_name, |attributes| {
+                        attributes.uses_traits().then(|| format!("{}::de::SkipDecode", crate_name))
+                    })?;
+                }
+                Ok(())
+            })?
+            .generate_fn("skip")
+            .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+            .with_arg("decoder", "&mut __D")
+            .with_return_type(format!("core::result::Result<(), {}::error::DecodeError>", crate_name))
+            .body(|fn_builder| {
+                if self.variants.is_empty() {
+                    fn_builder.push_parsed(format!(
+                        "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
+                        crate_name
+                    ))?;
+                } else if self.attributes.length_prefixed.is_some() {
+                    // the length tells how many bytes to skip
+                    fn_builder.push_parsed(format!(
+                        "{}::de::Decoder::with_length_prefix(decoder, |_| core::result::Result::Ok(()))",
+                        crate_name
+                    ))?;
+                } else {
+                    fn_builder.push_parsed(self.decode_tag(&enum_name, false)?)?;
+                    fn_builder.push_parsed("match variant_index")?;
+                    fn_builder.group(Delimiter::Brace, |variant_case| {
+                        for (variant_index, variant) in self.iter_fields()? {
+                            if self.tag_field(variant)?.is_some() {
+                                continue;
+                            }
+                            // idx => { <T as SkipDecode>::skip(decoder)?; ... Ok(()) }
+                            variant_case.push(variant_index);
+                            variant_case.puncts("=>");
+                            variant_case.group(Delimiter::Brace, |variant_body| {
+                                if let Some(fields) = variant.fields.as_ref() {
+                                    let is_tuple = matches!(fields, Fields::Tuple(_));
+                                    let type_name = format!("{}::{}", enum_name, variant.name);
+                                    for (idx, (name, field)) in fields.names().into_iter().zip(unnamed_fields(Some(fields))).enumerate() {
+                                        let field_name = if is_tuple { idx.to_string() } else { name.to_string() };
+                                        let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+                                        let context = field_error_context(&type_name, &field_name);
+                                        let (skip, _) = skip_field(crate_name, &crate::type_name(field), &attributes);
+                                        variant_body.push_parsed(format!("{}{}?;", skip, context))?;
+                                    }
+                                }
+                                variant_body.push_parsed("core::result::Result::Ok(())")?;
+                                Ok(())
+                            })?;
+                            variant_case.punct(',');
+                        }
 
-        Some((tokens, variant))
+                        // invalid idx
+                        self.invalid_variant_case(&enum_name, true, variant_case)
+                    })?;
+                }
+                Ok(())
+            })?;
+        Ok(())
     }
 }
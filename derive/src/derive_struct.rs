@@ -1,4 +1,13 @@
 use crate::attribute::{ContainerAttributes, FieldAttributes};
+use crate::{
+    borrow_decode_trait, check_borrow_attributes, check_borrowed_references,
+    check_endian_attributes, check_remaining_bytes, check_trait_objects, field_attributes,
+    field_error_context, fields_layout, find_reference, generate_borrow_decode_with_decode,
+    is_owned_lifetime, mentions, option_bitmap_bits, option_value_type, push_field_bounds,
+    push_struct_decode, skip_field, stats_markers, tokens_string, type_name, unnamed_fields,
+};
+use virtue::generate::Parent;
+use virtue::parse::{Generic, Visibility};
 use virtue::prelude::*;
 
 pub(crate) struct DeriveStruct {
@@ -7,8 +16,232 @@ pub(crate) struct DeriveStruct {
 }
 
 impl DeriveStruct {
+    /// Rejects the container attributes that only apply to enums.
+    fn check_attributes(&self) -> Result<()> {
+        if let Some((_, lit)) = &self.attributes.tag_type {
+            return Err(Error::custom_at(
+                "tag_type can only be used on enums",
+                lit.span(),
+            ));
+        }
+        if let Some(string_tags) = &self.attributes.string_tags {
+            return Err(Error::custom_at(
+                "string_tags can only be used on enums",
+                string_tags.span(),
+            ));
+        }
+        if let (Some(option_bitmap), Some(_)) =
+            (&self.attributes.option_bitmap, &self.attributes.prefix)
+        {
+            return Err(Error::custom_at(
+                "option_bitmap can not be combined with prefix, as the fields of the prefix are not at the start of the struct",
+                option_bitmap.span(),
+            ));
+        }
+        if let Some(length_prefixed) = &self.attributes.length_prefixed {
+            if self.attributes.pad_struct_to.is_some() || self.attributes.prefix.is_some() {
+                return Err(Error::custom_at(
+                    "length_prefixed can not be combined with pad_struct_to or prefix",
+                    length_prefixed.span(),
+                ));
+            }
+        }
+        check_remaining_bytes(
+            self.fields.as_ref(),
+            self.attributes.length_prefixed.is_some(),
+        )?;
+        check_endian_attributes(self.fields.as_ref())?;
+        check_borrow_attributes(
+            self.fields.as_ref(),
+            false,
+            self.attributes.decode_context.is_some(),
+        )
+    }
+
+    /// Returns the bit of every field in the option bitmap, or `None` for every field if the struct does not have `#[bincode(option_bitmap)]`.
+    fn option_bitmap_bits(&self) -> Result<Vec<Option<usize>>> {
+        if self.attributes.option_bitmap.is_some() {
+            option_bitmap_bits(self.fields.as_ref())
+        } else {
+            Ok(vec![None; unnamed_fields(self.fields.as_ref()).len()])
+        }
+    }
+
+    /// Generates the statement that decodes the option bitmap into `__options`, if the struct has one.
+    fn decode_option_bitmap(&self) -> Result<Option<String>> {
+        if self.attributes.option_bitmap.is_none() {
+            return Ok(None);
+        }
+        let options = self.option_bitmap_bits()?.into_iter().flatten().count();
+        Ok(Some(format!(
+            "let __options = {}::de::decode_option_bitmap::<_, {}>(decoder)?;",
+            self.attributes.crate_name, options
+        )))
+    }
+
+    /// Generates the struct of `#[bincode(prefix = "...")]`, which has the first fields of this struct and derives `derive` for them the same way.
+    ///
+    /// As the fields are decoded the same way, the struct can be decoded from the start of an encoded value of this struct.
+    pub fn generate_prefix(
+        &self,
+        generator: &mut Generator,
+        visibility: Visibility,
+        derive: &str,
+    ) -> Result<()> {
+        let Some((prefix, lit)) = &self.attributes.prefix else {
+            return Ok(());
+        };
+        let Some(Fields::Struct(fields)) = &self.fields else {
+            return Err(Error::custom_at(
+                "prefix can only be used on structs with named fields",
+                lit.span(),
+            ));
+        };
+        let is_prefix = prefix.fields.len() <= fields.len()
+            && prefix.fields.iter().zip(fields).all(|(name, (ident, _))| {
+                name.trim_start_matches("r#") == ident.to_string().trim_start_matches("r#")
+            });
+        if !is_prefix {
+            let expected: Vec<String> = fields
+                .iter()
+                .take(prefix.fields.len())
+                .map(|(ident, _)| ident.to_string())
+                .collect();
+            return Err(Error::custom_at(
+                format!(
+                    "The fields of a prefix must be the first fields of the struct, in order: \"{}: {}\"",
+                    prefix.name,
+                    expected.join(", ")
+                ),
+                lit.span(),
+            ));
+        }
+        let fields = &fields[..prefix.fields.len()];
+        let crate_name = &self.attributes.crate_name;
+        let target_name = generator.target_name().to_string();
+
+        // Only the generics that the fields use, as a struct can not have unused generics
+        let generics: Vec<Generic> = generator
+            .generics()
+            .map(|generics| {
+                generics
+                    .iter()
+                    .filter(|generic| {
+                        let name = match generic {
+                            Generic::Lifetime(lt) => format!("'{}", lt.ident),
+                            _ => generic.ident().to_string(),
+                        };
+                        fields
+                            .iter()
+                            .any(|(_, field)| mentions(&type_name(field), &name))
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut header = generator.generate_struct(&prefix.name);
+        if visibility == Visibility::Pub {
+            header.make_pub();
+        }
+        if !generics.is_empty() {
+            header.with_generics(generics.clone());
+        }
+        header
+            .with_parsed_attribute(format!("derive({}::{})", crate_name, derive))?
+            .with_parsed_attribute(format!(
+                "doc = \"The first fields of [`{}`], see `#[bincode(prefix)]`.\"",
+                target_name
+            ))?
+            .with_parsed_attribute(match &self.attributes.decode_context {
+                // a single attribute, as only the first `bincode` attribute of a container is read
+                Some((decode_context, _)) => format!(
+                    "bincode(crate = {:?}, decode_context = {:?})",
+                    crate_name, decode_context
+                ),
+                None => format!("bincode(crate = {:?})", crate_name),
+            })?;
+        for (ident, field) in fields {
+            let mut header_field = header.add_field(ident.to_string(), type_name(field));
+            if field.vis == Visibility::Pub {
+                header_field.make_pub();
+            }
+            for attribute in &field.attributes {
+                let is_copied = matches!(
+                    attribute.tokens.stream().into_iter().next(),
+                    Some(TokenTree::Ident(ident)) if ident.to_string() == "doc" || ident.to_string() == "bincode"
+                );
+                if is_copied {
+                    header_field.with_attribute_stream(attribute.tokens.stream());
+                }
+            }
+        }
+        drop(header);
+
+        let impl_generics: Vec<String> = generics
+            .iter()
+            .map(|generic| match generic {
+                Generic::Lifetime(lt) if lt.constraint.is_empty() => format!("'{}", lt.ident),
+                Generic::Lifetime(lt) => {
+                    format!("'{}: {}", lt.ident, tokens_string(&lt.constraint))
+                }
+                Generic::Generic(g) if g.constraints.is_empty() => g.ident.to_string(),
+                Generic::Generic(g) => format!("{}: {}", g.ident, tokens_string(&g.constraints)),
+                Generic::Const(c) => {
+                    format!("const {}: {}", c.ident, tokens_string(&c.constraints))
+                }
+                _ => generic.ident().to_string(),
+            })
+            .collect();
+        let type_generics: Vec<String> = generics
+            .iter()
+            .map(|generic| match generic {
+                Generic::Lifetime(lt) => format!("'{}", lt.ident),
+                _ => generic.ident().to_string(),
+            })
+            .collect();
+        let mut prefix_impl = StreamBuilder::new();
+        prefix_impl.push_parsed(format!(
+            "impl<{3}> {1}<{4}> {{
+                /// Decodes a `{1}` from the start of `src`, which holds an encoded `{2}`. Returns it with the amount of bytes it was decoded from, which is where the other fields of the `{2}` start.
+                #[allow(dead_code)]
+                pub fn decode_prefix<'__de, __C: {0}::config::Config>(
+                    src: &'__de [u8],
+                    config: __C,
+                ) -> core::result::Result<(Self, usize), {0}::error::DecodeError>
+                where
+                    Self: {0}::BorrowDecode<'__de, ()>,
+                {{
+                    {0}::borrow_decode_from_slice(src, config)
+                }}
+
+                /// Decodes a `{1}` with `context` from the start of `src`, which holds an encoded `{2}`. See `decode_prefix`.
+                #[allow(dead_code)]
+                pub fn decode_prefix_with_context<'__de, __C: {0}::config::Config, __Context>(
+                    src: &'__de [u8],
+                    config: __C,
+                    context: __Context,
+                ) -> core::result::Result<(Self, usize), {0}::error::DecodeError>
+                where
+                    Self: {0}::BorrowDecode<'__de, __Context>,
+                {{
+                    {0}::borrow_decode_from_slice_with_context(src, config, context)
+                }}
+            }}",
+            crate_name,
+            prefix.name,
+            target_name,
+            impl_generics.join(", "),
+            type_generics.join(", ")
+        ))?;
+        generator.append(prefix_impl);
+        Ok(())
+    }
+
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
         let crate_name = &self.attributes.crate_name;
+        let target_name = generator.target_name().to_string();
         generator
             .impl_for(format!("{}::Encode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
@@ -20,11 +253,17 @@ impl DeriveStruct {
                         .push_parsed_constraint(bounds)
                         .map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints
-                            .push_constraint(g, format!("{}::Encode", crate_name))
-                            .unwrap();
-                    }
+                    push_field_bounds(
+                        generics,
+                        where_constraints,
+                        unnamed_fields(self.fields.as_ref()),
+                        &target_name,
+                        |attributes| {
+                            attributes
+                                .uses_traits()
+                                .then(|| format!("{}::Encode", crate_name))
+                        },
+                    )?;
                 }
                 Ok(())
             })?
@@ -37,32 +276,111 @@ impl DeriveStruct {
                 crate_name
             ))
             .body(|fn_body| {
+                let mut body = String::new();
+                let bits = self.option_bitmap_bits()?;
+                if let (Some(_), Some(fields)) = (&self.attributes.option_bitmap, &self.fields) {
+                    let present: Vec<String> = fields
+                        .names()
+                        .into_iter()
+                        .zip(&bits)
+                        .filter(|(_, bit)| bit.is_some())
+                        .map(|(field, _)| format!("self.{}.is_some()", field))
+                        .collect();
+                    body.push_str(&format!(
+                        "{}::enc::encode_option_bitmap(encoder, &[{}])?;",
+                        crate_name,
+                        present.join(", ")
+                    ));
+                }
                 if let Some(fields) = self.fields.as_ref() {
-                    for field in fields.names() {
+                    for (field, bit) in fields.names().into_iter().zip(&bits) {
                         let attributes = field
                             .attributes()
                             .get_attribute::<FieldAttributes>()?
                             .unwrap_or_default();
-                        if attributes.with_serde {
-                            fn_body.push_parsed(format!(
-                                "{0}::Encode::encode(&{0}::serde::Compat(&self.{1}), encoder)?;",
+                        if bit.is_some() {
+                            // only the value of an option in the bitmap is written
+                            let encode = stats_markers(
+                                crate_name,
+                                "enc::Encoder",
+                                "encoder",
+                                &target_name,
+                                &field.to_string(),
+                                format!("{}::Encode::encode(value, encoder)", crate_name),
+                            );
+                            body.push_str(&format!(
+                                "if let core::option::Option::Some(value) = &self.{} {{ {}?; }}",
+                                field, encode
+                            ));
+                            continue;
+                        }
+                        let encode = if attributes.remaining_bytes.is_some() {
+                            format!(
+                                "{0}::enc::write::Writer::write({0}::enc::Encoder::writer(encoder), core::convert::AsRef::<[u8]>::as_ref(&self.{1}))",
                                 crate_name, field
-                            ))?;
-                        } else {
-                            fn_body.push_parsed(format!(
-                                "{}::Encode::encode(&self.{}, encoder)?;",
+                            )
+                        } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                            format!(
+                                "{0}::enc::encode_fixed_len(encoder, core::convert::AsRef::<[u8]>::as_ref(&self.{1}), {2})",
+                                crate_name, field, args
+                            )
+                        } else if attributes.with_serde {
+                            format!(
+                                "{0}::Encode::encode(&{0}::serde::Compat(&self.{1}), encoder)",
                                 crate_name, field
-                            ))?;
-                        }
+                            )
+                        } else if let Some((endian, _)) = attributes.endian {
+                            format!(
+                                "{0}::enc::encode_with_endian(encoder, &self.{1}, {0}::config::Endianness::{2})",
+                                crate_name, field, endian
+                            )
+                        } else {
+                            format!("{}::Encode::encode(&self.{}, encoder)", crate_name, field)
+                        };
+                        let encode = match &attributes.pad_to {
+                            Some(padding) => padding.encode(crate_name, &encode),
+                            None => encode,
+                        };
+                        let encode = stats_markers(
+                            crate_name,
+                            "enc::Encoder",
+                            "encoder",
+                            &target_name,
+                            &field.to_string(),
+                            encode,
+                        );
+                        body.push_str(&format!("{}?;", encode));
                     }
                 }
-                fn_body.push_parsed("core::result::Result::Ok(())")?;
+                body.push_str("core::result::Result::Ok(())");
+                match &self.attributes.pad_struct_to {
+                    Some(padding) => {
+                        fn_body.push_parsed(padding.encode(crate_name, &format!("{{ {} }}", body)))?
+                    }
+                    None if self.attributes.length_prefixed.is_some() => {
+                        fn_body.push_parsed(format!(
+                            "{}::enc::Encoder::with_length_prefix(encoder, |encoder| {{ {} }})",
+                            crate_name, body
+                        ))?
+                    }
+                    None => fn_body.push_parsed(body)?,
+                };
                 Ok(())
             })?;
         Ok(())
     }
 
     pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        let has_context = self.attributes.decode_context.is_some();
+        check_borrow_attributes(self.fields.as_ref(), true, has_context)?;
+        check_trait_objects(self.fields.as_ref())?;
+        let has_references = if has_context {
+            find_reference(self.fields.as_ref())?.is_some()
+        } else {
+            check_borrowed_references(self.fields.as_ref())?;
+            false
+        };
         // Remember to keep this mostly in sync with generate_borrow_decode
         let crate_name = &self.attributes.crate_name;
         let decode_context = if let Some((decode_context, _)) = &self.attributes.decode_context {
@@ -70,6 +388,7 @@ impl DeriveStruct {
         } else {
             "__Context"
         };
+        let type_name = generator.target_name().to_string();
 
         let mut impl_for = generator.impl_for(format!("{}::Decode", crate_name));
         if self.attributes.decode_context.is_none() {
@@ -83,9 +402,9 @@ impl DeriveStruct {
                     where_constraints.clear();
                     where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::Decode<{}>", crate_name, decode_context)).unwrap();
-                    }
+                    push_field_bounds(generics, where_constraints, unnamed_fields(self.fields.as_ref()), &type_name, |attributes| {
+                        attributes.uses_traits().then(|| format!("{}::Decode<{}>", crate_name, decode_context))
+                    })?;
                 }
                 Ok(())
             })?
@@ -95,34 +414,48 @@ impl DeriveStruct {
             .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
             .body(|fn_body| {
                 // Ok(Self {
-                fn_body.push_parsed("core::result::Result::Ok")?;
-                fn_body.group(Delimiter::Parenthesis, |ok_group| {
+                let mut value = StreamBuilder::new();
+                if let Some(decode_option_bitmap) = self.decode_option_bitmap()? {
+                    value.push_parsed(decode_option_bitmap)?;
+                }
+                value.push_parsed("core::result::Result::Ok")?;
+                value.group(Delimiter::Parenthesis, |ok_group| {
                     ok_group.ident_str("Self");
                     ok_group.group(Delimiter::Brace, |struct_body| {
                         // Fields
                         // {
-                        //      a: bincode::Decode::decode(decoder)?,
-                        //      b: bincode::Decode::decode(decoder)?,
+                        //      a: bincode::Decode::decode(decoder).map_err(|e| e.with_field("Self", "a"))?,
+                        //      b: bincode::Decode::decode(decoder).map_err(|e| e.with_field("Self", "b"))?,
                         //      ...
                         // }
+                        let bits = self.option_bitmap_bits()?;
                         if let Some(fields) = self.fields.as_ref() {
-                            for field in fields.names() {
+                            for (field, bit) in fields.names().into_iter().zip(bits) {
                                 let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                if attributes.with_serde {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: (<{0}::serde::Compat<_> as {0}::Decode::<{2}>>::decode(decoder)?).0,",
-                                            crate_name,
-                                            field,
-                                            decode_context,
-                                        ))?;
+                                let context = field_error_context(&type_name, &field.to_string());
+                                let (decode, suffix) = if attributes.remaining_bytes.is_some() {
+                                    (format!("{}::de::DecodeRemaining::decode_remaining(decoder)", crate_name), "")
+                                } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                                    (format!("{}::de::DecodeFixedLen::decode_fixed_len(decoder, {})", crate_name, args), "")
+                                } else if attributes.with_serde {
+                                    (format!("<{0}::serde::Compat<_> as {0}::Decode::<{1}>>::decode(decoder)", crate_name, decode_context), ".0")
+                                } else if let Some((endian, _)) = attributes.endian {
+                                    (format!("{0}::de::decode_with_endian(decoder, {0}::config::Endianness::{1})", crate_name, endian), "")
                                 } else {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: {0}::Decode::decode(decoder)?,",
-                                            crate_name,
-                                            field
-                                        ))?;
+                                    (format!("{}::Decode::decode(decoder)", crate_name), "")
+                                };
+                                let decode = match &attributes.pad_to {
+                                    Some(padding) => padding.decode(crate_name, &decode, false),
+                                    None => decode,
+                                };
+                                let decode = stats_markers(crate_name, "de::Decoder", "decoder", &type_name, &field.to_string(), format!("{}{}", decode, context));
+                                if let Some(bit) = bit {
+                                    struct_body.push_parsed(format!(
+                                        "{}: if __options[{}] {{ core::option::Option::Some({}?) }} else {{ core::option::Option::None }},",
+                                        field, bit, decode
+                                    ))?;
+                                } else {
+                                    struct_body.push_parsed(format!("{}: {}?{},", field, decode, suffix))?;
                                 }
                             }
                         }
@@ -130,21 +463,28 @@ impl DeriveStruct {
                     })?;
                     Ok(())
                 })?;
-                Ok(())
+                push_struct_decode(fn_body, crate_name, &self.attributes, false, value)
             })?;
-        self.generate_borrow_decode(generator)?;
+        if has_references {
+            generate_borrow_decode_with_decode(generator, crate_name, decode_context)?;
+        } else {
+            self.generate_borrow_decode(generator)?;
+        }
         Ok(())
     }
 
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        check_trait_objects(self.fields.as_ref())?;
         // Remember to keep this mostly in sync with generate_decode
-        let crate_name = self.attributes.crate_name;
+        let crate_name = self.attributes.crate_name.as_str();
 
         let decode_context = if let Some((decode_context, _)) = &self.attributes.decode_context {
             decode_context.as_str()
         } else {
             "__Context"
         };
+        let type_name = generator.target_name().to_string();
 
         let mut impl_for = generator
             .impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
@@ -159,11 +499,13 @@ impl DeriveStruct {
                     where_constraints.clear();
                     where_constraints.push_parsed_constraint(bounds).map_err(|e| e.with_span(lit.span()))?;
                 } else {
-                    for g in generics.iter_generics() {
-                        where_constraints.push_constraint(g, format!("{}::de::BorrowDecode<'__de, {}>", crate_name, decode_context)).unwrap();
-                    }
+                    push_field_bounds(generics, where_constraints, unnamed_fields(self.fields.as_ref()), &type_name, |attributes| {
+                        attributes.uses_traits().then(|| borrow_decode_trait(crate_name, decode_context, attributes))
+                    })?;
                     for lt in generics.iter_lifetimes() {
-                        where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        if !is_owned_lifetime(unnamed_fields(self.fields.as_ref()), &lt.ident.to_string())? {
+                            where_constraints.push_parsed_constraint(format!("'__de: '{}", lt.ident))?;
+                        }
                     }
                 }
                 Ok(())
@@ -174,29 +516,44 @@ impl DeriveStruct {
             .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
             .body(|fn_body| {
                 // Ok(Self {
-                fn_body.push_parsed("core::result::Result::Ok")?;
-                fn_body.group(Delimiter::Parenthesis, |ok_group| {
+                let mut value = StreamBuilder::new();
+                if let Some(decode_option_bitmap) = self.decode_option_bitmap()? {
+                    value.push_parsed(decode_option_bitmap)?;
+                }
+                value.push_parsed("core::result::Result::Ok")?;
+                value.group(Delimiter::Parenthesis, |ok_group| {
                     ok_group.ident_str("Self");
                     ok_group.group(Delimiter::Brace, |struct_body| {
+                        let bits = self.option_bitmap_bits()?;
                         if let Some(fields) = self.fields.as_ref() {
-                            for field in fields.names() {
-                                let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                if attributes.with_serde {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: (<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode::<'_, {2}>>::borrow_decode(decoder)?).0,",
-                                            crate_name,
-                                            field,
-                                            decode_context,
-                                        ))?;
+                            for ((field, bit), ty) in fields.names().into_iter().zip(bits).zip(unnamed_fields(Some(fields))) {
+                                let attributes = field_attributes(ty)?;
+                                let context = field_error_context(&type_name, &field.to_string());
+                                let (decode, suffix) = if attributes.remaining_bytes.is_some() {
+                                    (format!("{}::de::BorrowDecodeRemaining::borrow_decode_remaining(decoder)", crate_name), "")
+                                } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+                                    (format!("{}::de::BorrowDecodeFixedLen::borrow_decode_fixed_len(decoder, {})", crate_name, args), "")
+                                } else if attributes.is_owned() {
+                                    (format!("{}::Decode::<{}>::decode(decoder)", crate_name, decode_context), "")
+                                } else if attributes.with_serde {
+                                    (format!("<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode::<'_, {1}>>::borrow_decode(decoder)", crate_name, decode_context), ".0")
+                                } else if let Some((endian, _)) = attributes.endian {
+                                    (format!("{0}::de::borrow_decode_with_endian(decoder, {0}::config::Endianness::{1})", crate_name, endian), "")
+                                } else {
+                                    (format!("{}::BorrowDecode::<'_, {}>::borrow_decode(decoder)", crate_name, decode_context), "")
+                                };
+                                let decode = match &attributes.pad_to {
+                                    Some(padding) => padding.decode(crate_name, &decode, true),
+                                    None => decode,
+                                };
+                                let decode = stats_markers(crate_name, "de::Decoder", "decoder", &type_name, &field.to_string(), format!("{}{}", decode, context));
+                                if let Some(bit) = bit {
+                                    struct_body.push_parsed(format!(
+                                        "{}: if __options[{}] {{ core::option::Option::Some({}?) }} else {{ core::option::Option::None }},",
+                                        field, bit, decode
+                                    ))?;
                                 } else {
-                                    struct_body
-                                        .push_parsed(format!(
-                                            "{1}: {0}::BorrowDecode::<'_, {2}>::borrow_decode(decoder)?,",
-                                            crate_name,
-                                            field,
-                                            decode_context,
-                                        ))?;
+                                    struct_body.push_parsed(format!("{}: {}?{},", field, decode, suffix))?;
                                 }
                             }
                         }
@@ -204,8 +561,506 @@ impl DeriveStruct {
                     })?;
                     Ok(())
                 })?;
+                push_struct_decode(fn_body, crate_name, &self.attributes, true, value)
+            })?;
+        Ok(())
+    }
+
+    /// Generates `ENCODED_SIZE`, `encode_const` and `encoded` for `#[derive(ConstEncode)]`, which write the fields with a `ConstWriter`.
+    pub fn generate_skip_decode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        check_trait_objects(self.fields.as_ref())?;
+        let crate_name = self.attributes.crate_name.as_str();
+        let target_name = generator.target_name().to_string();
+        let fields = unnamed_fields(self.fields.as_ref());
+        let bits = self.option_bitmap_bits()?;
+
+        let mut skip = Vec::with_capacity(fields.len());
+        let mut sizes = Vec::with_capacity(fields.len());
+        for ((name, field), bit) in self
+            .fields
+            .as_ref()
+            .map(Fields::names)
+            .unwrap_or_default()
+            .into_iter()
+            .zip(&fields)
+            .zip(bits)
+        {
+            let attributes = field
+                .attributes
+                .get_attribute::<FieldAttributes>()?
+                .unwrap_or_default();
+            let context = field_error_context(&target_name, &name.to_string());
+            match bit.and_then(|bit| option_value_type(&field.r#type).map(|ty| (bit, ty))) {
+                // only the value of an option in the bitmap is encoded, if it is `Some`
+                Some((bit, ty)) => skip.push(format!(
+                    "if __options[{}] {{ <{} as {}::de::SkipDecode>::skip(decoder){}?; }}",
+                    bit,
+                    tokens_string(ty),
+                    crate_name,
+                    context
+                )),
+                None => {
+                    let (skip_field, size) = skip_field(crate_name, &type_name(field), &attributes);
+                    skip.push(format!("{}{}?;", skip_field, context));
+                    sizes.push(size);
+                }
+            }
+        }
+
+        let fixed_size = if let Some(padding) = &self.attributes.pad_struct_to {
+            format!("core::option::Option::Some({}usize)", padding.size)
+        } else if self.attributes.option_bitmap.is_some()
+            || self.attributes.length_prefixed.is_some()
+        {
+            "core::option::Option::None".to_string()
+        } else {
+            let mut sum = String::from("let __size: usize = 0;");
+            for size in sizes {
+                sum.push_str(&format!(" let __size = __size.checked_add({}?)?;", size));
+            }
+            sum.push_str(" core::option::Option::Some(__size)");
+            sum
+        };
+
+        let mut impl_for = generator.impl_for(format!("{}::de::SkipDecode", crate_name));
+        impl_for
+            .modify_generic_constraints(|generics, where_constraints| {
+                if let Some((bounds, lit)) = self.attributes.bounds.as_ref() {
+                    where_constraints.clear();
+                    where_constraints
+                        .push_parsed_constraint(bounds)
+                        .map_err(|e| e.with_span(lit.span()))?;
+                } else {
+                    push_field_bounds(
+                        generics,
+                        where_constraints,
+                        unnamed_fields(self.fields.as_ref()),
+                        &target_name,
+                        |attributes| {
+                            attributes
+                                .uses_traits()
+                                .then(|| format!("{}::de::SkipDecode", crate_name))
+                        },
+                    )?;
+                }
+                Ok(())
+            })?
+            .generate_fn("skip")
+            .with_generic_deps("__D", [format!("{}::de::Decoder", crate_name)])
+            .with_arg("decoder", "&mut __D")
+            .with_return_type(format!(
+                "core::result::Result<(), {}::error::DecodeError>",
+                crate_name
+            ))
+            .body(|fn_body| {
+                if let Some(padding) = &self.attributes.pad_struct_to {
+                    fn_body.push_parsed(format!(
+                        "{}::de::Decoder::skip_bytes(decoder, {})",
+                        crate_name, padding.size
+                    ))?;
+                    return Ok(());
+                }
+                if self.attributes.length_prefixed.is_some() {
+                    // the length tells how many bytes to skip
+                    fn_body.push_parsed(format!(
+                        "{}::de::Decoder::with_length_prefix(decoder, |_| core::result::Result::Ok(()))",
+                        crate_name
+                    ))?;
+                    return Ok(());
+                }
+                if let Some(decode_option_bitmap) = self.decode_option_bitmap()? {
+                    fn_body.push_parsed(decode_option_bitmap)?;
+                }
+                for skip in &skip {
+                    fn_body.push_parsed(skip)?;
+                }
+                fn_body.push_parsed("core::result::Result::Ok(())")?;
+                Ok(())
+            })?;
+        impl_for
+            .generate_fn("fixed_size")
+            .with_generic_deps("__C", [format!("{}::config::Config", crate_name)])
+            .with_return_type("core::option::Option<usize>")
+            .body(|fn_body| {
+                fn_body.push_parsed(&fixed_size)?;
                 Ok(())
             })?;
         Ok(())
     }
+
+    pub fn generate_const_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        if generator
+            .generics()
+            .is_some_and(|generics| !generics.is_empty())
+        {
+            return Err(Error::custom(
+                "ConstEncode can only be derived for structs without generics",
+            ));
+        }
+        if let Some(padding) = &self.attributes.pad_struct_to {
+            return Err(Error::custom_at(
+                "ConstEncode can not encode a struct with pad_struct_to",
+                padding.lit.span(),
+            ));
+        }
+        if let Some(option_bitmap) = &self.attributes.option_bitmap {
+            return Err(Error::custom_at(
+                "ConstEncode can not encode a struct with option_bitmap",
+                option_bitmap.span(),
+            ));
+        }
+        if let Some(length_prefixed) = &self.attributes.length_prefixed {
+            return Err(Error::custom_at(
+                "ConstEncode can not encode a struct with length_prefixed",
+                length_prefixed.span(),
+            ));
+        }
+        let crate_name = &self.attributes.crate_name;
+        let mut body = String::new();
+        let mut sizes = Vec::new();
+        if let Some(fields) = self.fields.as_ref() {
+            for (name, field) in fields.names().into_iter().zip(unnamed_fields(Some(fields))) {
+                let attributes = field
+                    .attributes
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                if attributes.with_serde
+                    || attributes.remaining_bytes.is_some()
+                    || attributes.pad_to.is_some()
+                    || attributes.len.is_some()
+                {
+                    return Err(Error::custom(format!(
+                        "ConstEncode can not encode `{}`, as it has a with_serde, remaining_bytes, pad_to or len attribute",
+                        name
+                    )));
+                }
+                let (write, size) = const_encode_value(&field.r#type, &format!("self.{}", name), 0);
+                body.push_str(&write);
+                sizes.push(size);
+            }
+        }
+        if sizes.is_empty() {
+            sizes.push("0".to_string());
+        }
+        let mut const_impl = StreamBuilder::new();
+        const_impl.push_parsed(format!(
+            "impl {1} {{
+                /// The amount of bytes that `encoded` returns.
+                pub const ENCODED_SIZE: usize = {2};
+
+                /// Writes the fields of this value to `__writer`, the same way `Encode` does with a fixint config.
+                pub const fn encode_const<const __N: usize>(&self, __writer: &mut {0}::enc::write::ConstWriter<__N>) {{
+                    {3}
+                }}
+
+                /// Encodes this value in a `const fn`, into the same bytes as `encode_to_vec` with `config`. `config` must be a fixint config.
+                pub const fn encoded<__C: {0}::config::Config>(&self, config: __C) -> [u8; Self::ENCODED_SIZE] {{
+                    let mut writer = {0}::enc::write::ConstWriter::new(config);
+                    self.encode_const(&mut writer);
+                    writer.finish()
+                }}
+            }}",
+            crate_name,
+            generator.target_name(),
+            sizes.join(" + "),
+            body
+        ))?;
+        generator.append(const_impl);
+        Ok(())
+    }
+
+    /// Generates `TransparentWire` for a `#[repr(transparent)]` struct with a single field, and `Encode`, `Decode` and `BorrowDecode` that encode and decode it exactly like its field.
+    ///
+    /// The impls are generated here instead of by the other derives, so the struct can not be validated when it is decoded, and its encoding can not be changed by attributes.
+    pub fn generate_transparent_wire(self, generator: &mut Generator) -> Result<()> {
+        if generator
+            .generics()
+            .is_some_and(|generics| !generics.is_empty())
+        {
+            return Err(Error::custom(
+                "TransparentWire can only be derived for structs without generics",
+            ));
+        }
+        let attributes = &self.attributes;
+        if attributes.pad_struct_to.is_some()
+            || attributes.option_bitmap.is_some()
+            || attributes.length_prefixed.is_some()
+            || attributes.prefix.is_some()
+            || attributes.encode_only.is_some()
+            || attributes.decode_only.is_some()
+        {
+            return Err(Error::custom(
+                "TransparentWire is encoded exactly like its field, so it can not have pad_struct_to, option_bitmap, length_prefixed, prefix, encode_only or decode_only",
+            ));
+        }
+        let (name, field, construct) = match &self.fields {
+            Some(Fields::Tuple(fields)) if fields.len() == 1 => {
+                ("0".to_string(), &fields[0], "Self(__value)".to_string())
+            }
+            Some(Fields::Struct(fields)) if fields.len() == 1 => {
+                let (ident, field) = &fields[0];
+                (
+                    ident.to_string(),
+                    field,
+                    format!("Self {{ {}: __value }}", ident),
+                )
+            }
+            _ => {
+                return Err(Error::custom(
+                    "TransparentWire can only be derived for structs with a single field",
+                ))
+            }
+        };
+        if field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .is_some()
+        {
+            return Err(Error::custom(format!(
+                "TransparentWire is encoded exactly like its field, so `{}` can not have bincode attributes",
+                name
+            )));
+        }
+
+        let crate_name = &attributes.crate_name;
+        let mut impls = StreamBuilder::new();
+        impls.push_parsed(format!(
+            "#[allow(unsafe_code)]
+            // Safety: the struct is `#[repr(transparent)]` around its only field, which implements `TransparentWire` itself, and is encoded and decoded exactly like it
+            unsafe impl {0}::TransparentWire for {1} {{
+                type Wire = <{2} as {0}::TransparentWire>::Wire;
+            }}
+
+            impl {0}::Encode for {1} {{
+                fn encode<__E: {0}::enc::Encoder>(&self, encoder: &mut __E) -> core::result::Result<(), {0}::error::EncodeError> {{
+                    {0}::Encode::encode(&self.{3}, encoder)
+                }}
+
+                fn transparent_wire() -> core::option::Option<{0}::transparent::Transparent<Self>> {{
+                    core::option::Option::Some({0}::transparent::Transparent::of())
+                }}
+            }}
+
+            impl<__Context> {0}::Decode<__Context> for {1} {{
+                const MIN_ENCODED_SIZE: usize = <{2} as {0}::Decode<__Context>>::MIN_ENCODED_SIZE;
+
+                fn decode<__D: {0}::de::Decoder<Context = __Context>>(decoder: &mut __D) -> core::result::Result<Self, {0}::error::DecodeError> {{
+                    let __value = <{2} as {0}::Decode<__Context>>::decode(decoder)?;
+                    core::result::Result::Ok({4})
+                }}
+
+                fn transparent_wire() -> core::option::Option<{0}::transparent::Transparent<Self>> {{
+                    core::option::Option::Some({0}::transparent::Transparent::of())
+                }}
+            }}
+
+            impl<'__de, __Context> {0}::BorrowDecode<'__de, __Context> for {1} {{
+                const MIN_ENCODED_SIZE: usize = <{2} as {0}::Decode<__Context>>::MIN_ENCODED_SIZE;
+
+                fn borrow_decode<__D: {0}::de::BorrowDecoder<'__de, Context = __Context>>(decoder: &mut __D) -> core::result::Result<Self, {0}::error::DecodeError> {{
+                    {0}::Decode::decode(decoder)
+                }}
+
+                fn transparent_wire() -> core::option::Option<{0}::transparent::Transparent<Self>> {{
+                    core::option::Option::Some({0}::transparent::Transparent::of())
+                }}
+            }}",
+            crate_name,
+            generator.target_name(),
+            type_name(field),
+            name,
+            construct
+        ))?;
+        generator.append(impls);
+        Ok(())
+    }
+
+    pub fn generate_layout(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        let crate_name = &self.attributes.crate_name;
+        let type_name = generator.target_name().to_string();
+        generator
+            .impl_for(format!("{}::layout::EncodedLayout", crate_name))
+            .modify_generic_constraints(|generics, where_constraints| {
+                for g in generics.iter_generics() {
+                    where_constraints
+                        .push_constraint(g, format!("{}::layout::EncodedLayout", crate_name))
+                        .unwrap();
+                }
+                Ok(())
+            })?
+            .generate_fn("layout")
+            .with_return_type(format!("{}::layout::Layout", crate_name))
+            .body(|fn_body| {
+                let layout = format!(
+                    "{}::layout::Layout::Struct {{ name: {:?}, fields: {} }}",
+                    crate_name,
+                    type_name,
+                    fields_layout(
+                        crate_name,
+                        self.fields.as_ref(),
+                        self.attributes.option_bitmap.is_some()
+                    )?
+                );
+                fn_body.push_parsed(match &self.attributes.pad_struct_to {
+                    Some(padding) => format!(
+                        "{}::layout::Layout::padded({}, {})",
+                        crate_name, padding.size, layout
+                    ),
+                    None if self.attributes.length_prefixed.is_some() => {
+                        format!(
+                            "{}::layout::Layout::length_prefixed({})",
+                            crate_name, layout
+                        )
+                    }
+                    None => layout,
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_incremental_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_attributes()?;
+        let attributes = &self.attributes;
+        if attributes.pad_struct_to.is_some()
+            || attributes.option_bitmap.is_some()
+            || attributes.length_prefixed.is_some()
+            || attributes.prefix.is_some()
+            || attributes.decode_only.is_some()
+        {
+            return Err(Error::custom(
+                "IncrementalEncode encodes the fields one by one, so it can not be derived with pad_struct_to, option_bitmap, length_prefixed, prefix or decode_only",
+            ));
+        }
+        let mut fields = Vec::new();
+        for field in self.fields.as_ref().map(Fields::names).unwrap_or_default() {
+            let plain = field
+                .attributes()
+                .get_attribute::<FieldAttributes>()?
+                .is_none_or(|attributes| attributes.is_plain());
+            if !plain {
+                return Err(Error::custom(format!(
+                    "IncrementalEncode encodes every field with its own IncrementalEncode, so `{}` can not have attributes that change its encoding",
+                    field
+                )));
+            }
+            fields.push(field.to_string());
+        }
+
+        let crate_name = &attributes.crate_name;
+        let target_name = generator.target_name().to_string();
+        let incremental = format!("{}::enc::incremental", crate_name);
+        let encoder_bound = format!("__E: {}::enc::Encoder", crate_name);
+        // The where clause is only generated for a type with generics of its own, so the bound of a type without them is added to `__E` directly
+        let impl_generic = if generator.generics().is_some() {
+            "__E".to_string()
+        } else {
+            encoder_bound.clone()
+        };
+        generator
+            .impl_for(format!("{}::IncrementalEncode", incremental))
+            .with_impl_generics([impl_generic])
+            .with_trait_generics(["__E"])
+            .modify_generic_constraints(|generics, where_constraints| {
+                where_constraints.push_parsed_constraint(&encoder_bound)?;
+                if let Some((bounds, lit)) =
+                    (attributes.encode_bounds.as_ref()).or(attributes.bounds.as_ref())
+                {
+                    where_constraints
+                        .push_parsed_constraint(bounds)
+                        .map_err(|e| e.with_span(lit.span()))?;
+                } else {
+                    push_field_bounds(
+                        generics,
+                        where_constraints,
+                        unnamed_fields(self.fields.as_ref()),
+                        &target_name,
+                        |_| Some(format!("{}::IncrementalEncode<__E>", incremental)),
+                    )?;
+                }
+                Ok(())
+            })?
+            .generate_fn("encode_start")
+            .with_self_arg(virtue::generate::FnSelfArg::RefSelf)
+            .with_arg("_encoder", "&mut __E")
+            .with_return_type(format!(
+                "core::result::Result<core::option::Option<{0}::Parts<'_, __E>>, {1}::error::EncodeError>",
+                incremental, crate_name
+            ))
+            .body(|fn_body| {
+                // the fields are the parts of the struct, which has no start of its own
+                let parts: Vec<String> = fields
+                    .iter()
+                    .map(|field| format!("{}::Part::Value(&self.{})", incremental, field))
+                    .collect();
+                fn_body.push_parsed(format!(
+                    "core::result::Result::Ok(core::option::Option::Some({}::parts([{}])))",
+                    incremental,
+                    parts.join(", ")
+                ))?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+/// The primitives that `ConstWriter` has a `write_*` function for, with the amount of bytes they are encoded in with a fixint config.
+const CONST_PRIMITIVES: [(&str, usize); 15] = [
+    ("u8", 1),
+    ("u16", 2),
+    ("u32", 4),
+    ("u64", 8),
+    ("u128", 16),
+    ("usize", 8),
+    ("i8", 1),
+    ("i16", 2),
+    ("i32", 4),
+    ("i64", 8),
+    ("i128", 16),
+    ("isize", 8),
+    ("f32", 4),
+    ("f64", 8),
+    ("bool", 1),
+];
+
+/// Generates the `ConstWriter` calls that write `value`, an expression of type `ty`, and returns them with an expression of the amount of bytes they write.
+///
+/// Primitives are written directly, and arrays element by element with a `while` loop over an index named after `depth`. Any other type must derive `ConstEncode` itself.
+fn const_encode_value(ty: &[TokenTree], value: &str, depth: usize) -> (String, String) {
+    if let [TokenTree::Ident(ident)] = ty {
+        let name = ident.to_string();
+        if let Some((_, size)) = CONST_PRIMITIVES.iter().find(|(p, _)| *p == name) {
+            return (
+                format!("__writer.write_{}({});", name, value),
+                size.to_string(),
+            );
+        }
+    }
+    if let [TokenTree::Group(group)] = ty {
+        let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+        let split = tokens
+            .iter()
+            .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'));
+        if let (Delimiter::Bracket, Some(split)) = (group.delimiter(), split) {
+            let len = tokens_string(&tokens[split + 1..]);
+            let idx = format!("__idx{}", depth);
+            let (write, size) =
+                const_encode_value(&tokens[..split], &format!("{}[{}]", value, idx), depth + 1);
+            return (
+                format!(
+                    "{{ let mut {0} = 0; while {0} < {1} {{ {2} {0} += 1; }} }}",
+                    idx, len, write
+                ),
+                format!("({}) * ({})", len, size),
+            );
+        }
+    }
+    let ty = tokens_string(ty);
+    (
+        format!("<{}>::encode_const(&{}, __writer);", ty, value),
+        format!("<{}>::ENCODED_SIZE", ty),
+    )
 }
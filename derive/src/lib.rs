@@ -2,8 +2,643 @@ mod attribute;
 mod derive_enum;
 mod derive_struct;
 
-use attribute::ContainerAttributes;
-use virtue::prelude::{AttributeAccess, Body, Parse, Result, TokenStream};
+use attribute::{ContainerAttributes, FieldAttributes, Repr};
+use virtue::parse::{GenericConstraints, Generics, SimpleGeneric, Visibility};
+use virtue::prelude::{
+    AttributeAccess, Body, Delimiter, Error, Fields, Generator, Parse, Result, StreamBuilder,
+    TokenStream, TokenTree, UnnamedField,
+};
+
+/// Generates `.map_err(..)` that adds the type and field name to the error of a field's decode call.
+fn field_error_context(type_name: &str, field_name: &str) -> String {
+    format!(
+        ".map_err(|e| e.with_field({:?}, {:?}))",
+        type_name,
+        field_name.trim_start_matches("r#")
+    )
+}
+
+/// Wraps `expr`, which encodes or decodes a field, in the `enter` and `leave` markers of the `stats` feature. `coder` is the trait with the markers, `enc::Encoder` or `de::Decoder`, and `arg` the name of the encoder or decoder. Without the feature, `expr` is returned as is.
+fn stats_markers(
+    crate_name: &str,
+    coder: &str,
+    arg: &str,
+    type_name: &str,
+    field_name: &str,
+    expr: String,
+) -> String {
+    if !cfg!(feature = "stats") {
+        return expr;
+    }
+    let name = format!("{}.{}", type_name, field_name.trim_start_matches("r#"));
+    format!(
+        "{{ {0}::{1}::enter({2}, {3:?}); let __result = {4}; {0}::{1}::leave({2}); __result }}",
+        crate_name, coder, arg, name, expr
+    )
+}
+
+/// Checks that only the last of the given fields has `#[bincode(remaining_bytes)]` or `#[bincode(unknown_tail)]`, as it is decoded from all remaining bytes of the input, and that `unknown_tail` is only used in a struct with `#[bincode(length_prefixed)]`.
+fn check_remaining_bytes(fields: Option<&Fields>, length_prefixed: bool) -> Result<()> {
+    let Some(fields) = fields else {
+        return Ok(());
+    };
+    let names = fields.names();
+    for (index, field) in names.iter().enumerate() {
+        let attributes = field
+            .attributes()
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        let is_last = index + 1 == names.len();
+        if let Some(unknown_tail) = attributes.unknown_tail {
+            if !length_prefixed {
+                return Err(Error::custom_at(
+                    "unknown_tail can only be used in a struct with #[bincode(length_prefixed)], as it holds the rest of the bytes of the struct",
+                    unknown_tail.span(),
+                ));
+            }
+            if !is_last {
+                return Err(Error::custom_at(
+                    "unknown_tail can only be used on the last field",
+                    unknown_tail.span(),
+                ));
+            }
+        } else if let (Some(remaining_bytes), false) = (attributes.remaining_bytes, is_last) {
+            return Err(Error::custom_at(
+                "remaining_bytes can only be used on the last field",
+                remaining_bytes.span(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Appends `decode`, which decodes a struct or enum with `decoder`, to `fn_body`, wrapped in the `pad_struct_to` of the struct if it has one, or in the section of a type with `length_prefixed`.
+fn push_struct_decode(
+    fn_body: &mut StreamBuilder,
+    crate_name: &str,
+    attributes: &ContainerAttributes,
+    borrow: bool,
+    decode: StreamBuilder,
+) -> Result<()> {
+    match &attributes.pad_struct_to {
+        Some(padding) => {
+            fn_body.push_parsed(padding.decode_fn(crate_name, borrow))?;
+            fn_body.group(Delimiter::Parenthesis, |args| {
+                args.push_parsed(format!("decoder, {}, |decoder|", padding.strict))?;
+                args.group(Delimiter::Brace, |body| {
+                    body.append(decode);
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        }
+        None if attributes.length_prefixed.is_some() => {
+            fn_body.push_parsed(format!("{}::de::Decoder::with_length_prefix", crate_name))?;
+            fn_body.group(Delimiter::Parenthesis, |args| {
+                args.push_parsed("decoder, |decoder|")?;
+                args.group(Delimiter::Brace, |body| {
+                    body.append(decode);
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        }
+        None => {
+            fn_body.append(decode);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the type of the value of an option, if `ty` is written as `Option<T>`, `core::option::Option<T>` or `std::option::Option<T>`.
+fn option_value_type(ty: &[TokenTree]) -> Option<&[TokenTree]> {
+    let start = ty
+        .iter()
+        .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '<'))?;
+    let path = tokens_string(&ty[..start]).replace(' ', "");
+    let is_option = matches!(
+        path.trim_start_matches("::"),
+        "Option" | "core::option::Option" | "std::option::Option"
+    );
+    match ty.last() {
+        Some(TokenTree::Punct(p)) if is_option && p.as_char() == '>' => {
+            Some(&ty[start + 1..ty.len() - 1])
+        }
+        _ => None,
+    }
+}
+
+/// Returns the bit of every field in the option bitmap of a struct with `#[bincode(option_bitmap)]`, or `None` for the fields that are not in it. Only options without attributes that change how they are encoded are in the bitmap.
+fn option_bitmap_bits(fields: Option<&Fields>) -> Result<Vec<Option<usize>>> {
+    let mut bits = Vec::new();
+    let mut next = 0;
+    for field in unnamed_fields(fields) {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if attributes.is_plain() && option_value_type(&field.r#type).is_some() {
+            bits.push(Some(next));
+            next += 1;
+        } else {
+            bits.push(None);
+        }
+    }
+    Ok(bits)
+}
+
+/// Returns the fields of a struct or variant, without their names.
+fn unnamed_fields(fields: Option<&Fields>) -> Vec<&UnnamedField> {
+    match fields {
+        None => Vec::new(),
+        Some(Fields::Tuple(fields)) => fields.iter().collect(),
+        Some(Fields::Struct(fields)) => fields.iter().map(|(_, field)| field).collect(),
+    }
+}
+
+/// Checks the `#[bincode(borrow)]` and `#[bincode(owned)]` attributes of the given fields.
+///
+/// A reference can not be `owned` unless the type has a `decode_context` (`has_context`), which can provide the memory for it, e.g. an arena. `borrow` fields can not be decoded by `Decode` at all, so `decode` is set when the fields are checked for a `Decode` impl.
+fn check_borrow_attributes(fields: Option<&Fields>, decode: bool, has_context: bool) -> Result<()> {
+    for field in unnamed_fields(fields) {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if let (false, Some(owned)) = (has_context, attributes.owned) {
+            if matches!(field.r#type.first(), Some(TokenTree::Punct(p)) if p.as_char() == '&') {
+                return Err(Error::custom_at(
+                    format!(
+                        "owned can not be used on `{}`, as a reference can only be decoded by borrowing from the input, or into a `decode_context` like an arena. Use a `Cow` instead",
+                        type_name(field)
+                    ),
+                    owned.span(),
+                ));
+            }
+        }
+        if let (true, Some(borrow)) = (decode, attributes.borrow) {
+            return Err(Error::custom_at(
+                "borrow fields can only be decoded with BorrowDecode, derive BorrowDecode instead of Decode",
+                borrow.span(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first of the given fields that contains a reference like `&'de str`, e.g. in `HashMap<&'de str, u64>`, with the `&` of the reference.
+fn find_reference(fields: Option<&Fields>) -> Result<Option<(&UnnamedField, &TokenTree)>> {
+    for field in unnamed_fields(fields) {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if !attributes.uses_traits() || is_phantom_data(&field.r#type) {
+            continue;
+        }
+        let reference = field
+            .r#type
+            .iter()
+            .find(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '&'));
+        if let Some(reference) = reference {
+            return Ok(Some((field, reference)));
+        }
+    }
+    Ok(None)
+}
+
+/// Checks that none of the given fields contains a reference, as references can only be decoded by borrowing from the input. This is checked for a `Decode` impl without a `decode_context`, which would otherwise fail with a trait bound error on the reference.
+fn check_borrowed_references(fields: Option<&Fields>) -> Result<()> {
+    match find_reference(fields)? {
+        Some((field, reference)) => Err(Error::custom_at(
+            format!(
+                "`{}` borrows from the input, so it can only be decoded with BorrowDecode. Derive BorrowDecode instead of Decode, and decode with `borrow_decode_from_slice`, or decode it into a `decode_context` like an arena",
+                type_name(field)
+            ),
+            reference.span(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Generates a `BorrowDecode` impl that decodes with `Decode`, for a type that derives `Decode` with a `decode_context` and has references among its fields. `Decode` puts them into the memory of the context, e.g. an arena, and the derived `BorrowDecode` would borrow them from the input instead, which the type may not allow.
+fn generate_borrow_decode_with_decode(
+    generator: &mut Generator,
+    crate_name: &str,
+    decode_context: &str,
+) -> Result<()> {
+    generator
+        .impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
+        .with_trait_generics([decode_context])
+        .modify_generic_constraints(|_, where_constraints| {
+            where_constraints.push_parsed_constraint(format!(
+                "Self: {}::Decode<{}>",
+                crate_name, decode_context
+            ))?;
+            Ok(())
+        })?
+        .generate_fn("borrow_decode")
+        .with_generic_deps(
+            "__D",
+            [format!(
+                "{}::de::BorrowDecoder<'__de, Context = {}>",
+                crate_name, decode_context
+            )],
+        )
+        .with_arg("decoder", "&mut __D")
+        .with_return_type(format!(
+            "core::result::Result<Self, {}::error::DecodeError>",
+            crate_name
+        ))
+        .body(|fn_body| {
+            fn_body.push_parsed(format!("{}::Decode::decode(decoder)", crate_name))?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Checks that none of the given fields is a trait object like `Box<dyn Trait>`, as the input does not say which type to decode behind it.
+fn check_trait_objects(fields: Option<&Fields>) -> Result<()> {
+    for field in unnamed_fields(fields) {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if !attributes.uses_traits() || is_phantom_data(&field.r#type) {
+            continue;
+        }
+        let dyn_token = field
+            .r#type
+            .iter()
+            .find(|t| matches!(t, TokenTree::Ident(ident) if ident.to_string() == "dyn"));
+        if let Some(dyn_token) = dyn_token {
+            return Err(Error::custom_at(
+                format!(
+                    "bincode cannot decode trait objects like `{}`. Decode an enum of the types that implement the trait instead, or implement Decode manually",
+                    type_name(field)
+                ),
+                dyn_token.span(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the fields with `#[bincode(endian = "...")]` are numbers or arrays of numbers, as the endianness does not apply to anything else.
+fn check_endian_attributes(fields: Option<&Fields>) -> Result<()> {
+    fn is_numeric(ty: &[TokenTree]) -> bool {
+        match ty {
+            [TokenTree::Ident(ident)] => matches!(
+                ident.to_string().as_str(),
+                "u8" | "u16"
+                    | "u32"
+                    | "u64"
+                    | "u128"
+                    | "usize"
+                    | "i8"
+                    | "i16"
+                    | "i32"
+                    | "i64"
+                    | "i128"
+                    | "isize"
+                    | "f32"
+                    | "f64"
+            ),
+            [TokenTree::Group(group)] if group.delimiter() == Delimiter::Bracket => {
+                let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+                tokens
+                    .iter()
+                    .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'))
+                    .is_some_and(|split| is_numeric(&tokens[..split]))
+            }
+            _ => false,
+        }
+    }
+
+    for field in unnamed_fields(fields) {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if let Some((_, endian)) = attributes.endian {
+            if !is_numeric(&field.r#type) {
+                return Err(Error::custom_at(
+                    format!(
+                        "endian can only be used on integers, floats and arrays of them, not on `{}`",
+                        type_name(field)
+                    ),
+                    endian.span(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if `lifetime` is used by at least one of the given fields, and only by fields that are decoded with `Decode`, like those with `#[bincode(owned)]`. `BorrowDecode` does not have to tie such a lifetime to the input.
+fn is_owned_lifetime<'a>(
+    fields: impl IntoIterator<Item = &'a UnnamedField>,
+    lifetime: &str,
+) -> Result<bool> {
+    let lifetime = format!("'{}", lifetime);
+    let mut used = false;
+    for field in fields {
+        if !mentions(&type_name(field), &lifetime) {
+            continue;
+        }
+        if !field_attributes(field)?.is_owned() {
+            return Ok(false);
+        }
+        used = true;
+    }
+    Ok(used)
+}
+
+/// Adds the where clauses of a derived impl, so the impl only requires the bounds that the fields need.
+///
+/// A field whose type uses one of the generics needs the trait that `field_trait` returns for it, e.g. `Wrapper<T>: Encode` instead of `T: Encode`. A `Cow<'a, T>` is bounded on what it points to, see [`cow_bound`]. Types without generics and `PhantomData` need nothing, and neither do fields for which `field_trait` returns `None`. A type that uses the type itself, like `Box<Tree<T>>`, would need the impl that is being generated, so the generics that it uses need the trait instead.
+///
+/// The same goes for types behind a pointer or in a collection, like `Vec<Expr<T>>`: a type can only contain itself through one of those, and it may do so through another type, e.g. `Expr<T>` containing a `Block<T>` that contains a `Vec<Expr<T>>`. The bounds of the two impls would then require each other, which the compiler can not resolve. Bounding the generics of these fields instead breaks every such cycle.
+fn push_field_bounds<'a>(
+    generics: &Generics,
+    where_constraints: &mut GenericConstraints,
+    fields: impl IntoIterator<Item = &'a UnnamedField>,
+    target_name: &str,
+    field_trait: impl Fn(&FieldAttributes) -> Option<String>,
+) -> Result<()> {
+    let mut bounds: Vec<String> = Vec::new();
+    for field in fields {
+        let ty = type_name(field);
+        let used: Vec<&SimpleGeneric> = generics
+            .iter_generics()
+            .filter(|g| mentions(&ty, &g.ident.to_string()))
+            .collect();
+        if used.is_empty() || is_phantom_data(&field.r#type) {
+            continue;
+        }
+        let attributes = field_attributes(field)?;
+        let Some(trait_name) = field_trait(&attributes) else {
+            continue;
+        };
+        let new_bounds: Vec<String> = if mentions(&ty, target_name)
+            || mentions(&ty, "Self")
+            || INDIRECTIONS.iter().any(|name| mentions(&ty, name))
+        {
+            used.into_iter()
+                .map(|g| format!("{}: {}", g.ident, trait_name))
+                .collect()
+        } else if let Some(bound) = cow_pointee(&field.r#type)
+            .and_then(|pointee| cow_bound(&tokens_string(pointee), &trait_name))
+        {
+            vec![bound]
+        } else {
+            vec![format!("{}: {}", ty, trait_name)]
+        };
+        for bound in new_bounds {
+            if !bounds.contains(&bound) {
+                bounds.push(bound);
+            }
+        }
+    }
+    // an empty `T:` takes care of a trailing comma in the where clause of the type, which `push_parsed_constraint` would repeat
+    if let (Some(generic), false) = (generics.iter_generics().next(), bounds.is_empty()) {
+        where_constraints.push_constraint(generic, "")?;
+    }
+    for bound in bounds {
+        where_constraints.push_parsed_constraint(bound)?;
+    }
+    Ok(())
+}
+
+/// Returns the bound that a `Cow` of `pointee` needs for `trait_name`, on the type that it points to: `T: Encode` for `Cow<'a, T>: Encode`, and `<T as ToOwned>::Owned: Decode` for `Decode`, which is also how a derived `BorrowDecode` decodes it. A `Cow<'a, T>: BorrowDecode<'__de>` bound could not be met for most `T`, see [`field_attributes`]. Returns `None` for the other traits, whose bound stays on the whole type.
+fn cow_bound(pointee: &str, trait_name: &str) -> Option<String> {
+    let path = trait_name.split('<').next().unwrap_or(trait_name);
+    match path.rsplit_once("::") {
+        Some((_, "Encode" | "SkipDecode" | "EncodedLayout")) => {
+            Some(format!("{}: {}", pointee, trait_name))
+        }
+        Some((crate_name, "Decode")) => Some(format!(
+            "<{} as {}::de::ToOwned>::Owned: {}",
+            pointee, crate_name, trait_name
+        )),
+        _ => None,
+    }
+}
+
+/// Returns the type that `ty` points to if it is written as `Cow<'a, T>`, with or without its path, e.g. the `[u8]` of `Cow<'a, [u8]>`.
+fn cow_pointee(ty: &[TokenTree]) -> Option<&[TokenTree]> {
+    let is_punct = |t: &TokenTree, c: char| matches!(t, TokenTree::Punct(p) if p.as_char() == c);
+    let start = ty.iter().position(|t| is_punct(t, '<'))?;
+    let comma = ty.iter().position(|t| is_punct(t, ','))?;
+    match (&ty[..start], ty.last()) {
+        ([.., TokenTree::Ident(ident)], Some(end))
+            if ident.to_string() == "Cow" && is_punct(end, '>') =>
+        {
+            Some(&ty[comma + 1..ty.len() - 1])
+        }
+        _ => None,
+    }
+}
+
+/// Returns the attributes of `field`. A `Cow` field that can not borrow from the input is marked as `owned_cow`, so a derived `BorrowDecode` decodes it with `Decode` into a `Cow::Owned`, as if it had `#[bincode(owned)]`.
+///
+/// Only `&str` and `&[u8]` implement `BorrowDecode`, so a `Cow<'a, str>` and a `Cow<'a, [u8]>` still borrow from the input, and so does a `Cow` with `#[bincode(borrow)]`, e.g. of a type with a manual `BorrowDecode` for its reference.
+fn field_attributes(field: &UnnamedField) -> Result<FieldAttributes> {
+    let mut attributes = field
+        .attributes
+        .get_attribute::<FieldAttributes>()?
+        .unwrap_or_default();
+    if attributes.uses_traits()
+        && attributes.endian.is_none()
+        && attributes.borrow.is_none()
+        && attributes.owned.is_none()
+    {
+        let borrows = |pointee: &[TokenTree]| match pointee {
+            [TokenTree::Ident(ident)] => ident.to_string() == "str",
+            [TokenTree::Group(group)] => {
+                group.delimiter() == Delimiter::Bracket && group.stream().to_string() == "u8"
+            }
+            _ => false,
+        };
+        attributes.owned_cow = cow_pointee(&field.r#type).is_some_and(|pointee| !borrows(pointee));
+    }
+    Ok(attributes)
+}
+
+/// The pointers and collections that a type can contain itself through, see [`push_field_bounds`]. `HashMap` and `HashSet` are not included, because the generics of their hasher do not implement the traits.
+const INDIRECTIONS: &[&str] = &[
+    "Box",
+    "Rc",
+    "Arc",
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "BinaryHeap",
+    "BTreeMap",
+    "BTreeSet",
+];
+
+/// Returns `true` if `ty` is written as `PhantomData<..>`, with or without its path.
+fn is_phantom_data(ty: &[TokenTree]) -> bool {
+    let end = ty
+        .iter()
+        .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '<'))
+        .unwrap_or(ty.len());
+    matches!(ty[..end].last(), Some(TokenTree::Ident(ident)) if ident.to_string() == "PhantomData")
+}
+
+/// Returns the trait that a field is decoded with in a derived `BorrowDecode`: `Decode` for a field with `#[bincode(owned)]` or a `Cow` that can not borrow from the input, and `BorrowDecode` otherwise.
+fn borrow_decode_trait(
+    crate_name: &str,
+    decode_context: &str,
+    attributes: &FieldAttributes,
+) -> String {
+    if attributes.is_owned() {
+        format!("{}::Decode<{}>", crate_name, decode_context)
+    } else {
+        format!(
+            "{}::de::BorrowDecode<'__de, {}>",
+            crate_name, decode_context
+        )
+    }
+}
+
+/// Returns `true` if `name`, e.g. a generic `T` or a lifetime `'a`, is used in `type_name`.
+fn mentions(type_name: &str, name: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    type_name.match_indices(name).any(|(idx, _)| {
+        !type_name[..idx].ends_with(is_word) && !type_name[idx + name.len()..].starts_with(is_word)
+    })
+}
+
+/// Generates the list of `Field`s of a struct or variant, for `EncodedLayout::layout`.
+fn fields_layout(crate_name: &str, fields: Option<&Fields>, option_bitmap: bool) -> Result<String> {
+    let fields: Vec<(String, &UnnamedField)> = match fields {
+        None => Vec::new(),
+        Some(Fields::Tuple(fields)) => fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| (idx.to_string(), field))
+            .collect(),
+        Some(Fields::Struct(fields)) => fields
+            .iter()
+            .map(|(ident, field)| {
+                (
+                    ident.to_string().trim_start_matches("r#").to_string(),
+                    field,
+                )
+            })
+            .collect(),
+    };
+    let mut result = String::from("core::convert::From::from([");
+    for (name, field) in fields {
+        let value_type = option_value_type(&field.r#type);
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        let mut layout = if attributes.remaining_bytes.is_some() {
+            format!("{}::layout::Layout::RemainingBytes", crate_name)
+        } else if let Some(args) = attributes.fixed_len_args(crate_name) {
+            format!("{}::layout::Layout::fixed_len({})", crate_name, args)
+        } else if attributes.with_serde || attributes.endian.is_some() {
+            format!(
+                "{}::layout::Layout::Opaque {{ name: {:?} }}",
+                crate_name,
+                type_name(field)
+            )
+        } else if let (true, true, Some(value_type)) =
+            (option_bitmap, attributes.is_plain(), value_type)
+        {
+            format!(
+                "{0}::layout::Layout::bitmap_option(<{1} as {0}::layout::EncodedLayout>::layout())",
+                crate_name,
+                tokens_string(value_type)
+            )
+        } else {
+            format!(
+                "<{} as {}::layout::EncodedLayout>::layout()",
+                type_name(field),
+                crate_name
+            )
+        };
+        if let Some(padding) = &attributes.pad_to {
+            layout = format!(
+                "{}::layout::Layout::padded({}, {})",
+                crate_name, padding.size, layout
+            );
+        }
+        result.push_str(&format!(
+            "{}::layout::Field::new({:?}, {}),",
+            crate_name, name, layout
+        ));
+    }
+    result.push_str("])");
+    Ok(result)
+}
+
+/// Returns the expression that skips a field of type `ty` with `decoder`, and the expression of its `SkipDecode::fixed_size` with config `__C`.
+fn skip_field(crate_name: &str, ty: &str, attributes: &FieldAttributes) -> (String, String) {
+    if let Some(size) = attributes
+        .pad_to
+        .as_ref()
+        .map(|padding| padding.size)
+        .or_else(|| attributes.len.as_ref().map(|(len, _)| *len))
+    {
+        (
+            format!("{}::de::Decoder::skip_bytes(decoder, {})", crate_name, size),
+            format!("core::option::Option::Some({}usize)", size),
+        )
+    } else if attributes.remaining_bytes.is_some() {
+        (
+            format!("{}::de::skip_remaining_bytes(decoder)", crate_name),
+            "core::option::Option::None".to_string(),
+        )
+    } else if attributes.with_serde {
+        (
+            format!(
+                "<{0}::serde::Compat<{1}> as {0}::Decode<__D::Context>>::decode(decoder).map(core::mem::drop)",
+                crate_name, ty
+            ),
+            "core::option::Option::None".to_string(),
+        )
+    } else {
+        (
+            format!("<{} as {}::de::SkipDecode>::skip(decoder)", ty, crate_name),
+            format!(
+                "<{} as {}::de::SkipDecode>::fixed_size::<__C>()",
+                ty, crate_name
+            ),
+        )
+    }
+}
+
+/// Returns the type of a field as it is written, e.g. `&'a str` instead of the `&'astr` of `type_string`.
+fn type_name(field: &UnnamedField) -> String {
+    tokens_string(&field.r#type)
+}
+
+/// Returns the given tokens as they are written, see `type_name`.
+fn tokens_string(tokens: &[TokenTree]) -> String {
+    let mut result = String::new();
+    for token in tokens {
+        let token = token.to_string();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        if result.ends_with(is_word) && token.starts_with(is_word) {
+            result.push(' ');
+        }
+        result.push_str(&token);
+    }
+    result
+}
+
+/// Returns the visibility of the type that is derived for.
+fn visibility(parse: &Parse) -> Visibility {
+    match parse {
+        Parse::Struct { visibility, .. } | Parse::Enum { visibility, .. } => visibility.clone(),
+        _ => Visibility::Default,
+    }
+}
 
 #[proc_macro_derive(Encode, attributes(bincode))]
 pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -19,6 +654,7 @@ fn derive_encode_inner(input: TokenStream) -> Result<TokenStream> {
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    attributes.check_direction("Encode", true)?;
 
     match body {
         Body::Struct(body) => {
@@ -51,18 +687,21 @@ pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// Returns an error if the input cannot be parsed or if the code generation fails.
 fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
+    let visibility = visibility(&parse);
     let (mut generator, attributes, body) = parse.into_generator();
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    attributes.check_direction("Decode", false)?;
 
     match body {
         Body::Struct(body) => {
-            derive_struct::DeriveStruct {
+            let derive = derive_struct::DeriveStruct {
                 fields: body.fields,
                 attributes,
-            }
-            .generate_decode(&mut generator)?;
+            };
+            derive.generate_prefix(&mut generator, visibility, "Decode")?;
+            derive.generate_decode(&mut generator)?;
         }
         Body::Enum(body) => {
             derive_enum::DeriveEnum {
@@ -87,18 +726,21 @@ pub fn derive_borrow_decode(input: proc_macro::TokenStream) -> proc_macro::Token
 /// Returns an error if the input cannot be parsed or if the code generation fails.
 fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
+    let visibility = visibility(&parse);
     let (mut generator, attributes, body) = parse.into_generator();
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    attributes.check_direction("BorrowDecode", false)?;
 
     match body {
         Body::Struct(body) => {
-            derive_struct::DeriveStruct {
+            let derive = derive_struct::DeriveStruct {
                 fields: body.fields,
                 attributes,
-            }
-            .generate_borrow_decode(&mut generator)?;
+            };
+            derive.generate_prefix(&mut generator, visibility, "BorrowDecode")?;
+            derive.generate_borrow_decode(&mut generator)?;
         }
         Body::Enum(body) => {
             derive_enum::DeriveEnum {
@@ -112,3 +754,187 @@ fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
     generator.export_to_file("bincode_next", "BorrowDecode");
     generator.finish()
 }
+
+#[proc_macro_derive(SkipDecode, attributes(bincode))]
+pub fn derive_skip_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_skip_decode_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or if the code generation fails.
+fn derive_skip_decode_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let visibility = visibility(&parse);
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+    attributes.check_direction("SkipDecode", false)?;
+
+    match body {
+        Body::Struct(body) => {
+            let derive = derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            };
+            derive.generate_prefix(&mut generator, visibility, "SkipDecode")?;
+            derive.generate_skip_decode(&mut generator)?;
+        }
+        Body::Enum(body) => {
+            derive_enum::DeriveEnum {
+                variants: body.variants,
+                attributes,
+            }
+            .generate_skip_decode(&mut generator)?;
+        }
+    }
+
+    generator.export_to_file("bincode_next", "SkipDecode");
+    generator.finish()
+}
+
+#[proc_macro_derive(EncodedLayout, attributes(bincode))]
+pub fn derive_encoded_layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_encoded_layout_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or if the code generation fails.
+fn derive_encoded_layout_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            }
+            .generate_layout(&mut generator)?;
+        }
+        Body::Enum(body) => {
+            derive_enum::DeriveEnum {
+                variants: body.variants,
+                attributes,
+            }
+            .generate_layout(&mut generator)?;
+        }
+    }
+
+    generator.export_to_file("bincode_next", "EncodedLayout");
+    generator.finish()
+}
+
+#[proc_macro_derive(ConstEncode, attributes(bincode))]
+pub fn derive_const_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_const_encode_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed, is not a struct, or if the code generation fails.
+fn derive_const_encode_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+    attributes.check_direction("ConstEncode", true)?;
+
+    match body {
+        Body::Struct(body) => {
+            derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            }
+            .generate_const_encode(&mut generator)?;
+        }
+        Body::Enum(_) => {
+            return Err(Error::custom("ConstEncode can only be derived for structs"));
+        }
+    }
+
+    generator.export_to_file("bincode_next", "ConstEncode");
+    generator.finish()
+}
+
+#[proc_macro_derive(IncrementalEncode, attributes(bincode))]
+pub fn derive_incremental_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_incremental_encode_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed, is not a struct, or if the code generation fails.
+fn derive_incremental_encode_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            }
+            .generate_incremental_encode(&mut generator)?;
+        }
+        Body::Enum(_) => {
+            return Err(Error::custom(
+                "IncrementalEncode can only be derived for structs",
+            ));
+        }
+    }
+
+    generator.export_to_file("bincode_next", "IncrementalEncode");
+    generator.finish()
+}
+
+#[proc_macro_derive(TransparentWire, attributes(bincode))]
+pub fn derive_transparent_wire(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_transparent_wire_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed, is not a `#[repr(transparent)]` struct with a single field, or if the code generation fails.
+fn derive_transparent_wire_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let transparent = attributes
+        .get_attribute::<Repr>()?
+        .is_some_and(|repr| repr.transparent);
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            if !transparent {
+                return Err(Error::custom(
+                    "TransparentWire can only be derived for structs with #[repr(transparent)]",
+                ));
+            }
+            derive_struct::DeriveStruct {
+                fields: body.fields,
+                attributes,
+            }
+            .generate_transparent_wire(&mut generator)?;
+        }
+        Body::Enum(_) => {
+            return Err(Error::custom(
+                "TransparentWire can only be derived for structs",
+            ));
+        }
+    }
+
+    generator.export_to_file("bincode_next", "TransparentWire");
+    generator.finish()
+}
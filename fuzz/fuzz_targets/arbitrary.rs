@@ -0,0 +1,22 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use arbitrary::Arbitrary;
+use std::collections::BTreeMap;
+
+#[derive(bincode::Decode, bincode::Encode, Arbitrary, PartialEq, Debug)]
+enum Message {
+    Empty,
+    Text(Vec<String>),
+    Lookup { id: u64, names: BTreeMap<String, u32> },
+    Scores(BTreeMap<u16, i64>),
+    Nested(Vec<Message>),
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Generated values are bounded by the size of `data`, so they don't need a limit
+    bincode::fuzz::roundtrip_arbitrary::<Message, _>(data, bincode::config::standard());
+
+    let config = bincode::config::standard().with_limit::<1024>();
+    bincode::fuzz::roundtrip::<Message, _>(data, config);
+});
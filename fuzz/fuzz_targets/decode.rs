@@ -0,0 +1,82 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use bincode::config::{self, Config};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ffi::CString;
+use std::net::{IpAddr, SocketAddr};
+use std::num::{NonZeroU128, NonZeroU32};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(bincode::Decode, bincode::Encode, Debug)]
+#[bincode(option_bitmap)]
+struct Header {
+    id: u64,
+    name: Option<String>,
+    parent: Option<u32>,
+    #[bincode(pad_to = 8)]
+    flags: u16,
+}
+
+#[derive(bincode::Decode, bincode::Encode, Debug)]
+#[bincode(length_prefixed)]
+struct Record {
+    header: Header,
+    tags: BTreeMap<String, Vec<u8>>,
+    #[bincode(unknown_tail)]
+    unknown: Vec<u8>,
+}
+
+#[derive(bincode::Decode, bincode::Encode, Debug)]
+#[bincode(string_tags)]
+enum Event {
+    Empty,
+    Record(Record),
+    Batch(Vec<Event>),
+    Times { at: SystemTime, took: Duration },
+}
+
+#[derive(bincode::Decode, bincode::Encode, Debug)]
+enum Std {
+    Collections(
+        HashMap<u16, String>,
+        HashSet<i64>,
+        BTreeSet<char>,
+        VecDeque<u128>,
+        BinaryHeap<u8>,
+    ),
+    Slices(Box<[u32]>, Rc<str>, Arc<[u16]>, Box<[u64; 4]>, Rc<[u8; 3]>),
+    Os(CString, PathBuf, IpAddr, SocketAddr),
+    Numbers(NonZeroU32, NonZeroU128, usize, isize, f32, char),
+    Nested(Option<Box<Std>>, Result<Vec<Std>, u8>),
+}
+
+type Borrowed<'a> = (&'a str, &'a [u8], Cow<'a, str>, Vec<&'a str>);
+
+/// Decodes `data` in every way, none of which may panic, whatever the bytes are
+fn decode_all<C: Config>(data: &[u8], config: C) {
+    let _ = bincode::decode_from_slice::<Event, _>(data, config);
+    let _ = bincode::decode_from_slice::<Vec<Std>, _>(data, config);
+    let _ = bincode::borrow_decode_from_slice::<Borrowed<'_>, _>(data, config);
+    let _ = bincode::decode_from_std_read::<Event, _, _>(&mut &data[..], config);
+    let _ = bincode::decode_from_std_read::<Std, _, _>(&mut &data[..], config);
+}
+
+fuzz_target!(|data: &[u8]| {
+    // without a limit, lengths are only bounded by the input
+    decode_all(data, config::standard());
+    decode_all(data, config::legacy().with_big_endian());
+    decode_all(
+        data,
+        config::standard()
+            .with_fixed_u32_lengths()
+            .with_exact_system_time()
+            .with_lossless_paths(),
+    );
+    decode_all(data, config::untrusted(1024));
+    decode_all(data, config::standard().with_limit::<64>());
+});
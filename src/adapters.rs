@@ -0,0 +1,296 @@
+//! Field types that check or convert a value while it is decoded, without implementing `Decode` for the struct that contains it.
+//!
+//! - [`Validated<T, V>`](Validated) is encoded as a `T`, and the [`Validator`] `V` checks every `T` that is decoded.
+//! - [`Mapped<Wire, Target, M>`](Mapped) is encoded as a `Wire`, and the [`Mapping`] `M` converts between a `Wire` and the `Target` the field holds.
+//!
+//! Both are transparent wrappers of the value they hold. A value that is rejected fails with [`DecodeError::InvalidValue`\], with the message of the validator or mapping.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::adapters::{InRange, Mapped, Mapping, Validated};
+//! use bincode::error::DecodeError;
+//!
+//! /// Stores names in lower case
+//! struct Lowercase;
+//!
+//! impl Mapping<String, String> for Lowercase {
+//!     fn from_wire(wire: String) -> Result<String, &'static str> {
+//!         Ok(wire.to_lowercase())
+//!     }
+//!
+//!     fn to_wire(name: &String) -> String {
+//!         name.clone()
+//!     }
+//! }
+//!
+//! #[derive(bincode::Encode, bincode::Decode, Debug)]
+//! struct User {
+//!     name: Mapped<String, String, Lowercase>,
+//!     age: Validated<u8, InRange<0, 150>>,
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let (user, _): (User, _) = bincode::decode_from_slice(&[3, b'A', b'n', b'N', 42], config).unwrap();
+//! assert_eq!(*user.name, "ann");
+//! assert_eq!(*user.age, 42);
+//!
+//! let err = bincode::decode_from_slice::<User, _>(&[0, 200], config).unwrap_err();
+//! assert!(matches!(err.inner(), DecodeError::InvalidValue { message: "out of range", .. }));
+//! ```
+
+use crate::{
+    config::Config,
+    de::{BorrowDecode, BorrowDecoder, Decode, Decoder, SkipDecode},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::{marker::PhantomData, ops::Deref};
+
+/// Checks the values of a [`Validated`] field.
+pub trait Validator<T> {
+    /// Returns `Err` with a message if `value` is not valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message that describes why `value` is not valid.
+    fn validate(value: &T) -> Result<(), &'static str>;
+}
+
+/// Accepts integers from `MIN` up to and including `MAX`, and rejects other integers with the message `"out of range"`.
+#[derive(Debug, Clone, Copy)]
+pub struct InRange<const MIN: i128, const MAX: i128>;
+
+impl<T: Copy + Into<i128>, const MIN: i128, const MAX: i128> Validator<T> for InRange<MIN, MAX> {
+    fn validate(value: &T) -> Result<(), &'static str> {
+        let value: i128 = (*value).into();
+        if (MIN..=MAX).contains(&value) {
+            Ok(())
+        } else {
+            Err("out of range")
+        }
+    }
+}
+
+/// A `T` that the [`Validator`] `V` accepted. It is encoded as the `T`, and fails to decode with [`DecodeError::InvalidValue`\] if `V` rejects the decoded `T`. See the [module documentation](self).
+#[repr(transparent)]
+pub struct Validated<T, V> {
+    value: T,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<T, V: Validator<T>> Validated<T, V> {
+    /// Validates `value` with `V`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the message of `V` if it rejects `value`.
+    pub fn new(value: T) -> Result<Self, &'static str> {
+        V::validate(&value)?;
+        Ok(Self {
+            value,
+            _validator: PhantomData,
+        })
+    }
+}
+
+impl<T, V> Validated<T, V> {
+    /// Returns the value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, V> Deref for Validated<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: core::fmt::Debug, V> core::fmt::Debug for Validated<T, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: Clone, V> Clone for Validated<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            _validator: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, V> Copy for Validated<T, V> {}
+
+impl<T: PartialEq, V> PartialEq for Validated<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, V> Eq for Validated<T, V> {}
+
+impl<T: Encode, V> Encode for Validated<T, V> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.value.encode(encoder)
+    }
+}
+
+impl<Context, T: Decode<Context>, V: Validator<T>> Decode<Context> for Validated<T, V> {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Self::new(T::decode(decoder)?).map_err(invalid_value::<T>)
+    }
+}
+
+impl<'de, Context, T: BorrowDecode<'de, Context>, V: Validator<T>> BorrowDecode<'de, Context>
+    for Validated<T, V>
+{
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::new(T::borrow_decode(decoder)?).map_err(invalid_value::<T>)
+    }
+}
+
+impl<T: SkipDecode, V> SkipDecode for Validated<T, V> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+/// Converts between the `Wire` type that a [`Mapped`] field is encoded as, and the `Target` type that it holds.
+pub trait Mapping<Wire, Target> {
+    /// Converts a decoded `Wire` into a `Target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message that describes why `wire` can not be converted.
+    fn from_wire(wire: Wire) -> Result<Target, &'static str>;
+
+    /// Converts a `Target` into the `Wire` that is encoded.
+    fn to_wire(target: &Target) -> Wire;
+}
+
+/// A `Target` that is encoded as a `Wire`, converted by the [`Mapping`] `M`. It fails to decode with [`DecodeError::InvalidValue`\] if `M` can not convert the decoded `Wire`. See the [module documentation](self).
+#[repr(transparent)]
+pub struct Mapped<Wire, Target, M> {
+    value: Target,
+    _mapping: PhantomData<fn() -> (Wire, M)>,
+}
+
+impl<Wire, Target, M> Mapped<Wire, Target, M> {
+    /// Wraps `value`, which is encoded as the `Wire` that `M` converts it to.
+    #[must_use]
+    pub const fn new(value: Target) -> Self {
+        Self {
+            value,
+            _mapping: PhantomData,
+        }
+    }
+
+    /// Returns the value.
+    #[must_use]
+    pub fn into_inner(self) -> Target {
+        self.value
+    }
+}
+
+impl<Wire, Target, M> Deref for Mapped<Wire, Target, M> {
+    type Target = Target;
+
+    fn deref(&self) -> &Target {
+        &self.value
+    }
+}
+
+impl<Wire, Target: core::fmt::Debug, M> core::fmt::Debug for Mapped<Wire, Target, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<Wire, Target: Clone, M> Clone for Mapped<Wire, Target, M> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<Wire, Target: Copy, M> Copy for Mapped<Wire, Target, M> {}
+
+impl<Wire, Target: PartialEq, M> PartialEq for Mapped<Wire, Target, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<Wire, Target: Eq, M> Eq for Mapped<Wire, Target, M> {}
+
+impl<Wire, Target, M> From<Target> for Mapped<Wire, Target, M> {
+    fn from(value: Target) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<Wire: Encode, Target, M: Mapping<Wire, Target>> Encode for Mapped<Wire, Target, M> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        M::to_wire(&self.value).encode(encoder)
+    }
+}
+
+impl<Context, Wire: Decode<Context>, Target, M: Mapping<Wire, Target>> Decode<Context>
+    for Mapped<Wire, Target, M>
+{
+    const MIN_ENCODED_SIZE: usize = Wire::MIN_ENCODED_SIZE;
+
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        M::from_wire(Wire::decode(decoder)?)
+            .map(Self::new)
+            .map_err(invalid_value::<Target>)
+    }
+}
+
+impl<'de, Context, Wire: BorrowDecode<'de, Context>, Target, M: Mapping<Wire, Target>>
+    BorrowDecode<'de, Context> for Mapped<Wire, Target, M>
+{
+    const MIN_ENCODED_SIZE: usize = Wire::MIN_ENCODED_SIZE;
+
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        M::from_wire(Wire::borrow_decode(decoder)?)
+            .map(Self::new)
+            .map_err(invalid_value::<Target>)
+    }
+}
+
+impl<Wire: SkipDecode, Target, M> SkipDecode for Mapped<Wire, Target, M> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        Wire::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        Wire::fixed_size::<C>()
+    }
+}
+
+/// The error for a decoded `T` that a validator or mapping rejected with `message`
+fn invalid_value<T>(message: &'static str) -> DecodeError {
+    DecodeError::InvalidValue {
+        type_name: core::any::type_name::<T>(),
+        message,
+    }
+}
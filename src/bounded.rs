@@ -0,0 +1,524 @@
+//! Strings and lists with a fixed capacity, which live on the stack and need no allocator.
+//!
+//! [`BoundedString<N>`](BoundedString) holds a string of at most `N` bytes, and is encoded exactly like a `String`. [`BoundedVec<T, N>`](BoundedVec) holds at most `N` elements, and is encoded exactly like a `Vec<T>`. If the encoded length exceeds `N`, decoding fails with [`DecodeError::ArrayCapacityExceeded`\] before any element is read.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::bounded::{BoundedString, BoundedVec};
+//!
+//! #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+//! struct Hello {
+//!     name: BoundedString<32>,
+//!     features: BoundedVec<u16, 8>,
+//! }
+//!
+//! let hello = Hello {
+//!     name: BoundedString::try_from("client").unwrap(),
+//!     features: BoundedVec::try_from(&[1, 2, 3][..]).unwrap(),
+//! };
+//! let config = bincode::config::standard();
+//! let mut buffer = [0u8; 64];
+//! let len = bincode::encode_into_slice(&hello, &mut buffer, config).unwrap();
+//! let (decoded, _): (Hello, _) = bincode::decode_from_slice(&buffer[..len], config).unwrap();
+//! assert_eq!(decoded, hello);
+//! assert_eq!(&*decoded.name, "client");
+//! assert_eq!(decoded.features[1], 2);
+//! ```
+#![allow(unsafe_code)]
+
+use crate::{
+    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder, SkipDecode},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
+/// The error of a conversion into a [`BoundedString`\] or [`BoundedVec`\] that does not fit in its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The length that was needed, in bytes for a `BoundedString` and in elements for a `BoundedVec`
+    pub len: usize,
+    /// The capacity
+    pub cap: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a length of {} does not fit in a capacity of {}",
+            self.len, self.cap
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// A string of at most `N` bytes, which is stored inline. It is encoded exactly like a `String`. See the [module documentation](self).
+#[derive(Clone, Copy)]
+pub struct BoundedString<const N: usize> {
+    len: usize,
+    // invariant: `bytes[..len]` is valid UTF-8
+    bytes: [u8; N],
+}
+
+impl<const N: usize> BoundedString<N> {
+    /// Creates an empty string.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; N],
+        }
+    }
+
+    /// The string as a `&str`.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Safety: `bytes[..len]` is always valid UTF-8
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[..self.len]) }
+    }
+
+    /// The string as a `&mut str`.
+    #[must_use]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        // Safety: `bytes[..len]` is always valid UTF-8, and a `&mut str` can only be changed into valid UTF-8
+        unsafe { core::str::from_utf8_unchecked_mut(&mut self.bytes[..self.len]) }
+    }
+
+    /// The maximum length of the string in bytes, which is `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Appends `s` to the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CapacityError`\] and leaves the string as it was if `s` does not fit.
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        let len = self.len + s.len();
+        if len > N {
+            return Err(CapacityError { len, cap: N });
+        }
+        self.bytes[self.len..len].copy_from_slice(s.as_bytes());
+        self.len = len;
+        Ok(())
+    }
+
+    /// Appends `c` to the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CapacityError`\] and leaves the string as it was if `c` does not fit.
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError> {
+        self.try_push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes all contents of the string.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for BoundedString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = CapacityError;
+
+    fn try_from(s: &str) -> Result<Self, CapacityError> {
+        let mut string = Self::new();
+        string.try_push_str(s)?;
+        Ok(string)
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for BoundedString<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for BoundedString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<str> for BoundedString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Debug for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<BoundedString<M>> for BoundedString<N> {
+    fn eq(&self, other: &BoundedString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<str> for BoundedString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for BoundedString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> Eq for BoundedString<N> {}
+
+impl<const N: usize> PartialOrd for BoundedString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BoundedString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> Hash for BoundedString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<const N: usize> Encode for BoundedString<N> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_str().encode(encoder)
+    }
+}
+
+impl<Context, const N: usize> Decode<Context> for BoundedString<N> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_read::<u8>(len)?;
+
+        let mut string = Self::new();
+        let bytes = &mut string.bytes[..len];
+        decoder.reader().read(bytes)?;
+        crate::de::str_from_utf8(bytes)?;
+        string.len = len;
+        Ok(string)
+    }
+}
+
+impl<'de, Context, const N: usize> BorrowDecode<'de, Context> for BoundedString<N> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+impl<const N: usize> SkipDecode for BoundedString<N> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        str::skip(decoder)
+    }
+}
+
+/// A list of at most `N` elements, which are stored inline. It is encoded exactly like a `Vec<T>`. See the [module documentation](self).
+pub struct BoundedVec<T, const N: usize> {
+    len: usize,
+    // invariant: `items[..len]` are initialized
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// Creates an empty list.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            items: [const { MaybeUninit::uninit() }; N],
+        }
+    }
+
+    /// The elements as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        // Safety: `items[..len]` are initialized
+        unsafe { &*(core::ptr::from_ref(&self.items[..self.len]) as *const [T]) }
+    }
+
+    /// The elements as a mutable slice.
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // Safety: `items[..len]` are initialized
+        unsafe { &mut *(core::ptr::from_mut(&mut self.items[..self.len]) as *mut [T]) }
+    }
+
+    /// The maximum amount of elements, which is `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if no more elements fit.
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Appends `value` to the end of the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` back if the list is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        let Some(slot) = self.items.get_mut(self.len) else {
+            return Err(value);
+        };
+        slot.write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last element and returns it, or `None` if the list is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        // Safety: the element was initialized, and is no longer part of the list
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        let elements = core::ptr::from_mut(self.as_mut_slice());
+        self.len = 0;
+        // Safety: the elements were initialized, and are no longer part of the list
+        unsafe { core::ptr::drop_in_place(elements) };
+    }
+}
+
+impl<T, const N: usize> Drop for BoundedVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for BoundedVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut vec = Self::new();
+        for value in self {
+            // the clone has the same capacity, so this can not fail
+            let _ = vec.try_push(value.clone());
+        }
+        vec
+    }
+}
+
+impl<T: Clone, const N: usize> TryFrom<&[T]> for BoundedVec<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, CapacityError> {
+        if slice.len() > N {
+            return Err(CapacityError {
+                len: slice.len(),
+                cap: N,
+            });
+        }
+        let mut vec = Self::new();
+        for value in slice {
+            let _ = vec.try_push(value.clone());
+        }
+        Ok(vec)
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for BoundedVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for BoundedVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for BoundedVec<T, N> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> BorrowMut<[T]> for BoundedVec<T, N> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a BoundedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut BoundedVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for BoundedVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<BoundedVec<U, M>>
+    for BoundedVec<T, N>
+{
+    fn eq(&self, other: &BoundedVec<U, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U]> for BoundedVec<T, N> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for BoundedVec<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for BoundedVec<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for BoundedVec<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for BoundedVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for BoundedVec<T, N> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_slice().encode(encoder)
+    }
+}
+
+impl<Context, T: Decode<Context>, const N: usize> Decode<Context> for BoundedVec<T, N> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+            // `len` is checked against `N` above, so this can not fail
+            let _ = vec.try_push(T::decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, Context, T: BorrowDecode<'de, Context>, const N: usize> BorrowDecode<'de, Context>
+    for BoundedVec<T, N>
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+            // `len` is checked against `N` above, so this can not fail
+            let _ = vec.try_push(T::borrow_decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<T: SkipDecode, const N: usize> SkipDecode for BoundedVec<T, N> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[T]>::skip(decoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> crate::layout::EncodedLayout for BoundedString<N> {
+    fn layout() -> crate::layout::Layout {
+        crate::layout::Layout::Str
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: crate::layout::EncodedLayout, const N: usize> crate::layout::EncodedLayout
+    for BoundedVec<T, N>
+{
+    fn layout() -> crate::layout::Layout {
+        crate::layout::Layout::Seq(alloc::boxed::Box::new(T::layout()))
+    }
+}
@@ -15,6 +15,8 @@
 //! ```
 //!
 //! See [Configuration] for more information on the configuration options.
+//!
+//! To decode input that may have been crafted by an attacker, start from [`untrusted`\], which turns on every option that guards against malicious input.
 
 pub(crate) use self::internal::*;
 use core::marker::PhantomData;
@@ -26,17 +28,68 @@ use core::marker::PhantomData;
 ///
 /// - [`with_little_endian`\] and [`with_big_endian`\]
 /// - [`with_fixed_int_encoding`\] and [`with_variable_int_encoding`\]
+/// - [`with_utf8_paths`\] and [`with_lossless_paths`\]
+/// - [`with_infallible_allocation`\] and [`with_fallible_allocation`\]
+/// - [`with_strict_utf8_strings`\] and [`with_lossy_utf8_strings`\]
+/// - [`with_truncated_system_time`\] and [`with_exact_system_time`\]
+/// - [`with_int_encoded_lengths`\], [`with_varint_lengths`\], [`with_fixed_u32_lengths`\] and [`with_fixed_u64_lengths`\]
+/// - [`with_any_map_order`\] and [`with_strict_map_order`\]
+/// - [`with_any_trailing_bytes`\] and [`with_no_trailing_bytes`\]
+/// - [`with_utf8_char_encoding`\] and [`with_scalar_char_encoding`\]
+/// - [`with_no_error_offsets`\] and [`with_error_offsets`\]
 ///
 ///
 /// [with_little_endian]: #method.with_little_endian
 /// [with_big_endian]: #method.with_big_endian
 /// [with_fixed_int_encoding]: #method.with_fixed_int_encoding
 /// [with_variable_int_encoding]: #method.with_variable_int_encoding
+/// [with_utf8_paths]: #method.with_utf8_paths
+/// [with_lossless_paths]: #method.with_lossless_paths
+/// [with_infallible_allocation]: #method.with_infallible_allocation
+/// [with_fallible_allocation]: #method.with_fallible_allocation
+/// [with_strict_utf8_strings]: #method.with_strict_utf8_strings
+/// [with_lossy_utf8_strings]: #method.with_lossy_utf8_strings
+/// [with_truncated_system_time]: #method.with_truncated_system_time
+/// [with_exact_system_time]: #method.with_exact_system_time
+/// [with_int_encoded_lengths]: #method.with_int_encoded_lengths
+/// [with_varint_lengths]: #method.with_varint_lengths
+/// [with_fixed_u32_lengths]: #method.with_fixed_u32_lengths
+/// [with_fixed_u64_lengths]: #method.with_fixed_u64_lengths
+/// [with_any_map_order]: #method.with_any_map_order
+/// [with_strict_map_order]: #method.with_strict_map_order
+/// [with_any_trailing_bytes]: #method.with_any_trailing_bytes
+/// [with_no_trailing_bytes]: #method.with_no_trailing_bytes
+/// [with_utf8_char_encoding]: #method.with_utf8_char_encoding
+/// [with_scalar_char_encoding]: #method.with_scalar_char_encoding
+/// [with_no_error_offsets]: #method.with_no_error_offsets
+/// [with_error_offsets]: #method.with_error_offsets
 #[derive(Copy, Clone, Debug)]
-pub struct Configuration<E = LittleEndian, I = Varint, L = NoLimit> {
+pub struct Configuration<
+    E = LittleEndian,
+    I = Varint,
+    L = NoLimit,
+    P = Utf8Paths,
+    A = InfallibleAllocation,
+    U = StrictUtf8,
+    S = TruncatedSystemTime,
+    Len = IntLengths,
+    M = AnyMapOrder,
+    Tr = AnyTrailingBytes,
+    Ch = Utf8Chars,
+    Off = NoErrorOffsets,
+> {
     _e: PhantomData<E>,
     _i: PhantomData<I>,
-    _l: PhantomData<L>,
+    limit: L,
+    _p: PhantomData<P>,
+    _a: PhantomData<A>,
+    _u: PhantomData<U>,
+    _s: PhantomData<S>,
+    _len: PhantomData<Len>,
+    _m: PhantomData<M>,
+    _tr: PhantomData<Tr>,
+    _ch: PhantomData<Ch>,
+    _off: PhantomData<Off>,
 }
 
 // When adding more features to configuration, follow these steps:
@@ -52,44 +105,136 @@ pub struct Configuration<E = LittleEndian, I = Varint, L = NoLimit> {
 /// The default config for bincode 2.0. By default this will be:
 /// - Little endian
 /// - Variable int encoding
+/// - Paths as UTF-8 strings
+/// - Aborting if memory can not be allocated
+/// - Rejecting strings that are not valid UTF-8
+/// - Truncating a `SystemTime` to the precision of the platform
+/// - Lengths encoded like a `u64` with the int encoding
+/// - Accepting the keys of a `BTreeMap` or `BTreeSet` in any order
+/// - Ignoring the bytes after a value that is decoded from a slice
+/// - Chars as their UTF-8 bytes
+/// - Errors without the offset in the input that they occurred at
 #[must_use]
 pub const fn standard() -> Configuration {
-    generate()
+    generate(NoLimit)
 }
 
 /// Creates the "legacy" default config. This is the default config that was present in bincode 1.0
 /// - Little endian
 /// - Fixed int length encoding
 #[must_use]
-pub const fn legacy() -> Configuration<LittleEndian, Fixint, NoLimit> {
-    generate()
+pub const fn legacy() -> Configuration<
+    LittleEndian,
+    Fixint,
+    NoLimit,
+    Utf8Paths,
+    InfallibleAllocation,
+    StrictUtf8,
+    TruncatedSystemTime,
+    IntLengths,
+    AnyMapOrder,
+    AnyTrailingBytes,
+    Utf8Chars,
+    NoErrorOffsets,
+> {
+    generate(NoLimit)
+}
+
+/// The recommended starting point for decoding input that may have been crafted by an attacker, like requests from the network.
+///
+/// Based on [`standard`], it turns on every option that guards against malicious input:
+/// - A byte limit of `limit` bytes, see [`with_runtime_limit`](Configuration::with_runtime_limit). Collections that claim more entries than fit in the limit are rejected before anything is allocated for them, and every level of a recursive type takes at least one byte, so the limit also bounds how deeply values can be nested.
+/// - Returning an error if memory can not be allocated, see [`with_fallible_allocation`](Configuration::with_fallible_allocation)
+/// - Rejecting strings that are not valid UTF-8, see [`with_strict_utf8_strings`](Configuration::with_strict_utf8_strings)
+/// - Rejecting the keys of a `BTreeMap` or `BTreeSet` that are duplicated or not in ascending order, see [`with_strict_map_order`](Configuration::with_strict_map_order)
+/// - Rejecting the bytes after a value that is decoded from a slice, see [`with_no_trailing_bytes`](Configuration::with_no_trailing_bytes)
+///
+/// Booleans other than `0` and `1`, invalid `char`s and unknown enum variants are rejected with every config. Options that are added later and guard against malicious input are turned on here as well.
+///
+/// The wire format is the same as that of [`standard`], as long as the encoder writes the keys of maps in order. Every option can still be changed with the methods of [`Configuration`], e.g. to accept trailing bytes when decoding several values from one slice:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+///
+/// let config = bincode::config::untrusted(1024);
+/// let encoded = bincode::encode_to_vec(vec![0u8; 2048], config).unwrap();
+/// let err = bincode::decode_from_slice::<Vec<u8>, _>(&encoded, config).unwrap_err();
+/// assert!(matches!(err.inner(), DecodeError::LimitExceeded));
+///
+/// let config = config.with_any_trailing_bytes();
+/// let (value, len): (u8, _) = bincode::decode_from_slice(&[1, 2], config).unwrap();
+/// assert_eq!((value, len), (1, 1));
+/// ```
+#[must_use]
+pub const fn untrusted(
+    limit: usize,
+) -> Configuration<
+    LittleEndian,
+    Varint,
+    RuntimeLimit,
+    Utf8Paths,
+    FallibleAllocation,
+    StrictUtf8,
+    TruncatedSystemTime,
+    IntLengths,
+    StrictMapOrder,
+    NoTrailingBytes,
+    Utf8Chars,
+    NoErrorOffsets,
+> {
+    standard()
+        .with_runtime_limit(limit)
+        .with_fallible_allocation()
+        .with_strict_utf8_strings()
+        .with_strict_map_order()
+        .with_no_trailing_bytes()
 }
 
-impl<E, I, L> Default for Configuration<E, I, L> {
+impl<E, I, L: Default, P, A, U, S, Len, M, Tr, Ch, Off> Default
+    for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+{
     fn default() -> Self {
-        generate()
+        generate(L::default())
     }
 }
 
-const fn generate<E, I, L>() -> Configuration<E, I, L> {
+const fn generate<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>(
+    limit: L,
+) -> Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off> {
     Configuration {
         _e: PhantomData,
         _i: PhantomData,
-        _l: PhantomData,
+        limit,
+        _p: PhantomData,
+        _a: PhantomData,
+        _u: PhantomData,
+        _s: PhantomData,
+        _len: PhantomData,
+        _m: PhantomData,
+        _tr: PhantomData,
+        _ch: PhantomData,
+        _off: PhantomData,
     }
 }
 
-impl<E, I, L> Configuration<E, I, L> {
+impl<E, I, L: Copy, P, A, U, S, Len, M, Tr, Ch, Off>
+    Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+{
     /// Makes bincode encode all integer types in big endian.
     #[must_use]
-    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, L> {
-        generate()
+    pub const fn with_big_endian(
+        self,
+    ) -> Configuration<BigEndian, I, L, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
     }
 
     /// Makes bincode encode all integer types in little endian.
     #[must_use]
-    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, L> {
-        generate()
+    pub const fn with_little_endian(
+        self,
+    ) -> Configuration<LittleEndian, I, L, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
     }
 
     /// Makes bincode encode all integer types with a variable integer encoding.
@@ -149,8 +294,10 @@ impl<E, I, L> Configuration<E, I, L> {
     /// Note that u256 and the like are unsupported by this format; if and when they are added to the
     /// language, they may be supported via the extension point given by the 255 byte.
     #[must_use]
-    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, L> {
-        generate()
+    pub const fn with_variable_int_encoding(
+        self,
+    ) -> Configuration<E, Varint, L, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
     }
 
     /// Fixed-size integer encoding.
@@ -158,27 +305,325 @@ impl<E, I, L> Configuration<E, I, L> {
     /// * Fixed size integers are encoded directly
     /// * Enum discriminants are encoded as u32
     /// * Lengths and usize are encoded as u64
+    ///
+    /// Lengths can be encoded differently with [`with_fixed_u32_lengths`](Self::with_fixed_u32_lengths) and the like.
     #[must_use]
-    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, L> {
-        generate()
+    pub const fn with_fixed_int_encoding(
+        self,
+    ) -> Configuration<E, Fixint, L, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
     }
 
     /// Sets the byte limit to `limit`.
     #[must_use]
-    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, Limit<N>> {
-        generate()
+    pub const fn with_limit<const N: usize>(
+        self,
+    ) -> Configuration<E, I, Limit<N>, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(Limit)
+    }
+
+    /// Sets the byte limit to `limit`, which is only known at runtime, e.g. from a command line flag.
+    ///
+    /// This behaves exactly like [`with_limit`](Self::with_limit), but the limit is stored in the configuration instead of its type, so every limit uses the same configuration type. Checking it costs a comparison with the stored value instead of a constant.
+    #[must_use]
+    pub const fn with_runtime_limit(
+        self,
+        limit: usize,
+    ) -> Configuration<E, I, RuntimeLimit, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(RuntimeLimit(limit))
     }
 
     /// Clear the byte limit.
     #[must_use]
-    pub const fn with_no_limit(self) -> Configuration<E, I, NoLimit> {
-        generate()
+    pub const fn with_no_limit(
+        self,
+    ) -> Configuration<E, I, NoLimit, P, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(NoLimit)
+    }
+
+    /// Encode `Path` and `PathBuf` as UTF-8 strings. This is the default.
+    ///
+    /// Paths that are not valid UTF-8 can not be encoded, and fail with [`EncodeError::InvalidPathCharacters`](crate::error::EncodeError::InvalidPathCharacters).
+    #[must_use]
+    pub const fn with_utf8_paths(
+        self,
+    ) -> Configuration<E, I, L, Utf8Paths, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode `Path` and `PathBuf` losslessly, so every path of the platform can be encoded.
+    ///
+    /// A path is encoded as a `u32` tag followed by its data:
+    /// - `0`: the path as a UTF-8 string, if it is valid UTF-8
+    /// - `1`: the bytes of the path on Unix, from `OsStrExt::as_bytes`, encoded like a `Vec<u8>`
+    /// - `2`: the UTF-16 code units of the path on Windows, from `OsStrExt::encode_wide`, encoded like a `Vec<u16>`
+    ///
+    /// Paths that are valid UTF-8 can be decoded on every platform. The other forms can only be decoded on the platform that encoded them, and fail with [`DecodeError::ForeignPath`](crate::error::DecodeError::ForeignPath) elsewhere, which holds the raw data of the path.
+    ///
+    /// This is a different wire format than [`with_utf8_paths`](Self::with_utf8_paths), even for paths that are valid UTF-8.
+    #[must_use]
+    pub const fn with_lossless_paths(
+        self,
+    ) -> Configuration<E, I, L, LosslessPaths, A, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Abort the process if memory for a decoded value can not be allocated, like the standard library does. This is the default.
+    #[must_use]
+    pub const fn with_infallible_allocation(
+        self,
+    ) -> Configuration<E, I, L, P, InfallibleAllocation, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Return [`DecodeError::AllocationFailed`](crate::error::DecodeError::AllocationFailed) if memory for a decoded value can not be allocated, instead of aborting the process.
+    ///
+    /// The memory is reserved with `try_reserve` for `Vec`, `String`, `HashMap`, `HashSet` and the types that are decoded through them, e.g. `Box<[T]>`, `VecDeque` and `BinaryHeap`. Containers still grow by doubling their capacity. Other allocations, e.g. for `Box<T>` or the nodes of a `BTreeMap`, still abort if they fail.
+    ///
+    /// This does not change the wire format.
+    #[must_use]
+    pub const fn with_fallible_allocation(
+        self,
+    ) -> Configuration<E, I, L, P, FallibleAllocation, U, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Reject strings that are not valid UTF-8 with [`DecodeError::Utf8`](crate::error::DecodeError::Utf8). This is the default.
+    #[must_use]
+    pub const fn with_strict_utf8_strings(
+        self,
+    ) -> Configuration<E, I, L, P, A, StrictUtf8, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Decode owned strings that are not valid UTF-8 with `String::from_utf8_lossy`, which replaces every invalid sequence with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// This applies to `String`, `Box<str>`, `Rc<str>`, `Arc<str>` and `Cow<str>` when it is decoded as an owned string. A borrowed `&str` can not be modified, so it still fails with [`DecodeError::Utf8`](crate::error::DecodeError::Utf8), as does a `Cow<str>` that is borrow-decoded. Fixed-capacity strings (e.g. from `arrayvec` or `heapless`) also still fail, as the replacement characters may not fit.
+    ///
+    /// This does not change the wire format.
+    #[must_use]
+    pub const fn with_lossy_utf8_strings(
+        self,
+    ) -> Configuration<E, I, L, P, A, LossyUtf8, S, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Decode a `SystemTime` that is more precise than the platform supports by truncating it to the precision of the platform. This is the default.
+    ///
+    /// A `SystemTime` is encoded as the seconds (like a `u64`) and nanoseconds (like a `u32`) since `SystemTime::UNIX_EPOCH`, on every platform. Unix has a precision of 1 nanosecond, but Windows only has a precision of 100 nanoseconds, so a time that was encoded on Unix may lose its last digits when it is decoded on Windows. The encoded data itself always keeps the full precision.
+    ///
+    /// Times that are out of the range of the platform fail with [`DecodeError::InvalidSystemTime`](crate::error::DecodeError::InvalidSystemTime) either way.
+    #[must_use]
+    pub const fn with_truncated_system_time(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, TruncatedSystemTime, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Reject a `SystemTime` that is more precise than the platform supports with [`DecodeError::InvalidSystemTime`](crate::error::DecodeError::InvalidSystemTime), instead of truncating it. See [`with_truncated_system_time`](Self::with_truncated_system_time).
+    ///
+    /// With this, a decoded `SystemTime` is always exactly the time that was encoded, e.g. on Windows, a time with nanoseconds that are not a multiple of 100 is rejected.
+    ///
+    /// This does not change the wire format.
+    #[must_use]
+    pub const fn with_exact_system_time(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, ExactSystemTime, Len, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode the length of strings, sequences and maps like a `u64` with the int encoding of the config. This is the default.
+    #[must_use]
+    pub const fn with_int_encoded_lengths(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, IntLengths, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode the length of strings, sequences and maps as a varint of a `u64`, regardless of the int encoding of the config.
+    ///
+    /// With [`with_fixed_int_encoding`](Self::with_fixed_int_encoding), this keeps the lengths small while the other integers have a fixed size.
+    #[must_use]
+    pub const fn with_varint_lengths(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, VarintLengths, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode the length of strings, sequences and maps as 4 bytes of a `u32` in the endianness of the config, regardless of the int encoding of the config.
+    ///
+    /// This matches readers that expect a fixed `u32` in front of every collection. Encoding a collection with more than `u32::MAX` entries fails with [`EncodeError::LengthTooLarge`](crate::error::EncodeError::LengthTooLarge).
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// let config = bincode::config::standard().with_fixed_u32_lengths();
+    /// let encoded = bincode::encode_to_vec((vec![1u16, 300], 300u16), config).unwrap();
+    /// assert_eq!(encoded, [2, 0, 0, 0, 1, 251, 44, 1, 251, 44, 1]);
+    /// ```
+    #[must_use]
+    pub const fn with_fixed_u32_lengths(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, FixedU32Lengths, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode the length of strings, sequences and maps as 8 bytes of a `u64` in the endianness of the config, regardless of the int encoding of the config.
+    #[must_use]
+    pub const fn with_fixed_u64_lengths(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, FixedU64Lengths, M, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Accept the keys of a `BTreeMap` or `BTreeSet` in any order when decoding. A key that is decoded twice replaces the entry of the key before it. This is the default.
+    ///
+    /// Keys that are in strictly ascending order, like `BTreeMap` and `BTreeSet` encode them, are collected into the tree in one go, without searching the tree for every key.
+    #[must_use]
+    pub const fn with_any_map_order(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, AnyMapOrder, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Reject a `BTreeMap` or `BTreeSet` whose keys are not in strictly ascending order with [`DecodeError::NonCanonicalMapOrder`](crate::error::DecodeError::NonCanonicalMapOrder), so every map has only one encoding. This also rejects duplicate keys.
+    ///
+    /// `HashMap` and `HashSet` have no order, and are still accepted in any order.
+    ///
+    /// This does not change the wire format.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::error::DecodeError;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let config = bincode::config::standard().with_strict_map_order();
+    /// let encoded = bincode::encode_to_vec(vec![1u8, 3, 2], config).unwrap();
+    /// let err = bincode::decode_from_slice::<BTreeSet<u8>, _>(&encoded, config).unwrap_err();
+    /// assert!(matches!(err.inner(), DecodeError::NonCanonicalMapOrder { index: 2 }));
+    /// ```
+    #[must_use]
+    pub const fn with_strict_map_order(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, StrictMapOrder, Tr, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Accept bytes after a value that is decoded from a slice. The amount of bytes that the value was decoded from is returned with it, so the caller can decode the next value from the rest of the slice. This is the default.
+    #[must_use]
+    pub const fn with_any_trailing_bytes(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, AnyTrailingBytes, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Reject bytes after a value that is decoded from a slice with [`DecodeError::TrailingBytes`](crate::error::DecodeError::TrailingBytes), so a message can not carry data that the decoded value does not account for.
+    ///
+    /// This applies to [`decode_from_slice`](crate::decode_from_slice), [`borrow_decode_from_slice`](crate::borrow_decode_from_slice), [`borrow_decode_from_slice_mut`](crate::borrow_decode_from_slice_mut), [`decode_from_segments`](crate::decode_from_segments) and their `_with_context` variants. A [`Reader`](crate::de::read::Reader) does not know whether more bytes follow, so the other functions do not check it.
+    ///
+    /// This does not change the wire format.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::error::DecodeError;
+    ///
+    /// let config = bincode::config::standard().with_no_trailing_bytes();
+    /// let err = bincode::decode_from_slice::<u8, _>(&[1, 2, 3], config).unwrap_err();
+    /// assert!(matches!(err.inner(), DecodeError::TrailingBytes { remaining: 2 }));
+    /// ```
+    #[must_use]
+    pub const fn with_no_trailing_bytes(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, NoTrailingBytes, Ch, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode a `char` as the UTF-8 encoding of its Unicode scalar value, which is 1 to 4 bytes long. This is the default.
+    ///
+    /// The first byte tells how many bytes follow, so no length is written. Bytes that are not the UTF-8 encoding of a `char` fail with [`DecodeError::InvalidCharEncoding`](crate::error::DecodeError::InvalidCharEncoding), except for well-formed sequences of a surrogate or a value above `char::MAX`, which fail with [`DecodeError::InvalidCharValue`](crate::error::DecodeError::InvalidCharValue).
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// let config = bincode::config::standard().with_utf8_char_encoding();
+    /// assert_eq!(bincode::encode_to_vec('a', config).unwrap(), [0x61]);
+    /// assert_eq!(bincode::encode_to_vec('é', config).unwrap(), [0xC3, 0xA9]);
+    /// ```
+    #[must_use]
+    pub const fn with_utf8_char_encoding(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, Tr, Utf8Chars, Off> {
+        generate(self.limit)
+    }
+
+    /// Encode a `char` as its Unicode scalar value in 4 bytes of a `u32` in the endianness of the config, regardless of the int encoding of the config.
+    ///
+    /// Every `char` takes the same amount of bytes, so a collection of them can be skipped without decoding them. A value that is not a Unicode scalar value, like a surrogate or a value above `char::MAX`, fails with [`DecodeError::InvalidCharValue`](crate::error::DecodeError::InvalidCharValue).
+    ///
+    /// This is a different wire format than [`with_utf8_char_encoding`](Self::with_utf8_char_encoding), even for ASCII.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::error::DecodeError;
+    ///
+    /// let config = bincode::config::standard().with_scalar_char_encoding();
+    /// assert_eq!(bincode::encode_to_vec('é', config).unwrap(), [0xE9, 0, 0, 0]);
+    ///
+    /// let err = bincode::decode_from_slice::<char, _>(&[0, 0xD8, 0, 0], config).unwrap_err();
+    /// assert!(matches!(err.inner(), DecodeError::InvalidCharValue { found: 0xD800 }));
+    /// ```
+    #[must_use]
+    pub const fn with_scalar_char_encoding(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, Tr, ScalarChars, Off> {
+        generate(self.limit)
+    }
+
+    /// Return decode errors as they are, without the offset in the input that they occurred at. This is the default.
+    #[must_use]
+    pub const fn with_no_error_offsets(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, NoErrorOffsets> {
+        generate(self.limit)
+    }
+
+    /// Wrap decode errors in [`DecodeError::WithOffset`](crate::error::DecodeError::WithOffset) with the amount of bytes that were read from the input when the error occurred, like the `_with_offset` functions do, e.g. [`decode_from_slice_with_offset`](crate::decode_from_slice_with_offset).
+    ///
+    /// This applies to [`decode_from_slice`](crate::decode_from_slice), [`borrow_decode_from_slice`](crate::borrow_decode_from_slice), [`borrow_decode_from_slice_mut`](crate::borrow_decode_from_slice_mut), [`decode_extend_from_slice`](crate::decode_extend_from_slice), [`decode_from_segments`](crate::decode_from_segments), [`decode_checking_config_header`](crate::decode_checking_config_header), [`decode_from_reader`](crate::decode_from_reader), `decode_from_std_read`, `decode_from_std_read_take`, `decode_borrowed_from_buf_read`, `decode_from_bytes` and their `_with_context` variants. Errors of the `serde` functions and of framed formats like COBS are returned as they are. It does nothing without the `alloc` feature, because the wrapped error is boxed.
+    ///
+    /// This does not change the wire format.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::error::DecodeError;
+    ///
+    /// let config = bincode::config::standard().with_error_offsets();
+    /// let err = bincode::decode_from_slice::<(u8, bool), _>(&[1, 2], config).unwrap_err();
+    /// assert_eq!(err.offset(), Some(2));
+    /// assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(2)));
+    /// ```
+    #[must_use]
+    pub const fn with_error_offsets(
+        self,
+    ) -> Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, ReportErrorOffsets> {
+        generate(self.limit)
     }
 }
 
 /// Indicates a type is valid for controlling the bincode configuration
 pub trait Config:
-    InternalEndianConfig + InternalIntEncodingConfig + InternalLimitConfig + Copy + Clone
+    InternalEndianConfig
+    + InternalIntEncodingConfig
+    + InternalLimitConfig
+    + InternalPathEncodingConfig
+    + InternalAllocationConfig
+    + InternalUtf8Config
+    + InternalSystemTimeConfig
+    + InternalLengthEncodingConfig
+    + InternalMapOrderConfig
+    + InternalTrailingBytesConfig
+    + InternalCharEncodingConfig
+    + InternalErrorOffsetsConfig
+    + Copy
+    + Clone
 {
     /// This configuration's Endianness
     fn endianness(&self) -> Endianness;
@@ -188,11 +633,66 @@ pub trait Config:
 
     /// This configuration's byte limit, or `None` if no limit is configured
     fn limit(&self) -> Option<usize>;
+
+    /// This configuration's encoding of paths
+    fn path_encoding(&self) -> PathEncoding;
+
+    /// This configuration's handling of allocation failures
+    fn allocation(&self) -> Allocation;
+
+    /// This configuration's handling of strings that are not valid UTF-8
+    fn utf8_decoding(&self) -> Utf8Decoding;
+
+    /// This configuration's handling of a `SystemTime` that is more precise than the platform supports
+    fn system_time_decoding(&self) -> SystemTimeDecoding;
+
+    /// This configuration's encoding of the length of strings, sequences and maps
+    fn length_encoding(&self) -> LengthEncoding;
+
+    /// This configuration's handling of the order of the keys of a `BTreeMap` or `BTreeSet`
+    fn map_order(&self) -> MapOrder;
+
+    /// This configuration's handling of the bytes after a value that is decoded from a slice
+    fn trailing_bytes(&self) -> TrailingBytes;
+
+    /// This configuration's encoding of `char`
+    fn char_encoding(&self) -> CharEncoding;
+
+    /// Whether this configuration reports the offset of decode errors
+    fn error_offsets(&self) -> ErrorOffsets;
+
+    /// A compact descriptor of this configuration, see [`FormatDescription::wire_id`](crate::spec::FormatDescription::wire_id)
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::config::{self, Config};
+    ///
+    /// assert_eq!(config::standard().wire_id(), [1, 0, 0, 0]);
+    /// assert_eq!(config::legacy().with_big_endian().wire_id(), [1, 0b11, 0, 0]);
+    /// assert_ne!(config::standard().wire_id(), config::legacy().wire_id());
+    /// ```
+    #[must_use]
+    fn wire_id(&self) -> [u8; 4] {
+        crate::spec::describe(*self).wire_id()
+    }
 }
 
 impl<T> Config for T
 where
-    T: InternalEndianConfig + InternalIntEncodingConfig + InternalLimitConfig + Copy + Clone,
+    T: InternalEndianConfig
+        + InternalIntEncodingConfig
+        + InternalLimitConfig
+        + InternalPathEncodingConfig
+        + InternalAllocationConfig
+        + InternalUtf8Config
+        + InternalSystemTimeConfig
+        + InternalLengthEncodingConfig
+        + InternalMapOrderConfig
+        + InternalTrailingBytesConfig
+        + InternalCharEncodingConfig
+        + InternalErrorOffsetsConfig
+        + Copy
+        + Clone,
 {
     fn endianness(&self) -> Endianness {
         <T as InternalEndianConfig>::ENDIAN
@@ -203,7 +703,43 @@ where
     }
 
     fn limit(&self) -> Option<usize> {
-        <T as InternalLimitConfig>::LIMIT
+        <T as InternalLimitConfig>::byte_limit(self)
+    }
+
+    fn path_encoding(&self) -> PathEncoding {
+        <T as InternalPathEncodingConfig>::PATH_ENCODING
+    }
+
+    fn allocation(&self) -> Allocation {
+        <T as InternalAllocationConfig>::ALLOCATION
+    }
+
+    fn utf8_decoding(&self) -> Utf8Decoding {
+        <T as InternalUtf8Config>::UTF8_DECODING
+    }
+
+    fn system_time_decoding(&self) -> SystemTimeDecoding {
+        <T as InternalSystemTimeConfig>::SYSTEM_TIME_DECODING
+    }
+
+    fn length_encoding(&self) -> LengthEncoding {
+        <T as InternalLengthEncodingConfig>::LENGTH_ENCODING
+    }
+
+    fn map_order(&self) -> MapOrder {
+        <T as InternalMapOrderConfig>::MAP_ORDER
+    }
+
+    fn trailing_bytes(&self) -> TrailingBytes {
+        <T as InternalTrailingBytesConfig>::TRAILING_BYTES
+    }
+
+    fn char_encoding(&self) -> CharEncoding {
+        <T as InternalCharEncodingConfig>::CHAR_ENCODING
+    }
+
+    fn error_offsets(&self) -> ErrorOffsets {
+        <T as InternalErrorOffsetsConfig>::ERROR_OFFSETS
     }
 }
 
@@ -240,17 +776,183 @@ impl InternalIntEncodingConfig for Varint {
 }
 
 /// Sets an unlimited byte limit.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub struct NoLimit;
 impl InternalLimitConfig for NoLimit {
-    const LIMIT: Option<usize> = None;
+    const LIMITED: bool = false;
+
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Sets the byte limit to N.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub struct Limit<const N: usize>;
 impl<const N: usize> InternalLimitConfig for Limit<N> {
-    const LIMIT: Option<usize> = Some(N);
+    const LIMITED: bool = true;
+
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        Some(N)
+    }
+}
+
+/// Sets the byte limit to a value that is only known at runtime, see [`Configuration::with_runtime_limit`].
+#[derive(Copy, Clone, Debug)]
+pub struct RuntimeLimit(usize);
+impl InternalLimitConfig for RuntimeLimit {
+    const LIMITED: bool = true;
+
+    #[inline]
+    fn byte_limit(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+/// Encodes paths as UTF-8 strings.
+#[derive(Copy, Clone, Debug)]
+pub struct Utf8Paths;
+impl InternalPathEncodingConfig for Utf8Paths {
+    const PATH_ENCODING: PathEncoding = PathEncoding::Utf8;
+}
+
+/// Encodes paths losslessly, see [`Configuration::with_lossless_paths`].
+#[derive(Copy, Clone, Debug)]
+pub struct LosslessPaths;
+impl InternalPathEncodingConfig for LosslessPaths {
+    const PATH_ENCODING: PathEncoding = PathEncoding::Lossless;
+}
+
+/// Aborts if memory can not be allocated.
+#[derive(Copy, Clone, Debug)]
+pub struct InfallibleAllocation;
+impl InternalAllocationConfig for InfallibleAllocation {
+    const ALLOCATION: Allocation = Allocation::Infallible;
+}
+
+/// Returns an error if memory can not be allocated, see [`Configuration::with_fallible_allocation`].
+#[derive(Copy, Clone, Debug)]
+pub struct FallibleAllocation;
+impl InternalAllocationConfig for FallibleAllocation {
+    const ALLOCATION: Allocation = Allocation::Fallible;
+}
+
+/// Rejects strings that are not valid UTF-8.
+#[derive(Copy, Clone, Debug)]
+pub struct StrictUtf8;
+impl InternalUtf8Config for StrictUtf8 {
+    const UTF8_DECODING: Utf8Decoding = Utf8Decoding::Strict;
+}
+
+/// Replaces invalid UTF-8 in owned strings, see [`Configuration::with_lossy_utf8_strings`].
+#[derive(Copy, Clone, Debug)]
+pub struct LossyUtf8;
+impl InternalUtf8Config for LossyUtf8 {
+    const UTF8_DECODING: Utf8Decoding = Utf8Decoding::Lossy;
+}
+
+/// Truncates a `SystemTime` to the precision of the platform, see [`Configuration::with_truncated_system_time`].
+#[derive(Copy, Clone, Debug)]
+pub struct TruncatedSystemTime;
+impl InternalSystemTimeConfig for TruncatedSystemTime {
+    const SYSTEM_TIME_DECODING: SystemTimeDecoding = SystemTimeDecoding::Truncate;
+}
+
+/// Rejects a `SystemTime` that is more precise than the platform supports, see [`Configuration::with_exact_system_time`].
+#[derive(Copy, Clone, Debug)]
+pub struct ExactSystemTime;
+impl InternalSystemTimeConfig for ExactSystemTime {
+    const SYSTEM_TIME_DECODING: SystemTimeDecoding = SystemTimeDecoding::Exact;
+}
+
+/// Encodes lengths like a `u64` with the int encoding of the config.
+#[derive(Copy, Clone, Debug)]
+pub struct IntLengths;
+impl InternalLengthEncodingConfig for IntLengths {
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::Int;
+}
+
+/// Encodes lengths as a varint, see [`Configuration::with_varint_lengths`].
+#[derive(Copy, Clone, Debug)]
+pub struct VarintLengths;
+impl InternalLengthEncodingConfig for VarintLengths {
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::Varint;
+}
+
+/// Encodes lengths as a fixed `u32`, see [`Configuration::with_fixed_u32_lengths`].
+#[derive(Copy, Clone, Debug)]
+pub struct FixedU32Lengths;
+impl InternalLengthEncodingConfig for FixedU32Lengths {
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::FixedU32;
+}
+
+/// Encodes lengths as a fixed `u64`, see [`Configuration::with_fixed_u64_lengths`].
+#[derive(Copy, Clone, Debug)]
+pub struct FixedU64Lengths;
+impl InternalLengthEncodingConfig for FixedU64Lengths {
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::FixedU64;
+}
+
+/// Accepts the keys of a `BTreeMap` or `BTreeSet` in any order.
+#[derive(Copy, Clone, Debug)]
+pub struct AnyMapOrder;
+impl InternalMapOrderConfig for AnyMapOrder {
+    const MAP_ORDER: MapOrder = MapOrder::Any;
+}
+
+/// Rejects the keys of a `BTreeMap` or `BTreeSet` that are not in strictly ascending order, see [`Configuration::with_strict_map_order`].
+#[derive(Copy, Clone, Debug)]
+pub struct StrictMapOrder;
+impl InternalMapOrderConfig for StrictMapOrder {
+    const MAP_ORDER: MapOrder = MapOrder::Strict;
+}
+
+/// Accepts bytes after a value that is decoded from a slice.
+#[derive(Copy, Clone, Debug)]
+pub struct AnyTrailingBytes;
+impl InternalTrailingBytesConfig for AnyTrailingBytes {
+    const TRAILING_BYTES: TrailingBytes = TrailingBytes::Any;
+}
+
+/// Rejects bytes after a value that is decoded from a slice, see [`Configuration::with_no_trailing_bytes`].
+#[derive(Copy, Clone, Debug)]
+pub struct NoTrailingBytes;
+impl InternalTrailingBytesConfig for NoTrailingBytes {
+    const TRAILING_BYTES: TrailingBytes = TrailingBytes::Reject;
+}
+
+/// Encodes a `char` as its UTF-8 bytes, see [`Configuration::with_utf8_char_encoding`].
+#[derive(Copy, Clone, Debug)]
+pub struct Utf8Chars;
+
+impl InternalCharEncodingConfig for Utf8Chars {
+    const CHAR_ENCODING: CharEncoding = CharEncoding::Utf8;
+}
+
+/// Encodes a `char` as 4 bytes of its Unicode scalar value, see [`Configuration::with_scalar_char_encoding`].
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarChars;
+
+impl InternalCharEncodingConfig for ScalarChars {
+    const CHAR_ENCODING: CharEncoding = CharEncoding::Scalar;
+}
+
+/// Returns decode errors without their offset in the input.
+#[derive(Copy, Clone, Debug)]
+pub struct NoErrorOffsets;
+
+impl InternalErrorOffsetsConfig for NoErrorOffsets {
+    const ERROR_OFFSETS: ErrorOffsets = ErrorOffsets::Omit;
+}
+
+/// Wraps decode errors with their offset in the input, see [`Configuration::with_error_offsets`].
+#[derive(Copy, Clone, Debug)]
+pub struct ReportErrorOffsets;
+
+impl InternalErrorOffsetsConfig for ReportErrorOffsets {
+    const ERROR_OFFSETS: ErrorOffsets = ErrorOffsets::Report;
 }
 
 /// Endianness of a `Configuration`.
@@ -273,14 +975,231 @@ pub enum IntEncoding {
     Variable,
 }
 
+/// Path Encoding of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathEncoding {
+    /// Paths as UTF-8 strings, see `Utf8Paths`.
+    Utf8,
+    /// Paths as UTF-8 strings or their platform-specific data, see `LosslessPaths`.
+    Lossless,
+}
+
+/// Allocation failure handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Allocation {
+    /// Abort if memory can not be allocated, see `InfallibleAllocation`.
+    Infallible,
+    /// Return an error if memory can not be allocated, see `FallibleAllocation`.
+    Fallible,
+}
+
+/// UTF-8 handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Utf8Decoding {
+    /// Reject strings that are not valid UTF-8, see `StrictUtf8`.
+    Strict,
+    /// Replace invalid UTF-8 in owned strings, see `LossyUtf8`.
+    Lossy,
+}
+
+/// `SystemTime` handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SystemTimeDecoding {
+    /// Truncate a `SystemTime` to the precision of the platform, see `TruncatedSystemTime`.
+    Truncate,
+    /// Reject a `SystemTime` that is more precise than the platform supports, see `ExactSystemTime`.
+    Exact,
+}
+
+/// Length encoding of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthEncoding {
+    /// Like a `u64` with the int encoding of the config, see `IntLengths`.
+    Int,
+    /// A varint of a `u64`, see `VarintLengths`.
+    Varint,
+    /// 4 bytes of a `u32`, see `FixedU32Lengths`.
+    FixedU32,
+    /// 8 bytes of a `u64`, see `FixedU64Lengths`.
+    FixedU64,
+}
+
+/// Key order handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MapOrder {
+    /// Accept keys in any order, see `AnyMapOrder`.
+    Any,
+    /// Reject keys that are not in strictly ascending order, see `StrictMapOrder`.
+    Strict,
+}
+
+/// Trailing byte handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrailingBytes {
+    /// Accept bytes after the value, see `AnyTrailingBytes`.
+    Any,
+    /// Reject bytes after the value, see `NoTrailingBytes`.
+    Reject,
+}
+
+/// `char` encoding of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CharEncoding {
+    /// 1 to 4 bytes of UTF-8, see `Utf8Chars`.
+    Utf8,
+    /// 4 bytes of a `u32`, see `ScalarChars`.
+    Scalar,
+}
+
+/// Error offset handling of a `Configuration`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorOffsets {
+    /// Return errors as they are, see `NoErrorOffsets`.
+    Omit,
+    /// Wrap errors with their offset in the input, see `ReportErrorOffsets`.
+    Report,
+}
+
+/// How a derived enum writes the tag of its variant, chosen with `#[bincode(tag_type = "...")]` on the enum.
+///
+/// Without the attribute, the tag is encoded like a `u32` with the int encoding of the config. With it, the tag has the same width with every config:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(tag_type = "u8")]
+/// enum Op {
+///     Push(u8),
+///     Pop,
+/// }
+///
+/// let encoded = bincode::encode_to_vec(Op::Push(7), bincode::config::legacy()).unwrap();
+/// assert_eq!(encoded, [0, 7]);
+/// ```
+///
+/// The derive fails to compile if the enum has more variants than the tag type can count, like this enum with 257 variants:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode)]
+/// #[bincode(tag_type = "u8")]
+/// enum TooMany {
+/// #     V0, V1, V2, V3, V4, V5, V6, V7, V8, V9, V10, V11, V12, V13, V14, V15,
+/// #     V16, V17, V18, V19, V20, V21, V22, V23, V24, V25, V26, V27, V28, V29, V30, V31,
+/// #     V32, V33, V34, V35, V36, V37, V38, V39, V40, V41, V42, V43, V44, V45, V46, V47,
+/// #     V48, V49, V50, V51, V52, V53, V54, V55, V56, V57, V58, V59, V60, V61, V62, V63,
+/// #     V64, V65, V66, V67, V68, V69, V70, V71, V72, V73, V74, V75, V76, V77, V78, V79,
+/// #     V80, V81, V82, V83, V84, V85, V86, V87, V88, V89, V90, V91, V92, V93, V94, V95,
+/// #     V96, V97, V98, V99, V100, V101, V102, V103, V104, V105, V106, V107, V108, V109, V110, V111,
+/// #     V112, V113, V114, V115, V116, V117, V118, V119, V120, V121, V122, V123, V124, V125, V126, V127,
+/// #     V128, V129, V130, V131, V132, V133, V134, V135, V136, V137, V138, V139, V140, V141, V142, V143,
+/// #     V144, V145, V146, V147, V148, V149, V150, V151, V152, V153, V154, V155, V156, V157, V158, V159,
+/// #     V160, V161, V162, V163, V164, V165, V166, V167, V168, V169, V170, V171, V172, V173, V174, V175,
+/// #     V176, V177, V178, V179, V180, V181, V182, V183, V184, V185, V186, V187, V188, V189, V190, V191,
+/// #     V192, V193, V194, V195, V196, V197, V198, V199, V200, V201, V202, V203, V204, V205, V206, V207,
+/// #     V208, V209, V210, V211, V212, V213, V214, V215, V216, V217, V218, V219, V220, V221, V222, V223,
+/// #     V224, V225, V226, V227, V228, V229, V230, V231, V232, V233, V234, V235, V236, V237, V238, V239,
+/// #     V240, V241, V242, V243, V244, V245, V246, V247, V248, V249, V250, V251, V252, V253, V254, V255,
+/// #     V256,
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TagType {
+    /// A single byte, for enums with up to 256 variants.
+    U8,
+    /// 2 bytes in the endianness of the config, for enums with up to 65536 variants.
+    U16,
+    /// 4 bytes in the endianness of the config.
+    U32,
+    /// A varint of a `u32`, like the tag is encoded by default with [`Configuration::with_variable_int_encoding`], regardless of the int encoding of the config.
+    Varint,
+}
+
+/// The padding of a field with `#[bincode(len = N)]`, which is encoded as exactly `N` bytes without a length in front of them.
+///
+/// Set it with `#[bincode(len = N, pad = "zero")]`, `pad = "space"` or `pad = "none"`; the default is `"zero"`.
+///
+/// A shorter value is padded with the padding byte when it is encoded, and the padding bytes at the end are stripped again when it is decoded. Values with `FixedLenPadding::None` must be exactly `N` bytes, and keep all `N` bytes when they are decoded.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// struct Entry {
+///     #[bincode(len = 4)]
+///     tag: Vec<u8>,
+///     #[bincode(len = 8, pad = "space")]
+///     name: String,
+///     #[bincode(len = 2, pad = "none")]
+///     kind: String,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let entry = Entry { tag: vec![1, 2], name: String::from("ab"), kind: String::from("xy") };
+/// let encoded = bincode::encode_to_vec(&entry, config).unwrap();
+/// assert_eq!(encoded, *b"\x01\x02\0\0ab      xy");
+///
+/// let (decoded, _): (Entry, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(decoded, entry);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixedLenPadding {
+    /// No padding: the value must be exactly `N` bytes.
+    None,
+    /// Padded with `0` bytes.
+    Zero,
+    /// Padded with ASCII spaces, `b' '`.
+    Space,
+}
+
+impl FixedLenPadding {
+    /// The byte that a value is padded with, if it can be padded.
+    #[must_use]
+    pub const fn byte(self) -> Option<u8> {
+        match self {
+            Self::None => None,
+            Self::Zero => Some(0),
+            Self::Space => Some(b' '),
+        }
+    }
+
+    /// Strips the padding bytes from the end of `bytes`.
+    #[must_use]
+    pub fn trim(self, bytes: &[u8]) -> &[u8] {
+        self.byte().map_or(bytes, |pad| {
+            let len = bytes
+                .iter()
+                .rposition(|&byte| byte != pad)
+                .map_or(0, |i| i + 1);
+            &bytes[..len]
+        })
+    }
+}
+
 mod internal {
-    use super::{Configuration, Endianness, IntEncoding};
+    use super::{
+        Allocation, CharEncoding, Configuration, Endianness, ErrorOffsets, IntEncoding,
+        LengthEncoding, MapOrder, PathEncoding, SystemTimeDecoding, TrailingBytes, Utf8Decoding,
+    };
+    use core::marker::PhantomData;
 
     pub trait InternalEndianConfig {
         const ENDIAN: Endianness;
     }
 
-    impl<E: InternalEndianConfig, I, L> InternalEndianConfig for Configuration<E, I, L> {
+    impl<E: InternalEndianConfig, I, L, P, A, U, S, Len, M, Tr, Ch, Off> InternalEndianConfig
+        for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
         const ENDIAN: Endianness = E::ENDIAN;
     }
 
@@ -288,15 +1207,186 @@ mod internal {
         const INT_ENCODING: IntEncoding;
     }
 
-    impl<E, I: InternalIntEncodingConfig, L> InternalIntEncodingConfig for Configuration<E, I, L> {
+    impl<E, I: InternalIntEncodingConfig, L, P, A, U, S, Len, M, Tr, Ch, Off>
+        InternalIntEncodingConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
         const INT_ENCODING: IntEncoding = I::INT_ENCODING;
     }
 
     pub trait InternalLimitConfig {
-        const LIMIT: Option<usize>;
+        /// Whether there is a limit at all. This is a constant, so the bytes that are read are not counted without a limit.
+        const LIMITED: bool;
+
+        fn byte_limit(&self) -> Option<usize>;
+    }
+
+    impl<E, I, L: InternalLimitConfig, P, A, U, S, Len, M, Tr, Ch, Off> InternalLimitConfig
+        for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const LIMITED: bool = L::LIMITED;
+
+        #[inline]
+        fn byte_limit(&self) -> Option<usize> {
+            self.limit.byte_limit()
+        }
+    }
+
+    pub trait InternalPathEncodingConfig {
+        const PATH_ENCODING: PathEncoding;
+    }
+
+    impl<E, I, L, P: InternalPathEncodingConfig, A, U, S, Len, M, Tr, Ch, Off>
+        InternalPathEncodingConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const PATH_ENCODING: PathEncoding = P::PATH_ENCODING;
+    }
+
+    pub trait InternalAllocationConfig {
+        const ALLOCATION: Allocation;
+    }
+
+    impl<E, I, L, P, A: InternalAllocationConfig, U, S, Len, M, Tr, Ch, Off>
+        InternalAllocationConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const ALLOCATION: Allocation = A::ALLOCATION;
+    }
+
+    pub trait InternalUtf8Config {
+        const UTF8_DECODING: Utf8Decoding;
+    }
+
+    impl<E, I, L, P, A, U: InternalUtf8Config, S, Len, M, Tr, Ch, Off> InternalUtf8Config
+        for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const UTF8_DECODING: Utf8Decoding = U::UTF8_DECODING;
+    }
+
+    pub trait InternalSystemTimeConfig {
+        const SYSTEM_TIME_DECODING: SystemTimeDecoding;
+    }
+
+    impl<E, I, L, P, A, U, S: InternalSystemTimeConfig, Len, M, Tr, Ch, Off>
+        InternalSystemTimeConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const SYSTEM_TIME_DECODING: SystemTimeDecoding = S::SYSTEM_TIME_DECODING;
+    }
+
+    pub trait InternalLengthEncodingConfig {
+        const LENGTH_ENCODING: LengthEncoding;
+    }
+
+    impl<E, I, L, P, A, U, S, Len: InternalLengthEncodingConfig, M, Tr, Ch, Off>
+        InternalLengthEncodingConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const LENGTH_ENCODING: LengthEncoding = Len::LENGTH_ENCODING;
+    }
+
+    pub trait InternalMapOrderConfig {
+        const MAP_ORDER: MapOrder;
+    }
+
+    impl<E, I, L, P, A, U, S, Len, M: InternalMapOrderConfig, Tr, Ch, Off> InternalMapOrderConfig
+        for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const MAP_ORDER: MapOrder = M::MAP_ORDER;
+    }
+
+    pub trait InternalTrailingBytesConfig {
+        const TRAILING_BYTES: TrailingBytes;
+    }
+
+    impl<E, I, L, P, A, U, S, Len, M, Tr: InternalTrailingBytesConfig, Ch, Off>
+        InternalTrailingBytesConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const TRAILING_BYTES: TrailingBytes = Tr::TRAILING_BYTES;
+    }
+
+    pub trait InternalCharEncodingConfig {
+        const CHAR_ENCODING: CharEncoding;
+    }
+
+    impl<E, I, L, P, A, U, S, Len, M, Tr, Ch: InternalCharEncodingConfig, Off>
+        InternalCharEncodingConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const CHAR_ENCODING: CharEncoding = Ch::CHAR_ENCODING;
+    }
+
+    pub trait InternalErrorOffsetsConfig {
+        const ERROR_OFFSETS: ErrorOffsets;
+    }
+
+    impl<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off: InternalErrorOffsetsConfig>
+        InternalErrorOffsetsConfig for Configuration<E, I, L, P, A, U, S, Len, M, Tr, Ch, Off>
+    {
+        const ERROR_OFFSETS: ErrorOffsets = Off::ERROR_OFFSETS;
+    }
+
+    /// The config `C` with the endianness of `E`, for the fields of a derived type with `#[bincode(endian = "...")]`.
+    #[derive(Copy, Clone, Debug)]
+    pub struct WithEndian<C, E> {
+        config: C,
+        _e: PhantomData<E>,
+    }
+
+    impl<C, E> WithEndian<C, E> {
+        pub const fn new(config: C) -> Self {
+            Self {
+                config,
+                _e: PhantomData,
+            }
+        }
+    }
+
+    impl<C, E: InternalEndianConfig> InternalEndianConfig for WithEndian<C, E> {
+        const ENDIAN: Endianness = E::ENDIAN;
+    }
+
+    impl<C: InternalIntEncodingConfig, E> InternalIntEncodingConfig for WithEndian<C, E> {
+        const INT_ENCODING: IntEncoding = C::INT_ENCODING;
+    }
+
+    impl<C: InternalLimitConfig, E> InternalLimitConfig for WithEndian<C, E> {
+        const LIMITED: bool = C::LIMITED;
+
+        #[inline]
+        fn byte_limit(&self) -> Option<usize> {
+            self.config.byte_limit()
+        }
+    }
+
+    impl<C: InternalPathEncodingConfig, E> InternalPathEncodingConfig for WithEndian<C, E> {
+        const PATH_ENCODING: PathEncoding = C::PATH_ENCODING;
+    }
+
+    impl<C: InternalAllocationConfig, E> InternalAllocationConfig for WithEndian<C, E> {
+        const ALLOCATION: Allocation = C::ALLOCATION;
+    }
+
+    impl<C: InternalUtf8Config, E> InternalUtf8Config for WithEndian<C, E> {
+        const UTF8_DECODING: Utf8Decoding = C::UTF8_DECODING;
+    }
+
+    impl<C: InternalSystemTimeConfig, E> InternalSystemTimeConfig for WithEndian<C, E> {
+        const SYSTEM_TIME_DECODING: SystemTimeDecoding = C::SYSTEM_TIME_DECODING;
+    }
+
+    impl<C: InternalLengthEncodingConfig, E> InternalLengthEncodingConfig for WithEndian<C, E> {
+        const LENGTH_ENCODING: LengthEncoding = C::LENGTH_ENCODING;
+    }
+
+    impl<C: InternalMapOrderConfig, E> InternalMapOrderConfig for WithEndian<C, E> {
+        const MAP_ORDER: MapOrder = C::MAP_ORDER;
+    }
+
+    impl<C: InternalTrailingBytesConfig, E> InternalTrailingBytesConfig for WithEndian<C, E> {
+        const TRAILING_BYTES: TrailingBytes = C::TRAILING_BYTES;
+    }
+
+    impl<C: InternalCharEncodingConfig, E> InternalCharEncodingConfig for WithEndian<C, E> {
+        const CHAR_ENCODING: CharEncoding = C::CHAR_ENCODING;
     }
 
-    impl<E, I, L: InternalLimitConfig> InternalLimitConfig for Configuration<E, I, L> {
-        const LIMIT: Option<usize> = L::LIMIT;
+    impl<C: InternalErrorOffsetsConfig, E> InternalErrorOffsetsConfig for WithEndian<C, E> {
+        const ERROR_OFFSETS: ErrorOffsets = C::ERROR_OFFSETS;
     }
 }
@@ -0,0 +1,213 @@
+use super::{BorrowDecode, BorrowDecoder, Decode, Decoder};
+use crate::{error::DecodeError, transparent::Transparent};
+use core::marker::PhantomData;
+
+/// Decodes the entries of a collection, following the protocol that keeps the [limit](crate::config::Configuration::with_limit) intact (see [`Decoder`]).
+///
+/// [`new`](CollectionDecoder::new) reads the length of the collection and claims its entries. Every entry that is decoded afterwards un-claims its share first, and errors are tagged with the index of the entry. This is what the implementations for `Vec`, `HashMap` and the other collections use, so a collection that is decoded with this behaves exactly like them.
+///
+/// The entries are written by [`encode_collection`](crate::enc::encode_collection). If `T` implements [`Decode`], this is also an iterator over the entries, e.g. to `collect` them.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::{CollectionDecoder, Decode, Decoder};
+/// use bincode::enc::{encode_collection, Encode, Encoder};
+/// use bincode::error::{DecodeError, EncodeError};
+///
+/// /// A list that keeps its entries sorted
+/// #[derive(Debug, PartialEq)]
+/// struct SortedList(Vec<u32>);
+///
+/// impl Encode for SortedList {
+///     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+///         encode_collection(encoder, self.0.len(), &self.0)
+///     }
+/// }
+///
+/// impl<Context> Decode<Context> for SortedList {
+///     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+///         let mut entries = CollectionDecoder::<_, u32>::new(decoder)?;
+///         let mut list = Vec::with_capacity(entries.initial_capacity());
+///         while let Some(entry) = entries.next_entry()? {
+///             let index = list.partition_point(|e| *e < entry);
+///             list.insert(index, entry);
+///         }
+///         Ok(Self(list))
+///     }
+/// }
+///
+/// let config = bincode::config::standard();
+/// let encoded = bincode::encode_to_vec(SortedList(vec![1, 5, 9]), config).unwrap();
+/// let (list, _): (SortedList, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(list, SortedList(vec![1, 5, 9]));
+/// ```
+pub struct CollectionDecoder<'a, D: Decoder, T> {
+    decoder: &'a mut D,
+    len: usize,
+    index: usize,
+    entry_size: usize,
+    transparent: Option<Transparent<T>>,
+    entry: PhantomData<fn() -> T>,
+}
+
+impl<'a, D: Decoder, T> CollectionDecoder<'a, D, T> {
+    /// Reads the length of the collection, and claims room for that many entries of `T`, using its [`Decode::MIN_ENCODED_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length can not be read, or `DecodeError::LimitExceeded` if the entries do not fit in the limit.
+    pub fn new(decoder: &'a mut D) -> Result<Self, DecodeError>
+    where
+        T: Decode<D::Context>,
+    {
+        let mut entries = Self::with_entry_size(decoder, T::MIN_ENCODED_SIZE)?;
+        entries.transparent = T::transparent_wire();
+        Ok(entries)
+    }
+
+    /// Reads the length of the collection, and claims room for that many entries of `T`, using its [`BorrowDecode::MIN_ENCODED_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length can not be read, or `DecodeError::LimitExceeded` if the entries do not fit in the limit.
+    pub fn new_borrowed<'de>(decoder: &'a mut D) -> Result<Self, DecodeError>
+    where
+        D: BorrowDecoder<'de>,
+        T: BorrowDecode<'de, D::Context>,
+    {
+        let mut entries = Self::with_entry_size(decoder, T::MIN_ENCODED_SIZE)?;
+        entries.transparent = T::transparent_wire();
+        Ok(entries)
+    }
+
+    /// Reads the length of the collection, and claims room for that many entries of at least `entry_size` bytes each.
+    ///
+    /// Use this for entries that do not implement [`Decode`] or [`BorrowDecode`] themselves, and are decoded with [`decode_entry_with`](CollectionDecoder::decode_entry_with).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length can not be read, or `DecodeError::LimitExceeded` if the entries do not fit in the limit.
+    pub fn with_entry_size(decoder: &'a mut D, entry_size: usize) -> Result<Self, DecodeError> {
+        let len = super::decode_slice_len(decoder)?;
+        decoder.claim_container_entries(len, entry_size)?;
+        Ok(Self {
+            decoder,
+            len,
+            index: 0,
+            entry_size,
+            transparent: None,
+            entry: PhantomData,
+        })
+    }
+
+    /// Returns the amount of entries in the collection.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the collection has no entries.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of entries that are not decoded yet.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.len - self.index
+    }
+
+    /// Returns how many entries to allocate room for up front.
+    ///
+    /// The length comes from the input, so it can be much larger than the input itself. This limits it to what could fit in the input that is left, or to a fixed amount of memory if the reader does not know how much is left. Let the collection grow beyond this as the entries are decoded.
+    pub fn initial_capacity(&mut self) -> usize {
+        super::preallocation_len::<T, _>(self.decoder, self.len)
+    }
+
+    /// Decodes the next entry with `decode`, or returns `Ok(None)` if all entries are decoded.
+    ///
+    /// Use this for entries that are decoded differently than with [`Decode`] or [`BorrowDecode`], e.g. a map that decodes its key and value separately. `decode` must read a single entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `decode`, with the index of the entry. No more entries are decoded after an error.
+    pub fn decode_entry_with<U>(
+        &mut self,
+        decode: impl FnOnce(&mut D) -> Result<U, DecodeError>,
+    ) -> Result<Option<U>, DecodeError> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let index = self.index;
+        // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+        self.decoder.unclaim_container_entry(self.entry_size);
+        match decode(self.decoder) {
+            Ok(entry) => {
+                self.index += 1;
+                Ok(Some(entry))
+            }
+            Err(e) => {
+                self.index = self.len;
+                Err(e.with_index(index))
+            }
+        }
+    }
+
+    /// Decodes the next entry, or returns `Ok(None)` if all entries are decoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of decoding the entry, with the index of the entry.
+    pub fn next_entry(&mut self) -> Result<Option<T>, DecodeError>
+    where
+        T: Decode<D::Context>,
+    {
+        self.decode_entry_with(T::decode)
+    }
+
+    /// Decodes the next entry, which may borrow from the input, or returns `Ok(None)` if all entries are decoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of decoding the entry, with the index of the entry.
+    pub fn next_borrowed_entry<'de>(&mut self) -> Result<Option<T>, DecodeError>
+    where
+        D: BorrowDecoder<'de>,
+        T: BorrowDecode<'de, D::Context>,
+    {
+        self.decode_entry_with(T::borrow_decode)
+    }
+
+    /// Returns the decoder, for reading all entries at once.
+    ///
+    /// The entries stay claimed, so reading them counts them against the limit only once.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn decoder(&mut self) -> &mut D {
+        self.decoder
+    }
+
+    /// Returns the amount of bytes that are claimed for every entry.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn entry_size(&self) -> usize {
+        self.entry_size
+    }
+
+    /// Returns the `transparent_wire` of `T`, if the entries were claimed with its `Decode` or `BorrowDecode` implementation.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn transparent(&self) -> Option<Transparent<T>> {
+        self.transparent
+    }
+}
+
+impl<D: Decoder, T: Decode<D::Context>> Iterator for CollectionDecoder<'_, D, T> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining()))
+    }
+}
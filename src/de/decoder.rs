@@ -1,13 +1,16 @@
 use super::{
-    read::{BorrowReader, Reader},
+    read::{BorrowReader, Reader, TakeReader},
     BorrowDecoder, Decoder,
 };
-use crate::{config::Config, error::DecodeError, utils::Sealed};
+use crate::{
+    config::{Config, InternalEndianConfig, WithEndian},
+    error::DecodeError,
+    utils::Sealed,
+};
 
 /// A Decoder that reads bytes from a given reader `R`.
 ///
-/// This struct should rarely be used.
-/// In most cases, prefer any of the `decode` functions.
+/// In most cases, prefer any of the `decode` functions. Use a decoder directly to decode several values one after another, e.g. a header and then a body whose type depends on the header. To decode several values from one slice and to inspect the bytes in between, create a decoder over `&mut` a [`SliceReader`](super::read::SliceReader) for every value, see its documentation.
 ///
 /// The `ByteOrder` that is chosen will impact the endianness that
 /// is used to read integers out of the reader.
@@ -21,6 +24,24 @@ use crate::{config::Config, error::DecodeError, utils::Sealed};
 /// // this u32 can be any Decode
 /// let value = u32::decode(&mut decoder).unwrap();
 /// ```
+///
+/// The limit of the config applies to every value decoded with the same decoder together, see [`bytes_claimed`](Decoder::bytes_claimed) and [`limit_remaining`](Decoder::limit_remaining):
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+/// use bincode::error::DecodeError;
+///
+/// let config = bincode::config::standard().with_limit::<2>();
+/// let mut decoder = DecoderImpl::new(SliceReader::new(&[1, 2, 3]), config, ());
+/// assert_eq!(u8::decode(&mut decoder).unwrap(), 1);
+/// assert_eq!(u8::decode(&mut decoder).unwrap(), 2);
+/// assert_eq!(decoder.limit_remaining(), Some(0));
+/// assert!(matches!(u8::decode(&mut decoder), Err(DecodeError::LimitExceeded)));
+///
+/// // the reader is at the third byte
+/// assert_eq!(decoder.into_reader().remaining(), [3]);
+/// ```
 pub struct DecoderImpl<R, C: Config, Context> {
     reader: R,
     config: C,
@@ -38,6 +59,12 @@ impl<R: Reader, C: Config, Context> DecoderImpl<R, C, Context> {
             context,
         }
     }
+
+    /// Return the underlying reader
+    #[inline]
+    pub fn into_reader(self) -> R {
+        self.reader
+    }
 }
 
 impl<R, C: Config, Context> Sealed for DecoderImpl<R, C, Context> {}
@@ -68,8 +95,8 @@ impl<R: Reader, C: Config, Context> Decoder for DecoderImpl<R, C, Context> {
 
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
-        // C::LIMIT is a const so this check should get compiled away
-        if let Some(limit) = C::LIMIT {
+        // C::LIMITED is a const so this check should get compiled away
+        if let (true, Some(limit)) = (C::LIMITED, self.config.byte_limit()) {
             // Make sure we don't accidentally overflow `bytes_read`
             self.bytes_read = self
                 .bytes_read
@@ -85,12 +112,16 @@ impl<R: Reader, C: Config, Context> Decoder for DecoderImpl<R, C, Context> {
         }
     }
 
+    fn bytes_claimed(&self) -> usize {
+        self.bytes_read
+    }
+
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
-        // C::LIMIT is a const so this check should get compiled away
-        if C::LIMIT.is_some() {
-            // We should always be claiming more than we unclaim, so this should never underflow
-            self.bytes_read -= n;
+        // C::LIMITED is a const so this check should get compiled away
+        if C::LIMITED {
+            // We should always be claiming more than we unclaim, but a `Decode` implementation that un-claims too much must not panic
+            self.bytes_read = self.bytes_read.saturating_sub(n);
         }
     }
 
@@ -129,6 +160,22 @@ impl<Context, D: Decoder + ?Sized> Decoder for WithContext<'_, D, Context> {
         self.decoder.claim_bytes_read(n)
     }
 
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        self.decoder.claim_container_entries(len, entry_size)
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        self.decoder.bytes_claimed()
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        self.decoder.limit_remaining()
+    }
+
     fn unclaim_bytes_read(&mut self, n: usize) {
         self.decoder.unclaim_bytes_read(n);
     }
@@ -140,3 +187,367 @@ impl<'de, C, D: BorrowDecoder<'de>> BorrowDecoder<'de> for WithContext<'_, D, C>
         self.decoder.borrow_reader()
     }
 }
+
+/// A decoder that reads with the endianness of `E` instead of the endianness of the config of the wrapped decoder, see [`decode_with_endian`](super::decode_with_endian).
+pub struct EndianDecoder<'a, D: Decoder, E> {
+    decoder: &'a mut D,
+    config: WithEndian<D::C, E>,
+}
+
+impl<'a, D: Decoder, E> EndianDecoder<'a, D, E> {
+    pub fn new(decoder: &'a mut D) -> Self {
+        let config = WithEndian::new(*decoder.config());
+        Self { decoder, config }
+    }
+}
+
+impl<D: Decoder, E> Sealed for EndianDecoder<'_, D, E> {}
+
+impl<D: Decoder, E: InternalEndianConfig + Copy> Decoder for EndianDecoder<'_, D, E> {
+    type R = D::R;
+
+    type C = WithEndian<D::C, E>;
+
+    type Context = D::Context;
+
+    fn context(&mut self) -> &mut Self::Context {
+        self.decoder.context()
+    }
+
+    fn reader(&mut self) -> &mut Self::R {
+        self.decoder.reader()
+    }
+
+    fn config(&self) -> &Self::C {
+        &self.config
+    }
+
+    fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.decoder.claim_bytes_read(n)
+    }
+
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        self.decoder.claim_container_entries(len, entry_size)
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        self.decoder.bytes_claimed()
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        self.decoder.limit_remaining()
+    }
+
+    fn unclaim_bytes_read(&mut self, n: usize) {
+        self.decoder.unclaim_bytes_read(n);
+    }
+}
+
+impl<'de, D: BorrowDecoder<'de>, E: InternalEndianConfig + Copy> BorrowDecoder<'de>
+    for EndianDecoder<'_, D, E>
+{
+    type BR = D::BR;
+    fn borrow_reader(&mut self) -> &mut Self::BR {
+        self.decoder.borrow_reader()
+    }
+}
+
+/// A decoder that reads a value with a fixed size from `reader`, see [`Decoder::with_padding`](super::Decoder::with_padding). Everything else is passed on to the wrapped decoder.
+pub struct PaddedDecoder<'a, D, R> {
+    pub(crate) decoder: &'a mut D,
+    pub(crate) reader: R,
+}
+
+impl<D, R> Sealed for PaddedDecoder<'_, D, R> {}
+
+impl<D: Decoder, R: Reader> Decoder for PaddedDecoder<'_, D, R> {
+    type R = R;
+
+    type C = D::C;
+
+    type Context = D::Context;
+
+    fn context(&mut self) -> &mut Self::Context {
+        self.decoder.context()
+    }
+
+    fn reader(&mut self) -> &mut Self::R {
+        &mut self.reader
+    }
+
+    fn config(&self) -> &Self::C {
+        self.decoder.config()
+    }
+
+    fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.decoder.claim_bytes_read(n)
+    }
+
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        self.decoder.claim_container_entries(len, entry_size)
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        self.decoder.bytes_claimed()
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        self.decoder.limit_remaining()
+    }
+
+    fn unclaim_bytes_read(&mut self, n: usize) {
+        self.decoder.unclaim_bytes_read(n);
+    }
+}
+
+impl<'de, D: Decoder, R: BorrowReader<'de>> BorrowDecoder<'de> for PaddedDecoder<'_, D, R> {
+    type BR = R;
+    fn borrow_reader(&mut self) -> &mut Self::BR {
+        &mut self.reader
+    }
+}
+
+/// A decoder that may claim at most a limit of its own, see [`Decoder::with_sub_limit`](super::Decoder::with_sub_limit). The claims are passed on to the wrapped decoder as well.
+///
+/// The limit of the scope is checked even if the config has no limit. [`bytes_claimed`](Decoder::bytes_claimed) is the amount of bytes claimed in this scope, and [`limit_remaining`](Decoder::limit_remaining) is the least of what is left of the limit of the scope and of the wrapped decoder.
+pub struct SubLimitDecoder<'a, D> {
+    decoder: &'a mut D,
+    scope: &'static str,
+    limit: usize,
+    claimed: usize,
+}
+
+impl<'a, D: Decoder> SubLimitDecoder<'a, D> {
+    pub(crate) const fn new(decoder: &'a mut D, scope: &'static str, limit: usize) -> Self {
+        Self {
+            decoder,
+            scope,
+            limit,
+            claimed: 0,
+        }
+    }
+
+    /// The name of the scope.
+    #[must_use]
+    pub const fn scope(&self) -> &'static str {
+        self.scope
+    }
+}
+
+impl<D> Sealed for SubLimitDecoder<'_, D> {}
+
+impl<D: Decoder> Decoder for SubLimitDecoder<'_, D> {
+    type R = D::R;
+
+    type C = D::C;
+
+    type Context = D::Context;
+
+    fn context(&mut self) -> &mut Self::Context {
+        self.decoder.context()
+    }
+
+    fn reader(&mut self) -> &mut Self::R {
+        self.decoder.reader()
+    }
+
+    fn config(&self) -> &Self::C {
+        self.decoder.config()
+    }
+
+    fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
+        let claimed = self
+            .claimed
+            .checked_add(n)
+            .filter(|&claimed| claimed <= self.limit)
+            .ok_or(DecodeError::SubLimitExceeded {
+                scope: self.scope,
+                limit: self.limit,
+            })?;
+        self.decoder.claim_bytes_read(n)?;
+        self.claimed = claimed;
+        Ok(())
+    }
+
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        // unlike the default, this claims even if the config has no limit, as the scope has one
+        len.checked_mul(entry_size.max(1)).map_or(
+            Err(DecodeError::SubLimitExceeded {
+                scope: self.scope,
+                limit: self.limit,
+            }),
+            |val| self.claim_bytes_read(val),
+        )
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        self.claimed
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        let remaining = self.limit - self.claimed;
+        Some(
+            self.decoder
+                .limit_remaining()
+                .map_or(remaining, |outer| outer.min(remaining)),
+        )
+    }
+
+    fn unclaim_bytes_read(&mut self, n: usize) {
+        self.claimed = self.claimed.saturating_sub(n);
+        self.decoder.unclaim_bytes_read(n);
+    }
+}
+
+impl<'de, D: BorrowDecoder<'de>> BorrowDecoder<'de> for SubLimitDecoder<'_, D> {
+    type BR = D::BR;
+    fn borrow_reader(&mut self) -> &mut Self::BR {
+        self.decoder.borrow_reader()
+    }
+}
+
+/// A reader that reads from the reader of the wrapped decoder, so [`SectionDecoder`] can limit what is read from it while the bytes are still claimed with the wrapped decoder.
+pub struct DecoderReader<'a, D>(&'a mut D);
+
+impl<D: Decoder> Reader for DecoderReader<'_, D> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.0.reader().read(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.0.reader().peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.0.reader().consume(n);
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.0.reader().skip(n)
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        None
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        self.0.reader().take_shared_bytes(length)
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.0.reader().enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.0.reader().leave();
+    }
+}
+
+impl<'de, D: BorrowDecoder<'de>> BorrowReader<'de> for DecoderReader<'_, D> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'de [u8], DecodeError> {
+        self.0.borrow_reader().take_bytes(length)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'de mut [u8], DecodeError> {
+        self.0.borrow_reader().take_bytes_mut(length)
+    }
+}
+
+/// A decoder that reads a section with a length in front of it, see [`Decoder::with_length_prefix`](super::Decoder::with_length_prefix). It can not read past the end of the section, and everything else is passed on to the wrapped decoder.
+pub struct SectionDecoder<'a, D> {
+    reader: TakeReader<DecoderReader<'a, D>>,
+}
+
+impl<'a, D: Decoder> SectionDecoder<'a, D> {
+    pub(crate) const fn new(decoder: &'a mut D, len: usize) -> Self {
+        Self {
+            reader: TakeReader::new(DecoderReader(decoder), len),
+        }
+    }
+
+    const fn decoder(&self) -> &D {
+        self.reader.get_ref().0
+    }
+
+    const fn decoder_mut(&mut self) -> &mut D {
+        self.reader.get_mut().0
+    }
+}
+
+impl<D> Sealed for SectionDecoder<'_, D> {}
+
+impl<'a, D: Decoder> Decoder for SectionDecoder<'a, D> {
+    type R = TakeReader<DecoderReader<'a, D>>;
+
+    type C = D::C;
+
+    type Context = D::Context;
+
+    fn context(&mut self) -> &mut Self::Context {
+        self.decoder_mut().context()
+    }
+
+    fn reader(&mut self) -> &mut Self::R {
+        &mut self.reader
+    }
+
+    fn config(&self) -> &Self::C {
+        self.decoder().config()
+    }
+
+    fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.decoder_mut().claim_bytes_read(n)
+    }
+
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        self.decoder_mut().claim_container_entries(len, entry_size)
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        self.decoder().bytes_claimed()
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        self.decoder().limit_remaining()
+    }
+
+    fn unclaim_bytes_read(&mut self, n: usize) {
+        self.decoder_mut().unclaim_bytes_read(n);
+    }
+}
+
+impl<'de, 'a, D: BorrowDecoder<'de>> BorrowDecoder<'de> for SectionDecoder<'a, D> {
+    type BR = TakeReader<DecoderReader<'a, D>>;
+    fn borrow_reader(&mut self) -> &mut Self::BR {
+        &mut self.reader
+    }
+}
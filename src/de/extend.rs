@@ -0,0 +1,58 @@
+use super::Decoder;
+use crate::error::DecodeError;
+
+/// A collection that the entries of an encoded collection can be added to, instead of decoding them into a new collection.
+///
+/// This reads the same bytes as the [`Decode`](super::Decode) implementation of the collection, and claims the same amount of the limit, but inserts every entry into `self` as it is decoded. A map keeps its existing entries, and the entries that are decoded overwrite the ones with the same key. The final state is the same as when the collection is decoded and then extended with the decoded entries, including the [`with_strict_map_order`](crate::config::Configuration::with_strict_map_order) check of a `BTreeMap` or `BTreeSet`.
+///
+/// Use [`decode_extend_from_slice`](crate::decode_extend_from_slice) to decode from a slice:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use std::collections::HashMap;
+///
+/// let config = bincode::config::standard();
+/// let mut state = HashMap::from([(1u32, String::from("idle")), (2, String::from("idle"))]);
+///
+/// let batch = HashMap::from([(2u32, String::from("busy")), (3, String::from("idle"))]);
+/// let encoded = bincode::encode_to_vec(&batch, config).unwrap();
+/// let (extended, _) = bincode::decode_extend_from_slice(&mut state, &encoded, config).unwrap();
+///
+/// assert_eq!((extended.inserted, extended.overwritten), (1, 1));
+/// assert_eq!(state.len(), 3);
+/// assert_eq!(state[&2], "busy");
+/// ```
+///
+/// If decoding fails, the entries that were decoded before the error are already in the collection.
+pub trait DecodeExtend<Context>: Sized {
+    /// Decodes a collection from `decoder`, and adds its entries to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as decoding the collection itself.
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError>;
+}
+
+/// The amount of entries that [`DecodeExtend::decode_extend`] added to a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Extended {
+    /// The amount of entries that were added, or the amount of bytes that were added to a `String`
+    pub inserted: usize,
+    /// The amount of entries that replaced an entry with the same key
+    pub overwritten: usize,
+}
+
+impl Extended {
+    /// Counts an entry that was added, which replaced an entry with the same key if `overwritten`.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn add(&mut self, overwritten: bool) {
+        if overwritten {
+            self.overwritten += 1;
+        } else {
+            self.inserted += 1;
+        }
+    }
+}
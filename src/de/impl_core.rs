@@ -27,42 +27,32 @@ impl<T, const N: usize> Drop for Guard<'_, T, N> {
     }
 }
 
-/// Pulls `N` items from `iter` and returns them as an array. If the iterator
-/// yields fewer than `N` items, `None` is returned and all already yielded
-/// items are dropped.
-///
-/// Since the iterator is passed as a mutable reference and this function calls
-/// `next` at most `N` times, the iterator can still be used afterwards to
-/// retrieve the remaining items.
-///
-/// If `iter.next()` panicks, all items already yielded by the iterator are
-/// dropped.
-#[allow(clippy::while_let_on_iterator)]
-pub fn collect_into_array<E, I, T, const N: usize>(iter: &mut I) -> Option<Result<[T; N], E>>
+/// Creates an array `[T; N]` where each fallible array element `T` is
+/// returned by the `cb` call. Unlike [`core::array::from_fn`], where the
+/// element creation can't fail, this version will return an error if any
+/// element creation was unsuccessful.
+///
+/// The array is built in place, so `T` does not need a default value. If `cb`
+/// returns an error or panics, all elements that were already created are
+/// dropped, in order.
+///
+/// Mirrors the unstable `core::array::try_from_fn`, which returns a
+/// `Try` type instead of a `Result`.
+pub fn try_from_fn<T, E, F, const N: usize>(mut cb: F) -> Result<[T; N], E>
 where
-    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(usize) -> Result<T, E>,
 {
-    if N == 0 {
-        // SAFETY: An empty array is always inhabited and has no validity invariants.
-        return unsafe { Some(Ok(mem::zeroed())) };
-    }
-
     let mut array = uninit_array::<T, N>();
     let mut guard = Guard {
         array_mut: &mut array,
         initialized: 0,
     };
 
-    while let Some(item_rslt) = iter.next() {
-        let item = match item_rslt {
-            Err(err) => {
-                return Some(Err(err));
-            }
-            Ok(elem) => elem,
-        };
+    while guard.initialized < N {
+        let item = cb(guard.initialized)?;
 
         // SAFETY: `guard.initialized` starts at 0, is increased by one in the
-        // loop and the loop is aborted once it reaches N (which is
+        // loop and the loop stops once it reaches N (which is
         // `array.len()`).
         unsafe {
             guard
@@ -71,22 +61,47 @@ where
                 .write(item);
         }
         guard.initialized += 1;
+    }
+    mem::forget(guard);
 
-        // Check if the whole array was initialized.
-        if guard.initialized == N {
-            mem::forget(guard);
+    // SAFETY: the loop above only finishes once all elements are initialized.
+    Ok(unsafe { array_assume_init(&array) })
+}
 
-            // SAFETY: the condition above asserts that all elements are
-            // initialized.
-            let out = unsafe { array_assume_init(&array) };
-            return Some(Ok(out));
-        }
+/// Initializes every element of `array` in place with `cb`, like [`try_from_fn`].
+///
+/// `cb` must initialize the element it is given if it returns `Ok`, and leave it uninitialized if it returns an error. If `cb` returns an error or panics, all elements that were already initialized are dropped, in order, and `array` is left uninitialized.
+pub fn try_init_in_place<T, E, F, const N: usize>(
+    array: &mut [MaybeUninit<T>; N],
+    mut cb: F,
+) -> Result<(), E>
+where
+    F: FnMut(&mut MaybeUninit<T>) -> Result<(), E>,
+{
+    let mut guard = Guard {
+        array_mut: array,
+        initialized: 0,
+    };
+
+    while guard.initialized < N {
+        // SAFETY: `guard.initialized` starts at 0, is increased by one in the
+        // loop and the loop stops once it reaches N (which is
+        // `array.len()`).
+        cb(unsafe { guard.array_mut.get_unchecked_mut(guard.initialized) })?;
+        guard.initialized += 1;
     }
+    mem::forget(guard);
+    Ok(())
+}
 
-    // This is only reached if the iterator is exhausted before
-    // `guard.initialized` reaches `N`. Also note that `guard` is dropped here,
-    // dropping all already initialized elements.
-    None
+/// Returns the elements of an uninitialized array.
+#[inline(always)]
+pub fn array_elements_mut<T, const N: usize>(
+    array: &mut MaybeUninit<[T; N]>,
+) -> &mut [MaybeUninit<T>; N] {
+    // SAFETY: `MaybeUninit<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`, and both
+    // are valid in any state.
+    unsafe { &mut *array.as_mut_ptr().cast::<[MaybeUninit<T>; N]>() }
 }
 
 /// Assuming all the elements are initialized, get a mutable slice to them.
@@ -11,6 +11,8 @@ macro_rules! impl_tuple {
             $extra : BorrowDecode<'de, Context>,
         )*
          {
+            const MIN_ENCODED_SIZE: usize = $first::MIN_ENCODED_SIZE $(.saturating_add($extra::MIN_ENCODED_SIZE))*;
+
             fn borrow_decode<BD: BorrowDecoder<'de, Context = Context>>(decoder: &mut BD) -> Result<Self, DecodeError> {
                 Ok((
                     $first::borrow_decode(decoder)?,
@@ -26,6 +28,8 @@ macro_rules! impl_tuple {
             $extra : Decode<Context>,
         )*
         {
+            const MIN_ENCODED_SIZE: usize = $first::MIN_ENCODED_SIZE $(.saturating_add($extra::MIN_ENCODED_SIZE))*;
+
             fn decode<DE: Decoder<Context = Context>>(decoder: &mut DE) -> Result<Self, DecodeError> {
                 Ok((
                     $first::decode(decoder)?,
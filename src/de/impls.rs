@@ -1,21 +1,27 @@
 #![allow(unsafe_code, clippy::cast_possible_truncation)]
 use super::{
-    read::{BorrowReader, Reader},
-    BorrowDecode, BorrowDecoder, Decode, Decoder,
+    read::{peek_exact, BorrowReader, Reader},
+    BorrowDecode, BorrowDecodeFixedLen, BorrowDecodeRemaining, BorrowDecoder, Decode, Decoder,
 };
 use crate::{
-    config::{Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig},
+    config::{
+        CharEncoding, Endianness, FixedLenPadding, IntEncoding, InternalCharEncodingConfig,
+        InternalEndianConfig, InternalIntEncodingConfig,
+    },
     error::{DecodeError, IntegerType},
     impl_borrow_decode,
+    utils::Private,
 };
 use core::{
     cell::{Cell, RefCell},
     cmp::Reverse,
+    mem::MaybeUninit,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
     },
     ops::{Bound, Range, RangeInclusive},
+    task::Poll,
     time::Duration,
 };
 
@@ -34,7 +40,7 @@ impl<Context> Decode<Context> for u8 {
     #[inline]
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(1)?;
-        if let Some(buf) = decoder.reader().peek_read(1) {
+        if let Some(buf) = peek_exact(decoder.reader(), 1) {
             let byte = buf[0];
             decoder.reader().consume(1);
             Ok(byte)
@@ -371,6 +377,8 @@ impl<Context> Decode<Context> for NonZeroIsize {
 impl_borrow_decode!(NonZeroIsize);
 
 impl<Context> Decode<Context> for f32 {
+    const MIN_ENCODED_SIZE: usize = 4;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(4)?;
         let mut bytes = [0u8; 4];
@@ -384,6 +392,8 @@ impl<Context> Decode<Context> for f32 {
 impl_borrow_decode!(f32);
 
 impl<Context> Decode<Context> for f64 {
+    const MIN_ENCODED_SIZE: usize = 8;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(8)?;
         let mut bytes = [0u8; 8];
@@ -397,11 +407,15 @@ impl<Context> Decode<Context> for f64 {
 impl_borrow_decode!(f64);
 
 impl<Context, T: Decode<Context>> Decode<Context> for Wrapping<T> {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Self(T::decode(decoder)?))
     }
 }
 impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for Wrapping<T> {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
@@ -410,12 +424,16 @@ impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for
 }
 
 impl<Context, T: Decode<Context>> Decode<Context> for Reverse<T> {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         Ok(Self(T::decode(decoder)?))
     }
 }
 
 impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for Reverse<T> {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
@@ -425,34 +443,65 @@ impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for
 
 impl<Context> Decode<Context> for char {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if D::C::CHAR_ENCODING == CharEncoding::Scalar {
+            decoder.claim_bytes_read(4)?;
+            let mut bytes = [0u8; 4];
+            decoder.reader().read(&mut bytes)?;
+            let found = match D::C::ENDIAN {
+                Endianness::Little => u32::from_le_bytes(bytes),
+                Endianness::Big => u32::from_be_bytes(bytes),
+            };
+            return Self::from_u32(found).ok_or(DecodeError::InvalidCharValue { found });
+        }
+
         let mut array = [0u8; 4];
 
         // Look at the first byte to see how many bytes must be read
+        decoder.claim_bytes_read(1)?;
         decoder.reader().read(&mut array[..1])?;
 
         let width = utf8_char_width(array[0]);
         if width == 0 {
             return Err(DecodeError::InvalidCharEncoding(array));
         }
-        // Normally we have to `.claim_bytes_read` before reading, however in this
-        // case the amount of bytes read from `char` can vary wildly, and it should
-        // only read up to 4 bytes too much.
-        decoder.claim_bytes_read(width)?;
+        decoder.claim_bytes_read(width - 1)?;
         if width == 1 {
             return Ok(array[0] as Self);
         }
 
         // read the remaining pain
         decoder.reader().read(&mut array[1..width])?;
-        let res = core::str::from_utf8(&array[..width])
+        core::str::from_utf8(&array[..width])
             .ok()
             .and_then(|s| s.chars().next())
-            .ok_or(DecodeError::InvalidCharEncoding(array))?;
-        Ok(res)
+            .ok_or_else(|| invalid_utf8_char(array, width))
     }
 }
 impl_borrow_decode!(char);
 
+/// Returns the error for the `width` bytes of `array` that are not the UTF-8 encoding of a `char`.
+///
+/// A sequence that is well-formed, but encodes a surrogate or a value above `char::MAX`, is reported with its value. Everything else is reported with its bytes.
+fn invalid_utf8_char(array: [u8; 4], width: usize) -> DecodeError {
+    let continuation = &array[1..width];
+    if continuation.iter().all(|b| b & 0xC0 == 0x80) {
+        let lead = u32::from(array[0]) & (0x7F >> width);
+        let found = continuation
+            .iter()
+            .fold(lead, |value, b| value << 6 | u32::from(b & 0x3F));
+        // the smallest value that needs `width` bytes, so overlong encodings are not reported as values
+        let min = match width {
+            2 => 0x80,
+            3 => 0x800,
+            _ => 0x1_0000,
+        };
+        if found >= min && char::from_u32(found).is_none() {
+            return DecodeError::InvalidCharValue { found };
+        }
+    }
+    DecodeError::InvalidCharEncoding(array)
+}
+
 impl<'a, 'de: 'a, Context> BorrowDecode<'de, Context> for &'a [u8] {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
@@ -463,12 +512,62 @@ impl<'a, 'de: 'a, Context> BorrowDecode<'de, Context> for &'a [u8] {
     }
 }
 
+impl<'a, 'de: 'a> BorrowDecodeRemaining<'de> for &'a [u8] {
+    fn borrow_decode_remaining<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        decoder.borrow_remaining_bytes()
+    }
+}
+
+impl<'a, 'de: 'a> BorrowDecodeFixedLen<'de> for &'a [u8] {
+    fn borrow_decode_fixed_len<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        decoder.claim_bytes_read(len)?;
+        let bytes = decoder.borrow_reader().take_bytes(len)?;
+        Ok(padding.trim(bytes))
+    }
+}
+
+impl<'a, 'de: 'a> BorrowDecodeFixedLen<'de> for &'a str {
+    fn borrow_decode_fixed_len<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        let bytes = <&[u8]>::borrow_decode_fixed_len(decoder, len, padding)?;
+        super::str_from_utf8(bytes)
+    }
+}
+
 impl<'a, 'de: 'a, Context> BorrowDecode<'de, Context> for &'a str {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
         let slice = <&[u8]>::borrow_decode(decoder)?;
-        core::str::from_utf8(slice).map_err(|inner| DecodeError::Utf8 { inner })
+        super::str_from_utf8(slice)
+    }
+}
+
+impl<'a, 'de: 'a, Context> BorrowDecode<'de, Context> for &'a mut [u8] {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = super::decode_slice_len(decoder)?;
+        decoder.claim_bytes_read(len)?;
+        decoder.borrow_reader().take_bytes_mut(len)
+    }
+}
+
+impl<'a, 'de: 'a, Context> BorrowDecode<'de, Context> for &'a mut str {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let slice = <&mut [u8]>::borrow_decode(decoder)?;
+        super::str_from_utf8_mut(slice)
     }
 }
 
@@ -476,29 +575,42 @@ impl<Context, T, const N: usize> Decode<Context> for [T; N]
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE.saturating_mul(N);
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
 
-        if unty::type_equal::<T, u8>() {
-            let mut buf = [0u8; N];
-            decoder.reader().read(&mut buf)?;
-            let ptr = (&raw mut buf).cast::<[T; N]>();
+        if let Some(array) = crate::plain::decode_array(decoder, T::transparent_wire())? {
+            // optimize for reading arrays of primitives
+            return Ok(array);
+        }
 
-            // Safety: we know that T is a u8, so it is perfectly safe to
-            // translate an array of u8 into an array of T
-            let res = unsafe { ptr.read() };
-            Ok(res)
-        } else {
-            let result = super::impl_core::collect_into_array(&mut (0..N).map(|_| {
-                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-                T::decode(decoder)
-            }));
+        super::impl_core::try_from_fn(|_| {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            T::decode(decoder)
+        })
+    }
 
-            // result is only None if N does not match the values of `(0..N)`, which it always should
-            // So this unwrap should never occur
-            result.unwrap()
+    fn decode_in_place<D: Decoder<Context = Context>>(
+        decoder: &mut D,
+        place: &mut MaybeUninit<Self>,
+        _: Private,
+    ) -> Result<(), DecodeError> {
+        decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
+
+        if crate::plain::decode_array_in_place(decoder, place, T::transparent_wire())? {
+            return Ok(());
         }
+
+        super::impl_core::try_init_in_place(
+            super::impl_core::array_elements_mut(place),
+            |element| {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                T::decode_in_place(decoder, element, Private)
+            },
+        )
     }
 }
 
@@ -506,35 +618,50 @@ impl<'de, T, const N: usize, Context> BorrowDecode<'de, Context> for [T; N]
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE.saturating_mul(N);
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
 
-        if unty::type_equal::<T, u8>() {
-            let mut buf = [0u8; N];
-            decoder.reader().read(&mut buf)?;
-            let ptr = (&raw mut buf).cast::<[T; N]>();
+        if let Some(array) = crate::plain::decode_array(decoder, T::transparent_wire())? {
+            // optimize for reading arrays of primitives
+            return Ok(array);
+        }
 
-            // Safety: we know that T is a u8, so it is perfectly safe to
-            // translate an array of u8 into an array of T
-            let res = unsafe { ptr.read() };
-            Ok(res)
-        } else {
-            let result = super::impl_core::collect_into_array(&mut (0..N).map(|_| {
-                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-                T::borrow_decode(decoder)
-            }));
+        super::impl_core::try_from_fn(|_| {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            T::borrow_decode(decoder)
+        })
+    }
 
-            // result is only None if N does not match the values of `(0..N)`, which it always should
-            // So this unwrap should never occur
-            result.unwrap()
+    fn borrow_decode_in_place<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+        place: &mut MaybeUninit<Self>,
+        _: Private,
+    ) -> Result<(), DecodeError> {
+        decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
+
+        if crate::plain::decode_array_in_place(decoder, place, T::transparent_wire())? {
+            return Ok(());
         }
+
+        super::impl_core::try_init_in_place(
+            super::impl_core::array_elements_mut(place),
+            |element| {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                T::borrow_decode_in_place(decoder, element, Private)
+            },
+        )
     }
 }
 
 impl<Context> Decode<Context> for () {
+    const MIN_ENCODED_SIZE: usize = 0;
+
     fn decode<D: Decoder<Context = Context>>(_: &mut D) -> Result<Self, DecodeError> {
         Ok(())
     }
@@ -542,6 +669,8 @@ impl<Context> Decode<Context> for () {
 impl_borrow_decode!(());
 
 impl<Context, T> Decode<Context> for core::marker::PhantomData<T> {
+    const MIN_ENCODED_SIZE: usize = 0;
+
     fn decode<D: Decoder<Context = Context>>(_: &mut D) -> Result<Self, DecodeError> {
         Ok(Self)
     }
@@ -597,8 +726,8 @@ where
                 Ok(Err(u))
             }
             x => Err(DecodeError::UnexpectedVariant {
-                found: x,
-                allowed: &crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
+                found: u64::from(x),
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ok", 0), ("Err", 1)]),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -624,8 +753,8 @@ where
                 Ok(Err(u))
             }
             x => Err(DecodeError::UnexpectedVariant {
-                found: x,
-                allowed: &crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
+                found: u64::from(x),
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ok", 0), ("Err", 1)]),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -636,6 +765,8 @@ impl<Context, T> Decode<Context> for Cell<T>
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let t = T::decode(decoder)?;
         Ok(Self::new(t))
@@ -646,6 +777,8 @@ impl<'de, T, Context> BorrowDecode<'de, Context> for Cell<T>
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
@@ -658,6 +791,8 @@ impl<Context, T> Decode<Context> for RefCell<T>
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let t = T::decode(decoder)?;
         Ok(Self::new(t))
@@ -668,6 +803,8 @@ impl<'de, T, Context> BorrowDecode<'de, Context> for RefCell<T>
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
@@ -746,8 +883,12 @@ where
             1 => Ok(Self::Included(T::decode(decoder)?)),
             2 => Ok(Self::Excluded(T::decode(decoder)?)),
             x => Err(DecodeError::UnexpectedVariant {
-                allowed: &crate::error::AllowedEnumVariants::Range { max: 2, min: 0 },
-                found: x,
+                allowed: &crate::error::AllowedEnumVariants::Named(&[
+                    ("Unbounded", 0),
+                    ("Included", 1),
+                    ("Excluded", 2),
+                ]),
+                found: u64::from(x),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -766,8 +907,48 @@ where
             1 => Ok(Self::Included(T::borrow_decode(decoder)?)),
             2 => Ok(Self::Excluded(T::borrow_decode(decoder)?)),
             x => Err(DecodeError::UnexpectedVariant {
-                allowed: &crate::error::AllowedEnumVariants::Range { max: 2, min: 0 },
-                found: x,
+                allowed: &crate::error::AllowedEnumVariants::Named(&[
+                    ("Unbounded", 0),
+                    ("Included", 1),
+                    ("Excluded", 2),
+                ]),
+                found: u64::from(x),
+                type_name: core::any::type_name::<Self>(),
+            }),
+        }
+    }
+}
+
+impl<T, Context> Decode<Context> for Poll<T>
+where
+    T: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(Self::Ready(T::decode(decoder)?)),
+            1 => Ok(Self::Pending),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ready", 0), ("Pending", 1)]),
+                found: u64::from(x),
+                type_name: core::any::type_name::<Self>(),
+            }),
+        }
+    }
+}
+
+impl<'de, T, Context> BorrowDecode<'de, Context> for Poll<T>
+where
+    T: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(Self::Ready(T::borrow_decode(decoder)?)),
+            1 => Ok(Self::Pending),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ready", 0), ("Pending", 1)]),
+                found: u64::from(x),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -1,23 +1,43 @@
 //! Decoder-based structs and traits.
 
+mod collection;
 mod decoder;
+mod extend;
 mod impl_core;
 mod impl_tuples;
 mod impls;
+#[cfg(feature = "alloc")]
+pub(crate) mod reserve;
+mod skip;
 
 use self::{
-    decoder::WithContext,
-    read::{BorrowReader, Reader},
+    decoder::{EndianDecoder, WithContext},
+    read::{BorrowReader, Reader, SliceReader},
 };
 use crate::{
-    config::{Config, InternalLimitConfig},
+    config::{
+        BigEndian, Config, Endianness, FixedLenPadding, InternalEndianConfig,
+        InternalLengthEncodingConfig, InternalLimitConfig, LengthEncoding, LittleEndian, TagType,
+        TrailingBytes,
+    },
     error::DecodeError,
-    utils::Sealed,
+    utils::{Private, Sealed},
 };
+use core::mem::MaybeUninit;
 
 pub mod read;
 
-pub use self::decoder::DecoderImpl;
+/// The `ToOwned` of a `Cow`, for the bounds that the derive macros put on what a `Cow` field points to.
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub use alloc::borrow::ToOwned;
+
+pub use self::{
+    collection::CollectionDecoder,
+    decoder::{DecoderImpl, DecoderReader, PaddedDecoder, SectionDecoder, SubLimitDecoder},
+    extend::{DecodeExtend, Extended},
+    skip::{skip_remaining_bytes, SkipDecode},
+};
 
 /// Trait that makes a type able to be decoded, akin to serde's `DeserializeOwned` trait.
 ///
@@ -100,13 +120,187 @@ pub use self::decoder::DecoderImpl;
 ///     }
 /// # }
 /// ```
+///
+/// # Decoding the first fields of a struct
+///
+/// `#[bincode(prefix = "Name: field_a, field_b")]` on a struct that derives `Decode` or `BorrowDecode` generates a struct `Name` with the given fields, which must be the first fields of the struct, in order. It derives the same trait and copies the attributes of the fields, so it can be decoded from the start of an encoded value of the struct, e.g. to read a header without the body after it. `Name::decode_prefix` (or `Name::decode_prefix_with_context`) also returns how many bytes the fields used:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(prefix = "Header: version, kind")]
+/// struct Message {
+///     version: u8,
+///     kind: u16,
+///     body: Vec<u8>,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let encoded = bincode::encode_to_vec(Message { version: 1, kind: 2, body: vec![3; 10] }, config).unwrap();
+/// let (header, len) = Header::decode_prefix(&encoded, config).unwrap();
+/// assert_eq!((header.version, header.kind), (1, 2));
+/// assert_eq!(&encoded[len..], [10, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3]);
+/// ```
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(prefix = "Header: version, body")]
+/// struct Message {
+///     version: u8,
+///     kind: u16,
+///     body: Vec<u8>,
+/// }
+/// ```
+///
+/// # Discriminants
+///
+/// The variants of a derived enum are tagged 0, 1, 2, ... in order. `#[bincode(discriminant = N)]` on a variant sets its tag to any `u64` literal instead, and the variants after it continue from `N + 1`. The tag is encoded as a `u32` with the int encoding of the config, or as a `u64` if a tag does not fit in a `u32`, so e.g. hashes can be used as tags. An unknown tag is reported as `DecodeError::UnexpectedVariant` with the full tag:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+///
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// enum Message {
+///     #[bincode(discriminant = 0x9E37_79B9_7F4A_7C15)]
+///     Ping,
+///     #[bincode(discriminant = 0xC2B2_AE3D_27D4_EB4F)]
+///     Data(u8),
+/// }
+///
+/// let config = bincode::config::legacy();
+/// let encoded = bincode::encode_to_vec(Message::Data(7), config).unwrap();
+/// assert_eq!(encoded, [0x4F, 0xEB, 0xD4, 0x27, 0x3D, 0xAE, 0xB2, 0xC2, 7]);
+///
+/// let unknown = 0x1234_5678_9ABC_DEF0u64.to_le_bytes();
+/// let err = bincode::decode_from_slice::<Message, _>(&unknown, config).unwrap_err();
+/// assert!(matches!(
+///     err.into_inner(),
+///     DecodeError::UnexpectedVariant { found: 0x1234_5678_9ABC_DEF0, .. }
+/// ));
+/// ```
+///
+/// # Unknown variants
+///
+/// `#[bincode(other)]` on a variant decodes every tag that is not the tag of another variant into it, instead of failing with `DecodeError::UnexpectedVariant`, so a message with a variant that a newer version added can still be decoded. The variant has no fields, or a single field that holds the tag, like `Unknown(u32)`, and is encoded with that tag again. The field is a `u32`, or a `u64` if the tags are encoded as a `u64`.
+///
+/// Only the tag of an unknown variant is known, not how many bytes its fields take, so an enum with fields needs `#[bincode(length_prefixed)]`, which writes the length of the enum in bytes in front of it, like for a struct. The rest of an unknown variant is then skipped:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// #[bincode(length_prefixed)]
+/// enum Event {
+///     Start { id: u32 },
+///     Stop,
+///     #[bincode(other)]
+///     Unknown(u32),
+/// }
+///
+/// // a newer version of `Event`, which added a variant
+/// #[derive(bincode::Encode)]
+/// #[bincode(length_prefixed)]
+/// enum NewEvent {
+///     Start { id: u32 },
+///     Stop,
+///     Pause { seconds: u64 },
+/// }
+///
+/// let config = bincode::config::standard();
+/// let encoded = bincode::encode_to_vec([NewEvent::Pause { seconds: 30 }, NewEvent::Stop], config).unwrap();
+/// let (events, _): ([Event; 2], _) = bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(events, [Event::Unknown(2), Event::Stop]);
+/// ```
+///
+/// Without it, `other` can only be used in an enum whose variants have no fields:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// enum Event {
+///     Start { id: u32 },
+///     #[bincode(other)]
+///     Unknown(u32),
+/// }
+/// ```
+///
+/// # Trait objects
+///
+/// `Box<[T]>`, `Rc<[T]>`, `Arc<[T]>`, `Rc<str>` and `Arc<str>` are decoded into a single allocation of exactly their length when the input is known to be long enough. A trait object like `Box<dyn Trait>` can not be decoded at all, as the input does not say which type to decode behind it, so deriving `Decode` or `BorrowDecode` for a type with such a field fails. Decode an enum of the types that implement the trait instead, or implement `Decode` manually:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// trait Shape {}
+///
+/// #[derive(bincode::Decode)]
+/// struct Drawing {
+///     shapes: Vec<Box<dyn Shape>>,
+/// }
+/// ```
 pub trait Decode<Context>: Sized {
+    /// The least amount of bytes that an encoded value of this type takes, with any config.
+    ///
+    /// Containers claim this many bytes for every entry against the [limit](crate::config::Configuration::with_limit) before decoding them, see [`Decoder`]. The default of 1 fits any type that reads at least one byte. Override it for types that always take more, e.g. `f64` takes 8 bytes and `[u8; 32]` takes 32, so a container of them reaches the limit as soon as its length could not fit. It must never be more than the encoded size of any value, or valid input could exceed the limit.
+    const MIN_ENCODED_SIZE: usize = 1;
+
     /// Attempt to decode this type with the given [`Decode`\].
     ///
     /// # Errors
     ///
     /// Returns any error encountered during decoding.
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError>;
+
+    /// Decode this type into `place`, which is initialized if this returns `Ok`.
+    ///
+    /// `Box<T>`, `Rc<T>` and `Arc<T>` allocate room for a `T` and decode it with this, so a large `T` does not have to fit on the stack. By default this decodes the value with [`decode`](Self::decode) and moves it into `place`. Arrays decode their elements directly into `place`, so e.g. a `Box<[f64; 1_000_000]>` or a `Box<[[u8; 4096]; 4096]>` is never on the stack.
+    ///
+    /// The allocation is assumed to be initialized when this returns `Ok`, so only this crate can override this. Its last argument has a type that can not be named outside of the crate:
+    ///
+    /// ```compile_fail
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{Decode, Decoder};
+    /// use bincode::error::DecodeError;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// struct Empty;
+    ///
+    /// impl<Context> Decode<Context> for Empty {
+    ///     fn decode<D: Decoder<Context = Context>>(_: &mut D) -> Result<Self, DecodeError> {
+    ///         Ok(Self)
+    ///     }
+    ///
+    ///     fn decode_in_place<D: Decoder<Context = Context>>(
+    ///         _: &mut D,
+    ///         _: &mut MaybeUninit<Self>,
+    ///         _: bincode::utils::Private,
+    ///     ) -> Result<(), DecodeError> {
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered during decoding. `place` is left uninitialized, and the parts of the value that were decoded before the error are dropped.
+    #[doc(hidden)]
+    #[inline]
+    fn decode_in_place<D: Decoder<Context = Context>>(
+        decoder: &mut D,
+        place: &mut MaybeUninit<Self>,
+        _: Private,
+    ) -> Result<(), DecodeError> {
+        place.write(Self::decode(decoder)?);
+        Ok(())
+    }
+
+    /// Returns `Some` if this type implements [`TransparentWire`](crate::TransparentWire), so collections of it can be decoded like collections of its wire type. Only `#[derive(TransparentWire)]` overrides this.
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    fn transparent_wire() -> Option<crate::transparent::Transparent<Self>> {
+        None
+    }
 }
 
 /// Trait that makes a type able to be decoded, akin to serde's `Deserialize` trait.
@@ -114,7 +308,89 @@ pub trait Decode<Context>: Sized {
 /// This trait should be implemented for types that contain borrowed data, like `&str` and `&[u8]`. If your type does not have borrowed data, consider implementing [`Decode`\] instead.
 ///
 /// This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::Decode)]` to a type with a lifetime.
+///
+/// A derived `BorrowDecode` borrows every field it can from the input. A field with `#[bincode(owned)]` is decoded with [`Decode`\] instead, e.g. as a `Cow::Owned`, and its lifetime is not tied to the input if no other field uses it. A field with `#[bincode(borrow)]` is always decoded with `BorrowDecode`, so its type can only derive `BorrowDecode`. Only a `Cow<'a, str>` and a `Cow<'a, [u8]>` can borrow from the input, so other `Cow` fields, like a `Cow<'a, MyStruct>` or a `Cow<'a, [u32]>`, are decoded as if they had `#[bincode(owned)]`, unless they have `#[bincode(borrow)]`:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use std::borrow::Cow;
+///
+/// #[derive(bincode::Encode, bincode::BorrowDecode)]
+/// struct Entry<'a> {
+///     #[bincode(borrow)]
+///     key: Cow<'a, str>,
+///     #[bincode(owned)]
+///     value: Cow<'a, str>,
+/// }
+///
+/// let encoded = bincode::encode_to_vec(
+///     Entry { key: "key".into(), value: "value".into() },
+///     bincode::config::standard(),
+/// )
+/// .unwrap();
+/// let (entry, _): (Entry, _) =
+///     bincode::borrow_decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+/// assert!(matches!(entry.key, Cow::Borrowed("key")));
+/// assert!(matches!(entry.value, Cow::Owned(_)));
+/// ```
+///
+/// A reference can not be `owned`, use a `Cow` instead:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::BorrowDecode)]
+/// struct Entry<'a> {
+///     #[bincode(owned)]
+///     key: &'a str,
+/// }
+/// ```
+///
+/// And a type with `borrow` fields can not derive `Decode`:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// # use std::borrow::Cow;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// struct Entry<'a> {
+///     #[bincode(borrow)]
+///     key: Cow<'a, str>,
+/// }
+/// ```
+///
+/// # Borrowed maps
+///
+/// The keys and values of a `HashMap`, `BTreeMap` or `HashSet` can borrow from the input like any other field, so a read-only map does not allocate a `String` for every key. The hasher of a `HashMap` is created with `Default`, like by the `Decode` implementation:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use std::collections::HashMap;
+///
+/// #[derive(bincode::Encode, bincode::BorrowDecode)]
+/// struct Archive<'a> {
+///     files: HashMap<&'a str, &'a [u8]>,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let archive = Archive { files: HashMap::from([("a.txt", &b"abc"[..])]) };
+/// let encoded = bincode::encode_to_vec(&archive, config).unwrap();
+/// let (archive, _): (Archive, _) = bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(archive.files["a.txt"], b"abc");
+/// ```
+///
+/// Such a type can only be decoded with `BorrowDecode`, so deriving `Decode` for it fails, pointing at the reference:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// # use std::collections::HashMap;
+/// #[derive(bincode::Decode)]
+/// struct Archive<'a> {
+///     files: HashMap<&'a str, &'a [u8]>,
+/// }
+/// ```
 pub trait BorrowDecode<'de, Context>: Sized {
+    /// The least amount of bytes that an encoded value of this type takes, with any config. See [`Decode::MIN_ENCODED_SIZE`].
+    const MIN_ENCODED_SIZE: usize = 1;
+
     /// Attempt to decode this type with the given [`BorrowDecode`\].
     ///
     /// # Errors
@@ -123,6 +399,30 @@ pub trait BorrowDecode<'de, Context>: Sized {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError>;
+
+    /// Borrow-decode this type into `place`, which is initialized if this returns `Ok`. See [`Decode::decode_in_place`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered during decoding. `place` is left uninitialized, and the parts of the value that were decoded before the error are dropped.
+    #[doc(hidden)]
+    #[inline]
+    fn borrow_decode_in_place<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+        place: &mut MaybeUninit<Self>,
+        _: Private,
+    ) -> Result<(), DecodeError> {
+        place.write(Self::borrow_decode(decoder)?);
+        Ok(())
+    }
+
+    /// Returns `Some` if this type implements [`TransparentWire`](crate::TransparentWire). See [`Decode::transparent_wire`].
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    fn transparent_wire() -> Option<crate::transparent::Transparent<Self>> {
+        None
+    }
 }
 
 /// Helper macro to implement `BorrowDecode` for any type that implements `Decode`.
@@ -130,6 +430,8 @@ pub trait BorrowDecode<'de, Context>: Sized {
 macro_rules! impl_borrow_decode {
     ($ty:ty $(, $param:tt)*) => {
         impl<'de $(, $param)*, __Context> $crate::BorrowDecode<'de, __Context> for $ty {
+            const MIN_ENCODED_SIZE: usize = <Self as $crate::Decode<__Context>>::MIN_ENCODED_SIZE;
+
             fn borrow_decode<D: $crate::de::BorrowDecoder<'de, Context = __Context>>(
                 decoder: &mut D,
             ) -> core::result::Result<Self, $crate::error::DecodeError> {
@@ -144,6 +446,8 @@ macro_rules! impl_borrow_decode {
 macro_rules! impl_borrow_decode_with_context {
     ($ty:ty, $context:ty $(, $param:tt)*) => {
         impl<'de $(, $param)*> $crate::BorrowDecode<'de, $context> for $ty {
+            const MIN_ENCODED_SIZE: usize = <Self as $crate::Decode<$context>>::MIN_ENCODED_SIZE;
+
             fn borrow_decode<D: $crate::de::BorrowDecoder<'de, Context = $context>>(
                 decoder: &mut D,
             ) -> core::result::Result<Self, $crate::error::DecodeError> {
@@ -154,6 +458,16 @@ macro_rules! impl_borrow_decode_with_context {
 }
 
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
+///
+/// # Limits
+///
+/// If the config has a [limit](crate::config::Configuration::with_limit), the decoder keeps track of the bytes that are claimed, so that a malformed length can not make the decoder allocate more memory than the limit allows. Every implementation of [Decode] claims the bytes that it reads before reading them, including the tags of enums, so deeply nested values reach the limit before more input than the limit is read. Implementations of [Decode] for containers take part in this in three steps:
+///
+/// 1. Before allocating room for `len` entries, claim them with [`claim_container_entries`](Decoder::claim_container_entries), passing the [`MIN_ENCODED_SIZE`](Decode::MIN_ENCODED_SIZE) of the entries. This fails with `DecodeError::LimitExceeded` if they do not fit in the limit. Basing the claim on the encoded size instead of the size in memory makes the limit behave the same for every entry type: a length that the input could hold is never rejected up front, whether the entries are `u8`, `Box<u8>` or `String`.
+/// 2. Before decoding every entry, un-claim it again with [`unclaim_container_entry`](Decoder::unclaim_container_entry). The entry claims its own bytes while it is decoded, so without this it would be counted twice.
+/// 3. When the container is done, all of its claims are balanced, and only the bytes that its entries claimed are left.
+///
+/// [`CollectionDecoder`] takes care of these steps. Use [`limit_remaining`](Decoder::limit_remaining) to find out how much is left before claiming, e.g. to fail early or to pick a smaller initial capacity.
 pub trait Decoder: Sealed {
     /// The concrete [Reader] type
     type R: Reader;
@@ -189,15 +503,108 @@ pub trait Decoder: Sealed {
     /// Returns `DecodeError::LimitExceeded` if the limit is exceeded.
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError>;
 
-    /// Claim that we're going to read a container which contains `len` entries of `T`.
-    /// This will correctly handle overflowing if `len * size_of::<T>() > usize::max_value`
+    /// Returns the amount of bytes that are currently claimed with [`claim_bytes_read`](Decoder::claim_bytes_read) and not un-claimed yet.
+    ///
+    /// Claims are only counted if the config has a limit, so this is always 0 without one, except in a scope of [`with_sub_limit`](Decoder::with_sub_limit).
+    fn bytes_claimed(&self) -> usize;
+
+    /// Discard the next `n` bytes of the input, e.g. a section of a newer version of a type that this version does not know about. The bytes are claimed against the limit like any other bytes that are read.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[1, 2, 3, 4]), bincode::config::standard(), ());
+    /// decoder.skip_bytes(3).unwrap();
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 4);
+    /// ```
     ///
     /// # Errors
     ///
-    /// Returns `DecodeError::LimitExceeded` if the limit is exceeded or if `len * size_of::<T>()` overflows.
-    fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
-        if <Self::C as InternalLimitConfig>::LIMIT.is_some() {
-            len.checked_mul(core::mem::size_of::<T>())
+    /// Returns `DecodeError::LimitExceeded` if the limit is exceeded, or `DecodeError::UnexpectedEnd` if the input does not have `n` more bytes.
+    fn skip_bytes(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.claim_bytes_read(n)?;
+        self.reader().skip(n)
+    }
+
+    /// Decodes all remaining bytes of the input, without a length in front of them. This is how fields with `#[bincode(remaining_bytes)]` are decoded.
+    ///
+    /// This only works with readers that know where the input ends (see [`Reader::remaining_hint`]), like [`SliceReader`](read::SliceReader) and [`TakeReader`](read::TakeReader). Wrap a stream in a `TakeReader` if the length of the message is known from its framing.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[7, 1, 2, 3]), bincode::config::standard(), ());
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+    /// assert_eq!(decoder.take_remaining_bytes().unwrap(), [1, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::RemainingBytesUnknown` if the reader does not know where the input ends, or `DecodeError::LimitExceeded` if the bytes exceed the limit.
+    #[cfg(feature = "alloc")]
+    fn take_remaining_bytes(&mut self) -> Result<alloc::vec::Vec<u8>, DecodeError>
+    where
+        Self: Sized,
+    {
+        let len = claim_remaining_bytes(self)?;
+        crate::plain::decode_byte_vec(self, len)
+    }
+
+    /// Returns the amount of bytes that can still be claimed before the limit is exceeded, or `None` if the config has no limit and the decoder is not in a scope of [`with_sub_limit`](Decoder::with_sub_limit).
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let config = bincode::config::standard().with_limit::<100>();
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[5, 6]), config, ());
+    /// assert_eq!(decoder.limit_remaining(), Some(100));
+    /// let _ = u8::decode(&mut decoder).unwrap();
+    /// assert_eq!(decoder.bytes_claimed(), 1);
+    /// assert_eq!(decoder.limit_remaining(), Some(99));
+    ///
+    /// let config = bincode::config::standard();
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[5, 6]), config, ());
+    /// assert_eq!(decoder.limit_remaining(), None);
+    /// ```
+    fn limit_remaining(&self) -> Option<usize> {
+        self.config()
+            .byte_limit()
+            .map(|limit| limit.saturating_sub(self.bytes_claimed()))
+    }
+
+    /// Claim that we're going to read a container which contains `len` entries of at least `entry_size` bytes each, usually the [`MIN_ENCODED_SIZE`](Decode::MIN_ENCODED_SIZE) of the entries.
+    /// This will correctly handle overflowing if `len * entry_size > usize::max_value`
+    ///
+    /// Every entry is counted as at least one byte, even if it is a zero-sized type like `()`. Otherwise a container of zero-sized types could claim any length without reaching the limit, and decoding it would take forever.
+    ///
+    /// Use [`unclaim_container_entry`](Decoder::unclaim_container_entry) for every entry that is decoded, to keep the claimed bytes balanced.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// let config = bincode::config::standard().with_limit::<100>();
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[]), config, ());
+    /// // 12 entries of at least 8 bytes fit in the limit, one more does not
+    /// let entry_size = <f64 as Decode<()>>::MIN_ENCODED_SIZE;
+    /// decoder.claim_container_entries(12, entry_size).unwrap();
+    /// assert_eq!(decoder.bytes_claimed(), 96);
+    /// assert!(decoder.claim_container_entries(1, entry_size).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::LimitExceeded` if the limit is exceeded or if `len * entry_size` overflows.
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        if <Self::C as InternalLimitConfig>::LIMITED {
+            len.checked_mul(entry_size.max(1))
                 .map_or(Err(DecodeError::LimitExceeded), |val| {
                     self.claim_bytes_read(val)
                 })
@@ -206,6 +613,31 @@ pub trait Decoder: Sealed {
         }
     }
 
+    /// Un-claim the bytes that [`claim_container_entries`](Decoder::claim_container_entries) claimed for a single entry of `entry_size` bytes. Call this before decoding every entry of the container.
+    ///
+    /// See [`unclaim_bytes_read`](Decoder::unclaim_bytes_read) as to why this is needed.
+    fn unclaim_container_entry(&mut self, entry_size: usize) {
+        self.unclaim_bytes_read(entry_size.max(1));
+    }
+
+    /// Claim that we're going to read a container which contains `len` entries of `T`, counting every entry as `size_of::<T>()` bytes.
+    ///
+    /// Prefer [`claim_container_entries`](Decoder::claim_container_entries) with the [`MIN_ENCODED_SIZE`](Decode::MIN_ENCODED_SIZE) of `T`: the size of `T` in memory has little to do with the size of its encoding, so e.g. a `Vec<String>` would reach the limit with far fewer entries than a `Vec<u8>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::LimitExceeded` if the limit is exceeded or if `len * size_of::<T>()` overflows.
+    fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
+        self.claim_container_entries(len, core::mem::size_of::<T>())
+    }
+
+    /// Un-claim the bytes that [`claim_container_read`](Decoder::claim_container_read) claimed for a single entry of `T`. Call this before decoding every entry of the container.
+    ///
+    /// See [`unclaim_bytes_read`](Decoder::unclaim_bytes_read) as to why this is needed.
+    fn unclaim_container_element<T>(&mut self) {
+        self.unclaim_container_entry(core::mem::size_of::<T>());
+    }
+
     /// Notify the decoder that `n` bytes are being reclaimed.
     ///
     /// When decoding container types, a typical implementation would claim to read `len * size_of::<T>()` bytes.
@@ -266,6 +698,150 @@ pub trait Decoder: Sealed {
     /// }
     /// ```
     fn unclaim_bytes_read(&mut self, n: usize);
+
+    /// Decode a value with `f` from the next `N` bytes, which were written by [`Encoder::with_padding`](crate::enc::Encoder::with_padding). `f` decodes the value from the start of the `N` bytes, and the padding after it is skipped. If `strict` is set, the padding must only contain zero bytes.
+    ///
+    /// The bytes are read into a buffer of `N` bytes on the stack first. This is used by `#[derive(Decode)]` for fields with `#[bincode(pad_to = N)]` and structs with `#[bincode(pad_struct_to = N)]`, and `#[bincode(strict_padding)]` sets `strict`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, `DecodeError::UnexpectedEnd` if `f` reads more than `N` bytes, `DecodeError::NonZeroPadding` if `strict` is set and the padding is not zero, or an error if the reader fails.
+    fn with_padding<const N: usize, T>(
+        &mut self,
+        strict: bool,
+        f: impl FnOnce(&mut PaddedDecoder<'_, Self, SliceReader<'_>>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+    {
+        let mut bytes = [0u8; N];
+        self.reader().read(&mut bytes)?;
+        let mut decoder = PaddedDecoder {
+            decoder: self,
+            reader: SliceReader::new(&bytes),
+        };
+        let value = f(&mut decoder)?;
+        skip_padding(decoder.decoder, N, decoder.reader.slice, strict)?;
+        Ok(value)
+    }
+
+    /// Decode a section with `f`, which was written by [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix): the length of the section as a `u64` of 8 bytes in the endianness of the config, followed by the section.
+    ///
+    /// `f` can not read past the end of the section, and its reader knows where the section ends, so [`take_remaining_bytes`](Decoder::take_remaining_bytes) returns the rest of the section. The bytes of the section that `f` does not read are skipped, so a newer version of a type can add fields at the end of its section. This is used by `#[derive(Decode)]` for structs and enums with `#[bincode(length_prefixed)]`.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    ///
+    /// // a section of 3 bytes, followed by another byte
+    /// let bytes = [3, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4];
+    ///
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard(), ());
+    /// // only the first byte of the section is decoded, the others are skipped
+    /// let first = decoder.with_length_prefix(|d| u8::decode(d)).unwrap();
+    /// assert_eq!(first, 1);
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 4);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, `DecodeError::TakeLimitExceeded` if `f` reads past the end of the section, or an error if the reader fails.
+    fn with_length_prefix<T>(
+        &mut self,
+        f: impl FnOnce(&mut SectionDecoder<'_, Self>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+    {
+        self.claim_bytes_read(8)?;
+        let mut bytes = [0u8; 8];
+        self.reader().read(&mut bytes)?;
+        let len = match Self::C::ENDIAN {
+            Endianness::Little => u64::from_le_bytes(bytes),
+            Endianness::Big => u64::from_be_bytes(bytes),
+        };
+        let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+        let mut decoder = SectionDecoder::new(self, len);
+        let value = f(&mut decoder)?;
+        let rest = decoder.reader().remaining();
+        decoder.skip_bytes(rest)?;
+        Ok(value)
+    }
+
+    /// Decode a value with `f`, which may claim at most `limit` bytes, e.g. so a header can not use up the limit that the body of a message needs.
+    ///
+    /// The claims of `f` count against the limit of this decoder as well, and stay claimed after `f` returns, so the rest of the message has the limit that is left. Scopes can be nested. A claim that exceeds the limit of the scope fails with [`DecodeError::SubLimitExceeded`\] with the name of the `scope`, and a claim that exceeds the limit of an outer scope or of the config fails with the error of that limit.
+    ///
+    /// The limit of the scope is checked whether or not the config has a [limit](crate::config::Configuration::with_limit), so a scope also bounds the claims of `f` with an unlimited config like [`standard`](crate::config::standard).
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    /// use bincode::error::DecodeError;
+    ///
+    /// let config = bincode::config::standard().with_limit::<1000>();
+    /// let bytes = bincode::encode_to_vec((String::from("name"), vec![0u8; 500]), config).unwrap();
+    ///
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    /// let (name, claimed) = decoder
+    ///     .with_sub_limit("header", 16, |d| Ok((String::decode(d)?, d.bytes_claimed())))
+    ///     .unwrap();
+    /// assert_eq!(name, "name");
+    /// // the bytes claimed by the header are no longer available for the body
+    /// assert_eq!(decoder.limit_remaining(), Some(1000 - claimed));
+    /// let body: Vec<u8> = Vec::decode(&mut decoder).unwrap();
+    /// assert_eq!(body.len(), 500);
+    ///
+    /// // the header may not use more than 2 bytes
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    /// let err = decoder.with_sub_limit("header", 2, |d| String::decode(d)).unwrap_err();
+    /// assert!(matches!(err, DecodeError::SubLimitExceeded { scope: "header", limit: 2 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`.
+    fn with_sub_limit<T>(
+        &mut self,
+        scope: &'static str,
+        limit: usize,
+        f: impl FnOnce(&mut SubLimitDecoder<'_, Self>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+    {
+        f(&mut SubLimitDecoder::new(self, scope, limit))
+    }
+
+    /// Marks that the following bytes belong to the field or value `name`, until the matching [`leave`](Decoder::leave), see [`Reader::enter`]. Derived implementations call this for every field when the `stats` feature is enabled. Without the feature, the derive does not emit any markers.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.reader().enter(name);
+    }
+
+    /// Ends the innermost [`enter`](Decoder::enter) marker.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.reader().leave();
+    }
+}
+
+/// Claims the `padding` after a value with a fixed size of `pad_to` bytes, and checks that it is zero if `strict` is set.
+fn skip_padding<D: Decoder>(
+    decoder: &mut D,
+    pad_to: usize,
+    padding: &[u8],
+    strict: bool,
+) -> Result<(), DecodeError> {
+    if strict && padding.iter().any(|&byte| byte != 0) {
+        return Err(DecodeError::NonZeroPadding {
+            pad_to,
+            size: pad_to - padding.len(),
+        });
+    }
+    decoder.claim_bytes_read(padding.len())
 }
 
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
@@ -277,6 +853,42 @@ pub trait BorrowDecoder<'de>: Decoder {
 
     /// Returns a mutable reference to the borrow reader
     fn borrow_reader(&mut self) -> &mut Self::BR;
+
+    /// Borrows all remaining bytes of the input, without a length in front of them. See [`Decoder::take_remaining_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::RemainingBytesUnknown` if the reader does not know where the input ends, or `DecodeError::LimitExceeded` if the bytes exceed the limit.
+    fn borrow_remaining_bytes(&mut self) -> Result<&'de [u8], DecodeError>
+    where
+        Self: Sized,
+    {
+        let len = claim_remaining_bytes(self)?;
+        self.borrow_reader().take_bytes(len)
+    }
+
+    /// Decode a value with `f` from the next `N` bytes, like [`Decoder::with_padding`], but borrows the bytes from the input instead of reading them into a buffer, so `f` can decode borrowed data. This is used by `#[derive(BorrowDecode)]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, `DecodeError::UnexpectedEnd` if `f` reads more than `N` bytes, `DecodeError::NonZeroPadding` if `strict` is set and the padding is not zero, or an error if the reader fails.
+    fn borrow_with_padding<const N: usize, T>(
+        &mut self,
+        strict: bool,
+        f: impl FnOnce(&mut PaddedDecoder<'_, Self, SliceReader<'de>>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+    {
+        let bytes = self.borrow_reader().take_bytes(N)?;
+        let mut decoder = PaddedDecoder {
+            decoder: self,
+            reader: SliceReader::new(bytes),
+        };
+        let value = f(&mut decoder)?;
+        skip_padding(decoder.decoder, N, decoder.reader.slice, strict)?;
+        Ok(value)
+    }
 }
 
 impl<T> Decoder for &mut T
@@ -302,6 +914,22 @@ where
         T::claim_bytes_read(self, n)
     }
 
+    fn claim_container_entries(
+        &mut self,
+        len: usize,
+        entry_size: usize,
+    ) -> Result<(), DecodeError> {
+        T::claim_container_entries(self, len, entry_size)
+    }
+
+    fn bytes_claimed(&self) -> usize {
+        T::bytes_claimed(self)
+    }
+
+    fn limit_remaining(&self) -> Option<usize> {
+        T::limit_remaining(self)
+    }
+
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
         T::unclaim_bytes_read(self, n);
@@ -323,6 +951,127 @@ where
     }
 }
 
+/// A type that can be decoded from all remaining bytes of the input, for fields with `#[bincode(remaining_bytes)]`.
+///
+/// Such a field is encoded as its bytes, without a length in front of them, so it can only be decoded when the end of the input is known, e.g. with [`decode_from_slice`](crate::decode_from_slice) or [`decode_from_std_read_take`](crate::decode_from_std_read_take). The value that contains it must be the last value of the input, and the field must be the last field of its type:
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// struct Message {
+///     #[bincode(remaining_bytes)]
+///     payload: Vec<u8>,
+///     checksum: u32,
+/// }
+/// ```
+///
+/// This is implemented for `Vec<u8>`, `&[u8]` and, with the `bytes` feature, `Bytes`.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// struct Message {
+///     kind: u8,
+///     #[bincode(remaining_bytes)]
+///     payload: Vec<u8>,
+/// }
+///
+/// let message = Message { kind: 3, payload: vec![1, 2, 3] };
+/// let encoded = bincode::encode_to_vec(&message, bincode::config::standard()).unwrap();
+/// assert_eq!(encoded, [3, 1, 2, 3]);
+///
+/// let (decoded, _): (Message, _) =
+///     bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+/// assert_eq!(decoded, message);
+/// ```
+pub trait DecodeRemaining: Sized {
+    /// Decodes all remaining bytes of the input, see [`Decoder::take_remaining_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::RemainingBytesUnknown` if the reader does not know where the input ends.
+    fn decode_remaining<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError>;
+}
+
+/// A type that can be decoded from all remaining bytes of the input, borrowing them if it can. See [`DecodeRemaining`].
+pub trait BorrowDecodeRemaining<'de>: Sized {
+    /// Decodes all remaining bytes of the input, see [`BorrowDecoder::borrow_remaining_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::RemainingBytesUnknown` if the reader does not know where the input ends.
+    fn borrow_decode_remaining<D: BorrowDecoder<'de>>(decoder: &mut D)
+        -> Result<Self, DecodeError>;
+}
+
+/// A type that can be decoded from exactly `len` bytes without a length in front of them, for fields with `#[bincode(len = N)]`.
+///
+/// See [`FixedLenPadding`](crate::config::FixedLenPadding) for how the field is padded.
+///
+/// This is implemented for `Vec<u8>` and `String`. [`BorrowDecodeFixedLen`] is also implemented for `&[u8]` and `&str`.
+pub trait DecodeFixedLen: Sized {
+    /// Decodes exactly `len` bytes, and strips the `padding` from the end of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::UnexpectedEnd` if the input has less than `len` bytes, or `DecodeError::Utf8` if a string is not valid UTF-8.
+    fn decode_fixed_len<D: Decoder>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError>;
+}
+
+/// A type that can be decoded from exactly `len` bytes, borrowing them if it can. See [`DecodeFixedLen`].
+pub trait BorrowDecodeFixedLen<'de>: Sized {
+    /// Decodes exactly `len` bytes, and strips the `padding` from the end of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::UnexpectedEnd` if the input has less than `len` bytes, or `DecodeError::Utf8` if a string is not valid UTF-8.
+    fn borrow_decode_fixed_len<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError>;
+}
+
+/// Decodes the option bitmap of a struct with `#[bincode(option_bitmap)]` and its `N` options.
+///
+/// The bitmap was written by [`encode_option_bitmap`](crate::enc::encode_option_bitmap). Returns whether each option is `Some`.
+///
+/// # Errors
+///
+/// Returns `DecodeError::InvalidOptionBitmap` if a bit is set after the last option, or an error if the reader fails.
+pub fn decode_option_bitmap<D: Decoder, const N: usize>(
+    decoder: &mut D,
+) -> Result<[bool; N], DecodeError> {
+    let mut present = [false; N];
+    for options in present.chunks_mut(8) {
+        let byte = <u8 as Decode<D::Context>>::decode(decoder)?;
+        if u16::from(byte) >> options.len() != 0 {
+            return Err(DecodeError::InvalidOptionBitmap {
+                options: N,
+                found: byte,
+            });
+        }
+        for (bit, is_some) in options.iter_mut().enumerate() {
+            *is_some = byte & (1 << bit) != 0;
+        }
+    }
+    Ok(present)
+}
+
+/// Claims the remaining bytes of the input, and returns how many there are.
+pub(crate) fn claim_remaining_bytes<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+    let len = decoder
+        .reader()
+        .remaining_hint()
+        .ok_or(DecodeError::RemainingBytesUnknown)?;
+    decoder.claim_bytes_read(len)?;
+    Ok(len)
+}
+
 /// Decodes only the option variant from the decoder. Will not read any more data than that.
 #[inline]
 pub(crate) fn decode_option_variant<D: Decoder>(
@@ -334,17 +1083,306 @@ pub(crate) fn decode_option_variant<D: Decoder>(
         0 => Ok(None),
         1 => Ok(Some(())),
         x => Err(DecodeError::UnexpectedVariant {
-            found: u32::from(x),
-            allowed: &crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
+            found: u64::from(x),
+            allowed: &crate::error::AllowedEnumVariants::Named(&[("None", 0), ("Some", 1)]),
             type_name,
         }),
     }
 }
 
+/// Decodes the tag of an enum variant that was encoded as `tag_type`. This is used by `#[derive(Decode)]` for enums with `#[bincode(tag_type = "...")]`, see [`TagType`].
+///
+/// # Errors
+///
+/// Returns an error if the reader fails or the varint is invalid.
+pub fn decode_variant_tag<D: Decoder>(
+    decoder: &mut D,
+    tag_type: TagType,
+) -> Result<u32, DecodeError> {
+    match tag_type {
+        TagType::U8 => u8::decode(decoder).map(u32::from),
+        TagType::U16 => {
+            decoder.claim_bytes_read(2)?;
+            let mut bytes = [0u8; 2];
+            decoder.reader().read(&mut bytes)?;
+            Ok(u32::from(match D::C::ENDIAN {
+                Endianness::Little => u16::from_le_bytes(bytes),
+                Endianness::Big => u16::from_be_bytes(bytes),
+            }))
+        }
+        TagType::U32 => {
+            decoder.claim_bytes_read(4)?;
+            let mut bytes = [0u8; 4];
+            decoder.reader().read(&mut bytes)?;
+            Ok(match D::C::ENDIAN {
+                Endianness::Little => u32::from_le_bytes(bytes),
+                Endianness::Big => u32::from_be_bytes(bytes),
+            })
+        }
+        TagType::Varint => {
+            decoder.claim_bytes_read(4)?;
+            crate::varint::varint_decode_u32(decoder.reader(), D::C::ENDIAN)
+        }
+    }
+}
+
+/// Decodes the name of a variant of an enum with `#[bincode(string_tags)]`, and returns the index of the name in `names`. This is used by `#[derive(Decode)]` and `#[derive(SkipDecode)]`.
+///
+/// If the reader can [peek](Reader::peek_read) the name, like a [`SliceReader`], it is compared where it is in the input. Otherwise it is read and compared in parts. A name that is in `names` is never allocated.
+///
+/// # Errors
+///
+/// Returns `DecodeError::UnexpectedVariantName` if the name is not in `names`, or `DecodeError::Other` without the `alloc` feature. Returns an error if the reader fails.
+pub fn decode_variant_name<D: Decoder>(
+    decoder: &mut D,
+    type_name: &'static str,
+    names: &'static [&'static str],
+) -> Result<usize, DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    decoder.claim_bytes_read(len)?;
+    if let Some(bytes) = read::peek_exact(decoder.reader(), len) {
+        let index = names.iter().position(|name| name.as_bytes() == bytes);
+        let result = index.ok_or_else(|| unexpected_variant_name(type_name, names, bytes));
+        decoder.reader().consume(len);
+        return result;
+    }
+
+    // The names that match the parts so far all start with the same bytes as `candidate`, so only one of them has to be kept
+    let mut candidate = names.iter().position(|name| name.len() == len);
+    let mut buffer = [0u8; 64];
+    let mut read = 0;
+    while read < len {
+        let part = &mut buffer[..(len - read).min(64)];
+        decoder.reader().read(part)?;
+        let matches = |name: &&str, before: &[u8]| {
+            let name = name.as_bytes();
+            name.len() == len && name[..read] == *before && name[read..read + part.len()] == *part
+        };
+        let Some(before) = candidate.map(|index| &names[index].as_bytes()[..read]) else {
+            return Err(unexpected_variant_name_in_parts(
+                decoder,
+                type_name,
+                names,
+                &[],
+                part,
+                len,
+            )?);
+        };
+        candidate = names.iter().position(|name| matches(name, before));
+        if candidate.is_none() {
+            return Err(unexpected_variant_name_in_parts(
+                decoder, type_name, names, before, part, len,
+            )?);
+        }
+        read += part.len();
+    }
+    candidate.ok_or_else(|| unexpected_variant_name(type_name, names, &[]))
+}
+
+/// Decodes the name of a variant like [`decode_variant_name`]. This is used by `#[derive(BorrowDecode)]`.
+///
+/// The name is compared to `names` where it is in the input, without allocating.
+///
+/// # Errors
+///
+/// Returns `DecodeError::UnexpectedVariantName` if the name is not in `names`, or `DecodeError::Other` without the `alloc` feature. Returns an error if the reader fails.
+pub fn borrow_decode_variant_name<'de, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+    type_name: &'static str,
+    names: &'static [&'static str],
+) -> Result<usize, DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    decoder.claim_bytes_read(len)?;
+    let bytes = decoder.borrow_reader().take_bytes(len)?;
+    names
+        .iter()
+        .position(|name| name.as_bytes() == bytes)
+        .ok_or_else(|| unexpected_variant_name(type_name, names, bytes))
+}
+
+/// Returns the error for a variant name that is not in `names`.
+#[cold]
+#[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+fn unexpected_variant_name(
+    type_name: &'static str,
+    names: &'static [&'static str],
+    found: &[u8],
+) -> DecodeError {
+    #[cfg(feature = "alloc")]
+    return DecodeError::UnexpectedVariantName(alloc::boxed::Box::new(
+        crate::error::UnexpectedVariantName {
+            type_name,
+            allowed: names,
+            found: alloc::string::String::from_utf8_lossy(found).into_owned(),
+        },
+    ));
+    #[cfg(not(feature = "alloc"))]
+    DecodeError::Other("unexpected variant name")
+}
+
+/// Reads the rest of a variant name of `len` bytes that is not in `names`, after the bytes `before` and `part` of it are read, and returns the error for it.
+#[cold]
+fn unexpected_variant_name_in_parts<D: Decoder>(
+    decoder: &mut D,
+    type_name: &'static str,
+    names: &'static [&'static str],
+    before: &[u8],
+    part: &[u8],
+    len: usize,
+) -> Result<DecodeError, DecodeError> {
+    let rest = len - before.len() - part.len();
+    #[cfg(feature = "alloc")]
+    {
+        let mut found = alloc::vec::Vec::with_capacity(before.len() + part.len());
+        found.extend_from_slice(before);
+        found.extend_from_slice(part);
+        found.extend(crate::plain::decode_byte_vec(decoder, rest)?);
+        Ok(unexpected_variant_name(type_name, names, &found))
+    }
+    #[cfg(not(feature = "alloc"))]
+    {
+        decoder.reader().skip(rest)?;
+        Ok(unexpected_variant_name(type_name, names, &[]))
+    }
+}
+
+/// Decodes a `T` with `endian` instead of the endianness of the config. This is used by `#[derive(Decode)]` for fields with `#[bincode(endian = "...")]`.
+///
+/// # Errors
+///
+/// Returns any error encountered during decoding.
+pub fn decode_with_endian<T: Decode<D::Context>, D: Decoder>(
+    decoder: &mut D,
+    endian: Endianness,
+) -> Result<T, DecodeError> {
+    match endian {
+        Endianness::Little => T::decode(&mut EndianDecoder::<_, LittleEndian>::new(decoder)),
+        Endianness::Big => T::decode(&mut EndianDecoder::<_, BigEndian>::new(decoder)),
+    }
+}
+
+/// Decodes a `T` with `endian` instead of the endianness of the config, like [`decode_with_endian`]. This is used by `#[derive(BorrowDecode)]`.
+///
+/// # Errors
+///
+/// Returns any error encountered during decoding.
+pub fn borrow_decode_with_endian<'de, T: BorrowDecode<'de, D::Context>, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+    endian: Endianness,
+) -> Result<T, DecodeError> {
+    match endian {
+        Endianness::Little => T::borrow_decode(&mut EndianDecoder::<_, LittleEndian>::new(decoder)),
+        Endianness::Big => T::borrow_decode(&mut EndianDecoder::<_, BigEndian>::new(decoder)),
+    }
+}
+
 /// Decodes the length of any slice, container, etc from the decoder
+///
+/// The length is a `u64` on the wire, see [`encode_slice_len`](crate::enc::encode_slice_len). A length that does not fit in the `usize` of this target, e.g. a length above `u32::MAX` that was encoded on a 64 bit target and is decoded on a 32 bit one, fails with [`DecodeError::OutsideUsizeRange`] instead of being truncated.
 #[inline]
 pub(crate) fn decode_slice_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
-    let v = u64::decode(decoder)?;
+    let v = match D::C::LENGTH_ENCODING {
+        LengthEncoding::Int => u64::decode(decoder)?,
+        LengthEncoding::Varint => {
+            decoder.claim_bytes_read(8)?;
+            crate::varint::varint_decode_u64(decoder.reader(), D::C::ENDIAN)?
+        }
+        LengthEncoding::FixedU32 => {
+            decoder.claim_bytes_read(4)?;
+            let mut bytes = [0u8; 4];
+            decoder.reader().read(&mut bytes)?;
+            u64::from(match D::C::ENDIAN {
+                Endianness::Little => u32::from_le_bytes(bytes),
+                Endianness::Big => u32::from_be_bytes(bytes),
+            })
+        }
+        LengthEncoding::FixedU64 => {
+            decoder.claim_bytes_read(8)?;
+            let mut bytes = [0u8; 8];
+            decoder.reader().read(&mut bytes)?;
+            match D::C::ENDIAN {
+                Endianness::Little => u64::from_le_bytes(bytes),
+                Endianness::Big => u64::from_be_bytes(bytes),
+            }
+        }
+    };
 
     v.try_into().map_err(|_| DecodeError::OutsideUsizeRange(v))
 }
+
+/// Converts decoded bytes to a `str`. With the `simdutf8` feature the bytes are validated with SIMD instructions, and only invalid strings are checked again by `core::str::from_utf8` to report the exact same [`DecodeError::Utf8`\].
+#[inline]
+pub(crate) fn str_from_utf8(bytes: &[u8]) -> Result<&str, DecodeError> {
+    #[cfg(feature = "simdutf8")]
+    if let Ok(str) = simdutf8::basic::from_utf8(bytes) {
+        return Ok(str);
+    }
+    core::str::from_utf8(bytes).map_err(|inner| DecodeError::utf8(bytes, inner))
+}
+
+/// Converts decoded bytes to a mutable `str`, validated like [`str_from_utf8`].
+#[inline]
+#[allow(unsafe_code)]
+pub(crate) fn str_from_utf8_mut(bytes: &mut [u8]) -> Result<&mut str, DecodeError> {
+    str_from_utf8(bytes)?;
+    // Safety: the bytes were validated as UTF-8 above
+    Ok(unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+}
+
+/// The maximum amount of memory that is allocated for the elements of a container before they are decoded, if the reader does not know how much input is left.
+pub(crate) const MAX_PREALLOCATION_BYTES: usize = 64 * 1024;
+
+/// Returns how many elements of `T` to allocate room for, before decoding a container that claims to have `len` elements.
+///
+/// The length comes from the input and can be much larger than the input itself. Every element takes at least one byte of input, so if the reader knows how much input is left, the capacity is limited to that. Otherwise it is limited to [`MAX_PREALLOCATION_BYTES`]. The container grows as its elements are decoded.
+#[inline]
+pub(crate) fn preallocation_len<T, D: Decoder>(decoder: &mut D, len: usize) -> usize {
+    let size = core::mem::size_of::<T>();
+    if size == 0 {
+        return len;
+    }
+    let max = decoder
+        .reader()
+        .remaining_hint()
+        .unwrap_or(MAX_PREALLOCATION_BYTES / size);
+    len.min(max)
+}
+
+/// Returns `value` if there are no `remaining` bytes after it in the slice it was decoded from, or if the config accepts them
+#[inline]
+pub(crate) fn check_trailing_bytes<C: Config, T>(
+    value: T,
+    remaining: usize,
+) -> Result<T, DecodeError> {
+    if remaining > 0 && C::TRAILING_BYTES == TrailingBytes::Reject {
+        return Err(DecodeError::TrailingBytes { remaining });
+    }
+    Ok(value)
+}
+
+/// Wraps `err` in [`DecodeError::WithOffset`] if the config reports error offsets, see [`Configuration::with_error_offsets`](crate::config::Configuration::with_error_offsets). [`DecodeError::CleanEof`] is returned as is, because no value was started.
+#[inline]
+pub(crate) fn report_offset<C: Config>(err: DecodeError, offset: usize) -> DecodeError {
+    match C::ERROR_OFFSETS {
+        #[cfg(feature = "alloc")]
+        crate::config::ErrorOffsets::Report if !matches!(err, DecodeError::CleanEof) => {
+            err.with_offset(offset)
+        }
+        _ => {
+            let _ = offset;
+            err
+        }
+    }
+}
+
+/// Decodes the length of a fixed-capacity container, and makes sure it does not exceed `CAP`
+#[inline]
+pub(crate) fn decode_capacity_len<D: Decoder, const CAP: usize>(
+    decoder: &mut D,
+) -> Result<usize, DecodeError> {
+    let len = decode_slice_len(decoder)?;
+    if len > CAP {
+        return Err(DecodeError::ArrayCapacityExceeded { len, cap: CAP });
+    }
+    Ok(len)
+}
@@ -8,10 +8,13 @@
 //!
 //! Specifically the `Reader` trait is used by [`Decode`\] and the `BorrowReader` trait is used by [`BorrowDecode`\].
 //!
+//! Both are implemented for `&mut R`, also if `R` is a `dyn Reader`, so a reader can be passed to a decoder as `&mut reader` and be used again afterwards, e.g. to decode several values one after another.
+//!
 //! [Decode]: ../trait.Decode.html
 //! [BorrowDecode]: ../trait.BorrowDecode.html
 
 use crate::error::DecodeError;
+use core::{mem::MaybeUninit, ops::ControlFlow};
 
 /// A reader for owned data. See the module documentation for more information.
 pub trait Reader {
@@ -23,8 +26,28 @@ pub trait Reader {
     /// Returns `DecodeError::UnexpectedEnd` if the reader does not have enough bytes.
     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError>;
 
+    /// Fill `bytes`, which may be uninitialized, like [`read`](Reader::read), and return them as initialized bytes.
+    ///
+    /// Decoding a `Vec<u8>`, a `String` or another collection of plain bytes reads into the fresh, uninitialized memory of the collection with this. The default implementation zeroes `bytes` and passes them to `read`. Readers that can write into uninitialized memory directly, like [`SliceReader`], override this, so large payloads are not written twice.
+    ///
+    /// The returned slice must be `bytes` itself, and decoding fails if it is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::UnexpectedEnd` if the reader does not have enough bytes.
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let bytes = zeroed(bytes);
+        self.read(bytes)?;
+        Ok(bytes)
+    }
+
     /// If this reader wraps a buffer of any kind, this function lets callers access contents of
     /// the buffer without passing data through a buffer first.
+    ///
+    /// Return `None` if fewer than `n` bytes are buffered. Bincode falls back to [`read`](Reader::read) if the slice is shorter than `n` bytes, and only looks at the first `n` bytes of a longer one.
     #[inline]
     fn peek_read(&mut self, _: usize) -> Option<&[u8]> {
         None
@@ -34,17 +57,121 @@ pub trait Reader {
     /// must be provided so that subsequent reads or peek-reads do not return the same bytes
     #[inline]
     fn consume(&mut self, _: usize) {}
+
+    /// Discard the next `n` bytes. Exactly `n` bytes must be discarded, or else an error must be returned.
+    ///
+    /// The default implementation reads the bytes through a small buffer on the stack. Readers that can skip cheaper, like [`SliceReader`\] by advancing its cursor, override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::UnexpectedEnd` if the reader does not have enough bytes.
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        let mut buffer = [0u8; 256];
+        let mut remaining = n;
+        while remaining > 0 {
+            let chunk = remaining.min(buffer.len());
+            remaining -= chunk;
+            self.read(&mut buffer[..chunk]).map_err(|e| match e {
+                DecodeError::UnexpectedEnd { additional } => DecodeError::UnexpectedEnd {
+                    additional: additional + remaining,
+                },
+                e => e,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The amount of bytes that are left in this reader, if it knows this.
+    ///
+    /// Decoding uses this to avoid allocating room for more elements than the input can contain, when a container claims a large length. Readers that cannot tell how much input is left return `None`, which is the default.
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// If this reader wraps a [`bytes::Bytes`\] buffer, this function lets callers take the next
+    /// `length` bytes as a cheap, reference-counted slice of that buffer instead of copying them.
+    ///
+    /// Returns `None` if the reader does not support this, or if not enough bytes are available.
+    /// In that case the caller should fall back to `read`.
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, _: usize) -> Option<bytes::Bytes> {
+        None
+    }
+
+    /// Marks that the following bytes belong to the field or value `name`, until the matching [`leave`](Reader::leave). Markers nest. By default this does nothing.
+    ///
+    /// Derived `Decode` and `BorrowDecode` implementations call this through [`Decoder::enter`](crate::de::Decoder::enter) for every field. [`StatsReader`](crate::stats::StatsReader) uses the markers to attribute the read bytes to the fields.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        let _ = name;
+    }
+
+    /// Ends the innermost [`enter`](Reader::enter) marker. By default this does nothing.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {}
+}
+
+/// Zeroes `bytes`, and returns them as initialized bytes.
+#[allow(unsafe_code)]
+pub(crate) fn zeroed(bytes: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    bytes.fill(MaybeUninit::new(0));
+    // Safety: every byte was just initialized
+    unsafe { assume_init(bytes) }
+}
+
+/// Copies `src` into the start of `dst`, and returns the copied bytes.
+///
+/// # Panics
+///
+/// Panics if `dst` is shorter than `src`.
+#[allow(unsafe_code)]
+pub(crate) fn copy_to_uninit<'a>(dst: &'a mut [MaybeUninit<u8>], src: &[u8]) -> &'a mut [u8] {
+    let dst = &mut dst[..src.len()];
+    // Safety: `dst` is exactly as long as `src`, and a `&mut` to it can not overlap `src`. Every byte of `dst` is initialized by the copy.
+    unsafe {
+        core::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr().cast::<u8>(), src.len());
+        assume_init(dst)
+    }
+}
+
+/// Returns `bytes` as initialized bytes.
+///
+/// # Safety
+///
+/// Every byte of `bytes` must be initialized.
+#[allow(unsafe_code)]
+pub(crate) const unsafe fn assume_init(bytes: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    // Safety: `MaybeUninit<u8>` has the same layout as `u8`, and the caller guarantees that the bytes are initialized
+    unsafe { &mut *(core::ptr::from_mut(bytes) as *mut [u8]) }
+}
+
+/// Peek at exactly `n` bytes of `reader` with [`Reader::peek_read`], so a reader that returns a shorter slice can not make decoding index past its end.
+#[inline]
+pub(crate) fn peek_exact<R: Reader + ?Sized>(reader: &mut R, n: usize) -> Option<&[u8]> {
+    reader.peek_read(n)?.get(..n)
 }
 
 impl<T> Reader for &mut T
 where
-    T: Reader,
+    T: Reader + ?Sized,
 {
     #[inline]
     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
         (**self).read(bytes)
     }
 
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        (**self).read_uninit(bytes)
+    }
+
     #[inline]
     fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
         (**self).peek_read(n)
@@ -52,7 +179,35 @@ where
 
     #[inline]
     fn consume(&mut self, n: usize) {
-        (*self).consume(n);
+        (**self).consume(n);
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        (**self).skip(n)
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        (**self).remaining_hint()
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        (**self).take_shared_bytes(length)
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        (**self).enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        (**self).leave();
     }
 }
 
@@ -66,18 +221,90 @@ pub trait BorrowReader<'storage>: Reader {
     ///
     /// Returns `DecodeError::UnexpectedEnd` if the reader does not have enough bytes.
     fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError>;
+
+    /// Read exactly `length` bytes and return a mutable slice to this data, e.g. for `&mut [u8]`. Only readers over a mutable buffer, like [`SliceReaderMut`], can do this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecodeError::UnexpectedEnd` if the reader does not have enough bytes, or `DecodeError::MutableBorrowUnsupported` if the reader can not lend its bytes mutably.
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        let _ = length;
+        Err(DecodeError::MutableBorrowUnsupported)
+    }
+}
+
+impl<'storage, T> BorrowReader<'storage> for &mut T
+where
+    T: BorrowReader<'storage> + ?Sized,
+{
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        (**self).take_bytes(length)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        (**self).take_bytes_mut(length)
+    }
 }
 
 /// A reader type for `&[u8]` slices. Implements both [`Reader`\] and [`BorrowReader`\], and thus can be used for borrowed data.
+///
+/// Pass it to a [`DecoderImpl`](crate::de::DecoderImpl) as `&mut reader` to decode several values from the same slice, and to look at the bytes in between by hand. [`position`](Self::position) is the amount of bytes read so far, and [`remaining`](Self::remaining) are the bytes that are left, with the lifetime of the slice.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::{Reader, SliceReader};
+/// use bincode::de::{Decode, DecoderImpl};
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = bincode::encode_to_vec(7u16, config).unwrap();
+/// bytes.extend_from_slice(b"BINC");
+/// bytes.extend(bincode::encode_to_vec("payload", config).unwrap());
+///
+/// let mut reader = SliceReader::new(&bytes);
+/// let version = u16::decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+/// assert_eq!((version, reader.position()), (7, 1));
+///
+/// // check the magic by hand
+/// assert_eq!(reader.remaining()[..4], *b"BINC");
+/// reader.skip(4).unwrap();
+///
+/// let payload = String::decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+/// assert_eq!((payload.as_str(), reader.position()), ("payload", 13));
+/// assert!(reader.into_remainder().is_empty());
+/// ```
 pub struct SliceReader<'storage> {
     pub(crate) slice: &'storage [u8],
+    len: usize,
 }
 
 impl<'storage> SliceReader<'storage> {
     /// Constructs a slice reader
     #[must_use]
     pub const fn new(bytes: &'storage [u8]) -> Self {
-        Self { slice: bytes }
+        Self {
+            slice: bytes,
+            len: bytes.len(),
+        }
+    }
+
+    /// Returns the number of bytes read from the slice so far, which is the offset of the next byte.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.len - self.slice.len()
+    }
+
+    /// Returns the bytes that have not been read yet.
+    #[must_use]
+    pub const fn remaining(&self) -> &'storage [u8] {
+        self.slice
+    }
+
+    /// Returns the bytes that have not been read yet, and consumes the reader.
+    #[must_use]
+    pub const fn into_remainder(self) -> &'storage [u8] {
+        self.slice
     }
 }
 
@@ -96,6 +323,21 @@ impl<'storage> Reader for SliceReader<'storage> {
         Ok(())
     }
 
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        if bytes.len() > self.slice.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: bytes.len() - self.slice.len(),
+            });
+        }
+        let (read_slice, remaining) = self.slice.split_at(bytes.len());
+        self.slice = remaining;
+        Ok(copy_to_uninit(bytes, read_slice))
+    }
+
     #[inline]
     fn peek_read(&mut self, n: usize) -> Option<&'storage [u8]> {
         self.slice.get(..n)
@@ -105,6 +347,22 @@ impl<'storage> Reader for SliceReader<'storage> {
     fn consume(&mut self, n: usize) {
         self.slice = self.slice.get(n..).unwrap_or_default();
     }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        if n > self.slice.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - self.slice.len(),
+            });
+        }
+        self.slice = &self.slice[n..];
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
 }
 
 impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
@@ -120,3 +378,748 @@ impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
         Ok(read_slice)
     }
 }
+
+/// A reader type for `&mut [u8]` slices. Like [`SliceReader`], but it can also lend its bytes mutably with [`BorrowReader::take_bytes_mut`], so values like `&mut [u8]` can be decoded in place.
+///
+/// Every borrow splits off the front of the remaining slice, so the borrowed values never overlap.
+pub struct SliceReaderMut<'storage> {
+    pub(crate) slice: &'storage mut [u8],
+}
+
+impl<'storage> SliceReaderMut<'storage> {
+    /// Constructs a mutable slice reader
+    #[must_use]
+    pub const fn new(bytes: &'storage mut [u8]) -> Self {
+        Self { slice: bytes }
+    }
+
+    /// Splits the first `length` bytes off the remaining slice.
+    fn split_off(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        if length > self.slice.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: length - self.slice.len(),
+            });
+        }
+        let (read_slice, remaining) = core::mem::take(&mut self.slice).split_at_mut(length);
+        self.slice = remaining;
+        Ok(read_slice)
+    }
+}
+
+impl Reader for SliceReaderMut<'_> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        bytes.copy_from_slice(self.split_off(bytes.len())?);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.slice.get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        let _ = self.split_off(n.min(self.slice.len()));
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.split_off(n).map(|_| ())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<'storage> BorrowReader<'storage> for SliceReaderMut<'storage> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        self.split_off(length).map(|bytes| &*bytes)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        self.split_off(length)
+    }
+}
+
+/// A reader that reads at most `limit` bytes from the wrapped reader.
+///
+/// Reads past the limit fail with [`DecodeError::TakeLimitExceeded`] without reading anything from the wrapped reader, so a malformed value can never consume bytes that belong to whatever follows it. Use [`TakeReader::remaining`] after decoding to check that the value used all of its bytes.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::{SliceReader, TakeReader};
+///
+/// let config = bincode::config::legacy();
+/// let mut data = bincode::encode_to_vec(5u32, config).unwrap();
+/// data.extend_from_slice(&[1, 2, 3]);
+///
+/// let mut reader = TakeReader::new(SliceReader::new(&data), 4);
+/// let value: u32 = bincode::decode_from_reader(&mut reader, config).unwrap();
+/// assert_eq!(value, 5);
+/// assert_eq!(reader.remaining(), 0);
+///
+/// // A `u64` does not fit in the 4 bytes this message has
+/// let mut reader = TakeReader::new(SliceReader::new(&data), 4);
+/// let err = bincode::decode_from_reader::<u64, _, _>(&mut reader, config).unwrap_err();
+/// assert!(matches!(
+///     err.inner(),
+///     bincode::error::DecodeError::TakeLimitExceeded { limit: 4, additional: 4 }
+/// ));
+/// ```
+pub struct TakeReader<R> {
+    reader: R,
+    limit: usize,
+    remaining: usize,
+}
+
+impl<R: Reader> TakeReader<R> {
+    /// Create a new `TakeReader` that reads at most `limit` bytes from `reader`.
+    pub const fn new(reader: R, limit: usize) -> Self {
+        Self {
+            reader,
+            limit,
+            remaining: limit,
+        }
+    }
+
+    /// The amount of bytes that can still be read before the limit is reached.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    pub(crate) const fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub(crate) const fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    const fn claim(&mut self, n: usize) -> Result<(), DecodeError> {
+        if n > self.remaining {
+            return Err(DecodeError::TakeLimitExceeded {
+                limit: self.limit,
+                additional: n - self.remaining,
+            });
+        }
+        self.remaining -= n;
+        Ok(())
+    }
+}
+
+impl<R: Reader> Reader for TakeReader<R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.claim(bytes.len())?;
+        self.reader.read(bytes)
+    }
+
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        self.claim(bytes.len())?;
+        self.reader.read_uninit(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        if n > self.remaining {
+            return None;
+        }
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        let n = n.min(self.remaining);
+        self.remaining -= n;
+        self.reader.consume(n);
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.claim(n)?;
+        self.reader.skip(n)
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(
+            self.reader
+                .remaining_hint()
+                .map_or(self.remaining, |hint| hint.min(self.remaining)),
+        )
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        if length > self.remaining {
+            return None;
+        }
+        let bytes = self.reader.take_shared_bytes(length)?;
+        self.remaining -= bytes.len();
+        Some(bytes)
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.reader.enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.reader.leave();
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>> BorrowReader<'storage> for TakeReader<R> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        self.claim(length)?;
+        self.reader.take_bytes(length)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        self.claim(length)?;
+        self.reader.take_bytes_mut(length)
+    }
+}
+
+/// A reader over data that is split into several segments, e.g. the buffers of a scatter/gather read. Implements both [`Reader`] and [`BorrowReader`].
+///
+/// Reads may span any amount of segments. [`Reader::peek_read`] and [`BorrowReader::take_bytes`] only return data that lies within the current segment. Borrowing data that spans a segment boundary fails with [`DecodeError::BorrowSpansSegments`], so types like `&str` can only be borrowed if they do not cross a boundary. Owned types like `String` are always copied and can be decoded from anywhere.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::SegmentedSliceReader;
+///
+/// let config = bincode::config::standard();
+/// let data = bincode::encode_to_vec((1000u32, "hello"), config).unwrap();
+/// let (head, tail) = data.split_at(4);
+///
+/// let segments = [head, tail];
+/// let mut reader = SegmentedSliceReader::new(&segments);
+/// let value: (u32, String) = bincode::decode_from_reader(&mut reader, config).unwrap();
+/// assert_eq!(value, (1000, String::from("hello")));
+/// assert_eq!(reader.bytes_read(), data.len());
+/// ```
+pub struct SegmentedSliceReader<'storage> {
+    current: &'storage [u8],
+    segments: &'storage [&'storage [u8]],
+    bytes_read: usize,
+}
+
+impl<'storage> SegmentedSliceReader<'storage> {
+    /// Constructs a reader that reads the given segments in order
+    #[must_use]
+    pub const fn new(segments: &'storage [&'storage [u8]]) -> Self {
+        let mut reader = Self {
+            current: &[],
+            segments,
+            bytes_read: 0,
+        };
+        reader.skip_empty_segments();
+        reader
+    }
+
+    /// The amount of bytes that have been read so far.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// The amount of bytes that are left in all segments together.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.current.len() + self.segments.iter().map(|s| s.len()).sum::<usize>()
+    }
+
+    /// Moves to the next non-empty segment if the current segment is exhausted.
+    const fn skip_empty_segments(&mut self) {
+        while self.current.is_empty() {
+            match self.segments.split_first() {
+                Some((first, rest)) => {
+                    self.current = first;
+                    self.segments = rest;
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.current = &self.current[n..];
+        self.bytes_read += n;
+        self.skip_empty_segments();
+    }
+}
+
+impl<'storage> Reader for SegmentedSliceReader<'storage> {
+    #[inline]
+    fn read(&mut self, mut bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let remaining = self.remaining();
+        if bytes.len() > remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: bytes.len() - remaining,
+            });
+        }
+        while !bytes.is_empty() {
+            let n = bytes.len().min(self.current.len());
+            let (head, tail) = bytes.split_at_mut(n);
+            head.copy_from_slice(&self.current[..n]);
+            bytes = tail;
+            self.advance(n);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&'storage [u8]> {
+        self.current.get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.advance(n.min(self.current.len()));
+    }
+
+    #[inline]
+    fn skip(&mut self, mut n: usize) -> Result<(), DecodeError> {
+        let remaining = self.remaining();
+        if n > remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - remaining,
+            });
+        }
+        while n > 0 {
+            let step = n.min(self.current.len());
+            self.advance(step);
+            n -= step;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(self.remaining())
+    }
+}
+
+impl<'storage> BorrowReader<'storage> for SegmentedSliceReader<'storage> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        if length > self.current.len() {
+            let remaining = self.remaining();
+            if length > remaining {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: length - remaining,
+                });
+            }
+            return Err(DecodeError::BorrowSpansSegments {
+                length,
+                available: self.current.len(),
+            });
+        }
+        let bytes = &self.current[..length];
+        self.advance(length);
+        Ok(bytes)
+    }
+}
+
+/// A reader over an iterator of bytes, for sources that are neither a slice nor a `std::io::Read`, like a decompressor or the drain of a ring buffer.
+///
+/// Reads fill the buffer in one pass over the iterator, and [`skip`](Reader::skip) uses [`Iterator::nth`], which is cheap for iterators like `core::slice::Iter`. Use [`TryIterReader`] for an iterator that can fail.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::IterReader;
+///
+/// let config = bincode::config::standard();
+/// let encoded = bincode::encode_to_vec((5u32, [1u8, 2, 3]), config).unwrap();
+///
+/// // e.g. bytes that are stored inverted
+/// let inverted: Vec<u8> = encoded.iter().map(|byte| !byte).collect();
+/// let reader = IterReader::new(inverted.iter().map(|byte| !byte));
+/// let decoded: (u32, [u8; 3]) = bincode::decode_from_reader(reader, config).unwrap();
+/// assert_eq!(decoded, (5, [1, 2, 3]));
+/// ```
+pub struct IterReader<I> {
+    iter: I,
+}
+
+impl<I: Iterator<Item = u8>> IterReader<I> {
+    /// Create a new `IterReader` that reads the bytes of `iter`.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            iter: iter.into_iter(),
+        }
+    }
+
+    /// Returns the wrapped iterator, with the bytes that were not read yet.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator<Item = u8>> Reader for IterReader<I> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let mut filled = 0;
+        for (slot, byte) in bytes.iter_mut().zip(self.iter.by_ref()) {
+            *slot = byte;
+            filled += 1;
+        }
+        if filled < bytes.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: bytes.len() - filled,
+            });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        if n == 0 {
+            return Ok(());
+        }
+        // the iterator promises at least this many bytes, so they can be skipped at once
+        if n <= self.iter.size_hint().0 && self.iter.nth(n - 1).is_some() {
+            return Ok(());
+        }
+        let skipped = self.iter.by_ref().take(n).count();
+        if skipped < n {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - skipped,
+            });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
+}
+
+/// A reader over an iterator of bytes that can fail, like [`IterReader`]. An error of the iterator is converted into the `DecodeError` that reading returns.
+///
+/// Implement `From<YourError> for DecodeError` to use your own error type, e.g. with [`DecodeError::custom`].
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::TryIterReader;
+/// use bincode::error::DecodeError;
+///
+/// let config = bincode::config::standard();
+/// let chunks: Vec<Result<u8, DecodeError>> = vec![Ok(2), Ok(b'h'), Err(DecodeError::Other("disconnected"))];
+/// let result: Result<String, _> = bincode::decode_from_reader(TryIterReader::new(chunks), config);
+/// assert!(matches!(result.unwrap_err().into_inner(), DecodeError::Other("disconnected")));
+/// ```
+pub struct TryIterReader<I> {
+    iter: I,
+}
+
+impl<E: Into<DecodeError>, I: Iterator<Item = Result<u8, E>>> TryIterReader<I> {
+    /// Create a new `TryIterReader` that reads the bytes of `iter`.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            iter: iter.into_iter(),
+        }
+    }
+
+    /// Returns the wrapped iterator, with the bytes that were not read yet.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<E: Into<DecodeError>, I: Iterator<Item = Result<u8, E>>> Reader for TryIterReader<I> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let len = bytes.len();
+        for (filled, slot) in bytes.iter_mut().enumerate() {
+            match self.iter.next() {
+                Some(byte) => *slot = byte.map_err(Into::into)?,
+                None => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: len - filled,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
+}
+
+/// A reader that reports how many bytes were read from the wrapped reader, e.g. to drive a progress bar while a large value is decoded.
+///
+/// The callback is called with the total amount of bytes read so far, once at least `every` bytes were read since the last call, so a small `every` does not add a call to every read. If the callback returns [`ControlFlow::Break`], the read that called it and all reads after it fail with [`DecodeError::Cancelled`]. Decoding stops at the next read and drops whatever was decoded so far.
+///
+/// The callback only observes the reads, so it can wrap any reader, like an [`IoReader`](crate::IoReader) or a [`TakeReader`]. See [`ProgressReader::from_std_read`] for a `std::io::Read`.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::{ProgressReader, SliceReader};
+/// use bincode::error::DecodeError;
+/// use core::ops::ControlFlow;
+///
+/// let config = bincode::config::standard();
+/// let encoded = bincode::encode_to_vec(vec![7u8; 1000], config).unwrap();
+///
+/// let mut reports = Vec::new();
+/// let reader = ProgressReader::new(SliceReader::new(&encoded), 256, |read| {
+///     reports.push(read);
+///     ControlFlow::Continue(())
+/// });
+/// let decoded: Vec<u8> = bincode::decode_from_reader(reader, config).unwrap();
+/// assert_eq!(decoded.len(), 1000);
+/// assert_eq!(reports, [1003]);
+///
+/// // give up after 10 bytes
+/// let reader = ProgressReader::new(SliceReader::new(&encoded), 10, |read| {
+///     if read < 10 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+/// });
+/// let result: Result<Vec<u8>, _> = bincode::decode_from_reader(reader, config);
+/// assert!(matches!(result.unwrap_err().into_inner(), DecodeError::Cancelled));
+/// ```
+pub struct ProgressReader<R, F> {
+    reader: R,
+    callback: F,
+    every: u64,
+    read: u64,
+    next_report: u64,
+    cancelled: bool,
+}
+
+impl<R: Reader, F: FnMut(u64) -> ControlFlow<()>> ProgressReader<R, F> {
+    /// Create a new `ProgressReader` that calls `callback` each time at least `every` more bytes were read from `reader`. An `every` of 0 calls it after every read.
+    pub const fn new(reader: R, every: u64, callback: F) -> Self {
+        Self {
+            reader,
+            callback,
+            every,
+            read: 0,
+            next_report: every,
+            cancelled: false,
+        }
+    }
+
+    /// The amount of bytes read so far, also if the callback was not called for them yet.
+    #[must_use]
+    pub const fn bytes_read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns `true` if the callback returned [`ControlFlow::Break`].
+    #[must_use]
+    pub const fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    const fn check(&self) -> Result<(), DecodeError> {
+        if self.cancelled {
+            Err(DecodeError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.read += n as u64;
+        if self.read >= self.next_report && !self.cancelled {
+            self.next_report = self.read.saturating_add(self.every);
+            self.cancelled = (self.callback)(self.read).is_break();
+        }
+    }
+}
+
+impl<R: Reader, F: FnMut(u64) -> ControlFlow<()>> Reader for ProgressReader<R, F> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.check()?;
+        self.reader.read(bytes)?;
+        self.advance(bytes.len());
+        self.check()
+    }
+
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        self.check()?;
+        let bytes = self.reader.read_uninit(bytes)?;
+        self.advance(bytes.len());
+        self.check()?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        // once cancelled, the decoder falls back to `read`, which fails
+        if self.cancelled {
+            return None;
+        }
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.advance(n);
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.check()?;
+        self.reader.skip(n)?;
+        self.advance(n);
+        self.check()
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        self.reader.remaining_hint()
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        if self.cancelled {
+            return None;
+        }
+        let bytes = self.reader.take_shared_bytes(length)?;
+        self.advance(bytes.len());
+        Some(bytes)
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>, F: FnMut(u64) -> ControlFlow<()>> BorrowReader<'storage>
+    for ProgressReader<R, F>
+{
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        self.check()?;
+        let bytes = self.reader.take_bytes(length)?;
+        self.advance(length);
+        self.check().map(|()| bytes)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        self.check()?;
+        let bytes = self.reader.take_bytes_mut(length)?;
+        self.advance(length);
+        self.check().map(|()| bytes)
+    }
+}
+
+/// A reader that keeps track of the amount of bytes that were read from the wrapped reader, so the offset can be reported in errors.
+#[cfg(feature = "alloc")]
+pub(crate) struct OffsetReader<R> {
+    pub(crate) reader: R,
+    pub(crate) offset: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Reader> OffsetReader<R> {
+    pub(crate) const fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Reader> Reader for OffsetReader<R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.offset += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let bytes = self.reader.read_uninit(bytes)?;
+        self.offset += bytes.len();
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.offset += n;
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.reader.skip(n)?;
+        self.offset += n;
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        self.reader.remaining_hint()
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        let bytes = self.reader.take_shared_bytes(length)?;
+        self.offset += bytes.len();
+        Some(bytes)
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.reader.enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.reader.leave();
+    }
+}
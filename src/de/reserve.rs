@@ -0,0 +1,207 @@
+//! Reserving memory for decoded containers, with or without [`Configuration::with_fallible_allocation`](crate::config::Configuration::with_fallible_allocation).
+
+use super::Decoder;
+use crate::{
+    config::{Allocation, InternalAllocationConfig},
+    error::DecodeError,
+};
+use alloc::{collections::TryReserveError, vec::Vec};
+
+/// A container that room for more elements can be reserved in.
+pub trait Reserve {
+    /// The size of an element, to report how many bytes were requested
+    const ELEMENT_SIZE: usize;
+
+    /// The amount of elements in the container
+    fn len(&self) -> usize;
+
+    /// The amount of elements the container can hold without allocating
+    fn capacity(&self) -> usize;
+
+    /// Reserves room for at least `additional` more elements, and aborts if that fails
+    fn reserve(&mut self, additional: usize);
+
+    /// Reserves room for at least `additional` more elements
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>;
+}
+
+impl<T> Reserve for Vec<T> {
+    const ELEMENT_SIZE: usize = core::mem::size_of::<T>();
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve(additional)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S: core::hash::BuildHasher> Reserve for std::collections::HashMap<K, V, S>
+where
+    K: Eq + core::hash::Hash,
+{
+    const ELEMENT_SIZE: usize = core::mem::size_of::<(K, V)>();
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve(additional)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S: core::hash::BuildHasher> Reserve for std::collections::HashSet<T, S>
+where
+    T: Eq + core::hash::Hash,
+{
+    const ELEMENT_SIZE: usize = core::mem::size_of::<T>();
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve(additional)
+    }
+}
+
+const fn allocation_failed<C: Reserve>(additional: usize) -> DecodeError {
+    DecodeError::AllocationFailed {
+        requested: additional.saturating_mul(C::ELEMENT_SIZE),
+    }
+}
+
+/// Reserves room for at least `additional` more elements in `container`, before decoding them.
+///
+/// # Errors
+///
+/// Returns `DecodeError::AllocationFailed` if the config of `D` allocates fallibly and the memory can not be allocated.
+pub fn reserve<D: Decoder, C: Reserve>(
+    container: &mut C,
+    additional: usize,
+) -> Result<(), DecodeError> {
+    match D::C::ALLOCATION {
+        Allocation::Infallible => {
+            container.reserve(additional);
+            Ok(())
+        }
+        Allocation::Fallible => container
+            .try_reserve(additional)
+            .map_err(|_| allocation_failed::<C>(additional)),
+    }
+}
+
+/// Makes room for one more element in `container`, if it is full and the config of `D` allocates fallibly. Otherwise the container grows by itself when the element is added.
+///
+/// The container grows like it would by itself, so adding elements one by one stays amortized O(1).
+///
+/// # Errors
+///
+/// Returns `DecodeError::AllocationFailed` if the memory can not be allocated.
+pub fn reserve_entry<D: Decoder, C: Reserve>(container: &mut C) -> Result<(), DecodeError> {
+    if D::C::ALLOCATION == Allocation::Fallible && container.len() == container.capacity() {
+        container
+            .try_reserve(1)
+            .map_err(|_| allocation_failed::<C>(1))?;
+    }
+    Ok(())
+}
+
+/// Reserves room for exactly `additional` more elements in `vec`, like `Vec::reserve_exact`.
+///
+/// # Errors
+///
+/// Returns `DecodeError::AllocationFailed` if the config of `D` allocates fallibly and the memory can not be allocated.
+pub fn reserve_exact<D: Decoder, T>(
+    vec: &mut Vec<T>,
+    additional: usize,
+) -> Result<(), DecodeError> {
+    match D::C::ALLOCATION {
+        Allocation::Infallible => {
+            vec.reserve_exact(additional);
+            Ok(())
+        }
+        Allocation::Fallible => vec
+            .try_reserve_exact(additional)
+            .map_err(|_| allocation_failed::<Vec<T>>(additional)),
+    }
+}
+
+#[test]
+fn test_reserve_failure() {
+    use crate::{config, de::read::SliceReader, de::DecoderImpl};
+
+    /// A container that never has room, and fails to reserve any
+    struct Full;
+
+    impl Reserve for Full {
+        const ELEMENT_SIZE: usize = 4;
+
+        fn len(&self) -> usize {
+            0
+        }
+
+        fn capacity(&self) -> usize {
+            0
+        }
+
+        fn reserve(&mut self, _additional: usize) {}
+
+        fn try_reserve(&mut self, _additional: usize) -> Result<(), TryReserveError> {
+            // `TryReserveError` can not be constructed, so overflow the capacity of a `Vec` to get one
+            Vec::<u32>::new().try_reserve(usize::MAX)
+        }
+    }
+
+    type Infallible = DecoderImpl<SliceReader<'static>, config::Configuration, ()>;
+    type Fallible = DecoderImpl<
+        SliceReader<'static>,
+        config::Configuration<
+            config::LittleEndian,
+            config::Varint,
+            config::NoLimit,
+            config::Utf8Paths,
+            config::FallibleAllocation,
+        >,
+        (),
+    >;
+
+    assert!(reserve::<Infallible, _>(&mut Full, 10).is_ok());
+    assert!(reserve_entry::<Infallible, _>(&mut Full).is_ok());
+    assert!(matches!(
+        reserve::<Fallible, _>(&mut Full, 10),
+        Err(DecodeError::AllocationFailed { requested: 40 })
+    ));
+    assert!(matches!(
+        reserve_entry::<Fallible, _>(&mut Full),
+        Err(DecodeError::AllocationFailed { requested: 4 })
+    ));
+}
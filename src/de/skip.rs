@@ -0,0 +1,374 @@
+use super::{read::Reader, Decode, Decoder};
+use crate::{
+    config::{CharEncoding, Config, IntEncoding},
+    error::DecodeError,
+};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    task::Poll,
+    time::Duration,
+};
+
+/// Trait that makes a type able to step over its encoding without decoding it. This is useful to reach a field further in the input, without paying to build the fields before it.
+///
+/// Lengths and tags are read to find out where the value ends, everything else is skipped with [`Decoder::skip_bytes`]. A container of elements that always take the same amount of bytes (see [`fixed_size`](SkipDecode::fixed_size)) is skipped in one go, e.g. a `Vec<u32>` with a fixint config or a `String`. Other containers skip their elements one by one. Nothing is allocated.
+///
+/// The bytes that are skipped are not validated: a skipped `bool` can be any byte and a skipped `String` does not have to be UTF-8. Lengths and tags are validated, because they are needed to find the end of the value. Types without lengths or tags that are not fixed-size, like varints and `char`, are skipped by decoding and dropping them.
+///
+/// This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::SkipDecode)]` to your type. See [`skip_value`](crate::skip_value) for an example.
+pub trait SkipDecode {
+    /// Advances `decoder` past an encoded `Self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a length or tag is invalid, or if the input ends before the value does.
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError>;
+
+    /// The amount of bytes that every encoded `Self` takes with config `C`, or `None` if it depends on the value. Containers use this to skip all of their elements at once.
+    #[inline]
+    #[must_use]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        None
+    }
+}
+
+/// Skips a sequence of `len` elements of `T`, all at once if `T` is fixed-size.
+fn skip_elements<T: SkipDecode + ?Sized, D: Decoder>(
+    decoder: &mut D,
+    len: usize,
+) -> Result<(), DecodeError> {
+    if let Some(size) = T::fixed_size::<D::C>() {
+        // the input can not hold more than `usize::MAX` bytes, so an overflow fails like any other length that is too large
+        return decoder.skip_bytes(len.saturating_mul(size));
+    }
+    for _ in 0..len {
+        T::skip(decoder)?;
+    }
+    Ok(())
+}
+
+/// Skips a sequence with its length in front of it, like a slice, `Vec` or map.
+fn skip_sequence<T: SkipDecode + ?Sized, D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+    let len = super::decode_slice_len(decoder)?;
+    skip_elements::<T, D>(decoder, len)
+}
+
+/// Skips all remaining bytes of the input. This is how fields with `#[bincode(remaining_bytes)]` are skipped by `#[derive(SkipDecode)]`.
+///
+/// # Errors
+///
+/// Returns `DecodeError::RemainingBytesUnknown` if the reader does not know where the input ends.
+pub fn skip_remaining_bytes<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+    let len = decoder
+        .reader()
+        .remaining_hint()
+        .ok_or(DecodeError::RemainingBytesUnknown)?;
+    decoder.skip_bytes(len)
+}
+
+macro_rules! impl_skip_fixed {
+    ($($ty:ty => $size:expr),* $(,)?) => {
+        $(
+            impl SkipDecode for $ty {
+                #[inline]
+                fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+                    decoder.skip_bytes($size)
+                }
+
+                #[inline]
+                fn fixed_size<C: Config>() -> Option<usize> {
+                    Some($size)
+                }
+            }
+        )*
+    };
+}
+
+impl_skip_fixed! {
+    bool => 1,
+    u8 => 1,
+    i8 => 1,
+    NonZeroU8 => 1,
+    NonZeroI8 => 1,
+    f32 => 4,
+    f64 => 8,
+    () => 0,
+}
+
+impl<T: ?Sized> SkipDecode for PhantomData<T> {
+    #[inline]
+    fn skip<D: Decoder>(_: &mut D) -> Result<(), DecodeError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        Some(0)
+    }
+}
+
+/// Integers are fixed-size with a fixint config. Varints are decoded to find out how long they are.
+macro_rules! impl_skip_int {
+    ($($ty:ty => $int:ty, $size:expr),* $(,)?) => {
+        $(
+            impl SkipDecode for $ty {
+                #[inline]
+                fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+                    match Self::fixed_size::<D::C>() {
+                        Some(size) => decoder.skip_bytes(size),
+                        None => <$int as Decode<D::Context>>::decode(decoder).map(drop),
+                    }
+                }
+
+                #[inline]
+                fn fixed_size<C: Config>() -> Option<usize> {
+                    match C::INT_ENCODING {
+                        IntEncoding::Fixed => Some($size),
+                        IntEncoding::Variable => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_skip_int! {
+    u16 => u16, 2,
+    u32 => u32, 4,
+    u64 => u64, 8,
+    u128 => u128, 16,
+    usize => u64, 8,
+    i16 => i16, 2,
+    i32 => i32, 4,
+    i64 => i64, 8,
+    i128 => i128, 16,
+    isize => i64, 8,
+    NonZeroU16 => u16, 2,
+    NonZeroU32 => u32, 4,
+    NonZeroU64 => u64, 8,
+    NonZeroU128 => u128, 16,
+    NonZeroUsize => u64, 8,
+    NonZeroI16 => i16, 2,
+    NonZeroI32 => i32, 4,
+    NonZeroI64 => i64, 8,
+    NonZeroI128 => i128, 16,
+    NonZeroIsize => i64, 8,
+}
+
+/// A `char` is fixed-size with a scalar char encoding. As 1 to 4 bytes of UTF-8, it is decoded to find out how long it is.
+impl SkipDecode for char {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <Self as Decode<D::Context>>::decode(decoder).map(drop)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        match C::CHAR_ENCODING {
+            CharEncoding::Scalar => Some(4),
+            CharEncoding::Utf8 => None,
+        }
+    }
+}
+
+impl SkipDecode for Duration {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <(u64, u32)>::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        <(u64, u32)>::fixed_size::<C>()
+    }
+}
+
+impl SkipDecode for str {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        skip_sequence::<u8, D>(decoder)
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for [T] {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        skip_sequence::<T, D>(decoder)
+    }
+}
+
+impl<T: SkipDecode, const N: usize> SkipDecode for [T; N] {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        skip_elements::<T, D>(decoder, N)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()?.checked_mul(N)
+    }
+}
+
+impl<T: SkipDecode + ?Sized> SkipDecode for &T {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode + ?Sized> SkipDecode for &mut T {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+macro_rules! impl_skip_wrapper {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<T: SkipDecode> SkipDecode for $ty<T> {
+                fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+                    T::skip(decoder)
+                }
+
+                #[inline]
+                fn fixed_size<C: Config>() -> Option<usize> {
+                    T::fixed_size::<C>()
+                }
+            }
+        )*
+    };
+}
+
+impl_skip_wrapper!(Wrapping, Reverse, Cell, RefCell);
+
+impl<T: SkipDecode> SkipDecode for Range<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <(T, T)>::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        <(T, T)>::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for RangeInclusive<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <(T, T)>::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        <(T, T)>::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for Option<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match super::decode_option_variant(decoder, core::any::type_name::<Self>())? {
+            Some(()) => T::skip(decoder),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: SkipDecode, U: SkipDecode> SkipDecode for Result<T, U> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match <u32 as Decode<D::Context>>::decode(decoder)? {
+            0 => T::skip(decoder),
+            1 => U::skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                found: u64::from(x),
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ok", 0), ("Err", 1)]),
+                type_name: core::any::type_name::<Self>(),
+            }),
+        }
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for Bound<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match <u32 as Decode<D::Context>>::decode(decoder)? {
+            0 => Ok(()),
+            1 | 2 => T::skip(decoder),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Named(&[
+                    ("Unbounded", 0),
+                    ("Included", 1),
+                    ("Excluded", 2),
+                ]),
+                found: u64::from(x),
+                type_name: core::any::type_name::<Self>(),
+            }),
+        }
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for Poll<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        match <u32 as Decode<D::Context>>::decode(decoder)? {
+            0 => T::skip(decoder),
+            1 => Ok(()),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("Ready", 0), ("Pending", 1)]),
+                found: u64::from(x),
+                type_name: core::any::type_name::<Self>(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_skip_tuple {
+    () => {};
+    ($first:ident $(, $extra:ident)*) => {
+        impl<$first $(, $extra)*> SkipDecode for ($first, $($extra, )*)
+        where
+            $first: SkipDecode,
+        $(
+            $extra: SkipDecode,
+        )*
+        {
+            fn skip<DE: Decoder>(decoder: &mut DE) -> Result<(), DecodeError> {
+                if let Some(size) = Self::fixed_size::<DE::C>() {
+                    return decoder.skip_bytes(size);
+                }
+                $first::skip(decoder)?;
+                $($extra::skip(decoder)?;)*
+                Ok(())
+            }
+
+            #[inline]
+            fn fixed_size<CF: Config>() -> Option<usize> {
+                $first::fixed_size::<CF>() $(?.checked_add($extra::fixed_size::<CF>()?))*
+            }
+        }
+    }
+}
+
+impl_skip_tuple!(A);
+impl_skip_tuple!(A, B);
+impl_skip_tuple!(A, B, C);
+impl_skip_tuple!(A, B, C, D);
+impl_skip_tuple!(A, B, C, D, E);
+impl_skip_tuple!(A, B, C, D, E, F);
+impl_skip_tuple!(A, B, C, D, E, F, G);
+impl_skip_tuple!(A, B, C, D, E, F, G, H);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_skip_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
@@ -0,0 +1,229 @@
+//! A human-readable rendering of a value, appended after its encoding, for debugging payloads that can not be decoded.
+//!
+//! [`encode_to_vec_with_debug_info`] encodes a value like [`encode_to_vec`](crate::encode_to_vec), and appends its `Debug` rendering, compressed, in a trailer after it. Decoding reads the value and stops before the trailer, so the bytes decode like any other encoding. [`debug_info_from_slice`] reads the trailer back without knowing the type of the value, e.g. from a payload that was dumped because it failed to decode:
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! #[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+//! struct Order {
+//!     id: u64,
+//!     items: Vec<String>,
+//! }
+//!
+//! let order = Order { id: 7, items: vec!["apple".into(), "pear".into()] };
+//! let config = bincode::config::standard();
+//! let encoded = bincode::encode_to_vec_with_debug_info(&order, config).unwrap();
+//!
+//! let (decoded, len): (Order, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+//! assert_eq!(decoded, order);
+//! assert_eq!(&encoded[..len], bincode::encode_to_vec(&order, config).unwrap());
+//!
+//! let text = bincode::debug_info_from_slice(&encoded).unwrap().unwrap();
+//! assert_eq!(text, format!("{order:#?}"));
+//! ```
+//!
+//! # Format
+//!
+//! The trailer is the compressed text, followed by a footer of 16 bytes: the length of the text and the length of the compressed text as `u32`s in little endian, and the 8 bytes of [`MAGIC`]. The footer is at the end instead of in front of the text, as the length of the value is not known without its type. The trailer does not depend on the config, so it can be read without it.
+//!
+//! The text is compressed with a small LZ77 scheme, which takes out most of the repetition of the field names in a rendering of a collection. It is a sequence of blocks, each starting with a control byte `c`:
+//!
+//! - `c < 0x80`: the `c + 1` bytes after the control byte are copied to the output.
+//! - `c >= 0x80`: `(c & 0x7F) + 4` bytes are copied from earlier output, starting the amount of bytes back that the `u16` in little endian after the control byte says.
+//!
+//! A config with [`with_no_trailing_bytes`](crate::config::Configuration::with_no_trailing_bytes) rejects the trailer, so remove it with [`strip_debug_info`] before decoding with such a config. Encoding without debug info is not affected in any way.
+
+use crate::{
+    config::Config,
+    enc::Encode,
+    error::{DecodeError, EncodeError},
+};
+use alloc::{format, string::String, vec::Vec};
+
+/// The last 8 bytes of data with debug info.
+pub const MAGIC: [u8; 8] = *b"bincDBG\x01";
+
+/// The size of the footer: the two lengths and [`MAGIC`].
+const FOOTER_SIZE: usize = 16;
+
+/// The shortest match that is copied from earlier output.
+const MIN_MATCH: usize = 4;
+
+/// The longest match that fits in a control byte.
+const MAX_MATCH: usize = MIN_MATCH + 0x7F;
+
+/// The longest run of literal bytes that fits in a control byte.
+const MAX_LITERALS: usize = 0x80;
+
+/// How far back a match can start.
+const WINDOW: usize = u16::MAX as usize;
+
+/// The amount of bits of the hashes that earlier positions are looked up by.
+const HASH_BITS: u32 = 12;
+
+/// Encode the given value into a `Vec<u8>` like [`encode_to_vec`](crate::encode_to_vec), followed by its `Debug` rendering.
+///
+/// The rendering is pretty-printed (`{:#?}`), and appended in a trailer that decoding skips. See the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value cannot be encoded, or if its rendering is longer than `u32::MAX` bytes.
+pub fn encode_to_vec_with_debug_info<E: Encode + core::fmt::Debug, C: Config>(
+    val: E,
+    config: C,
+) -> Result<Vec<u8>, EncodeError> {
+    let text = format!("{val:#?}");
+    encode_to_vec_with_debug_text(val, config, &text)
+}
+
+/// Encode the given value into a `Vec<u8>` like [`encode_to_vec_with_debug_info`], with `text` as its rendering instead of its `Debug` rendering.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value cannot be encoded, or if `text` is longer than `u32::MAX` bytes.
+pub fn encode_to_vec_with_debug_text<E: Encode, C: Config>(
+    val: E,
+    config: C,
+    text: &str,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut bytes = crate::encode_to_vec(val, config)?;
+    append_debug_info(&mut bytes, text)?;
+    Ok(bytes)
+}
+
+/// Appends `text` as the debug info of the encoded value in `bytes`, e.g. of a value that was encoded into a `Vec` with another function than [`encode_to_vec`](crate::encode_to_vec).
+///
+/// # Errors
+///
+/// Returns `EncodeError::Other` if `text` is longer than `u32::MAX` bytes.
+pub fn append_debug_info(bytes: &mut Vec<u8>, text: &str) -> Result<(), EncodeError> {
+    let too_long = |_| EncodeError::Other("debug info is longer than u32::MAX bytes");
+    let text_len = u32::try_from(text.len()).map_err(too_long)?;
+    let start = bytes.len();
+    compress(text.as_bytes(), bytes);
+    let compressed_len = u32::try_from(bytes.len() - start).map_err(too_long)?;
+    bytes.extend_from_slice(&text_len.to_le_bytes());
+    bytes.extend_from_slice(&compressed_len.to_le_bytes());
+    bytes.extend_from_slice(&MAGIC);
+    Ok(())
+}
+
+/// Returns the debug info that [`encode_to_vec_with_debug_info`] appended to `bytes`, or `None` if `bytes` do not end with [`MAGIC`].
+///
+/// The type of the value in front of the debug info does not need to be known.
+///
+/// # Errors
+///
+/// Returns `DecodeError::InvalidDebugInfo` if `bytes` end with [`MAGIC`], but the trailer in front of it is invalid.
+pub fn debug_info_from_slice(bytes: &[u8]) -> Result<Option<String>, DecodeError> {
+    let Some((compressed, text_len)) = trailer(bytes)? else {
+        return Ok(None);
+    };
+    let text = decompress(compressed, text_len).ok_or(DecodeError::InvalidDebugInfo)?;
+    String::from_utf8(text)
+        .map(Some)
+        .map_err(|_| DecodeError::InvalidDebugInfo)
+}
+
+/// Returns `bytes` without the debug info that [`encode_to_vec_with_debug_info`] appended to them, or all of `bytes` if they do not end with [`MAGIC`].
+///
+/// # Errors
+///
+/// Returns `DecodeError::InvalidDebugInfo` if `bytes` end with [`MAGIC`], but the compressed text is longer than the bytes in front of the footer.
+pub fn strip_debug_info(bytes: &[u8]) -> Result<&[u8], DecodeError> {
+    Ok(match trailer(bytes)? {
+        Some((compressed, _)) => &bytes[..bytes.len() - FOOTER_SIZE - compressed.len()],
+        None => bytes,
+    })
+}
+
+/// Returns the compressed text at the end of `bytes` and the length of the text, or `None` if `bytes` do not end with [`MAGIC`].
+fn trailer(bytes: &[u8]) -> Result<Option<(&[u8], usize)>, DecodeError> {
+    let Some((rest, footer)) = bytes.split_last_chunk::<FOOTER_SIZE>() else {
+        return Ok(None);
+    };
+    let (lengths, magic) = footer.split_at(FOOTER_SIZE - MAGIC.len());
+    if magic != MAGIC {
+        return Ok(None);
+    }
+    let length = |bytes: &[u8]| {
+        let bytes: [u8; 4] = bytes
+            .try_into()
+            .map_err(|_| DecodeError::InvalidDebugInfo)?;
+        usize::try_from(u32::from_le_bytes(bytes)).map_err(|_| DecodeError::InvalidDebugInfo)
+    };
+    let text_len = length(&lengths[..4])?;
+    let compressed_len = length(&lengths[4..])?;
+    let start = rest
+        .len()
+        .checked_sub(compressed_len)
+        .ok_or(DecodeError::InvalidDebugInfo)?;
+    Ok(Some((&rest[start..], text_len)))
+}
+
+/// Appends the compressed `input` to `output`, in the format of the [module documentation](self).
+#[allow(clippy::cast_possible_truncation)]
+fn compress(input: &[u8], output: &mut Vec<u8>) {
+    // the last position that every hash of 4 bytes was seen at
+    let mut positions = alloc::vec![usize::MAX; 1 << HASH_BITS];
+    let mut literals = 0;
+    let mut pos = 0;
+    while let Some(key) = input.get(pos..pos + MIN_MATCH) {
+        let hash = u32::from_le_bytes([key[0], key[1], key[2], key[3]]).wrapping_mul(0x9E37_79B1)
+            >> (32 - HASH_BITS);
+        let candidate = core::mem::replace(&mut positions[hash as usize], pos);
+        if candidate == usize::MAX
+            || pos - candidate > WINDOW
+            || input[candidate..candidate + MIN_MATCH] != *key
+        {
+            pos += 1;
+            continue;
+        }
+
+        // the match may overlap the bytes it is copied to, which the decompression copies byte by byte
+        let mut len = MIN_MATCH;
+        while len < MAX_MATCH && input.get(pos + len) == Some(&input[candidate + len]) {
+            len += 1;
+        }
+        push_literals(output, &input[literals..pos]);
+        output.push(0x80 | (len - MIN_MATCH) as u8);
+        output.extend_from_slice(&((pos - candidate) as u16).to_le_bytes());
+        pos += len;
+        literals = pos;
+    }
+    push_literals(output, &input[literals..]);
+}
+
+/// Appends `literals` to `output` as literal blocks.
+#[allow(clippy::cast_possible_truncation)]
+fn push_literals(output: &mut Vec<u8>, literals: &[u8]) {
+    for run in literals.chunks(MAX_LITERALS) {
+        output.push((run.len() - 1) as u8);
+        output.extend_from_slice(run);
+    }
+}
+
+/// Decompresses the output of [`compress`], or returns `None` if `input` is invalid or does not decompress into exactly `len` bytes.
+fn decompress(mut input: &[u8], len: usize) -> Option<Vec<u8>> {
+    // every byte of input decompresses into at most `MAX_MATCH / 3` bytes, so a wrong `len` does not allocate more than that
+    let mut output = Vec::with_capacity(len.min(input.len().saturating_mul(MAX_MATCH / 3)));
+    while let Some((&control, rest)) = input.split_first() {
+        if control < 0x80 {
+            let (run, rest) = rest.split_at_checked(usize::from(control) + 1)?;
+            output.extend_from_slice(run);
+            input = rest;
+        } else {
+            let (offset, rest) = rest.split_first_chunk::<2>()?;
+            let offset = usize::from(u16::from_le_bytes(*offset));
+            let start = output.len().checked_sub(offset).filter(|_| offset > 0)?;
+            for i in 0..usize::from(control & 0x7F) + MIN_MATCH {
+                output.push(output[start + i]);
+            }
+            input = rest;
+        }
+        if output.len() > len {
+            return None;
+        }
+    }
+    (output.len() == len).then_some(output)
+}
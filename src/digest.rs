@@ -0,0 +1,268 @@
+//! Hashing the encoded bytes of a value while it is encoded or decoded, e.g. to sign a message or to check its signature, without encoding it to a buffer first.
+//!
+//! [`DigestWriter`\] passes every byte that is written to it to the wrapped writer and to a hasher, and [`DigestReader`\] does the same for the bytes that are read from it. A hasher is anything that implements [`Update`\]. [`encode_and_digest`\] and [`decode_and_verify_digest`\] wrap both for a hasher that also implements [`Finalize`\].
+//!
+//! With the `digest` feature, [`DigestAdapter`\] makes the hashers of the `digest` crate, like `sha2::Sha256`, usable here.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::digest::{decode_and_verify_digest, encode_and_digest, Finalize, Update};
+//! use bincode::de::read::SliceReader;
+//! use bincode::error::DecodeError;
+//!
+//! /// The sum of all bytes, as a stand-in for a real hash function
+//! #[derive(Default)]
+//! struct Sum(u32);
+//!
+//! impl Update for Sum {
+//!     fn update(&mut self, bytes: &[u8]) {
+//!         self.0 = bytes.iter().fold(self.0, |sum, &byte| sum.wrapping_add(byte.into()));
+//!     }
+//! }
+//!
+//! impl Finalize for Sum {
+//!     type Output = u32;
+//!
+//!     fn finalize(self) -> u32 {
+//!         self.0
+//!     }
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let mut message = Vec::new();
+//! let digest =
+//!     encode_and_digest(("hello", 5u8), bincode::IoWriter::new(&mut message), config, Sum::default())
+//!         .unwrap();
+//! assert_eq!(digest, message.iter().map(|&byte| u32::from(byte)).sum());
+//!
+//! let reader = SliceReader::new(&message);
+//! let (text, n): (String, u8) =
+//!     decode_and_verify_digest(reader, config, Sum::default(), &digest).unwrap();
+//! assert_eq!((text.as_str(), n), ("hello", 5));
+//!
+//! message[1] = b'j';
+//! let reader = SliceReader::new(&message);
+//! let err = decode_and_verify_digest::<(String, u8), _, _, _>(reader, config, Sum::default(), &digest)
+//!     .unwrap_err();
+//! assert!(matches!(err, DecodeError::DigestMismatch));
+//! ```
+
+use crate::{
+    config::Config,
+    de::{
+        read::{BorrowReader, Reader},
+        Decode,
+    },
+    enc::{write::Writer, Encode, EncoderImpl},
+    error::{DecodeError, EncodeError},
+};
+
+/// A hasher that the bytes of [`DigestWriter`\] and [`DigestReader`\] are passed to.
+pub trait Update {
+    /// Adds `bytes` to the hashed data.
+    fn update(&mut self, bytes: &[u8]);
+}
+
+impl<H: Update + ?Sized> Update for &mut H {
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        (**self).update(bytes);
+    }
+}
+
+/// A hasher that can return the digest of the bytes that were passed to it, for [`encode_and_digest`\] and [`decode_and_verify_digest`\].
+pub trait Finalize: Update {
+    /// The digest, e.g. an array of bytes.
+    type Output;
+
+    /// Returns the digest of all bytes that were passed to [`Update::update`].
+    fn finalize(self) -> Self::Output;
+}
+
+/// A writer that passes the bytes written to it to the wrapped writer and to a hasher.
+///
+/// Only bytes that the wrapped writer accepted are hashed. This does not implement [`SeekWriter`](crate::enc::write::SeekWriter), because bytes that were hashed cannot be overwritten. [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix) collects its section in a `Vec<u8>` instead.
+pub struct DigestWriter<W, H> {
+    writer: W,
+    hasher: H,
+}
+
+impl<W: Writer, H: Update> DigestWriter<W, H> {
+    /// Create a new `DigestWriter` that writes to `writer` and hashes with `hasher`.
+    pub const fn new(writer: W, hasher: H) -> Self {
+        Self { writer, hasher }
+    }
+
+    /// The hasher, which has been passed all bytes written so far.
+    #[must_use]
+    pub const fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the wrapped writer and the hasher.
+    pub fn into_parts(self) -> (W, H) {
+        (self.writer, self.hasher)
+    }
+}
+
+impl<W: Writer, H: Update> Writer for DigestWriter<W, H> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write(bytes)?;
+        self.hasher.update(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_byte_slice(bytes)?;
+        self.hasher.update(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+}
+
+/// A reader that passes the bytes read from it to a hasher.
+///
+/// Skipped bytes are read and hashed as well. Bytes that are borrowed from a [`BorrowReader`\] are hashed when they are taken.
+pub struct DigestReader<R, H> {
+    reader: R,
+    hasher: H,
+}
+
+impl<R: Reader, H: Update> DigestReader<R, H> {
+    /// Create a new `DigestReader` that reads from `reader` and hashes with `hasher`.
+    pub const fn new(reader: R, hasher: H) -> Self {
+        Self { reader, hasher }
+    }
+
+    /// The hasher, which has been passed all bytes read so far.
+    #[must_use]
+    pub const fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the wrapped reader and the hasher.
+    pub fn into_parts(self) -> (R, H) {
+        (self.reader, self.hasher)
+    }
+}
+
+impl<R: Reader, H: Update> Reader for DigestReader<R, H> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.hasher.update(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [core::mem::MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let bytes = self.reader.read_uninit(bytes)?;
+        self.hasher.update(bytes);
+        Ok(bytes)
+    }
+
+    // `peek_read`, `consume` and `skip` are not passed on, so every byte goes through `read`
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        self.reader.remaining_hint()
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>, H: Update> BorrowReader<'storage> for DigestReader<R, H> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.hasher.update(bytes);
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        let bytes = self.reader.take_bytes_mut(length)?;
+        self.hasher.update(bytes);
+        Ok(bytes)
+    }
+}
+
+/// Encode `val` into `writer`, and return the digest of the encoded bytes.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the writer fails or the value cannot be encoded.
+pub fn encode_and_digest<E: Encode, W: Writer, C: Config, H: Finalize>(
+    val: E,
+    writer: W,
+    config: C,
+    hasher: H,
+) -> Result<H::Output, EncodeError> {
+    let mut encoder = EncoderImpl::<_, C>::new(DigestWriter::new(writer, hasher), config);
+    val.encode(&mut encoder)?;
+    let (_, hasher) = encoder.into_writer().into_parts();
+    Ok(hasher.finalize())
+}
+
+/// Decode a `D` from `reader`, and check that the digest of the bytes it was decoded from is `expected`.
+///
+/// Only the bytes that were needed to decode `D` are hashed. Bytes after them are left in `reader`.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::DigestMismatch`\] if the digest differs from `expected`, or a `DecodeError` if the reader fails or the data is invalid.
+pub fn decode_and_verify_digest<D: Decode<()>, R: Reader, C: Config, H: Finalize>(
+    reader: R,
+    config: C,
+    hasher: H,
+    expected: &H::Output,
+) -> Result<D, DecodeError>
+where
+    H::Output: PartialEq,
+{
+    let mut reader = DigestReader::new(reader, hasher);
+    let value = crate::decode_from_reader(&mut reader, config)?;
+    let (_, hasher) = reader.into_parts();
+    if hasher.finalize() == *expected {
+        Ok(value)
+    } else {
+        Err(DecodeError::DigestMismatch)
+    }
+}
+
+/// Makes a hasher of the `digest` crate usable as a hasher of [`DigestWriter`\], [`DigestReader`\], [`encode_and_digest`\] and [`decode_and_verify_digest`\].
+///
+/// ```ignore
+/// use bincode::digest::{encode_and_digest, DigestAdapter};
+/// use sha2::{Digest, Sha256};
+///
+/// let hash = encode_and_digest(&message, writer, config, DigestAdapter(Sha256::new()))?;
+/// ```
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+#[derive(Clone, Debug, Default)]
+pub struct DigestAdapter<D>(pub D);
+
+#[cfg(feature = "digest")]
+impl<D: ::digest::Update> Update for DigestAdapter<D> {
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<D: ::digest::Update + ::digest::FixedOutput> Finalize for DigestAdapter<D> {
+    type Output = ::digest::Output<D>;
+
+    fn finalize(self) -> Self::Output {
+        self.0.finalize_fixed()
+    }
+}
@@ -1,10 +1,12 @@
 use super::{write::Writer, Encoder};
-use crate::{config::Config, utils::Sealed};
+use crate::{
+    config::{Config, InternalEndianConfig, WithEndian},
+    utils::Sealed,
+};
 
 /// An Encoder that writes bytes into a given writer `W`.
 ///
-/// This struct should rarely be used.
-/// In most cases, prefer any of the `encode` functions.
+/// In most cases, prefer any of the `encode` functions. Use an encoder directly to encode several values one after another into the same writer, which is the same as encoding them as a tuple.
 ///
 /// The `ByteOrder` that is chosen will impact the endianness that
 /// is used to write integers to the writer.
@@ -23,12 +25,19 @@ use crate::{config::Config, utils::Sealed};
 pub struct EncoderImpl<W: Writer, C: Config> {
     writer: W,
     config: C,
+    #[cfg(feature = "alloc")]
+    shared: crate::shared::SharedPointers,
 }
 
 impl<W: Writer, C: Config> EncoderImpl<W, C> {
     /// Create a new Encoder
     pub const fn new(writer: W, config: C) -> Self {
-        Self { writer, config }
+        Self {
+            writer,
+            config,
+            #[cfg(feature = "alloc")]
+            shared: crate::shared::SharedPointers::new(),
+        }
     }
 
     /// Return the underlying writer
@@ -36,6 +45,12 @@ impl<W: Writer, C: Config> EncoderImpl<W, C> {
     pub fn into_writer(self) -> W {
         self.writer
     }
+
+    /// Return a reference to the underlying writer
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn writer_ref(&self) -> &W {
+        &self.writer
+    }
 }
 
 impl<W: Writer, C: Config> Encoder for EncoderImpl<W, C> {
@@ -52,6 +67,49 @@ impl<W: Writer, C: Config> Encoder for EncoderImpl<W, C> {
     fn config(&self) -> &Self::C {
         &self.config
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn shared_pointers(&mut self) -> &mut crate::shared::SharedPointers {
+        &mut self.shared
+    }
 }
 
 impl<W: Writer, C: Config> Sealed for EncoderImpl<W, C> {}
+
+/// An encoder that writes with the endianness of `E` instead of the endianness of the config of the wrapped encoder, see [`encode_with_endian`](super::encode_with_endian).
+pub struct EndianEncoder<'a, Enc: Encoder, E> {
+    encoder: &'a mut Enc,
+    config: WithEndian<Enc::C, E>,
+}
+
+impl<'a, Enc: Encoder, E> EndianEncoder<'a, Enc, E> {
+    pub fn new(encoder: &'a mut Enc) -> Self {
+        let config = WithEndian::new(*encoder.config());
+        Self { encoder, config }
+    }
+}
+
+impl<Enc: Encoder, E: InternalEndianConfig + Copy> Encoder for EndianEncoder<'_, Enc, E> {
+    type W = Enc::W;
+
+    type C = WithEndian<Enc::C, E>;
+
+    #[inline]
+    fn writer(&mut self) -> &mut Self::W {
+        self.encoder.writer()
+    }
+
+    #[inline]
+    fn config(&self) -> &Self::C {
+        &self.config
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn shared_pointers(&mut self) -> &mut crate::shared::SharedPointers {
+        self.encoder.shared_pointers()
+    }
+}
+
+impl<Enc: Encoder, E> Sealed for EndianEncoder<'_, Enc, E> {}
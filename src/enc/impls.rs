@@ -1,7 +1,10 @@
 #![allow(unsafe_code)]
 use super::{write::Writer, Encode, Encoder};
 use crate::{
-    config::{Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig},
+    config::{
+        CharEncoding, Endianness, IntEncoding, InternalCharEncodingConfig, InternalEndianConfig,
+        InternalIntEncodingConfig,
+    },
     error::EncodeError,
 };
 use core::cmp::Reverse;
@@ -13,6 +16,7 @@ use core::{
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
     },
     ops::{Bound, Range, RangeInclusive},
+    task::Poll,
     time::Duration,
 };
 
@@ -290,7 +294,15 @@ impl<T: Encode> Encode for Reverse<T> {
 
 impl Encode for char {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        encode_utf8(encoder.writer(), *self)
+        match (E::C::CHAR_ENCODING, E::C::ENDIAN) {
+            (CharEncoding::Utf8, _) => encode_utf8(encoder.writer(), *self),
+            (CharEncoding::Scalar, Endianness::Big) => {
+                encoder.writer().write(&u32::from(*self).to_be_bytes())
+            }
+            (CharEncoding::Scalar, Endianness::Little) => {
+                encoder.writer().write(&u32::from(*self).to_le_bytes())
+            }
+        }
     }
 }
 
@@ -299,12 +311,12 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        super::encode_slice_len(encoder, self.len())?;
+        if let Some(bytes) = crate::plain::as_bytes(self) {
+            return encoder.encode_byte_slice(bytes);
+        }
 
-        if unty::type_equal::<T, u8>() {
-            // Safety: T = u8
-            let t: &[u8] = unsafe { &*(core::ptr::from_ref::<[T]>(self) as *const [u8]) };
-            encoder.writer().write(t)?;
+        super::encode_slice_len(encoder, self.len())?;
+        if crate::plain::encode_slice(encoder, self)? {
             return Ok(());
         }
 
@@ -360,17 +372,14 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        if unty::type_equal::<T, u8>() {
-            // Safety: this is &[u8; N]
-            let array_slice: &[u8] =
-                unsafe { core::slice::from_raw_parts(self.as_ptr().cast(), N) };
-            encoder.writer().write(array_slice)
-        } else {
-            for item in self {
-                item.encode(encoder)?;
-            }
-            Ok(())
+        if crate::plain::encode_slice(encoder, self)? {
+            return Ok(());
         }
+
+        for item in self {
+            item.encode(encoder)?;
+        }
+        Ok(())
     }
 }
 
@@ -482,6 +491,21 @@ where
     }
 }
 
+impl<T> Encode for Poll<T>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            Self::Ready(val) => {
+                0u32.encode(encoder)?;
+                val.encode(encoder)
+            }
+            Self::Pending => 1u32.encode(encoder),
+        }
+    }
+}
+
 impl<T> Encode for &T
 where
     T: Encode + ?Sized,
@@ -490,3 +514,12 @@ where
         T::encode(self, encoder)
     }
 }
+
+impl<T> Encode for &mut T
+where
+    T: Encode + ?Sized,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        T::encode(self, encoder)
+    }
+}
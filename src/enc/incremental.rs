@@ -0,0 +1,365 @@
+//! Encoding a large value in bounded steps, e.g. spread over the frames of a game loop instead of all at once.
+//!
+//! [`IncrementalEncoder`] encodes a value that implements [`IncrementalEncode`] into a writer, a few bytes at a time: every call to [`step`](IncrementalEncoder::step) writes about `max_bytes` bytes and returns, and the next call continues where it stopped. The bytes of all steps together are exactly the bytes of [`encode_into_writer`](crate::encode_into_writer).
+//!
+//! Instead of recursing through the whole value, the encoder keeps an explicit stack of the containers that it is in the middle of. A value encodes its start, like the length of a `Vec`, and returns the parts that follow it, which the encoder works through one by one. Byte slices and strings are split at exactly `max_bytes`, everything else is only split between the values it is made of.
+//!
+//! `#[derive(IncrementalEncode)]` (with the `derive` feature) implements it for a struct, whose fields must implement it as well:
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::enc::incremental::{IncrementalEncoder, StepResult};
+//! use std::collections::BTreeMap;
+//!
+//! #[derive(bincode::Encode, bincode::IncrementalEncode)]
+//! struct Snapshot {
+//!     tick: u64,
+//!     names: Vec<String>,
+//!     positions: BTreeMap<u32, (f32, f32)>,
+//! }
+//!
+//! let snapshot = Snapshot {
+//!     tick: 7,
+//!     names: vec!["crab".into(); 1000],
+//!     positions: (0..1000).map(|id| (id, (1.0, 2.0))).collect(),
+//! };
+//! let config = bincode::config::standard();
+//! let mut encoder = IncrementalEncoder::new(&snapshot, bincode::VecWriter::default(), config);
+//! let mut steps = 0;
+//! while encoder.step(1024).unwrap() == StepResult::More {
+//!     // the rest of the frame
+//!     steps += 1;
+//! }
+//! assert!(steps > 10);
+//! let encoded = encoder.into_writer().into_inner();
+//! assert_eq!(encoded, bincode::encode_to_vec(&snapshot, config).unwrap());
+//! ```
+//!
+//! Primitives are encoded at once. A type without an implementation can implement it by encoding itself completely in [`encode_start`](IncrementalEncode::encode_start) and returning `None`.
+
+use super::{
+    encode_option_variant, encode_slice_len,
+    write::{ProgressWriter, Writer},
+    Encode, EncoderImpl,
+};
+use crate::{config::Config, enc::Encoder, error::EncodeError};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    string::String,
+    vec::Vec,
+};
+use core::ops::ControlFlow;
+
+/// A value that can be encoded in steps by an [`IncrementalEncoder`]. See the [module documentation](self).
+///
+/// The bytes that [`encode_start`](IncrementalEncode::encode_start) writes, followed by the bytes of its parts in order, must be exactly the bytes of the `Encode` implementation of the type.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::incremental::{parts, IncrementalEncode, Part, Parts};
+/// use bincode::enc::{Encode, Encoder};
+/// use bincode::error::EncodeError;
+///
+/// #[derive(bincode::Encode)]
+/// struct Level {
+///     id: u32,
+///     tiles: Vec<u8>,
+/// }
+///
+/// impl<E: Encoder> IncrementalEncode<E> for Level {
+///     fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+///         self.id.encode(encoder)?;
+///         Ok(Some(parts([Part::Value(&self.tiles)])))
+///     }
+/// }
+/// ```
+pub trait IncrementalEncode<E: Encoder> {
+    /// Encode the start of `self`, and return the parts that follow it in order, or `None` if `self` was encoded completely.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodeError` if the start can not be encoded.
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError>;
+}
+
+/// The parts of a value after its start, returned by [`IncrementalEncode::encode_start`].
+pub type Parts<'a, E> = Box<dyn Iterator<Item = Part<'a, E>> + 'a>;
+
+/// A part of a value that an [`IncrementalEncoder`] encodes.
+pub enum Part<'a, E> {
+    /// A value that is encoded in steps itself
+    Value(&'a dyn IncrementalEncode<E>),
+    /// Bytes that are written as they are, e.g. the contents of a string after its length
+    Bytes(&'a [u8]),
+}
+
+/// Returns the parts of a value with a fixed amount of parts, e.g. the fields of a struct.
+#[must_use]
+pub fn parts<'a, E: 'a, const N: usize>(parts: [Part<'a, E>; N]) -> Parts<'a, E> {
+    Box::new(parts.into_iter())
+}
+
+/// The result of [`IncrementalEncoder::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+    /// The value is not encoded completely yet
+    More,
+    /// The value is encoded completely
+    Done,
+}
+
+/// The writer of a [`StepEncoder`], which counts the bytes that are written.
+pub type StepWriter<W> = ProgressWriter<W, fn(u64) -> ControlFlow<()>>;
+
+/// The encoder that an [`IncrementalEncoder`] passes to the values that it encodes.
+pub type StepEncoder<W, C> = EncoderImpl<StepWriter<W>, C>;
+
+/// Encodes a value into a writer in bounded steps. See the [module documentation](self).
+pub struct IncrementalEncoder<'a, W: Writer, C: Config> {
+    encoder: StepEncoder<W, C>,
+    /// The value, until the first step starts it
+    value: Option<&'a dyn IncrementalEncode<StepEncoder<W, C>>>,
+    /// The remaining parts of every value that was started but not finished, innermost last
+    stack: Vec<Parts<'a, StepEncoder<W, C>>>,
+    /// The rest of a `Part::Bytes` that did not fit in the last step
+    bytes: &'a [u8],
+}
+
+impl<'a, W: Writer, C: Config> IncrementalEncoder<'a, W, C> {
+    /// Create a new `IncrementalEncoder` that encodes `value` into `writer`. Nothing is written until the first [`step`](Self::step).
+    pub fn new<T: IncrementalEncode<StepEncoder<W, C>>>(
+        value: &'a T,
+        writer: W,
+        config: C,
+    ) -> Self {
+        let writer = ProgressWriter::new(writer, u64::MAX, (|_| ControlFlow::Continue(())) as _);
+        Self {
+            encoder: EncoderImpl::new(writer, config),
+            value: Some(value),
+            stack: Vec::new(),
+            bytes: &[],
+        }
+    }
+
+    /// Encode the next part of the value, of about `max_bytes` bytes.
+    ///
+    /// A step stops once it wrote at least `max_bytes` bytes, and at least one byte if `max_bytes` is 0. Byte slices and strings are split at exactly `max_bytes`, but a primitive or a length is always written at once, so a step can write a few bytes more than `max_bytes`. The last step can write less.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodeError` if a part of the value can not be encoded or the writer fails. The encoder must not be stepped again after an error.
+    pub fn step(&mut self, max_bytes: usize) -> Result<StepResult, EncodeError> {
+        let end = self
+            .bytes_written()
+            .saturating_add(u64::try_from(max_bytes.max(1)).unwrap_or(u64::MAX));
+        loop {
+            let written = self.bytes_written();
+            if written >= end {
+                return Ok(StepResult::More);
+            }
+            if !self.bytes.is_empty() {
+                let len = usize::try_from(end - written)
+                    .map_or(self.bytes.len(), |left| left.min(self.bytes.len()));
+                let (chunk, rest) = self.bytes.split_at(len);
+                self.encoder.writer().write(chunk)?;
+                self.bytes = rest;
+                continue;
+            }
+
+            let part = match self.value.take() {
+                Some(value) => Part::Value(value),
+                None => match self.stack.last_mut().map(Iterator::next) {
+                    Some(Some(part)) => part,
+                    Some(None) => {
+                        self.stack.pop();
+                        continue;
+                    }
+                    None => return Ok(StepResult::Done),
+                },
+            };
+            match part {
+                Part::Value(value) => {
+                    if let Some(parts) = value.encode_start(&mut self.encoder)? {
+                        self.stack.push(parts);
+                    }
+                }
+                Part::Bytes(bytes) => self.bytes = bytes,
+            }
+        }
+    }
+
+    /// The amount of bytes written so far.
+    #[must_use]
+    pub const fn bytes_written(&self) -> u64 {
+        self.encoder.writer_ref().bytes_written()
+    }
+
+    /// Return the underlying writer, e.g. once [`step`](Self::step) returned [`StepResult::Done`].
+    pub fn into_writer(self) -> W {
+        self.encoder.into_writer().into_inner()
+    }
+}
+
+macro_rules! impl_at_once {
+    ($($ty:ty),*) => {$(
+        impl<E: Encoder> IncrementalEncode<E> for $ty {
+            fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+                self.encode(encoder)?;
+                Ok(None)
+            }
+        }
+    )*};
+}
+
+impl_at_once!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    core::time::Duration
+);
+
+/// Returns the elements of `slice` as parts, or its bytes if they are its in-memory bytes.
+fn elements<'a, T, E>(slice: &'a [T]) -> Parts<'a, E>
+where
+    T: IncrementalEncode<E> + Encode,
+    E: Encoder + 'a,
+{
+    match crate::plain::native_bytes::<T, E::C>(slice) {
+        Some(bytes) => parts([Part::Bytes(bytes)]),
+        None => Box::new(slice.iter().map(|item| Part::Value(item))),
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E> + Encode> IncrementalEncode<E> for [T] {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(elements(self)))
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E> + Encode, const N: usize> IncrementalEncode<E> for [T; N] {
+    fn encode_start<'a>(&'a self, _: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        Ok(Some(elements(self)))
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E> + Encode> IncrementalEncode<E> for Vec<T> {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        self.as_slice().encode_start(encoder)
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E> + Encode> IncrementalEncode<E> for VecDeque<T> {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        let (front, back) = self.as_slices();
+        Ok(Some(Box::new(elements(front).chain(elements(back)))))
+    }
+}
+
+impl<E: Encoder> IncrementalEncode<E> for str {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(parts([Part::Bytes(self.as_bytes())])))
+    }
+}
+
+impl<E: Encoder> IncrementalEncode<E> for String {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        self.as_str().encode_start(encoder)
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E> + ?Sized> IncrementalEncode<E> for Box<T> {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        (**self).encode_start(encoder)
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E>> IncrementalEncode<E> for Option<T> {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_option_variant(encoder, self.as_ref())?;
+        self.as_ref()
+            .map_or(Ok(None), |value| value.encode_start(encoder))
+    }
+}
+
+impl<E, K, V> IncrementalEncode<E> for BTreeMap<K, V>
+where
+    E: Encoder,
+    K: IncrementalEncode<E>,
+    V: IncrementalEncode<E>,
+{
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(Box::new(self.iter().flat_map(|(key, value)| {
+            [Part::Value(key), Part::Value(value)]
+        }))))
+    }
+}
+
+impl<E: Encoder, T: IncrementalEncode<E>> IncrementalEncode<E> for BTreeSet<T> {
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(Box::new(self.iter().map(|item| Part::Value(item)))))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E, K, V, S> IncrementalEncode<E> for std::collections::HashMap<K, V, S>
+where
+    E: Encoder,
+    K: IncrementalEncode<E>,
+    V: IncrementalEncode<E>,
+{
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(Box::new(self.iter().flat_map(|(key, value)| {
+            [Part::Value(key), Part::Value(value)]
+        }))))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Encoder, T: IncrementalEncode<E>, S> IncrementalEncode<E>
+    for std::collections::HashSet<T, S>
+{
+    fn encode_start<'a>(&'a self, encoder: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+        encode_slice_len(encoder, self.len())?;
+        Ok(Some(Box::new(self.iter().map(|item| Part::Value(item)))))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident . $index:tt),+) => {
+        impl<E: Encoder, $($name: IncrementalEncode<E>),+> IncrementalEncode<E> for ($($name,)+) {
+            fn encode_start<'a>(&'a self, _: &mut E) -> Result<Option<Parts<'a, E>>, EncodeError> {
+                Ok(Some(parts([$(Part::Value(&self.$index)),+])))
+            }
+        }
+    };
+}
+
+impl_tuple!(A.0);
+impl_tuple!(A.0, B.1);
+impl_tuple!(A.0, B.1, C.2);
+impl_tuple!(A.0, B.1, C.2, D.3);
+impl_tuple!(A.0, B.1, C.2, D.3, F.4);
+impl_tuple!(A.0, B.1, C.2, D.3, F.4, G.5);
+impl_tuple!(A.0, B.1, C.2, D.3, F.4, G.5, H.6);
+impl_tuple!(A.0, B.1, C.2, D.3, F.4, G.5, H.6, I.7);
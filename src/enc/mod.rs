@@ -4,9 +4,18 @@ mod encoder;
 mod impl_tuples;
 mod impls;
 
-use self::write::Writer;
-use crate::{config::Config, error::EncodeError, utils::Sealed};
+use self::{encoder::EndianEncoder, write::Writer};
+use crate::{
+    config::{
+        BigEndian, Config, Endianness, FixedLenPadding, InternalEndianConfig,
+        InternalLengthEncodingConfig, LengthEncoding, LittleEndian, TagType,
+    },
+    error::EncodeError,
+    utils::Sealed,
+};
 
+#[cfg(feature = "alloc")]
+pub mod incremental;
 pub mod write;
 
 pub use self::encoder::EncoderImpl;
@@ -15,6 +24,19 @@ pub use self::encoder::EncoderImpl;
 ///
 /// This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::Encode)]` to your trait.
 ///
+/// The derived implementation requires the types of the fields that use generic parameters to implement `Encode`, not the generic parameters themselves. Fields in a `Box`, `Vec` or another pointer or collection require the generic parameters that they use instead, because a recursive type would otherwise require its own implementation. A `PhantomData<T>` field does not require anything of `T`. If these bounds do not fit, they can be replaced with `#[bincode(encode_bounds = "...")]`.
+///
+/// A type that is only ever encoded, like a request that is sent but never received, can say so with `#[bincode(encode_only)]`. Deriving `Decode`, `BorrowDecode` or `SkipDecode` for it is then a compile error, instead of an implementation with bounds that its generic parameters do not meet. `#[bincode(decode_only)]` does the same for the other direction.
+///
+/// ```compile_fail
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(encode_only)]
+/// struct Request {
+///     id: u32,
+/// }
+/// ```
+///
 /// # Implementing this trait manually
 ///
 /// If you want to implement this trait for your type, the easiest way is to add a `#[derive(bincode::Encode)]`, build and check your `target/generated/bincode/` folder. This should generate a `<Struct name>_Encode.rs` file.
@@ -54,6 +76,17 @@ pub trait Encode {
     ///
     /// Returns any error encountered during encoding.
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError>;
+
+    /// Returns `Some` if this type implements [`TransparentWire`](crate::TransparentWire), so slices of it can be encoded like slices of its wire type. Only `#[derive(TransparentWire)]` overrides this.
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    fn transparent_wire() -> Option<crate::transparent::Transparent<Self>>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Helper trait to encode basic types into.
@@ -69,6 +102,176 @@ pub trait Encoder: Sealed {
 
     /// Returns a reference to the config
     fn config(&self) -> &Self::C;
+
+    /// Returns the shared values that this encoder encoded so far, see the [`shared`](crate::shared) module.
+    #[cfg(feature = "alloc")]
+    fn shared_pointers(&mut self) -> &mut crate::shared::SharedPointers;
+
+    /// Encode a byte slice: its length, followed by its bytes. This is how `&[u8]`, `Vec<u8>` and `String` are encoded.
+    ///
+    /// The bytes are written with [`Writer::write_byte_slice`], so writers can handle them as a single blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodeError` if the writer fails.
+    #[inline]
+    fn encode_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+    {
+        encode_slice_len(self, bytes.len())?;
+        self.writer().write_byte_slice(bytes)
+    }
+
+    /// Encode a section with `f`, prefixed by the amount of bytes that `f` wrote. The length is always written as a `u64` of 8 bytes in the endianness of the config, also when the config uses varint encoding, so there is room for it before the section is written.
+    ///
+    /// If the writer is a [`SeekWriter`](write::SeekWriter), as returned by [`Writer::as_seek_writer`], the section is written in a single pass, and the length is written in place afterwards. Otherwise the section is collected in a `Vec<u8>` first, see [`SectionWriter`](write::SectionWriter). Sections can be nested.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::enc::{Encode, Encoder};
+    /// use bincode::error::EncodeError;
+    ///
+    /// struct Chapter {
+    ///     title: String,
+    ///     pages: Vec<String>,
+    /// }
+    ///
+    /// impl Encode for Chapter {
+    ///     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+    ///         // readers can skip a chapter without decoding its pages
+    ///         encoder.with_length_prefix(|encoder| {
+    ///             self.title.encode(encoder)?;
+    ///             self.pages.encode(encoder)
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let chapter = Chapter {
+    ///     title: String::from("One"),
+    ///     pages: vec![String::from("It was a dark and stormy night")],
+    /// };
+    /// let encoded = bincode::encode_to_vec(&chapter, bincode::config::standard()).unwrap();
+    /// assert_eq!(encoded[..8], (encoded.len() as u64 - 8).to_le_bytes());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, or an error if the writer fails. Without the `alloc` feature, this fails with `EncodeError::Other` if the writer is not a `SeekWriter`.
+    fn with_length_prefix<R>(
+        &mut self,
+        f: impl FnOnce(&mut EncoderImpl<write::SectionWriter<'_>, Self::C>) -> Result<R, EncodeError>,
+    ) -> Result<R, EncodeError>
+    where
+        Self: Sized,
+    {
+        let config = *self.config();
+        // the section is encoded by another encoder, which takes over the shared values
+        #[cfg(feature = "alloc")]
+        let mut shared = core::mem::take(self.shared_pointers());
+        let encode = || {
+            let writer = write::SectionWriter::start(self.writer(), Self::C::ENDIAN)?;
+            let mut encoder = EncoderImpl::new(writer, config);
+            #[cfg(feature = "alloc")]
+            core::mem::swap(&mut shared, encoder.shared_pointers());
+            let result = f(&mut encoder);
+            #[cfg(feature = "alloc")]
+            core::mem::swap(&mut shared, encoder.shared_pointers());
+            let value = result?;
+            #[cfg(feature = "alloc")]
+            if let Some(buffer) = encoder.into_writer().finish()? {
+                self.writer().write(&buffer)?;
+            }
+            #[cfg(not(feature = "alloc"))]
+            encoder.into_writer().finish()?;
+            Ok(value)
+        };
+        let result = encode();
+        #[cfg(feature = "alloc")]
+        {
+            *self.shared_pointers() = shared;
+        }
+        result
+    }
+
+    /// Encode a value with `f`, followed by zero bytes up to a total of `pad_to` bytes, for formats with fields or records of a fixed size. Decode it with [`Decoder::with_padding`](crate::de::Decoder::with_padding).
+    ///
+    /// This is used by `#[derive(Encode)]` for fields with `#[bincode(pad_to = N)]` and structs with `#[bincode(pad_struct_to = N)]`.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    /// #[bincode(pad_struct_to = 16)]
+    /// struct Record {
+    ///     #[bincode(pad_to = 8)]
+    ///     name: String,
+    ///     id: u32,
+    /// }
+    ///
+    /// let config = bincode::config::legacy();
+    /// let record = Record { name: String::from("ab"), id: 7 };
+    /// let encoded = bincode::encode_to_vec(&record, config);
+    /// // the length of the name takes up 8 bytes with this config, so it only has room for an empty name
+    /// assert!(matches!(
+    ///     encoded,
+    ///     Err(bincode::error::EncodeError::PaddingExceeded { pad_to: 8, size: 10 })
+    /// ));
+    ///
+    /// let config = bincode::config::standard();
+    /// let encoded = bincode::encode_to_vec(&record, config).unwrap();
+    /// assert_eq!(encoded, [2, b'a', b'b', 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0]);
+    /// let (decoded, len): (Record, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    /// assert_eq!((decoded, len), (record, 16));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, `EncodeError::PaddingExceeded` if `f` wrote more than `pad_to` bytes, or an error if the writer fails.
+    fn with_padding<R>(
+        &mut self,
+        pad_to: usize,
+        f: impl FnOnce(&mut EncoderImpl<write::PaddedWriter<'_>, Self::C>) -> Result<R, EncodeError>,
+    ) -> Result<R, EncodeError>
+    where
+        Self: Sized,
+    {
+        let config = *self.config();
+        // like `with_length_prefix`, the value is encoded by another encoder, which takes over the shared values
+        #[cfg(feature = "alloc")]
+        let mut shared = core::mem::take(self.shared_pointers());
+        let encode = || {
+            let writer = write::PaddedWriter::new(self.writer(), pad_to);
+            let mut encoder = EncoderImpl::new(writer, config);
+            #[cfg(feature = "alloc")]
+            core::mem::swap(&mut shared, encoder.shared_pointers());
+            let result = f(&mut encoder);
+            #[cfg(feature = "alloc")]
+            core::mem::swap(&mut shared, encoder.shared_pointers());
+            let value = result?;
+            encoder.into_writer().finish()?;
+            Ok(value)
+        };
+        let result = encode();
+        #[cfg(feature = "alloc")]
+        {
+            *self.shared_pointers() = shared;
+        }
+        result
+    }
+
+    /// Marks that the following bytes belong to the field or value `name`, until the matching [`leave`](Encoder::leave), see [`Writer::enter`]. Derived implementations call this for every field when the `stats` feature is enabled. Without the feature, the derive does not emit any markers.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.writer().enter(name);
+    }
+
+    /// Ends the innermost [`enter`](Encoder::enter) marker.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.writer().leave();
+    }
 }
 
 impl<T> Encoder for &mut T
@@ -86,6 +289,11 @@ where
     fn config(&self) -> &Self::C {
         T::config(self)
     }
+
+    #[cfg(feature = "alloc")]
+    fn shared_pointers(&mut self) -> &mut crate::shared::SharedPointers {
+        T::shared_pointers(self)
+    }
 }
 
 /// Encode the variant of the given option. Will not encode the option itself.
@@ -100,8 +308,249 @@ pub(crate) fn encode_option_variant<E: Encoder, T>(
     }
 }
 
+/// Encodes the tag of an enum variant as `tag_type`. This is used by `#[derive(Encode)]` for enums with `#[bincode(tag_type = "...")]`, see [`TagType`].
+///
+/// # Errors
+///
+/// Returns `EncodeError::Other` if `tag` does not fit in `tag_type`, or an error if the writer fails.
+pub fn encode_variant_tag<E: Encoder>(
+    encoder: &mut E,
+    tag: u32,
+    tag_type: TagType,
+) -> Result<(), EncodeError> {
+    let too_large = |_| EncodeError::Other("enum variant tag does not fit in its tag type");
+    match tag_type {
+        TagType::U8 => u8::try_from(tag).map_err(too_large)?.encode(encoder),
+        TagType::U16 => {
+            let tag = u16::try_from(tag).map_err(too_large)?;
+            match E::C::ENDIAN {
+                Endianness::Little => encoder.writer().write(&tag.to_le_bytes()),
+                Endianness::Big => encoder.writer().write(&tag.to_be_bytes()),
+            }
+        }
+        TagType::U32 => match E::C::ENDIAN {
+            Endianness::Little => encoder.writer().write(&tag.to_le_bytes()),
+            Endianness::Big => encoder.writer().write(&tag.to_be_bytes()),
+        },
+        TagType::Varint => crate::varint::varint_encode_u32(encoder.writer(), E::C::ENDIAN, tag),
+    }
+}
+
+/// Encodes `value` with `endian` instead of the endianness of the config. This is used by `#[derive(Encode)]` for fields with `#[bincode(endian = "...")]`.
+///
+/// The attribute can be used on integers, floats and arrays of them, e.g. for a format that is little endian except for a few fields. `Decode` and `BorrowDecode` read these fields with the same endianness.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// struct Record {
+///     id: u32,
+///     #[bincode(endian = "big")]
+///     legacy_id: u32,
+///     #[bincode(endian = "big")]
+///     legacy_pair: [u16; 2],
+/// }
+///
+/// let config = bincode::config::legacy();
+/// let record = Record { id: 1, legacy_id: 2, legacy_pair: [3, 4] };
+/// let encoded = bincode::encode_to_vec(&record, config).unwrap();
+/// assert_eq!(encoded, [1, 0, 0, 0, 0, 0, 0, 2, 0, 3, 0, 4]);
+///
+/// let (decoded, _): (Record, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(decoded, record);
+/// ```
+///
+/// # Errors
+///
+/// Returns any error encountered during encoding.
+pub fn encode_with_endian<E: Encoder, T: Encode + ?Sized>(
+    encoder: &mut E,
+    value: &T,
+    endian: Endianness,
+) -> Result<(), EncodeError> {
+    match endian {
+        Endianness::Little => value.encode(&mut EndianEncoder::<_, LittleEndian>::new(encoder)),
+        Endianness::Big => value.encode(&mut EndianEncoder::<_, BigEndian>::new(encoder)),
+    }
+}
+
+/// Encodes `bytes` as exactly `len` bytes, without a length in front of them, padded with the byte of `padding` if they are shorter.
+///
+/// This is used by `#[derive(Encode)]` for fields with `#[bincode(len = N)]`, see [`FixedLenPadding`].
+///
+/// # Errors
+///
+/// Returns `EncodeError::FixedLengthMismatch` if `bytes` is longer than `len`, or shorter with `FixedLenPadding::None`, or an error if the writer fails.
+pub fn encode_fixed_len<E: Encoder>(
+    encoder: &mut E,
+    bytes: &[u8],
+    len: usize,
+    padding: FixedLenPadding,
+) -> Result<(), EncodeError> {
+    let pad = match padding.byte() {
+        Some(pad) if bytes.len() <= len => pad,
+        _ if bytes.len() == len => 0,
+        _ => {
+            return Err(EncodeError::FixedLengthMismatch {
+                required: len,
+                found: bytes.len(),
+            })
+        }
+    };
+    encoder.writer().write(bytes)?;
+    let mut remaining = len - bytes.len();
+    let chunk = [pad; 16];
+    while remaining > 0 {
+        let n = remaining.min(chunk.len());
+        encoder.writer().write(&chunk[..n])?;
+        remaining -= n;
+    }
+    Ok(())
+}
+
+/// Encodes the option bitmap of a struct with `#[bincode(option_bitmap)]`, which has a bit for every option of `present`.
+///
+/// The bits are packed into `present.len().div_ceil(8)` bytes, starting at the lowest bit of the first byte. The derived `Encode` writes the bitmap before the fields, and then only writes the values of the options that are `Some`, so a struct with many options that are mostly `None` takes up a bit instead of a byte for each of them. Fields that are not written as `Option<...>`, or that have other `#[bincode(...)]` attributes than `borrow` or `owned`, are encoded as usual. Decode the bitmap with [`decode_option_bitmap`](crate::de::decode_option_bitmap).
+///
+/// As the struct is encoded differently, the attribute has to be added to an existing format on purpose, like a new version of it.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Default)]
+/// #[bincode(option_bitmap)]
+/// struct Settings {
+///     version: u8,
+///     width: Option<u32>,
+///     height: Option<u32>,
+///     title: Option<String>,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let settings = Settings { version: 1, height: Some(7), ..Default::default() };
+/// let encoded = bincode::encode_to_vec(&settings, config).unwrap();
+/// // the bitmap has the bit of `height` set, and the version is written after it
+/// assert_eq!(encoded, [0b010, 1, 7]);
+///
+/// let (decoded, _): (Settings, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(decoded, settings);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn encode_option_bitmap<E: Encoder>(
+    encoder: &mut E,
+    present: &[bool],
+) -> Result<(), EncodeError> {
+    for options in present.chunks(8) {
+        let byte = options
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (bit, &is_some)| {
+                byte | (u8::from(is_some) << bit)
+            });
+        encoder.writer().write(&[byte])?;
+    }
+    Ok(())
+}
+
 /// Encodes the length of any slice, container, etc into the given encoder
+///
+/// The length is a `u64` on the wire, written with the length encoding of the config. Every `usize` of the supported targets fits in a `u64`, so only [`with_fixed_u32_lengths`](crate::config::Configuration::with_fixed_u32_lengths) can fail with [`EncodeError::LengthTooLarge`], for lengths above `u32::MAX`. A length is never truncated.
 #[inline]
 pub(crate) fn encode_slice_len<E: Encoder>(encoder: &mut E, len: usize) -> Result<(), EncodeError> {
-    (len as u64).encode(encoder)
+    let wire_len =
+        u64::try_from(len).map_err(|_| EncodeError::LengthTooLarge { len, max: u64::MAX })?;
+    match E::C::LENGTH_ENCODING {
+        LengthEncoding::Int => wire_len.encode(encoder),
+        LengthEncoding::Varint => {
+            crate::varint::varint_encode_u64(encoder.writer(), E::C::ENDIAN, wire_len)
+        }
+        LengthEncoding::FixedU32 => {
+            let len = u32::try_from(len).map_err(|_| EncodeError::LengthTooLarge {
+                len,
+                max: u32::MAX.into(),
+            })?;
+            match E::C::ENDIAN {
+                Endianness::Little => encoder.writer().write(&len.to_le_bytes()),
+                Endianness::Big => encoder.writer().write(&len.to_be_bytes()),
+            }
+        }
+        LengthEncoding::FixedU64 => match E::C::ENDIAN {
+            Endianness::Little => encoder.writer().write(&wire_len.to_le_bytes()),
+            Endianness::Big => encoder.writer().write(&wire_len.to_be_bytes()),
+        },
+    }
+}
+
+/// Encodes a collection of `len` entries, like the implementations for `Vec` and `HashMap` do.
+///
+/// This writes the length followed by every entry of `entries`, which must yield exactly `len` entries. Decode the collection with [`CollectionDecoder`](crate::de::CollectionDecoder).
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::{encode_collection, Encode, Encoder};
+/// use bincode::error::EncodeError;
+/// use std::collections::BTreeMap;
+///
+/// /// A map that is encoded sorted by value
+/// struct ByValue(BTreeMap<&'static str, u32>);
+///
+/// impl Encode for ByValue {
+///     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+///         let mut entries: Vec<_> = self.0.iter().collect();
+///         entries.sort_by_key(|(_, value)| **value);
+///         encode_collection(encoder, entries.len(), entries)
+///     }
+/// }
+///
+/// let map = ByValue(BTreeMap::from([("b", 1), ("a", 2)]));
+/// let encoded = bincode::encode_to_vec(map, bincode::config::standard()).unwrap();
+/// assert_eq!(encoded, [2, 1, b'b', 1, 1, b'a', 2]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if an entry fails to encode, or `EncodeError::Other` if `entries` does not yield `len` entries.
+pub fn encode_collection<E, I>(encoder: &mut E, len: usize, entries: I) -> Result<(), EncodeError>
+where
+    E: Encoder,
+    I: IntoIterator,
+    I::Item: Encode,
+{
+    encode_slice_len(encoder, len)?;
+    let mut count = 0;
+    for entry in entries {
+        if count == len {
+            return Err(EncodeError::Other(
+                "collection has more entries than its length",
+            ));
+        }
+        entry.encode(encoder)?;
+        count += 1;
+    }
+    if count < len {
+        return Err(EncodeError::Other(
+            "collection has fewer entries than its length",
+        ));
+    }
+    Ok(())
+}
+
+/// Turns the `UnexpectedEnd` error of encoding into a slice of `capacity` bytes into `EncodeError::SliceTooSmall`.
+/// `required` encodes the value again into a [`SizeWriter`](write::SizeWriter) to find out how many bytes are needed.
+pub(crate) fn slice_too_small(
+    err: EncodeError,
+    capacity: usize,
+    required: impl FnOnce() -> Result<usize, EncodeError>,
+) -> EncodeError {
+    match err {
+        EncodeError::UnexpectedEnd => match required() {
+            Ok(required) if required > capacity => {
+                EncodeError::SliceTooSmall { capacity, required }
+            }
+            _ => err,
+        },
+        err => err,
+    }
 }
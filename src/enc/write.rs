@@ -1,8 +1,14 @@
 //! This module contains writer-based structs and traits.
 //!
 //! Because `std::io::Write` is only limited to `std` and not `core`, we provide our own [Writer].
+//!
+//! Like `std::io::Write`, [Writer] and [`SeekWriter`] are implemented for `&mut W`, also if `W` is a `dyn Writer`. Pass `&mut writer` to an encoder or an adapter like [`BufferedWriter`] to keep using the writer afterwards.
 
-use crate::error::EncodeError;
+use crate::{
+    config::{Config, Endianness, IntEncoding},
+    error::EncodeError,
+};
+use core::{mem::MaybeUninit, ops::ControlFlow};
 
 /// Trait that indicates that a struct can be used as a destination to encode data too. This is used by [Encode]
 ///
@@ -14,13 +20,155 @@ pub trait Writer {
     ///
     /// Returns `EncodeError::UnexpectedEnd` if the writer does not have enough space.
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
+
+    /// Write the contents of a byte slice, like a `Vec<u8>`, `&[u8]` or `String`. By default this is the same as [`write`](Writer::write).
+    ///
+    /// The length of the slice is written before this is called, so `bytes` is exactly the payload as it appears in the output. Writers can override this to handle large contiguous blobs differently, e.g. to store them out of line or to hash them, as long as the output stays the same as if `bytes` was passed to `write`.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::enc::write::Writer;
+    /// use bincode::error::EncodeError;
+    ///
+    /// /// Keeps the bytes of every slice separately
+    /// #[derive(Default)]
+    /// struct Chunks(Vec<Vec<u8>>);
+    ///
+    /// impl Writer for Chunks {
+    ///     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+    ///         match self.0.last_mut() {
+    ///             Some(last) => last.extend_from_slice(bytes),
+    ///             None => self.0.push(bytes.to_vec()),
+    ///         }
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+    ///         self.0.push(bytes.to_vec());
+    ///         self.0.push(Vec::new());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut chunks = Chunks::default();
+    /// bincode::encode_into_writer((1u8, "blob", 2u8), &mut chunks, bincode::config::standard()).unwrap();
+    /// assert_eq!(chunks.0, [vec![1, 4], b"blob".to_vec(), vec![2]]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `EncodeError::UnexpectedEnd` if the writer does not have enough space.
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.write(bytes)
+    }
+
+    /// Make sure that all bytes written so far have reached their destination, e.g. by flushing an internal buffer. By default this does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodeError` if the bytes could not be flushed.
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    /// Returns this writer as a [`SeekWriter`] if it can overwrite the bytes it wrote before. By default this returns `None`.
+    ///
+    /// [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix) uses this to write the length of a section in place after the section was written, instead of buffering the section to find out its length. Writers that implement [`SeekWriter`] should return `Some(self)`.
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        None
+    }
+
+    /// Marks that the following bytes belong to the field or value `name`, until the matching [`leave`](Writer::leave). Markers nest. By default this does nothing.
+    ///
+    /// Derived `Encode` implementations call this through [`Encoder::enter`](crate::enc::Encoder::enter) for every field. [`StatsWriter`](crate::stats::StatsWriter) uses the markers to attribute the written bytes to the fields.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        let _ = name;
+    }
+
+    /// Ends the innermost [`enter`](Writer::enter) marker. By default this does nothing.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {}
 }
 
-impl<T: Writer> Writer for &mut T {
+impl<T: Writer + ?Sized> Writer for &mut T {
     #[inline]
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
         (**self).write(bytes)
     }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        (**self).write_byte_slice(bytes)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        (**self).as_seek_writer()
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        (**self).enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        (**self).leave();
+    }
+}
+
+/// A [`Writer`] that can go back and overwrite bytes it wrote before, like a `std::io::Write + std::io::Seek`.
+///
+/// This lets [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix) reserve room for the length of a section, write the section, and then write its length in the reserved room, in a single pass. It is implemented for [`SliceWriter`], [`UninitSliceWriter`], [`SizeWriter`], [`BufferedWriter`] over a `SeekWriter`, [`VecWriter`](crate::VecWriter) and [`IoWriter`](crate::IoWriter) over a `std::io::Seek`.
+pub trait SeekWriter: Writer {
+    /// Returns the amount of bytes written so far, which is the offset of the next write.
+    fn position(&self) -> usize;
+
+    /// Overwrite the bytes at `offset` with `bytes`. The bytes must have been written before, so `offset + bytes.len()` must not be more than [`position`](Self::position). The position does not change.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EncodeError::Other` if the bytes were not written yet, or an error if the writer fails.
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError>;
+}
+
+impl<T: SeekWriter + ?Sized> SeekWriter for &mut T {
+    #[inline]
+    fn position(&self) -> usize {
+        (**self).position()
+    }
+
+    #[inline]
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        (**self).write_at(offset, bytes)
+    }
+}
+
+/// Returns the part of `written` that [`SeekWriter::write_at`] overwrites.
+pub(crate) fn written_range(
+    written: &mut [u8],
+    offset: usize,
+    len: usize,
+) -> Result<&mut [u8], EncodeError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| written.get_mut(offset..end))
+        .ok_or(EncodeError::Other(
+            "write_at can only overwrite bytes that were written before",
+        ))
 }
 
 /// A helper struct that implements `Writer` for a `&[u8]` slice.
@@ -33,42 +181,748 @@ impl<T: Writer> Writer for &mut T {
 /// writer.write(&[1, 2, 3, 4, 5]).unwrap();
 ///
 /// assert_eq!(writer.bytes_written(), 5);
+/// assert_eq!(writer.remaining(), 95);
+/// assert_eq!(writer.into_written(), &[1, 2, 3, 4, 5]);
 /// assert_eq!(destination[0..6], [1, 2, 3, 4, 5, 0]);
 /// ```
+///
+/// Manual writes can be mixed with encoded values by creating an [`EncoderImpl`](crate::enc::EncoderImpl) over this writer:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::{write::{SliceWriter, Writer}, Encode, Encoder, EncoderImpl};
+///
+/// let mut buffer = [0u8; 16];
+/// let mut encoder = EncoderImpl::new(SliceWriter::new(&mut buffer), bincode::config::standard());
+/// encoder.writer().write(b"HDR").unwrap();
+/// 5u32.encode(&mut encoder).unwrap();
+/// "hi".encode(&mut encoder).unwrap();
+///
+/// let written = encoder.into_writer().into_written();
+/// assert_eq!(written, &[b'H', b'D', b'R', 5, 2, b'h', b'i']);
+/// ```
 pub struct SliceWriter<'storage> {
     slice: &'storage mut [u8],
-    original_length: usize,
+    position: usize,
 }
 
 impl<'storage> SliceWriter<'storage> {
     /// Create a new instance of `SliceWriter` with the given byte array.
     pub const fn new(bytes: &'storage mut [u8]) -> Self {
-        let original = bytes.len();
         Self {
             slice: bytes,
-            original_length: original,
+            position: 0,
         }
     }
 
     /// Return the amount of bytes written so far.
     #[must_use]
     pub const fn bytes_written(&self) -> usize {
-        self.original_length - self.slice.len()
+        self.position
+    }
+
+    /// Return the position in the slice where the next write starts. This is the same as [`bytes_written`](Self::bytes_written).
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the amount of bytes that can still be written before the slice is full.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.slice.len() - self.position
+    }
+
+    /// Return the part of the slice that has been written to.
+    #[must_use]
+    pub fn into_written(self) -> &'storage mut [u8] {
+        let Self { slice, position } = self;
+        &mut slice[..position]
     }
 }
 
 impl Writer for SliceWriter<'_> {
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
-        if bytes.len() > self.slice.len() {
+        if bytes.len() > self.remaining() {
+            return Err(EncodeError::UnexpectedEnd);
+        }
+        let end = self.position + bytes.len();
+        self.slice[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+}
+
+impl SeekWriter for SliceWriter<'_> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        written_range(&mut self.slice[..self.position], offset, bytes.len())?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A [`Writer`] into a slice of uninitialized memory, like a buffer from FFI or a shared-memory ring buffer, so the slice does not have to be zeroed first.
+///
+/// It keeps track of the prefix of the slice that has been written to, and [`into_written`](Self::into_written) returns that prefix as an initialized `&mut [u8]`. Like [`SliceWriter`], a write that does not fit fails with `EncodeError::UnexpectedEnd`.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::write::{UninitSliceWriter, Writer};
+/// use core::mem::MaybeUninit;
+///
+/// let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+/// let mut writer = UninitSliceWriter::new(&mut buffer);
+/// writer.write(&[1, 2, 3]).unwrap();
+/// assert_eq!(writer.remaining(), 5);
+/// assert_eq!(writer.into_written(), [1, 2, 3]);
+/// ```
+pub struct UninitSliceWriter<'storage> {
+    slice: &'storage mut [MaybeUninit<u8>],
+    /// `slice[..position]` is initialized
+    position: usize,
+}
+
+impl<'storage> UninitSliceWriter<'storage> {
+    /// Create a new instance of `UninitSliceWriter` with the given memory.
+    pub const fn new(bytes: &'storage mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            slice: bytes,
+            position: 0,
+        }
+    }
+
+    /// Return the amount of bytes written so far.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.position
+    }
+
+    /// Return the amount of bytes that can still be written before the slice is full.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.slice.len() - self.position
+    }
+
+    /// Return the part of the slice that has been written to, which is initialized.
+    #[must_use]
+    pub fn into_written(self) -> &'storage mut [u8] {
+        let Self { slice, position } = self;
+        assume_init_mut(&mut slice[..position])
+    }
+
+    /// Return the part of the slice that has been written to.
+    fn written_mut(&mut self) -> &mut [u8] {
+        assume_init_mut(&mut self.slice[..self.position])
+    }
+}
+
+/// Returns `bytes` as an initialized slice. `bytes` must be a part of `UninitSliceWriter::slice` before `UninitSliceWriter::position`.
+#[allow(unsafe_code)]
+fn assume_init_mut(bytes: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    // Safety: `MaybeUninit<u8>` has the same layout as `u8`, and the callers only pass bytes that
+    // `UninitSliceWriter::write` has initialized. Only initialized bytes can be written through the
+    // returned slice, so the bytes stay initialized.
+    unsafe { &mut *(core::ptr::from_mut(bytes) as *mut [u8]) }
+}
+
+impl Writer for UninitSliceWriter<'_> {
+    #[inline(always)]
+    #[allow(unsafe_code)]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() > self.remaining() {
+            return Err(EncodeError::UnexpectedEnd);
+        }
+        let end = self.position + bytes.len();
+        let dst = &mut self.slice[self.position..end];
+        // Safety: `dst` is valid for writes of `bytes.len()` bytes, because it was just sliced to that
+        // length, and a `&[u8]` can not overlap the `&mut [MaybeUninit<u8>]`. `MaybeUninit<u8>` has
+        // the same layout as `u8`.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                dst.as_mut_ptr().cast::<u8>(),
+                bytes.len(),
+            );
+        }
+        // only move the position once the bytes before it are initialized
+        self.position = end;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+}
+
+impl SeekWriter for UninitSliceWriter<'_> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        written_range(self.written_mut(), offset, bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A writer into a buffer of `N` bytes that it owns, e.g. on the stack of a `no_std` target.
+///
+/// Unlike [`SliceWriter`], writes do not fail when the buffer is full. The writer stops storing bytes, but keeps counting them, so after encoding a value it knows exactly how many bytes the value needs. [`finish`](Self::finish) returns [`EncodeError::SliceTooSmall`] if the value did not fit, without encoding it a second time.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::{write::FixedBufferWriter, EncoderImpl, Encode};
+/// use bincode::error::EncodeError;
+///
+/// let mut encoder = EncoderImpl::new(FixedBufferWriter::<4>::new(), bincode::config::legacy());
+/// (1u32, 2u16).encode(&mut encoder).unwrap();
+/// let writer = encoder.into_writer();
+/// assert_eq!(writer.written(), [1, 0, 0, 0]);
+/// assert_eq!(writer.overflowed_by(), 2);
+/// assert!(matches!(
+///     writer.finish(),
+///     Err(EncodeError::SliceTooSmall { capacity: 4, required: 6 })
+/// ));
+/// ```
+pub struct FixedBufferWriter<const N: usize> {
+    buffer: [u8; N],
+    stored: usize,
+    required: usize,
+}
+
+impl<const N: usize> FixedBufferWriter<N> {
+    /// Create a new, empty `FixedBufferWriter`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            stored: 0,
+            required: 0,
+        }
+    }
+
+    /// Return the bytes that were stored in the buffer. If the buffer overflowed, these are the bytes before the first write that did not fit.
+    #[must_use]
+    pub fn written(&self) -> &[u8] {
+        &self.buffer[..self.stored]
+    }
+
+    /// Return the amount of bytes that were written, including the bytes that did not fit in the buffer.
+    #[must_use]
+    pub const fn bytes_required(&self) -> usize {
+        self.required
+    }
+
+    /// Return how many more bytes the buffer would need to hold everything that was written, or 0 if it did not overflow.
+    #[must_use]
+    pub const fn overflowed_by(&self) -> usize {
+        self.required.saturating_sub(N)
+    }
+
+    /// Return the written bytes if all of them fit in the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodeError::SliceTooSmall`] with the capacity `N` and the amount of bytes that were written if they did not fit.
+    pub fn finish(&self) -> Result<&[u8], EncodeError> {
+        if self.stored < self.required {
+            return Err(EncodeError::SliceTooSmall {
+                capacity: N,
+                required: self.required,
+            });
+        }
+        Ok(self.written())
+    }
+}
+
+impl<const N: usize> Default for FixedBufferWriter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Writer for FixedBufferWriter<N> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        // once a write did not fit, nothing is stored anymore, so the stored bytes stay a prefix of the output
+        if self.stored == self.required && bytes.len() <= N - self.stored {
+            self.buffer[self.stored..self.stored + bytes.len()].copy_from_slice(bytes);
+            self.stored += bytes.len();
+        }
+        self.required = self.required.saturating_add(bytes.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+}
+
+/// Only the part of the overwritten bytes that is stored in the buffer is changed.
+impl<const N: usize> SeekWriter for FixedBufferWriter<N> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.required
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        match offset.checked_add(bytes.len()) {
+            Some(end) if end <= self.required => {
+                let stored_end = end.min(self.stored);
+                if offset < stored_end {
+                    self.buffer[offset..stored_end].copy_from_slice(&bytes[..stored_end - offset]);
+                }
+                Ok(())
+            }
+            _ => Err(EncodeError::Other(
+                "write_at can only overwrite bytes that were written before",
+            )),
+        }
+    }
+}
+
+/// The writer of a value with a fixed size, see [`Encoder::with_padding`](crate::enc::Encoder::with_padding).
+///
+/// It counts the bytes that are written to the wrapped writer, and [`finish`](Self::finish) writes zero bytes up to the fixed size.
+pub struct PaddedWriter<'a> {
+    writer: &'a mut dyn Writer,
+    pad_to: usize,
+    bytes_written: usize,
+}
+
+impl<'a> PaddedWriter<'a> {
+    pub(crate) fn new(writer: &'a mut dyn Writer, pad_to: usize) -> Self {
+        Self {
+            writer,
+            pad_to,
+            bytes_written: 0,
+        }
+    }
+
+    /// Return the amount of bytes written so far, without the padding.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Write zero bytes up to the fixed size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EncodeError::PaddingExceeded` if more bytes than the fixed size were written, or an error if the writer fails.
+    pub fn finish(self) -> Result<(), EncodeError> {
+        const ZEROS: [u8; 64] = [0; 64];
+        let Some(mut padding) = self.pad_to.checked_sub(self.bytes_written) else {
+            return Err(EncodeError::PaddingExceeded {
+                pad_to: self.pad_to,
+                size: self.bytes_written,
+            });
+        };
+        while padding > 0 {
+            let chunk = padding.min(ZEROS.len());
+            self.writer.write(&ZEROS[..chunk])?;
+            padding -= chunk;
+        }
+        Ok(())
+    }
+}
+
+impl Writer for PaddedWriter<'_> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write(bytes)?;
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_byte_slice(bytes)?;
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        self.writer.enter(name);
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        self.writer.leave();
+    }
+}
+
+/// A writer over a `[u8; N]` that can be used in a `const fn`, to encode values into a byte array at compile time.
+///
+/// Encoding through [`Encode`](crate::Encode) can not happen in a `const` context, so this writer has a `const fn` for every primitive, which writes it the same way its `Encode` implementation does with a fixint config, see [`Fixint`](crate::config::Fixint). Varint configs are not supported. Structs that only consist of these primitives and arrays of them can derive `ConstEncode` (with the `derive` feature), which generates a `const fn encoded` for them.
+///
+/// The writer panics if more than `N` bytes are written, or if [`finish`](Self::finish) is called before all `N` bytes are written, which is a compile error in a `const` context. It also implements [`Writer`], which returns `EncodeError::UnexpectedEnd` instead when it is full.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::write::ConstWriter;
+///
+/// const HEADER: [u8; 7] = {
+///     let mut writer = ConstWriter::new(bincode::config::legacy().with_big_endian());
+///     writer.write_bytes(b"BNC");
+///     writer.write_u32(2);
+///     writer.finish()
+/// };
+///
+/// let config = bincode::config::legacy().with_big_endian();
+/// let mut expected = b"BNC".to_vec();
+/// expected.extend(bincode::encode_to_vec(2u32, config).unwrap());
+/// assert_eq!(HEADER[..], expected);
+/// ```
+pub struct ConstWriter<const N: usize> {
+    bytes: [u8; N],
+    position: usize,
+    endian: Endianness,
+}
+
+macro_rules! const_write_ints {
+    ($($name:ident: $ty:ty,)*) => {$(
+        #[doc = concat!("Write a `", stringify!($ty), "` in the endianness of the config.")]
+        ///
+        /// # Panics
+        ///
+        /// Panics if the writer does not have enough room left.
+        pub const fn $name(&mut self, value: $ty) {
+            match self.endian {
+                Endianness::Little => self.write_bytes(&value.to_le_bytes()),
+                Endianness::Big => self.write_bytes(&value.to_be_bytes()),
+            }
+        }
+    )*};
+}
+
+impl<const N: usize> ConstWriter<N> {
+    /// Create a new `ConstWriter` that writes in the endianness of `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config` uses varint encoding.
+    #[must_use]
+    pub const fn new<C: Config>(_config: C) -> Self {
+        assert!(
+            matches!(C::INT_ENCODING, IntEncoding::Fixed),
+            "ConstWriter only supports fixint configs"
+        );
+        Self {
+            bytes: [0; N],
+            position: 0,
+            endian: C::ENDIAN,
+        }
+    }
+
+    /// Return the amount of bytes written so far.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.position
+    }
+
+    /// Write `bytes` as they are, without a length in front of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer does not have enough room left.
+    pub const fn write_bytes(&mut self, bytes: &[u8]) {
+        assert!(
+            bytes.len() <= N - self.position,
+            "ConstWriter does not have enough room left"
+        );
+        let mut idx = 0;
+        while idx < bytes.len() {
+            self.bytes[self.position + idx] = bytes[idx];
+            idx += 1;
+        }
+        self.position += bytes.len();
+    }
+
+    /// Write a `u8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer is full.
+    pub const fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    /// Write an `i8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer is full.
+    pub const fn write_i8(&mut self, value: i8) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// Write a `bool` as a single byte, `0` or `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer is full.
+    pub const fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    const_write_ints! {
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_u128: u128,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_i128: i128,
+    }
+
+    /// Write a `usize` as a `u64`, like its `Encode` implementation does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer does not have enough room left.
+    pub const fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    /// Write an `isize` as an `i64`, like its `Encode` implementation does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer does not have enough room left.
+    pub const fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+
+    /// Write the bits of an `f32` in the endianness of the config.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer does not have enough room left.
+    pub const fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    /// Write the bits of an `f64` in the endianness of the config.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer does not have enough room left.
+    pub const fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Return the written bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `N` bytes were written.
+    #[must_use]
+    pub const fn finish(self) -> [u8; N] {
+        assert!(self.position == N, "ConstWriter was not filled");
+        self.bytes
+    }
+}
+
+impl<const N: usize> Writer for ConstWriter<N> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() > N - self.position {
             return Err(EncodeError::UnexpectedEnd);
         }
-        let (a, b) = core::mem::take(&mut self.slice).split_at_mut(bytes.len());
-        a.copy_from_slice(bytes);
-        self.slice = b;
+        self.write_bytes(bytes);
+        Ok(())
+    }
+}
+
+/// A writer that collects small writes in an inline buffer of `N` bytes before passing them on to the wrapped writer.
+///
+/// Encoding a struct writes every field separately, which is slow for writers where every write has a fixed cost, like an [`IoWriter`](crate::IoWriter) over a `std::net::TcpStream`. This writer passes the collected bytes on when the buffer is full, so most writes are only a copy into the buffer. Writes that are at least `N` bytes long are passed on directly, after the buffered bytes. The bytes that reach the wrapped writer are identical to the ones written without this writer.
+///
+/// Call [`finish`](Self::finish) after encoding to pass on the remaining buffered bytes and to find out if that failed. Dropping the writer also tries to pass them on, but ignores any errors.
+///
+/// The buffered bytes are always passed on in order, so errors of the wrapped writer, like the `index` of [`EncodeError::Io`], refer to the position in the output as if there was no buffer.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::write::{BufferedWriter, SliceWriter};
+///
+/// let config = bincode::config::standard();
+/// let value = (1u8, 2u32, "three", [4u16; 8]);
+///
+/// let mut output = [0u8; 64];
+/// let mut writer = BufferedWriter::new(SliceWriter::new(&mut output));
+/// bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+/// let len = writer.bytes_written();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(&output[..len], bincode::encode_to_vec(&value, config).unwrap());
+///
+/// // a larger buffer
+/// let mut output = [0u8; 64];
+/// let writer = BufferedWriter::<_, 1024>::with_buffer_size(SliceWriter::new(&mut output));
+/// # drop(writer);
+/// ```
+pub struct BufferedWriter<W: Writer, const N: usize = 256> {
+    writer: W,
+    buf: [u8; N],
+    len: usize,
+    bytes_written: usize,
+}
+
+impl<W: Writer> BufferedWriter<W> {
+    /// Create a new `BufferedWriter` with a buffer of 256 bytes.
+    pub const fn new(writer: W) -> Self {
+        Self::with_buffer_size(writer)
+    }
+}
+
+impl<W: Writer, const N: usize> BufferedWriter<W, N> {
+    /// Create a new `BufferedWriter` with a buffer of `N` bytes.
+    pub const fn with_buffer_size(writer: W) -> Self {
+        Self {
+            writer,
+            buf: [0; N],
+            len: 0,
+            bytes_written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written to this writer, including the bytes that are still buffered.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Returns the bytes that have been written to this writer, but not passed on to the wrapped writer yet.
+    #[must_use]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Pass the buffered bytes on to the wrapped writer, and return the wrapped writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the wrapped writer if it fails to write the buffered bytes.
+    #[allow(unsafe_code)]
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        self.write_buffered()?;
+        let this = core::mem::ManuallyDrop::new(self);
+        // Safety: `this` is never used or dropped again, so `writer` is moved out exactly once.
+        // The other fields do not need to be dropped.
+        Ok(unsafe { core::ptr::read(&raw const this.writer) })
+    }
+
+    /// Pass the buffered bytes on to the wrapped writer, and clear the buffer.
+    ///
+    /// The buffer is cleared even if this fails, so the bytes are never passed on twice.
+    fn write_buffered(&mut self) -> Result<(), EncodeError> {
+        let len = core::mem::take(&mut self.len);
+        if len > 0 {
+            self.writer.write(&self.buf[..len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Writer, const N: usize> Writer for BufferedWriter<W, N> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() <= N - self.len {
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        } else {
+            self.write_buffered()?;
+            if bytes.len() < N {
+                self.buf[..bytes.len()].copy_from_slice(bytes);
+                self.len = bytes.len();
+            } else {
+                self.writer.write(bytes)?;
+            }
+        }
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
 
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() < N {
+            return self.write(bytes);
+        }
+        // large enough to pass on directly, so the wrapped writer can handle it as a slice
+        self.write_buffered()?;
+        self.writer.write_byte_slice(bytes)?;
+        self.bytes_written += bytes.len();
         Ok(())
     }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.write_buffered()?;
+        self.writer.flush()
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        self.writer.as_seek_writer()?;
+        Some(self)
+    }
+}
+
+/// Bytes that are still buffered are overwritten in the buffer, the others by the wrapped writer. [`Writer::as_seek_writer`] only returns this writer if the wrapped writer is a `SeekWriter` as well.
+impl<W: Writer, const N: usize> SeekWriter for BufferedWriter<W, N> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.bytes_written
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        let buffered = self.bytes_written - self.len;
+        if offset >= buffered {
+            written_range(&mut self.buf[..self.len], offset - buffered, bytes.len())?
+                .copy_from_slice(bytes);
+            return Ok(());
+        }
+        self.write_buffered()?;
+        self.writer.as_seek_writer().map_or(
+            Err(EncodeError::Other(
+                "write_at needs a BufferedWriter over a SeekWriter",
+            )),
+            |writer| writer.write_at(offset, bytes),
+        )
+    }
+}
+
+impl<W: Writer, const N: usize> Drop for BufferedWriter<W, N> {
+    fn drop(&mut self) {
+        let _ = self.write_buffered();
+    }
 }
 
 /// A writer that counts how many bytes were written. This is useful for e.g. pre-allocating buffers before writing to them.
@@ -84,4 +938,259 @@ impl Writer for SizeWriter {
 
         Ok(())
     }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+}
+
+/// Overwriting bytes does not change the size, so `write_at` only checks that the bytes were written before.
+impl SeekWriter for SizeWriter {
+    #[inline]
+    fn position(&self) -> usize {
+        self.bytes_written
+    }
+
+    #[inline]
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        match offset.checked_add(bytes.len()) {
+            Some(end) if end <= self.bytes_written => Ok(()),
+            _ => Err(EncodeError::Other(
+                "write_at can only overwrite bytes that were written before",
+            )),
+        }
+    }
+}
+
+/// A writer that reports how many bytes were written to the wrapped writer, like [`ProgressReader`](crate::de::read::ProgressReader) does for reads.
+///
+/// The callback is called with the total amount of bytes written so far, once at least `every` bytes were written since the last call. If the callback returns [`ControlFlow::Break`], the write that called it and all writes after it fail with [`EncodeError::Cancelled`]. The bytes of the write that called it were already passed to the wrapped writer.
+///
+/// See [`ProgressWriter::from_std_write`] for a `std::io::Write`.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::enc::write::{ProgressWriter, SliceWriter};
+/// use core::ops::ControlFlow;
+///
+/// let mut output = [0u8; 64];
+/// let mut reports = Vec::new();
+/// let mut writer = ProgressWriter::new(SliceWriter::new(&mut output), 16, |written| {
+///     reports.push(written);
+///     ControlFlow::Continue(())
+/// });
+/// bincode::encode_into_writer([(1u16, 2u16); 10], &mut writer, bincode::config::legacy()).unwrap();
+/// assert_eq!(writer.bytes_written(), 40);
+/// assert_eq!(reports, [16, 32]);
+/// ```
+pub struct ProgressWriter<W, F> {
+    writer: W,
+    callback: F,
+    every: u64,
+    written: u64,
+    next_report: u64,
+    cancelled: bool,
+}
+
+impl<W: Writer, F: FnMut(u64) -> ControlFlow<()>> ProgressWriter<W, F> {
+    /// Create a new `ProgressWriter` that calls `callback` each time at least `every` more bytes were written to `writer`. An `every` of 0 calls it after every write.
+    pub const fn new(writer: W, every: u64, callback: F) -> Self {
+        Self {
+            writer,
+            callback,
+            every,
+            written: 0,
+            next_report: every,
+            cancelled: false,
+        }
+    }
+
+    /// The amount of bytes written so far, also if the callback was not called for them yet.
+    #[must_use]
+    pub const fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns `true` if the callback returned [`ControlFlow::Break`].
+    #[must_use]
+    pub const fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn advance(&mut self, n: usize) -> Result<(), EncodeError> {
+        self.written += n as u64;
+        if self.written >= self.next_report {
+            self.next_report = self.written.saturating_add(self.every);
+            self.cancelled = (self.callback)(self.written).is_break();
+        }
+        self.check()
+    }
+
+    const fn check(&self) -> Result<(), EncodeError> {
+        if self.cancelled {
+            Err(EncodeError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<W: Writer, F: FnMut(u64) -> ControlFlow<()>> Writer for ProgressWriter<W, F> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.check()?;
+        self.writer.write(bytes)?;
+        self.advance(bytes.len())
+    }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.check()?;
+        self.writer.write_byte_slice(bytes)?;
+        self.advance(bytes.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+}
+
+/// The writer of the encoder that [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix) passes to its closure.
+///
+/// If the writer of the outer encoder is a [`SeekWriter`], the section is written to it directly, and its length is written in place afterwards. Otherwise the section is collected in a `Vec<u8>`, which needs the `alloc` feature, and copied to the outer writer once its length is known. This costs an extra copy of every byte of the section, and keeps the section in memory until it is done.
+pub struct SectionWriter<'a> {
+    section: Section<'a>,
+    /// The position of the reserved length
+    start: usize,
+    endian: Endianness,
+}
+
+enum Section<'a> {
+    Seek(&'a mut dyn SeekWriter),
+    #[cfg(feature = "alloc")]
+    Buffer(alloc::vec::Vec<u8>),
+}
+
+impl<'a> SectionWriter<'a> {
+    /// Reserves room for the length of a section in `writer`.
+    pub(crate) fn start<W: Writer>(
+        writer: &'a mut W,
+        endian: Endianness,
+    ) -> Result<Self, EncodeError> {
+        #[cfg(feature = "alloc")]
+        let section = writer
+            .as_seek_writer()
+            .map_or_else(|| Section::Buffer(alloc::vec::Vec::new()), Section::Seek);
+        #[cfg(not(feature = "alloc"))]
+        let section = Section::Seek(writer.as_seek_writer().ok_or(EncodeError::Other(
+            "length prefixes need a SeekWriter when the alloc feature is disabled",
+        ))?);
+        let mut writer = Self {
+            start: 0,
+            section,
+            endian,
+        };
+        writer.start = writer.position();
+        writer.write(&[0; 8])?;
+        Ok(writer)
+    }
+
+    /// Writes the length of the section in the reserved room. Returns the bytes of a buffered section, which still have to be written to the outer writer.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn finish(mut self) -> Result<Option<alloc::vec::Vec<u8>>, EncodeError> {
+        self.write_len()?;
+        match self.section {
+            Section::Seek(_) => Ok(None),
+            Section::Buffer(buffer) => Ok(Some(buffer)),
+        }
+    }
+
+    /// Writes the length of the section in the reserved room.
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn finish(mut self) -> Result<(), EncodeError> {
+        self.write_len()
+    }
+
+    fn write_len(&mut self) -> Result<(), EncodeError> {
+        let len = (self.position() - self.start - 8) as u64;
+        let bytes = match self.endian {
+            Endianness::Little => len.to_le_bytes(),
+            Endianness::Big => len.to_be_bytes(),
+        };
+        self.write_at(self.start, &bytes)
+    }
+}
+
+impl Writer for SectionWriter<'_> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        match &mut self.section {
+            Section::Seek(writer) => writer.write(bytes),
+            #[cfg(feature = "alloc")]
+            Section::Buffer(buffer) => {
+                buffer.extend_from_slice(bytes);
+                Ok(())
+            }
+        }
+    }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        match &mut self.section {
+            Section::Seek(writer) => writer.write_byte_slice(bytes),
+            #[cfg(feature = "alloc")]
+            Section::Buffer(_) => self.write(bytes),
+        }
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+
+    /// A buffered section is passed on as a whole, so the markers are only passed on when the section is written directly.
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn enter(&mut self, name: &'static str) {
+        if let Section::Seek(writer) = &mut self.section {
+            writer.enter(name);
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    fn leave(&mut self) {
+        if let Section::Seek(writer) = &mut self.section {
+            writer.leave();
+        }
+    }
+}
+
+impl SeekWriter for SectionWriter<'_> {
+    #[inline]
+    fn position(&self) -> usize {
+        match &self.section {
+            Section::Seek(writer) => writer.position(),
+            #[cfg(feature = "alloc")]
+            Section::Buffer(buffer) => buffer.len(),
+        }
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        match &mut self.section {
+            Section::Seek(writer) => writer.write_at(offset, bytes),
+            #[cfg(feature = "alloc")]
+            Section::Buffer(buffer) => {
+                written_range(buffer, offset, bytes.len())?.copy_from_slice(bytes);
+                Ok(())
+            }
+        }
+    }
 }
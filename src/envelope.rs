@@ -0,0 +1,129 @@
+//! A header of magic bytes and a format version in front of a file, so files of another format are rejected and old versions stay readable.
+//!
+//! [`write_header`] writes the 4 bytes of the magic of a format, followed by its version as a `u16` in little endian. [`read_header`] checks the magic and returns the version. The header does not depend on the config, so it can be checked before anything else is known about the file.
+//!
+//! [`encode_file`] writes the header followed by a value, and [`decode_file`] reads the header and passes its version to a closure that decodes the value of that version. Every version that was ever written stays readable, e.g. by decoding the old type and converting it into the current one:
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::de::read::SliceReader;
+//! use bincode::envelope::{decode_file, encode_file};
+//! use bincode::error::DecodeError;
+//! use bincode::Decode;
+//!
+//! const MAGIC: [u8; 4] = *b"SAVE";
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! struct SaveV1 {
+//!     level: u32,
+//! }
+//!
+//! #[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+//! struct Save {
+//!     level: u32,
+//!     lives: u8,
+//! }
+//!
+//! impl From<SaveV1> for Save {
+//!     fn from(save: SaveV1) -> Self {
+//!         Save { level: save.level, lives: 3 }
+//!     }
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let mut old = Vec::new();
+//! encode_file(&SaveV1 { level: 4 }, bincode::IoWriter::new(&mut old), MAGIC, 1, config).unwrap();
+//!
+//! let save: Save = decode_file(SliceReader::new(&old), MAGIC, config, |version, decoder| {
+//!     match version {
+//!         1 => SaveV1::decode(decoder).map(Into::into),
+//!         2 => Save::decode(decoder),
+//!         _ => Err(DecodeError::UnsupportedVersion { version }),
+//!     }
+//! })
+//! .unwrap();
+//! assert_eq!(save, Save { level: 4, lives: 3 });
+//! ```
+//!
+//! A file with other magic bytes fails with [`DecodeError::WrongMagic`], and a version that the closure does not know with [`DecodeError::UnsupportedVersion`], so "not our file" and "our file, but written by a newer version" can be told apart.
+
+use crate::{
+    config::Config,
+    de::{read::Reader, DecoderImpl},
+    enc::{write::Writer, Encode, EncoderImpl},
+    error::{DecodeError, EncodeError},
+};
+
+/// The size of the header in bytes: the magic and the version.
+pub const HEADER_SIZE: usize = 6;
+
+/// Writes the header of a file with `magic` and `version` to `writer`.
+///
+/// # Errors
+///
+/// Returns an error if the writer fails.
+pub fn write_header<W: Writer>(
+    mut writer: W,
+    magic: [u8; 4],
+    version: u16,
+) -> Result<(), EncodeError> {
+    writer.write(&magic)?;
+    writer.write(&version.to_le_bytes())
+}
+
+/// Reads the header of a file from `reader`, and returns its version if its magic is `expected_magic`.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::WrongMagic`] if the magic of the header is not `expected_magic`, or an error if the reader fails.
+pub fn read_header<R: Reader>(mut reader: R, expected_magic: [u8; 4]) -> Result<u16, DecodeError> {
+    let mut header = [0u8; HEADER_SIZE];
+    reader.read(&mut header[..4])?;
+    let found = [header[0], header[1], header[2], header[3]];
+    if found != expected_magic {
+        return Err(DecodeError::WrongMagic {
+            expected: expected_magic,
+            found,
+        });
+    }
+    reader.read(&mut header[4..])?;
+    Ok(u16::from_le_bytes([header[4], header[5]]))
+}
+
+/// Writes the header of a file with `magic` and `version` to `writer`, followed by `value`.
+///
+/// # Errors
+///
+/// Returns an error if `value` fails to encode or the writer fails.
+pub fn encode_file<T: Encode + ?Sized, W: Writer, C: Config>(
+    value: &T,
+    mut writer: W,
+    magic: [u8; 4],
+    version: u16,
+    config: C,
+) -> Result<(), EncodeError> {
+    write_header(&mut writer, magic, version)?;
+    value.encode(&mut EncoderImpl::new(writer, config))
+}
+
+/// Reads the header of a file from `reader`, and decodes the value after it with `decode`, which gets the version of the header.
+///
+/// `decode` decodes the type of the value that the version wrote, and returns [`DecodeError::UnsupportedVersion`] for a version that it does not know. See the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns [`DecodeError::WrongMagic`] if the magic of the header is not `expected_magic`, the error of `decode`, or an error if the reader fails.
+pub fn decode_file<T, R, C, F>(
+    mut reader: R,
+    expected_magic: [u8; 4],
+    config: C,
+    decode: F,
+) -> Result<T, DecodeError>
+where
+    R: Reader,
+    C: Config,
+    F: FnOnce(u16, &mut DecoderImpl<R, C, ()>) -> Result<T, DecodeError>,
+{
+    let version = read_header(&mut reader, expected_magic)?;
+    decode(version, &mut DecoderImpl::new(reader, config, ()))
+}
@@ -7,6 +7,40 @@ pub enum EncodeError {
     /// The writer ran out of storage.
     UnexpectedEnd,
 
+    /// The slice given to [`encode_into_slice`](crate::encode_into_slice), or the buffer of a [`FixedBufferWriter`](crate::enc::write::FixedBufferWriter), is too small to hold the encoded value.
+    ///
+    /// The slice is left with a partially encoded prefix of the value, which should not be used. Retry with a slice of at least `required` bytes, which is `required - capacity` more than this one.
+    SliceTooSmall {
+        /// The length of the slice
+        capacity: usize,
+        /// The amount of bytes needed to encode the whole value
+        required: usize,
+    },
+
+    /// A field with `#[bincode(pad_to = N)]` or a struct with `#[bincode(pad_struct_to = N)]` was encoded into more than its padded size, see [`Encoder::with_padding`](crate::enc::Encoder::with_padding).
+    PaddingExceeded {
+        /// The size that the value is padded to
+        pad_to: usize,
+        /// The amount of bytes that the value was encoded into
+        size: usize,
+    },
+
+    /// A field with `#[bincode(len = N)]` is longer than `N` bytes, or shorter with `pad = "none"`, see [`encode_fixed_len`](crate::enc::encode_fixed_len).
+    FixedLengthMismatch {
+        /// The amount of bytes that the field is encoded as
+        required: usize,
+        /// The length of the field
+        found: usize,
+    },
+
+    /// A string, sequence or map has more entries than the length encoding of the config can hold, e.g. more than `u32::MAX` with [`with_fixed_u32_lengths`](crate::config::Configuration::with_fixed_u32_lengths).
+    LengthTooLarge {
+        /// The length of the value
+        len: usize,
+        /// The largest length that can be encoded
+        max: u64,
+    },
+
     /// The `RefCell<T>` is already borrowed
     RefCellAlreadyBorrowed {
         /// The inner borrow error
@@ -22,6 +56,10 @@ pub enum EncodeError {
     #[cfg(feature = "alloc")]
     OtherString(alloc::string::String),
 
+    /// An error of a custom `Encode` implementation, see [`EncodeError::custom`\]. [`source`](core::error::Error::source) returns the inner error, and it can be downcast to get it back.
+    #[cfg(feature = "alloc")]
+    Custom(alloc::boxed::Box<dyn core::error::Error + Send + Sync>),
+
     /// A `std::path::Path` was being encoded but did not contain a valid `&str` representation
     #[cfg(feature = "std")]
     InvalidPathCharacters,
@@ -51,6 +89,17 @@ pub enum EncodeError {
         time: std::boxed::Box<std::time::SystemTime>,
     },
 
+    /// A [`SharedArc`](crate::shared::SharedArc) or [`SharedRc`](crate::shared::SharedRc) contains itself, e.g. through a `Mutex`. Shared values can not be encoded in a cycle.
+    #[cfg(feature = "alloc")]
+    SharedCycle,
+
+    /// A [`PreEncoded`](crate::pre_encoded::PreEncoded) value was encoded with a config that encodes values differently than the config it was created with, so its bytes can not be used.
+    #[cfg(feature = "alloc")]
+    PreEncodedConfigMismatch,
+
+    /// The callback of a [`ProgressWriter`](crate::enc::write::ProgressWriter) returned `ControlFlow::Break`.
+    Cancelled,
+
     #[cfg(feature = "serde")]
     /// A serde-specific error that occurred while decoding.
     Serde(crate::features::serde::EncodeError),
@@ -58,8 +107,49 @@ pub enum EncodeError {
 
 impl core::fmt::Display for EncodeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        // TODO: Improve this?
-        write!(f, "{self:?}")
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Custom(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "serde")]
+            Self::Serde(inner) => write!(f, "{inner}"),
+            // TODO: Improve this?
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+impl EncodeError {
+    /// Creates an [`EncodeError::Custom`\] with the given error, for custom `Encode` implementations that fail for a reason of their own.
+    ///
+    /// This accepts any error type, as well as a `String` or `&str` message. Call `downcast_ref` on the [`source`](core::error::Error::source) of the returned error to get the original error back.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn custom(err: impl Into<alloc::boxed::Box<dyn core::error::Error + Send + Sync>>) -> Self {
+        Self::Custom(err.into())
+    }
+
+    /// Takes the `EncodeError` back out of an `std::io::Error` that was converted from it. Any other `std::io::Error`, including the inner error of an [`EncodeError::Io`\], becomes an [`EncodeError::Io`\] at index 0.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_io_error(error: std::io::Error) -> Self {
+        error
+            .downcast()
+            .unwrap_or_else(|inner| Self::Io { inner, index: 0 })
+    }
+
+    /// Creates an error with a formatted message, e.g. `EncodeError::custom_fmt(format_args!("invalid id {id}"))`.
+    ///
+    /// This is an [`EncodeError::Custom`\] with the message. Without the `alloc` feature, this is an [`EncodeError::Other`\] if the message has no arguments, and a generic message otherwise.
+    #[must_use]
+    pub fn custom_fmt(args: core::fmt::Arguments<'_>) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::custom(alloc::fmt::format(args))
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            Self::Other(args.as_str().unwrap_or("custom encode error"))
+        }
     }
 }
 
@@ -67,6 +157,8 @@ impl core::error::Error for EncodeError {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
             Self::RefCellAlreadyBorrowed { inner, .. } => Some(inner),
+            #[cfg(feature = "alloc")]
+            Self::Custom(inner) => Some(&**inner),
             #[cfg(feature = "std")]
             Self::Io { inner, .. } => Some(inner),
             #[cfg(feature = "std")]
@@ -78,29 +170,139 @@ impl core::error::Error for EncodeError {
 impl core::error::Error for DecodeError {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
-            Self::Utf8 { inner } => Some(inner),
+            Self::Utf8 { inner, .. } => Some(inner),
+            #[cfg(feature = "alloc")]
+            Self::Custom(inner) => Some(&**inner),
+            #[cfg(feature = "alloc")]
+            Self::WithOffset { inner, .. } | Self::Index { inner, .. } => inner.source(),
+            #[cfg(feature = "alloc")]
+            Self::Field(field) => field.inner.source(),
             _ => None,
         }
     }
 }
 
+/// Converts the error into an `std::io::Error`, for functions that return `std::io::Result`.
+///
+/// An [`EncodeError::Io`\] becomes the `std::io::Error` it contains. Every other error is wrapped with a matching `ErrorKind`, like `WriteZero` for [`EncodeError::UnexpectedEnd`\], and can be taken back out with [`EncodeError::from_io_error`\].
+#[cfg(feature = "std")]
+impl From<EncodeError> for std::io::Error {
+    fn from(error: EncodeError) -> Self {
+        use std::io::ErrorKind;
+        let kind = match error {
+            EncodeError::Io { inner, .. } => return inner,
+            EncodeError::UnexpectedEnd | EncodeError::SliceTooSmall { .. } => ErrorKind::WriteZero,
+            EncodeError::RefCellAlreadyBorrowed { .. }
+            | EncodeError::LockFailed { .. }
+            | EncodeError::Cancelled => ErrorKind::Other,
+            _ => ErrorKind::InvalidInput,
+        };
+        Self::new(kind, error)
+    }
+}
+
+/// Converts the error into an `std::io::Error`, for functions that return `std::io::Result`.
+///
+/// A [`DecodeError::Io`\] becomes the `std::io::Error` it contains, without the context of [`DecodeError::inner`\]. Every other error is wrapped with a matching `ErrorKind`, like `UnexpectedEof` for [`DecodeError::UnexpectedEnd`\] and `InvalidData` for invalid input, and can be taken back out with [`DecodeError::from_io_error`\].
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+/// use std::io::ErrorKind;
+///
+/// fn read_id(bytes: &[u8]) -> std::io::Result<u32> {
+///     let (id, _) = bincode::decode_from_slice(bytes, bincode::config::standard())?;
+///     Ok(id)
+/// }
+///
+/// let err = read_id(&[]).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+/// let err = DecodeError::from_io_error(err);
+/// assert!(matches!(err.inner(), DecodeError::UnexpectedEnd { .. }));
+/// ```
+#[cfg(feature = "std")]
+impl From<DecodeError> for std::io::Error {
+    fn from(error: DecodeError) -> Self {
+        use std::io::ErrorKind;
+        let error = match error.into_io_error() {
+            Ok(inner) => return inner,
+            Err(error) => error,
+        };
+        let kind = match error.inner() {
+            DecodeError::UnexpectedEnd { .. } | DecodeError::CleanEof => ErrorKind::UnexpectedEof,
+            DecodeError::AllocationFailed { .. } => ErrorKind::OutOfMemory,
+            DecodeError::Cancelled => ErrorKind::Other,
+            DecodeError::TimedOut { .. } => ErrorKind::TimedOut,
+            DecodeError::RemainingBytesUnknown
+            | DecodeError::MutableBorrowUnsupported
+            | DecodeError::BorrowSpansSegments { .. }
+            | DecodeError::BorrowedDataTooLarge { .. } => ErrorKind::Unsupported,
+            _ => ErrorKind::InvalidData,
+        };
+        Self::new(kind, error)
+    }
+}
+
 /// Errors that can be encountered by decoding a type
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum DecodeError {
     /// The reader reached its end but more bytes were expected.
     UnexpectedEnd {
-        /// Gives an estimate of how many extra bytes are needed.
-        ///
-        /// **Note**: this is only an estimate and not indicative of the actual bytes needed.
+        /// The amount of bytes the failing read needed beyond what was available. The built-in readers (e.g. [`SliceReader`](crate::de::read::SliceReader) and [`IoReader`](crate::IoReader)) all report this the same way, so it can be used as a hint for how much more data to wait for before trying again.
         ///
         /// **Note**: Bincode has no look-ahead mechanism. This means that this will only return the amount of bytes to be read for the current action, and not take into account the entire data structure being read.
         additional: usize,
     },
 
+    /// The reader reached its end before the first byte of the value, e.g. because the other side of a stream closed it after the last complete value.
+    ///
+    /// This is only returned by the functions that decode from a `std::io::Read`, like [`decode_from_std_read`](crate::decode_from_std_read). If the reader reaches its end after some bytes of the value were read, the value was cut off and [`DecodeError::UnexpectedEnd`\] is returned instead.
+    #[cfg(feature = "std")]
+    CleanEof,
+
     /// The given configuration limit was exceeded
     LimitExceeded,
 
+    /// A scope of [`Decoder::with_sub_limit`](crate::de::Decoder::with_sub_limit) claimed more than its own limit, like [`DecodeError::LimitExceeded`\] does for the limit of the config.
+    SubLimitExceeded {
+        /// The name of the scope
+        scope: &'static str,
+        /// The amount of bytes the scope was allowed to claim
+        limit: usize,
+    },
+
+    /// The decoder was asked for all remaining bytes of the input, e.g. for a field with `#[bincode(remaining_bytes)]`, but its reader does not know where the input ends. Decode from a slice, or wrap the reader in a [`TakeReader`](crate::de::read::TakeReader) with the length of the message.
+    RemainingBytesUnknown,
+
+    /// A [`TakeReader`](crate::de::read::TakeReader) was asked to read past the end of its budget of `limit` bytes.
+    TakeLimitExceeded {
+        /// The amount of bytes the `TakeReader` was allowed to read in total
+        limit: usize,
+        /// The amount of bytes the failing read needed beyond the remaining budget
+        additional: usize,
+    },
+
+    /// A [`SegmentedSliceReader`](crate::de::read::SegmentedSliceReader) was asked to borrow `length` bytes that span more than one segment. Borrowed data like `&str` and `&[u8]` must be contiguous, so decode an owned type like `String` or `Vec<u8>` instead.
+    BorrowSpansSegments {
+        /// The amount of bytes that were requested
+        length: usize,
+        /// The amount of bytes left in the current segment
+        available: usize,
+    },
+
+    /// The reader was asked to lend bytes mutably, e.g. for a `&mut [u8]`, but it can only lend shared bytes. Decode with [`borrow_decode_from_slice_mut`](crate::borrow_decode_from_slice_mut) instead.
+    MutableBorrowUnsupported,
+
+    /// [`decode_borrowed_from_buf_read`](crate::decode_borrowed_from_buf_read) was asked to borrow `requested` bytes that do not fit in the buffer of the reader, together with the data of the value that was borrowed before them. Use a reader with a larger buffer, or decode an owned type like `String` or `Vec<u8>` instead.
+    #[cfg(feature = "std")]
+    BorrowedDataTooLarge {
+        /// The amount of bytes that were requested
+        requested: usize,
+        /// The capacity of the buffer
+        capacity: usize,
+    },
+
     /// Invalid type was found. The decoder tried to read type `expected`, but found type `found` instead.
     InvalidIntegerType {
         /// The type that was being read from the reader
@@ -110,6 +312,8 @@ pub enum DecodeError {
     },
 
     /// The decoder tried to decode any of the `NonZero*` types but the value is zero
+    ///
+    /// This is returned for every width, e.g. `NonZeroU8`, `NonZero<i128>` and `NonZeroUsize`, with any int encoding, and also for a zero inside an `Option` or a collection.
     NonZeroTypeIsZero {
         /// The type that was being read from the reader
         non_zero_type: IntegerType,
@@ -123,19 +327,39 @@ pub enum DecodeError {
         /// The variants that are allowed
         allowed: &'static AllowedEnumVariants,
 
-        /// The index of the enum that the decoder encountered
-        found: u32,
+        /// The tag of the variant that the decoder encountered. Tags of derived enums can be up to a `u64`, see [`Decode`](crate::Decode#discriminants).
+        found: u64,
     },
 
+    /// The decoder read the name of a variant of an enum with `#[bincode(string_tags)]`, which is not the name of one of its variants.
+    ///
+    /// Without the `alloc` feature, an unknown name is reported as [`DecodeError::Other`\] instead.
+    #[cfg(feature = "alloc")]
+    UnexpectedVariantName(alloc::boxed::Box<UnexpectedVariantName>),
+
     /// The decoder tried to decode a `str`, but an utf8 error was encountered.
+    ///
+    /// With [`Configuration::with_lossy_utf8_strings`](crate::config::Configuration::with_lossy_utf8_strings), owned strings replace the invalid UTF-8 instead, but borrowed strings still fail with this error.
     Utf8 {
         /// The inner error
         inner: core::str::Utf8Error,
+        /// The index in the string where the invalid UTF-8 starts, like [`Utf8Error::valid_up_to`](core::str::Utf8Error::valid_up_to). The bytes before it are valid UTF-8.
+        valid_up_to: usize,
+        /// The byte at `valid_up_to`, which starts the invalid sequence. Use [`Utf8Error::error_len`](core::str::Utf8Error::error_len) of `inner` to tell if the sequence is invalid, or the string ends in the middle of a character.
+        byte: u8,
     },
 
     /// The decoder tried to decode a `char` and failed. The given buffer contains the bytes that are read at the moment of failure.
     InvalidCharEncoding([u8; 4]),
 
+    /// The decoder tried to decode a `char`, but found a value that is not a Unicode scalar value: a surrogate (`0xD800` to `0xDFFF`) or a value above `char::MAX`.
+    ///
+    /// With [`Configuration::with_scalar_char_encoding`](crate::config::Configuration::with_scalar_char_encoding), this is the `u32` that was read. With the UTF-8 encoding, it is the value of a sequence of bytes that is well-formed, but encodes one of these values.
+    InvalidCharValue {
+        /// The value that was decoded
+        found: u32,
+    },
+
     /// The decoder tried to decode a `bool` and failed. The given value is what is actually read.
     InvalidBooleanValue(u8),
 
@@ -147,6 +371,114 @@ pub enum DecodeError {
         found: usize,
     },
 
+    /// The padding after a field with `#[bincode(pad_to = N, strict_padding)]` or a struct with `#[bincode(pad_struct_to = N, strict_padding)]` contains a byte that is not zero, see [`Decoder::with_padding`](crate::de::Decoder::with_padding).
+    NonZeroPadding {
+        /// The size that the value is padded to
+        pad_to: usize,
+        /// The amount of bytes that the value was decoded from, where the padding starts
+        size: usize,
+    },
+
+    /// The option bitmap of a struct with `#[bincode(option_bitmap)]` has a bit set for an option that the struct does not have, see [`decode_option_bitmap`](crate::de::decode_option_bitmap).
+    InvalidOptionBitmap {
+        /// The amount of options in the bitmap
+        options: usize,
+        /// The last byte of the bitmap, which has the invalid bit
+        found: u8,
+    },
+
+    /// A COBS frame ended before the value was decoded, or the input ended before the delimiter of the frame.
+    CobsFrameTruncated,
+
+    /// A COBS frame has a zero byte inside of a block, so the stuffing is invalid.
+    CobsInvalidStuffing {
+        /// The offset of the zero byte, from the start of the frame
+        offset: usize,
+    },
+
+    /// Base64 or hex text read by an [armor](crate::armor) reader has a character that is not part of its alphabet, or a missing padding character.
+    InvalidArmorCharacter {
+        /// The character that was found
+        found: u8,
+        /// The offset of the character, from the start of the text
+        offset: usize,
+    },
+
+    /// The callback of a [`ProgressReader`](crate::de::read::ProgressReader) returned `ControlFlow::Break`.
+    Cancelled,
+
+    /// The key of the entry at `index` of a `BTreeMap` or `BTreeSet` is not greater than the key before it, with [`Configuration::with_strict_map_order`](crate::config::Configuration::with_strict_map_order).
+    NonCanonicalMapOrder {
+        /// The index of the entry
+        index: usize,
+    },
+
+    /// A [`DeadlineReader`](crate::DeadlineReader) ran out of time before the value was read.
+    TimedOut {
+        /// The time since the reader was created if it has a deadline, or the time it spent waiting for the underlying reader if it has a budget
+        elapsed: core::time::Duration,
+    },
+
+    /// [`decode_dispatch`](crate::message::decode_dispatch) read a message with an id that no handler is registered for. The payload of the message was skipped, so the next message can be decoded.
+    UnknownMessage {
+        /// The id of the message
+        id: u16,
+        /// The length of the payload in bytes
+        len: usize,
+    },
+
+    /// The digest of the bytes that [`decode_and_verify_digest`](crate::digest::decode_and_verify_digest) decoded a value from differs from the expected digest, so the bytes were changed or the digest is of other bytes.
+    DigestMismatch,
+
+    /// The bytes end with the [`MAGIC`](crate::debug_info::MAGIC) of [debug info](crate::debug_info), but the trailer in front of it is not valid, e.g. because it was cut off.
+    InvalidDebugInfo,
+
+    /// The header that [`read_header`](crate::envelope::read_header) read starts with other magic bytes than expected, so the bytes are not a file of the expected format.
+    WrongMagic {
+        /// The magic bytes of the expected format
+        expected: [u8; 4],
+        /// The magic bytes that were found
+        found: [u8; 4],
+    },
+
+    /// A file of the expected format has a version that [`decode_file`](crate::envelope::decode_file) can not decode, e.g. because it was written by a newer version of the program.
+    UnsupportedVersion {
+        /// The version of the file
+        version: u16,
+    },
+
+    /// A [`Validated`](crate::adapters::Validated) or [`Mapped`](crate::adapters::Mapped) field rejected the value that was decoded for it.
+    InvalidValue {
+        /// The type name of the value that was rejected
+        type_name: &'static str,
+        /// The message of the validator or mapping
+        message: &'static str,
+    },
+
+    /// The config header that [`decode_checking_config_header`](crate::decode_checking_config_header) read says that the data was encoded with a config that has a different format than the config it was decoded with.
+    ///
+    /// Both are [wire ids](crate::config::Config::wire_id). The message describes the settings that differ.
+    ConfigMismatch {
+        /// The wire id of the config that the data was decoded with
+        expected: [u8; 4],
+        /// The wire id in the config header of the data
+        found: [u8; 4],
+    },
+
+    /// A value was decoded from a slice, but `remaining` bytes after it were not part of the value, with [`Configuration::with_no_trailing_bytes`](crate::config::Configuration::with_no_trailing_bytes).
+    TrailingBytes {
+        /// The amount of bytes after the value
+        remaining: usize,
+    },
+
+    /// The decoder tried to decode a fixed-capacity container (e.g. a [`BoundedVec`](crate::bounded::BoundedVec) or an `ArrayVec`) of capacity `cap`, but the binary data contained `len` elements.
+    ArrayCapacityExceeded {
+        /// The amount of elements found in the binary format.
+        len: usize,
+        /// The capacity of the container being decoded.
+        cap: usize,
+    },
+
     /// The encoded value is outside of the range of the target usize type.
     ///
     /// This can happen if an usize was encoded on an architecture with a larger
@@ -171,27 +503,62 @@ pub enum DecodeError {
         nanos: u32,
     },
 
-    /// The decoder tried to decode a `SystemTime` and overflowed
+    /// The decoder tried to decode a `SystemTime` and overflowed, or the time was more precise than the platform supports with [`Configuration::with_exact_system_time`](crate::config::Configuration::with_exact_system_time)
     InvalidSystemTime {
         /// The duration which could not have been added to
         /// [`UNIX_EPOCH`\](`std::time::SystemTime::UNIX_EPOCH`)
         duration: core::time::Duration,
     },
 
-    /// The decoder tried to decode a `CString`, but the incoming data contained a 0 byte
+    /// The decoder tried to decode a date or time type (e.g. from the `chrono` or `time` crates), but the encoded value is outside of the range that type can represent.
+    InvalidDateTime {
+        /// The type name of the date or time type that was being decoded
+        type_name: &'static str,
+    },
+
+    /// Memory for a decoded value could not be allocated. This is only returned with [`Configuration::with_fallible_allocation`](crate::config::Configuration::with_fallible_allocation), otherwise the process aborts.
+    #[cfg(feature = "alloc")]
+    AllocationFailed {
+        /// The amount of bytes for the elements that room was being reserved for. A growing container may have asked for more, to grow by doubling its capacity.
+        requested: usize,
+    },
+
+    /// A [`SharedArc`](crate::shared::SharedArc) or [`SharedRc`](crate::shared::SharedRc) refers to the shared value `id`, but it was not decoded before, or it is of a different type.
+    #[cfg(feature = "alloc")]
+    InvalidSharedId {
+        /// The id of the shared value, counting from 0
+        id: usize,
+    },
+
+    /// A [`SharedArc`](crate::shared::SharedArc) or [`SharedRc`](crate::shared::SharedRc) refers to the shared value `id` while that value is still being decoded. The encoder never writes such a cycle.
+    #[cfg(feature = "alloc")]
+    SharedCycle {
+        /// The id of the shared value, counting from 0
+        id: usize,
+    },
+
+    /// The decoder tried to decode a `CString`, but the incoming data contained a 0 byte, or it tried to borrow a `&CStr` whose data does not end with exactly one 0 byte
     #[cfg(feature = "std")]
     CStringNulError {
-        /// Nul byte position
+        /// Nul byte position, or the length of the data if a `&CStr` is missing its terminator
         position: usize,
     },
 
+    /// The decoder tried to decode a path that was encoded losslessly on another platform, and can not be represented on this one. See [`Configuration::with_lossless_paths`](crate::config::Configuration::with_lossless_paths).
+    ///
+    /// The path is read completely, so decoding can continue after it. The raw data of the path is kept, e.g. to convert it lossily.
+    #[cfg(feature = "std")]
+    ForeignPath(std::boxed::Box<ForeignPath>),
+
     /// The reader encountered an IO error but more bytes were expected.
+    ///
+    /// If the reader reached its end, [`DecodeError::UnexpectedEnd`\] is returned instead.
     #[cfg(feature = "std")]
     Io {
         /// The IO error expected
         inner: std::io::Error,
 
-        /// Gives an estimate of how many extra bytes are needed.
+        /// The amount of bytes the failing read still needed when the error occurred.
         ///
         /// **Note**: this is only an estimate and not indicative of the actual bytes needed.
         ///
@@ -206,24 +573,346 @@ pub enum DecodeError {
     #[cfg(feature = "alloc")]
     OtherString(alloc::string::String),
 
+    /// An error of a custom `Decode` implementation, see [`DecodeError::custom`\]. [`source`](core::error::Error::source) returns the inner error, and it can be downcast to get it back.
+    #[cfg(feature = "alloc")]
+    Custom(alloc::boxed::Box<dyn core::error::Error + Send + Sync>),
+
     #[cfg(feature = "serde")]
     /// A serde-specific error that occurred while decoding.
     Serde(crate::features::serde::DecodeError),
+
+    /// The error `inner` occurred after `offset` bytes of the input were read.
+    ///
+    /// This is returned by the `_with_offset` decode functions, e.g. [`decode_from_slice_with_offset`](crate::decode_from_slice_with_offset), and by the other decode functions with a config that has [`with_error_offsets`](crate::config::Configuration::with_error_offsets). Use [`DecodeError::inner`\] or [`DecodeError::into_inner`\] to get the underlying error.
+    ///
+    /// The inner error is boxed, so this requires the `alloc` feature. Without it, errors never carry their offset.
+    #[cfg(feature = "alloc")]
+    WithOffset {
+        /// The amount of bytes that were read from the input when the error occurred. This is the position right after the value that could not be decoded, or the position of the read that failed.
+        offset: usize,
+        /// The error that occurred
+        inner: alloc::boxed::Box<Self>,
+    },
+
+    /// The error occurred while decoding a field of a struct or enum variant.
+    ///
+    /// `#[derive(Decode)]` and `#[derive(BorrowDecode)]` wrap the errors of every field in this variant.
+    #[cfg(feature = "alloc")]
+    Field(alloc::boxed::Box<FieldError>),
+
+    /// The error `inner` occurred while decoding the element at `index` of a collection, e.g. a `Vec` or a map.
+    #[cfg(feature = "alloc")]
+    Index {
+        /// The index of the element that could not be decoded
+        index: usize,
+        /// The error that occurred
+        inner: alloc::boxed::Box<Self>,
+    },
+}
+
+/// The raw data of a path that was encoded on another platform, see [`DecodeError::ForeignPath`\].
+#[cfg(feature = "std")]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ForeignPath {
+    /// The bytes of a path on Unix
+    Unix(std::vec::Vec<u8>),
+    /// The UTF-16 code units of a path on Windows, which may contain unpaired surrogates
+    Windows(std::vec::Vec<u16>),
+}
+
+/// The name that was found instead of the name of a variant, see [`DecodeError::UnexpectedVariantName`\].
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnexpectedVariantName {
+    /// The type name that was being decoded.
+    pub type_name: &'static str,
+    /// The names of the variants of the enum
+    pub allowed: &'static [&'static str],
+    /// The name that the decoder encountered. Invalid UTF-8 is replaced with `U+FFFD`, like [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy) does.
+    pub found: alloc::string::String,
+}
+
+/// The field that was being decoded when a [`DecodeError::Field`\] occurred.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct FieldError {
+    /// The name of the type the field belongs to. For enums, this includes the variant, e.g. `Shape::Circle`.
+    pub type_name: &'static str,
+    /// The name of the field. For tuple structs and tuple variants, this is the index of the field.
+    pub field_name: &'static str,
+    /// The error that occurred
+    pub inner: DecodeError,
 }
 
 impl core::fmt::Display for DecodeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        // TODO: Improve this?
-        write!(f, "{self:?}")
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::WithOffset { offset, inner } => write!(f, "{inner} at byte offset {offset}"),
+            #[cfg(feature = "alloc")]
+            Self::Field(field) => write!(
+                f,
+                "{}.{} -> {}",
+                field.type_name, field.field_name, field.inner
+            ),
+            #[cfg(feature = "alloc")]
+            Self::Index { index, inner } => write!(f, "[{index}] -> {inner}"),
+            #[cfg(feature = "alloc")]
+            Self::Custom(inner) => write!(f, "{inner}"),
+            #[cfg(feature = "serde")]
+            Self::Serde(inner) => write!(f, "{inner}"),
+            Self::Utf8 {
+                valid_up_to, byte, ..
+            } => write!(
+                f,
+                "invalid UTF-8 at index {valid_up_to} of the string, starting with byte {byte:#04x}"
+            ),
+            Self::InvalidValue { type_name, message } => {
+                write!(f, "invalid value of {type_name}: {message}")
+            }
+            Self::ConfigMismatch { expected, found } => {
+                crate::spec::fmt_config_mismatch(*expected, *found, f)
+            }
+            Self::InvalidCharValue { found } => {
+                write!(
+                    f,
+                    "invalid char {found:#x}, which is not a Unicode scalar value"
+                )
+            }
+            Self::InvalidArmorCharacter { found, offset } => write!(
+                f,
+                "invalid character {:?} at offset {offset} of the armored text",
+                char::from(*found)
+            ),
+            Self::WrongMagic { expected, found } => write!(
+                f,
+                "not a file of the expected format: expected magic bytes b\"{}\", found b\"{}\"",
+                expected.escape_ascii(),
+                found.escape_ascii()
+            ),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "unsupported format version {version}")
+            }
+            Self::NonZeroTypeIsZero { non_zero_type } => {
+                write!(f, "NonZero<{}> can not be zero", non_zero_type.name())
+            }
+            Self::UnexpectedVariant {
+                type_name,
+                allowed,
+                found,
+            } => write!(
+                f,
+                "unexpected variant of {type_name}: expected {allowed}, found {found}"
+            ),
+            #[cfg(feature = "alloc")]
+            Self::UnexpectedVariantName(name) => {
+                write!(
+                    f,
+                    "unexpected variant of {}: expected one of ",
+                    name.type_name
+                )?;
+                for (idx, allowed) in name.allowed.iter().enumerate() {
+                    let separator = if idx == 0 { "" } else { ", " };
+                    write!(f, "{separator}{allowed:?}")?;
+                }
+                write!(f, ", found {:?}", name.found)
+            }
+            // TODO: Improve this?
+            _ => write!(f, "{self:?}"),
+        }
     }
 }
 
 impl DecodeError {
+    /// Creates a [`DecodeError::Custom`\] with the given error, for custom `Decode` implementations that reject the input for a reason of their own.
+    ///
+    /// This accepts any error type, as well as a `String` or `&str` message. Call `downcast_ref` on the [`source`](core::error::Error::source) of the returned error to get the original error back.
+    ///
+    /// ```
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::{Decode, Decoder};
+    /// use bincode::error::DecodeError;
+    ///
+    /// #[derive(Debug)]
+    /// struct Port(u16);
+    ///
+    /// #[derive(Debug)]
+    /// struct ReservedPort(u16);
+    ///
+    /// impl std::fmt::Display for ReservedPort {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "port {} is reserved", self.0)
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ReservedPort {}
+    ///
+    /// impl<Context> Decode<Context> for Port {
+    ///     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         let port = u16::decode(decoder)?;
+    ///         if port < 1024 {
+    ///             return Err(DecodeError::custom(ReservedPort(port)));
+    ///         }
+    ///         Ok(Self(port))
+    ///     }
+    /// }
+    ///
+    /// let err = bincode::decode_from_slice::<Port, _>(&[80], bincode::config::standard()).unwrap_err();
+    /// let source = std::error::Error::source(&err).unwrap();
+    /// assert_eq!(source.downcast_ref::<ReservedPort>().unwrap().0, 80);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn custom(err: impl Into<alloc::boxed::Box<dyn core::error::Error + Send + Sync>>) -> Self {
+        Self::Custom(err.into())
+    }
+
+    /// Creates an error with a formatted message, e.g. `DecodeError::custom_fmt(format_args!("invalid id {id}"))`.
+    ///
+    /// This is a [`DecodeError::Custom`\] with the message. Without the `alloc` feature, this is a [`DecodeError::Other`\] if the message has no arguments, and a generic message otherwise.
+    #[must_use]
+    pub fn custom_fmt(args: core::fmt::Arguments<'_>) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::custom(alloc::fmt::format(args))
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            Self::Other(args.as_str().unwrap_or("custom decode error"))
+        }
+    }
+
+    /// The amount of bytes that were read from the input when this error occurred, if known.
+    ///
+    /// This is only known for errors returned from the `_with_offset` decode functions, e.g. [`decode_from_slice_with_offset`](crate::decode_from_slice_with_offset), or from a config with [`with_error_offsets`](crate::config::Configuration::with_error_offsets), and requires the `alloc` feature.
+    #[must_use]
+    pub const fn offset(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::WithOffset { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// The underlying error, without the context of [`DecodeError::WithOffset`\], [`DecodeError::Field`\] and [`DecodeError::Index`\].
+    #[must_use]
+    pub fn inner(&self) -> &Self {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::WithOffset { inner, .. } | Self::Index { inner, .. } => inner.inner(),
+            #[cfg(feature = "alloc")]
+            Self::Field(field) => field.inner.inner(),
+            _ => self,
+        }
+    }
+
+    /// The underlying error, without the context of [`DecodeError::WithOffset`\], [`DecodeError::Field`\] and [`DecodeError::Index`\].
+    #[must_use]
+    pub fn into_inner(self) -> Self {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::WithOffset { inner, .. } | Self::Index { inner, .. } => inner.into_inner(),
+            #[cfg(feature = "alloc")]
+            Self::Field(field) => field.inner.into_inner(),
+            _ => self,
+        }
+    }
+
+    /// The `std::io::Error` of a [`DecodeError::Io`\], also if it is wrapped in context, or the error itself if it is not an I/O error.
+    #[cfg(feature = "std")]
+    fn into_io_error(self) -> Result<std::io::Error, Self> {
+        match self {
+            Self::Io { inner, .. } => Ok(inner),
+            Self::WithOffset { inner, .. } | Self::Index { inner, .. }
+                if matches!(inner.inner(), Self::Io { .. }) =>
+            {
+                inner.into_io_error()
+            }
+            Self::Field(field) if matches!(field.inner.inner(), Self::Io { .. }) => {
+                field.inner.into_io_error()
+            }
+            error => Err(error),
+        }
+    }
+
+    /// Wrap this error in a [`DecodeError::Field`\], to indicate that it occurred while decoding the field `field_name` of `type_name`.
+    ///
+    /// This is called by `#[derive(Decode)]` for every field. Without the `alloc` feature, this returns the error unchanged.
+    #[must_use]
+    pub fn with_field(self, type_name: &'static str, field_name: &'static str) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::Field(alloc::boxed::Box::new(FieldError {
+                type_name,
+                field_name,
+                inner: self,
+            }))
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = (type_name, field_name);
+            self
+        }
+    }
+
+    /// Wrap this error in a [`DecodeError::Index`\], to indicate that it occurred while decoding the element at `index` of a collection.
+    ///
+    /// Without the `alloc` feature, this returns the error unchanged.
+    #[must_use]
+    pub fn with_index(self, index: usize) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::Index {
+                index,
+                inner: alloc::boxed::Box::new(self),
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = index;
+            self
+        }
+    }
+
+    /// Attach the given input offset to this error. Errors that already have an offset are returned as is.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub(crate) fn with_offset(self, offset: usize) -> Self {
+        match self {
+            Self::WithOffset { .. } => self,
+            inner => Self::WithOffset {
+                offset,
+                inner: alloc::boxed::Box::new(inner),
+            },
+        }
+    }
+
+    /// Creates a [`DecodeError::Utf8`\] for the string `bytes`, which failed to convert with `inner`.
+    pub(crate) const fn utf8(bytes: &[u8], inner: core::str::Utf8Error) -> Self {
+        let valid_up_to = inner.valid_up_to();
+        Self::Utf8 {
+            inner,
+            valid_up_to,
+            byte: bytes[valid_up_to],
+        }
+    }
+
     /// If the current error is `InvalidIntegerType`, change the `expected` and
     /// `found` values from `Ux` to `Ix`. This is needed to have correct error
     /// reporting in `src/varint/decode_signed.rs` since this calls
     /// `src/varint/decode_unsigned.rs` and needs to correct the `expected` and
     /// `found` types.
+    /// Takes the `DecodeError` back out of an `std::io::Error` that was converted from it. Any other `std::io::Error`, including the inner error of a [`DecodeError::Io`\], becomes a [`DecodeError::Io`\] that needs no additional bytes.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_io_error(error: std::io::Error) -> Self {
+        error.downcast().unwrap_or_else(|inner| Self::Io {
+            inner,
+            additional: 0,
+        })
+    }
+
     pub(crate) fn change_integer_type_to_signed(self) -> Self {
         match self {
             Self::InvalidIntegerType { expected, found } => Self::InvalidIntegerType {
@@ -241,9 +930,35 @@ impl DecodeError {
 pub enum AllowedEnumVariants {
     /// All values between `min` and `max` (inclusive) are allowed
     #[allow(missing_docs)]
-    Range { min: u32, max: u32 },
+    Range { min: u64, max: u64 },
     /// Each one of these values is allowed
-    Allowed(&'static [u32]),
+    Allowed(&'static [u64]),
+    /// Each one of these variants is allowed, by their name and their value. `#[derive(Decode)]` generates this for enums.
+    Named(&'static [(&'static str, u64)]),
+}
+
+/// Shows the allowed variants, e.g. `one of Ping(0), Pong(1)` or `one of 0..=2`.
+impl core::fmt::Display for AllowedEnumVariants {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("one of ")?;
+        match self {
+            Self::Range { min, max } => write!(f, "{min}..={max}"),
+            Self::Allowed(allowed) => {
+                for (idx, value) in allowed.iter().enumerate() {
+                    let separator = if idx == 0 { "" } else { ", " };
+                    write!(f, "{separator}{value}")?;
+                }
+                Ok(())
+            }
+            Self::Named(named) => {
+                for (idx, (name, value)) in named.iter().enumerate() {
+                    let separator = if idx == 0 { "" } else { ", " };
+                    write!(f, "{separator}{name}({value})")?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Integer types. Used by [`DecodeError`\]. These types have no purpose other than being shown in errors.
@@ -283,4 +998,23 @@ impl IntegerType {
             other => other,
         }
     }
+
+    /// The name of the Rust type, e.g. `u32`
+    pub(crate) const fn name(&self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::Usize => "usize",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::I128 => "i128",
+            Self::Isize => "isize",
+            Self::Reserved => "reserved",
+        }
+    }
 }
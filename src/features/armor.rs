@@ -0,0 +1,499 @@
+//! Base64 and hex armoring, for embedding encoded values in text like JSON or log lines.
+//!
+//! The writers armor the bytes while they are encoded, and the readers dearmor them while they are decoded, so the binary payload is never collected in a buffer.
+//!
+//! - [`Base64Writer`\] and [`HexWriter`\] write the armored text to a [`Writer`\]. Wrap a [`core::fmt::Write`], like a `String`, in a [`FmtWriter`\] to write to it.
+//! - [`Base64Reader`\] and [`HexReader`\] read the armored text from a [`Reader`\]. A character that is not part of the alphabet returns [`DecodeError::InvalidArmorCharacter`\] with its offset.
+//! - [`encode_to_base64_string`\] and [`decode_from_base64_str`\] do both in one call, and [`encode_to_hex_string`\] and [`decode_from_hex_str`\] do the same for hex.
+//!
+//! The alphabet and padding of base64 are chosen with [`Base64`\].
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::armor::{decode_from_base64_str, encode_to_base64_string, Base64};
+//!
+//! let config = bincode::config::standard();
+//! let value = (7u8, String::from("hi"), [0xFBu8, 0xFF]);
+//!
+//! let text = encode_to_base64_string(&value, config, Base64::STANDARD).unwrap();
+//! assert_eq!(text, "BwJoafv/");
+//! let text = encode_to_base64_string(&value, config, Base64::URL_SAFE).unwrap();
+//! assert_eq!(text, "BwJoafv_");
+//!
+//! let (decoded, len): ((u8, String, [u8; 2]), usize) =
+//!     decode_from_base64_str(&text, config, Base64::URL_SAFE).unwrap();
+//! assert_eq!((decoded, len), (value, text.len()));
+//! ```
+
+use crate::{
+    config::Config,
+    de::{read::Reader, read::SliceReader, Decode, DecoderImpl},
+    enc::write::Writer,
+    error::{DecodeError, EncodeError},
+};
+
+/// The alphabet and padding of base64 text.
+///
+/// With padding, the text is padded with `=` to a multiple of 4 characters, and [`Base64Reader::finish`] requires the padding. Without padding, `=` is an invalid character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64 {
+    url_safe: bool,
+    padding: bool,
+}
+
+impl Base64 {
+    /// The standard alphabet of RFC 4648, with `+` and `/`, and with padding.
+    pub const STANDARD: Self = Self {
+        url_safe: false,
+        padding: true,
+    };
+
+    /// The standard alphabet of RFC 4648, with `+` and `/`, without padding.
+    pub const STANDARD_NO_PAD: Self = Self {
+        url_safe: false,
+        padding: false,
+    };
+
+    /// The URL and filename safe alphabet of RFC 4648, with `-` and `_`, and with padding.
+    pub const URL_SAFE: Self = Self {
+        url_safe: true,
+        padding: true,
+    };
+
+    /// The URL and filename safe alphabet of RFC 4648, with `-` and `_`, without padding.
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        url_safe: true,
+        padding: false,
+    };
+
+    /// Returns the character of the 6 bit `value`.
+    const fn encode(self, value: u8) -> u8 {
+        match value {
+            0..=25 => b'A' + value,
+            26..=51 => b'a' + (value - 26),
+            52..=61 => b'0' + (value - 52),
+            62 if self.url_safe => b'-',
+            62 => b'+',
+            _ if self.url_safe => b'_',
+            _ => b'/',
+        }
+    }
+
+    /// Returns the 4 characters of the 3 bytes of `quantum`.
+    fn encode_quantum(self, quantum: [u8; 3]) -> [u8; 4] {
+        let bits = u32::from_be_bytes([0, quantum[0], quantum[1], quantum[2]]);
+        core::array::from_fn(|i| {
+            #[allow(clippy::cast_possible_truncation)]
+            let value = (bits >> (18 - 6 * i)) as u8 & 0x3F;
+            self.encode(value)
+        })
+    }
+
+    /// Returns the 6 bit value of `character`, or `None` if it is not part of the alphabet.
+    const fn decode(self, character: u8) -> Option<u8> {
+        match character {
+            b'A'..=b'Z' => Some(character - b'A'),
+            b'a'..=b'z' => Some(character - b'a' + 26),
+            b'0'..=b'9' => Some(character - b'0' + 52),
+            b'-' if self.url_safe => Some(62),
+            b'_' if self.url_safe => Some(63),
+            b'+' if !self.url_safe => Some(62),
+            b'/' if !self.url_safe => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// The amount of characters that [`Base64Writer`\] writes for `len` bytes, including the padding if `base64` has it, or `None` if it does not fit in a `usize`.
+#[must_use]
+pub const fn base64_len(len: usize, base64: Base64) -> Option<usize> {
+    // every 3 bytes are 4 characters, and the bytes after them are 2 or 3 characters, or 4 with padding
+    let rest = match (len % 3, base64.padding) {
+        (0, _) => 0,
+        (_, true) => 4,
+        (rest, false) => rest + 1,
+    };
+    match (len / 3).checked_mul(4) {
+        Some(full) => full.checked_add(rest),
+        None => None,
+    }
+}
+
+/// The bytes that are armored at once, so the characters fit in an inline buffer.
+const CHUNK: usize = 48;
+
+/// A writer that passes the bytes written to it on to a [`core::fmt::Write`], like a `String` or a `Formatter`.
+///
+/// The armoring writers only write ASCII, so wrap the `fmt::Write` in this to write armored text to it. Bytes that are not valid UTF-8 return an error.
+pub struct FmtWriter<W: core::fmt::Write> {
+    writer: W,
+}
+
+impl<W: core::fmt::Write> FmtWriter<W> {
+    /// Create a new `FmtWriter` that writes to `writer`.
+    pub const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Return the wrapped `fmt::Write`.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: core::fmt::Write> Writer for FmtWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let text = core::str::from_utf8(bytes)
+            .map_err(|_| EncodeError::Other("FmtWriter can only write UTF-8"))?;
+        self.writer
+            .write_str(text)
+            .map_err(|_| EncodeError::Other("the fmt::Write of a FmtWriter failed"))
+    }
+}
+
+/// A writer that base64-encodes the bytes written to it, and passes the characters on to the wrapped writer.
+///
+/// Every 3 bytes become 4 characters. Up to 2 bytes are kept until the next write completes them, so call [`finish`](Self::finish) after encoding to write the last characters and the padding.
+pub struct Base64Writer<W: Writer> {
+    writer: W,
+    base64: Base64,
+    pending: [u8; 2],
+    pending_len: usize,
+}
+
+impl<W: Writer> Base64Writer<W> {
+    /// Create a new `Base64Writer` that writes characters of `base64` to `writer`.
+    pub const fn new(writer: W, base64: Base64) -> Self {
+        Self {
+            writer,
+            base64,
+            pending: [0; 2],
+            pending_len: 0,
+        }
+    }
+
+    /// Write the characters of the last bytes and the padding, and return the wrapped writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the wrapped writer if it fails to write the characters.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        if self.pending_len > 0 {
+            let mut quantum = [0; 3];
+            quantum[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+            // the characters after the last byte are only padding
+            let mut characters = self.base64.encode_quantum(quantum);
+            characters[self.pending_len + 1..].fill(b'=');
+            let len = if self.base64.padding {
+                4
+            } else {
+                self.pending_len + 1
+            };
+            self.writer.write(&characters[..len])?;
+        }
+        Ok(self.writer)
+    }
+}
+
+impl<W: Writer> Writer for Base64Writer<W> {
+    fn write(&mut self, mut bytes: &[u8]) -> Result<(), EncodeError> {
+        // complete the bytes that were kept from the last write
+        if self.pending_len > 0 {
+            let len = (3 - self.pending_len).min(bytes.len());
+            let mut quantum = [0; 3];
+            quantum[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+            quantum[self.pending_len..self.pending_len + len].copy_from_slice(&bytes[..len]);
+            bytes = &bytes[len..];
+            if self.pending_len + len < 3 {
+                self.pending[..self.pending_len + len]
+                    .copy_from_slice(&quantum[..self.pending_len + len]);
+                self.pending_len += len;
+                return Ok(());
+            }
+            self.pending_len = 0;
+            self.writer.write(&self.base64.encode_quantum(quantum))?;
+        }
+
+        let mut characters = [0; CHUNK / 3 * 4];
+        let complete = bytes.len() - bytes.len() % 3;
+        for chunk in bytes[..complete].chunks(CHUNK) {
+            for (quantum, output) in chunk.chunks_exact(3).zip(characters.chunks_exact_mut(4)) {
+                output.copy_from_slice(
+                    &self
+                        .base64
+                        .encode_quantum([quantum[0], quantum[1], quantum[2]]),
+                );
+            }
+            self.writer.write(&characters[..chunk.len() / 3 * 4])?;
+        }
+
+        let rest = &bytes[complete..];
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+}
+
+/// A reader that decodes the base64 text of the wrapped reader.
+///
+/// Only the characters of the bytes that are read are taken from the wrapped reader. Call [`finish`](Self::finish) after decoding to read the padding, so the wrapped reader is right after the text.
+pub struct Base64Reader<R: Reader> {
+    reader: R,
+    base64: Base64,
+    /// The bits of the last character that are not part of a byte yet
+    bits: u32,
+    bits_len: u32,
+    chars_read: usize,
+}
+
+impl<R: Reader> Base64Reader<R> {
+    /// Create a new `Base64Reader` that reads characters of `base64` from `reader`.
+    pub const fn new(reader: R, base64: Base64) -> Self {
+        Self {
+            reader,
+            base64,
+            bits: 0,
+            bits_len: 0,
+            chars_read: 0,
+        }
+    }
+
+    /// Returns the number of characters read from the wrapped reader, including the padding if it was read.
+    #[must_use]
+    pub const fn chars_read(&self) -> usize {
+        self.chars_read
+    }
+
+    /// Read the padding after the last character, if the [`Base64`\] has padding, and return the wrapped reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DecodeError::InvalidArmorCharacter`\] if a character of the padding is not `=`, or the error of the wrapped reader if it ends before the padding.
+    pub fn finish(mut self) -> Result<R, DecodeError> {
+        let padding_len = (4 - self.chars_read % 4) % 4;
+        if self.base64.padding && padding_len > 0 {
+            let mut padding = [0; 3];
+            let padding = &mut padding[..padding_len];
+            self.reader.read(padding)?;
+            if let Some(i) = padding.iter().position(|&character| character != b'=') {
+                return Err(DecodeError::InvalidArmorCharacter {
+                    found: padding[i],
+                    offset: self.chars_read + i,
+                });
+            }
+            self.chars_read += padding.len();
+        }
+        Ok(self.reader)
+    }
+}
+
+impl<R: Reader> Reader for Base64Reader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let mut characters = [0; CHUNK / 3 * 4];
+        for chunk in bytes.chunks_mut(CHUNK) {
+            // the bits left from the last character are used first
+            let bits = chunk
+                .len()
+                .checked_mul(8)
+                .ok_or(DecodeError::Other("too many bytes to read"))?;
+            let needed = (bits - self.bits_len as usize).div_ceil(6);
+            let characters = &mut characters[..needed];
+            self.reader.read(characters)?;
+            let mut written = 0;
+            for (i, &character) in characters.iter().enumerate() {
+                let value =
+                    self.base64
+                        .decode(character)
+                        .ok_or(DecodeError::InvalidArmorCharacter {
+                            found: character,
+                            offset: self.chars_read + i,
+                        })?;
+                self.bits = (self.bits << 6) | u32::from(value);
+                self.bits_len += 6;
+                if self.bits_len >= 8 {
+                    self.bits_len -= 8;
+                    #[allow(clippy::cast_possible_truncation)]
+                    let byte = (self.bits >> self.bits_len) as u8;
+                    chunk[written] = byte;
+                    written += 1;
+                    self.bits &= (1 << self.bits_len) - 1;
+                }
+            }
+            self.chars_read += needed;
+        }
+        Ok(())
+    }
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// A writer that hex-encodes the bytes written to it with lowercase characters, and passes the characters on to the wrapped writer.
+pub struct HexWriter<W: Writer> {
+    writer: W,
+}
+
+impl<W: Writer> HexWriter<W> {
+    /// Create a new `HexWriter` that writes to `writer`.
+    pub const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Return the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Writer> Writer for HexWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let mut characters = [0; CHUNK * 2];
+        for chunk in bytes.chunks(CHUNK) {
+            for (byte, output) in chunk.iter().zip(characters.chunks_exact_mut(2)) {
+                output[0] = HEX[usize::from(byte >> 4)];
+                output[1] = HEX[usize::from(byte & 0xF)];
+            }
+            self.writer.write(&characters[..chunk.len() * 2])?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+}
+
+/// A reader that decodes the hex text of the wrapped reader. Both lowercase and uppercase characters are accepted.
+pub struct HexReader<R: Reader> {
+    reader: R,
+    chars_read: usize,
+}
+
+impl<R: Reader> HexReader<R> {
+    /// Create a new `HexReader` that reads from `reader`.
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chars_read: 0,
+        }
+    }
+
+    /// Returns the number of characters read from the wrapped reader.
+    #[must_use]
+    pub const fn chars_read(&self) -> usize {
+        self.chars_read
+    }
+
+    /// Return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reader> Reader for HexReader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let mut characters = [0; CHUNK * 2];
+        for chunk in bytes.chunks_mut(CHUNK) {
+            let characters = &mut characters[..chunk.len() * 2];
+            self.reader.read(characters)?;
+            for (i, character) in characters.iter().enumerate() {
+                let value = match character {
+                    b'0'..=b'9' => character - b'0',
+                    b'a'..=b'f' => character - b'a' + 10,
+                    b'A'..=b'F' => character - b'A' + 10,
+                    _ => {
+                        return Err(DecodeError::InvalidArmorCharacter {
+                            found: *character,
+                            offset: self.chars_read + i,
+                        })
+                    }
+                };
+                chunk[i / 2] = (chunk[i / 2] << 4) | value;
+            }
+            self.chars_read += characters.len();
+        }
+        Ok(())
+    }
+}
+
+/// Encode the given value into a base64 `String` of `base64`, without encoding it into a buffer of bytes first.
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value could not be encoded.
+///
+/// [config]: ../config/index.html
+#[cfg(feature = "alloc")]
+pub fn encode_to_base64_string<E: crate::Encode, C: Config>(
+    val: E,
+    config: C,
+    base64: Base64,
+) -> Result<alloc::string::String, EncodeError> {
+    let mut writer = Base64Writer::new(FmtWriter::new(alloc::string::String::new()), base64);
+    crate::encode_into_writer(val, &mut writer, config)?;
+    Ok(writer.finish()?.into_inner())
+}
+
+/// Attempt to decode a given type `D` from the base64 text of `base64` at the start of the given `str`. Returns the decoded output and the amount of characters read, including the padding.
+///
+/// Characters after the value and its padding are not read. See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the text has a character that is not part of the alphabet, if it ends before `D` is decoded, or if the data is invalid.
+///
+/// [config]: ../config/index.html
+pub fn decode_from_base64_str<D: Decode<()>, C: Config>(
+    src: &str,
+    config: C,
+    base64: Base64,
+) -> Result<(D, usize), DecodeError> {
+    let mut reader = Base64Reader::new(SliceReader::new(src.as_bytes()), base64);
+    let mut decoder = DecoderImpl::<_, C, ()>::new(&mut reader, config, ());
+    let value = D::decode(&mut decoder)?;
+    let reader = reader.finish()?;
+    Ok((value, src.len() - reader.remaining().len()))
+}
+
+/// Encode the given value into a lowercase hex `String`, without encoding it into a buffer of bytes first.
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value could not be encoded.
+///
+/// [config]: ../config/index.html
+#[cfg(feature = "alloc")]
+pub fn encode_to_hex_string<E: crate::Encode, C: Config>(
+    val: E,
+    config: C,
+) -> Result<alloc::string::String, EncodeError> {
+    let mut writer = HexWriter::new(FmtWriter::new(alloc::string::String::new()));
+    crate::encode_into_writer(val, &mut writer, config)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+/// Attempt to decode a given type `D` from the hex text at the start of the given `str`. Returns the decoded output and the amount of characters read.
+///
+/// Characters after the value are not read. See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the text has a character that is not a hex digit, if it ends before `D` is decoded, or if the data is invalid.
+///
+/// [config]: ../config/index.html
+pub fn decode_from_hex_str<D: Decode<()>, C: Config>(
+    src: &str,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    let mut reader = HexReader::new(SliceReader::new(src.as_bytes()));
+    let mut decoder = DecoderImpl::<_, C, ()>::new(&mut reader, config, ());
+    let value = D::decode(&mut decoder)?;
+    Ok((value, reader.chars_read()))
+}
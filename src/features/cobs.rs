@@ -0,0 +1,238 @@
+//! [COBS](https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing) framing, for transports where a zero byte marks the end of a frame, like serial links.
+//!
+//! COBS replaces every zero byte of the payload, so the only zero byte in a frame is the delimiter at its end. A receiver that loses track of the frames can start over at the next zero byte. The overhead is at most one byte per 254 bytes of payload, see [`max_encoded_len`].
+//!
+//! - [`CobsWriter`\] stuffs the bytes while they are encoded, and writes the delimiter in [`CobsWriter::finish`].
+//! - [`CobsReader`\] unstuffs the bytes while they are decoded. [`decode_from_cobs_slice`\] decodes a value from a frame at the start of a slice.
+//!
+//! Neither needs a buffer for the whole payload, so this works without `alloc`.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::cobs::{decode_from_cobs_slice, CobsWriter};
+//! use bincode::enc::write::SliceWriter;
+//!
+//! let config = bincode::config::standard();
+//! let value = (0u8, 1000u32, [0u8; 3]);
+//!
+//! let mut output = [0u8; 16];
+//! let mut writer = CobsWriter::new(SliceWriter::new(&mut output));
+//! bincode::encode_into_writer(value, &mut writer, config).unwrap();
+//! let len = writer.finish().unwrap().bytes_written();
+//! assert_eq!(output[..len], [1, 4, 251, 232, 3, 1, 1, 1, 0]);
+//!
+//! let (decoded, frame_len): ((u8, u32, [u8; 3]), usize) =
+//!     decode_from_cobs_slice(&output, config).unwrap();
+//! assert_eq!((decoded, frame_len), (value, len));
+//! ```
+
+use crate::{
+    config::Config,
+    de::{read::Reader, read::SliceReader, Decode, DecoderImpl},
+    enc::write::Writer,
+    error::{DecodeError, EncodeError},
+};
+
+/// The most bytes a block can have without a zero byte after them.
+const MAX_BLOCK: usize = 254;
+
+/// The most bytes that a payload of `len` bytes takes when it is COBS-encoded, including the delimiter.
+///
+/// This is reached when the payload has no zero bytes.
+#[must_use]
+pub const fn max_encoded_len(len: usize) -> usize {
+    len + len / MAX_BLOCK + 2
+}
+
+/// A writer that COBS-encodes the bytes written to it, and passes them on to the wrapped writer.
+///
+/// Bytes are collected in an inline buffer until a zero byte is written, or until 254 bytes are collected. Call [`finish`](Self::finish) after encoding to write the last bytes and the delimiter. Without it, the frame is incomplete.
+pub struct CobsWriter<W: Writer> {
+    writer: W,
+    block: [u8; MAX_BLOCK],
+    len: usize,
+}
+
+impl<W: Writer> CobsWriter<W> {
+    /// Create a new `CobsWriter` that starts a frame.
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            block: [0; MAX_BLOCK],
+            len: 0,
+        }
+    }
+
+    /// Write the remaining bytes and the delimiter, and return the wrapped writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the wrapped writer if it fails to write the bytes.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        self.write_block()?;
+        self.writer.write(&[0])?;
+        Ok(self.writer)
+    }
+
+    /// Write the collected bytes as a block, preceded by the code byte.
+    fn write_block(&mut self) -> Result<(), EncodeError> {
+        let len = core::mem::take(&mut self.len);
+        #[allow(clippy::cast_possible_truncation)]
+        self.writer.write(&[len as u8 + 1])?;
+        self.writer.write(&self.block[..len])
+    }
+}
+
+impl<W: Writer> Writer for CobsWriter<W> {
+    fn write(&mut self, mut bytes: &[u8]) -> Result<(), EncodeError> {
+        while !bytes.is_empty() {
+            // a full block is only written once more bytes follow, so the frame does not end in an empty block
+            if self.len == MAX_BLOCK {
+                self.write_block()?;
+            }
+            let chunk = &bytes[..bytes.len().min(MAX_BLOCK - self.len)];
+            let (data, rest) = chunk.iter().position(|&byte| byte == 0).map_or_else(
+                || (chunk, &bytes[chunk.len()..]),
+                |zero| (&chunk[..zero], &bytes[zero + 1..]),
+            );
+            self.block[self.len..self.len + data.len()].copy_from_slice(data);
+            self.len += data.len();
+            if data.len() < chunk.len() {
+                self.write_block()?;
+            }
+            bytes = rest;
+        }
+        Ok(())
+    }
+}
+
+/// A reader that decodes a COBS frame from the wrapped reader.
+///
+/// The bytes are unstuffed while they are read, so no buffer is needed. Reading past the end of the frame returns [`DecodeError::CobsFrameTruncated`], and a zero byte inside of a block returns [`DecodeError::CobsInvalidStuffing`].
+///
+/// Call [`finish`](Self::finish) after decoding to skip the rest of the frame, so the wrapped reader is at the start of the next frame.
+pub struct CobsReader<R: Reader> {
+    reader: R,
+    /// The bytes left in the current block
+    remaining: usize,
+    /// Whether the current block is followed by a zero byte, unless it is the last block
+    zero_follows: bool,
+    ended: bool,
+    bytes_read: usize,
+}
+
+impl<R: Reader> CobsReader<R> {
+    /// Create a new `CobsReader` that reads a frame from the start of `reader`.
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            remaining: 0,
+            zero_follows: false,
+            ended: false,
+            bytes_read: 0,
+        }
+    }
+
+    /// Returns the number of bytes read from the wrapped reader, including the delimiter if it was read.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Skip the rest of the frame, including the delimiter, and return the wrapped reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rest of the frame is not valid COBS, or if the wrapped reader ends before the delimiter.
+    pub fn finish(mut self) -> Result<R, DecodeError> {
+        let mut buffer = [0u8; 64];
+        while !self.ended {
+            if self.remaining == 0 {
+                self.next_block()?;
+            } else {
+                let len = self.remaining.min(buffer.len());
+                self.read_block(&mut buffer[..len])?;
+            }
+        }
+        Ok(self.reader)
+    }
+
+    /// Read the code byte of the next block. Returns `false` if it is the delimiter.
+    fn next_block(&mut self) -> Result<bool, DecodeError> {
+        let mut code = [0u8];
+        self.reader.read(&mut code).map_err(truncated)?;
+        self.bytes_read += 1;
+        if code[0] == 0 {
+            self.ended = true;
+            return Ok(false);
+        }
+        self.remaining = usize::from(code[0] - 1);
+        self.zero_follows = code[0] != 0xFF;
+        Ok(true)
+    }
+
+    /// Read `bytes.len()` bytes of the current block.
+    fn read_block(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes).map_err(truncated)?;
+        if let Some(zero) = bytes.iter().position(|&byte| byte == 0) {
+            return Err(DecodeError::CobsInvalidStuffing {
+                offset: self.bytes_read + zero,
+            });
+        }
+        self.bytes_read += bytes.len();
+        self.remaining -= bytes.len();
+        Ok(())
+    }
+}
+
+impl<R: Reader> Reader for CobsReader<R> {
+    fn read(&mut self, mut bytes: &mut [u8]) -> Result<(), DecodeError> {
+        while !bytes.is_empty() {
+            if self.remaining == 0 {
+                // the zero byte after a block is only there if another block follows
+                let zero = self.zero_follows;
+                if self.ended || !self.next_block()? {
+                    return Err(DecodeError::CobsFrameTruncated);
+                }
+                if zero {
+                    bytes[0] = 0;
+                    bytes = &mut bytes[1..];
+                }
+                continue;
+            }
+            let len = self.remaining.min(bytes.len());
+            let (block, rest) = core::mem::take(&mut bytes).split_at_mut(len);
+            self.read_block(block)?;
+            bytes = rest;
+        }
+        Ok(())
+    }
+}
+
+/// The end of the wrapped reader is the end of a frame without a delimiter.
+fn truncated(e: DecodeError) -> DecodeError {
+    match e {
+        DecodeError::UnexpectedEnd { .. } => DecodeError::CobsFrameTruncated,
+        e => e,
+    }
+}
+
+/// Attempt to decode a given type `D` from the COBS frame at the start of the given slice. Returns the decoded output and the length of the frame, including the delimiter.
+///
+/// Bytes of the frame that are left after decoding `D` are skipped. See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the frame is not valid COBS, if it ends before `D` is decoded, or if the data is invalid.
+///
+/// [config]: ../config/index.html
+pub fn decode_from_cobs_slice<D: Decode<()>, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    let mut reader = CobsReader::new(SliceReader::new(src));
+    let mut decoder = DecoderImpl::<_, C, ()>::new(&mut reader, config, ());
+    let value = D::decode(&mut decoder)?;
+    let frame = src.len() - reader.finish()?.slice.len();
+    Ok((value, frame))
+}
@@ -1,2 +1,8 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "derive-next")))]
-pub use bincode_derive_next::{BorrowDecode, Decode, Encode};
+pub use bincode_derive_next::{
+    BorrowDecode, ConstEncode, Decode, Encode, SkipDecode, TransparentWire,
+};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "derive", feature = "alloc"))))]
+pub use bincode_derive_next::{EncodedLayout, IncrementalEncode};
@@ -0,0 +1,222 @@
+//! Helpers for fuzzing `Encode` and `Decode` implementations, e.g. with [`cargo fuzz`](https://github.com/rust-fuzz/cargo-fuzz).
+//!
+//! There are two ways to fuzz a type:
+//!
+//! - [`roundtrip`\] takes the raw fuzzer input as encoded bytes. Inputs that fail to decode are ignored, every value that does decode must survive an encode/decode round trip.
+//! - [`roundtrip_arbitrary`\] uses [`arbitrary`](https://docs.rs/arbitrary) to generate a valid value from the fuzzer input, and then checks that this value survives an encode/decode round trip.
+//!
+//! Both panic with a [`RoundtripError`\] when the round trip fails, which is what fuzzers look for. Use [`check_roundtrip`\] or [`check_roundtrip_arbitrary`\] to handle the error yourself.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+//! enum Message {
+//!     Ping(u32),
+//!     Text(Vec<String>),
+//! }
+//!
+//! // inside of `fuzz_target!(|data: &[u8]| { ... })`
+//! # let data = &[1, 2, 1, b'a', 1, b'b'];
+//! bincode::fuzz::roundtrip::<Message, _>(data, bincode::config::standard());
+//! ```
+//!
+//! A round trip is successful if:
+//! - the value can be encoded,
+//! - the encoded bytes decode to a value that is equal to the original value,
+//! - decoding consumes exactly all of the encoded bytes, and
+//! - encoding the decoded value results in exactly the same bytes.
+//!
+//! Note that types whose `PartialEq` is not reflexive (e.g. `f32::NAN != f32::NAN`) will fail the equality check, and types without a deterministic encoding (e.g. a `HashMap` with more than one entry) will fail the byte check.
+
+use crate::{
+    config::Config,
+    de::Decode,
+    enc::Encode,
+    error::{DecodeError, EncodeError},
+};
+use alloc::{format, string::String, vec::Vec};
+use arbitrary::{Arbitrary, Unstructured};
+use core::fmt;
+
+/// The reason a round trip failed. See the [module documentation](self) for more information.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum RoundtripError {
+    /// The value could not be encoded
+    Encode(EncodeError),
+
+    /// The encoded value could not be decoded
+    Decode(DecodeError),
+
+    /// Decoding did not consume all of the encoded bytes
+    LengthMismatch {
+        /// The amount of bytes that were encoded
+        encoded: usize,
+        /// The amount of bytes that were read while decoding
+        read: usize,
+    },
+
+    /// The decoded value is not equal to the original value
+    ValueMismatch {
+        /// The `Debug` representation of the original value
+        original: String,
+        /// The `Debug` representation of the decoded value
+        decoded: String,
+    },
+
+    /// Encoding the decoded value resulted in different bytes than encoding the original value
+    BytesMismatch {
+        /// The bytes of the original value
+        original: Vec<u8>,
+        /// The bytes of the decoded value
+        decoded: Vec<u8>,
+    },
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encode(e) => write!(f, "failed to encode the value: {e}"),
+            Self::Decode(e) => write!(f, "failed to decode the encoded value: {e}"),
+            Self::LengthMismatch { encoded, read } => write!(
+                f,
+                "encoded {encoded} bytes, but decoding read {read} bytes"
+            ),
+            Self::ValueMismatch { original, decoded } => write!(
+                f,
+                "the decoded value is not equal to the original value\noriginal: {original}\ndecoded:  {decoded}"
+            ),
+            Self::BytesMismatch { original, decoded } => write!(
+                f,
+                "the decoded value encodes to different bytes\noriginal: {original:?}\ndecoded:  {decoded:?}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RoundtripError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Encode(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Decode a `T` from `data`, and check that the decoded value survives a round trip.
+///
+/// Does nothing if `data` can not be decoded into a `T`.
+///
+/// # Panics
+///
+/// Panics with the [`RoundtripError`\] if the round trip fails.
+pub fn roundtrip<T, C>(data: &[u8], config: C)
+where
+    T: Encode + Decode<()> + PartialEq + fmt::Debug,
+    C: Config,
+{
+    if let Err(e) = check_roundtrip::<T, C>(data, config) {
+        panic!("bincode round trip failed: {e}");
+    }
+}
+
+/// Decode a `T` from `data`, and check that the decoded value survives a round trip.
+///
+/// Returns `Ok(())` if `data` can not be decoded into a `T`.
+///
+/// # Errors
+///
+/// Returns a [`RoundtripError`\] if the decoded value does not survive the round trip.
+pub fn check_roundtrip<T, C>(data: &[u8], config: C) -> Result<(), RoundtripError>
+where
+    T: Encode + Decode<()> + PartialEq + fmt::Debug,
+    C: Config,
+{
+    crate::decode_from_slice::<T, C>(data, config)
+        .map_or(Ok(()), |(value, _)| check_value(&value, config))
+}
+
+/// Generate a `T` from `data` with [`Arbitrary`\], and check that it survives a round trip.
+///
+/// Does nothing if `data` does not contain enough bytes to generate a `T`.
+///
+/// # Panics
+///
+/// Panics with the [`RoundtripError`\] if the round trip fails.
+pub fn roundtrip_arbitrary<'a, T, C>(data: &'a [u8], config: C)
+where
+    T: Arbitrary<'a> + Encode + Decode<()> + PartialEq + fmt::Debug,
+    C: Config,
+{
+    if let Err(e) = check_roundtrip_arbitrary::<T, C>(data, config) {
+        panic!("bincode round trip failed: {e}");
+    }
+}
+
+/// Generate a `T` from `data` with [`Arbitrary`\], and check that it survives a round trip.
+///
+/// Returns `Ok(())` if `data` does not contain enough bytes to generate a `T`.
+///
+/// # Errors
+///
+/// Returns a [`RoundtripError`\] if the generated value does not survive the round trip.
+pub fn check_roundtrip_arbitrary<'a, T, C>(data: &'a [u8], config: C) -> Result<(), RoundtripError>
+where
+    T: Arbitrary<'a> + Encode + Decode<()> + PartialEq + fmt::Debug,
+    C: Config,
+{
+    T::arbitrary_take_rest(Unstructured::new(data))
+        .map_or(Ok(()), |value| check_value(&value, config))
+}
+
+/// Generate a `T` with [`Arbitrary`\] and encode it, returning both the value and the encoded bytes.
+///
+/// This is useful to give a fuzz target valid encoded input, e.g. to fuzz a protocol that embeds bincode-encoded messages.
+///
+/// # Errors
+///
+/// Returns an error if `u` does not contain enough data to generate a `T`, or if the generated `T` can not be encoded.
+pub fn arbitrary_encoded<'a, T, C>(
+    u: &mut Unstructured<'a>,
+    config: C,
+) -> arbitrary::Result<(T, Vec<u8>)>
+where
+    T: Arbitrary<'a> + Encode,
+    C: Config,
+{
+    let value = T::arbitrary(u)?;
+    let encoded =
+        crate::encode_to_vec(&value, config).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+    Ok((value, encoded))
+}
+
+fn check_value<T, C>(value: &T, config: C) -> Result<(), RoundtripError>
+where
+    T: Encode + Decode<()> + PartialEq + fmt::Debug,
+    C: Config,
+{
+    let encoded = crate::encode_to_vec(value, config).map_err(RoundtripError::Encode)?;
+    let (decoded, read) =
+        crate::decode_from_slice::<T, C>(&encoded, config).map_err(RoundtripError::Decode)?;
+    if read != encoded.len() {
+        return Err(RoundtripError::LengthMismatch {
+            encoded: encoded.len(),
+            read,
+        });
+    }
+    if &decoded != value {
+        return Err(RoundtripError::ValueMismatch {
+            original: format!("{value:?}"),
+            decoded: format!("{decoded:?}"),
+        });
+    }
+    let re_encoded = crate::encode_to_vec(&decoded, config).map_err(RoundtripError::Encode)?;
+    if re_encoded != encoded {
+        return Err(RoundtripError::BytesMismatch {
+            original: encoded,
+            decoded: re_encoded,
+        });
+    }
+    Ok(())
+}
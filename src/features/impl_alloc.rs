@@ -1,13 +1,23 @@
 #![allow(unsafe_code)]
 use crate::{
-    de::{read::Reader, BorrowDecoder, Decode, Decoder},
+    config::{
+        Allocation, FixedLenPadding, InternalAllocationConfig, InternalMapOrderConfig,
+        InternalUtf8Config, MapOrder, Utf8Decoding,
+    },
+    de::{
+        read::Reader, BorrowDecodeFixedLen, BorrowDecodeRemaining, BorrowDecoder,
+        CollectionDecoder, Decode, DecodeExtend, DecodeFixedLen, DecodeRemaining, Decoder,
+        Extended, SkipDecode,
+    },
     enc::{
         self,
-        write::{SizeWriter, Writer},
+        write::{SeekWriter, SizeWriter},
         Encode, Encoder,
     },
     error::{DecodeError, EncodeError},
-    impl_borrow_decode, BorrowDecode, Config,
+    impl_borrow_decode,
+    utils::Private,
+    BorrowDecode, Config,
 };
 use alloc::{
     borrow::{Cow, ToOwned},
@@ -17,6 +27,7 @@ use alloc::{
     string::String,
     vec::Vec,
 };
+use core::mem::MaybeUninit;
 
 #[cfg(target_has_atomic = "ptr")]
 use alloc::sync::Arc;
@@ -35,6 +46,12 @@ impl VecWriter {
             inner: Vec::with_capacity(cap),
         }
     }
+    /// Returns the bytes that were written.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.inner
+    }
+
     // May not be used in all feature combinations
     #[allow(dead_code)]
     pub(crate) fn collect(self) -> Vec<u8> {
@@ -48,6 +65,23 @@ impl enc::write::Writer for VecWriter {
         self.inner.extend_from_slice(bytes);
         Ok(())
     }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        Some(self)
+    }
+}
+
+impl SeekWriter for VecWriter {
+    #[inline]
+    fn position(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        enc::write::written_range(&mut self.inner, offset, bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
 }
 
 /// Encode the given value into a `Vec<u8>` with the given `Config`. See the [config] module for more information.
@@ -69,6 +103,21 @@ pub fn encode_to_vec<E: enc::Encode, C: Config>(val: E, config: C) -> Result<Vec
     Ok(encoder.into_writer().inner)
 }
 
+/// Encode the given value into a `Vec<u8>` like [`encode_to_vec`], with the [wire id](Config::wire_id) of `config` in front of it.
+///
+/// Decode it with [`decode_checking_config_header`](crate::decode_checking_config_header), which checks that the data is decoded with the same format.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value cannot be encoded.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn encode_with_config_header<E: enc::Encode, C: Config>(
+    val: E,
+    config: C,
+) -> Result<Vec<u8>, EncodeError> {
+    encode_to_vec((config.wire_id(), val), config)
+}
+
 impl<Context, T> Decode<Context> for BinaryHeap<T>
 where
     T: Decode<Context> + Ord,
@@ -93,13 +142,39 @@ where
     T: Encode + Ord,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        // BLOCKEDTODO(https://github.com/rust-lang/rust/issues/83659): we can u8 optimize this with `.as_slice()`
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for val in self {
-            val.encode(encoder)?;
+        // `as_slice` has the same order as `iter`, and lets `[T]` write `u8` heaps in one go
+        self.as_slice().encode(encoder)
+    }
+}
+
+/// Decodes the entries of a `BTreeMap` or `BTreeSet` with `next`.
+///
+/// As long as the keys are strictly ascending, like a `BTreeMap` or `BTreeSet` encodes them, the entries are collected into a `Vec` and built into the tree at once, in O(n) instead of O(n log n). At the first key that is not greater than the key before it, this fails with `DecodeError::NonCanonicalMapOrder` if the config of `D` has a strict map order. Otherwise the entries are inserted one by one from there on, like `insert` does.
+fn decode_sorted<D: Decoder, T, K: Ord, B: FromIterator<T> + Extend<T>>(
+    capacity: usize,
+    key: fn(&T) -> &K,
+    mut next: impl FnMut() -> Result<Option<T>, DecodeError>,
+) -> Result<B, DecodeError> {
+    let mut sorted = Vec::new();
+    crate::de::reserve::reserve::<D, _>(&mut sorted, capacity)?;
+    while let Some(entry) = next()? {
+        if sorted.last().is_some_and(|last| key(last) >= key(&entry)) {
+            if D::C::MAP_ORDER == MapOrder::Strict {
+                return Err(DecodeError::NonCanonicalMapOrder {
+                    index: sorted.len(),
+                });
+            }
+            let mut collection: B = sorted.into_iter().collect();
+            collection.extend([entry]);
+            while let Some(entry) = next()? {
+                collection.extend([entry]);
+            }
+            return Ok(collection);
         }
-        Ok(())
+        crate::de::reserve::reserve_entry::<D, _>(&mut sorted)?;
+        sorted.push(entry);
     }
+    Ok(sorted.into_iter().collect())
 }
 
 impl<Context, K, V> Decode<Context> for BTreeMap<K, V>
@@ -108,19 +183,9 @@ where
     V: Decode<Context>,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<(K, V)>(len)?;
-
-        let mut map = Self::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let key = K::decode(decoder)?;
-            let value = V::decode(decoder)?;
-            map.insert(key, value);
-        }
-        Ok(map)
+        let mut entries = CollectionDecoder::<_, (K, V)>::new(decoder)?;
+        let capacity = entries.initial_capacity();
+        decode_sorted::<D, _, _, _>(capacity, |entry: &(K, V)| &entry.0, || entries.next_entry())
     }
 }
 impl<'de, K, V, Context> BorrowDecode<'de, Context> for BTreeMap<K, V>
@@ -131,19 +196,13 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<(K, V)>(len)?;
-
-        let mut map = Self::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let key = K::borrow_decode(decoder)?;
-            let value = V::borrow_decode(decoder)?;
-            map.insert(key, value);
-        }
-        Ok(map)
+        let mut entries = CollectionDecoder::<_, (K, V)>::new_borrowed(decoder)?;
+        let capacity = entries.initial_capacity();
+        decode_sorted::<D, _, _, _>(
+            capacity,
+            |entry: &(K, V)| &entry.0,
+            || entries.next_borrowed_entry(),
+        )
     }
 }
 
@@ -153,12 +212,7 @@ where
     V: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for (key, val) in self {
-            key.encode(encoder)?;
-            val.encode(encoder)?;
-        }
-        Ok(())
+        crate::enc::encode_collection(encoder, self.len(), self)
     }
 }
 
@@ -167,18 +221,9 @@ where
     T: Decode<Context> + Ord,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<T>(len)?;
-
-        let mut map = Self::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.insert(key);
-        }
-        Ok(map)
+        let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+        let capacity = entries.initial_capacity();
+        decode_sorted::<D, _, _, _>(capacity, |key: &T| key, || entries.next_entry())
     }
 }
 impl<'de, T, Context> BorrowDecode<'de, Context> for BTreeSet<T>
@@ -188,18 +233,9 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<T>(len)?;
-
-        let mut map = Self::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::borrow_decode(decoder)?;
-            map.insert(key);
-        }
-        Ok(map)
+        let mut entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        let capacity = entries.initial_capacity();
+        decode_sorted::<D, _, _, _>(capacity, |key: &T| key, || entries.next_borrowed_entry())
     }
 }
 
@@ -208,11 +244,77 @@ where
     T: Encode + Ord,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for item in self {
-            item.encode(encoder)?;
+        crate::enc::encode_collection(encoder, self.len(), self)
+    }
+}
+
+/// Adds the entries of a `BTreeMap` or `BTreeSet` to a tree with `insert`, which returns whether it replaced an entry with the same key.
+///
+/// With a strict map order, every key must be greater than the key before it, like [`decode_sorted`] checks. The keys of the tree itself are not checked.
+fn extend_sorted<D: Decoder, T, K: Ord>(
+    key: fn(&T) -> &K,
+    mut next: impl FnMut() -> Result<Option<T>, DecodeError>,
+    mut insert: impl FnMut(T) -> bool,
+) -> Result<Extended, DecodeError> {
+    let mut extended = Extended::default();
+    if D::C::MAP_ORDER != MapOrder::Strict {
+        while let Some(entry) = next()? {
+            extended.add(insert(entry));
         }
-        Ok(())
+        return Ok(extended);
+    }
+
+    // every entry is held back until the key of the next one is checked
+    let mut previous: Option<T> = None;
+    let mut index = 0;
+    while let Some(entry) = next()? {
+        if let Some(previous) = previous.take() {
+            if key(&previous) >= key(&entry) {
+                return Err(DecodeError::NonCanonicalMapOrder { index });
+            }
+            extended.add(insert(previous));
+        }
+        previous = Some(entry);
+        index += 1;
+    }
+    if let Some(previous) = previous {
+        extended.add(insert(previous));
+    }
+    Ok(extended)
+}
+
+impl<Context, K, V> DecodeExtend<Context> for BTreeMap<K, V>
+where
+    K: Decode<Context> + Ord,
+    V: Decode<Context>,
+{
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let mut entries = CollectionDecoder::<_, (K, V)>::new(decoder)?;
+        extend_sorted::<D, _, _>(
+            |entry: &(K, V)| &entry.0,
+            || entries.next_entry(),
+            |(k, v)| self.insert(k, v).is_some(),
+        )
+    }
+}
+
+impl<Context, T> DecodeExtend<Context> for BTreeSet<T>
+where
+    T: Decode<Context> + Ord,
+{
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+        extend_sorted::<D, _, _>(
+            |key: &T| key,
+            || entries.next_entry(),
+            |key| self.replace(key).is_some(),
+        )
     }
 }
 
@@ -241,18 +343,11 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
-        if unty::type_equal::<T, u8>() {
-            let slices: (&[T], &[T]) = self.as_slices();
-            // Safety: T is u8 so turning this into `&[u8]` is okay
-            let slices: (&[u8], &[u8]) = unsafe {
-                (
-                    core::slice::from_raw_parts(slices.0.as_ptr().cast(), slices.0.len()),
-                    core::slice::from_raw_parts(slices.1.as_ptr().cast(), slices.1.len()),
-                )
-            };
-
-            encoder.writer().write(slices.0)?;
-            encoder.writer().write(slices.1)?;
+        let (front, back) = self.as_slices();
+        if crate::plain::encode_slice(encoder, front)? {
+            if !back.is_empty() {
+                crate::plain::encode_slice(encoder, back)?;
+            }
         } else {
             for item in self {
                 item.encode(encoder)?;
@@ -267,26 +362,185 @@ where
     T: Decode<Context>,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-
-        decoder.claim_container_read::<T>(len)?;
-        if unty::type_equal::<T, u8>() {
-            // optimize for reading u8 vecs
-            let mut vec = alloc::vec![0u8; len];
-            decoder.reader().read(&mut vec)?;
-            // Safety: Vec<T> is Vec<u8>
-            Ok(unsafe { core::mem::transmute::<Vec<u8>, Self>(vec) })
-        } else {
-            let mut vec = Self::with_capacity(len);
-            for _ in 0..len {
-                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+        let entries = CollectionDecoder::<_, T>::new(decoder)?;
+        collect_vec(entries, CollectionDecoder::next_entry)
+    }
+}
+
+/// Collects the entries of `entries` in a `Vec`, decoding every entry with `next`.
+fn collect_vec<'a, D: Decoder, T>(
+    mut entries: CollectionDecoder<'a, D, T>,
+    mut next: impl FnMut(&mut CollectionDecoder<'a, D, T>) -> Result<Option<T>, DecodeError>,
+) -> Result<Vec<T>, DecodeError> {
+    if let Some(vec) = crate::plain::decode_vec(&mut entries)? {
+        // optimize for reading vecs of primitives
+        return Ok(vec);
+    }
+
+    let mut vec = Vec::new();
+    let capacity = entries.initial_capacity();
+    crate::de::reserve::reserve::<D, _>(&mut vec, capacity)?;
+    while let Some(entry) = next(&mut entries)? {
+        crate::de::reserve::reserve_entry::<D, _>(&mut vec)?;
+        vec.push(entry);
+    }
+    Ok(vec)
+}
+
+/// A pointer to a slice that can be allocated before its elements are decoded, so they are decoded into it directly instead of being collected in a `Vec` and copied.
+trait UninitSlice<T>: From<Vec<T>> {
+    /// The same pointer to a slice of elements that may not be initialized yet
+    type Uninit;
+
+    /// Allocates room for `len` elements
+    fn new_uninit(len: usize) -> Self::Uninit;
+
+    /// The elements of a pointer that was just allocated with `new_uninit`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the allocation is shared, which a new allocation never is.
+    fn uninit_mut(uninit: &mut Self::Uninit) -> Result<&mut [MaybeUninit<T>], DecodeError>;
+
+    /// # Safety
+    ///
+    /// Every element of `uninit` must be initialized.
+    unsafe fn assume_init(uninit: Self::Uninit) -> Self;
+}
+
+impl<T> UninitSlice<T> for Box<[T]> {
+    type Uninit = Box<[MaybeUninit<T>]>;
+
+    fn new_uninit(len: usize) -> Self::Uninit {
+        Self::new_uninit_slice(len)
+    }
+
+    fn uninit_mut(uninit: &mut Self::Uninit) -> Result<&mut [MaybeUninit<T>], DecodeError> {
+        Ok(uninit)
+    }
+
+    unsafe fn assume_init(uninit: Self::Uninit) -> Self {
+        // Safety: upheld by the caller
+        unsafe { uninit.assume_init() }
+    }
+}
+
+impl<T> UninitSlice<T> for Rc<[T]> {
+    type Uninit = Rc<[MaybeUninit<T>]>;
+
+    fn new_uninit(len: usize) -> Self::Uninit {
+        Self::new_uninit_slice(len)
+    }
+
+    fn uninit_mut(uninit: &mut Self::Uninit) -> Result<&mut [MaybeUninit<T>], DecodeError> {
+        Rc::get_mut(uninit).ok_or(DecodeError::Other("a new Rc is shared"))
+    }
+
+    unsafe fn assume_init(uninit: Self::Uninit) -> Self {
+        // Safety: upheld by the caller
+        unsafe { uninit.assume_init() }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> UninitSlice<T> for Arc<[T]> {
+    type Uninit = Arc<[MaybeUninit<T>]>;
+
+    fn new_uninit(len: usize) -> Self::Uninit {
+        Self::new_uninit_slice(len)
+    }
+
+    fn uninit_mut(uninit: &mut Self::Uninit) -> Result<&mut [MaybeUninit<T>], DecodeError> {
+        Arc::get_mut(uninit).ok_or(DecodeError::Other("a new Arc is shared"))
+    }
+
+    unsafe fn assume_init(uninit: Self::Uninit) -> Self {
+        // Safety: upheld by the caller
+        unsafe { uninit.assume_init() }
+    }
+}
+
+/// Decodes the entries of `entries` into a single allocation of exactly their length, decoding every entry with `next`.
+///
+/// The slice is only allocated up front if all entries could fit in the input that is left, like [`CollectionDecoder::initial_capacity`] allows, and allocation is infallible. Otherwise the entries are collected in a `Vec` that grows as they arrive, and converted afterwards.
+fn collect_slice<'a, D: Decoder, T, S: UninitSlice<T>>(
+    mut entries: CollectionDecoder<'a, D, T>,
+    mut next: impl FnMut(&mut CollectionDecoder<'a, D, T>) -> Result<Option<T>, DecodeError>,
+) -> Result<S, DecodeError> {
+    /// Drops the elements that were decoded if a later one fails.
+    struct Initialized<'s, T> {
+        slice: &'s mut [MaybeUninit<T>],
+        len: usize,
+    }
 
-                vec.push(T::decode(decoder)?);
+    impl<T> Drop for Initialized<'_, T> {
+        fn drop(&mut self) {
+            // Safety: the first `len` elements are initialized
+            unsafe {
+                core::ptr::drop_in_place(
+                    core::ptr::from_mut(&mut self.slice[..self.len]) as *mut [T]
+                );
             }
-            Ok(vec)
         }
     }
+
+    let len = entries.len();
+    if D::C::ALLOCATION == Allocation::Fallible || entries.initial_capacity() < len {
+        return collect_vec(entries, next).map(S::from);
+    }
+
+    let mut uninit = S::new_uninit(len);
+    let slice = S::uninit_mut(&mut uninit)?;
+    if !crate::plain::decode_uninit(&mut entries, slice)? {
+        let mut initialized = Initialized { slice, len: 0 };
+        // `next` returns exactly `len` entries
+        while let Some(entry) = next(&mut entries)? {
+            initialized.slice[initialized.len].write(entry);
+            initialized.len += 1;
+        }
+        core::mem::forget(initialized);
+    }
+    // Safety: every element was initialized by either `decode_uninit` or `next`
+    Ok(unsafe { S::assume_init(uninit) })
+}
+
+impl DecodeRemaining for Vec<u8> {
+    fn decode_remaining<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.take_remaining_bytes()
+    }
+}
+
+impl<'de> BorrowDecodeRemaining<'de> for Vec<u8> {
+    fn borrow_decode_remaining<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        decoder.take_remaining_bytes()
+    }
+}
+
+impl DecodeFixedLen for Vec<u8> {
+    fn decode_fixed_len<D: Decoder>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        decoder.claim_bytes_read(len)?;
+        let mut bytes = alloc::vec![0; len];
+        decoder.reader().read(&mut bytes)?;
+        let trimmed = padding.trim(&bytes).len();
+        bytes.truncate(trimmed);
+        Ok(bytes)
+    }
+}
+
+impl<'de> BorrowDecodeFixedLen<'de> for Vec<u8> {
+    fn borrow_decode_fixed_len<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        Self::decode_fixed_len(decoder, len, padding)
+    }
 }
 
 impl<'de, T, Context> BorrowDecode<'de, Context> for Vec<T>
@@ -296,25 +550,8 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-
-        decoder.claim_container_read::<T>(len)?;
-        if unty::type_equal::<T, u8>() {
-            // optimize for reading u8 vecs
-            let mut vec = alloc::vec![0u8; len];
-            decoder.reader().read(&mut vec)?;
-            // Safety: Vec<T> is Vec<u8>
-            Ok(unsafe { core::mem::transmute::<Vec<u8>, Self>(vec) })
-        } else {
-            let mut vec = Self::with_capacity(len);
-            for _ in 0..len {
-                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-                vec.push(T::borrow_decode(decoder)?);
-            }
-            Ok(vec)
-        }
+        let entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        collect_vec(entries, CollectionDecoder::next_borrowed_entry)
     }
 }
 
@@ -323,27 +560,97 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        if unty::type_equal::<T, u8>() {
-            // Safety: T == u8
-            let slice: &[u8] =
-                unsafe { &*(core::ptr::from_ref::<[T]>(self.as_slice()) as *const [u8]) };
-            encoder.writer().write(slice)?;
+        self.as_slice().encode(encoder)
+    }
+}
+
+impl<Context, T> DecodeExtend<Context> for Vec<T>
+where
+    T: Decode<Context>,
+{
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+        let inserted = entries.len();
+        if let Some(mut vec) = crate::plain::decode_vec(&mut entries)? {
+            crate::de::reserve::reserve::<D, _>(self, vec.len())?;
+            self.append(&mut vec);
         } else {
-            for item in self {
-                item.encode(encoder)?;
+            let capacity = entries.initial_capacity();
+            crate::de::reserve::reserve::<D, _>(self, capacity)?;
+            while let Some(entry) = entries.next_entry()? {
+                crate::de::reserve::reserve_entry::<D, _>(self)?;
+                self.push(entry);
             }
         }
-        Ok(())
+        Ok(Extended {
+            inserted,
+            overwritten: 0,
+        })
+    }
+}
+
+impl<Context> DecodeExtend<Context> for String {
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let string = <Self as Decode<Context>>::decode(decoder)?;
+        let inserted = string.len();
+        if self.is_empty() {
+            *self = string;
+        } else {
+            // Safety: the bytes of `string` are valid UTF-8, so `self` stays valid UTF-8
+            let bytes = unsafe { self.as_mut_vec() };
+            crate::de::reserve::reserve::<D, _>(bytes, inserted)?;
+            bytes.extend_from_slice(string.as_bytes());
+        }
+        Ok(Extended {
+            inserted,
+            overwritten: 0,
+        })
     }
 }
 
 impl<Context> Decode<Context> for String {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let bytes = Vec::<u8>::decode(decoder)?;
-        Self::from_utf8(bytes).map_err(|e| DecodeError::Utf8 {
-            inner: e.utf8_error(),
-        })
+        string_from_utf8::<D>(bytes)
+    }
+}
+
+/// Converts decoded bytes to a `String`, replacing invalid UTF-8 if the config decodes strings lossily.
+fn string_from_utf8<D: Decoder>(bytes: Vec<u8>) -> Result<String, DecodeError> {
+    match crate::de::str_from_utf8(&bytes) {
+        // Safety: the bytes were validated as UTF-8
+        Ok(_) => Ok(unsafe { String::from_utf8_unchecked(bytes) }),
+        Err(e) => match D::C::UTF8_DECODING {
+            Utf8Decoding::Strict => Err(e),
+            Utf8Decoding::Lossy => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        },
+    }
+}
+
+impl DecodeFixedLen for String {
+    fn decode_fixed_len<D: Decoder>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        let bytes = Vec::<u8>::decode_fixed_len(decoder, len, padding)?;
+        string_from_utf8::<D>(bytes)
+    }
+}
+
+impl<'de> BorrowDecodeFixedLen<'de> for String {
+    fn borrow_decode_fixed_len<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+        len: usize,
+        padding: FixedLenPadding,
+    ) -> Result<Self, DecodeError> {
+        Self::decode_fixed_len(decoder, len, padding)
     }
 }
 impl_borrow_decode!(String);
@@ -365,23 +672,61 @@ impl<Context, T> Decode<Context> for Box<T>
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
-        Ok(Self::new(t))
+        decode_boxed(decoder, T::decode_in_place)
     }
 }
 impl<'de, T, Context> BorrowDecode<'de, Context> for Box<T>
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let t = T::borrow_decode(decoder)?;
-        Ok(Self::new(t))
+        decode_boxed(decoder, T::borrow_decode_in_place)
     }
 }
 
+/// Allocates room for a `T`, and decodes it into the allocation with `decode_in_place`, so a large `T` is never on the stack.
+fn decode_boxed<D, T>(
+    decoder: &mut D,
+    decode_in_place: impl FnOnce(&mut D, &mut MaybeUninit<T>, Private) -> Result<(), DecodeError>,
+) -> Result<Box<T>, DecodeError> {
+    let mut boxed = Box::<T>::new_uninit();
+    decode_in_place(decoder, &mut boxed, Private)?;
+    // Safety: `decode_in_place` initialized the value, because it returned `Ok`. Only this crate can override it, and its implementations do so
+    Ok(unsafe { boxed.assume_init() })
+}
+
+/// Like [`decode_boxed`], but for an `Rc<T>`, which is decoded into its own allocation instead of being copied from a `Box<T>`.
+fn decode_rc<D, T>(
+    decoder: &mut D,
+    decode_in_place: impl FnOnce(&mut D, &mut MaybeUninit<T>, Private) -> Result<(), DecodeError>,
+) -> Result<Rc<T>, DecodeError> {
+    let mut rc = Rc::<T>::new_uninit();
+    let uninit = Rc::get_mut(&mut rc).ok_or(DecodeError::Other("a new Rc is shared"))?;
+    decode_in_place(decoder, uninit, Private)?;
+    // Safety: `decode_in_place` initialized the value, because it returned `Ok`. Only this crate can override it, and its implementations do so
+    Ok(unsafe { rc.assume_init() })
+}
+
+/// Like [`decode_boxed`], but for an `Arc<T>`, which is decoded into its own allocation instead of being copied from a `Box<T>`.
+#[cfg(target_has_atomic = "ptr")]
+fn decode_arc<D, T>(
+    decoder: &mut D,
+    decode_in_place: impl FnOnce(&mut D, &mut MaybeUninit<T>, Private) -> Result<(), DecodeError>,
+) -> Result<Arc<T>, DecodeError> {
+    let mut arc = Arc::<T>::new_uninit();
+    let uninit = Arc::get_mut(&mut arc).ok_or(DecodeError::Other("a new Arc is shared"))?;
+    decode_in_place(decoder, uninit, Private)?;
+    // Safety: `decode_in_place` initialized the value, because it returned `Ok`. Only this crate can override it, and its implementations do so
+    Ok(unsafe { arc.assume_init() })
+}
+
 impl<T> Encode for Box<T>
 where
     T: Encode + ?Sized,
@@ -396,8 +741,8 @@ where
     T: Decode<Context> + 'static,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let vec = Vec::decode(decoder)?;
-        Ok(vec.into_boxed_slice())
+        let entries = CollectionDecoder::<_, T>::new(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_entry)
     }
 }
 
@@ -408,8 +753,8 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let vec = Vec::borrow_decode(decoder)?;
-        Ok(vec.into_boxed_slice())
+        let entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_borrowed_entry)
     }
 }
 
@@ -463,16 +808,24 @@ impl<Context, T> Decode<Context> for Rc<T>
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
-        Ok(Self::new(t))
+        decode_rc(decoder, T::decode_in_place)
     }
 }
 
 impl<Context> Decode<Context> for Rc<str> {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let string = String::decode(decoder)?;
-        Ok(string.into())
+        let bytes = Rc::<[u8]>::decode(decoder)?;
+        match crate::de::str_from_utf8(&bytes) {
+            // Safety: the bytes were validated as UTF-8, and `str` has the same layout as `[u8]`
+            Ok(_) => Ok(unsafe { Self::from_raw(Rc::into_raw(bytes) as *const str) }),
+            Err(e) => match D::C::UTF8_DECODING {
+                Utf8Decoding::Strict => Err(e),
+                Utf8Decoding::Lossy => Ok(String::from_utf8_lossy(&bytes).into()),
+            },
+        }
     }
 }
 
@@ -480,11 +833,12 @@ impl<'de, T, Context> BorrowDecode<'de, Context> for Rc<T>
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let t = T::borrow_decode(decoder)?;
-        Ok(Self::new(t))
+        decode_rc(decoder, T::borrow_decode_in_place)
     }
 }
 
@@ -492,8 +846,7 @@ impl<'de, Context> BorrowDecode<'de, Context> for Rc<str> {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let string = String::decode(decoder)?;
-        Ok(string.into())
+        Self::decode(decoder)
     }
 }
 
@@ -511,8 +864,8 @@ where
     T: Decode<Context> + 'static,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let vec = Vec::decode(decoder)?;
-        Ok(vec.into())
+        let entries = CollectionDecoder::<_, T>::new(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_entry)
     }
 }
 
@@ -523,8 +876,8 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let vec = Vec::borrow_decode(decoder)?;
-        Ok(vec.into())
+        let entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_borrowed_entry)
     }
 }
 
@@ -533,17 +886,25 @@ impl<Context, T> Decode<Context> for Arc<T>
 where
     T: Decode<Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
-        Ok(Self::new(t))
+        decode_arc(decoder, T::decode_in_place)
     }
 }
 
 #[cfg(target_has_atomic = "ptr")]
 impl<Context> Decode<Context> for Arc<str> {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let string = String::decode(decoder)?;
-        Ok(string.into())
+        let bytes = Arc::<[u8]>::decode(decoder)?;
+        match crate::de::str_from_utf8(&bytes) {
+            // Safety: the bytes were validated as UTF-8, and `str` has the same layout as `[u8]`
+            Ok(_) => Ok(unsafe { Self::from_raw(Arc::into_raw(bytes) as *const str) }),
+            Err(e) => match D::C::UTF8_DECODING {
+                Utf8Decoding::Strict => Err(e),
+                Utf8Decoding::Lossy => Ok(String::from_utf8_lossy(&bytes).into()),
+            },
+        }
     }
 }
 
@@ -552,11 +913,12 @@ impl<'de, T, Context> BorrowDecode<'de, Context> for Arc<T>
 where
     T: BorrowDecode<'de, Context>,
 {
+    const MIN_ENCODED_SIZE: usize = T::MIN_ENCODED_SIZE;
+
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let t = T::borrow_decode(decoder)?;
-        Ok(Self::new(t))
+        decode_arc(decoder, T::borrow_decode_in_place)
     }
 }
 
@@ -565,8 +927,7 @@ impl<'de, Context> BorrowDecode<'de, Context> for Arc<str> {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let string = String::decode(decoder)?;
-        Ok(string.into())
+        Self::decode(decoder)
     }
 }
 
@@ -586,8 +947,8 @@ where
     T: Decode<Context> + 'static,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let vec = Vec::decode(decoder)?;
-        Ok(vec.into())
+        let entries = CollectionDecoder::<_, T>::new(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_entry)
     }
 }
 
@@ -599,7 +960,78 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let vec = Vec::borrow_decode(decoder)?;
-        Ok(vec.into())
+        let entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        collect_slice(entries, CollectionDecoder::next_borrowed_entry)
+    }
+}
+
+impl SkipDecode for String {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        str::skip(decoder)
+    }
+}
+
+macro_rules! impl_skip_sequence {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<T: SkipDecode> SkipDecode for $ty<T> {
+                fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+                    <[T]>::skip(decoder)
+                }
+            }
+        )*
+    };
+}
+
+impl_skip_sequence!(Vec, VecDeque, BinaryHeap, BTreeSet);
+
+impl<K: SkipDecode, V: SkipDecode> SkipDecode for BTreeMap<K, V> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[(K, V)]>::skip(decoder)
+    }
+}
+
+impl<T: SkipDecode + ?Sized> SkipDecode for Box<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode + ?Sized> SkipDecode for Rc<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: SkipDecode + ?Sized> SkipDecode for Arc<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode + ToOwned + ?Sized> SkipDecode for Cow<'_, T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
     }
 }
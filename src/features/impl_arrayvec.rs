@@ -0,0 +1,90 @@
+//! `Encode` and `Decode` implementations for [`arrayvec::ArrayVec`](https://docs.rs/arrayvec) and `ArrayString`.
+//!
+//! `ArrayVec<T, CAP>` is encoded exactly like a `Vec<T>`, and `ArrayString<CAP>` exactly like a `String`.
+//! If the encoded length exceeds `CAP`, decoding fails with [`DecodeError::ArrayCapacityExceeded`\] before any element is read.
+
+use crate::{
+    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use arrayvec::{ArrayString, ArrayVec};
+
+impl<T, const CAP: usize> Encode for ArrayVec<T, CAP>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_slice().encode(encoder)
+    }
+}
+
+impl<Context, T, const CAP: usize> Decode<Context> for ArrayVec<T, CAP>
+where
+    T: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, CAP>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+
+            // This can not panic, `len` is checked against `CAP` above
+            vec.push(T::decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, Context, T, const CAP: usize> BorrowDecode<'de, Context> for ArrayVec<T, CAP>
+where
+    T: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, CAP>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+
+            // This can not panic, `len` is checked against `CAP` above
+            vec.push(T::borrow_decode(decoder)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<const CAP: usize> Encode for ArrayString<CAP> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_str().encode(encoder)
+    }
+}
+
+impl<Context, const CAP: usize> Decode<Context> for ArrayString<CAP> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, CAP>(decoder)?;
+        decoder.claim_container_read::<u8>(len)?;
+
+        let mut buffer = [0u8; CAP];
+        let bytes = &mut buffer[..len];
+        decoder.reader().read(bytes)?;
+        let str = crate::de::str_from_utf8(bytes)?;
+        // `str` is at most `CAP` bytes long, so this can not fail
+        Self::from(str).map_err(|_| DecodeError::ArrayCapacityExceeded { len, cap: CAP })
+    }
+}
+
+impl<'de, Context, const CAP: usize> BorrowDecode<'de, Context> for ArrayString<CAP> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
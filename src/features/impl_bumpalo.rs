@@ -0,0 +1,94 @@
+//! `Decode` implementations that allocate in a [`bumpalo`](https://docs.rs/bumpalo) arena, which is passed as the decode context.
+//!
+//! With a context of `&'bump Bump`, `&'bump str` and `&'bump [T]` are decoded into the arena. They live as long as the arena, not as long as the input, so the input can be dropped while the values are still in use. Types that hold them derive `Decode` with `#[bincode(decode_context = "&'bump Bump")]`, and are decoded with [`decode_from_slice_in`\] or any of the other `_with_context` functions.
+//!
+//! The encoding is the same as that of `String` and `Vec<T>`.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bumpalo::Bump;
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! #[bincode(decode_context = "&'bump Bump")]
+//! struct Entry<'bump> {
+//!     key: &'bump str,
+//!     values: &'bump [u32],
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let bump = Bump::new();
+//! let entry = {
+//!     let input = bincode::encode_to_vec(("answer", [42u32].as_slice()), config).unwrap();
+//!     let (entry, _): (Entry, _) = bincode::decode_from_slice_in(&input, config, &bump).unwrap();
+//!     entry
+//! };
+//! assert_eq!((entry.key, entry.values), ("answer", &[42][..]));
+//! ```
+
+use crate::{
+    config::{Config, InternalUtf8Config, Utf8Decoding},
+    de::{read::Reader, CollectionDecoder, Decode, Decoder},
+    error::DecodeError,
+};
+use bumpalo::{
+    collections::{String as BumpString, Vec as BumpVec},
+    Bump,
+};
+
+/// Attempt to decode a given type `D` from the given slice, allocating in `bump`. Returns the decoded output and the amount of bytes read.
+///
+/// This is [`decode_from_slice_with_context`](crate::decode_from_slice_with_context) with `bump` as the context. See the [module documentation](self) for the types that allocate in it.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "bumpalo")))]
+pub fn decode_from_slice_in<'bump, D: Decode<&'bump Bump>, C: Config>(
+    src: &[u8],
+    config: C,
+    bump: &'bump Bump,
+) -> Result<(D, usize), DecodeError> {
+    crate::decode_from_slice_with_context(src, config, bump)
+}
+
+impl<'bump> Decode<&'bump Bump> for &'bump str {
+    fn decode<D: Decoder<Context = &'bump Bump>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let bump = *decoder.context();
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_bytes_read(len)?;
+
+        // the length comes from the input, so the bytes are allocated as they are read
+        let mut bytes = BumpVec::new_in(bump);
+        while bytes.len() < len {
+            let start = bytes.len();
+            let chunk = crate::de::preallocation_len::<u8, _>(decoder, len - start).max(1);
+            bytes.resize(start + chunk, 0);
+            decoder.reader().read(&mut bytes[start..])?;
+        }
+
+        match BumpString::from_utf8(bytes) {
+            Ok(string) => Ok(string.into_bump_str()),
+            Err(e) => match D::C::UTF8_DECODING {
+                Utf8Decoding::Strict => Err(DecodeError::utf8(e.as_bytes(), e.utf8_error())),
+                Utf8Decoding::Lossy => {
+                    Ok(BumpString::from_utf8_lossy_in(e.as_bytes(), bump).into_bump_str())
+                }
+            },
+        }
+    }
+}
+
+impl<'bump, T> Decode<&'bump Bump> for &'bump [T]
+where
+    T: Decode<&'bump Bump>,
+{
+    fn decode<D: Decoder<Context = &'bump Bump>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let bump = *decoder.context();
+        let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+        let mut vec = BumpVec::with_capacity_in(entries.initial_capacity(), bump);
+        while let Some(entry) = entries.next_entry()? {
+            vec.push(entry);
+        }
+        Ok(vec.into_bump_slice())
+    }
+}
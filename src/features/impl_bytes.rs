@@ -0,0 +1,186 @@
+//! `Encode` and `Decode` implementations for the [`bytes`](https://docs.rs/bytes) crate.
+//!
+//! `Bytes` and `BytesMut` are encoded exactly like a `Vec<u8>`: a length prefix followed by the raw bytes.
+//!
+//! When decoding with a [`BytesReader`\] (e.g. through [`decode_from_bytes`\]), every `Bytes` value that is
+//! decoded will be a cheap slice of the input buffer instead of a copy.
+
+use crate::{
+    config::Config,
+    de::{
+        read::Reader, report_offset, BorrowDecodeRemaining, BorrowDecoder, Decode, DecodeRemaining,
+        Decoder, DecoderImpl,
+    },
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode,
+};
+use bytes::{Buf, Bytes, BytesMut};
+
+/// Attempt to decode a given type `D` from the given `Bytes`. Returns the decoded output and the amount of bytes read.
+///
+/// Any `Bytes` that are decoded will share the allocation of `src` instead of being copied.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if `src` is too small or the data is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub fn decode_from_bytes<D: Decode<()>, C: Config>(
+    src: Bytes,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    decode_from_bytes_with_context(src, config, ())
+}
+
+/// Attempt to decode a given type `D` from the given `Bytes` with `Context`. Returns the decoded output and the amount of bytes read.
+///
+/// Any `Bytes` that are decoded will share the allocation of `src` instead of being copied.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if `src` is too small or the data is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub fn decode_from_bytes_with_context<Context, D: Decode<Context>, C: Config>(
+    src: Bytes,
+    config: C,
+    context: Context,
+) -> Result<(D, usize), DecodeError> {
+    let len = src.len();
+    let reader = BytesReader::new(src);
+    let mut decoder = DecoderImpl::<_, C, Context>::new(reader, config, context);
+    let result = D::decode(&mut decoder);
+    let bytes_read = len - decoder.reader().bytes.len();
+    result
+        .map(|result| (result, bytes_read))
+        .map_err(|e| report_offset::<C>(e, bytes_read))
+}
+
+/// A reader for `Bytes`. Decoding a `Bytes` value from this reader returns a slice of the input, without copying.
+pub struct BytesReader {
+    bytes: Bytes,
+}
+
+impl BytesReader {
+    /// Create a new `BytesReader` from the given `Bytes`.
+    #[must_use]
+    pub const fn new(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the bytes that have not been read yet.
+    #[must_use]
+    pub fn into_inner(self) -> Bytes {
+        self.bytes
+    }
+}
+
+impl Reader for BytesReader {
+    #[inline(always)]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        if bytes.len() > self.bytes.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: bytes.len() - self.bytes.len(),
+            });
+        }
+        self.bytes.copy_to_slice(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.bytes.get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.bytes.advance(n.min(self.bytes.len()));
+    }
+
+    #[inline]
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        if n > self.bytes.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - self.bytes.len(),
+            });
+        }
+        self.bytes.advance(n);
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(self.bytes.len())
+    }
+
+    #[inline]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<Bytes> {
+        (length <= self.bytes.len()).then(|| self.bytes.split_to(length))
+    }
+}
+
+impl Encode for Bytes {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.encode_byte_slice(self)
+    }
+}
+
+impl<Context> Decode<Context> for Bytes {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_container_read::<u8>(len)?;
+        if let Some(bytes) = decoder.reader().take_shared_bytes(len) {
+            return Ok(bytes);
+        }
+        crate::plain::decode_byte_vec(decoder, len).map(Self::from)
+    }
+}
+impl_borrow_decode!(Bytes);
+
+impl DecodeRemaining for Bytes {
+    fn decode_remaining<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::claim_remaining_bytes(decoder)?;
+        if let Some(bytes) = decoder.reader().take_shared_bytes(len) {
+            return Ok(bytes);
+        }
+        crate::plain::decode_byte_vec(decoder, len).map(Self::from)
+    }
+}
+
+impl<'de> BorrowDecodeRemaining<'de> for Bytes {
+    fn borrow_decode_remaining<D: BorrowDecoder<'de>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode_remaining(decoder)
+    }
+}
+
+impl Encode for BytesMut {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.encode_byte_slice(self)
+    }
+}
+
+impl<Context> Decode<Context> for BytesMut {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_container_read::<u8>(len)?;
+        let mut bytes = Self::new();
+        for range in crate::plain::chunks(decoder, len, 1) {
+            bytes.resize(range.end, 0);
+            decoder
+                .reader()
+                .read(&mut bytes[range.start..])
+                .map_err(|e| crate::plain::with_more_needed(e, len - range.end))?;
+        }
+        Ok(bytes)
+    }
+}
+impl_borrow_decode!(BytesMut);
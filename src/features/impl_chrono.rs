@@ -0,0 +1,106 @@
+//! `Encode` and `Decode` implementations for the date and time types of the [`chrono`](https://docs.rs/chrono) crate.
+//!
+//! The types are encoded as follows:
+//!
+//! |Type|Encoding|
+//! |---|---|
+//! |`DateTime<Utc>`|`i64` seconds since the unix epoch, followed by a `u32` with the sub-second nanoseconds|
+//! |`NaiveDateTime`|Same as `DateTime<Utc>`, as if the value was in UTC|
+//! |`NaiveDate`|`i32` days since 1970-01-01|
+//! |`TimeDelta` (`chrono::Duration`)|`i64` seconds, followed by a `u32` with the nanoseconds, both normalized so that the nanoseconds are always positive|
+//!
+//! The nanoseconds of a `DateTime<Utc>` or `NaiveDateTime` can exceed `999_999_999` to represent a leap second, exactly like `chrono` does.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode,
+};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+
+/// The amount of days between 0001-01-01 (the epoch used by `NaiveDate::num_days_from_ce`) and 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+const NANOS_PER_SEC: i32 = 1_000_000_000;
+
+impl Encode for DateTime<Utc> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.timestamp().encode(encoder)?;
+        self.timestamp_subsec_nanos().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for DateTime<Utc> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let secs = i64::decode(decoder)?;
+        let nanos = u32::decode(decoder)?;
+        Self::from_timestamp(secs, nanos).ok_or_else(|| DecodeError::InvalidDateTime {
+            type_name: core::any::type_name::<Self>(),
+        })
+    }
+}
+impl_borrow_decode!(DateTime<Utc>);
+
+impl Encode for NaiveDateTime {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.and_utc().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for NaiveDateTime {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let secs = i64::decode(decoder)?;
+        let nanos = u32::decode(decoder)?;
+        DateTime::from_timestamp(secs, nanos)
+            .map(|date_time| date_time.naive_utc())
+            .ok_or_else(|| DecodeError::InvalidDateTime {
+                type_name: core::any::type_name::<Self>(),
+            })
+    }
+}
+impl_borrow_decode!(NaiveDateTime);
+
+impl Encode for NaiveDate {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        // `NaiveDate` is limited to roughly 262_000 years around the common era, so this can never overflow
+        (self.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE).encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for NaiveDate {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let days = i32::decode(decoder)?;
+        days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(Self::from_num_days_from_ce_opt)
+            .ok_or_else(|| DecodeError::InvalidDateTime {
+                type_name: core::any::type_name::<Self>(),
+            })
+    }
+}
+impl_borrow_decode!(NaiveDate);
+
+impl Encode for TimeDelta {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        // `subsec_nanos` has the same sign as `num_seconds`, normalize it so the nanoseconds are always positive.
+        // `TimeDelta` stores its value in this normalized form, so this can not overflow.
+        let mut secs = self.num_seconds();
+        let mut nanos = self.subsec_nanos();
+        if nanos < 0 {
+            secs -= 1;
+            nanos += NANOS_PER_SEC;
+        }
+        secs.encode(encoder)?;
+        nanos.unsigned_abs().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for TimeDelta {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let secs = i64::decode(decoder)?;
+        let nanos = u32::decode(decoder)?;
+        Self::new(secs, nanos).ok_or_else(|| DecodeError::InvalidDateTime {
+            type_name: core::any::type_name::<Self>(),
+        })
+    }
+}
+impl_borrow_decode!(TimeDelta);
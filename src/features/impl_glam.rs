@@ -0,0 +1,84 @@
+//! `Encode` and `Decode` implementations for the math types of the [`glam`](https://docs.rs/glam) crate.
+//!
+//! Every type is encoded as its components, exactly like the equivalent array (e.g. `[f32; 3]` for a `Vec3`), without a length prefix:
+//!
+//! |Type|Encoded as|Component order|
+//! |---|---|---|
+//! |`Vec2`, `Vec3`, `Vec3A`, `Vec4`|`[f32; N]`|`x, y, z, w`|
+//! |`IVec2`, `IVec3`, `IVec4`|`[i32; N]`|`x, y, z, w`|
+//! |`UVec2`, `UVec3`, `UVec4`|`[u32; N]`|`x, y, z, w`|
+//! |`Quat`|`[f32; 4]`|`x, y, z, w`|
+//! |`Mat2`, `Mat3`, `Mat4`|`[f32; N * N]`|column-major|
+//! |`Affine3A`|`[f32; 12]`|column-major, the 3x3 matrix followed by the translation|
+//!
+//! `Vec3A` is encoded as 3 components, the same as `Vec3`.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode,
+};
+use glam::{
+    Affine3A, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec3A,
+    Vec4,
+};
+
+macro_rules! impl_glam_vector {
+    ($($ty:ty => [$component:ty; $len:literal]),* $(,)?) => {
+        $(
+            impl Encode for $ty {
+                fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+                    self.to_array().encode(encoder)
+                }
+            }
+
+            impl<Context> Decode<Context> for $ty {
+                fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                    <[$component; $len]>::decode(decoder).map(Self::from_array)
+                }
+            }
+            impl_borrow_decode!($ty);
+        )*
+    };
+}
+
+macro_rules! impl_glam_matrix {
+    ($($ty:ty => [f32; $len:literal]),* $(,)?) => {
+        $(
+            impl Encode for $ty {
+                fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+                    self.to_cols_array().encode(encoder)
+                }
+            }
+
+            impl<Context> Decode<Context> for $ty {
+                fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                    <[f32; $len]>::decode(decoder).map(|cols| Self::from_cols_array(&cols))
+                }
+            }
+            impl_borrow_decode!($ty);
+        )*
+    };
+}
+
+impl_glam_vector!(
+    Vec2 => [f32; 2],
+    Vec3 => [f32; 3],
+    Vec3A => [f32; 3],
+    Vec4 => [f32; 4],
+    IVec2 => [i32; 2],
+    IVec3 => [i32; 3],
+    IVec4 => [i32; 4],
+    UVec2 => [u32; 2],
+    UVec3 => [u32; 3],
+    UVec4 => [u32; 4],
+    Quat => [f32; 4],
+);
+
+impl_glam_matrix!(
+    Mat2 => [f32; 4],
+    Mat3 => [f32; 9],
+    Mat4 => [f32; 16],
+    Affine3A => [f32; 12],
+);
@@ -0,0 +1,45 @@
+//! `Encode` and `Decode` implementations for the half-precision floats of the [`half`](https://docs.rs/half) crate.
+//!
+//! `f16` and `bf16` are encoded as the 2 bytes of their bit pattern in the endianness of the config, exactly like the `u16` from `to_bits` with fixed int encoding. Like `f32` and `f64`, they are never varint-encoded, and every bit pattern is decoded as-is, so the payload of a NaN is preserved.
+
+use crate::{
+    config::{Endianness, InternalEndianConfig},
+    de::{read::Reader, Decode, Decoder},
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode,
+};
+use half::{bf16, f16};
+
+macro_rules! impl_half_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+                    let bits = self.to_bits();
+                    match E::C::ENDIAN {
+                        Endianness::Big => encoder.writer().write(&bits.to_be_bytes()),
+                        Endianness::Little => encoder.writer().write(&bits.to_le_bytes()),
+                    }
+                }
+            }
+
+            impl<Context> Decode<Context> for $ty {
+                const MIN_ENCODED_SIZE: usize = 2;
+
+                fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                    decoder.claim_bytes_read(2)?;
+                    let mut bytes = [0u8; 2];
+                    decoder.reader().read(&mut bytes)?;
+                    Ok(Self::from_bits(match D::C::ENDIAN {
+                        Endianness::Little => u16::from_le_bytes(bytes),
+                        Endianness::Big => u16::from_be_bytes(bytes),
+                    }))
+                }
+            }
+            impl_borrow_decode!($ty);
+        )*
+    };
+}
+
+impl_half_float!(f16, bf16);
@@ -0,0 +1,178 @@
+//! `Encode` and `Decode` implementations for the fixed-capacity containers of the [`heapless`](https://docs.rs/heapless) crate.
+//!
+//! `Vec<T, N>` is encoded exactly like an `alloc::vec::Vec<T>`, `String<N>` exactly like an `alloc::string::String` and `IndexMap<K, V, S, N>` exactly like a `HashMap<K, V>`.
+//! If the encoded length exceeds `N`, decoding fails with [`DecodeError::ArrayCapacityExceeded`\] before any element is read.
+//!
+//! `heapless::Vec<u8, N>` also implements [`Writer`\], so it can be used with `encode_into_writer` without an allocator.
+
+use crate::{
+    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder},
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::hash::{BuildHasher, Hash};
+use heapless::{IndexMap, String, Vec};
+
+impl<const N: usize> Writer for Vec<u8, N> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes)
+            .map_err(|()| EncodeError::UnexpectedEnd)
+    }
+}
+
+impl<T, const N: usize> Encode for Vec<T, N>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_slice().encode(encoder)
+    }
+}
+
+impl<Context, T, const N: usize> Decode<Context> for Vec<T, N>
+where
+    T: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+
+            // `len` is checked against `N` above, so this can not fail
+            vec.push(T::decode(decoder)?)
+                .map_err(|_| DecodeError::ArrayCapacityExceeded { len, cap: N })?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, Context, T, const N: usize> BorrowDecode<'de, Context> for Vec<T, N>
+where
+    T: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(len, T::MIN_ENCODED_SIZE)?;
+
+        let mut vec = Self::new();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(T::MIN_ENCODED_SIZE);
+
+            // `len` is checked against `N` above, so this can not fail
+            vec.push(T::borrow_decode(decoder)?)
+                .map_err(|_| DecodeError::ArrayCapacityExceeded { len, cap: N })?;
+        }
+        Ok(vec)
+    }
+}
+
+impl<const N: usize> Encode for String<N> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_str().encode(encoder)
+    }
+}
+
+impl<Context, const N: usize> Decode<Context> for String<N> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_read::<u8>(len)?;
+
+        let mut buffer = [0u8; N];
+        let bytes = &mut buffer[..len];
+        decoder.reader().read(bytes)?;
+        let str = crate::de::str_from_utf8(bytes)?;
+        let mut string = Self::new();
+        // `str` is at most `N` bytes long, so this can not fail
+        string
+            .push_str(str)
+            .map_err(|()| DecodeError::ArrayCapacityExceeded { len, cap: N })?;
+        Ok(string)
+    }
+}
+
+impl<'de, Context, const N: usize> BorrowDecode<'de, Context> for String<N> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+impl<K, V, S, const N: usize> Encode for IndexMap<K, V, S, N>
+where
+    K: Encode,
+    V: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for (k, v) in self {
+            Encode::encode(k, encoder)?;
+            Encode::encode(v, encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Context, K, V, S, const N: usize> Decode<Context> for IndexMap<K, V, S, N>
+where
+    K: Decode<Context> + Eq + Hash,
+    V: Decode<Context>,
+    S: BuildHasher + Default,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(len, <(K, V) as Decode<Context>>::MIN_ENCODED_SIZE)?;
+
+        let mut map = Self::default();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_container_entry(<(K, V) as Decode<Context>>::MIN_ENCODED_SIZE);
+
+            let k = K::decode(decoder)?;
+            let v = V::decode(decoder)?;
+            // `len` is checked against `N` above, so this can not fail
+            map.insert(k, v)
+                .map_err(|_| DecodeError::ArrayCapacityExceeded { len, cap: N })?;
+        }
+        Ok(map)
+    }
+}
+
+impl<'de, Context, K, V, S, const N: usize> BorrowDecode<'de, Context> for IndexMap<K, V, S, N>
+where
+    K: BorrowDecode<'de, Context> + Eq + Hash,
+    V: BorrowDecode<'de, Context>,
+    S: BuildHasher + Default,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_capacity_len::<_, N>(decoder)?;
+        decoder.claim_container_entries(
+            len,
+            <(K, V) as BorrowDecode<'de, Context>>::MIN_ENCODED_SIZE,
+        )?;
+
+        let mut map = Self::default();
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder
+                .unclaim_container_entry(<(K, V) as BorrowDecode<'de, Context>>::MIN_ENCODED_SIZE);
+
+            let k = K::borrow_decode(decoder)?;
+            let v = V::borrow_decode(decoder)?;
+            // `len` is checked against `N` above, so this can not fail
+            map.insert(k, v)
+                .map_err(|_| DecodeError::ArrayCapacityExceeded { len, cap: N })?;
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,157 @@
+//! Decoding directly from memory-mapped files, using the [`memmap2`](https://docs.rs/memmap2) crate.
+//!
+//! Borrow-decoding from an [`Mmap`] works the same as from a slice: `&str` and `&[u8]` fields point into the mapped file, so huge datasets can be decoded without first reading them into memory.
+//!
+//! **Note**: the values returned by these functions borrow from the map, so the file must not be modified while they are alive. See the [`memmap2` documentation](https://docs.rs/memmap2/latest/memmap2/struct.Mmap.html#safety) for details.
+
+use crate::{
+    config::Config,
+    de::{read::SliceReader, BorrowDecode, Decoder, DecoderImpl},
+    error::DecodeError,
+};
+use core::marker::PhantomData;
+use memmap2::Mmap;
+
+/// Attempt to borrow-decode a given type `D` from the given memory map. Returns the decoded output and the amount of bytes read.
+///
+/// Borrowed fields like `&str` and `&[u8]` point into `mmap`.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the map is too small or the data is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub fn decode_from_mmap<'a, D: BorrowDecode<'a, ()>, C: Config>(
+    mmap: &'a Mmap,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    decode_from_mmap_with_context(mmap, config, ())
+}
+
+/// Attempt to borrow-decode a given type `D` from the given memory map with `Context`. Returns the decoded output and the amount of bytes read.
+///
+/// See [`decode_from_mmap`] and the [config] module for more information.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the map is too small or the data is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub fn decode_from_mmap_with_context<'a, Context, D: BorrowDecode<'a, Context>, C: Config>(
+    mmap: &'a Mmap,
+    config: C,
+    context: Context,
+) -> Result<(D, usize), DecodeError> {
+    crate::borrow_decode_from_slice_with_context(mmap, config, context)
+}
+
+/// Lazily borrow-decode the records that were encoded one after another into the given memory map.
+///
+/// See [`MmapRecords`] for more information.
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub fn decode_records_from_mmap<'a, D: BorrowDecode<'a, ()>, C: Config>(
+    mmap: &'a Mmap,
+    config: C,
+) -> MmapRecords<'a, D, C> {
+    decode_records_from_mmap_with_context(mmap, config, ())
+}
+
+/// Lazily borrow-decode the records that were encoded one after another into the given memory map with `Context`.
+///
+/// Every record is decoded with a clone of `context`. See [`MmapRecords`] for more information.
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub fn decode_records_from_mmap_with_context<
+    'a,
+    Context: Clone,
+    D: BorrowDecode<'a, Context>,
+    C: Config,
+>(
+    mmap: &'a Mmap,
+    config: C,
+    context: Context,
+) -> MmapRecords<'a, D, C, Context> {
+    MmapRecords {
+        data: mmap,
+        offset: 0,
+        config,
+        context,
+        failed: false,
+        _record: PhantomData,
+    }
+}
+
+/// An iterator over records that were encoded one after another. Created with [`decode_records_from_mmap`] or [`decode_records_from_mmap_with_context`].
+///
+/// The records are typically written by calling [`encode_into_std_write`](crate::encode_into_std_write) repeatedly on the same file.
+///
+/// Every item is the offset of a record in the map together with the decoded record. Each record is decoded with a new decoder, so a limit in the config (see [`Configuration::with_limit`](crate::config::Configuration::with_limit)) applies to every record separately.
+///
+/// The iterator ends when all of the map has been decoded. If a record cannot be decoded, the error is returned and the iterator ends, because the start of the next record is unknown.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// # use std::io::Write;
+/// let config = bincode::config::standard();
+/// let mut file = tempfile::tempfile().unwrap();
+/// for name in ["first", "second"] {
+///     bincode::encode_into_std_write(name, &mut file, config).unwrap();
+/// }
+///
+/// // Safety: the file is not modified while it is mapped
+/// let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+/// let records: Vec<(usize, &str)> = bincode::decode_records_from_mmap(&mmap, config)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(records, vec![(0, "first"), (6, "second")]);
+/// ```
+pub struct MmapRecords<'a, D, C, Context = ()> {
+    data: &'a [u8],
+    offset: usize,
+    config: C,
+    context: Context,
+    failed: bool,
+    _record: PhantomData<fn() -> D>,
+}
+
+impl<D, C, Context> MmapRecords<'_, D, C, Context> {
+    /// The offset of the next record in the map. After the iterator has ended without an error, this is the length of the map.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, Context: Clone, D: BorrowDecode<'a, Context>, C: Config> Iterator
+    for MmapRecords<'a, D, C, Context>
+{
+    type Item = Result<(usize, D), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.data.len() {
+            return None;
+        }
+        let start = self.offset;
+        let remaining = &self.data[start..];
+        let mut decoder = DecoderImpl::new(
+            SliceReader::new(remaining),
+            self.config,
+            self.context.clone(),
+        );
+        let result = D::borrow_decode(&mut decoder);
+        let bytes_read = remaining.len() - decoder.reader().slice.len();
+        match result {
+            Ok(record) => {
+                self.offset += bytes_read;
+                Some(Ok((start, record)))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    config::Config,
+    enc::{write::Writer, Encode, Encoder, EncoderImpl},
+    error::EncodeError,
+    features::{IoWriter, VecWriter},
+};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// The most elements that are encoded into a single buffer.
+const MAX_CHUNK_LEN: usize = 4096;
+
+/// The amount of elements in every chunk of `len` elements, so every thread gets a couple of chunks.
+fn chunk_len(len: usize) -> usize {
+    (len / (rayon::current_num_threads() * 4)).clamp(1, MAX_CHUNK_LEN)
+}
+
+/// The bytes of a chunk of elements, encoded on its own.
+struct Chunk {
+    result: Result<Vec<u8>, EncodeError>,
+    /// Whether the chunk encoded [shared](crate::shared) values. Their encoding depends on the values that were encoded before them, so the chunk has to be encoded again after the chunks before it.
+    shared: bool,
+}
+
+fn encode_chunk<T: Encode, C: Config>(chunk: &[T], config: C) -> Chunk {
+    let mut encoder = EncoderImpl::<_, C>::new(VecWriter::default(), config);
+    let result = encode_elements(&mut encoder, chunk);
+    Chunk {
+        shared: !encoder.shared_pointers().is_empty(),
+        result: result.map(|()| encoder.into_writer().collect()),
+    }
+}
+
+/// Encodes the elements of `slice` without a length, like `[T]::encode` does after its length.
+fn encode_elements<T: Encode, E: Encoder>(encoder: &mut E, slice: &[T]) -> Result<(), EncodeError> {
+    if crate::plain::encode_slice(encoder, slice)? {
+        return Ok(());
+    }
+    for item in slice {
+        item.encode(encoder)?;
+    }
+    Ok(())
+}
+
+/// Encodes the chunks of `slice` in parallel and writes them to `writer` in order. Everything from the first chunk that encodes shared values onwards is encoded on the current thread instead.
+fn write_chunks<T: Encode + Sync, C: Config + Sync, W: Writer>(
+    slice: &[T],
+    writer: &mut W,
+    config: C,
+    wave_len: usize,
+) -> Result<(), EncodeError> {
+    let chunk_len = chunk_len(slice.len());
+    let mut start = 0;
+    while start < slice.len() {
+        let end = slice
+            .len()
+            .min(start.saturating_add(wave_len.saturating_mul(chunk_len)));
+        let chunks: Vec<Chunk> = slice[start..end]
+            .par_chunks(chunk_len)
+            .map(|chunk| encode_chunk(chunk, config))
+            .collect();
+        for chunk in chunks {
+            if chunk.shared {
+                let mut encoder = EncoderImpl::<_, C>::new(writer, config);
+                return encode_elements(&mut encoder, &slice[start..]);
+            }
+            writer.write(&chunk.result?)?;
+            start = slice.len().min(start + chunk_len);
+        }
+    }
+    Ok(())
+}
+
+/// Encode `slice` into a `Vec<u8>`, encoding chunks of its elements on the threads of the rayon thread pool.
+///
+/// The result is the same as `encode_to_vec(slice, config)`, including the error if an element fails to encode: the elements are encoded independently of each other, so their bytes are simply concatenated after the length of the slice. Elements that contain [shared](crate::shared) values do depend on the values before them, so the elements from the first chunk with a shared value onwards are encoded sequentially.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// let values: Vec<u64> = (0..100_000).map(|i| i * 1000).collect();
+/// let config = bincode::config::standard();
+///
+/// let encoded = bincode::encode_to_vec_parallel(&values, config).unwrap();
+/// assert_eq!(encoded, bincode::encode_to_vec(&values, config).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if an element cannot be encoded.
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn encode_to_vec_parallel<T: Encode + Sync, C: Config + Sync>(
+    slice: &[T],
+    config: C,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut encoder = EncoderImpl::<_, C>::new(VecWriter::default(), config);
+    crate::enc::encode_slice_len(&mut encoder, slice.len())?;
+    let mut writer = encoder.into_writer();
+    write_chunks(slice, &mut writer, config, usize::MAX)?;
+    Ok(writer.collect())
+}
+
+/// Encode `slice` into any type that implements `std::io::Write`, encoding chunks of its elements on the threads of the rayon thread pool.
+///
+/// The chunks are encoded a couple at a time per thread, and written to `dst` in order as soon as all of them are done, so only a small part of the encoded slice is kept in memory. The bytes are the same as those of [`encode_into_std_write`](crate::encode_into_std_write), see [`encode_to_vec_parallel`]. This does not flush `dst`.
+///
+/// Returns the amount of bytes written.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if an element cannot be encoded, or if the writer fails. Chunks before the failing element may have been written already.
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn encode_into_std_write_parallel<T: Encode + Sync, C: Config + Sync, W: std::io::Write>(
+    slice: &[T],
+    dst: &mut W,
+    config: C,
+) -> Result<usize, EncodeError> {
+    let mut encoder = EncoderImpl::<_, C>::new(IoWriter::new(dst), config);
+    crate::enc::encode_slice_len(&mut encoder, slice.len())?;
+    let mut writer = encoder.into_writer();
+    write_chunks(slice, &mut writer, config, rayon::current_num_threads() * 4)?;
+    Ok(writer.bytes_written())
+}
@@ -0,0 +1,52 @@
+//! `Encode` and `Decode` implementations for [`smallvec::SmallVec`](https://docs.rs/smallvec).
+//!
+//! A `SmallVec` is encoded exactly like a `Vec<T>`. Decoding only spills to the heap if the encoded length exceeds the inline capacity.
+
+use crate::{
+    de::{BorrowDecode, BorrowDecoder, CollectionDecoder, Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use smallvec::{Array, SmallVec};
+
+impl<A> Encode for SmallVec<A>
+where
+    A: Array,
+    A::Item: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_slice().encode(encoder)
+    }
+}
+
+impl<Context, A> Decode<Context> for SmallVec<A>
+where
+    A: Array,
+    A::Item: Decode<Context>,
+{
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut entries = CollectionDecoder::<_, A::Item>::new(decoder)?;
+        let mut vec = Self::with_capacity(entries.initial_capacity());
+        while let Some(entry) = entries.next_entry()? {
+            vec.push(entry);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'de, Context, A> BorrowDecode<'de, Context> for SmallVec<A>
+where
+    A: Array,
+    A::Item: BorrowDecode<'de, Context>,
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let mut entries = CollectionDecoder::<_, A::Item>::new_borrowed(decoder)?;
+        let mut vec = Self::with_capacity(entries.initial_capacity());
+        while let Some(entry) = entries.next_borrowed_entry()? {
+            vec.push(entry);
+        }
+        Ok(vec)
+    }
+}
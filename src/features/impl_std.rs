@@ -1,20 +1,30 @@
 use crate::{
-    config::Config,
-    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder, DecoderImpl},
-    enc::{write::Writer, Encode, Encoder, EncoderImpl},
-    error::{DecodeError, EncodeError},
+    config::{
+        Config, InternalPathEncodingConfig, InternalSystemTimeConfig, PathEncoding,
+        SystemTimeDecoding,
+    },
+    de::{
+        read::{BorrowReader, ProgressReader, Reader, TakeReader},
+        report_offset, BorrowDecode, BorrowDecoder, CollectionDecoder, Decode, DecodeExtend,
+        Decoder, DecoderImpl, Extended, SkipDecode,
+    },
+    enc::{
+        write::{ProgressWriter, SeekWriter, Writer},
+        Encode, Encoder, EncoderImpl,
+    },
+    error::{DecodeError, EncodeError, ForeignPath},
     impl_borrow_decode,
 };
-use core::time::Duration;
+use core::{ops::ControlFlow, time::Duration};
 use std::{
     collections::{HashMap, HashSet},
     ffi::{CStr, CString},
     hash::Hash,
-    io::Read,
+    io::{Read, Seek, SeekFrom},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     path::{Path, PathBuf},
     sync::{Mutex, RwLock},
-    time::SystemTime,
+    time::{Instant, SystemTime},
 };
 
 /// Decode type `D` from the given reader with the given `Config`. The reader can be any type that implements `std::io::Read`, e.g. `std::fs::File`.
@@ -25,7 +35,29 @@ use std::{
 ///
 /// # Errors
 ///
-/// Returns a `DecodeError` if the reader fails or the data is invalid.
+/// Returns a `DecodeError` if the reader fails or the data is invalid. If the reader is at its end before the first byte of the value, [`DecodeError::CleanEof`] is returned, so the end of a stream of values can be told apart from a value that was cut off:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+///
+/// let config = bincode::config::standard();
+/// let mut stream = Vec::new();
+/// for message in ["first", "second"] {
+///     bincode::encode_into_std_write(message, &mut stream, config).unwrap();
+/// }
+///
+/// let mut reader = stream.as_slice();
+/// let mut messages = Vec::new();
+/// loop {
+///     match bincode::decode_from_std_read::<String, _, _>(&mut reader, config) {
+///         Ok(message) => messages.push(message),
+///         Err(DecodeError::CleanEof) => break,
+///         Err(e) => panic!("message was cut off: {e}"),
+///     }
+/// }
+/// assert_eq!(messages, ["first", "second"]);
+/// ```
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub fn decode_from_std_read<D: Decode<()>, C: Config, R: std::io::Read>(
     src: &mut R,
@@ -34,6 +66,101 @@ pub fn decode_from_std_read<D: Decode<()>, C: Config, R: std::io::Read>(
     decode_from_std_read_with_context(src, config, ())
 }
 
+/// Decode type `D` from the given reader with the given `Config`. Returns the decoded output and the amount of bytes read from the reader, like [`decode_from_slice`](crate::decode_from_slice) does.
+///
+/// This is useful to keep track of the position in a stream of encoded values, e.g. an append-only log.
+///
+/// See the [config] module for more information about config options.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid, or [`DecodeError::CleanEof`] if the reader is at its end before the first byte of the value.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_with_bytes_read<D: Decode<()>, C: Config, R: std::io::Read>(
+    src: &mut R,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    decode_from_std_read_with_bytes_read_with_context(src, config, ())
+}
+
+/// Decode type `D` from the given reader with the given `Config` and `Context`. Returns the decoded output and the amount of bytes read from the reader.
+///
+/// See [`decode_from_std_read_with_bytes_read`] and the [config] module for more information.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid, or [`DecodeError::CleanEof`] if the reader is at its end before the first byte of the value.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_with_bytes_read_with_context<
+    Context,
+    D: Decode<Context>,
+    C: Config,
+    R: std::io::Read,
+>(
+    src: &mut R,
+    config: C,
+    context: Context,
+) -> Result<(D, usize), DecodeError> {
+    let (result, bytes_read) = decode_from_io_reader(IoReader::new(src), config, context);
+    result.map(|result| (result, bytes_read))
+}
+
+/// Decode type `D` from the next `len` bytes of the given reader with the given `Config`. Returns the decoded output and the amount of bytes read.
+///
+/// The decoder is never allowed to read past those `len` bytes, even if the data is malformed. Reads past the limit fail with [`DecodeError::TakeLimitExceeded`]. If the returned amount of bytes is less than `len`, the value did not use all of its bytes and the rest are still unread.
+///
+/// This is useful for reading a message with a known length from a stream that contains other data after it.
+///
+/// See the [config] module for more information about config options.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails, the data is invalid or the value needs more than `len` bytes.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_take<D: Decode<()>, C: Config, R: std::io::Read>(
+    src: &mut R,
+    len: usize,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    decode_from_std_read_take_with_context(src, len, config, ())
+}
+
+/// Decode type `D` from the next `len` bytes of the given reader with the given `Config` and `Context`. Returns the decoded output and the amount of bytes read.
+///
+/// See [`decode_from_std_read_take`] and the [config] module for more information.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails, the data is invalid or the value needs more than `len` bytes.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_take_with_context<
+    Context,
+    D: Decode<Context>,
+    C: Config,
+    R: std::io::Read,
+>(
+    src: &mut R,
+    len: usize,
+    config: C,
+    context: Context,
+) -> Result<(D, usize), DecodeError> {
+    let reader = TakeReader::new(IoReader::new(src), len);
+    let mut decoder = DecoderImpl::<_, C, Context>::new(reader, config, context);
+    let result = D::decode(&mut decoder);
+    let bytes_read = len - decoder.reader().remaining();
+    result
+        .map(|result| (result, bytes_read))
+        .map_err(|e| report_offset::<C>(e, bytes_read))
+}
+
 /// Decode type `D` from the given reader with the given `Config` and `Context`. The reader can be any type that implements `std::io::Read`, e.g. `std::fs::File`.
 ///
 /// See the [config] module for more information about config options.
@@ -42,7 +169,7 @@ pub fn decode_from_std_read<D: Decode<()>, C: Config, R: std::io::Read>(
 ///
 /// # Errors
 ///
-/// Returns a `DecodeError` if the reader fails or the data is invalid.
+/// Returns a `DecodeError` if the reader fails or the data is invalid, or [`DecodeError::CleanEof`] if the reader is at its end before the first byte of the value.
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub fn decode_from_std_read_with_context<
     Context,
@@ -54,21 +181,120 @@ pub fn decode_from_std_read_with_context<
     config: C,
     context: Context,
 ) -> Result<D, DecodeError> {
-    let reader = IoReader::new(src);
+    decode_from_io_reader(IoReader::new(src), config, context).0
+}
+
+/// Decode type `D` from the given reader with the given `Config`, like [`decode_from_std_read`], but report where in the input an error occurred.
+///
+/// The offset is the amount of bytes that were read from the reader. See [`decode_from_slice_with_offset`](crate::decode_from_slice_with_offset).
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid, or [`DecodeError::CleanEof`] if the reader is at its end before the first byte of the value. `CleanEof` is not wrapped.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_from_std_read_with_offset<D: Decode<()>, C: Config, R: std::io::Read>(
+    src: &mut R,
+    config: C,
+) -> Result<D, DecodeError> {
+    let (result, bytes_read) = decode_from_io_reader(IoReader::new(src), config, ());
+    result.map_err(|e| match e {
+        DecodeError::CleanEof => e,
+        e => e.with_offset(bytes_read),
+    })
+}
+
+/// Decodes a `D` from `reader`. Returns the result and the amount of bytes that were read, also if decoding failed.
+fn decode_from_io_reader<Context, D: Decode<Context>, C: Config, R: std::io::Read>(
+    reader: IoReader<R>,
+    config: C,
+    context: Context,
+) -> (Result<D, DecodeError>, usize) {
     let mut decoder = DecoderImpl::<_, C, Context>::new(reader, config, context);
-    D::decode(&mut decoder)
+    let result = D::decode(&mut decoder);
+    let bytes_read = decoder.reader().bytes_read();
+    let result = result.map_err(|e| {
+        if bytes_read == 0 && matches!(e.inner(), DecodeError::UnexpectedEnd { .. }) {
+            DecodeError::CleanEof
+        } else {
+            report_offset::<C>(e, bytes_read)
+        }
+    });
+    (result, bytes_read)
 }
 
 /// A reader that reads from a `std::io::Read`.
+///
+/// Every primitive is read with a separate call to the underlying reader. For readers where every call is expensive, like `std::fs::File` or `std::net::TcpStream`, use [`BufferedIoReader`] instead.
+///
+/// Skipped bytes are read and discarded. Use [`SeekReader`] to seek over them instead.
 pub struct IoReader<R> {
     reader: R,
+    bytes_read: usize,
 }
 
 impl<R> IoReader<R> {
     /// Create a new `IoReader` from the given reader.
     pub const fn new(reader: R) -> Self {
-        Self { reader }
+        Self {
+            reader,
+            bytes_read: 0,
+        }
+    }
+
+    /// Returns the number of bytes read from the underlying reader.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+}
+
+impl<R: Read, F: FnMut(u64) -> ControlFlow<()>> ProgressReader<IoReader<R>, F> {
+    /// Create a new `ProgressReader` over an [`IoReader`] of `src`, e.g. to report the progress of decoding a large file.
+    ///
+    /// ```no_run
+    /// # extern crate bincode_next as bincode;
+    /// use bincode::de::read::ProgressReader;
+    /// use core::ops::ControlFlow;
+    ///
+    /// let file = std::io::BufReader::new(std::fs::File::open("snapshot.bin")?);
+    /// let reader = ProgressReader::from_std_read(file, 1 << 20, |read| {
+    ///     println!("{} MiB read", read >> 20);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// let snapshot: Vec<u64> = bincode::decode_from_reader(reader, bincode::config::standard())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub const fn from_std_read(src: R, every: u64, callback: F) -> Self {
+        Self::new(IoReader::new(src), every, callback)
+    }
+}
+
+/// Returns the error for an `std::io::Error` of the underlying reader when `additional` more bytes were needed. An error that carries a [`DecodeError`\], like the [`DecodeError::TimedOut`\] of a [`DeadlineReader`\], is taken back out.
+fn io_error(inner: std::io::Error, additional: usize) -> DecodeError {
+    match inner.downcast::<DecodeError>() {
+        Ok(error) => error,
+        Err(inner) => DecodeError::Io { inner, additional },
+    }
+}
+
+/// The amount of bytes that [`IoReader::read_uninit`](Reader::read_uninit) zeroes and reads at a time.
+const ZEROED_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Like [`std::io::Read::read_exact`\], but returns [`DecodeError::UnexpectedEnd`\] with the amount of missing bytes if the reader reaches its end.
+fn read_exact<R: Read>(reader: &mut R, mut bytes: &mut [u8]) -> Result<(), DecodeError> {
+    while !bytes.is_empty() {
+        match reader.read(bytes) {
+            Ok(0) => {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: bytes.len(),
+                })
+            }
+            Ok(n) => bytes = &mut bytes[n..],
+            Err(inner) if inner.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(inner) => return Err(io_error(inner, bytes.len())),
+        }
     }
+    Ok(())
 }
 
 impl<R> Reader for IoReader<R>
@@ -77,34 +303,662 @@ where
 {
     #[inline(always)]
     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        if let Err(e) = read_exact(&mut self.reader, bytes) {
+            // The bytes before the end were read from the underlying reader all the same
+            if let DecodeError::UnexpectedEnd { additional } = e {
+                self.bytes_read += bytes.len() - additional;
+            }
+            return Err(e);
+        }
+        self.bytes_read += bytes.len();
+        Ok(())
+    }
+
+    #[allow(unsafe_code)]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [core::mem::MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        // A `std::io::Read` may read from the buffer that it is given, so it is only given initialized bytes. Every chunk is zeroed right before it is read into, while it is in the cache, instead of all of `bytes` in a separate pass.
+        let len = bytes.len();
+        let mut done = 0;
+        for chunk in bytes.chunks_mut(ZEROED_CHUNK_SIZE) {
+            let chunk = crate::de::read::zeroed(chunk);
+            self.read(chunk)
+                .map_err(|e| crate::plain::with_more_needed(e, len - done - chunk.len()))?;
+            done += chunk.len();
+        }
+        // Safety: every chunk was zeroed, so every byte is initialized
+        Ok(unsafe { crate::de::read::assume_init(bytes) })
+    }
+}
+
+/// A reader that reads from a `std::io::Read + std::io::Seek`, like [`IoReader`], but skips bytes by seeking instead of reading them.
+///
+/// This makes [`Reader::skip`] cheap for sources like `std::fs::File`, e.g. when skipping sections of a file that a decoder does not understand.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::read::Reader;
+/// use std::io::Cursor;
+///
+/// let config = bincode::config::standard();
+/// let mut data = vec![0u8; 1000];
+/// bincode::encode_into_std_write(5u32, &mut data, config).unwrap();
+///
+/// let mut reader = bincode::SeekReader::new(Cursor::new(data));
+/// reader.skip(1000).unwrap();
+/// let value: u32 = bincode::decode_from_reader(&mut reader, config).unwrap();
+/// assert_eq!(value, 5);
+/// assert_eq!(reader.bytes_read(), 1001);
+/// ```
+pub struct SeekReader<R> {
+    reader: R,
+    bytes_read: usize,
+}
+
+impl<R> SeekReader<R> {
+    /// Create a new `SeekReader` from the given reader.
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            bytes_read: 0,
+        }
+    }
+
+    /// Returns the number of bytes read or skipped from the underlying reader.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
         self.reader
-            .read_exact(bytes)
-            .map_err(|inner| DecodeError::Io {
-                inner,
-                additional: bytes.len(),
-            })
     }
 }
 
-impl<R> Reader for std::io::BufReader<R>
-where
-    R: std::io::Read,
-{
+impl<R: Read + Seek> SeekReader<R> {
+    /// Seek `n` bytes forward, if the reader has that many bytes left.
+    fn seek_forward(&mut self, n: usize) -> Result<(), DecodeError> {
+        let io = |inner| io_error(inner, n);
+        let current = self.reader.stream_position().map_err(io)?;
+        let end = self.reader.seek(SeekFrom::End(0)).map_err(io)?;
+        let available = usize::try_from(end.saturating_sub(current)).unwrap_or(usize::MAX);
+        if n > available {
+            self.reader.seek(SeekFrom::Start(current)).map_err(io)?;
+            return Err(DecodeError::UnexpectedEnd {
+                additional: n - available,
+            });
+        }
+        self.reader
+            .seek(SeekFrom::Start(current + n as u64))
+            .map_err(io)?;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Reader for SeekReader<R> {
+    #[inline(always)]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        read_exact(&mut self.reader, bytes)?;
+        self.bytes_read += bytes.len();
+        Ok(())
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.seek_forward(n)?;
+        self.bytes_read += n;
+        Ok(())
+    }
+}
+
+/// A `std::io::Read` that gives up once it runs out of time, for decoding from peers that can not be trusted to send their bytes in time.
+///
+/// A size limit does not stop a peer that sends one byte per second from keeping a decode call, and its thread, busy for as long as it likes. This reader checks its time before every read from the underlying reader, and fails with [`DecodeError::TimedOut`\] once it is used up:
+/// - [`DeadlineReader::new`] gives up at a deadline.
+/// - [`DeadlineReader::with_budget`] gives up once the reads of the underlying reader took longer than the budget together.
+///
+/// Wrap it in an [`IoReader`] or a [`BufferedIoReader`] to decode from it, or pass it to [`decode_from_std_read`]. These readers turn the error back into a `DecodeError::TimedOut`.
+///
+/// The time is only checked between reads, so a read that blocks is not interrupted. Set a read timeout on the underlying reader, like [`TcpStream::set_read_timeout`](std::net::TcpStream::set_read_timeout), to bound how long a single read can wait. A read that fails with `ErrorKind::WouldBlock` or `ErrorKind::TimedOut` is retried as long as there is time left.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+/// use bincode::DeadlineReader;
+/// use std::io::Read;
+/// use std::time::Duration;
+///
+/// /// A peer that sends the byte 100 every 10 milliseconds, forever
+/// struct Slow;
+///
+/// impl Read for Slow {
+///     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+///         std::thread::sleep(Duration::from_millis(10));
+///         buf[0] = 100;
+///         Ok(1)
+///     }
+/// }
+///
+/// let mut reader = DeadlineReader::with_budget(Slow, Duration::from_millis(50));
+/// let result: Result<Vec<u8>, _> = bincode::decode_from_std_read(&mut reader, bincode::config::standard());
+/// assert!(matches!(result.unwrap_err().inner(), DecodeError::TimedOut { .. }));
+/// ```
+pub struct DeadlineReader<R> {
+    reader: R,
+    limit: TimeLimit,
+    started: Instant,
+    spent: Duration,
+}
+
+/// When a [`DeadlineReader`] runs out of time.
+#[derive(Clone, Copy)]
+enum TimeLimit {
+    Deadline(Instant),
+    Budget(Duration),
+}
+
+impl<R> DeadlineReader<R> {
+    /// Create a new `DeadlineReader` that fails when it is read from at or after `deadline`.
+    pub fn new(reader: R, deadline: Instant) -> Self {
+        Self {
+            reader,
+            limit: TimeLimit::Deadline(deadline),
+            started: Instant::now(),
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// Create a new `DeadlineReader` that fails when it is read from after the reads of `reader` took `budget` together. The time between reads, e.g. for decoding, does not count.
+    pub fn with_budget(reader: R, budget: Duration) -> Self {
+        Self {
+            reader,
+            limit: TimeLimit::Budget(budget),
+            started: Instant::now(),
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// The time the reads of the underlying reader took so far.
+    #[must_use]
+    pub const fn time_spent(&self) -> Duration {
+        self.spent
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Returns [`DecodeError::TimedOut`\] as an `std::io::Error` if the time is used up.
+    fn check(&self) -> std::io::Result<()> {
+        let elapsed = match self.limit {
+            TimeLimit::Deadline(deadline) => {
+                let now = Instant::now();
+                if now < deadline {
+                    return Ok(());
+                }
+                now.duration_since(self.started)
+            }
+            TimeLimit::Budget(budget) => {
+                if self.spent < budget {
+                    return Ok(());
+                }
+                self.spent
+            }
+        };
+        Err(DecodeError::TimedOut { elapsed }.into())
+    }
+}
+
+impl<R: Read> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            self.check()?;
+            let start = Instant::now();
+            let result = self.reader.read(buf);
+            self.spent += start.elapsed();
+            match result {
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<R> Reader for std::io::BufReader<R>
+where
+    R: std::io::Read,
+{
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        read_exact(self, bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.buffer().get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        <Self as std::io::BufRead>::consume(self, n);
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        let mut remaining = n;
+        while remaining > 0 {
+            let available = match std::io::BufRead::fill_buf(self) {
+                Ok(buffer) => buffer.len(),
+                Err(inner) if inner.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(inner) => return Err(io_error(inner, remaining)),
+            };
+            if available == 0 {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: remaining,
+                });
+            }
+            let step = remaining.min(available);
+            <Self as std::io::BufRead>::consume(self, step);
+            remaining -= step;
+        }
+        Ok(())
+    }
+}
+
+/// A reader that reads from a `std::io::Read` through an internal buffer.
+///
+/// Unlike [`IoReader`], which reads every primitive with a separate call to the underlying reader, this reader fills its buffer in large chunks and implements [`Reader::peek_read`], so the decoder can use the same fast paths it uses for slices. This makes a big difference for readers where every call is a syscall, like `std::fs::File` or `std::net::TcpStream`.
+///
+/// **Note**: this reader reads ahead. After decoding a value, up to the capacity of the buffer may have been read from the underlying reader beyond the end of that value. Keep using the same `BufferedIoReader` (e.g. by passing `&mut reader` to [`decode_from_reader`](crate::decode_from_reader)) to decode the values that follow, or use [`BufferedIoReader::buffer`] to get the bytes that were read ahead before reading from the underlying reader directly.
+///
+/// The underlying reader is only read from when the buffer is empty, or when the decoder needs more bytes than are buffered, so this never blocks waiting for data that the current value does not need.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// let config = bincode::config::standard();
+/// let mut stream = Vec::new();
+/// bincode::encode_into_std_write(vec![1u64, 2, 3], &mut stream, config).unwrap();
+/// bincode::encode_into_std_write(String::from("next"), &mut stream, config).unwrap();
+///
+/// let mut reader = bincode::BufferedIoReader::new(stream.as_slice());
+/// let numbers: Vec<u64> = bincode::decode_from_reader(&mut reader, config).unwrap();
+/// let next: String = bincode::decode_from_reader(&mut reader, config).unwrap();
+/// assert_eq!(numbers, vec![1, 2, 3]);
+/// assert_eq!(next, "next");
+/// assert_eq!(reader.bytes_read(), stream.len());
+/// ```
+pub struct BufferedIoReader<R> {
+    reader: R,
+    buf: std::boxed::Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    bytes_read: usize,
+}
+
+impl<R: std::io::Read> BufferedIoReader<R> {
+    /// The capacity of the buffer used by [`BufferedIoReader::new`].
+    pub const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+    /// Create a new `BufferedIoReader` with a buffer of [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) bytes.
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY, reader)
+    }
+
+    /// Create a new `BufferedIoReader` with a buffer of `capacity` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        assert!(
+            capacity > 0,
+            "BufferedIoReader needs a buffer of at least 1 byte"
+        );
+        Self {
+            reader,
+            buf: std::vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            bytes_read: 0,
+        }
+    }
+
+    /// Returns the number of bytes that were consumed by the decoder. This does not include the bytes that are still in the buffer.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Returns the bytes that were read from the underlying reader, but not consumed by the decoder yet.
+    #[must_use]
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Returns the underlying reader. The bytes in the [`buffer`](Self::buffer) are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Refill the empty buffer with a single read from the underlying reader.
+    fn fill_buf(&mut self) -> std::io::Result<usize> {
+        debug_assert_eq!(self.pos, self.filled);
+        loop {
+            match self.reader.read(&mut self.buf) {
+                Ok(n) => {
+                    self.pos = 0;
+                    self.filled = n;
+                    return Ok(n);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<R: std::io::Read> Reader for BufferedIoReader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let len = bytes.len();
+        let mut remaining = &mut *bytes;
+        loop {
+            let n = remaining.len().min(self.filled - self.pos);
+            remaining[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            self.pos += n;
+            remaining = &mut remaining[n..];
+            if remaining.is_empty() {
+                break;
+            }
+
+            // The buffer is empty now. Large reads go straight to the underlying reader.
+            if remaining.len() >= self.buf.len() {
+                read_exact(&mut self.reader, remaining)?;
+                break;
+            }
+            match self.fill_buf() {
+                Ok(0) => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: remaining.len(),
+                    })
+                }
+                Ok(_) => {}
+                Err(inner) => return Err(io_error(inner, remaining.len())),
+            }
+        }
+        self.bytes_read += len;
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        if self.pos == self.filled {
+            // Errors are reported by the `read` that the decoder falls back to
+            self.fill_buf().ok()?;
+        }
+        self.buf[self.pos..self.filled].get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        let n = n.min(self.filled - self.pos);
+        self.pos += n;
+        self.bytes_read += n;
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        let mut remaining = n;
+        loop {
+            let step = remaining.min(self.filled - self.pos);
+            self.pos += step;
+            remaining -= step;
+            if remaining == 0 {
+                break;
+            }
+
+            // The buffer is empty now, so the refilled buffer is skipped next
+            match self.fill_buf() {
+                Ok(0) => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: remaining,
+                    })
+                }
+                Ok(_) => {}
+                Err(inner) => return Err(io_error(inner, remaining)),
+            }
+        }
+        self.bytes_read += n;
+        Ok(())
+    }
+}
+
+/// Decode type `D` from the given [`BufferedIoReader`] with the given `Config`, borrowing data like `&str` and `&[u8]` from the buffer of the reader.
+///
+/// Returns the decoded output and the amount of bytes read.
+///
+/// Unlike [`decode_from_std_read`], this can decode borrowed types without reading the whole input into memory first. The bytes of the value are collected in the buffer of the reader, reading more from the underlying reader as needed. Borrowed data is never moved or overwritten while the value exists, so all borrowed fields of the value must fit in the buffer together, along with small fields like length prefixes between them. Data that does not fit fails with [`DecodeError::BorrowedDataTooLarge`]. Bytes that are not borrowed, like integers and owned strings, do not take up room in the buffer once they are decoded.
+///
+/// The returned value borrows the reader. Drop it before decoding the next value from the same reader.
+///
+/// This needs a `BufferedIoReader` instead of a `std::io::BufReader`, because the buffer of a `std::io::BufReader` can only be refilled once everything in it is consumed.
+///
+/// See the [config] module for more information about config options.
+///
+/// [config]: config/index.html
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// let config = bincode::config::standard();
+/// let mut stream = Vec::new();
+/// bincode::encode_into_std_write((1u32, "first"), &mut stream, config).unwrap();
+/// bincode::encode_into_std_write((2u32, "second"), &mut stream, config).unwrap();
+///
+/// let mut reader = bincode::BufferedIoReader::with_capacity(16, stream.as_slice());
+/// let (first, len): ((u32, &str), _) =
+///     bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+/// assert_eq!(first, (1, "first"));
+/// assert_eq!(len, 7);
+///
+/// let (second, _): ((u32, &str), _) =
+///     bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+/// assert_eq!(second, (2, "second"));
+/// ```
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails, the data is invalid or a borrowed field does not fit in the buffer.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_borrowed_from_buf_read<'de, D: BorrowDecode<'de, ()>, C: Config, R: Read>(
+    reader: &'de mut BufferedIoReader<R>,
+    config: C,
+) -> Result<(D, usize), DecodeError> {
+    decode_borrowed_from_buf_read_with_context(reader, config, ())
+}
+
+/// Decode the next value from a [`BufferedIoReader`] with the given `Config` and `Context`, borrowing from its buffer. Returns the decoded output and the amount of bytes read.
+///
+/// See [`decode_borrowed_from_buf_read`] and the [config] module for more information.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails, the data is invalid or a borrowed field does not fit in the buffer.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_borrowed_from_buf_read_with_context<
+    'de,
+    Context,
+    D: BorrowDecode<'de, Context>,
+    C: Config,
+    R: Read,
+>(
+    reader: &'de mut BufferedIoReader<R>,
+    config: C,
+    context: Context,
+) -> Result<(D, usize), DecodeError> {
+    // Move the buffered bytes to the start, so the whole buffer is available for this value
+    reader.buf.copy_within(reader.pos..reader.filled, 0);
+    reader.filled -= reader.pos;
+    reader.pos = 0;
+
+    let BufferedIoReader {
+        reader,
+        buf,
+        pos,
+        filled,
+        bytes_read,
+    } = reader;
+    let borrowing = BorrowingIoReader {
+        reader,
+        buf: buf.as_mut_ptr(),
+        capacity: buf.len(),
+        pos,
+        filled,
+        bytes_read,
+        borrowed: 0,
+        value_bytes_read: 0,
+        _buf: core::marker::PhantomData,
+    };
+    let mut decoder = DecoderImpl::<_, C, Context>::new(borrowing, config, context);
+    let result = D::borrow_decode(&mut decoder);
+    let value_bytes_read = decoder.reader().value_bytes_read;
+    result
+        .map(|result| (result, value_bytes_read))
+        .map_err(|e| report_offset::<C>(e, value_bytes_read))
+}
+
+/// Reads a single value through the buffer of a [`BufferedIoReader`], see [`decode_borrowed_from_buf_read`].
+///
+/// The bytes before `borrowed` may be borrowed by the value, so they are never written to. The bytes after it are only moved or written to through `buf`.
+struct BorrowingIoReader<'de, R> {
+    reader: &'de mut R,
+    /// The start of the buffer of the `BufferedIoReader`, which is borrowed for `'de`
+    buf: *mut u8,
+    capacity: usize,
+    pos: &'de mut usize,
+    filled: &'de mut usize,
+    bytes_read: &'de mut usize,
+    /// The end of the bytes that were borrowed
+    borrowed: usize,
+    value_bytes_read: usize,
+    _buf: core::marker::PhantomData<&'de mut [u8]>,
+}
+
+impl<'de, R: Read> BorrowingIoReader<'de, R> {
+    /// Returns the bytes between `pos` and `filled`. Only the bytes that are marked as borrowed may be used after the next call to `&mut self`.
+    #[allow(unsafe_code)]
+    const fn buffered(&self) -> &'de [u8] {
+        // Safety: the buffer is borrowed for `'de`, and the bytes before `filled` are initialized.
+        // Bytes before `borrowed` are never written to again, the others are not used after the next change.
+        unsafe { core::slice::from_raw_parts(self.buf.add(*self.pos), *self.filled - *self.pos) }
+    }
+
+    /// Returns whether `n` more bytes fit in the buffer. If they do not fit after `pos`, the bytes that were not consumed yet are moved to the end of the borrowed bytes first, reusing the room of the bytes that were consumed without being borrowed.
+    #[allow(unsafe_code)]
+    const fn make_room(&mut self, n: usize) -> bool {
+        if n <= self.capacity - *self.pos {
+            return true;
+        }
+        if self.borrowed < *self.pos {
+            let consumed = *self.pos - self.borrowed;
+            // Safety: both ranges are in the buffer and after `borrowed`, so they are not borrowed. `ptr::copy` allows them to overlap.
+            unsafe {
+                core::ptr::copy(
+                    self.buf.add(*self.pos),
+                    self.buf.add(self.borrowed),
+                    *self.filled - *self.pos,
+                );
+            }
+            *self.pos = self.borrowed;
+            *self.filled -= consumed;
+        }
+        n <= self.capacity - *self.pos
+    }
+
+    /// Read from the underlying reader until there are `end` bytes in the buffer. `end` must not be more than the capacity.
+    #[allow(unsafe_code)]
+    fn fill_to(&mut self, end: usize) -> Result<(), DecodeError> {
+        debug_assert!(end <= self.capacity);
+        while *self.filled < end {
+            // Safety: the buffer is `capacity` bytes long and borrowed for `'de`. Borrowed slices only cover bytes before `filled`, so nothing else refers to the bytes after it.
+            let unfilled = unsafe {
+                core::slice::from_raw_parts_mut(
+                    self.buf.add(*self.filled),
+                    self.capacity - *self.filled,
+                )
+            };
+            match self.reader.read(unfilled) {
+                Ok(0) => {
+                    return Err(DecodeError::UnexpectedEnd {
+                        additional: end - *self.filled,
+                    })
+                }
+                Ok(n) => *self.filled += n,
+                Err(inner) if inner.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(inner) => return Err(io_error(inner, end - *self.filled)),
+            }
+        }
+        Ok(())
+    }
+
+    const fn advance(&mut self, n: usize) {
+        *self.pos += n;
+        *self.bytes_read += n;
+        self.value_bytes_read += n;
+    }
+}
+
+impl<R: Read> Reader for BorrowingIoReader<'_, R> {
     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
-        self.read_exact(bytes).map_err(|inner| DecodeError::Io {
-            inner,
-            additional: bytes.len(),
-        })
+        let len = bytes.len();
+        if self.make_room(len) {
+            self.fill_to(*self.pos + len)?;
+            bytes.copy_from_slice(&self.buffered()[..len]);
+            self.advance(len);
+        } else {
+            // Does not fit in the buffer, so read the rest straight from the underlying reader
+            let buffered = self.buffered();
+            bytes[..buffered.len()].copy_from_slice(buffered);
+            read_exact(self.reader, &mut bytes[buffered.len()..])?;
+            *self.pos = *self.filled;
+            *self.bytes_read += len;
+            self.value_bytes_read += len;
+        }
+        Ok(())
     }
 
     #[inline]
     fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
-        self.buffer().get(..n)
+        if self.make_room(n) {
+            // Errors are reported by the `read` that the decoder falls back to
+            self.fill_to(*self.pos + n).ok()?;
+        }
+        self.buffered().get(..n)
     }
 
     #[inline]
     fn consume(&mut self, n: usize) {
-        <Self as std::io::BufRead>::consume(self, n);
+        self.advance(n.min(*self.filled - *self.pos));
+    }
+}
+
+impl<'de, R: Read> BorrowReader<'de> for BorrowingIoReader<'de, R> {
+    fn take_bytes(&mut self, length: usize) -> Result<&'de [u8], DecodeError> {
+        if !self.make_room(length) {
+            return Err(DecodeError::BorrowedDataTooLarge {
+                requested: length,
+                capacity: self.capacity,
+            });
+        }
+        self.fill_to(*self.pos + length)?;
+        let bytes = &self.buffered()[..length];
+        self.advance(length);
+        self.borrowed = *self.pos;
+        Ok(bytes)
     }
 }
 
@@ -113,6 +967,8 @@ where
 /// See the [config] module for more information.
 /// Returns the amount of bytes written.
 ///
+/// This does not flush `dst`. If `dst` is buffered, e.g. a `std::io::BufWriter<TcpStream>`, the bytes may stay in its buffer until it is flushed or dropped. Use [`encode_into_std_write_flushed`] to flush it after encoding.
+///
 /// [config]: config/index.html
 ///
 /// # Errors
@@ -130,10 +986,42 @@ pub fn encode_into_std_write<E: Encode, C: Config, W: std::io::Write>(
     Ok(encoder.into_writer().bytes_written())
 }
 
+/// Encode the given value into any type that implements `std::io::Write` with the given `Config`, and flush it afterwards.
+///
+/// This is [`encode_into_std_write`] followed by `dst.flush()`, which makes sure a buffered writer like `std::io::BufWriter<TcpStream>` passes the bytes on right away.
+///
+/// See the [config] module for more information.
+/// Returns the amount of bytes written.
+///
+/// [config]: config/index.html
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the writer fails. If flushing fails, this is `EncodeError::Io` with `index` set to the amount of bytes written.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn encode_into_std_write_flushed<E: Encode, C: Config, W: std::io::Write>(
+    val: E,
+    dst: &mut W,
+    config: C,
+) -> Result<usize, EncodeError> {
+    let writer = IoWriter::new(dst);
+    let mut encoder = EncoderImpl::<_, C>::new(writer, config);
+    val.encode(&mut encoder)?;
+    let mut writer = encoder.into_writer();
+    writer.flush()?;
+    Ok(writer.bytes_written())
+}
+
 /// A writer that writes to a `std::io::Write`.
+///
+/// Every write is passed on directly. Wrap it in a [`BufferedWriter`](crate::enc::write::BufferedWriter) to collect small writes first.
+///
+/// If the writer is also a `std::io::Seek`, e.g. a `std::fs::File` or a `std::io::Cursor`, this is a [`SeekWriter`]. Create it with [`new_seekable`](Self::new_seekable) so [`Writer::as_seek_writer`] returns it, and [`Encoder::with_length_prefix`] writes lengths in place instead of buffering the sections.
 pub struct IoWriter<'a, W: std::io::Write> {
     writer: &'a mut W,
     bytes_written: usize,
+    /// Returns this writer as a `SeekWriter`, if `W` is a `Seek`
+    seek: Option<fn(&mut Self) -> &mut dyn SeekWriter>,
 }
 
 impl<'a, W: std::io::Write> IoWriter<'a, W> {
@@ -142,6 +1030,7 @@ impl<'a, W: std::io::Write> IoWriter<'a, W> {
         Self {
             writer,
             bytes_written: 0,
+            seek: None,
         }
     }
 
@@ -152,6 +1041,13 @@ impl<'a, W: std::io::Write> IoWriter<'a, W> {
     }
 }
 
+impl<'a, W: std::io::Write, F: FnMut(u64) -> ControlFlow<()>> ProgressWriter<IoWriter<'a, W>, F> {
+    /// Create a new `ProgressWriter` over an [`IoWriter`] of `dst`.
+    pub const fn from_std_write(dst: &'a mut W, every: u64, callback: F) -> Self {
+        Self::new(IoWriter::new(dst), every, callback)
+    }
+}
+
 impl<W: std::io::Write> Writer for IoWriter<'_, W> {
     #[inline]
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
@@ -164,6 +1060,190 @@ impl<W: std::io::Write> Writer for IoWriter<'_, W> {
         self.bytes_written += bytes.len();
         Ok(())
     }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush().map_err(|inner| EncodeError::Io {
+            inner,
+            index: self.bytes_written,
+        })
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        self.seek.map(|seek| seek(self))
+    }
+}
+
+impl<'a, W: std::io::Write + Seek> IoWriter<'a, W> {
+    /// Create a new `IoWriter` from the given writer, which [`Writer::as_seek_writer`] returns as a [`SeekWriter`].
+    pub const fn new_seekable(writer: &'a mut W) -> Self {
+        Self {
+            writer,
+            bytes_written: 0,
+            seek: Some(|writer| writer),
+        }
+    }
+}
+
+/// Positions are relative to where the writer was when the `IoWriter` was created. `write_at` seeks back to the offset and returns to the end afterwards.
+impl<W: std::io::Write + Seek> SeekWriter for IoWriter<'_, W> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.bytes_written
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        let back = match offset.checked_add(bytes.len()) {
+            Some(end) if end <= self.bytes_written => self.bytes_written - offset,
+            _ => {
+                return Err(EncodeError::Other(
+                    "write_at can only overwrite bytes that were written before",
+                ))
+            }
+        };
+        let io_error = |inner| EncodeError::Io {
+            inner,
+            index: offset,
+        };
+        let seek = |distance: usize| {
+            i64::try_from(distance).map_err(|_| io_error(std::io::ErrorKind::InvalidInput.into()))
+        };
+        self.writer
+            .seek(SeekFrom::Current(-seek(back)?))
+            .map_err(io_error)?;
+        self.writer.write_all(bytes).map_err(io_error)?;
+        self.writer
+            .seek(SeekFrom::Current(seek(back - bytes.len())?))
+            .map_err(io_error)?;
+        Ok(())
+    }
+}
+
+/// A writer that writes to a `std::io::Write` through an internal staging buffer.
+///
+/// Small writes, like the ones for integers and length prefixes, are collected in the buffer. Writes that do not fit in the buffer are sent to the underlying writer together with the buffered bytes in a single [`write_vectored`](std::io::Write::write_vectored) call. This greatly reduces the amount of calls to the underlying writer, which matters for writers where every call is a syscall, like `std::net::TcpStream`. The written bytes are identical to the ones written by [`IoWriter`].
+///
+/// Call [`BufferedIoWriter::flush`] after encoding to write the remaining buffered bytes and to find out if that failed. Dropping the writer also tries to write them, but ignores any errors.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// let config = bincode::config::standard();
+/// let blobs = (vec![1u8; 100_000], 5u32, vec![2u8; 100_000]);
+///
+/// let mut output = Vec::new();
+/// let mut writer = bincode::BufferedIoWriter::new(&mut output);
+/// bincode::encode_into_writer(&blobs, &mut writer, config).unwrap();
+/// writer.flush().unwrap();
+/// drop(writer);
+///
+/// assert_eq!(output, bincode::encode_to_vec(&blobs, config).unwrap());
+/// ```
+pub struct BufferedIoWriter<'a, W: std::io::Write> {
+    writer: &'a mut W,
+    buf: std::vec::Vec<u8>,
+    bytes_written: usize,
+}
+
+impl<'a, W: std::io::Write> BufferedIoWriter<'a, W> {
+    /// The capacity of the buffer used by [`BufferedIoWriter::new`].
+    pub const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+    /// Create a new `BufferedIoWriter` with a buffer of [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) bytes.
+    pub fn new(writer: &'a mut W) -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY, writer)
+    }
+
+    /// Create a new `BufferedIoWriter` with a buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, writer: &'a mut W) -> Self {
+        Self {
+            writer,
+            buf: std::vec::Vec::with_capacity(capacity),
+            bytes_written: 0,
+        }
+    }
+
+    /// Returns the number of bytes written to this writer, including the bytes that are still buffered.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Write the buffered bytes to the underlying writer, and flush it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EncodeError::Io` if the underlying writer fails.
+    pub fn flush(&mut self) -> Result<(), EncodeError> {
+        self.write_buffered(&[])?;
+        self.writer.flush().map_err(|inner| EncodeError::Io {
+            inner,
+            index: self.bytes_written,
+        })
+    }
+
+    /// Write the buffered bytes followed by `bytes` to the underlying writer, and clear the buffer.
+    fn write_buffered(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let flushed = self.bytes_written - self.buf.len();
+        let mut written = 0;
+        let mut slices = [
+            std::io::IoSlice::new(&self.buf),
+            std::io::IoSlice::new(bytes),
+        ];
+        let mut slices = &mut slices[..];
+        // removes the empty slices
+        std::io::IoSlice::advance_slices(&mut slices, 0);
+        while !slices.is_empty() {
+            match self.writer.write_vectored(slices) {
+                Ok(0) => {
+                    return Err(EncodeError::Io {
+                        inner: std::io::ErrorKind::WriteZero.into(),
+                        index: flushed + written,
+                    })
+                }
+                Ok(n) => {
+                    written += n;
+                    std::io::IoSlice::advance_slices(&mut slices, n);
+                }
+                Err(inner) if inner.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(inner) => {
+                    return Err(EncodeError::Io {
+                        inner,
+                        index: flushed + written,
+                    })
+                }
+            }
+        }
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+impl<W: std::io::Write> Writer for BufferedIoWriter<'_, W> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() <= self.buf.capacity() - self.buf.len() {
+            self.buf.extend_from_slice(bytes);
+        } else if bytes.len() < self.buf.capacity() {
+            self.write_buffered(&[])?;
+            self.buf.extend_from_slice(bytes);
+        } else {
+            self.write_buffered(bytes)?;
+        }
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        Self::flush(self)
+    }
+}
+
+impl<W: std::io::Write> Drop for BufferedIoWriter<'_, W> {
+    fn drop(&mut self) {
+        let _ = self.write_buffered(&[]);
+    }
 }
 
 impl Encode for &CStr {
@@ -188,6 +1268,22 @@ impl<Context> Decode<Context> for CString {
 }
 impl_borrow_decode!(CString);
 
+/// Borrows the C string from the input, without allocating.
+///
+/// A `&CStr` needs its nul terminator in memory, so the encoded bytes must end with the terminator: the length counts it, e.g. as written by encoding [`CStr::to_bytes_with_nul`]. `CString` and `&CStr` themselves are encoded without the terminator, so their encoding can only be decoded as an owned `CString`.
+///
+/// Returns `DecodeError::CStringNulError` with the position of the first 0 byte if it is not the last byte, or with the length of the bytes if the terminator is missing.
+impl<'de, Context> BorrowDecode<'de, Context> for &'de CStr {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let bytes = <&[u8]>::borrow_decode(decoder)?;
+        CStr::from_bytes_with_nul(bytes).map_err(|_| DecodeError::CStringNulError {
+            position: bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len()),
+        })
+    }
+}
+
 impl<T> Encode for Mutex<T>
 where
     T: Encode,
@@ -269,18 +1365,72 @@ impl Encode for SystemTime {
 impl<Context> Decode<Context> for SystemTime {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let duration = Duration::decode(decoder)?;
-        Self::UNIX_EPOCH
+        let time = Self::UNIX_EPOCH
             .checked_add(duration)
-            .ok_or(DecodeError::InvalidSystemTime { duration })
+            .ok_or(DecodeError::InvalidSystemTime { duration })?;
+        // Windows only keeps multiples of 100 nanoseconds
+        if D::C::SYSTEM_TIME_DECODING == SystemTimeDecoding::Exact
+            && time.duration_since(Self::UNIX_EPOCH).ok() != Some(duration)
+        {
+            return Err(DecodeError::InvalidSystemTime { duration });
+        }
+        Ok(time)
     }
 }
 impl_borrow_decode!(SystemTime);
 
+// The tags of a path that is encoded with `LosslessPaths`, see `Configuration::with_lossless_paths`
+const PATH_UTF8: u32 = 0;
+const PATH_UNIX: u32 = 1;
+const PATH_WINDOWS: u32 = 2;
+
 impl Encode for &'_ Path {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        self.to_str()
-            .ok_or(EncodeError::InvalidPathCharacters)?
-            .encode(encoder)
+        match E::C::PATH_ENCODING {
+            PathEncoding::Utf8 => self
+                .to_str()
+                .ok_or(EncodeError::InvalidPathCharacters)?
+                .encode(encoder),
+            PathEncoding::Lossless => encode_lossless_path(self, encoder),
+        }
+    }
+}
+
+fn encode_lossless_path<E: Encoder>(path: &Path, encoder: &mut E) -> Result<(), EncodeError> {
+    if let Some(str) = path.to_str() {
+        PATH_UTF8.encode(encoder)?;
+        return str.encode(encoder);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PATH_UNIX.encode(encoder)?;
+        path.as_os_str().as_bytes().encode(encoder)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        PATH_WINDOWS.encode(encoder)?;
+        let units: std::vec::Vec<u16> = path.as_os_str().encode_wide().collect();
+        units.encode(encoder)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(EncodeError::InvalidPathCharacters)
+    }
+}
+
+/// Returns the error for a path with an unknown tag.
+#[allow(clippy::cast_lossless)]
+const fn invalid_path_tag(found: u32) -> DecodeError {
+    DecodeError::UnexpectedVariant {
+        allowed: &crate::error::AllowedEnumVariants::Named(&[
+            ("Utf8", PATH_UTF8 as u64),
+            ("Unix", PATH_UNIX as u64),
+            ("Windows", PATH_WINDOWS as u64),
+        ]),
+        found: found as u64,
+        type_name: "Path",
     }
 }
 
@@ -288,6 +1438,43 @@ impl<'de, Context> BorrowDecode<'de, Context> for &'de Path {
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
+        if D::C::PATH_ENCODING == PathEncoding::Lossless {
+            match u32::decode(decoder)? {
+                PATH_UTF8 => {}
+                PATH_UNIX => {
+                    let bytes = <&'de [u8]>::borrow_decode(decoder)?;
+                    #[cfg(unix)]
+                    {
+                        use std::os::unix::ffi::OsStrExt;
+                        return Ok(Path::new(std::ffi::OsStr::from_bytes(bytes)));
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        return Err(DecodeError::ForeignPath(std::boxed::Box::new(
+                            ForeignPath::Unix(bytes.to_vec()),
+                        )));
+                    }
+                }
+                PATH_WINDOWS => {
+                    let units = std::vec::Vec::<u16>::decode(decoder)?;
+                    // UTF-16 can not be borrowed as a `Path`, not even on Windows
+                    #[cfg(windows)]
+                    {
+                        let _ = units;
+                        return Err(DecodeError::Other(
+                            "a path that is not valid UTF-8 can not be borrowed on Windows, decode a PathBuf instead",
+                        ));
+                    }
+                    #[cfg(not(windows))]
+                    {
+                        return Err(DecodeError::ForeignPath(std::boxed::Box::new(
+                            ForeignPath::Windows(units),
+                        )));
+                    }
+                }
+                found => return Err(invalid_path_tag(found)),
+            }
+        }
         let str = <&'de str>::borrow_decode(decoder)?;
         Ok(Path::new(str))
     }
@@ -301,6 +1488,40 @@ impl Encode for PathBuf {
 
 impl<Context> Decode<Context> for PathBuf {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if D::C::PATH_ENCODING == PathEncoding::Lossless {
+            match u32::decode(decoder)? {
+                PATH_UTF8 => {}
+                PATH_UNIX => {
+                    let bytes = std::vec::Vec::<u8>::decode(decoder)?;
+                    #[cfg(unix)]
+                    {
+                        use std::os::unix::ffi::OsStringExt;
+                        return Ok(std::ffi::OsString::from_vec(bytes).into());
+                    }
+                    #[cfg(not(unix))]
+                    {
+                        return Err(DecodeError::ForeignPath(std::boxed::Box::new(
+                            ForeignPath::Unix(bytes),
+                        )));
+                    }
+                }
+                PATH_WINDOWS => {
+                    let units = std::vec::Vec::<u16>::decode(decoder)?;
+                    #[cfg(windows)]
+                    {
+                        use std::os::windows::ffi::OsStringExt;
+                        return Ok(std::ffi::OsString::from_wide(&units).into());
+                    }
+                    #[cfg(not(windows))]
+                    {
+                        return Err(DecodeError::ForeignPath(std::boxed::Box::new(
+                            ForeignPath::Windows(units),
+                        )));
+                    }
+                }
+                found => return Err(invalid_path_tag(found)),
+            }
+        }
         let string = std::string::String::decode(decoder)?;
         Ok(string.into())
     }
@@ -328,8 +1549,8 @@ impl<Context> Decode<Context> for IpAddr {
             0 => Ok(Self::V4(Ipv4Addr::decode(decoder)?)),
             1 => Ok(Self::V6(Ipv6Addr::decode(decoder)?)),
             found => Err(DecodeError::UnexpectedVariant {
-                allowed: &crate::error::AllowedEnumVariants::Range { min: 0, max: 1 },
-                found,
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("V4", 0), ("V6", 1)]),
+                found: u64::from(found),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -344,7 +1565,10 @@ impl Encode for Ipv4Addr {
 }
 
 impl<Context> Decode<Context> for Ipv4Addr {
+    const MIN_ENCODED_SIZE: usize = 4;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.claim_bytes_read(4)?;
         let mut buff = [0u8; 4];
         decoder.reader().read(&mut buff)?;
         Ok(Self::from(buff))
@@ -359,7 +1583,10 @@ impl Encode for Ipv6Addr {
 }
 
 impl<Context> Decode<Context> for Ipv6Addr {
+    const MIN_ENCODED_SIZE: usize = 16;
+
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.claim_bytes_read(16)?;
         let mut buff = [0u8; 16];
         decoder.reader().read(&mut buff)?;
         Ok(Self::from(buff))
@@ -388,8 +1615,8 @@ impl<Context> Decode<Context> for SocketAddr {
             0 => Ok(Self::V4(SocketAddrV4::decode(decoder)?)),
             1 => Ok(Self::V6(SocketAddrV6::decode(decoder)?)),
             found => Err(DecodeError::UnexpectedVariant {
-                allowed: &crate::error::AllowedEnumVariants::Range { min: 0, max: 1 },
-                found,
+                allowed: &crate::error::AllowedEnumVariants::Named(&[("V4", 0), ("V6", 1)]),
+                found: u64::from(found),
                 type_name: core::any::type_name::<Self>(),
             }),
         }
@@ -435,12 +1662,7 @@ where
     V: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for (k, v) in self {
-            Encode::encode(k, encoder)?;
-            Encode::encode(v, encoder)?;
-        }
-        Ok(())
+        crate::enc::encode_collection(encoder, self.len(), self)
     }
 }
 
@@ -451,20 +1673,7 @@ where
     S: std::hash::BuildHasher + Default,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<(K, V)>(len)?;
-
-        let hash_builder: S = Default::default();
-        let mut map = Self::with_capacity_and_hasher(len, hash_builder);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let k = K::decode(decoder)?;
-            let v = V::decode(decoder)?;
-            map.insert(k, v);
-        }
-        Ok(map)
+        decode_hash_map_with_hasher(decoder, S::default())
     }
 }
 impl<'de, K, V, S, Context> BorrowDecode<'de, Context> for HashMap<K, V, S>
@@ -476,20 +1685,7 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<(K, V)>(len)?;
-
-        let hash_builder: S = Default::default();
-        let mut map = Self::with_capacity_and_hasher(len, hash_builder);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let k = K::borrow_decode(decoder)?;
-            let v = V::borrow_decode(decoder)?;
-            map.insert(k, v);
-        }
-        Ok(map)
+        borrow_decode_hash_map_with_hasher(decoder, S::default())
     }
 }
 
@@ -499,19 +1695,7 @@ where
     S: std::hash::BuildHasher + Default,
 {
     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<T>(len)?;
-
-        let hash_builder: S = Default::default();
-        let mut map: Self = Self::with_capacity_and_hasher(len, hash_builder);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.insert(key);
-        }
-        Ok(map)
+        decode_hash_set_with_hasher(decoder, S::default())
     }
 }
 
@@ -523,19 +1707,180 @@ where
     fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<T>(len)?;
+        borrow_decode_hash_set_with_hasher(decoder, S::default())
+    }
+}
 
-        let mut map = Self::with_capacity_and_hasher(len, S::default());
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+impl<Context, K, V, S> DecodeExtend<Context> for HashMap<K, V, S>
+where
+    K: Decode<Context> + Eq + Hash,
+    V: Decode<Context>,
+    S: std::hash::BuildHasher,
+{
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let mut entries = CollectionDecoder::<_, (K, V)>::new(decoder)?;
+        let capacity = entries.initial_capacity();
+        crate::de::reserve::reserve::<D, _>(self, capacity)?;
+        let mut extended = Extended::default();
+        while let Some((k, v)) = entries.next_entry()? {
+            crate::de::reserve::reserve_entry::<D, _>(self)?;
+            extended.add(self.insert(k, v).is_some());
+        }
+        Ok(extended)
+    }
+}
 
-            let key = T::borrow_decode(decoder)?;
-            map.insert(key);
+impl<Context, T, S> DecodeExtend<Context> for HashSet<T, S>
+where
+    T: Decode<Context> + Eq + Hash,
+    S: std::hash::BuildHasher,
+{
+    fn decode_extend<D: Decoder<Context = Context>>(
+        &mut self,
+        decoder: &mut D,
+    ) -> Result<Extended, DecodeError> {
+        let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+        let capacity = entries.initial_capacity();
+        crate::de::reserve::reserve::<D, _>(self, capacity)?;
+        let mut extended = Extended::default();
+        while let Some(key) = entries.next_entry()? {
+            crate::de::reserve::reserve_entry::<D, _>(self)?;
+            extended.add(self.replace(key).is_some());
         }
-        Ok(map)
+        Ok(extended)
+    }
+}
+
+/// Decode a `HashMap` that uses the given hasher.
+///
+/// The `Decode` implementation of `HashMap` creates its hasher with `S::default()`. Use this instead for hashers that need to be constructed with explicit keys, e.g. to harden the map against collisions that are crafted in the input. Call it from the `Decode` implementation of the type that holds the map:
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::{Decode, Decoder};
+/// use bincode::error::DecodeError;
+/// use std::collections::HashMap;
+/// use std::hash::RandomState;
+///
+/// struct Index {
+///     entries: HashMap<String, u32, RandomState>,
+/// }
+///
+/// impl<Context> Decode<Context> for Index {
+///     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+///         let hasher = RandomState::new();
+///         Ok(Self {
+///             entries: bincode::decode_hash_map_with_hasher(decoder, hasher)?,
+///         })
+///     }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as the `Decode` implementation of `HashMap`.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_hash_map_with_hasher<D, K, V, S>(
+    decoder: &mut D,
+    hasher: S,
+) -> Result<HashMap<K, V, S>, DecodeError>
+where
+    D: Decoder,
+    K: Decode<D::Context> + Eq + Hash,
+    V: Decode<D::Context>,
+    S: std::hash::BuildHasher,
+{
+    let mut entries = CollectionDecoder::<_, (K, V)>::new(decoder)?;
+    let mut map = HashMap::with_hasher(hasher);
+    let capacity = entries.initial_capacity();
+    crate::de::reserve::reserve::<D, _>(&mut map, capacity)?;
+    while let Some((k, v)) = entries.next_entry()? {
+        crate::de::reserve::reserve_entry::<D, _>(&mut map)?;
+        map.insert(k, v);
+    }
+    Ok(map)
+}
+
+/// Borrow-decode a `HashMap` that uses the given hasher. See [`decode_hash_map_with_hasher`].
+///
+/// # Errors
+///
+/// Returns the same errors as the `BorrowDecode` implementation of `HashMap`.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn borrow_decode_hash_map_with_hasher<'de, D, K, V, S>(
+    decoder: &mut D,
+    hasher: S,
+) -> Result<HashMap<K, V, S>, DecodeError>
+where
+    D: BorrowDecoder<'de>,
+    K: BorrowDecode<'de, D::Context> + Eq + Hash,
+    V: BorrowDecode<'de, D::Context>,
+    S: std::hash::BuildHasher,
+{
+    let mut entries = CollectionDecoder::<_, (K, V)>::new_borrowed(decoder)?;
+    let mut map = HashMap::with_hasher(hasher);
+    let capacity = entries.initial_capacity();
+    crate::de::reserve::reserve::<D, _>(&mut map, capacity)?;
+    while let Some((k, v)) = entries.next_borrowed_entry()? {
+        crate::de::reserve::reserve_entry::<D, _>(&mut map)?;
+        map.insert(k, v);
+    }
+    Ok(map)
+}
+
+/// Decode a `HashSet` that uses the given hasher. See [`decode_hash_map_with_hasher`].
+///
+/// # Errors
+///
+/// Returns the same errors as the `Decode` implementation of `HashSet`.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_hash_set_with_hasher<D, T, S>(
+    decoder: &mut D,
+    hasher: S,
+) -> Result<HashSet<T, S>, DecodeError>
+where
+    D: Decoder,
+    T: Decode<D::Context> + Eq + Hash,
+    S: std::hash::BuildHasher,
+{
+    let mut entries = CollectionDecoder::<_, T>::new(decoder)?;
+    let mut set = HashSet::with_hasher(hasher);
+    let capacity = entries.initial_capacity();
+    crate::de::reserve::reserve::<D, _>(&mut set, capacity)?;
+    while let Some(key) = entries.next_entry()? {
+        crate::de::reserve::reserve_entry::<D, _>(&mut set)?;
+        set.insert(key);
+    }
+    Ok(set)
+}
+
+/// Borrow-decode a `HashSet` that uses the given hasher. See [`decode_hash_map_with_hasher`].
+///
+/// # Errors
+///
+/// Returns the same errors as the `BorrowDecode` implementation of `HashSet`.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn borrow_decode_hash_set_with_hasher<'de, D, T, S>(
+    decoder: &mut D,
+    hasher: S,
+) -> Result<HashSet<T, S>, DecodeError>
+where
+    D: BorrowDecoder<'de>,
+    T: BorrowDecode<'de, D::Context> + Eq + Hash,
+    S: std::hash::BuildHasher,
+{
+    let mut entries = CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+    let mut set = HashSet::with_hasher(hasher);
+    let capacity = entries.initial_capacity();
+    crate::de::reserve::reserve::<D, _>(&mut set, capacity)?;
+    while let Some(key) = entries.next_borrowed_entry()? {
+        crate::de::reserve::reserve_entry::<D, _>(&mut set)?;
+        set.insert(key);
     }
+    Ok(set)
 }
 
 impl<T, S> Encode for HashSet<T, S>
@@ -543,10 +1888,108 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for item in self {
-            item.encode(encoder)?;
+        crate::enc::encode_collection(encoder, self.len(), self)
+    }
+}
+
+impl<K: SkipDecode, V: SkipDecode, S> SkipDecode for HashMap<K, V, S> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[(K, V)]>::skip(decoder)
+    }
+}
+
+impl<T: SkipDecode, S> SkipDecode for HashSet<T, S> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[T]>::skip(decoder)
+    }
+}
+
+impl SkipDecode for CStr {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[u8]>::skip(decoder)
+    }
+}
+
+impl SkipDecode for CString {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        <[u8]>::skip(decoder)
+    }
+}
+
+impl SkipDecode for Path {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        if D::C::PATH_ENCODING == PathEncoding::Lossless {
+            match u32::decode(decoder)? {
+                PATH_UTF8 | PATH_UNIX => {}
+                PATH_WINDOWS => return <[u16]>::skip(decoder),
+                found => return Err(invalid_path_tag(found)),
+            }
         }
-        Ok(())
+        <[u8]>::skip(decoder)
+    }
+}
+
+impl SkipDecode for PathBuf {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        Path::skip(decoder)
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for Mutex<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+impl<T: SkipDecode> SkipDecode for RwLock<T> {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        T::skip(decoder)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        T::fixed_size::<C>()
+    }
+}
+
+/// Network addresses and `SystemTime` have a tag or are validated, so they are skipped by decoding them. They do not allocate.
+macro_rules! impl_skip_by_decode {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SkipDecode for $ty {
+                fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+                    <Self as Decode<D::Context>>::decode(decoder).map(drop)
+                }
+            }
+        )*
+    };
+}
+
+impl_skip_by_decode!(IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6, SystemTime);
+
+impl SkipDecode for Ipv4Addr {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.skip_bytes(4)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        Some(4)
+    }
+}
+
+impl SkipDecode for Ipv6Addr {
+    fn skip<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+        decoder.skip_bytes(16)
+    }
+
+    #[inline]
+    fn fixed_size<C: Config>() -> Option<usize> {
+        Some(16)
     }
 }
@@ -0,0 +1,93 @@
+//! `Encode` and `Decode` implementations for the date and time types of the [`time`](https://docs.rs/time) crate.
+//!
+//! The types are encoded as follows:
+//!
+//! |Type|Encoding|
+//! |---|---|
+//! |`OffsetDateTime`|`i64` seconds since the unix epoch, followed by a `u32` with the sub-second nanoseconds, followed by an `i32` with the UTC offset in seconds|
+//! |`PrimitiveDateTime`|`i64` seconds since the unix epoch, followed by a `u32` with the sub-second nanoseconds, as if the value was in UTC|
+//! |`Duration`|`i64` whole seconds, followed by an `i32` with the sub-second nanoseconds. Both values have the same sign|
+//!
+//! The seconds and nanoseconds of an `OffsetDateTime` always describe the instant in UTC, the offset is only used to restore the local representation.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode,
+};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// Decode an `i64` timestamp and `u32` nanoseconds pair into an `OffsetDateTime` in UTC.
+fn decode_utc_timestamp<D: Decoder>(
+    decoder: &mut D,
+    type_name: &'static str,
+) -> Result<OffsetDateTime, DecodeError> {
+    let secs = i64::decode(decoder)?;
+    let nanos = u32::decode(decoder)?;
+    OffsetDateTime::from_unix_timestamp(secs)
+        .and_then(|date_time| date_time.replace_nanosecond(nanos))
+        .map_err(|_| DecodeError::InvalidDateTime { type_name })
+}
+
+impl Encode for OffsetDateTime {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.unix_timestamp().encode(encoder)?;
+        self.nanosecond().encode(encoder)?;
+        self.offset().whole_seconds().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for OffsetDateTime {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let type_name = core::any::type_name::<Self>();
+        let utc = decode_utc_timestamp(decoder, type_name)?;
+        let offset = i32::decode(decoder)?;
+        UtcOffset::from_whole_seconds(offset)
+            .ok()
+            .and_then(|offset| utc.checked_to_offset(offset))
+            .ok_or(DecodeError::InvalidDateTime { type_name })
+    }
+}
+impl_borrow_decode!(OffsetDateTime);
+
+impl Encode for PrimitiveDateTime {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let utc = self.assume_utc();
+        utc.unix_timestamp().encode(encoder)?;
+        utc.nanosecond().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for PrimitiveDateTime {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let utc = decode_utc_timestamp(decoder, core::any::type_name::<Self>())?;
+        Ok(Self::new(utc.date(), utc.time()))
+    }
+}
+impl_borrow_decode!(PrimitiveDateTime);
+
+impl Encode for Duration {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.whole_seconds().encode(encoder)?;
+        self.subsec_nanoseconds().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for Duration {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        const NANOS_PER_SEC: i32 = 1_000_000_000;
+        let secs = i64::decode(decoder)?;
+        let nanos = i32::decode(decoder)?;
+        // `Duration::new` panics if normalizing the nanoseconds overflows the seconds. This can
+        // only happen if the nanoseconds are out of range or have a different sign than the seconds.
+        let sign_mismatch = (secs > 0 && nanos < 0) || (secs < 0 && nanos > 0);
+        if sign_mismatch || nanos <= -NANOS_PER_SEC || nanos >= NANOS_PER_SEC {
+            return Err(DecodeError::InvalidDateTime {
+                type_name: core::any::type_name::<Self>(),
+            });
+        }
+        Ok(Self::new(secs, nanos))
+    }
+}
+impl_borrow_decode!(Duration);
@@ -13,6 +13,67 @@ mod derive;
 #[cfg(feature = "derive")]
 pub use self::derive::*;
 
+#[cfg(feature = "chrono")]
+mod impl_chrono;
+
+#[cfg(feature = "time")]
+mod impl_time;
+
+#[cfg(feature = "bytes")]
+mod impl_bytes;
+#[cfg(feature = "bytes")]
+pub use self::impl_bytes::*;
+
+#[cfg(feature = "smallvec")]
+mod impl_smallvec;
+
+#[cfg(feature = "arrayvec")]
+mod impl_arrayvec;
+
+#[cfg(feature = "heapless")]
+mod impl_heapless;
+
+#[cfg(feature = "glam")]
+mod impl_glam;
+
+#[cfg(feature = "half")]
+mod impl_half;
+
+#[cfg(feature = "memmap2")]
+mod impl_memmap2;
+#[cfg(feature = "memmap2")]
+pub use self::impl_memmap2::*;
+
+#[cfg(feature = "bumpalo")]
+mod impl_bumpalo;
+#[cfg(feature = "bumpalo")]
+pub use self::impl_bumpalo::*;
+
+#[cfg(feature = "rayon")]
+mod impl_rayon;
+#[cfg(feature = "rayon")]
+pub use self::impl_rayon::*;
+
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+pub mod fuzz;
+
+#[cfg(feature = "cobs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cobs")))]
+pub mod cobs;
+
+#[cfg(feature = "armor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "armor")))]
+pub mod armor;
+
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub mod stats;
+
+#[cfg(feature = "test-utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod test_utils;
+
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod serde;
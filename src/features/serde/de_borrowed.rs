@@ -46,7 +46,28 @@ impl<'de, C: Config, Context> BorrowedSerdeDecoder<'de, DecoderImpl<SliceReader<
 
 /// Attempt to decode a given type `D` from the given slice. Returns the decoded output and the amount of bytes read.
 ///
+/// `&'de str` and `&'de [u8]` fields (and `#[serde(borrow)]` fields like `Cow<'de, str>`) borrow from `slice` instead of being copied, just like with [`BorrowDecode`].
+///
 /// See the [config](../config/index.html) module for more information on configurations.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// # use serde_derive::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Log<'a> {
+///     msg: &'a str,
+///     payload: &'a [u8],
+/// }
+///
+/// let config = bincode::config::standard();
+/// let log = Log { msg: "disk full", payload: &[1, 2, 3] };
+/// let encoded = bincode::serde::encode_to_vec(&log, config).unwrap();
+///
+/// let (decoded, _): (Log, usize) = bincode::serde::borrow_decode_from_slice(&encoded, config).unwrap();
+/// assert_eq!(decoded.msg, "disk full");
+/// assert!(encoded.as_ptr_range().contains(&decoded.msg.as_ptr()));
+/// ```
+///
 /// # Errors
 ///
 /// Returns a `DecodeError` if the slice cannot be decoded.
@@ -295,7 +316,7 @@ impl<'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'_, 'de, DE
     where
         V: serde::de::Visitor<'de>,
     {
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
         self.deserialize_tuple(len, visitor)
     }
 
@@ -403,7 +424,7 @@ impl<'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'_, 'de, DE
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
         visitor.visit_map(Access {
             deserializer: &mut self,
@@ -75,12 +75,11 @@ where
 /// Returns a `DecodeError` if the reader fails or the data is invalid.
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-pub fn decode_from_std_read<'r, D: DeserializeOwned, C: Config, R: std::io::Read>(
-    src: &'r mut R,
+pub fn decode_from_std_read<D: DeserializeOwned, C: Config, R: std::io::Read>(
+    src: &mut R,
     config: C,
 ) -> Result<D, DecodeError> {
-    let mut serde_decoder =
-        OwnedSerdeDecoder::<DecoderImpl<IoReader<&'r mut R>, C, ()>>::from_std_read(src, config);
+    let mut serde_decoder = OwnedSerdeDecoder::from_std_read(src, config);
     D::deserialize(serde_decoder.as_deserializer())
 }
 
@@ -96,7 +95,7 @@ pub fn decode_from_reader<D: DeserializeOwned, R: Reader, C: Config>(
     reader: R,
     config: C,
 ) -> Result<D, DecodeError> {
-    let mut serde_decoder = OwnedSerdeDecoder::<DecoderImpl<R, C, ()>>::from_reader(reader, config);
+    let mut serde_decoder = OwnedSerdeDecoder::from_reader(reader, config);
     D::deserialize(serde_decoder.as_deserializer())
 }
 
@@ -120,8 +119,7 @@ where
     C: Config,
     R: std::io::Read,
 {
-    let mut serde_decoder =
-        OwnedSerdeDecoder::<DecoderImpl<IoReader<&'r mut R>, C, ()>>::from_std_read(src, config);
+    let mut serde_decoder = OwnedSerdeDecoder::from_std_read(src, config);
     seed.deserialize(serde_decoder.as_deserializer())
 }
 
@@ -273,20 +271,26 @@ impl<'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'_, DE> {
         Err(SerdeDecodeError::CannotAllocate.into())
     }
 
-    #[cfg(feature = "alloc")]
-    fn deserialize_bytes<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_byte_buf(Decode::decode(&mut self.de)?)
-    }
-
-    #[cfg(not(feature = "alloc"))]
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: serde::de::Visitor<'de>,
-    {
-        Err(SerdeDecodeError::CannotBorrowOwnedData.into())
+        let len = crate::de::decode_slice_len(self.de)?;
+        self.de.claim_container_read::<u8>(len)?;
+        // a buffered reader can lend the bytes to the visitor without copying them into a `Vec`
+        if let Some(bytes) = crate::de::read::peek_exact(self.de.reader(), len) {
+            let value = visitor.visit_bytes(bytes)?;
+            self.de.reader().consume(len);
+            return Ok(value);
+        }
+        #[cfg(feature = "alloc")]
+        {
+            visitor.visit_byte_buf(crate::plain::decode_byte_vec(self.de, len)?)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            Err(SerdeDecodeError::CannotAllocate.into())
+        }
     }
 
     #[cfg(feature = "alloc")]
@@ -349,7 +353,7 @@ impl<'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'_, DE> {
     where
         V: serde::de::Visitor<'de>,
     {
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
         self.deserialize_tuple(len, visitor)
     }
 
@@ -454,7 +458,7 @@ impl<'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'_, DE> {
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
         visitor.visit_map(Access {
             deserializer: &mut self,
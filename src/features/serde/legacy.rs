@@ -0,0 +1,123 @@
+//! Decoding of data that was serialized with the serde integration of bincode 1.
+//!
+//! bincode 1 had a single serde-based wire format, which this crate still supports when it's used with the matching config:
+//!
+//! |bincode 1|Config|
+//! |---------|------|
+//! |`bincode::serialize`, `bincode::DefaultOptions::new().with_fixint_encoding()`|[`config::legacy()`](crate::config::legacy)|
+//! |`bincode::options()`, `bincode::DefaultOptions::new()`|[`config::standard()`](crate::config::standard)|
+//! |`.with_big_endian()`|`.with_big_endian()`|
+//! |`.with_varint_encoding()` / `.with_fixint_encoding()`|`.with_variable_int_encoding()` / `.with_fixed_int_encoding()`|
+//! |`.with_limit(N)`|`.with_limit::<N>()`|
+//!
+//! With these configs, the [`Deserializer`\] in this module reads bincode 1 data as bincode 1 would:
+//! - sequence, map and string lengths are `u64`s, or varints in varint mode,
+//! - `Option` is a `u8` tag of `0` or `1`, followed by the value if the tag is `1`,
+//! - enum variants (including unit variants) are a `u32` variant index, followed by the content of the variant,
+//! - strings are their UTF-8 bytes prefixed with the length, and `char` is its UTF-8 bytes without a length prefix.
+//!
+//! Trailing bytes are allowed by [`deserialize`\], like `bincode::deserialize` did. Use [`Deserializer::end`\] to reject them, like `bincode::options().deserialize` did.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! # use serde_derive::Deserialize;
+//! #[derive(Deserialize, PartialEq, Debug)]
+//! enum Shape {
+//!     Point,
+//!     Circle { radius: u32 },
+//! }
+//!
+//! // `bincode_1::serialize(&vec![Shape::Point, Shape::Circle { radius: 5 }])`
+//! let bytes = [2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0];
+//!
+//! let shapes: Vec<Shape> = bincode::serde::legacy::deserialize(&bytes, bincode::config::legacy()).unwrap();
+//! assert_eq!(shapes, vec![Shape::Point, Shape::Circle { radius: 5 }]);
+//! ```
+
+use super::{BorrowedSerdeDecoder, DecodeError as SerdeDecodeError};
+use crate::{
+    config::Config,
+    de::{read::SliceReader, Decoder, DecoderImpl},
+    error::DecodeError,
+};
+use serde::de::Deserialize;
+
+/// A serde deserializer for data that was serialized by bincode 1.
+///
+/// See the [module documentation](self) for which config to use.
+pub struct Deserializer<'de, C: Config> {
+    inner: BorrowedSerdeDecoder<'de, DecoderImpl<SliceReader<'de>, C, ()>>,
+    len: usize,
+}
+
+impl<'de, C: Config> Deserializer<'de, C> {
+    /// Creates a deserializer that reads from the given slice.
+    pub const fn from_slice(slice: &'de [u8], config: C) -> Self {
+        Self {
+            inner: BorrowedSerdeDecoder::from_slice(slice, config, ()),
+            len: slice.len(),
+        }
+    }
+
+    /// Return a type implementing `serde::Deserializer`.
+    pub fn as_deserializer<'a>(
+        &'a mut self,
+    ) -> impl serde::Deserializer<'de, Error = DecodeError> + 'a {
+        self.inner.as_deserializer()
+    }
+
+    /// The amount of bytes that have been read so far.
+    pub fn bytes_read(&mut self) -> usize {
+        self.len - self.remaining()
+    }
+
+    fn remaining(&mut self) -> usize {
+        self.inner.de.reader().slice.len()
+    }
+
+    /// Checks that all of the input has been read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TrailingBytes`](SerdeDecodeError::TrailingBytes) if there are bytes left in the input.
+    pub fn end(mut self) -> Result<(), DecodeError> {
+        match self.remaining() {
+            0 => Ok(()),
+            remaining => Err(SerdeDecodeError::TrailingBytes(remaining).into()),
+        }
+    }
+}
+
+/// Deserialize a `T` from data that was serialized by bincode 1, like `bincode::deserialize` did. Trailing bytes are ignored.
+///
+/// See the [module documentation](self) for which config to use.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice cannot be decoded.
+pub fn deserialize<'de, T, C>(slice: &'de [u8], config: C) -> Result<T, DecodeError>
+where
+    T: Deserialize<'de>,
+    C: Config,
+{
+    let mut deserializer = Deserializer::from_slice(slice, config);
+    T::deserialize(deserializer.as_deserializer())
+}
+
+/// Deserialize a `T` from a reader containing data that was serialized by bincode 1, like `bincode::deserialize_from` did.
+///
+/// See the [module documentation](self) for which config to use.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn deserialize_from<T, C, R>(reader: &mut R, config: C) -> Result<T, DecodeError>
+where
+    T: serde::de::DeserializeOwned,
+    C: Config,
+    R: std::io::Read,
+{
+    super::decode_from_std_read(reader, config)
+}
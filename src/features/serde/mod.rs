@@ -10,6 +10,10 @@
 //! - [`Compat`\]
 //! - [`BorrowCompat`\]
 //!
+//! To read data that was serialized by bincode 1, see the [`legacy`\] module.
+//!
+//! The decode functions of this module have no `_with_context` variants: serde's `Deserialize` has no access to a bincode decode context, so there is nothing to pass it to. To decode a type that needs a context next to serde types, derive [Decode] for it, mark the serde fields with `#[bincode(with_serde)]`, and use e.g. [`decode_from_slice_with_context`](crate::decode_from_slice_with_context).
+//!
 //! For interop with bincode's `derive` feature, you can use the `#[bincode(with_serde)]` attribute on each field that implements serde's traits.
 //!
 //! ```
@@ -50,7 +54,56 @@
 //! - `#[serde(tag = "...")]`
 //! - `#[serde(untagged)]`
 //!
-//! **Using any of the above attributes can and will cause issues with bincode and will result in lost data**. Consider using bincode's own derive macro instead.
+//! **Using any of the above attributes can and will cause issues with bincode and will result in lost data**. Consider using bincode's own derive macro instead, or the [self-describing format](#self-describing-format).
+//!
+//! Errors of `Deserialize` implementations are kept as [`DecodeError::Custom`\], with the message exactly as serde formats it, including the unexpected value and what was expected. `Deserialize` implementations that need a self-describing format fail with an error that names the serde method bincode does not support, see [`DecodeError::unsupported_method`\].
+//!
+//! Bincode writes the length of a sequence or map before its elements. When a type serializes a sequence or map without knowing its length up front, e.g. with `Serializer::collect_seq` on a filtered iterator, the elements are buffered until the length is known. This needs the `alloc` feature, and the output is the same as for a sequence with a known length.
+//!
+//! Serde serializes a `Vec<u8>` as a sequence of `u8` elements. Types that call `serialize_bytes`, like the fields marked with `#[serde(with = "serde_bytes")]`, are written with a single write instead, and are read with a single read, borrowed from the input by [`borrow_decode_from_slice`\]. The output is the same either way, and the same as a `Vec<u8>` encoded with [Encode], so bytes written by one can be read by the other.
+//!
+//! # Self-describing format
+//!
+//! Some serde features need the format to describe its own types, e.g. `#[serde(untagged)]`, `#[serde(flatten)]` and types like `serde_json::Value` which call `deserialize_any`. For these, the serde module has a separate, opt-in format:
+//! - [`encode_to_vec_self_describing`\]
+//! - [`encode_into_slice_self_describing`\]
+//! - [`decode_from_slice_self_describing`\]
+//!
+//! **This is a different wire format.** Data encoded with these functions can only be decoded with [`decode_from_slice_self_describing`\], and vice versa.
+//!
+//! Every value is prefixed with a one-byte type tag. Payloads are encoded like bincode would encode them with the given config.
+//!
+//! |Tag|Value|Payload|
+//! |---|-----|-------|
+//! |0|unit, unit struct|-|
+//! |1, 2|`false`, `true`|-|
+//! |3-7|`i8`, `i16`, `i32`, `i64`, `i128`|the integer|
+//! |8-12|`u8`, `u16`, `u32`, `u64`, `u128`|the integer|
+//! |13, 14|`f32`, `f64`|the float|
+//! |15|`char`|the char|
+//! |16|string, unit variant|the string, or the name of the variant|
+//! |17|bytes|the bytes|
+//! |18, 19|`None`, `Some`|nothing, or the value|
+//! |20|sequence, tuple, tuple struct|the elements, followed by tag 22|
+//! |21|map, struct|the keys and values, followed by tag 22. Struct fields are keyed by their name|
+//! |22|end of a sequence or map|-|
+//!
+//! Newtype structs are encoded as their content. Enum variants with data are encoded as a map with a single entry, from the variant name to the content of the variant.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! #[serde(untagged)]
+//! enum Id {
+//!     Number(u64),
+//!     Name(String),
+//! }
+//!
+//! let config = bincode_next::config::standard();
+//! let encoded = bincode_next::serde::encode_to_vec_self_describing(Id::Name("root".into()), config).unwrap();
+//! let (decoded, _): (Id, usize) = bincode_next::serde::decode_from_slice_self_describing(&encoded, config).unwrap();
+//! assert_eq!(decoded, Id::Name("root".into()));
+//! ```
 //!
 //! # Why move away from serde?
 //!
@@ -68,10 +121,13 @@
 
 mod de_borrowed;
 mod de_owned;
+pub mod legacy;
+mod self_describing;
 mod ser;
 
 pub use self::de_borrowed::*;
 pub use self::de_owned::*;
+pub use self::self_describing::*;
 pub use self::ser::*;
 
 /// A serde-specific error that occurred while decoding.
@@ -94,6 +150,12 @@ pub enum DecodeError {
     /// Serde tried decoding a borrowed value from an owned reader. Use `serde_decode_borrowed_from_*` instead
     CannotBorrowOwnedData,
 
+    /// [`legacy::Deserializer::end`\] found bytes left in the input. Contains the amount of bytes that were left.
+    TrailingBytes(usize),
+
+    /// The [self-describing format](index.html#self-describing-format) contained a type tag that is unknown, or not valid at this position.
+    InvalidTypeTag(u8),
+
     /// Could not allocate data like `String` and `Vec<u8>`
     #[cfg(not(feature = "alloc"))]
     CannotAllocate,
@@ -101,6 +163,49 @@ pub enum DecodeError {
     /// Custom serde error but bincode is unable to allocate a string. Set a breakpoint where this is thrown for more information.
     #[cfg(not(feature = "alloc"))]
     CustomError,
+
+    /// A `Deserialize` implementation returned an error with [`serde::de::Error::custom`\], or with one of serde's other constructors like `invalid_type` and `invalid_value`. Contains the message exactly as serde formats it, including the [`Unexpected`](serde::de::Unexpected) value and what was expected.
+    #[cfg(feature = "alloc")]
+    Custom(alloc::boxed::Box<str>),
+
+    /// A `Deserialize` implementation of a struct did not get the field with this name.
+    MissingField(&'static str),
+
+    /// A `Deserialize` implementation of a struct got the field with this name more than once.
+    DuplicateField(&'static str),
+}
+
+impl DecodeError {
+    /// The serde method that bincode does not support, for [`DecodeError::AnyNotSupported`\], [`DecodeError::IdentifierNotSupported`\] and [`DecodeError::IgnoredAnyNotSupported`\].
+    ///
+    /// These are called by `Deserialize` implementations that need a self-describing format, e.g. `#[serde(untagged)]` enums. See the "known issues" list in the serde module for more information.
+    #[must_use]
+    pub const fn unsupported_method(&self) -> Option<&'static str> {
+        match self {
+            Self::AnyNotSupported => Some("deserialize_any"),
+            Self::IdentifierNotSupported => Some("deserialize_identifier"),
+            Self::IgnoredAnyNotSupported => Some("deserialize_ignored_any"),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(method) = self.unsupported_method() {
+            return write!(
+                f,
+                "serde's `{method}` is not supported by bincode, because its format is not self-describing"
+            );
+        }
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Custom(message) => f.write_str(message),
+            Self::MissingField(field) => write!(f, "missing field `{field}`"),
+            Self::DuplicateField(field) => write!(f, "duplicate field `{field}`"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -110,7 +215,15 @@ impl serde::de::Error for crate::error::DecodeError {
         T: core::fmt::Display,
     {
         use alloc::string::ToString;
-        Self::OtherString(msg.to_string())
+        DecodeError::Custom(msg.to_string().into_boxed_str()).into()
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        DecodeError::MissingField(field).into()
+    }
+
+    fn duplicate_field(field: &'static str) -> Self {
+        DecodeError::DuplicateField(field).into()
     }
 }
 
@@ -122,6 +235,14 @@ impl serde::de::Error for crate::error::DecodeError {
     {
         DecodeError::CustomError.into()
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        DecodeError::MissingField(field).into()
+    }
+
+    fn duplicate_field(field: &'static str) -> Self {
+        DecodeError::DuplicateField(field).into()
+    }
 }
 
 #[allow(clippy::from_over_into)]
@@ -135,7 +256,7 @@ impl Into<crate::error::DecodeError> for DecodeError {
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum EncodeError {
-    /// Serde provided bincode with a sequence without a length, which is not supported in bincode
+    /// Serde provided bincode with a sequence or map without a length, which needs the `alloc` feature to be buffered until the length is known
     SequenceMustHaveLength,
 
     /// [Serializer::collect_str] got called but bincode was unable to allocate memory.
@@ -145,6 +266,20 @@ pub enum EncodeError {
     /// Custom serde error but bincode is unable to allocate a string. Set a breakpoint where this is thrown for more information.
     #[cfg(not(feature = "alloc"))]
     CustomError,
+
+    /// A `Serialize` implementation returned an error with [`serde::ser::Error::custom`\]. Contains the message exactly as it was given.
+    #[cfg(feature = "alloc")]
+    Custom(alloc::boxed::Box<str>),
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Custom(message) => f.write_str(message),
+            _ => write!(f, "{self:?}"),
+        }
+    }
 }
 
 #[allow(clippy::from_over_into)]
@@ -162,7 +297,7 @@ impl serde::ser::Error for crate::error::EncodeError {
     {
         use alloc::string::ToString;
 
-        Self::OtherString(msg.to_string())
+        EncodeError::Custom(msg.to_string().into_boxed_str()).into()
     }
 }
 
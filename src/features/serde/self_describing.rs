@@ -0,0 +1,732 @@
+use super::DecodeError as SerdeDecodeError;
+#[cfg(not(feature = "alloc"))]
+use super::EncodeError as SerdeEncodeError;
+use crate::{
+    config::Config,
+    de::{read::SliceReader, BorrowDecode, BorrowDecoder, Decode, DecoderImpl},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use serde::{
+    de::{
+        Deserialize, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+        SeqAccess, VariantAccess, Visitor,
+    },
+    ser::{
+        Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+    },
+};
+
+// The type tags of the self-describing format. See the serde module documentation for the layout.
+const TAG_UNIT: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_I8: u8 = 3;
+const TAG_I16: u8 = 4;
+const TAG_I32: u8 = 5;
+const TAG_I64: u8 = 6;
+const TAG_I128: u8 = 7;
+const TAG_U8: u8 = 8;
+const TAG_U16: u8 = 9;
+const TAG_U32: u8 = 10;
+const TAG_U64: u8 = 11;
+const TAG_U128: u8 = 12;
+const TAG_F32: u8 = 13;
+const TAG_F64: u8 = 14;
+const TAG_CHAR: u8 = 15;
+const TAG_STR: u8 = 16;
+const TAG_BYTES: u8 = 17;
+const TAG_NONE: u8 = 18;
+const TAG_SOME: u8 = 19;
+const TAG_SEQ: u8 = 20;
+const TAG_MAP: u8 = 21;
+const TAG_END: u8 = 22;
+
+/// Encode the given value into a `Vec<u8>` with the [self-describing format](index.html#self-describing-format).
+///
+/// See the [config](../config/index.html) module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the encoding fails.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn encode_to_vec_self_describing<E, C>(val: E, config: C) -> Result<Vec<u8>, EncodeError>
+where
+    E: Serialize,
+    C: Config,
+{
+    let mut encoder = crate::enc::EncoderImpl::new(crate::VecWriter::default(), config);
+    let serializer = SelfDescribingEncoder { enc: &mut encoder };
+    val.serialize(serializer)?;
+    Ok(encoder.into_writer().collect())
+}
+
+/// Encode the given value into the given slice with the [self-describing format](index.html#self-describing-format). Returns the amount of bytes that have been written.
+///
+/// See the [config](../config/index.html) module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the encoding fails. If the slice is too small, this is [`EncodeError::SliceTooSmall`](crate::error::EncodeError::SliceTooSmall) with the amount of bytes the value needs.
+pub fn encode_into_slice_self_describing<E, C>(
+    val: E,
+    dst: &mut [u8],
+    config: C,
+) -> Result<usize, EncodeError>
+where
+    E: Serialize,
+    C: Config,
+{
+    let capacity = dst.len();
+    let mut encoder =
+        crate::enc::EncoderImpl::new(crate::enc::write::SliceWriter::new(dst), config);
+    let serializer = SelfDescribingEncoder { enc: &mut encoder };
+    val.serialize(serializer).map_err(|e| {
+        crate::enc::slice_too_small(e, capacity, || {
+            let mut encoder =
+                crate::enc::EncoderImpl::new(crate::enc::write::SizeWriter::default(), config);
+            val.serialize(SelfDescribingEncoder { enc: &mut encoder })?;
+            Ok(encoder.into_writer().bytes_written)
+        })
+    })?;
+    Ok(encoder.into_writer().bytes_written())
+}
+
+/// Decode a type `D` that was encoded with the [self-describing format](index.html#self-describing-format) from the given slice. Returns the decoded output and the amount of bytes read.
+///
+/// Borrowed `&'de str` and `&'de [u8]` values refer to the given slice.
+///
+/// See the [config](../config/index.html) module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice cannot be decoded.
+pub fn decode_from_slice_self_describing<'de, D, C>(
+    slice: &'de [u8],
+    config: C,
+) -> Result<(D, usize), DecodeError>
+where
+    D: Deserialize<'de>,
+    C: Config,
+{
+    let mut decoder = DecoderImpl::new(SliceReader::new(slice), config, ());
+    let result = D::deserialize(SelfDescribingDecoder::new(&mut decoder, None))?;
+    let bytes_read = slice.len() - decoder.borrow_reader().slice.len();
+    Ok((result, bytes_read))
+}
+
+struct SelfDescribingEncoder<'a, ENC: Encoder> {
+    enc: &'a mut ENC,
+}
+
+impl<'a, ENC: Encoder> SelfDescribingEncoder<'a, ENC> {
+    fn tagged<T: Encode>(self, tag: u8, value: T) -> Result<(), EncodeError> {
+        tag.encode(self.enc)?;
+        value.encode(self.enc)
+    }
+
+    fn compound(self, tag: u8, variant: Option<&str>) -> Result<Compound<'a, ENC>, EncodeError> {
+        if let Some(variant) = variant {
+            TAG_MAP.encode(self.enc)?;
+            TAG_STR.encode(self.enc)?;
+            variant.encode(self.enc)?;
+        }
+        tag.encode(self.enc)?;
+        Ok(Compound {
+            enc: self.enc,
+            variant: variant.is_some(),
+        })
+    }
+}
+
+impl<'a, ENC> Serializer for SelfDescribingEncoder<'a, ENC>
+where
+    ENC: Encoder,
+{
+    type Ok = ();
+
+    type Error = EncodeError;
+
+    type SerializeSeq = Compound<'a, ENC>;
+    type SerializeTuple = Compound<'a, ENC>;
+    type SerializeTupleStruct = Compound<'a, ENC>;
+    type SerializeTupleVariant = Compound<'a, ENC>;
+    type SerializeMap = Compound<'a, ENC>;
+    type SerializeStruct = Compound<'a, ENC>;
+    type SerializeStructVariant = Compound<'a, ENC>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        let tag = if v { TAG_TRUE } else { TAG_FALSE };
+        tag.encode(self.enc)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_I8, v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_I16, v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_I32, v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_I64, v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_I128, v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_U8, v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_U16, v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_U32, v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_U64, v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_U128, v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_F32, v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_F64, v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_CHAR, v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_STR, v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_BYTES, v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        TAG_NONE.encode(self.enc)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        TAG_SOME.encode(self.enc)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        TAG_UNIT.encode(self.enc)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        TAG_UNIT.encode(self.enc)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.tagged(TAG_STR, variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let Self { enc } = self;
+        TAG_MAP.encode(enc)?;
+        TAG_STR.encode(enc)?;
+        variant.encode(enc)?;
+        value.serialize(SelfDescribingEncoder { enc })?;
+        TAG_END.encode(enc)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.compound(TAG_SEQ, None)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.compound(TAG_SEQ, None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.compound(TAG_SEQ, None)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.compound(TAG_SEQ, Some(variant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.compound(TAG_MAP, None)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.compound(TAG_MAP, None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.compound(TAG_MAP, Some(variant))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn collect_str<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: core::fmt::Display + ?Sized,
+    {
+        Err(SerdeEncodeError::CannotCollectStr.into())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct Compound<'a, ENC: Encoder> {
+    enc: &'a mut ENC,
+    /// Set if this compound is the content of an enum variant, which needs to close the outer map as well
+    variant: bool,
+}
+
+impl<ENC: Encoder> Compound<'_, ENC> {
+    fn element<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(SelfDescribingEncoder { enc: self.enc })
+    }
+
+    fn field<T>(&mut self, key: &'static str, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        TAG_STR.encode(self.enc)?;
+        key.encode(self.enc)?;
+        self.element(value)
+    }
+
+    fn finish(self) -> Result<(), EncodeError> {
+        TAG_END.encode(self.enc)?;
+        if self.variant {
+            TAG_END.encode(self.enc)?;
+        }
+        Ok(())
+    }
+}
+
+impl<ENC: Encoder> SerializeSeq for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeTuple for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeTupleStruct for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeTupleVariant for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeMap for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeStruct for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<ENC: Encoder> SerializeStructVariant for Compound<'_, ENC> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+struct SelfDescribingDecoder<'a, 'de, DE: BorrowDecoder<'de>> {
+    de: &'a mut DE,
+    /// The tag of the next value, if it has already been read
+    tag: Option<u8>,
+    pd: PhantomData<&'de ()>,
+}
+
+impl<'a, 'de, DE: BorrowDecoder<'de>> SelfDescribingDecoder<'a, 'de, DE> {
+    const fn new(de: &'a mut DE, tag: Option<u8>) -> Self {
+        Self {
+            de,
+            tag,
+            pd: PhantomData,
+        }
+    }
+
+    fn read_tag(&mut self) -> Result<u8, DecodeError> {
+        match self.tag.take() {
+            Some(tag) => Ok(tag),
+            None => u8::decode(&mut *self.de),
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), DecodeError> {
+        match self.read_tag()? {
+            TAG_END => Ok(()),
+            tag => Err(SerdeDecodeError::InvalidTypeTag(tag).into()),
+        }
+    }
+}
+
+impl<'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SelfDescribingDecoder<'_, 'de, DE> {
+    type Error = DecodeError;
+
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.read_tag()? {
+            TAG_UNIT => visitor.visit_unit(),
+            TAG_FALSE => visitor.visit_bool(false),
+            TAG_TRUE => visitor.visit_bool(true),
+            TAG_I8 => visitor.visit_i8(Decode::decode(self.de)?),
+            TAG_I16 => visitor.visit_i16(Decode::decode(self.de)?),
+            TAG_I32 => visitor.visit_i32(Decode::decode(self.de)?),
+            TAG_I64 => visitor.visit_i64(Decode::decode(self.de)?),
+            TAG_I128 => visitor.visit_i128(Decode::decode(self.de)?),
+            TAG_U8 => visitor.visit_u8(Decode::decode(self.de)?),
+            TAG_U16 => visitor.visit_u16(Decode::decode(self.de)?),
+            TAG_U32 => visitor.visit_u32(Decode::decode(self.de)?),
+            TAG_U64 => visitor.visit_u64(Decode::decode(self.de)?),
+            TAG_U128 => visitor.visit_u128(Decode::decode(self.de)?),
+            TAG_F32 => visitor.visit_f32(Decode::decode(self.de)?),
+            TAG_F64 => visitor.visit_f64(Decode::decode(self.de)?),
+            TAG_CHAR => visitor.visit_char(Decode::decode(self.de)?),
+            TAG_STR => visitor.visit_borrowed_str(BorrowDecode::borrow_decode(self.de)?),
+            TAG_BYTES => visitor.visit_borrowed_bytes(BorrowDecode::borrow_decode(self.de)?),
+            TAG_NONE => visitor.visit_none(),
+            TAG_SOME => visitor.visit_some(SelfDescribingDecoder::new(self.de, None)),
+            TAG_SEQ => {
+                let mut access = Access::new(self.de);
+                let value = visitor.visit_seq(&mut access)?;
+                access.finish()?;
+                Ok(value)
+            }
+            TAG_MAP => {
+                let mut access = Access::new(self.de);
+                let value = visitor.visit_map(&mut access)?;
+                access.finish()?;
+                Ok(value)
+            }
+            tag => Err(SerdeDecodeError::InvalidTypeTag(tag).into()),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        mut self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.read_tag()? {
+            TAG_STR => {
+                let variant: &'de str = BorrowDecode::borrow_decode(self.de)?;
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            TAG_MAP => visitor.visit_enum(Access::new(self.de)),
+            tag => Err(SerdeDecodeError::InvalidTypeTag(tag).into()),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Access to the content of a sequence, map or enum variant.
+struct Access<'a, 'de, DE: BorrowDecoder<'de>> {
+    de: &'a mut DE,
+    /// Set once the end tag of the sequence or map has been read
+    ended: bool,
+    pd: PhantomData<&'de ()>,
+}
+
+impl<'a, 'de, DE: BorrowDecoder<'de>> Access<'a, 'de, DE> {
+    const fn new(de: &'a mut DE) -> Self {
+        Self {
+            de,
+            ended: false,
+            pd: PhantomData,
+        }
+    }
+
+    /// Make sure the end tag is consumed, even if the visitor stopped reading early.
+    fn finish(self) -> Result<(), DecodeError> {
+        if self.ended {
+            Ok(())
+        } else {
+            SelfDescribingDecoder::new(self.de, None).expect_end()
+        }
+    }
+}
+
+impl<'de, DE: BorrowDecoder<'de>> SeqAccess<'de> for Access<'_, 'de, DE> {
+    type Error = DecodeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.ended {
+            return Ok(None);
+        }
+        match u8::decode(&mut *self.de)? {
+            TAG_END => {
+                self.ended = true;
+                Ok(None)
+            }
+            tag => seed
+                .deserialize(SelfDescribingDecoder::new(self.de, Some(tag)))
+                .map(Some),
+        }
+    }
+}
+
+impl<'de, DE: BorrowDecoder<'de>> MapAccess<'de> for Access<'_, 'de, DE> {
+    type Error = DecodeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(SelfDescribingDecoder::new(self.de, None))
+    }
+}
+
+impl<'a, 'de, DE: BorrowDecoder<'de>> EnumAccess<'de> for Access<'a, 'de, DE> {
+    type Error = DecodeError;
+    type Variant = SelfDescribingDecoder<'a, 'de, DE>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(SelfDescribingDecoder::new(&mut *self.de, None))?;
+        Ok((variant, SelfDescribingDecoder::new(self.de, None)))
+    }
+}
+
+impl<'de, DE: BorrowDecoder<'de>> VariantAccess<'de> for SelfDescribingDecoder<'_, 'de, DE> {
+    type Error = DecodeError;
+
+    fn unit_variant(mut self) -> Result<(), Self::Error> {
+        match self.read_tag()? {
+            TAG_UNIT => self.expect_end(),
+            tag => Err(SerdeDecodeError::InvalidTypeTag(tag).into()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(SelfDescribingDecoder::new(&mut *self.de, None))?;
+        SelfDescribingDecoder::new(self.de, None).expect_end()?;
+        Ok(value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = SelfDescribingDecoder::new(&mut *self.de, None).deserialize_any(visitor)?;
+        SelfDescribingDecoder::new(self.de, None).expect_end()?;
+        Ok(value)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.tuple_variant(0, visitor)
+    }
+}
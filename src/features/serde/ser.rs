@@ -1,4 +1,5 @@
 #![allow(deprecated)]
+#[cfg(not(feature = "alloc"))]
 use super::EncodeError as SerdeEncodeError;
 use crate::{
     config::Config,
@@ -6,6 +7,8 @@ use crate::{
     error::EncodeError,
 };
 #[cfg(feature = "alloc")]
+use crate::{enc::EncoderImpl, VecWriter};
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use serde::ser::{
     Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
@@ -42,16 +45,24 @@ where
 ///
 /// # Errors
 ///
-/// Returns an `EncodeError` if the encoding fails.
+/// Returns an `EncodeError` if the encoding fails. If the slice is too small, this is [`EncodeError::SliceTooSmall`](crate::error::EncodeError::SliceTooSmall) with the amount of bytes the value needs.
 pub fn encode_into_slice<E, C>(val: E, dst: &mut [u8], config: C) -> Result<usize, EncodeError>
 where
     E: Serialize,
     C: Config,
 {
+    let capacity = dst.len();
     let mut encoder =
         crate::enc::EncoderImpl::new(crate::enc::write::SliceWriter::new(dst), config);
     let serializer = SerdeEncoder { enc: &mut encoder };
-    val.serialize(serializer)?;
+    val.serialize(serializer).map_err(|e| {
+        crate::enc::slice_too_small(e, capacity, || {
+            let mut encoder =
+                crate::enc::EncoderImpl::new(crate::enc::write::SizeWriter::default(), config);
+            val.serialize(SerdeEncoder { enc: &mut encoder })?;
+            Ok(encoder.into_writer().bytes_written)
+        })
+    })?;
     Ok(encoder.into_writer().bytes_written())
 }
 
@@ -90,7 +101,7 @@ pub(super) struct SerdeEncoder<'a, ENC: Encoder> {
     pub(super) enc: &'a mut ENC,
 }
 
-impl<ENC> Serializer for SerdeEncoder<'_, ENC>
+impl<'a, ENC> Serializer for SerdeEncoder<'a, ENC>
 where
     ENC: Encoder,
 {
@@ -98,13 +109,13 @@ where
 
     type Error = EncodeError;
 
-    type SerializeSeq = Self;
-    type SerializeTuple = Self;
-    type SerializeTupleStruct = Self;
-    type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
-    type SerializeStruct = Self;
-    type SerializeStructVariant = Self;
+    type SerializeSeq = Compound<'a, ENC>;
+    type SerializeTuple = Compound<'a, ENC>;
+    type SerializeTupleStruct = Compound<'a, ENC>;
+    type SerializeTupleVariant = Compound<'a, ENC>;
+    type SerializeMap = Compound<'a, ENC>;
+    type SerializeStruct = Compound<'a, ENC>;
+    type SerializeStructVariant = Compound<'a, ENC>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
         v.encode(self.enc)
@@ -228,14 +239,12 @@ where
         value.serialize(self)
     }
 
-    fn serialize_seq(mut self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        let len = len.ok_or_else(|| SerdeEncodeError::SequenceMustHaveLength.into())?;
-        len.encode(&mut self.enc)?;
-        Ok(self)
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Compound::with_len(self.enc, len)
     }
 
     fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Ok(self)
+        Ok(Compound::new(self.enc))
     }
 
     fn serialize_tuple_struct(
@@ -243,7 +252,7 @@ where
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Ok(self)
+        Ok(Compound::new(self.enc))
     }
 
     fn serialize_tuple_variant(
@@ -254,13 +263,11 @@ where
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
         variant_index.encode(&mut self.enc)?;
-        Ok(self)
+        Ok(Compound::new(self.enc))
     }
 
-    fn serialize_map(mut self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        let len = len.ok_or_else(|| SerdeEncodeError::SequenceMustHaveLength.into())?;
-        len.encode(&mut self.enc)?;
-        Ok(self)
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Compound::with_len(self.enc, len)
     }
 
     fn serialize_struct(
@@ -268,7 +275,7 @@ where
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(self)
+        Ok(Compound::new(self.enc))
     }
 
     fn serialize_struct_variant(
@@ -279,15 +286,31 @@ where
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         variant_index.encode(&mut self.enc)?;
-        Ok(self)
+        Ok(Compound::new(self.enc))
     }
 
-    #[cfg(not(feature = "alloc"))]
-    fn collect_str<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+    fn collect_str<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: core::fmt::Display + ?Sized,
     {
-        Err(SerdeEncodeError::CannotCollectStr.into())
+        use core::fmt::Write;
+
+        // Format the value twice: once to count its bytes for the length, and once to write them. This avoids allocating a `String` for it.
+        let mut counter = StrCounter(0);
+        write!(counter, "{value}").map_err(|_| fmt_error())?;
+        crate::enc::encode_slice_len(&mut *self.enc, counter.0)?;
+
+        let mut writer = StrWriter {
+            writer: self.enc.writer(),
+            remaining: counter.0,
+            error: None,
+        };
+        let result = write!(writer, "{value}");
+        match writer.error {
+            Some(e) => Err(e),
+            None if result.is_err() || writer.remaining != 0 => Err(fmt_error()),
+            None => Ok(()),
+        }
     }
 
     fn is_human_readable(&self) -> bool {
@@ -295,7 +318,128 @@ where
     }
 }
 
-type Compound<'a, ENC> = SerdeEncoder<'a, ENC>;
+/// The error for a `Display` implementation that failed, or wrote something else the second time it was called.
+const fn fmt_error() -> EncodeError {
+    EncodeError::Other("the Display implementation given to collect_str failed")
+}
+
+/// Counts the bytes that are formatted into it.
+struct StrCounter(usize);
+
+impl core::fmt::Write for StrCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 = self.0.checked_add(s.len()).ok_or(core::fmt::Error)?;
+        Ok(())
+    }
+}
+
+/// Writes the bytes that are formatted into it, but no more than `remaining`.
+struct StrWriter<'a, W: Writer> {
+    writer: &'a mut W,
+    remaining: usize,
+    error: Option<EncodeError>,
+}
+
+impl<W: Writer> core::fmt::Write for StrWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        // The length is already written, so writing more than that would corrupt the output
+        self.remaining = self
+            .remaining
+            .checked_sub(s.len())
+            .ok_or(core::fmt::Error)?;
+        self.writer.write(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
+
+/// Serializes the elements of a sequence, map, tuple or struct.
+pub(super) struct Compound<'a, ENC: Encoder> {
+    enc: &'a mut ENC,
+    /// The elements of a sequence or map whose length was not known up front. These are written after the length, once all of them are serialized.
+    #[cfg(feature = "alloc")]
+    buffered: Option<Buffered<ENC::C>>,
+}
+
+#[cfg(feature = "alloc")]
+struct Buffered<C: Config> {
+    encoder: EncoderImpl<VecWriter, C>,
+    len: usize,
+}
+
+impl<'a, ENC: Encoder> Compound<'a, ENC> {
+    const fn new(enc: &'a mut ENC) -> Self {
+        Self {
+            enc,
+            #[cfg(feature = "alloc")]
+            buffered: None,
+        }
+    }
+
+    /// Starts a sequence or map of `len` elements. If `len` is not known, the elements are buffered until `end` is called, so the output is the same as when the length is known.
+    fn with_len(enc: &'a mut ENC, len: Option<usize>) -> Result<Self, EncodeError> {
+        match len {
+            Some(len) => {
+                crate::enc::encode_slice_len(&mut *enc, len)?;
+                Ok(Self::new(enc))
+            }
+            #[cfg(feature = "alloc")]
+            None => {
+                let config = *enc.config();
+                Ok(Self {
+                    enc,
+                    buffered: Some(Buffered {
+                        encoder: EncoderImpl::new(VecWriter::default(), config),
+                        len: 0,
+                    }),
+                })
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => Err(SerdeEncodeError::SequenceMustHaveLength.into()),
+        }
+    }
+
+    /// Serializes an element of a sequence, or a key of a map, and counts it if the elements are buffered.
+    fn serialize_counted<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        #[cfg(feature = "alloc")]
+        if let Some(buffered) = &mut self.buffered {
+            buffered.len += 1;
+            return value.serialize(SerdeEncoder {
+                enc: &mut buffered.encoder,
+            });
+        }
+        value.serialize(SerdeEncoder { enc: self.enc })
+    }
+
+    /// Serializes a value of a map.
+    fn serialize_uncounted<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        #[cfg(feature = "alloc")]
+        if let Some(buffered) = &mut self.buffered {
+            return value.serialize(SerdeEncoder {
+                enc: &mut buffered.encoder,
+            });
+        }
+        value.serialize(SerdeEncoder { enc: self.enc })
+    }
+
+    /// Writes the length and the elements, if they are buffered.
+    fn finish(self) -> Result<(), EncodeError> {
+        #[cfg(feature = "alloc")]
+        if let Some(buffered) = self.buffered {
+            crate::enc::encode_slice_len(&mut *self.enc, buffered.len)?;
+            let bytes: Vec<u8> = buffered.encoder.into_writer().collect();
+            return self.enc.writer().write(&bytes);
+        }
+        Ok(())
+    }
+}
 
 impl<ENC: Encoder> SerializeSeq for Compound<'_, ENC> {
     type Ok = ();
@@ -305,11 +449,11 @@ impl<ENC: Encoder> SerializeSeq for Compound<'_, ENC> {
     where
         T: Serialize + ?Sized,
     {
-        value.serialize(SerdeEncoder { enc: self.enc })
+        self.serialize_counted(value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        self.finish()
     }
 }
 
@@ -369,18 +513,18 @@ impl<ENC: Encoder> SerializeMap for Compound<'_, ENC> {
     where
         T: Serialize + ?Sized,
     {
-        key.serialize(SerdeEncoder { enc: self.enc })
+        self.serialize_counted(key)
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: Serialize + ?Sized,
     {
-        value.serialize(SerdeEncoder { enc: self.enc })
+        self.serialize_uncounted(value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        self.finish()
     }
 }
 
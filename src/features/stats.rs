@@ -0,0 +1,308 @@
+//! Statistics of how many bytes every field of an encoded value takes.
+//!
+//! With the `stats` feature, derived `Encode`, `Decode` and `BorrowDecode` implementations mark every field with [`Encoder::enter`](crate::enc::Encoder::enter) and [`Encoder::leave`](crate::enc::Encoder::leave), or the same methods of the [`Decoder`](crate::de::Decoder). [`StatsWriter`\] and [`StatsReader`\] wrap a writer or reader, and attribute every byte to the path of fields it belongs to. Without the feature, the derive does not emit the markers at all.
+//!
+//! A path consists of the markers that are entered, joined with `/`. A field is marked as `Type.field`, and a field of an enum variant as `Type::Variant.field`, so a path shows the types along the way. Bytes are attributed to the innermost path only: the bytes of a `Vec` field are attributed to the field, the bytes of the fields of a derived struct to those fields. Bytes that are not part of a field, like the tag of an enum, are attributed to the path around them, which is the empty path at the top level. So the bytes of all paths add up to the total.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::stats::StatsWriter;
+//! use bincode::enc::write::SizeWriter;
+//!
+//! #[derive(bincode::Encode)]
+//! struct Header {
+//!     version: u8,
+//!     name: String,
+//! }
+//!
+//! #[derive(bincode::Encode)]
+//! struct Packet {
+//!     header: Header,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let packet = Packet {
+//!     header: Header { version: 1, name: "hi".into() },
+//!     payload: vec![0; 100],
+//! };
+//! let mut writer = StatsWriter::new(SizeWriter::default());
+//! bincode::encode_into_writer(&packet, &mut writer, bincode::config::standard()).unwrap();
+//!
+//! assert_eq!(writer.bytes_written(), 105);
+//! assert_eq!(writer.stats(), [
+//!     ("Packet.header/Header.version".to_string(), 1),
+//!     ("Packet.header/Header.name".to_string(), 3),
+//!     ("Packet.payload".to_string(), 101),
+//! ]);
+//! ```
+//!
+//! Bytes are attributed when they reach the `StatsWriter`, so wrap it around adapters that buffer bytes, like a [`BufferedWriter`](crate::enc::write::BufferedWriter), and not the other way around. A section of [`Encoder::with_length_prefix`](crate::enc::Encoder::with_length_prefix) is buffered as well if the wrapped writer is not a [`SeekWriter`\]. If encoding or decoding fails halfway, the markers of the fields that were not finished are not left, so the statistics after an error are not meaningful.
+
+use crate::{
+    de::read::{BorrowReader, Reader},
+    enc::write::{SeekWriter, Writer},
+    error::{DecodeError, EncodeError},
+};
+use alloc::{string::String, vec::Vec};
+
+/// The paths that are entered, and the bytes attributed to every path so far
+#[derive(Default)]
+struct Stats {
+    path: Vec<&'static str>,
+    entries: Vec<(String, usize)>,
+    /// The index in `entries` of `path`, if it was looked up since the path changed
+    current: Option<usize>,
+}
+
+impl Stats {
+    fn enter(&mut self, name: &'static str) {
+        self.path.push(name);
+        self.current = None;
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+        self.current = None;
+    }
+
+    fn add(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let index = if let Some(index) = self.current {
+            index
+        } else {
+            let path = self.path.join("/");
+            let index = if let Some(index) = self.entries.iter().position(|(p, _)| *p == path) {
+                index
+            } else {
+                self.entries.push((path, 0));
+                self.entries.len() - 1
+            };
+            self.current = Some(index);
+            index
+        };
+        if let Some((_, bytes)) = self.entries.get_mut(index) {
+            *bytes += n;
+        }
+    }
+}
+
+/// A [`Writer`\] that attributes the bytes it writes to the fields they belong to. See the [module documentation](self).
+pub struct StatsWriter<W: Writer> {
+    writer: W,
+    stats: Stats,
+    bytes_written: usize,
+}
+
+impl<W: Writer> StatsWriter<W> {
+    /// Wraps `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            stats: Stats::default(),
+            bytes_written: 0,
+        }
+    }
+
+    /// The amount of bytes written so far, which is the sum of the bytes of all paths.
+    #[must_use]
+    pub const fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Every path that bytes were written to, with the amount of bytes, in the order in which the paths were first written to.
+    #[must_use]
+    pub fn stats(&self) -> &[(String, usize)] {
+        &self.stats.entries
+    }
+
+    /// Returns the wrapped writer and the statistics.
+    pub fn into_parts(self) -> (W, Vec<(String, usize)>) {
+        (self.writer, self.stats.entries)
+    }
+}
+
+impl<W: Writer> Writer for StatsWriter<W> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write(bytes)?;
+        self.stats.add(bytes.len());
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_byte_slice(bytes)?;
+        self.stats.add(bytes.len());
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), EncodeError> {
+        self.writer.flush()
+    }
+
+    #[inline]
+    fn as_seek_writer(&mut self) -> Option<&mut dyn SeekWriter> {
+        self.writer.as_seek_writer()?;
+        Some(self)
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        self.stats.enter(name);
+    }
+
+    fn leave(&mut self) {
+        self.stats.leave();
+    }
+}
+
+/// Overwritten bytes are not counted again. [`Writer::as_seek_writer`] only returns this writer if the wrapped writer is a `SeekWriter` as well.
+impl<W: Writer> SeekWriter for StatsWriter<W> {
+    #[inline]
+    fn position(&self) -> usize {
+        self.bytes_written
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.as_seek_writer().map_or(
+            Err(EncodeError::Other(
+                "write_at needs a StatsWriter over a SeekWriter",
+            )),
+            |writer| writer.write_at(offset, bytes),
+        )
+    }
+}
+
+/// A [`Reader`\] that attributes the bytes it reads to the fields they belong to, like a [`StatsWriter`\] does for the bytes it writes. See the [module documentation](self).
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::{read::SliceReader, Decode, DecoderImpl};
+/// use bincode::stats::StatsReader;
+///
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let config = bincode::config::legacy();
+/// let bytes = bincode::encode_to_vec(Point { x: 1, y: 2 }, config).unwrap();
+///
+/// let mut reader = StatsReader::new(SliceReader::new(&bytes));
+/// let point = Point::decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+/// assert_eq!(point, Point { x: 1, y: 2 });
+/// assert_eq!(reader.stats(), [("Point.x".to_string(), 4), ("Point.y".to_string(), 4)]);
+/// ```
+pub struct StatsReader<R: Reader> {
+    reader: R,
+    stats: Stats,
+    bytes_read: usize,
+}
+
+impl<R: Reader> StatsReader<R> {
+    /// Wraps `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            stats: Stats::default(),
+            bytes_read: 0,
+        }
+    }
+
+    /// The amount of bytes read so far, which is the sum of the bytes of all paths.
+    #[must_use]
+    pub const fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Every path that bytes were read from, with the amount of bytes, in the order in which the paths were first read from.
+    #[must_use]
+    pub fn stats(&self) -> &[(String, usize)] {
+        &self.stats.entries
+    }
+
+    /// Returns the wrapped reader and the statistics.
+    pub fn into_parts(self) -> (R, Vec<(String, usize)>) {
+        (self.reader, self.stats.entries)
+    }
+
+    fn add(&mut self, n: usize) {
+        self.stats.add(n);
+        self.bytes_read += n;
+    }
+}
+
+impl<R: Reader> Reader for StatsReader<R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.add(bytes.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [core::mem::MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        let bytes = self.reader.read_uninit(bytes)?;
+        self.add(bytes.len());
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn peek_read(&mut self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.add(n);
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.reader.skip(n)?;
+        self.add(n);
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_hint(&self) -> Option<usize> {
+        self.reader.remaining_hint()
+    }
+
+    #[cfg(feature = "bytes")]
+    fn take_shared_bytes(&mut self, length: usize) -> Option<bytes::Bytes> {
+        let bytes = self.reader.take_shared_bytes(length)?;
+        self.add(length);
+        Some(bytes)
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        self.stats.enter(name);
+    }
+
+    fn leave(&mut self) {
+        self.stats.leave();
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>> BorrowReader<'storage> for StatsReader<R> {
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.add(length);
+        Ok(bytes)
+    }
+
+    fn take_bytes_mut(&mut self, length: usize) -> Result<&'storage mut [u8], DecodeError> {
+        let bytes = self.reader.take_bytes_mut(length)?;
+        self.add(length);
+        Ok(bytes)
+    }
+}
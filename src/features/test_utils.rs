@@ -0,0 +1,178 @@
+//! Assertions for testing `Encode` and `Decode` implementations, e.g. hand-written ones.
+//!
+//! [`assert_roundtrip`\] checks a value with every standard config, [`assert_roundtrip_with_config`\] with a single config. Both panic with a message that names the config and shows the encoded bytes when a check fails, like `assert_eq!` does.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::de::{Decode, Decoder};
+//! use bincode::enc::{Encode, Encoder};
+//! use bincode::error::{DecodeError, EncodeError};
+//!
+//! /// Encoded as the amount of minutes since midnight
+//! #[derive(PartialEq, Debug)]
+//! struct Time {
+//!     hour: u8,
+//!     minute: u8,
+//! }
+//!
+//! impl Encode for Time {
+//!     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+//!         (u16::from(self.hour) * 60 + u16::from(self.minute)).encode(encoder)
+//!     }
+//! }
+//!
+//! impl<Context> Decode<Context> for Time {
+//!     fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+//!         let minutes = u16::decode(decoder)?;
+//!         if minutes >= 24 * 60 {
+//!             return Err(DecodeError::Other("time out of range"));
+//!         }
+//!         Ok(Self {
+//!             hour: (minutes / 60) as u8,
+//!             minute: (minutes % 60) as u8,
+//!         })
+//!     }
+//! }
+//!
+//! bincode::test_utils::assert_roundtrip(&Time { hour: 23, minute: 59 });
+//! ```
+//!
+//! For every config, a round trip checks that:
+//! - the value can be encoded, and the size that [`SizeWriter`](crate::enc::write::SizeWriter) counts is the amount of bytes that are written,
+//! - encoding into a slice that is one byte too short fails,
+//! - the encoded bytes decode to a value that is equal to the original value,
+//! - decoding consumes exactly all of the encoded bytes,
+//! - encoding the decoded value results in exactly the same bytes, and
+//! - decoding every strict prefix of the encoded bytes fails with a [`DecodeError`\] instead of panicking or returning a value.
+//!
+//! Decoding every prefix takes time quadratic in the size of the encoded value, so keep the values small. Types that are not equal to themselves (e.g. `f32::NAN`) can be checked with [`assert_roundtrip_by`\], and types without a deterministic encoding (e.g. a `HashMap` with more than one entry) can not be checked at all.
+
+use crate::{
+    config::{self, Config},
+    de::Decode,
+    enc::{write::SizeWriter, Encode},
+    error::DecodeError,
+};
+use alloc::vec;
+use core::{any::type_name, fmt::Debug};
+
+/// Checks that `value` survives a round trip with every standard config. See the [module documentation](self) for the checks.
+///
+/// The configs are [`standard`](config::standard) with every combination of endianness and int encoding, and with each of the options that change how values are decoded: fallible allocation, lossless paths, lossy UTF-8, exact `SystemTime`s and scalar chars.
+///
+/// # Panics
+///
+/// Panics if one of the checks fails.
+pub fn assert_roundtrip<T>(value: &T)
+where
+    T: Encode + Decode<()> + PartialEq + Debug,
+{
+    assert_roundtrip_by(value, T::eq);
+}
+
+/// Checks that `value` survives a round trip with every standard config, comparing the decoded value with `eq`. See [`assert_roundtrip`\].
+///
+/// # Panics
+///
+/// Panics if one of the checks fails.
+pub fn assert_roundtrip_by<T, F>(value: &T, eq: F)
+where
+    T: Encode + Decode<()> + Debug,
+    F: Fn(&T, &T) -> bool,
+{
+    let standard = config::standard();
+    assert_roundtrip_with_config_by(value, standard, &eq);
+    assert_roundtrip_with_config_by(value, standard.with_big_endian(), &eq);
+    assert_roundtrip_with_config_by(value, standard.with_fixed_int_encoding(), &eq);
+    assert_roundtrip_with_config_by(
+        value,
+        standard.with_big_endian().with_fixed_int_encoding(),
+        &eq,
+    );
+    assert_roundtrip_with_config_by(value, standard.with_fallible_allocation(), &eq);
+    assert_roundtrip_with_config_by(value, standard.with_lossless_paths(), &eq);
+    assert_roundtrip_with_config_by(value, standard.with_lossy_utf8_strings(), &eq);
+    assert_roundtrip_with_config_by(value, standard.with_exact_system_time(), &eq);
+    assert_roundtrip_with_config_by(value, standard.with_scalar_char_encoding(), &eq);
+}
+
+/// Checks that `value` survives a round trip with `config`. See the [module documentation](self) for the checks.
+///
+/// # Panics
+///
+/// Panics if one of the checks fails.
+pub fn assert_roundtrip_with_config<T, C>(value: &T, config: C)
+where
+    T: Encode + Decode<()> + PartialEq + Debug,
+    C: Config,
+{
+    assert_roundtrip_with_config_by(value, config, T::eq);
+}
+
+/// Checks that `value` survives a round trip with `config`, comparing the decoded value with `eq`. See [`assert_roundtrip_with_config`\].
+///
+/// # Panics
+///
+/// Panics if one of the checks fails.
+pub fn assert_roundtrip_with_config_by<T, C, F>(value: &T, config: C, eq: F)
+where
+    T: Encode + Decode<()> + Debug,
+    C: Config,
+    F: Fn(&T, &T) -> bool,
+{
+    let config_name = type_name::<C>();
+    let encoded = crate::encode_to_vec(value, config)
+        .unwrap_or_else(|e| panic!("failed to encode {value:?} with {config_name}: {e}"));
+
+    let mut size = SizeWriter::default();
+    crate::encode_into_writer(value, &mut size, config).unwrap_or_else(|e| {
+        panic!("failed to count the size of {value:?} with {config_name}: {e}")
+    });
+    assert!(
+        size.bytes_written == encoded.len(),
+        "the size of {value:?} with {config_name} is {}, but {} bytes were written\nbytes: {encoded:?}",
+        size.bytes_written,
+        encoded.len(),
+    );
+
+    if let Some(short) = encoded.len().checked_sub(1) {
+        let mut buffer = vec![0; short];
+        assert!(
+            crate::encode_into_slice(value, &mut buffer, config).is_err(),
+            "encoding {value:?} with {config_name} into {short} bytes did not fail, but it needs {} bytes",
+            encoded.len(),
+        );
+    }
+
+    let (decoded, read) = crate::decode_from_slice::<T, C>(&encoded, config).unwrap_or_else(|e| {
+        panic!("failed to decode {value:?} with {config_name}: {e}\nbytes: {encoded:?}")
+    });
+    assert!(
+        eq(value, &decoded),
+        "the decoded value is not equal to the original value with {config_name}\noriginal: {value:?}\ndecoded:  {decoded:?}\nbytes: {encoded:?}",
+    );
+    assert!(
+        read == encoded.len(),
+        "encoded {value:?} with {config_name} into {} bytes, but decoding read {read} bytes\nbytes: {encoded:?}",
+        encoded.len(),
+    );
+
+    let re_encoded = crate::encode_to_vec(&decoded, config).unwrap_or_else(|e| {
+        panic!("failed to encode the decoded {decoded:?} with {config_name}: {e}")
+    });
+    assert!(
+        re_encoded == encoded,
+        "the decoded value encodes to different bytes with {config_name}\noriginal: {encoded:?}\ndecoded:  {re_encoded:?}",
+    );
+
+    for len in 0..encoded.len() {
+        let result: Result<(T, usize), DecodeError> =
+            crate::decode_from_slice(&encoded[..len], config);
+        if let Ok((decoded, _)) = result {
+            panic!(
+                "decoding the first {len} of {} bytes of {value:?} with {config_name} did not fail, but returned {decoded:?}\nbytes: {encoded:?}",
+                encoded.len(),
+            );
+        }
+    }
+}
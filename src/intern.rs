@@ -0,0 +1,188 @@
+//! Strings that repeat throughout the input, decoded into a single shared `Arc<str>` instead of a new `String` for every occurrence.
+//!
+//! An [`Interned`\] is encoded exactly like a `String`, so it can replace a `String` field without changing the format. When it is decoded, the string is looked up in an [`Interner`\] in the decode context: a string that was seen before is another reference to the same `Arc<str>`, and only new strings are allocated and added to the interner.
+//!
+//! The context must implement [`InternContext`\]. Pass an `Interner` as the context, or implement the trait for your own context. Derived types that contain interned strings need `#[bincode(decode_context = "Interner")]`.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+//! use bincode::intern::{Interned, Interner};
+//! use std::sync::Arc;
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! #[bincode(decode_context = "Interner")]
+//! struct Record {
+//!     id: u32,
+//!     country: Interned,
+//! }
+//!
+//! // encoded like a `String`
+//! let records: Vec<(u32, &str)> = (0..100).map(|id| (id, ["NL", "DE"][id as usize % 2])).collect();
+//! let config = bincode::config::standard();
+//! let encoded = bincode::encode_to_vec(&records, config).unwrap();
+//!
+//! let mut decoder = DecoderImpl::new(SliceReader::new(&encoded), config, Interner::new());
+//! let decoded = Vec::<Record>::decode(&mut decoder).unwrap();
+//! assert_eq!(&*decoded[3].country, "DE");
+//! assert!(Arc::ptr_eq(&decoded[1].country.0, &decoded[99].country.0));
+//! assert_eq!(decoder.context().len(), 2);
+//! ```
+//!
+//! When it is borrow-decoded, e.g. with [`borrow_decode_from_slice_with_context`](crate::borrow_decode_from_slice_with_context), the string is looked up in the input itself, so a string that was seen before is never copied. Otherwise short strings are read into a buffer on the stack to look them up, and longer ones into a temporary `Vec`.
+
+use crate::{
+    config::{InternalUtf8Config, Utf8Decoding},
+    de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::{borrow::Cow, collections::BTreeSet, string::String, sync::Arc};
+
+/// The longest string that is read into a buffer on the stack to look it up, instead of into a `Vec`.
+const STACK_BUFFER_LEN: usize = 64;
+
+/// An `Arc<str>` that is encoded like a `String`, and looked up in the [`Interner`\] of the context when it is decoded. See the [module documentation](self).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interned<T: ?Sized = str>(pub Arc<T>);
+
+impl<T: ?Sized> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for Interned<T> {
+    fn from(value: Arc<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Interned {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<T: ?Sized> core::ops::Deref for Interned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl Encode for Interned {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context: InternContext> Decode<Context> for Interned {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_bytes_read(len)?;
+        if len <= STACK_BUFFER_LEN {
+            let mut buffer = [0u8; STACK_BUFFER_LEN];
+            let bytes = &mut buffer[..len];
+            decoder.reader().read(bytes)?;
+            intern(decoder, bytes)
+        } else {
+            let bytes = crate::plain::decode_byte_vec(decoder, len)?;
+            intern(decoder, &bytes)
+        }
+    }
+}
+
+impl<'de, Context: InternContext> BorrowDecode<'de, Context> for Interned {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let bytes = <&[u8]>::borrow_decode(decoder)?;
+        intern(decoder, bytes)
+    }
+}
+
+/// Validates the bytes of a string like `String` does, and looks it up in the interner of the context.
+fn intern<D: Decoder>(decoder: &mut D, bytes: &[u8]) -> Result<Interned, DecodeError>
+where
+    D::Context: InternContext,
+{
+    let string = match crate::de::str_from_utf8(bytes) {
+        Ok(string) => Cow::Borrowed(string),
+        Err(e) => match D::C::UTF8_DECODING {
+            Utf8Decoding::Strict => return Err(e),
+            Utf8Decoding::Lossy => String::from_utf8_lossy(bytes),
+        },
+    };
+    Ok(Interned(decoder.context().interner().intern(&string)))
+}
+
+/// A decode context that keeps the strings that [`Interned`\] values refer to. See the [module documentation](self).
+pub trait InternContext {
+    /// Returns the interned strings.
+    fn interner(&mut self) -> &mut Interner;
+}
+
+/// A set of strings, that hands out another reference to a string that it already contains instead of a new allocation. See the [module documentation](self).
+///
+/// Unlike a [`SharedTable`](crate::shared::SharedTable), an interner can be used for any amount of values, e.g. for all messages of a stream, so strings are shared between them as well.
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    strings: BTreeSet<Arc<str>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            strings: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the shared reference to `string`, and adds it first if the interner does not contain it yet.
+    pub fn intern(&mut self, string: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(string) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = string.into();
+        self.strings.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Returns the shared reference to `string`, if the interner contains it.
+    #[must_use]
+    pub fn get(&self, string: &str) -> Option<Arc<str>> {
+        self.strings.get(string).cloned()
+    }
+
+    /// Returns the amount of distinct strings in the interner.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the interner contains no strings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Removes all strings from the interner. Strings that were decoded before stay valid.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
+
+impl InternContext for Interner {
+    fn interner(&mut self) -> &mut Interner {
+        self
+    }
+}
+
+impl<T: InternContext> InternContext for &mut T {
+    fn interner(&mut self) -> &mut Interner {
+        T::interner(self)
+    }
+}
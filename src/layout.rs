@@ -0,0 +1,761 @@
+//! A description of the bytes that bincode writes for a type, for parsing them in other languages.
+//!
+//! Types implement [`EncodedLayout`] to describe how their [`Encode`](crate::Encode) implementation lays out its bytes, as a tree of [`Layout`] values. Structs and enums can derive it with `#[derive(EncodedLayout)]` (with the `derive` feature), which describes the layout of the derived `Encode`. [`Layout::to_json`] renders the tree as JSON, with the details of a config filled in, as the input for a code generator on the other side.
+//!
+//! This is a description of the layout, not a hash of it: two types with the same layout are not distinguished by their names.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::layout::{EncodedLayout, Layout};
+//!
+//! #[derive(bincode::Encode, bincode::EncodedLayout)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let json = Point::layout().to_json(bincode::config::standard());
+//! assert_eq!(
+//!     json,
+//!     r#"{"endian":"little","layout":{"kind":"struct","name":"Point","fields":[{"name":"x","layout":{"kind":"int","signed":true,"bits":32,"encoding":"varint"}},{"name":"y","layout":{"kind":"int","signed":true,"bits":32,"encoding":"varint"}}]}}"#
+//! );
+//! ```
+//!
+//! # The JSON format
+//!
+//! The JSON is an object with the `"endian"` of the config (`"little"` or `"big"`) and the `"layout"` of the type. Every layout is an object with a `"kind"`, and the other members depend on the kind:
+//!
+//! |kind    |members|bytes|
+//! |--------|-------|-----|
+//! |`unit`  ||nothing|
+//! |`bool`  ||1 byte, `0` or `1`|
+//! |`int`   |`signed`, `bits`, `encoding`|`bits / 8` bytes if `encoding` is `"fixed"`, or a varint (see the [config](crate::config) module) if it is `"varint"`. 8-bit integers are always `"fixed"`.|
+//! |`float` |`bits`|`bits / 8` bytes, the IEEE 754 bit pattern|
+//! |`char`  ||1 to 4 bytes, the UTF-8 encoding of the char|
+//! |`str`   |`len`|the length in bytes as the `len` int layout, followed by that many bytes of UTF-8|
+//! |`option`|`value`|1 byte, `0` for none, or `1` followed by the `value` layout|
+//! |`seq`   |`len`, `element`|the amount of elements as the `len` int layout, followed by the elements|
+//! |`array` |`len`, `element`|exactly `len` elements, without a length in front of them|
+//! |`map`   |`len`, `key`, `value`|the amount of entries as the `len` int layout, followed by the entries, each a key followed by its value|
+//! |`tuple` |`elements`|every layout of `elements` in order|
+//! |`struct`|`name`, `fields`|the layout of every field in order. A field is an object with a `name` and a `layout`. The fields of tuple structs are named `"0"`, `"1"`, ...|
+//! |`enum`  |`name`, `tag`, `variants`|the tag of the variant as the `tag` int layout, followed by the fields of the variant. A variant is an object with a `name`, its `tag` as a number, and its `fields`.|
+//! |`string_tagged_enum`|`name`, `len`, `variants`|the name of the variant as a `str` with the `len` int layout, followed by the fields of the variant, for an enum with `#[bincode(string_tags)]`. A variant is an object with a `name` and its `fields`.|
+//! |`padded`|`size`, `layout`|the `layout`, followed by zero bytes up to a total of `size` bytes, for a field with `#[bincode(pad_to = N)]` or a struct with `#[bincode(pad_struct_to = N)]`|
+//! |`length_prefixed`|`layout`|the length of the `layout` in bytes as a `u64` of 8 bytes, also with varint encoding, followed by the `layout`, for a struct or enum with `#[bincode(length_prefixed)]`. A decoder skips the bytes after the `layout` up to the length.|
+//! |`fixed_len`|`len`, `padding`|exactly `len` bytes without a length in front of them, for a field with `#[bincode(len = N)]`. A shorter value is followed by the `padding` byte, `"zero"` or `"space"`, up to `len` bytes, and a value with a `"none"` padding is always `len` bytes.|
+//! |`bitmap_option`|`value`|the `value` layout if the bit of the option is set in the option bitmap of its struct, or nothing. A struct with `#[bincode(option_bitmap)]` starts with a bitmap of `n / 8` bytes, rounded up, for its `n` fields with this layout, with a bit for each of them in order, starting at the lowest bit of the first byte.|
+//! |`remaining_bytes`||all bytes up to the end of the input, without a length in front of them. This is always the last field of its type.|
+//! |`opaque`|`name`|unknown, e.g. a field that is encoded with serde. The `name` is the Rust type.|
+//!
+//! # Recursive types
+//!
+//! The layout of a type that contains itself, e.g. a tree whose nodes hold a `Vec` of nodes, would be infinite. Calling [`EncodedLayout::layout`] on such a type overflows the stack. Implement [`EncodedLayout`] by hand for these types, and describe the recursive part as [`Layout::Opaque`].
+
+use crate::config::{Config, Endianness, FixedLenPadding, IntEncoding, LengthEncoding, TagType};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    task::Poll,
+    time::Duration,
+};
+
+/// Describes the bytes that bincode writes for a type.
+///
+/// See the [module documentation](self) for how to derive this, and how the layout is rendered as JSON.
+pub trait EncodedLayout {
+    /// Returns the layout of the encoded bytes of `Self`.
+    fn layout() -> Layout;
+}
+
+/// The layout of the encoded bytes of a type. See the [module documentation](self) for what is written for every kind of layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Layout {
+    /// Nothing is written, e.g. for `()` and `PhantomData`.
+    Unit,
+    /// A single byte, `0` or `1`.
+    Bool,
+    /// An integer that is encoded with the int encoding of the config. `usize` and `isize` are encoded as 64 bits.
+    Int {
+        /// Whether the integer is signed
+        signed: bool,
+        /// The width of the integer in bits
+        bits: u32,
+    },
+    /// A floating point number, as its bytes.
+    Float {
+        /// The width of the float in bits
+        bits: u32,
+    },
+    /// A `char`, as 1 to 4 bytes of UTF-8, or 4 bytes of its Unicode scalar value with [`with_scalar_char_encoding`](crate::config::Configuration::with_scalar_char_encoding).
+    Char,
+    /// A string, as its length in bytes followed by its bytes.
+    Str,
+    /// A byte that tells if a value is present, followed by the value if it is.
+    Option(Box<Self>),
+    /// A length, followed by that many elements.
+    Seq(Box<Self>),
+    /// A fixed amount of elements, without a length.
+    Array {
+        /// The amount of elements
+        len: usize,
+        /// The layout of every element
+        element: Box<Self>,
+    },
+    /// A length, followed by that many key-value pairs.
+    Map {
+        /// The layout of every key
+        key: Box<Self>,
+        /// The layout of every value
+        value: Box<Self>,
+    },
+    /// The elements of a tuple, in order.
+    Tuple(Vec<Self>),
+    /// The fields of a struct, in order.
+    Struct {
+        /// The name of the struct
+        name: &'static str,
+        /// The fields of the struct
+        fields: Vec<Field>,
+    },
+    /// The tag of a variant, followed by the fields of that variant.
+    Enum {
+        /// The name of the enum
+        name: &'static str,
+        /// How the tag is encoded, if the enum has `#[bincode(tag_type = "...")]`. Otherwise it is a `u32` with the int encoding of the config, or a `u64` if the tag of a variant does not fit in a `u32`.
+        tag: Option<TagType>,
+        /// The variants of the enum
+        variants: Vec<Variant>,
+    },
+    /// The name of a variant as a string, followed by the fields of that variant, for an enum with `#[bincode(string_tags)]`.
+    StringTaggedEnum {
+        /// The name of the enum
+        name: &'static str,
+        /// The variants of the enum. Their `tag` is not written, and is their index.
+        variants: Vec<Variant>,
+    },
+    /// A value followed by zero bytes up to a fixed size, for a field with `#[bincode(pad_to = N)]` or a struct with `#[bincode(pad_struct_to = N)]`.
+    Padded {
+        /// The amount of bytes of the value and its padding
+        size: usize,
+        /// The layout of the value
+        layout: Box<Self>,
+    },
+    /// The length of a value in bytes as a `u64` of 8 bytes, followed by the value, for a struct or enum with `#[bincode(length_prefixed)]`.
+    LengthPrefixed(Box<Self>),
+    /// Exactly `len` bytes, without a length in front of them, for a field with `#[bincode(len = N)]`.
+    FixedLen {
+        /// The amount of bytes
+        len: usize,
+        /// What a shorter value is padded with
+        padding: FixedLenPadding,
+    },
+    /// An option of a struct with `#[bincode(option_bitmap)]`, which is only written if its bit in the option bitmap of the struct is set.
+    BitmapOption(Box<Self>),
+    /// All remaining bytes of the input, without a length in front of them, for a field with `#[bincode(remaining_bytes)]`.
+    RemainingBytes,
+    /// Bytes with an unknown layout, e.g. a field that is encoded with serde.
+    Opaque {
+        /// The name of the type
+        name: &'static str,
+    },
+}
+
+/// A named field of a [`Layout::Struct`] or [`Variant`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the field. The fields of tuple structs and tuple variants are named `"0"`, `"1"`, ...
+    pub name: &'static str,
+    /// The layout of the field
+    pub layout: Layout,
+}
+
+impl Field {
+    /// Creates a field with the given name and layout.
+    #[must_use]
+    pub const fn new(name: &'static str, layout: Layout) -> Self {
+        Self { name, layout }
+    }
+}
+
+/// A variant of a [`Layout::Enum`] or [`Layout::StringTaggedEnum`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// The name of the variant
+    pub name: &'static str,
+    /// The tag that is written for this variant
+    pub tag: u64,
+    /// The fields of the variant, in order
+    pub fields: Vec<Field>,
+}
+
+impl Variant {
+    /// Creates a variant with the given name, tag and fields.
+    #[must_use]
+    pub const fn new(name: &'static str, tag: u64, fields: Vec<Field>) -> Self {
+        Self { name, tag, fields }
+    }
+}
+
+impl Layout {
+    /// Creates the layout of `layout`, padded to `size` bytes.
+    #[must_use]
+    pub fn padded(size: usize, layout: Self) -> Self {
+        Self::Padded {
+            size,
+            layout: Box::new(layout),
+        }
+    }
+
+    /// Creates the layout of `layout` with its length in front of it.
+    #[must_use]
+    pub fn length_prefixed(layout: Self) -> Self {
+        Self::LengthPrefixed(Box::new(layout))
+    }
+
+    /// Creates the layout of a field with `#[bincode(len = N)]`.
+    #[must_use]
+    pub const fn fixed_len(len: usize, padding: FixedLenPadding) -> Self {
+        Self::FixedLen { len, padding }
+    }
+
+    /// Creates the layout of an option in the option bitmap of a struct, with the layout of its value.
+    #[must_use]
+    pub fn bitmap_option(value: Self) -> Self {
+        Self::BitmapOption(Box::new(value))
+    }
+
+    /// Renders the layout as JSON, for the given config. See the [module documentation](self) for the format.
+    #[must_use]
+    pub fn to_json<C: Config>(&self, config: C) -> String {
+        let mut out = String::new();
+        out.push_str(r#"{"endian":"#);
+        out.push_str(match config.endianness() {
+            Endianness::Little => r#""little""#,
+            Endianness::Big => r#""big""#,
+        });
+        out.push_str(r#","layout":"#);
+        self.write_json(config.int_encoding(), config.length_encoding(), &mut out);
+        out.push('}');
+        out
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn write_json(
+        &self,
+        int_encoding: IntEncoding,
+        length_encoding: LengthEncoding,
+        out: &mut String,
+    ) {
+        match self {
+            Self::Unit => out.push_str(r#"{"kind":"unit"}"#),
+            Self::Bool => out.push_str(r#"{"kind":"bool"}"#),
+            Self::Int { signed, bits } => {
+                let encoding = if *bits > 8 && matches!(int_encoding, IntEncoding::Variable) {
+                    "varint"
+                } else {
+                    "fixed"
+                };
+                out.push_str(r#"{"kind":"int","signed":"#);
+                out.push_str(if *signed { "true" } else { "false" });
+                out.push_str(r#","bits":"#);
+                out.push_str(&bits.to_string());
+                out.push_str(r#","encoding":""#);
+                out.push_str(encoding);
+                out.push_str(r#""}"#);
+            }
+            Self::Float { bits } => {
+                out.push_str(r#"{"kind":"float","bits":"#);
+                out.push_str(&bits.to_string());
+                out.push('}');
+            }
+            Self::Char => out.push_str(r#"{"kind":"char"}"#),
+            Self::Str => {
+                out.push_str(r#"{"kind":"str","len":"#);
+                write_json_len(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Option(value) => {
+                out.push_str(r#"{"kind":"option","value":"#);
+                value.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Seq(element) => {
+                out.push_str(r#"{"kind":"seq","len":"#);
+                write_json_len(int_encoding, length_encoding, out);
+                out.push_str(r#","element":"#);
+                element.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Array { len, element } => {
+                out.push_str(r#"{"kind":"array","len":"#);
+                out.push_str(&len.to_string());
+                out.push_str(r#","element":"#);
+                element.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Map { key, value } => {
+                out.push_str(r#"{"kind":"map","len":"#);
+                write_json_len(int_encoding, length_encoding, out);
+                out.push_str(r#","key":"#);
+                key.write_json(int_encoding, length_encoding, out);
+                out.push_str(r#","value":"#);
+                value.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Tuple(elements) => {
+                out.push_str(r#"{"kind":"tuple","elements":["#);
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    element.write_json(int_encoding, length_encoding, out);
+                }
+                out.push_str("]}");
+            }
+            Self::Struct { name, fields } => {
+                out.push_str(r#"{"kind":"struct","name":"#);
+                write_json_str(name, out);
+                out.push_str(r#","fields":"#);
+                write_json_fields(fields, int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Enum {
+                name,
+                tag,
+                variants,
+            } => {
+                out.push_str(r#"{"kind":"enum","name":"#);
+                write_json_str(name, out);
+                out.push_str(r#","tag":"#);
+                write_json_tag(*tag, variants, int_encoding, length_encoding, out);
+                out.push_str(r#","variants":"#);
+                write_json_variants(variants, true, int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::StringTaggedEnum { name, variants } => {
+                out.push_str(r#"{"kind":"string_tagged_enum","name":"#);
+                write_json_str(name, out);
+                out.push_str(r#","len":"#);
+                write_json_len(int_encoding, length_encoding, out);
+                out.push_str(r#","variants":"#);
+                write_json_variants(variants, false, int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::Padded { size, layout } => {
+                out.push_str(r#"{"kind":"padded","size":"#);
+                out.push_str(&size.to_string());
+                out.push_str(r#","layout":"#);
+                layout.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::LengthPrefixed(layout) => {
+                out.push_str(r#"{"kind":"length_prefixed","layout":"#);
+                layout.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::FixedLen { len, padding } => {
+                out.push_str(r#"{"kind":"fixed_len","len":"#);
+                out.push_str(&len.to_string());
+                out.push_str(r#","padding":"#);
+                out.push_str(match padding {
+                    FixedLenPadding::None => r#""none""#,
+                    FixedLenPadding::Zero => r#""zero""#,
+                    FixedLenPadding::Space => r#""space""#,
+                });
+                out.push('}');
+            }
+            Self::BitmapOption(value) => {
+                out.push_str(r#"{"kind":"bitmap_option","value":"#);
+                value.write_json(int_encoding, length_encoding, out);
+                out.push('}');
+            }
+            Self::RemainingBytes => out.push_str(r#"{"kind":"remaining_bytes"}"#),
+            Self::Opaque { name } => {
+                out.push_str(r#"{"kind":"opaque","name":"#);
+                write_json_str(name, out);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_json_variants(
+    variants: &[Variant],
+    with_tags: bool,
+    int_encoding: IntEncoding,
+    length_encoding: LengthEncoding,
+    out: &mut String,
+) {
+    out.push('[');
+    for (i, variant) in variants.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(r#"{"name":"#);
+        write_json_str(variant.name, out);
+        if with_tags {
+            out.push_str(r#","tag":"#);
+            out.push_str(&variant.tag.to_string());
+        }
+        out.push_str(r#","fields":"#);
+        write_json_fields(&variant.fields, int_encoding, length_encoding, out);
+        out.push('}');
+    }
+    out.push(']');
+}
+
+/// Writes the int layout of the tag of an enum. Without a `tag_type`, this is a `u32` with the int encoding of the config, or a `u64` if a tag of `variants` does not fit in a `u32`, like `#[derive(Encode)]` does.
+fn write_json_tag(
+    tag: Option<TagType>,
+    variants: &[Variant],
+    int_encoding: IntEncoding,
+    length_encoding: LengthEncoding,
+    out: &mut String,
+) {
+    let (bits, tag_encoding) = match tag {
+        None if variants.iter().any(|v| v.tag > u64::from(u32::MAX)) => (64, int_encoding),
+        None => (32, int_encoding),
+        Some(TagType::U8) => (8, IntEncoding::Fixed),
+        Some(TagType::U16) => (16, IntEncoding::Fixed),
+        Some(TagType::U32) => (32, IntEncoding::Fixed),
+        Some(TagType::Varint) => (32, IntEncoding::Variable),
+    };
+    Layout::Int {
+        signed: false,
+        bits,
+    }
+    .write_json(tag_encoding, length_encoding, out);
+}
+
+/// Writes the int layout of the length of strings, sequences and maps. With `LengthEncoding::Int`, this is a `u64` with the int encoding of the config.
+fn write_json_len(int_encoding: IntEncoding, length_encoding: LengthEncoding, out: &mut String) {
+    let (bits, len_encoding) = match length_encoding {
+        LengthEncoding::Varint => (64, IntEncoding::Variable),
+        LengthEncoding::FixedU32 => (32, IntEncoding::Fixed),
+        LengthEncoding::FixedU64 => (64, IntEncoding::Fixed),
+        LengthEncoding::Int => (64, int_encoding),
+    };
+    Layout::Int {
+        signed: false,
+        bits,
+    }
+    .write_json(len_encoding, length_encoding, out);
+}
+
+fn write_json_fields(
+    fields: &[Field],
+    int_encoding: IntEncoding,
+    length_encoding: LengthEncoding,
+    out: &mut String,
+) {
+    out.push('[');
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(r#"{"name":"#);
+        write_json_str(field.name, out);
+        out.push_str(r#","layout":"#);
+        field.layout.write_json(int_encoding, length_encoding, out);
+        out.push('}');
+    }
+    out.push(']');
+}
+
+fn write_json_str(s: &str, out: &mut String) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c < ' ' => {
+                let b = c as u8;
+                out.push_str("\\u00");
+                out.push(char::from(HEX[usize::from(b >> 4)]));
+                out.push(char::from(HEX[usize::from(b & 0xf)]));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+macro_rules! impl_layout {
+    ($($ty:ty => $layout:expr),* $(,)?) => {
+        $(
+            impl EncodedLayout for $ty {
+                fn layout() -> Layout {
+                    $layout
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! int_layout {
+    ($signed:expr, $bits:expr) => {
+        Layout::Int {
+            signed: $signed,
+            bits: $bits,
+        }
+    };
+}
+
+impl_layout! {
+    () => Layout::Unit,
+    bool => Layout::Bool,
+    u8 => int_layout!(false, 8),
+    u16 => int_layout!(false, 16),
+    u32 => int_layout!(false, 32),
+    u64 => int_layout!(false, 64),
+    u128 => int_layout!(false, 128),
+    usize => int_layout!(false, 64),
+    i8 => int_layout!(true, 8),
+    i16 => int_layout!(true, 16),
+    i32 => int_layout!(true, 32),
+    i64 => int_layout!(true, 64),
+    i128 => int_layout!(true, 128),
+    isize => int_layout!(true, 64),
+    NonZeroU8 => int_layout!(false, 8),
+    NonZeroU16 => int_layout!(false, 16),
+    NonZeroU32 => int_layout!(false, 32),
+    NonZeroU64 => int_layout!(false, 64),
+    NonZeroU128 => int_layout!(false, 128),
+    NonZeroUsize => int_layout!(false, 64),
+    NonZeroI8 => int_layout!(true, 8),
+    NonZeroI16 => int_layout!(true, 16),
+    NonZeroI32 => int_layout!(true, 32),
+    NonZeroI64 => int_layout!(true, 64),
+    NonZeroI128 => int_layout!(true, 128),
+    NonZeroIsize => int_layout!(true, 64),
+    f32 => Layout::Float { bits: 32 },
+    f64 => Layout::Float { bits: 64 },
+    char => Layout::Char,
+    str => Layout::Str,
+    String => Layout::Str,
+    Duration => Layout::Struct {
+        name: "Duration",
+        fields: Vec::from([
+            Field::new("secs", u64::layout()),
+            Field::new("nanos", u32::layout()),
+        ]),
+    },
+}
+
+impl<T: ?Sized> EncodedLayout for PhantomData<T> {
+    fn layout() -> Layout {
+        Layout::Unit
+    }
+}
+
+macro_rules! impl_layout_transparent {
+    ($($ty:ident),*) => {
+        $(
+            impl<T: EncodedLayout + ?Sized> EncodedLayout for $ty<T> {
+                fn layout() -> Layout {
+                    T::layout()
+                }
+            }
+        )*
+    };
+}
+
+impl_layout_transparent!(Box, Rc, Cell, RefCell);
+
+#[cfg(target_has_atomic = "ptr")]
+impl_layout_transparent!(Arc);
+
+impl<T: EncodedLayout> EncodedLayout for Wrapping<T> {
+    fn layout() -> Layout {
+        T::layout()
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for Reverse<T> {
+    fn layout() -> Layout {
+        T::layout()
+    }
+}
+
+impl<T: EncodedLayout + ?Sized> EncodedLayout for &T {
+    fn layout() -> Layout {
+        T::layout()
+    }
+}
+
+impl<T: EncodedLayout + ?Sized> EncodedLayout for &mut T {
+    fn layout() -> Layout {
+        T::layout()
+    }
+}
+
+impl<T: EncodedLayout + ToOwned + ?Sized> EncodedLayout for Cow<'_, T> {
+    fn layout() -> Layout {
+        T::layout()
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for Option<T> {
+    fn layout() -> Layout {
+        Layout::Option(Box::new(T::layout()))
+    }
+}
+
+impl<T: EncodedLayout, U: EncodedLayout> EncodedLayout for Result<T, U> {
+    fn layout() -> Layout {
+        Layout::Enum {
+            name: "Result",
+            tag: None,
+            variants: Vec::from([
+                Variant::new("Ok", 0, Vec::from([Field::new("0", T::layout())])),
+                Variant::new("Err", 1, Vec::from([Field::new("0", U::layout())])),
+            ]),
+        }
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for Bound<T> {
+    fn layout() -> Layout {
+        Layout::Enum {
+            name: "Bound",
+            tag: None,
+            variants: Vec::from([
+                Variant::new("Unbounded", 0, Vec::new()),
+                Variant::new("Included", 1, Vec::from([Field::new("0", T::layout())])),
+                Variant::new("Excluded", 2, Vec::from([Field::new("0", T::layout())])),
+            ]),
+        }
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for Poll<T> {
+    fn layout() -> Layout {
+        Layout::Enum {
+            name: "Poll",
+            tag: None,
+            variants: Vec::from([
+                Variant::new("Ready", 0, Vec::from([Field::new("0", T::layout())])),
+                Variant::new("Pending", 1, Vec::new()),
+            ]),
+        }
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for Range<T> {
+    fn layout() -> Layout {
+        Layout::Struct {
+            name: "Range",
+            fields: Vec::from([
+                Field::new("start", T::layout()),
+                Field::new("end", T::layout()),
+            ]),
+        }
+    }
+}
+
+impl<T: EncodedLayout> EncodedLayout for RangeInclusive<T> {
+    fn layout() -> Layout {
+        Layout::Struct {
+            name: "RangeInclusive",
+            fields: Vec::from([
+                Field::new("start", T::layout()),
+                Field::new("end", T::layout()),
+            ]),
+        }
+    }
+}
+
+impl<T: EncodedLayout, const N: usize> EncodedLayout for [T; N] {
+    fn layout() -> Layout {
+        Layout::Array {
+            len: N,
+            element: Box::new(T::layout()),
+        }
+    }
+}
+
+macro_rules! impl_layout_seq {
+    ($($ty:ty),*) => {
+        $(
+            impl<T: EncodedLayout> EncodedLayout for $ty {
+                fn layout() -> Layout {
+                    Layout::Seq(Box::new(T::layout()))
+                }
+            }
+        )*
+    };
+}
+
+impl_layout_seq!([T], Vec<T>, VecDeque<T>, BTreeSet<T>, BinaryHeap<T>);
+
+impl<K: EncodedLayout, V: EncodedLayout> EncodedLayout for BTreeMap<K, V> {
+    fn layout() -> Layout {
+        Layout::Map {
+            key: Box::new(K::layout()),
+            value: Box::new(V::layout()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: EncodedLayout, S> EncodedLayout for std::collections::HashSet<T, S> {
+    fn layout() -> Layout {
+        Layout::Seq(Box::new(T::layout()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: EncodedLayout, V: EncodedLayout, S> EncodedLayout for std::collections::HashMap<K, V, S> {
+    fn layout() -> Layout {
+        Layout::Map {
+            key: Box::new(K::layout()),
+            value: Box::new(V::layout()),
+        }
+    }
+}
+
+macro_rules! impl_layout_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: EncodedLayout),+> EncodedLayout for ($($name,)+) {
+            fn layout() -> Layout {
+                Layout::Tuple(Vec::from([$($name::layout()),+]))
+            }
+        }
+    };
+}
+
+impl_layout_tuple!(A);
+impl_layout_tuple!(A B);
+impl_layout_tuple!(A B C);
+impl_layout_tuple!(A B C D);
+impl_layout_tuple!(A B C D E);
+impl_layout_tuple!(A B C D E F);
+impl_layout_tuple!(A B C D E F G);
+impl_layout_tuple!(A B C D E F G H);
+impl_layout_tuple!(A B C D E F G H I);
+impl_layout_tuple!(A B C D E F G H I J);
+impl_layout_tuple!(A B C D E F G H I J K);
+impl_layout_tuple!(A B C D E F G H I J K L);
+impl_layout_tuple!(A B C D E F G H I J K L M);
+impl_layout_tuple!(A B C D E F G H I J K L M N);
+impl_layout_tuple!(A B C D E F G H I J K L M N O);
+impl_layout_tuple!(A B C D E F G H I J K L M N O P);
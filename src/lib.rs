@@ -19,8 +19,26 @@
 //! |std   | Yes    | No          |`HashMap` and `HashSet`|`decode_from_std_read` and `encode_into_std_write`|
 //! |alloc | Yes    | No          |All common containers in alloc, like `Vec`, `String`, `Box`|`encode_to_vec`|
 //! |atomic| Yes    | No          |All `Atomic*` integer types, e.g. `AtomicUsize`, and `AtomicBool`||
-//! |derive| Yes    | No          |||Enables the `BorrowDecode`, `Decode` and `Encode` derive macros|
+//! |derive| Yes    | No          |||Enables the `BorrowDecode`, `Decode`, `Encode` and `SkipDecode` derive macros, `ConstEncode` (see [`ConstWriter`](enc::write::ConstWriter)), `TransparentWire` (see the [`transparent`] module), and `EncodedLayout` (see the [`layout`] module) and `IncrementalEncode` (see [`enc::incremental`]) with `alloc`|
 //! |serde | No     | Yes (MSRV reliant on serde)|`Compat` and `BorrowCompat`, which will work for all types that implement serde's traits|serde-specific encode/decode functions in the [`serde`\] module|Note: There are several [known issues](serde/index.html#known-issues) when using serde and bincode|
+//! |chrono| No     | No          |`DateTime<Utc>`, `NaiveDateTime`, `NaiveDate` and `TimeDelta` from the `chrono` crate||
+//! |time  | No     | Yes (MSRV reliant on time)|`OffsetDateTime`, `PrimitiveDateTime` and `Duration` from the `time` crate||
+//! |bytes | No     | No          |`Bytes` and `BytesMut` from the `bytes` crate|`decode_from_bytes`, which decodes `Bytes` fields without copying||
+//! |smallvec| No   | No          |`SmallVec` from the `smallvec` crate||
+//! |arrayvec| No   | No          |`ArrayVec` and `ArrayString` from the `arrayvec` crate||
+//! |heapless| No   | No          |`Vec`, `String` and `IndexMap` from the `heapless` crate|`heapless::Vec<u8, N>` implements `Writer`||
+//! |glam  | No     | No          |Vectors, `Quat`, matrices and `Affine3A` from the `glam` crate||`no_std` targets also need to enable `glam/libm`|
+//! |arbitrary| No  | No          ||Round trip helpers for fuzzing in the [`fuzz`\] module||
+//! |memmap2| No    | No          ||`decode_from_mmap` and `decode_records_from_mmap`, which borrow-decode directly from a memory-mapped file||
+//! |half  | No     | No          |`f16` and `bf16` from the `half` crate||
+//! |simdutf8| No  | No          |||Validates the UTF-8 of decoded `String`, `Cow<str>` and `&str` with SIMD instructions. Errors are the same as without the feature|
+//! |rayon | No     | Yes (MSRV reliant on rayon)||`encode_to_vec_parallel` and `encode_into_std_write_parallel`, which encode the elements of a slice on several threads||
+//! |bumpalo| No   | No          |`&'bump str` and `&'bump [T]`, allocated in a `bumpalo::Bump` that is passed as the decode context|`decode_from_slice_in`||
+//! |cobs  | No     | No          ||`CobsWriter`, `CobsReader` and `decode_from_cobs_slice` in the [`cobs`\] module, for COBS-framed payloads||
+//! |armor | No     | No          ||`Base64Writer`, `HexWriter`, their readers and `encode_to_base64_string` in the [`armor`\] module, for embedding values in text||
+//! |digest| No   | No          ||`DigestAdapter` in the [`digest`\] module, for the hashers of the `digest` crate||
+//! |test-utils| No | No          ||Round trip assertions for tests of `Encode` and `Decode` implementations in the [`test_utils`\] module||
+//! |stats | No     | No          ||`StatsWriter` and `StatsReader` in the [`stats`\] module, which count the bytes of every field of derived types|Derived implementations only mark their fields with this feature|
 //!
 //! # Which functions to use
 //!
@@ -130,6 +148,7 @@ extern crate std;
 
 mod atomic;
 mod features;
+pub(crate) mod plain;
 pub(crate) mod utils;
 pub(crate) mod varint;
 
@@ -140,18 +159,51 @@ use enc::write::Writer;
     feature = "alloc",
     feature = "std",
     feature = "derive",
-    feature = "serde"
+    feature = "serde",
+    feature = "bytes",
+    feature = "bumpalo",
+    feature = "armor"
 ))]
 pub use features::*;
 
+pub mod adapters;
+pub mod bounded;
 pub mod config;
 #[macro_use]
 pub mod de;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod debug_info;
+pub mod digest;
 pub mod enc;
+pub mod envelope;
 pub mod error;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod intern;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod layout;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod message;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod pre_encoded;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod shared;
+// TODO: Currently our doctests fail when trying to include the specs because the specs depend on `derive` and `alloc`.
+// But we want to have the specs in the docs always, so `spec.md` is only part of the docs of this module when building the docs.
+pub mod spec;
+pub mod transparent;
 
-pub use de::{BorrowDecode, Decode};
+pub use de::{BorrowDecode, Decode, SkipDecode};
 pub use enc::Encode;
+pub use transparent::TransparentWire;
+
+#[cfg(feature = "alloc")]
+pub use debug_info::{debug_info_from_slice, encode_to_vec_with_debug_info};
 
 use config::Config;
 
@@ -161,7 +213,7 @@ use config::Config;
 ///
 /// # Errors
 ///
-/// Returns an `EncodeError` if the slice is too small or the value cannot be encoded.
+/// Returns an `EncodeError` if the value cannot be encoded. If the slice is too small, this is [`EncodeError::SliceTooSmall`](error::EncodeError::SliceTooSmall) with the amount of bytes the value needs, so the caller can retry with a large enough slice.
 ///
 /// [config]: config/index.html
 pub fn encode_into_slice<E: enc::Encode, C: Config>(
@@ -169,12 +221,55 @@ pub fn encode_into_slice<E: enc::Encode, C: Config>(
     dst: &mut [u8],
     config: C,
 ) -> Result<usize, error::EncodeError> {
+    let capacity = dst.len();
     let writer = enc::write::SliceWriter::new(dst);
     let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
-    val.encode(&mut encoder)?;
+    val.encode(&mut encoder).map_err(|e| {
+        enc::slice_too_small(e, capacity, || {
+            let mut encoder =
+                enc::EncoderImpl::<_, C>::new(enc::write::SizeWriter::default(), config);
+            val.encode(&mut encoder)?;
+            Ok(encoder.into_writer().bytes_written)
+        })
+    })?;
     Ok(encoder.into_writer().bytes_written())
 }
 
+/// Encode the given value into the given slice of uninitialized memory. Returns the part of the slice that has been written to.
+///
+/// This is the same as [`encode_into_slice`], but the slice does not have to be zeroed first, like a buffer from FFI or a shared-memory ring buffer. The returned part is initialized.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use core::mem::MaybeUninit;
+///
+/// let mut buffer = [MaybeUninit::<u8>::uninit(); 64];
+/// let written = bincode::encode_into_uninit_slice("hello", &mut buffer, bincode::config::standard()).unwrap();
+/// assert_eq!(written, b"\x05hello");
+/// ```
+///
+/// # Errors
+///
+/// Returns an `EncodeError` if the value cannot be encoded. If the slice is too small, this is [`EncodeError::SliceTooSmall`](error::EncodeError::SliceTooSmall) with the amount of bytes the value needs, and the slice is left with a partially encoded prefix of the value.
+pub fn encode_into_uninit_slice<E: enc::Encode, C: Config>(
+    val: E,
+    dst: &mut [core::mem::MaybeUninit<u8>],
+    config: C,
+) -> Result<&mut [u8], error::EncodeError> {
+    let capacity = dst.len();
+    let writer = enc::write::UninitSliceWriter::new(dst);
+    let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
+    val.encode(&mut encoder).map_err(|e| {
+        enc::slice_too_small(e, capacity, || {
+            let mut encoder =
+                enc::EncoderImpl::<_, C>::new(enc::write::SizeWriter::default(), config);
+            val.encode(&mut encoder)?;
+            Ok(encoder.into_writer().bytes_written)
+        })
+    })?;
+    Ok(encoder.into_writer().into_written())
+}
+
 /// Encode the given value into a custom [`Writer`\].
 ///
 /// See the [config] module for more information on configurations.
@@ -230,9 +325,122 @@ pub fn decode_from_slice_with_context<Context, D: de::Decode<Context>, C: Config
 ) -> Result<(D, usize), error::DecodeError> {
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
-    let result = D::decode(&mut decoder)?;
+    let result = D::decode(&mut decoder);
+    let bytes_read = src.len() - decoder.reader().slice.len();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
+}
+
+/// Attempt to decode a given type `D` from the given slice, like [`decode_from_slice`], but report where in the input an error occurred.
+///
+/// Errors are wrapped in [`DecodeError::WithOffset`](error::DecodeError::WithOffset) with the amount of bytes that were read when the error occurred. Use [`DecodeError::offset`](error::DecodeError::offset) to get it, and [`DecodeError::inner`](error::DecodeError::inner) to match on the underlying error. To do this for every decode function, use a config with [`with_error_offsets`](config::Configuration::with_error_offsets).
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::error::DecodeError;
+///
+/// let err = bincode::decode_from_slice_with_offset::<(u8, bool), _>(&[1, 2], bincode::config::standard())
+///     .unwrap_err();
+/// assert_eq!(err.offset(), Some(2));
+/// assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(2)));
+/// ```
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid.
+#[cfg(feature = "alloc")]
+pub fn decode_from_slice_with_offset<D: de::Decode<()>, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C, ()>::new(reader, config, ());
+    let result = D::decode(&mut decoder);
     let bytes_read = src.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| e.with_offset(bytes_read))
+}
+
+/// Attempt to decode a given type `D` from a slice that was encoded with [`encode_with_config_header`]. Returns the decoded output and the amount of bytes read, including the header.
+///
+/// The header is checked against the [wire id](config::Config::wire_id) of `config` before the value is decoded. Only the settings that change the encoded bytes have to match, so data that was encoded with [`config::standard`] can be decoded with [`config::untrusted`].
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::{config, error::DecodeError};
+///
+/// let encoded = bincode::encode_with_config_header(300u32, config::standard()).unwrap();
+/// let (value, _): (u32, usize) =
+///     bincode::decode_checking_config_header(&encoded, config::untrusted(1024)).unwrap();
+/// assert_eq!(value, 300);
+///
+/// let err = bincode::decode_checking_config_header::<u32, _>(&encoded, config::legacy()).unwrap_err();
+/// assert!(matches!(err, DecodeError::ConfigMismatch { .. }));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`DecodeError::ConfigMismatch`](error::DecodeError::ConfigMismatch) if the header belongs to a config with a different format, or a `DecodeError` if the slice is too small or the data is invalid.
+pub fn decode_checking_config_header<D: de::Decode<()>, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    let Some((header, value)) = src.split_first_chunk::<4>() else {
+        return Err(error::DecodeError::UnexpectedEnd {
+            additional: 4 - src.len(),
+        });
+    };
+    spec::check_wire_id(config.wire_id(), *header)?;
+    let reader = de::read::SliceReader::new(value);
+    let mut decoder = de::DecoderImpl::<_, C, ()>::new(reader, config, ());
+    let result = D::decode(&mut decoder);
+    let bytes_read = src.len() - decoder.reader().slice.len();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
+}
+
+/// Decode a collection from the given slice, and add its entries to `target` instead of decoding them into a new collection. Returns the amount of entries that were added and the amount of bytes read.
+///
+/// See [`DecodeExtend`](de::DecodeExtend) for the collections that support this, and the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid. The entries that were decoded before the error are already added to `target`.
+///
+/// [config]: config/index.html
+pub fn decode_extend_from_slice<M: de::DecodeExtend<()>, C: Config>(
+    target: &mut M,
+    src: &[u8],
+    config: C,
+) -> Result<(de::Extended, usize), error::DecodeError> {
+    decode_extend_from_slice_with_context(target, src, config, ())
+}
+
+/// Decode a collection from the given slice with `Context`, and add its entries to `target`. See [`decode_extend_from_slice`].
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid. The entries that were decoded before the error are already added to `target`.
+pub fn decode_extend_from_slice_with_context<Context, M: de::DecodeExtend<Context>, C: Config>(
+    target: &mut M,
+    src: &[u8],
+    config: C,
+    context: Context,
+) -> Result<(de::Extended, usize), error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
+    let result = target.decode_extend(&mut decoder);
+    let bytes_read = src.len() - decoder.reader().slice.len();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
 }
 
 /// Attempt to decode a given type `D` from the given slice. Returns the decoded output and the amount of bytes read.
@@ -272,9 +480,140 @@ pub fn borrow_decode_from_slice_with_context<
 ) -> Result<(D, usize), error::DecodeError> {
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
-    let result = D::borrow_decode(&mut decoder)?;
+    let result = D::borrow_decode(&mut decoder);
     let bytes_read = src.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
+}
+
+/// Attempt to decode a given type `D` from the given slice, like [`borrow_decode_from_slice`], but report where in the input an error occurred.
+///
+/// See [`decode_from_slice_with_offset`].
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid.
+#[cfg(feature = "alloc")]
+pub fn borrow_decode_from_slice_with_offset<'a, D: de::BorrowDecode<'a, ()>, C: Config>(
+    src: &'a [u8],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C, ()>::new(reader, config, ());
+    let result = D::borrow_decode(&mut decoder);
+    let bytes_read = src.len() - decoder.reader().slice.len();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, src.len() - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| e.with_offset(bytes_read))
+}
+
+/// Attempt to decode a given type `D` from the given mutable slice. Returns the decoded output and the amount of bytes read.
+///
+/// Unlike [`borrow_decode_from_slice`], this can decode values that borrow the input mutably, like `&mut [u8]` and `&mut str`, so they can be changed in place after decoding.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// #[derive(bincode::Encode, bincode::BorrowDecode)]
+/// struct Packet<'a> {
+///     id: u8,
+///     payload: &'a mut [u8],
+/// }
+///
+/// let config = bincode::config::standard();
+/// let mut buffer = bincode::encode_to_vec(Packet { id: 1, payload: &mut [1, 2, 3] }, config).unwrap();
+/// let (packet, _): (Packet, _) = bincode::borrow_decode_from_slice_mut(&mut buffer, config).unwrap();
+/// packet.payload.fill(0);
+/// assert_eq!(buffer, [1, 3, 0, 0, 0]);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn borrow_decode_from_slice_mut<'a, D: de::BorrowDecode<'a, ()>, C: Config>(
+    src: &'a mut [u8],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    borrow_decode_from_slice_mut_with_context(src, config, ())
+}
+
+/// Attempt to decode a given type `D` from the given mutable slice with `Context`. Returns the decoded output and the amount of bytes read.
+///
+/// See [`borrow_decode_from_slice_mut`] and the [config] module for more information.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the slice is too small or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn borrow_decode_from_slice_mut_with_context<
+    'a,
+    Context,
+    D: de::BorrowDecode<'a, Context>,
+    C: Config,
+>(
+    src: &'a mut [u8],
+    config: C,
+    context: Context,
+) -> Result<(D, usize), error::DecodeError> {
+    let len = src.len();
+    let reader = de::read::SliceReaderMut::new(src);
+    let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
+    let result = D::borrow_decode(&mut decoder);
+    let bytes_read = len - decoder.reader().slice.len();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, len - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
+}
+
+/// Attempt to decode a given type `D` from data that is split into several segments, e.g. the buffers of a scatter/gather read. Returns the decoded output and the amount of bytes read.
+///
+/// This avoids copying the segments into one contiguous buffer first. Values that span a segment boundary are decoded as if the segments were contiguous. See [`SegmentedSliceReader`](de::read::SegmentedSliceReader) for a reader that can also borrow data from the segments.
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the segments are too small or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn decode_from_segments<D: de::Decode<()>, C: Config>(
+    segments: &[&[u8]],
+    config: C,
+) -> Result<(D, usize), error::DecodeError> {
+    decode_from_segments_with_context(segments, config, ())
+}
+
+/// Attempt to decode a given type `D` from data that is split into several segments with `Context`. Returns the decoded output and the amount of bytes read.
+///
+/// See [`decode_from_segments`] and the [config] module for more information.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the segments are too small or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn decode_from_segments_with_context<Context, D: de::Decode<Context>, C: Config>(
+    segments: &[&[u8]],
+    config: C,
+    context: Context,
+) -> Result<(D, usize), error::DecodeError> {
+    let reader = de::read::SegmentedSliceReader::new(segments);
+    let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
+    let result = D::decode(&mut decoder);
+    let bytes_read = decoder.reader().bytes_read();
+    let len = segments.iter().map(|segment| segment.len()).sum::<usize>();
+    result
+        .and_then(|result| de::check_trailing_bytes::<C, _>(result, len - bytes_read))
+        .map(|result| (result, bytes_read))
+        .map_err(|e| de::report_offset::<C>(e, bytes_read))
 }
 
 /// Attempt to decode a given type `D` from the given [`Reader`\].
@@ -290,15 +629,96 @@ pub fn decode_from_reader<D: de::Decode<()>, R: Reader, C: Config>(
     reader: R,
     config: C,
 ) -> Result<D, error::DecodeError> {
-    let mut decoder = de::DecoderImpl::<_, C, ()>::new(reader, config, ());
+    decode_from_reader_with_context(reader, config, ())
+}
+
+/// Attempt to decode a given type `D` from the given [`Reader`\] with `Context`.
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn decode_from_reader_with_context<Context, D: de::Decode<Context>, R: Reader, C: Config>(
+    reader: R,
+    config: C,
+    context: Context,
+) -> Result<D, error::DecodeError> {
+    #[cfg(feature = "alloc")]
+    if C::ERROR_OFFSETS == config::ErrorOffsets::Report {
+        let reader = de::read::OffsetReader::new(reader);
+        let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
+        return D::decode(&mut decoder).map_err(|e| e.with_offset(decoder.reader().offset));
+    }
+    let mut decoder = de::DecoderImpl::<_, C, Context>::new(reader, config, context);
     D::decode(&mut decoder)
 }
 
-// TODO: Currently our doctests fail when trying to include the specs because the specs depend on `derive` and `alloc`.
-// But we want to have the specs in the docs always
-#[cfg(all(feature = "alloc", feature = "derive", doc))]
-pub mod spec {
-    #![doc = include_str!("../docs/spec.md")]
+/// Attempt to decode a given type `D` from the given [`Reader`\], like [`decode_from_reader`], but report where in the input an error occurred.
+///
+/// The offset is the amount of bytes that were read from `reader`. See [`decode_from_slice_with_offset`].
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the reader fails or the data is invalid.
+#[cfg(feature = "alloc")]
+pub fn decode_from_reader_with_offset<D: de::Decode<()>, R: Reader, C: Config>(
+    reader: R,
+    config: C,
+) -> Result<D, error::DecodeError> {
+    let reader = de::read::OffsetReader::new(reader);
+    let mut decoder = de::DecoderImpl::<_, C, ()>::new(reader, config, ());
+    D::decode(&mut decoder).map_err(|e| e.with_offset(decoder.reader().offset))
+}
+
+/// Attempt to decode a given type `D` from the bytes of the given iterator. Bytes that are not needed to decode `D` are not taken from the iterator.
+///
+/// This is [`decode_from_reader`\] with an [`IterReader`](de::read::IterReader). Use a [`TryIterReader`](de::read::TryIterReader) for an iterator that can fail.
+///
+/// See the [config] module for more information on configurations.
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if the iterator ends too early or the data is invalid.
+///
+/// [config]: config/index.html
+pub fn decode_from_iter<D: de::Decode<()>, I: IntoIterator<Item = u8>, C: Config>(
+    iter: I,
+    config: C,
+) -> Result<D, error::DecodeError> {
+    decode_from_reader(de::read::IterReader::new(iter), config)
+}
+
+/// Advance the given decoder past an encoded `T`, without decoding it. See [`SkipDecode`](de::SkipDecode) for which bytes are read and which are skipped.
+///
+/// ```
+/// # extern crate bincode_next as bincode;
+/// use bincode::de::{read::SliceReader, Decode, DecoderImpl};
+///
+/// #[derive(bincode::Encode, bincode::SkipDecode)]
+/// struct Record {
+///     id: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// let config = bincode::config::standard();
+/// let records = vec![Record { id: 1, tags: vec!["a".into(), "b".into()] }];
+/// let bytes = bincode::encode_to_vec((&records, 42u8), config).unwrap();
+///
+/// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+/// bincode::skip_value::<Vec<Record>, _>(&mut decoder).unwrap();
+/// assert_eq!(u8::decode(&mut decoder).unwrap(), 42);
+/// ```
+///
+/// # Errors
+///
+/// Returns a `DecodeError` if a length or tag is invalid, or if the input ends before the value does.
+pub fn skip_value<T: de::SkipDecode + ?Sized, D: de::Decoder>(
+    decoder: &mut D,
+) -> Result<(), error::DecodeError> {
+    T::skip(decoder)
 }
 
 #[cfg(doc)]
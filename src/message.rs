@@ -0,0 +1,150 @@
+//! Messages of several types on one stream, each identified by a `u16` id in front of it.
+//!
+//! [`encode_message`\] writes the id of a message as 2 bytes, followed by its payload with a length prefix of 8 bytes (see [`Encoder::with_length_prefix`]). Both are written in the endianness of the config, and never with variable-length integers, so the header of a message always has the same size. A [`MessageSet`\] holds a handler for every id that is expected, and [`decode_dispatch`\] reads the next message, decodes its payload as the type that was registered for its id, and passes it to the handler:
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::de::read::SliceReader;
+//! use bincode::message::{decode_dispatch, encode_message, MessageSet};
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! struct Ping(u64);
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! struct Chat {
+//!     from: String,
+//!     text: String,
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let mut stream = Vec::new();
+//! encode_message(1, &Ping(7), bincode::IoWriter::new(&mut stream), config).unwrap();
+//! let chat = Chat { from: "ann".into(), text: "hi".into() };
+//! encode_message(2, &chat, bincode::IoWriter::new(&mut stream), config).unwrap();
+//!
+//! let mut set = MessageSet::new();
+//! set.on(1, |ping: Ping| format!("ping {}", ping.0))
+//!     .on(2, |chat: Chat| format!("{}: {}", chat.from, chat.text));
+//!
+//! let mut reader = SliceReader::new(&stream);
+//! assert_eq!(decode_dispatch(&mut reader, config, &mut set).unwrap(), "ping 7");
+//! assert_eq!(decode_dispatch(&mut reader, config, &mut set).unwrap(), "ann: hi");
+//! ```
+//!
+//! A message with an id that no handler is registered for fails with [`DecodeError::UnknownMessage`\] with its id and the length of its payload. The payload is skipped, so the next message can be decoded right away. The rest of a payload that the handler did not read is skipped as well, so a newer version of a message type can add fields at the end, and a payload that fails to decode does not leave the stream in the middle of a message.
+
+use crate::{
+    config::{Config, Endianness},
+    de::{
+        read::{Reader, TakeReader},
+        Decode, Decoder, DecoderImpl,
+    },
+    enc::{write::Writer, Encode, Encoder, EncoderImpl},
+    error::{DecodeError, EncodeError},
+};
+use alloc::{boxed::Box, collections::BTreeMap};
+
+/// The decoder that the payload of a message is decoded with.
+type PayloadDecoder<'r, R, C> = DecoderImpl<TakeReader<&'r mut R>, C, ()>;
+
+type Handler<'a, R, C, Out> =
+    Box<dyn for<'r> FnMut(&mut PayloadDecoder<'r, R, C>) -> Result<Out, DecodeError> + 'a>;
+
+/// The handlers of the messages that [`decode_dispatch`\] expects from a reader `R` with config `C`. Every handler returns an `Out`. See the [module documentation](self).
+pub struct MessageSet<'a, R: Reader, C: Config, Out = ()> {
+    handlers: BTreeMap<u16, Handler<'a, R, C, Out>>,
+}
+
+impl<'a, R: Reader, C: Config, Out> MessageSet<'a, R, C, Out> {
+    /// Creates a set without any handlers.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `handler` for the messages with `id`, which have a payload of type `T`. This replaces the handler that was registered for `id` before.
+    pub fn on<T: Decode<()>>(
+        &mut self,
+        id: u16,
+        mut handler: impl FnMut(T) -> Out + 'a,
+    ) -> &mut Self {
+        self.handlers.insert(
+            id,
+            Box::new(move |decoder| T::decode(decoder).map(&mut handler)),
+        );
+        self
+    }
+
+    /// Returns `true` if a handler is registered for `id`.
+    #[must_use]
+    pub fn contains(&self, id: u16) -> bool {
+        self.handlers.contains_key(&id)
+    }
+}
+
+impl<R: Reader, C: Config, Out> Default for MessageSet<'_, R, C, Out> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes `value` as the message with `id`, so it can be read with [`decode_dispatch`\].
+///
+/// # Errors
+///
+/// Returns an error if `value` fails to encode or the writer fails.
+pub fn encode_message<T: Encode + ?Sized, W: Writer, C: Config>(
+    id: u16,
+    value: &T,
+    writer: W,
+    config: C,
+) -> Result<(), EncodeError> {
+    let mut encoder = EncoderImpl::new(writer, config);
+    let id = match C::ENDIAN {
+        Endianness::Little => id.to_le_bytes(),
+        Endianness::Big => id.to_be_bytes(),
+    };
+    encoder.writer().write(&id)?;
+    encoder.with_length_prefix(|encoder| value.encode(encoder))
+}
+
+/// Reads the next message from `reader`, decodes its payload as the type that is registered for its id in `set`, and returns what the handler returns.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::UnknownMessage`\] if no handler is registered for the id of the message, `DecodeError::TakeLimitExceeded` if the payload is shorter than its type needs, or an error if the payload is invalid or the reader fails.
+pub fn decode_dispatch<R: Reader, C: Config, Out>(
+    reader: &mut R,
+    config: C,
+    set: &mut MessageSet<'_, R, C, Out>,
+) -> Result<Out, DecodeError> {
+    let mut decoder = DecoderImpl::new(&mut *reader, config, ());
+    decoder.claim_bytes_read(10)?;
+    let mut id = [0u8; 2];
+    decoder.reader().read(&mut id)?;
+    let id = match C::ENDIAN {
+        Endianness::Little => u16::from_le_bytes(id),
+        Endianness::Big => u16::from_be_bytes(id),
+    };
+    let mut bytes = [0u8; 8];
+    decoder.reader().read(&mut bytes)?;
+    let len = match C::ENDIAN {
+        Endianness::Little => u64::from_le_bytes(bytes),
+        Endianness::Big => u64::from_be_bytes(bytes),
+    };
+    let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+
+    let Some(handler) = set.handlers.get_mut(&id) else {
+        reader.skip(len)?;
+        return Err(DecodeError::UnknownMessage { id, len });
+    };
+    let mut decoder = DecoderImpl::new(TakeReader::new(&mut *reader, len), config, ());
+    let result = handler(&mut decoder);
+    let rest = decoder.reader().remaining();
+    let skipped = decoder.reader().skip(rest);
+    let out = result?;
+    skipped?;
+    Ok(out)
+}
@@ -0,0 +1,337 @@
+//! Encoding and decoding slices of primitive types as their in-memory bytes.
+//!
+//! Integers with fixed int encoding and floats are encoded as their bytes in the config's endianness. When that endianness matches the target, this is exactly their in-memory representation, so a slice of them can be written with a single `write` and read with a single `read` instead of element by element. When it does not match, the bytes of every element are reversed in a separate pass.
+//!
+//! The length of a slice comes from the input, so the elements are read in chunks that grow as they arrive (see [`chunks`]), instead of allocating room for all of them before anything is read.
+//!
+//! `bool` and `char` are never handled here, because not every bit pattern is a valid value and decoding them needs validation.
+//!
+//! Newtypes that implement [`TransparentWire`](crate::TransparentWire) are handled like the primitive they wrap. Their `transparent_wire` method returns a [`Transparent`], which the functions here take from the `Encode`, `Decode` or `BorrowDecode` implementation of the element type.
+
+#![allow(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+use crate::de::CollectionDecoder;
+use crate::{
+    config::{Endianness, IntEncoding, InternalEndianConfig, InternalIntEncodingConfig},
+    de::{read::Reader, Decoder},
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    transparent::Transparent,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use unty::type_equal;
+
+/// The size of the buffer that elements are copied into when their bytes need to be reversed before writing. This is a multiple of every element size, so elements are never split between two writes.
+const SWAP_BUFFER_SIZE: usize = 1024;
+
+/// How a slice of a primitive type is encoded.
+#[derive(Clone, Copy)]
+pub struct Plain {
+    /// The size of a single element
+    size: usize,
+    /// Whether the bytes of every element are reversed compared to the in-memory representation
+    swap: bool,
+}
+
+/// Returns how a slice of `T` is encoded with the config `C`, or `None` if `T` has to be encoded element by element. `transparent` is the `transparent_wire` of `T`.
+fn plain<T, C: InternalEndianConfig + InternalIntEncodingConfig>(
+    transparent: Option<Transparent<T>>,
+) -> Option<Plain> {
+    primitive::<T>(C::ENDIAN, C::INT_ENCODING).or_else(|| {
+        transparent.and_then(|transparent| transparent.plain(C::ENDIAN, C::INT_ENCODING))
+    })
+}
+
+/// Returns how a slice of `T` is encoded with the given endianness and int encoding if `T` is an integer or a float, or `None` otherwise.
+pub fn primitive<T>(endian: Endianness, int_encoding: IntEncoding) -> Option<Plain> {
+    let size = core::mem::size_of::<T>();
+    if type_equal::<T, u8>() || type_equal::<T, i8>() {
+        return Some(Plain { size, swap: false });
+    }
+
+    let is_float = type_equal::<T, f32>() || type_equal::<T, f64>();
+    // the half-precision floats of the `half` crate are `u16` bit patterns in memory
+    #[cfg(feature = "half")]
+    let is_float = is_float || type_equal::<T, half::f16>() || type_equal::<T, half::bf16>();
+    let is_int = type_equal::<T, u16>()
+        || type_equal::<T, u32>()
+        || type_equal::<T, u64>()
+        || type_equal::<T, u128>()
+        || type_equal::<T, i16>()
+        || type_equal::<T, i32>()
+        || type_equal::<T, i64>()
+        || type_equal::<T, i128>()
+        // `usize` and `isize` are always encoded as 8 bytes
+        || (size == 8 && (type_equal::<T, usize>() || type_equal::<T, isize>()));
+    if !(is_float || (is_int && int_encoding == IntEncoding::Fixed)) {
+        return None;
+    }
+
+    let native = if cfg!(target_endian = "big") {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    };
+    Some(Plain {
+        size,
+        swap: endian != native,
+    })
+}
+
+/// Reverses the bytes of every element in `bytes`.
+fn swap_elements(bytes: &mut [u8], size: usize) {
+    for element in bytes.chunks_exact_mut(size) {
+        element.reverse();
+    }
+}
+
+/// Returns `slice` as a byte slice if `T` is `u8`.
+pub fn as_bytes<T>(slice: &[T]) -> Option<&[u8]> {
+    // Safety: `T` is `u8`
+    type_equal::<T, u8>()
+        .then(|| unsafe { core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), slice.len()) })
+}
+
+/// Writes `slice` as the bytes of its elements, without a length prefix.
+///
+/// Returns `Ok(false)` without writing anything if `T` has to be encoded element by element.
+pub fn encode_slice<T: Encode, E: Encoder>(
+    encoder: &mut E,
+    slice: &[T],
+) -> Result<bool, EncodeError> {
+    let Some(plain) = plain::<T, E::C>(T::transparent_wire()) else {
+        return Ok(false);
+    };
+
+    // Safety: `plain` only accepts integers and floats and `TransparentWire` types around them, which have no padding bytes
+    let bytes = unsafe {
+        core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), core::mem::size_of_val(slice))
+    };
+    if plain.swap {
+        let mut buffer = [0u8; SWAP_BUFFER_SIZE];
+        for chunk in bytes.chunks(SWAP_BUFFER_SIZE) {
+            let buffer = &mut buffer[..chunk.len()];
+            buffer.copy_from_slice(chunk);
+            swap_elements(buffer, plain.size);
+            encoder.writer().write(buffer)?;
+        }
+    } else {
+        encoder.writer().write(bytes)?;
+    }
+    Ok(true)
+}
+
+/// Returns the bytes that [`encode_slice`] writes for `slice`, if they are its in-memory bytes as they are.
+///
+/// This is `None` if `T` has to be encoded element by element, or if the bytes of every element are reversed.
+#[cfg(feature = "alloc")]
+pub fn native_bytes<T: Encode, C: InternalEndianConfig + InternalIntEncodingConfig>(
+    slice: &[T],
+) -> Option<&[u8]> {
+    let plain = plain::<T, C>(T::transparent_wire())?;
+    // Safety: `plain` only accepts integers and floats and `TransparentWire` types around them, which have no padding bytes
+    (!plain.swap).then(|| unsafe {
+        core::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), core::mem::size_of_val(slice))
+    })
+}
+
+/// Reads the entries of `entries` that were written by [`encode_slice`].
+///
+/// Returns `Ok(None)` without reading anything if `T` has to be decoded element by element. The collection claimed every entry with its minimum encoded size, so the rest of the size of the elements is claimed here.
+#[cfg(feature = "alloc")]
+pub fn decode_vec<T, D: Decoder>(
+    entries: &mut CollectionDecoder<'_, D, T>,
+) -> Result<Option<Vec<T>>, DecodeError> {
+    let Some(plain) = plain::<T, D::C>(entries.transparent()) else {
+        return Ok(None);
+    };
+    let len = entries.len();
+    let unclaimed = plain.size.saturating_sub(entries.entry_size());
+    let decoder = entries.decoder();
+    if unclaimed > 0 {
+        decoder.claim_container_entries(len, unclaimed)?;
+    }
+    read_vec(decoder, len, plain).map(Some)
+}
+
+/// Reads the entries of a collection that was written by [`encode_slice`] into `slice`, which has room for all of them, like [`decode_vec`].
+///
+/// Returns `Ok(false)` without reading anything if `T` has to be decoded element by element. Every element of `slice` is initialized if this returns `Ok(true)`.
+#[cfg(feature = "alloc")]
+pub fn decode_uninit<T, D: Decoder>(
+    entries: &mut CollectionDecoder<'_, D, T>,
+    slice: &mut [core::mem::MaybeUninit<T>],
+) -> Result<bool, DecodeError> {
+    let Some(plain) = plain::<T, D::C>(entries.transparent()) else {
+        return Ok(false);
+    };
+    let unclaimed = plain.size.saturating_sub(entries.entry_size());
+    let decoder = entries.decoder();
+    if unclaimed > 0 {
+        decoder.claim_container_entries(slice.len(), unclaimed)?;
+    }
+    read_uninit_elements(decoder, slice, plain)?;
+    Ok(true)
+}
+
+/// Reads `len` bytes into a `Vec`, without allocating room for all of them up front if there may not be enough input.
+#[cfg(feature = "alloc")]
+pub fn decode_byte_vec<D: Decoder>(decoder: &mut D, len: usize) -> Result<Vec<u8>, DecodeError> {
+    read_vec(
+        decoder,
+        len,
+        Plain {
+            size: 1,
+            swap: false,
+        },
+    )
+}
+
+#[cfg(feature = "alloc")]
+fn read_vec<T, D: Decoder>(
+    decoder: &mut D,
+    len: usize,
+    plain: Plain,
+) -> Result<Vec<T>, DecodeError> {
+    let mut vec = Vec::<T>::new();
+    for range in chunks(decoder, len, plain.size) {
+        crate::de::reserve::reserve_exact::<D, _>(&mut vec, range.len())?;
+        read_uninit_elements(decoder, &mut vec.spare_capacity_mut()[..range.len()], plain)
+            .map_err(|e| with_more_needed(e, (len - range.end).saturating_mul(plain.size)))?;
+        // Safety: the elements up to `range.end` are initialized
+        unsafe { vec.set_len(range.end) };
+    }
+    Ok(vec)
+}
+
+/// Splits reading `len` elements of `size` bytes into ranges of elements, so the memory for them can be allocated as they arrive instead of all at once.
+///
+/// If the reader knows how much input is left and all elements fit in it, there is a single range. Otherwise the first range is limited to what could fit in the input, or to [`MAX_PREALLOCATION_BYTES`](crate::de::MAX_PREALLOCATION_BYTES) if the reader does not know, and every following range doubles the amount of elements.
+#[cfg(feature = "alloc")]
+pub fn chunks<D: Decoder>(decoder: &mut D, len: usize, size: usize) -> Chunks {
+    // if the reader knows how much input is left, one more element than fits, so reading the first range fails right away
+    let first = decoder
+        .reader()
+        .remaining_hint()
+        .map_or(crate::de::MAX_PREALLOCATION_BYTES / size, |remaining| {
+            remaining / size + 1
+        });
+    Chunks {
+        len,
+        end: 0,
+        first: first.max(1),
+    }
+}
+
+/// An iterator over the ranges returned by [`chunks`].
+#[cfg(feature = "alloc")]
+pub struct Chunks {
+    len: usize,
+    end: usize,
+    first: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Chunks {
+    type Item = core::ops::Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.end;
+        if start >= self.len {
+            return None;
+        }
+        self.end = self.len.min(start + start.max(self.first));
+        Some(start..self.end)
+    }
+}
+
+/// Adds `more` bytes to the amount of bytes that a failed read reported it still needed, for reads that are only a part of a larger value.
+#[cfg(feature = "alloc")]
+pub fn with_more_needed(err: DecodeError, more: usize) -> DecodeError {
+    match err {
+        DecodeError::UnexpectedEnd { additional } => DecodeError::UnexpectedEnd {
+            additional: additional.saturating_add(more),
+        },
+        DecodeError::TakeLimitExceeded { limit, additional } => DecodeError::TakeLimitExceeded {
+            limit,
+            additional: additional.saturating_add(more),
+        },
+        #[cfg(feature = "std")]
+        DecodeError::Io { inner, additional } => DecodeError::Io {
+            inner,
+            additional: additional.saturating_add(more),
+        },
+        err => err,
+    }
+}
+
+/// Reads an array that was written by [`encode_slice`]. `transparent` is the `transparent_wire` of `T`.
+///
+/// Returns `Ok(None)` without reading anything if `T` has to be decoded element by element. The caller is responsible for claiming the bytes that are read.
+pub fn decode_array<T, D: Decoder, const N: usize>(
+    decoder: &mut D,
+    transparent: Option<Transparent<T>>,
+) -> Result<Option<[T; N]>, DecodeError> {
+    let Some(plain) = plain::<T, D::C>(transparent) else {
+        return Ok(None);
+    };
+
+    let mut array = core::mem::MaybeUninit::<[T; N]>::uninit();
+    read_uninit_elements(decoder, array_elements(&mut array), plain)?;
+    // Safety: every element is initialized
+    Ok(Some(unsafe { array.assume_init() }))
+}
+
+/// Reads an array that was written by [`encode_slice`] into `place`, like [`decode_array`].
+///
+/// Returns `Ok(false)` without reading anything if `T` has to be decoded element by element. `place` is initialized if this returns `Ok(true)`.
+pub fn decode_array_in_place<T, D: Decoder, const N: usize>(
+    decoder: &mut D,
+    place: &mut core::mem::MaybeUninit<[T; N]>,
+    transparent: Option<Transparent<T>>,
+) -> Result<bool, DecodeError> {
+    let Some(plain) = plain::<T, D::C>(transparent) else {
+        return Ok(false);
+    };
+
+    read_uninit_elements(decoder, array_elements(place), plain)?;
+    Ok(true)
+}
+
+/// Returns the elements of an uninitialized array.
+const fn array_elements<T, const N: usize>(
+    array: &mut core::mem::MaybeUninit<[T; N]>,
+) -> &mut [core::mem::MaybeUninit<T>] {
+    // Safety: `MaybeUninit<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`
+    unsafe { &mut *array.as_mut_ptr().cast::<[core::mem::MaybeUninit<T>; N]>() }
+}
+
+/// Reads the bytes of `elements` with [`Reader::read_uninit`], which initializes every element if this returns `Ok`.
+///
+/// The elements do not need to be zeroed first, so every byte of a large collection is only written once.
+fn read_uninit_elements<T, D: Decoder>(
+    decoder: &mut D,
+    elements: &mut [core::mem::MaybeUninit<T>],
+    plain: Plain,
+) -> Result<(), DecodeError> {
+    let len = core::mem::size_of_val(elements);
+    if len == 0 {
+        return Ok(());
+    }
+    let start = elements.as_mut_ptr().cast::<core::mem::MaybeUninit<u8>>();
+    // Safety: the elements may hold any bytes, including uninitialized ones, so their memory can be written as `MaybeUninit<u8>`
+    let bytes = unsafe { core::slice::from_raw_parts_mut(start, len) };
+    let bytes = decoder.reader().read_uninit(bytes)?;
+    // a reader that returns other bytes than it was given would leave the elements uninitialized
+    if bytes.as_mut_ptr() != start.cast::<u8>() || bytes.len() != len {
+        return Err(DecodeError::Other(
+            "Reader::read_uninit returned other bytes than it was given",
+        ));
+    }
+    if plain.swap {
+        swap_elements(bytes, plain.size);
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Values that are encoded once, and written as the same bytes every time they are encoded again.
+//!
+//! A large value that does not change, but is part of many messages, is encoded again for every message. [`PreEncoded`\] encodes it once when it is created, and writes the bytes it encoded to every message it is part of. The bytes are the same as those of the value itself, so a `PreEncoded<T>` can be decoded as a `T` and the other way around.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::pre_encoded::PreEncoded;
+//!
+//! #[derive(bincode::Encode, bincode::Decode, Clone, PartialEq, Debug)]
+//! struct Manifest {
+//!     assets: Vec<String>,
+//! }
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! struct Update {
+//!     tick: u64,
+//!     manifest: PreEncoded<Manifest>,
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let manifest = Manifest { assets: vec![String::from("map.bin")] };
+//! let cached = PreEncoded::new(&manifest, config).unwrap();
+//!
+//! for tick in 0..3 {
+//!     let update = Update { tick, manifest: cached.clone() };
+//!     let encoded = bincode::encode_to_vec(&update, config).unwrap();
+//!     assert_eq!(encoded, bincode::encode_to_vec((tick, &manifest), config).unwrap());
+//!
+//!     let (decoded, _): (Update, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+//!     assert_eq!(decoded.manifest.get(), &manifest);
+//! }
+//! ```
+//!
+//! The bytes depend on the config, so a `PreEncoded` can only be encoded with a config that encodes values the same way as the config it was created with: the endianness, the int encoding, the length encoding and the path encoding must be the same. Any other config fails with [`EncodeError::PreEncodedConfigMismatch`\]. The options that only affect decoding, like the byte limit, can differ.
+//!
+//! The value is encoded on its own, by an encoder of its own. Values that depend on the other values of the message they are encoded in, like [`SharedArc`](crate::shared::SharedArc), do not refer to the values outside of it.
+
+use crate::{
+    config::{Config, Endianness, IntEncoding, LengthEncoding, PathEncoding},
+    de::{BorrowDecode, BorrowDecoder, Decode, Decoder},
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::vec::Vec;
+
+/// A value and the bytes it is encoded as. See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct PreEncoded<T> {
+    value: T,
+    bytes: Vec<u8>,
+    format: Format,
+}
+
+impl<T: Encode + Clone> PreEncoded<T> {
+    /// Encodes `value` with `config`, and keeps a copy of it along with its bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while encoding `value`.
+    pub fn new<C: Config>(value: &T, config: C) -> Result<Self, EncodeError> {
+        Ok(Self {
+            bytes: crate::encode_to_vec(value, config)?,
+            value: value.clone(),
+            format: Format::of(&config),
+        })
+    }
+}
+
+impl<T> PreEncoded<T> {
+    /// Returns the value.
+    #[must_use]
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the value, dropping its bytes.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the bytes that the value is encoded as.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<T: PartialEq> PartialEq for PreEncoded<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Encode for PreEncoded<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if Format::of(encoder.config()) != self.format {
+            return Err(EncodeError::PreEncodedConfigMismatch);
+        }
+        encoder.writer().write(&self.bytes)
+    }
+}
+
+impl<Context, T: Decode<Context> + Encode> Decode<Context> for PreEncoded<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let value = T::decode(decoder)?;
+        cache(value, *decoder.config())
+    }
+}
+
+impl<'de, Context, T: BorrowDecode<'de, Context> + Encode> BorrowDecode<'de, Context>
+    for PreEncoded<T>
+{
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let value = T::borrow_decode(decoder)?;
+        cache(value, *decoder.config())
+    }
+}
+
+/// Encodes a decoded `value` again with the config it was decoded with
+fn cache<T: Encode, C: Config>(value: T, config: C) -> Result<PreEncoded<T>, DecodeError> {
+    let bytes = crate::encode_to_vec(&value, config).map_err(|err| {
+        DecodeError::OtherString(alloc::format!("could not encode the decoded value: {err}"))
+    })?;
+    Ok(PreEncoded {
+        value,
+        bytes,
+        format: Format::of(&config),
+    })
+}
+
+/// The options of a config that change the bytes a value is encoded as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Format {
+    endian: Endianness,
+    int_encoding: IntEncoding,
+    length_encoding: LengthEncoding,
+    path_encoding: PathEncoding,
+}
+
+impl Format {
+    fn of<C: Config>(config: &C) -> Self {
+        Self {
+            endian: config.endianness(),
+            int_encoding: config.int_encoding(),
+            length_encoding: config.length_encoding(),
+            path_encoding: config.path_encoding(),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Values behind an `Rc` or `Arc` that are shared by several references, and encoded only once.
+//!
+//! `Rc<T>` and `Arc<T>` are encoded as their value, so a value with 1000 references is encoded 1000 times, and decoded into 1000 separate copies. [`SharedRc`\] and [`SharedArc`\] keep the sharing intact instead:
+//!
+//! - The first time a shared value is encoded, a `0` is written, followed by the value.
+//! - Every following time, only `n` is written: the value is the `n`th shared value that was encoded, counting from 1.
+//!
+//! The tags are encoded like a `usize`. The encoder keeps track of the shared values on its own. The decoder keeps track of them in its context, which must implement [`SharedContext`\]. Pass a [`SharedTable`\] as the context, or implement the trait for your own context. Derived types that contain shared values need `#[bincode(decode_context = "SharedTable")]`.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::shared::{SharedArc, SharedTable};
+//! use std::sync::Arc;
+//!
+//! #[derive(bincode::Encode, bincode::Decode)]
+//! #[bincode(decode_context = "SharedTable")]
+//! struct Node {
+//!     mesh: SharedArc<Vec<u8>>,
+//! }
+//!
+//! let mesh = SharedArc(Arc::new(vec![0u8; 1000]));
+//! let nodes: Vec<Node> = (0..100).map(|_| Node { mesh: mesh.clone() }).collect();
+//!
+//! let config = bincode::config::standard();
+//! let encoded = bincode::encode_to_vec(&nodes, config).unwrap();
+//! assert!(encoded.len() < 1200);
+//!
+//! let (decoded, _): (Vec<Node>, _) =
+//!     bincode::decode_from_slice_with_context(&encoded, config, SharedTable::default()).unwrap();
+//! assert!(Arc::ptr_eq(&decoded[0].mesh.0, &decoded[99].mesh.0));
+//! ```
+//!
+//! Shared values can not contain themselves: a cycle (e.g. through a `Mutex`) fails with [`EncodeError::SharedCycle`\] when it is encoded.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::{boxed::Box, collections::BTreeMap, rc::Rc, vec::Vec};
+use core::any::Any;
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// An `Arc` that is encoded only once, no matter how many references to its value are encoded. See the [module documentation](self).
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SharedArc<T>(pub Arc<T>);
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Clone for SharedArc<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> From<Arc<T>> for SharedArc<T> {
+    fn from(value: Arc<T>) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: Encode> Encode for SharedArc<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_shared(encoder, Arc::as_ptr(&self.0).cast(), &*self.0)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<Context: SharedContext, T: Decode<Context> + 'static> Decode<Context> for SharedArc<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decode_shared(decoder, Arc::new).map(Self)
+    }
+}
+#[cfg(target_has_atomic = "ptr")]
+impl<'de, Context: SharedContext, T: Decode<Context> + 'static> crate::BorrowDecode<'de, Context>
+    for SharedArc<T>
+{
+    fn borrow_decode<D: crate::de::BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+/// An `Rc` that is encoded only once, no matter how many references to its value are encoded. See the [module documentation](self).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SharedRc<T>(pub Rc<T>);
+
+impl<T> Clone for SharedRc<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> From<Rc<T>> for SharedRc<T> {
+    fn from(value: Rc<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Encode> Encode for SharedRc<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encode_shared(encoder, Rc::as_ptr(&self.0).cast(), &*self.0)
+    }
+}
+
+impl<Context: SharedContext, T: Decode<Context> + 'static> Decode<Context> for SharedRc<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decode_shared(decoder, Rc::new).map(Self)
+    }
+}
+impl<'de, Context: SharedContext, T: Decode<Context> + 'static> crate::BorrowDecode<'de, Context>
+    for SharedRc<T>
+{
+    fn borrow_decode<D: crate::de::BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+/// A decode context that keeps track of the shared values that were decoded. See the [module documentation](self).
+pub trait SharedContext {
+    /// Returns the shared values that were decoded so far.
+    fn shared_table(&mut self) -> &mut SharedTable;
+}
+
+/// The shared values that were decoded so far, to hand out another reference when they are referred to again. See the [module documentation](self).
+///
+/// Use a new table for every value that is decoded, as the references only refer to values within the same encoded value.
+#[derive(Default)]
+pub struct SharedTable {
+    /// The decoded values, as the `Rc` or `Arc` that holds them. `None` while the value is being decoded.
+    values: Vec<Option<Box<dyn Any>>>,
+}
+
+impl SharedTable {
+    /// Creates an empty table.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    /// Returns the amount of shared values that were decoded.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no shared values were decoded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl core::fmt::Debug for SharedTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedTable")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+impl SharedContext for SharedTable {
+    fn shared_table(&mut self) -> &mut SharedTable {
+        self
+    }
+}
+
+impl<T: SharedContext> SharedContext for &mut T {
+    fn shared_table(&mut self) -> &mut SharedTable {
+        T::shared_table(self)
+    }
+}
+
+/// The shared values that an encoder encoded so far, by the address of their value. Every encoder has its own, see [`Encoder::shared_pointers`].
+#[derive(Default)]
+pub struct SharedPointers {
+    /// The id of every value, and whether it is still being encoded
+    ids: BTreeMap<usize, (usize, bool)>,
+}
+
+impl SharedPointers {
+    pub(crate) const fn new() -> Self {
+        Self {
+            ids: BTreeMap::new(),
+        }
+    }
+
+    /// Returns whether no shared values were encoded yet.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+fn encode_shared<E: Encoder, T: Encode>(
+    encoder: &mut E,
+    ptr: *const (),
+    value: &T,
+) -> Result<(), EncodeError> {
+    let address = ptr as usize;
+    let pointers = encoder.shared_pointers();
+    if let Some(&(id, encoding)) = pointers.ids.get(&address) {
+        if encoding {
+            return Err(EncodeError::SharedCycle);
+        }
+        return (id + 1).encode(encoder);
+    }
+    let id = pointers.ids.len();
+    pointers.ids.insert(address, (id, true));
+    0usize.encode(encoder)?;
+    value.encode(encoder)?;
+    encoder.shared_pointers().ids.insert(address, (id, false));
+    Ok(())
+}
+
+fn decode_shared<D, T, P>(decoder: &mut D, wrap: impl FnOnce(T) -> P) -> Result<P, DecodeError>
+where
+    D: Decoder,
+    D::Context: SharedContext,
+    T: Decode<D::Context>,
+    P: Clone + 'static,
+{
+    match usize::decode(decoder)? {
+        0 => {
+            let values = &mut decoder.context().shared_table().values;
+            let id = values.len();
+            values.push(None);
+            let shared = wrap(T::decode(decoder)?);
+            let values = &mut decoder.context().shared_table().values;
+            if let Some(slot) = values.get_mut(id) {
+                *slot = Some(Box::new(shared.clone()));
+            }
+            Ok(shared)
+        }
+        tag => {
+            let id = tag - 1;
+            match decoder.context().shared_table().values.get(id) {
+                Some(Some(value)) => value
+                    .downcast_ref::<P>()
+                    .cloned()
+                    .ok_or(DecodeError::InvalidSharedId { id }),
+                Some(None) => Err(DecodeError::SharedCycle { id }),
+                None => Err(DecodeError::InvalidSharedId { id }),
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+#![cfg_attr(
+    all(feature = "alloc", feature = "derive", doc),
+    doc = include_str!("../docs/spec.md")
+)]
+//! # Describing and checking the format
+//!
+//! [`describe`] returns the settings of a config as a [`FormatDescription`], so an application can store which format its data was encoded with next to the data.
+//!
+//! [`canonical_encodings`] encodes a fixed set of values, like every integer type at the boundaries of the varint encoding, strings, collections, options and enums, and [`decode_canonical`] checks that bytes decode to one of those values. The tests of this crate compare them against golden byte vectors for every combination of endianness, int encoding and length encoding, so a release that changes the bytes of any of these values fails its tests. An application that archives data can run the same check against the bytes its own version produced.
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! use bincode::config::{self, Endianness, IntEncoding};
+//!
+//! let format = bincode::spec::describe(config::legacy());
+//! assert_eq!(format.endianness, Endianness::Little);
+//! assert_eq!(format.int_encoding, IntEncoding::Fixed);
+//! assert!(format.to_string().starts_with("little endian, fixed int encoding"));
+//!
+//! let encodings = bincode::spec::canonical_encodings(config::standard()).unwrap();
+//! let (name, bytes) = &encodings[0];
+//! assert!(bincode::spec::decode_canonical(name, bytes, config::standard()).unwrap());
+//! ```
+
+use crate::config::{
+    Allocation, CharEncoding, Config, Endianness, IntEncoding, LengthEncoding, MapOrder,
+    PathEncoding, SystemTimeDecoding, TrailingBytes, Utf8Decoding,
+};
+use core::fmt;
+
+/// The settings of a config, see [`describe`].
+///
+/// The first five settings change the bytes that are written. The others only change which input is accepted when decoding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FormatDescription {
+    /// The byte order of integers and floats
+    pub endianness: Endianness,
+    /// How integers, enum tags and lengths are encoded
+    pub int_encoding: IntEncoding,
+    /// How the lengths of collections and strings are encoded
+    pub length_encoding: LengthEncoding,
+    /// How paths are encoded
+    pub path_encoding: PathEncoding,
+    /// How a `char` is encoded
+    pub char_encoding: CharEncoding,
+    /// The maximum amount of bytes that a decoded value may claim, if any
+    pub limit: Option<usize>,
+    /// How a failed allocation is handled while decoding
+    pub allocation: Allocation,
+    /// How strings that are not valid UTF-8 are decoded
+    pub utf8_decoding: Utf8Decoding,
+    /// How a `SystemTime` that the platform can not represent exactly is decoded
+    pub system_time_decoding: SystemTimeDecoding,
+    /// Whether the keys of a `BTreeMap` or `BTreeSet` must be in ascending order
+    pub map_order: MapOrder,
+    /// Whether bytes after a value that is decoded from a slice are rejected
+    pub trailing_bytes: TrailingBytes,
+}
+
+/// Returns the settings of `config`.
+#[must_use]
+pub fn describe<C: Config>(config: C) -> FormatDescription {
+    FormatDescription {
+        endianness: config.endianness(),
+        int_encoding: config.int_encoding(),
+        length_encoding: config.length_encoding(),
+        path_encoding: config.path_encoding(),
+        char_encoding: config.char_encoding(),
+        limit: config.limit(),
+        allocation: config.allocation(),
+        utf8_decoding: config.utf8_decoding(),
+        system_time_decoding: config.system_time_decoding(),
+        map_order: config.map_order(),
+        trailing_bytes: config.trailing_bytes(),
+    }
+}
+
+impl FormatDescription {
+    /// Returns a compact descriptor of these settings, which [`encode_with_config_header`](crate::encode_with_config_header) writes in front of a value so the decoder can check that it uses the same format. It is also returned by [`Config::wire_id`].
+    ///
+    /// The descriptor is 4 bytes:
+    /// 1. The version of this layout, which is 1.
+    /// 2. The settings that change the encoded bytes. Bit 0 is set for big endian, bit 1 for fixed int encoding, bits 2 and 3 are the length encoding (0 for lengths like a `u64`, 1 for varint lengths, 2 for fixed `u32` lengths and 3 for fixed `u64` lengths), bit 4 is set for lossless paths and bit 5 for scalar chars.
+    /// 3. The settings that only change which input is accepted when decoding. Bit 0 is set if there is a limit, bit 1 for fallible allocation, bit 2 for lossy UTF-8, bit 3 for exact system time, bit 4 for strict map order and bit 5 for no trailing bytes.
+    /// 4. Zero, reserved for later settings.
+    ///
+    /// The descriptor of a config never changes in later versions of this crate.
+    #[must_use]
+    pub const fn wire_id(&self) -> [u8; 4] {
+        let format = (matches!(self.endianness, Endianness::Big) as u8)
+            | (matches!(self.int_encoding, IntEncoding::Fixed) as u8) << 1
+            | (match self.length_encoding {
+                LengthEncoding::Int => 0,
+                LengthEncoding::Varint => 1,
+                LengthEncoding::FixedU32 => 2,
+                LengthEncoding::FixedU64 => 3,
+            }) << 2
+            | (matches!(self.path_encoding, PathEncoding::Lossless) as u8) << 4
+            | (matches!(self.char_encoding, CharEncoding::Scalar) as u8) << 5;
+        let decoding = (self.limit.is_some() as u8)
+            | (matches!(self.allocation, Allocation::Fallible) as u8) << 1
+            | (matches!(self.utf8_decoding, Utf8Decoding::Lossy) as u8) << 2
+            | (matches!(self.system_time_decoding, SystemTimeDecoding::Exact) as u8) << 3
+            | (matches!(self.map_order, MapOrder::Strict) as u8) << 4
+            | (matches!(self.trailing_bytes, TrailingBytes::Reject) as u8) << 5;
+        [WIRE_ID_VERSION, format, decoding, 0]
+    }
+}
+
+/// The version of the layout of [`FormatDescription::wire_id`]
+const WIRE_ID_VERSION: u8 = 1;
+
+/// The bits of the second byte of a wire id that are used
+const WIRE_ID_FORMAT_BITS: u8 = 0b11_1111;
+
+/// Returns the names of the settings in the second byte of a wire id, in the order of the bits.
+const fn format_names(format: u8) -> [&'static str; 5] {
+    [
+        if format & 1 == 0 {
+            "little endian"
+        } else {
+            "big endian"
+        },
+        if format & 0b10 == 0 {
+            "variable int encoding"
+        } else {
+            "fixed int encoding"
+        },
+        match (format >> 2) & 0b11 {
+            0 => "lengths like a u64",
+            1 => "varint lengths",
+            2 => "fixed u32 lengths",
+            _ => "fixed u64 lengths",
+        },
+        if format & 0b1_0000 == 0 {
+            "UTF-8 paths"
+        } else {
+            "lossless paths"
+        },
+        if format & 0b10_0000 == 0 {
+            "UTF-8 chars"
+        } else {
+            "scalar chars"
+        },
+    ]
+}
+
+/// Checks that the wire id `found` in a config header has the same format as `expected`. The settings that only change what is accepted when decoding may differ.
+pub(crate) const fn check_wire_id(
+    expected: [u8; 4],
+    found: [u8; 4],
+) -> Result<(), crate::error::DecodeError> {
+    if found[0] == expected[0] && found[1] == expected[1] {
+        Ok(())
+    } else {
+        Err(crate::error::DecodeError::ConfigMismatch { expected, found })
+    }
+}
+
+/// Describes how the format of the wire id `found` differs from `expected`, for [`DecodeError::ConfigMismatch`](crate::error::DecodeError::ConfigMismatch).
+pub(crate) fn fmt_config_mismatch(
+    expected: [u8; 4],
+    found: [u8; 4],
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    if found[0] != WIRE_ID_VERSION || found[1] & !WIRE_ID_FORMAT_BITS != 0 {
+        return write!(f, "the data has an unknown config header {found:02x?}");
+    }
+    f.write_str("the data was encoded with a different config:")?;
+    let mut first = true;
+    for (expected, found) in format_names(expected[1])
+        .into_iter()
+        .zip(format_names(found[1]))
+    {
+        if expected != found {
+            let separator = if first { "" } else { "," };
+            write!(f, "{separator} {found} instead of {expected}")?;
+            first = false;
+        }
+    }
+    Ok(())
+}
+
+/// Lists every setting in words, e.g. `little endian, variable int encoding, lengths like a u64, ...`.
+impl fmt::Display for FormatDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [endianness, int_encoding, length_encoding, path_encoding, char_encoding] =
+            format_names(self.wire_id()[1]);
+        write!(
+            f,
+            "{endianness}, {int_encoding}, {length_encoding}, {path_encoding}, {char_encoding}"
+        )?;
+        match self.limit {
+            Some(limit) => write!(f, ", a limit of {limit} bytes")?,
+            None => f.write_str(", no limit")?,
+        }
+        f.write_str(match self.allocation {
+            Allocation::Infallible => ", infallible allocation",
+            Allocation::Fallible => ", fallible allocation",
+        })?;
+        f.write_str(match self.utf8_decoding {
+            Utf8Decoding::Strict => ", strict UTF-8",
+            Utf8Decoding::Lossy => ", lossy UTF-8",
+        })?;
+        f.write_str(match self.system_time_decoding {
+            SystemTimeDecoding::Truncate => ", truncated system time",
+            SystemTimeDecoding::Exact => ", exact system time",
+        })?;
+        f.write_str(match self.map_order {
+            MapOrder::Any => ", any map order",
+            MapOrder::Strict => ", strict map order",
+        })?;
+        f.write_str(match self.trailing_bytes {
+            TrailingBytes::Any => ", any trailing bytes",
+            TrailingBytes::Reject => ", no trailing bytes",
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::canonical::{canonical_encodings, decode_canonical};
+
+#[cfg(feature = "alloc")]
+mod canonical {
+    use crate::{
+        config::Config,
+        error::{DecodeError, EncodeError},
+    };
+    use alloc::{
+        collections::BTreeMap,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::{ops::Bound, task::Poll, time::Duration};
+
+    /// Defines `canonical_encodings` and `decode_canonical` for a list of `"name": Type = value` entries.
+    macro_rules! canonical_values {
+        ($($name:literal: $ty:ty = $value:expr,)*) => {
+            /// Encodes every value of the canonical set with `config`, and returns the name and the bytes of each value, in a fixed order.
+            ///
+            /// The names are stable. New values are only added at the end.
+            ///
+            /// # Errors
+            ///
+            /// Returns an `EncodeError` if a value can not be encoded with `config`, e.g. because it exceeds a limit.
+            pub fn canonical_encodings<C: Config>(
+                config: C,
+            ) -> Result<Vec<(&'static str, Vec<u8>)>, EncodeError> {
+                Ok(vec![$(($name, crate::encode_to_vec::<$ty, C>($value, config)?),)*])
+            }
+
+            /// Decodes `bytes` as the value of the canonical set called `name`, and returns whether all bytes were read and the decoded value encodes to the same bytes as that value.
+            ///
+            /// Returns `Ok(false)` if there is no value called `name`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a `DecodeError` if `bytes` can not be decoded as the type of the value.
+            pub fn decode_canonical<C: Config>(
+                name: &str,
+                bytes: &[u8],
+                config: C,
+            ) -> Result<bool, DecodeError> {
+                match name {
+                    $($name => {
+                        let (decoded, len): ($ty, usize) = crate::decode_from_slice(bytes, config)?;
+                        // compared by their bytes, so e.g. `-0.0` is not the same as `0.0`
+                        let expected = crate::encode_to_vec::<$ty, C>($value, config).ok();
+                        Ok(len == bytes.len() && expected.is_some() && crate::encode_to_vec(decoded, config).ok() == expected)
+                    })*
+                    _ => Ok(false),
+                }
+            }
+        };
+    }
+
+    canonical_values! {
+        "unit": () = (),
+        "bool false": bool = false,
+        "bool true": bool = true,
+        "u8 max": u8 = u8::MAX,
+        "u16 250": u16 = 250,
+        "u16 251": u16 = 251,
+        "u16 max": u16 = u16::MAX,
+        "u32 0": u32 = 0,
+        "u32 250": u32 = 250,
+        "u32 251": u32 = 251,
+        "u32 65535": u32 = 65_535,
+        "u32 65536": u32 = 65_536,
+        "u32 max": u32 = u32::MAX,
+        "u64 250": u64 = 250,
+        "u64 4294967295": u64 = u64::from(u32::MAX),
+        "u64 4294967296": u64 = 1 << 32,
+        "u64 max": u64 = u64::MAX,
+        "u128 18446744073709551615": u128 = u128::from(u64::MAX),
+        "u128 18446744073709551616": u128 = 1 << 64,
+        "u128 max": u128 = u128::MAX,
+        "usize 251": usize = 251,
+        "i8 min": i8 = i8::MIN,
+        "i8 -1": i8 = -1,
+        "i8 max": i8 = i8::MAX,
+        "i16 -126": i16 = -126,
+        "i16 125": i16 = 125,
+        "i16 126": i16 = 126,
+        "i16 min": i16 = i16::MIN,
+        "i16 max": i16 = i16::MAX,
+        "i32 -1": i32 = -1,
+        "i32 -32769": i32 = -32_769,
+        "i32 min": i32 = i32::MIN,
+        "i32 max": i32 = i32::MAX,
+        "i64 -2147483649": i64 = i64::from(i32::MIN) - 1,
+        "i64 min": i64 = i64::MIN,
+        "i64 max": i64 = i64::MAX,
+        "i128 min": i128 = i128::MIN,
+        "i128 max": i128 = i128::MAX,
+        "isize -1": isize = -1,
+        "f32 -0": f32 = -0.0,
+        "f32 1.5": f32 = 1.5,
+        "f32 smallest subnormal": f32 = f32::from_bits(1),
+        "f32 infinity": f32 = f32::INFINITY,
+        "f64 -2.5": f64 = -2.5,
+        "f64 max": f64 = f64::MAX,
+        "f64 negative infinity": f64 = f64::NEG_INFINITY,
+        "char a": char = 'a',
+        "char e acute": char = '\u{e9}',
+        "char 3 bytes": char = '\u{6587}',
+        "char 4 bytes": char = '\u{1f600}',
+        "string empty": String = String::new(),
+        "string ascii": String = "hello".to_string(),
+        "string unicode": String = "gr\u{fc}\u{df}e \u{1f600}".to_string(),
+        "string 251 bytes": String = "x".repeat(251),
+        "bytes": Vec<u8> = vec![0, 1, 254, 255],
+        "vec u16": Vec<u16> = vec![1, 256, u16::MAX],
+        "vec u32 varint": Vec<u32> = vec![0, 251, u32::MAX],
+        "vec i64": Vec<i64> = vec![-1, 0, i64::MAX],
+        "vec string": Vec<String> = vec!["a".to_string(), String::new()],
+        "vec empty": Vec<u64> = Vec::new(),
+        "array u8": [u8; 4] = [1, 2, 3, 4],
+        "array u32": [u32; 3] = [1, 300, 70_000],
+        "tuple": (u8, u16, u32, u64) = (1, 2, 3, 4),
+        "option none": Option<u32> = None,
+        "option some": Option<u32> = Some(300),
+        "option nested": Option<Option<()>> = Some(None),
+        "result ok": Result<u8, String> = Ok(7),
+        "result err": Result<u8, String> = Err("no".to_string()),
+        "enum bound included": Bound<u16> = Bound::Included(300),
+        "enum bound excluded": Bound<u16> = Bound::Excluded(1),
+        "enum bound unbounded": Bound<u16> = Bound::Unbounded,
+        "enum poll ready": Poll<i8> = Poll::Ready(-1),
+        "enum poll pending": Poll<i8> = Poll::Pending,
+        "duration": Duration = Duration::new(90_061, 5),
+        "map": BTreeMap<u32, String> = BTreeMap::from([(1, "one".to_string()), (300, "three hundred".to_string())]),
+        "nested": Vec<(String, Option<Vec<u16>>)> = vec![("a".to_string(), Some(vec![1, 2])), ("b".to_string(), None)],
+    }
+}
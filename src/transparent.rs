@@ -0,0 +1,107 @@
+//! Newtypes around primitives that are encoded exactly like the primitive, so slices of them use the same fast paths.
+//!
+//! Slices, arrays, `Vec`s and `VecDeque`s of integers and floats are written and read as their in-memory bytes in a single call instead of element by element, when the config allows it (floats always, integers with fixed int encoding). A newtype like `struct Meters(f32)` is encoded exactly like an `f32`, but bincode can not know that its memory is an `f32` as well, so a `Vec<Meters>` is encoded element by element.
+//!
+//! `#[derive(TransparentWire)]` (with the `derive` feature) on a `#[repr(transparent)]` newtype implements [`TransparentWire`], as well as [`Encode`], [`Decode`] and [`BorrowDecode`](crate::BorrowDecode) as the inner value, and lets the collections of the newtype use the fast paths of the primitive:
+//!
+//! ```
+//! # extern crate bincode_next as bincode;
+//! #[derive(bincode::TransparentWire, Clone, Copy, PartialEq, Debug)]
+//! #[repr(transparent)]
+//! struct Meters(f32);
+//!
+//! let config = bincode::config::standard();
+//! let distances = vec![Meters(1.5), Meters(2.0), Meters(-0.25)];
+//! let encoded = bincode::encode_to_vec(&distances, config).unwrap();
+//! assert_eq!(encoded, bincode::encode_to_vec(vec![1.5f32, 2.0, -0.25], config).unwrap());
+//!
+//! let (decoded, _): (Vec<Meters>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+//! assert_eq!(decoded, distances);
+//! ```
+//!
+//! The inner value can be a primitive or another newtype that derives `TransparentWire`. Do not derive `Encode` or `Decode` as well, the derive already implements them. The derive only accepts structs with `#[repr(transparent)]`, no generics and a single field without attributes:
+//!
+//! ```compile_fail
+//! # extern crate bincode_next as bincode;
+//! #[derive(bincode::TransparentWire)]
+//! struct Meters(f32);
+//! ```
+//!
+//! Values are never validated: every value of the inner type decodes into a valid newtype. A newtype that only allows some values, like a `Percentage(u8)` of at most 100, must implement `Decode` itself instead.
+
+#![allow(unsafe_code)]
+
+use crate::{
+    config::{Endianness, IntEncoding},
+    de::Decode,
+    enc::Encode,
+    plain::Plain,
+};
+use core::marker::PhantomData;
+
+/// A type that has the same memory layout and encoding as [`Wire`](TransparentWire::Wire), so slices of it can be encoded and decoded as slices of `Wire`. See the [module documentation](self).
+///
+/// Implement this with `#[derive(TransparentWire)]`.
+///
+/// # Safety
+///
+/// `Self` must have the same size, alignment and memory layout as `Wire`, e.g. because it is a `#[repr(transparent)]` struct around it, and every value of `Wire` must be a valid value of `Self`. Its [`Encode`] and [`Decode`] implementations must encode and decode exactly like those of `Wire`, and return [`Transparent::of`] from `transparent_wire`.
+pub unsafe trait TransparentWire: Sized {
+    /// The primitive that this type is encoded as. Slices of this type only use the fast paths if this is an integer or a float.
+    type Wire: Encode + Decode<()>;
+}
+
+macro_rules! impl_transparent_wire {
+    ($($ty:ty),*) => {$(
+        // Safety: every primitive is its own wire type
+        unsafe impl TransparentWire for $ty {
+            type Wire = Self;
+        }
+    )*};
+}
+
+impl_transparent_wire!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Proof that `T` implements [`TransparentWire`], returned by the `transparent_wire` method of `Encode`, `Decode` and `BorrowDecode`.
+#[doc(hidden)]
+pub struct Transparent<T> {
+    /// How a slice of the wire type of `T` is encoded
+    plain: fn(Endianness, IntEncoding) -> Option<Plain>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Transparent<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Transparent<T> {}
+
+impl<T: TransparentWire> Transparent<T> {
+    /// The proof that `T` implements [`TransparentWire`].
+    #[must_use]
+    pub const fn of() -> Self {
+        Self {
+            plain: wire_plain::<T>,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Transparent<T> {
+    /// Returns how a slice of `T` is encoded with the given endianness and int encoding, or `None` if it has to be encoded element by element.
+    pub(crate) fn plain(self, endian: Endianness, int_encoding: IntEncoding) -> Option<Plain> {
+        (self.plain)(endian, int_encoding)
+    }
+}
+
+fn wire_plain<T: TransparentWire>(endian: Endianness, int_encoding: IntEncoding) -> Option<Plain> {
+    // the bytes of `T` are only reinterpreted as its wire type if their sizes match
+    if core::mem::size_of::<T>() != core::mem::size_of::<T::Wire>()
+        || core::mem::align_of::<T>() != core::mem::align_of::<T::Wire>()
+    {
+        return None;
+    }
+    crate::plain::primitive::<T::Wire>(endian, int_encoding)
+}
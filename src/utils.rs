@@ -1,3 +1,6 @@
 pub trait Sealed {}
 
 impl<T> Sealed for &mut T where T: Sealed {}
+
+/// An argument of trait methods that only this crate may call and override. The type can not be named outside of the crate, so an implementation there can not override such a method, and has to use its default.
+pub struct Private;
@@ -3,7 +3,7 @@ use core::convert::TryInto;
 use super::{SINGLE_BYTE_MAX, U128_BYTE, U16_BYTE, U32_BYTE, U64_BYTE};
 use crate::{
     config::Endianness,
-    de::read::Reader,
+    de::read::{peek_exact, Reader},
     error::{DecodeError, IntegerType},
 };
 
@@ -200,7 +200,7 @@ const fn invalid_varint_discriminant<T>(
 }
 
 pub fn varint_decode_u16<R: Reader>(read: &mut R, endian: Endianness) -> Result<u16, DecodeError> {
-    if let Some(bytes) = read.peek_read(3) {
+    if let Some(bytes) = peek_exact(read, 3) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
             byte @ 0..=SINGLE_BYTE_MAX => (u16::from(byte), 1),
@@ -226,7 +226,7 @@ pub fn varint_decode_u16<R: Reader>(read: &mut R, endian: Endianness) -> Result<
 }
 
 pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endianness) -> Result<u32, DecodeError> {
-    if let Some(bytes) = read.peek_read(5) {
+    if let Some(bytes) = peek_exact(read, 5) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
             byte @ 0..=SINGLE_BYTE_MAX => (u32::from(byte), 1),
@@ -259,7 +259,7 @@ pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endianness) -> Result<
 }
 
 pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endianness) -> Result<u64, DecodeError> {
-    if let Some(bytes) = read.peek_read(9) {
+    if let Some(bytes) = peek_exact(read, 9) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
             byte @ 0..=SINGLE_BYTE_MAX => (u64::from(byte), 1),
@@ -302,7 +302,7 @@ pub fn varint_decode_usize<R: Reader>(
     read: &mut R,
     endian: Endianness,
 ) -> Result<usize, DecodeError> {
-    if let Some(bytes) = read.peek_read(9) {
+    if let Some(bytes) = peek_exact(read, 9) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
             byte @ 0..=SINGLE_BYTE_MAX => (byte as usize, 1),
@@ -348,7 +348,7 @@ pub fn varint_decode_u128<R: Reader>(
     read: &mut R,
     endian: Endianness,
 ) -> Result<u128, DecodeError> {
-    if let Some(bytes) = read.peek_read(17) {
+    if let Some(bytes) = peek_exact(read, 17) {
         let (discriminant, bytes) = bytes.split_at(1);
         let (out, used) = match discriminant[0] {
             byte @ 0..=SINGLE_BYTE_MAX => (u128::from(byte), 1),
@@ -0,0 +1,181 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::{string::String, string::ToString, vec::Vec};
+use bincode::{
+    adapters::{InRange, Mapped, Mapping, Validated, Validator},
+    config,
+    error::DecodeError,
+};
+
+/// Accepts port numbers that do not need root
+struct Unprivileged;
+
+impl Validator<u16> for Unprivileged {
+    fn validate(port: &u16) -> Result<(), &'static str> {
+        if *port >= 1024 {
+            Ok(())
+        } else {
+            Err("privileged port")
+        }
+    }
+}
+
+/// Trims names and stores them in lower case, and rejects empty names
+struct Normalized;
+
+impl Mapping<String, String> for Normalized {
+    fn from_wire(wire: String) -> Result<String, &'static str> {
+        let name = wire.trim().to_lowercase();
+        if name.is_empty() {
+            Err("empty name")
+        } else {
+            Ok(name)
+        }
+    }
+
+    fn to_wire(name: &String) -> String {
+        name.clone()
+    }
+}
+
+/// Stores a user id as a `u64`, which is `0` for none
+struct OptionalId;
+
+impl Mapping<u64, Option<u32>> for OptionalId {
+    fn from_wire(wire: u64) -> Result<Option<u32>, &'static str> {
+        match wire {
+            0 => Ok(None),
+            id => u32::try_from(id).map(Some).map_err(|_| "id out of range"),
+        }
+    }
+
+    fn to_wire(id: &Option<u32>) -> u64 {
+        id.map_or(0, u64::from)
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Service {
+    name: Mapped<String, String, Normalized>,
+    port: Validated<u16, Unprivileged>,
+    weight: Validated<i8, InRange<-10, 10>>,
+    owner: Mapped<u64, Option<u32>, OptionalId>,
+}
+
+fn encode_raw(name: &str, port: u16, weight: i8, owner: u64) -> Vec<u8> {
+    bincode::encode_to_vec((name, port, weight, owner), config::standard()).unwrap()
+}
+
+#[test]
+fn test_validated_range() {
+    let config = config::standard();
+
+    let (service, _): (Service, usize) =
+        bincode::decode_from_slice(&encode_raw("db", 5432, -3, 0), config).unwrap();
+    assert_eq!(*service.port, 5432);
+    assert_eq!(*service.weight, -3);
+
+    let err =
+        bincode::decode_from_slice::<Service, _>(&encode_raw("db", 80, 1, 0), config).unwrap_err();
+    assert!(
+        matches!(
+            err.inner(),
+            DecodeError::InvalidValue {
+                type_name: "u16",
+                message: "privileged port",
+            }
+        ),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("privileged port"), "{err}");
+
+    for weight in [11, -11, i8::MAX, i8::MIN] {
+        let err =
+            bincode::decode_from_slice::<Service, _>(&encode_raw("db", 5432, weight, 0), config)
+                .unwrap_err();
+        assert!(
+            matches!(
+                err.inner(),
+                DecodeError::InvalidValue {
+                    type_name: "i8",
+                    message: "out of range",
+                }
+            ),
+            "{weight}: {err:?}"
+        );
+    }
+
+    assert_eq!(
+        Validated::<u16, Unprivileged>::new(22).unwrap_err(),
+        "privileged port"
+    );
+    assert!(Validated::<i8, InRange<-10, 10>>::new(10).is_ok());
+}
+
+#[test]
+fn test_normalized_string_round_trip() {
+    let config = config::standard();
+
+    let (service, len): (Service, usize) =
+        bincode::decode_from_slice(&encode_raw("  PostgreS ", 5432, 0, 7), config).unwrap();
+    assert_eq!(*service.name, "postgres");
+    assert_eq!(*service.owner, Some(7));
+
+    // the normalized value is encoded, and decodes to the same value
+    let encoded = bincode::encode_to_vec(&service, config).unwrap();
+    assert_eq!(encoded, encode_raw("postgres", 5432, 0, 7));
+    assert!(encoded.len() < len);
+    let (decoded, _): (Service, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, service);
+    assert_eq!(decoded.name.into_inner(), "postgres");
+
+    let service = Service {
+        name: Mapped::new(String::from("cache")),
+        port: Validated::new(6379).unwrap(),
+        weight: Validated::new(1).unwrap(),
+        owner: Mapped::new(None),
+    };
+    let encoded = bincode::encode_to_vec(&service, config).unwrap();
+    assert_eq!(encoded, encode_raw("cache", 6379, 1, 0));
+
+    let err = bincode::decode_from_slice::<Service, _>(&encode_raw("   ", 5432, 0, 0), config)
+        .unwrap_err();
+    assert!(
+        matches!(
+            err.inner(),
+            DecodeError::InvalidValue {
+                message: "empty name",
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+    let err =
+        bincode::decode_from_slice::<Service, _>(&encode_raw("db", 5432, 0, u64::MAX), config)
+            .unwrap_err();
+    assert!(
+        matches!(
+            err.inner(),
+            DecodeError::InvalidValue {
+                message: "id out of range",
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_transparent() {
+    assert_eq!(
+        core::mem::size_of::<Validated<u16, Unprivileged>>(),
+        core::mem::size_of::<u16>()
+    );
+    assert_eq!(
+        core::mem::size_of::<Mapped<u64, Option<u32>, OptionalId>>(),
+        core::mem::size_of::<Option<u32>>()
+    );
+}
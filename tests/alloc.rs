@@ -178,6 +178,89 @@ fn test_container_limits() {
     }
 }
 
+#[test]
+fn test_fallible_allocation() {
+    let config = bincode::config::standard().with_fallible_allocation();
+    assert_eq!(
+        bincode::config::Config::allocation(&config.with_limit::<1024>()),
+        bincode::config::Allocation::Fallible
+    );
+
+    // The wire format is the same as with infallible allocation
+    let value = (
+        vec![1u32, 2, 3],
+        String::from("Hello world"),
+        vec![String::from("a"), String::from("b")],
+        VecDeque::from(vec![4u64, 5]),
+    );
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(&value, bincode::config::standard()).unwrap()
+    );
+    let (decoded, len) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(value, decoded);
+    assert_eq!(len, encoded.len());
+
+    // Elements that are decoded one by one grow the vec as they arrive
+    let strings: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+    let encoded = bincode::encode_to_vec(&strings, config).unwrap();
+    let (decoded, _): (Vec<String>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, strings);
+}
+
+#[test]
+fn test_zero_sized_container_limits() {
+    use bincode::{error::DecodeError, Decode};
+    use core::marker::PhantomData;
+
+    const DECODE_LIMIT: usize = 100_000;
+    let config = bincode::config::standard().with_limit::<DECODE_LIMIT>();
+
+    fn validate_fail<T: Decode<()> + core::fmt::Debug>(slice: &[u8]) {
+        let config = bincode::config::standard().with_limit::<DECODE_LIMIT>();
+        let start = std::time::Instant::now();
+        let result = bincode::decode_from_slice::<T, _>(slice, config);
+
+        let name = core::any::type_name::<T>();
+        match result.map_err(DecodeError::into_inner) {
+            Ok(_) => panic!("Decoding {name} should fail, it instead succeeded"),
+            Err(DecodeError::LimitExceeded) => {}
+            Err(e) => panic!("Expected LimitExceeded whilst decoding {name}, got {e:?}"),
+        }
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(1),
+            "Decoding {name} took {:?}",
+            start.elapsed()
+        );
+    }
+
+    // zero-sized elements take no bytes of input at all, so a length prefix is all that is needed
+    for len in [usize::MAX as u64, (DECODE_LIMIT + 1) as u64] {
+        let slice = bincode::encode_to_vec(len, bincode::config::standard()).unwrap();
+        validate_fail::<Vec<()>>(&slice);
+        validate_fail::<Vec<PhantomData<u32>>>(&slice);
+        validate_fail::<VecDeque<()>>(&slice);
+        validate_fail::<BTreeSet<()>>(&slice);
+        validate_fail::<BTreeMap<(), ()>>(&slice);
+        validate_fail::<Vec<Vec<()>>>(&slice);
+        #[cfg(feature = "std")]
+        {
+            validate_fail::<std::collections::HashMap<(), ()>>(&slice);
+            validate_fail::<std::collections::HashSet<()>>(&slice);
+        }
+    }
+
+    // every element counts as one byte, and is un-claimed again when it is decoded
+    let encoded = bincode::encode_to_vec(vec![(); DECODE_LIMIT - 10], config).unwrap();
+    let (decoded, _): (Vec<()>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded.len(), DECODE_LIMIT - 10);
+    let nested = vec![vec![(); 1000]; 50];
+    let encoded = bincode::encode_to_vec(&nested, config).unwrap();
+    let (decoded, _): (Vec<Vec<()>>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, nested);
+}
+
 #[cfg(target_has_atomic = "ptr")]
 #[test]
 fn test_arc_str() {
@@ -196,3 +279,552 @@ fn test_arc_str() {
     let decoded: Arc<str> = bincode::borrow_decode_from_slice(slice, config).unwrap().0;
     assert_eq!(decoded, start);
 }
+
+#[cfg(target_has_atomic = "ptr")]
+#[test]
+fn test_single_allocation_slices() {
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+    use bincode::error::DecodeError;
+
+    fn check<T>(config: impl bincode::config::Config)
+    where
+        T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+        T: for<'de> bincode::BorrowDecode<'de, ()> + for<'a> From<&'a [u32]>,
+    {
+        for len in [0, 1, 100] {
+            let numbers: Vec<u32> = (0..len).map(|i| i * 1000).collect();
+            let value = T::from(&numbers[..]);
+            let encoded = bincode::encode_to_vec(&value, config).unwrap();
+            assert_eq!(encoded, bincode::encode_to_vec(&numbers, config).unwrap());
+            let (decoded, read): (T, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+            assert_eq!((decoded, read), (T::from(&numbers[..]), encoded.len()));
+            let (decoded, _): (T, usize) =
+                bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+            assert_eq!(decoded, value);
+            if len > 0 {
+                let err = bincode::decode_from_slice::<T, _>(&encoded[..encoded.len() - 1], config)
+                    .unwrap_err();
+                assert!(matches!(
+                    err.into_inner(),
+                    DecodeError::UnexpectedEnd { .. }
+                ));
+            }
+        }
+    }
+
+    let standard = bincode::config::standard();
+    check::<Box<[u32]>>(standard);
+    check::<Box<[u32]>>(standard.with_fixed_int_encoding());
+    check::<Box<[u32]>>(standard.with_fallible_allocation());
+    check::<Rc<[u32]>>(standard);
+    check::<Rc<[u32]>>(standard.with_big_endian().with_fixed_int_encoding());
+    check::<Arc<[u32]>>(standard);
+    check::<Arc<[u32]>>(standard.with_fixed_int_encoding());
+
+    let strings: Vec<String> = ["a", "bc", "def"].map(String::from).into();
+    let encoded = bincode::encode_to_vec(&strings, standard).unwrap();
+    let decoded: Rc<[String]> = bincode::decode_from_slice(&encoded, standard).unwrap().0;
+    assert_eq!(*decoded, *strings);
+    let decoded: Arc<[String]> = bincode::decode_from_slice(&encoded, standard).unwrap().0;
+    assert_eq!(*decoded, *strings);
+    let decoded: Box<[Cow<str>]> = bincode::borrow_decode_from_slice(&encoded, standard)
+        .unwrap()
+        .0;
+    assert!(matches!(decoded[2], Cow::Borrowed("def")));
+
+    // the strings that were decoded are dropped when a later one fails
+    let mut invalid = encoded.clone();
+    *invalid.last_mut().unwrap() = 0xFF;
+    let err = bincode::decode_from_slice::<Rc<[String]>, _>(&invalid, standard).unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::Utf8 { .. }));
+
+    // a length that does not fit in the input does not allocate room for it up front
+    let huge = bincode::encode_to_vec(u64::MAX / 2, standard).unwrap();
+    let err = bincode::decode_from_slice::<Arc<[String]>, _>(&huge, standard).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
+
+#[cfg(target_has_atomic = "ptr")]
+#[test]
+fn test_shared_str() {
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+    use bincode::error::DecodeError;
+
+    let standard = bincode::config::standard();
+    let lossy = standard.with_lossy_utf8_strings();
+    for text in ["", "a", "Hello, wörld!"] {
+        let encoded = bincode::encode_to_vec(text, standard).unwrap();
+        let rc: Rc<str> = bincode::decode_from_slice(&encoded, standard).unwrap().0;
+        let arc: Arc<str> = bincode::borrow_decode_from_slice(&encoded, lossy)
+            .unwrap()
+            .0;
+        assert_eq!((&*rc, &*arc), (text, text));
+    }
+
+    let encoded = bincode::encode_to_vec(b"ab\xFFc".as_slice(), standard).unwrap();
+    let err = bincode::decode_from_slice::<Rc<str>, _>(&encoded, standard).unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::Utf8 { .. }));
+    let rc: Rc<str> = bincode::decode_from_slice(&encoded, lossy).unwrap().0;
+    let arc: Arc<str> = bincode::decode_from_slice(&encoded, lossy).unwrap().0;
+    assert_eq!((&*rc, &*arc), ("ab\u{FFFD}c", "ab\u{FFFD}c"));
+}
+
+/// Records the length of every read, without a `peek_read` fast path
+struct RecordingReader<'a> {
+    inner: bincode::de::read::SliceReader<'a>,
+    reads: Vec<usize>,
+}
+
+impl bincode::de::read::Reader for RecordingReader<'_> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), bincode::error::DecodeError> {
+        self.reads.push(bytes.len());
+        self.inner.read(bytes)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+}
+
+/// Records the length of every write
+#[derive(Default)]
+struct RecordingWriter {
+    output: Vec<u8>,
+    writes: Vec<usize>,
+}
+
+impl bincode::enc::write::Writer for RecordingWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.writes.push(bytes.len());
+        self.output.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Checks that `value` is written and read with one call for the length and one call for its bytes
+fn check_bulk<T>(value: T, len: usize)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+{
+    let config = bincode::config::legacy();
+    let mut writer = RecordingWriter::default();
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    assert_eq!(writer.writes, [8, len]);
+    assert_eq!(
+        writer.output,
+        bincode::encode_to_vec(&value, config).unwrap()
+    );
+
+    let mut reader = RecordingReader {
+        inner: bincode::de::read::SliceReader::new(&writer.output),
+        reads: Vec::new(),
+    };
+    let decoded: T = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(reader.reads, [8, len]);
+}
+
+#[test]
+fn test_bulk_bytes() {
+    const LEN: usize = 1024 * 1024;
+    let bytes: Vec<u8> = (0..LEN).map(|i| i as u8).collect();
+
+    check_bulk(bytes.clone(), LEN);
+    check_bulk(bytes.clone().into_boxed_slice(), LEN);
+    check_bulk(VecDeque::from(bytes.clone()), LEN);
+    check_bulk("a".repeat(LEN), LEN);
+    check_bulk(String::from("ünïcödé").into_boxed_str(), 11);
+
+    // `BinaryHeap` has no `PartialEq`
+    let config = bincode::config::legacy();
+    let mut writer = RecordingWriter::default();
+    bincode::encode_into_writer(BinaryHeap::from(bytes), &mut writer, config).unwrap();
+    assert_eq!(writer.writes, [8, LEN]);
+
+    // arrays have no length prefix
+    let array: [u8; 300] = core::array::from_fn(|i| i as u8);
+    let mut writer = RecordingWriter::default();
+    bincode::encode_into_writer(array, &mut writer, config).unwrap();
+    assert_eq!(writer.writes, [300]);
+    let mut reader = RecordingReader {
+        inner: bincode::de::read::SliceReader::new(&writer.output),
+        reads: Vec::new(),
+    };
+    let decoded: [u8; 300] = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(decoded, array);
+    assert_eq!(reader.reads, [300]);
+}
+
+#[test]
+fn test_deque_and_heap_bulk() {
+    let config = bincode::config::legacy();
+    let bytes: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+
+    // a deque that wraps around is written in two slices, with the same output as a `Vec`
+    let mut deque = VecDeque::with_capacity(bytes.len());
+    for _ in 0..deque.capacity() - 400 {
+        deque.push_back(0);
+        deque.pop_front();
+    }
+    deque.extend(bytes.iter().copied());
+    let (front, back) = deque.as_slices();
+    let (front, back) = (front.len(), back.len());
+    assert!(front > 0 && back > 0);
+    let mut writer = RecordingWriter::default();
+    bincode::encode_into_writer(&deque, &mut writer, config).unwrap();
+    assert_eq!(writer.writes, [8, front, back]);
+    assert_eq!(writer.output, encoded);
+
+    // decoding a heap reads the elements at once, and builds the heap from them afterwards
+    let mut reader = RecordingReader {
+        inner: bincode::de::read::SliceReader::new(&encoded),
+        reads: Vec::new(),
+    };
+    let heap: BinaryHeap<u8> = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(reader.reads, [8, 1000]);
+    let mut sorted = bytes.clone();
+    sorted.sort_unstable();
+    assert_eq!(heap.into_sorted_vec(), sorted);
+
+    // both claim as much of the limit as a `Vec`
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+    let numbers: Vec<u32> = (0..100).map(|i| i * 1000).collect();
+    let encoded_numbers = bincode::encode_to_vec(&numbers, config).unwrap();
+    for limit in [0, 999, 1000, 1001, 2000] {
+        let config = config.with_runtime_limit(limit);
+        let vec = bincode::decode_from_slice::<Vec<u8>, _>(&encoded, config).is_ok();
+        assert_eq!(
+            bincode::decode_from_slice::<VecDeque<u8>, _>(&encoded, config).is_ok(),
+            vec
+        );
+        assert_eq!(
+            bincode::decode_from_slice::<BinaryHeap<u8>, _>(&encoded, config).is_ok(),
+            vec
+        );
+
+        let vec = bincode::decode_from_slice::<Vec<u32>, _>(&encoded_numbers, config).is_ok();
+        assert_eq!(
+            bincode::decode_from_slice::<VecDeque<u32>, _>(&encoded_numbers, config).is_ok(),
+            vec
+        );
+        assert_eq!(
+            bincode::decode_from_slice::<BinaryHeap<u32>, _>(&encoded_numbers, config).is_ok(),
+            vec
+        );
+    }
+}
+
+/// Records where every byte slice starts in the output, and how long it is
+#[derive(Default)]
+struct BlobWriter {
+    output: Vec<u8>,
+    blobs: Vec<(usize, usize)>,
+}
+
+impl bincode::enc::write::Writer for BlobWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.output.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn write_byte_slice(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.blobs.push((self.output.len(), bytes.len()));
+        self.output.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_byte_slice() {
+    #[derive(bincode::Encode)]
+    struct Blobs<'a> {
+        id: u32,
+        vec: Vec<u8>,
+        slice: &'a [u8],
+        string: String,
+        boxed: Box<str>,
+        cow: Cow<'a, [u8]>,
+        empty: Vec<u8>,
+        numbers: Vec<u16>,
+        array: [u8; 4],
+    }
+
+    let blobs = Blobs {
+        id: 7,
+        vec: vec![1; 300],
+        slice: &[2; 10],
+        string: String::from("ünïcödé"),
+        boxed: "boxed".into(),
+        cow: Cow::Borrowed(&[3; 70_000]),
+        empty: Vec::new(),
+        numbers: vec![4; 5],
+        array: [5; 4],
+    };
+    let payloads: [&[u8]; 6] = [
+        &blobs.vec,
+        blobs.slice,
+        blobs.string.as_bytes(),
+        blobs.boxed.as_bytes(),
+        &blobs.cow,
+        &blobs.empty,
+    ];
+
+    fn check<C: bincode::config::Config>(blobs: &Blobs, payloads: &[&[u8]], config: C) {
+        let mut writer = BlobWriter::default();
+        bincode::encode_into_writer(blobs, &mut writer, config).unwrap();
+
+        // the output is the same as with the default implementation
+        assert_eq!(
+            writer.output,
+            bincode::encode_to_vec(blobs, config).unwrap()
+        );
+        // every blob is exactly the payload of a byte slice
+        assert_eq!(writer.blobs.len(), payloads.len());
+        for ((start, len), payload) in writer.blobs.iter().zip(payloads) {
+            assert_eq!(&writer.output[*start..*start + *len], *payload);
+        }
+        // followed by the numbers and the array, which are not byte slices
+        let (start, len) = writer.blobs[payloads.len() - 1];
+        assert!(start + len < writer.output.len() - 4);
+    }
+    check(&blobs, &payloads, bincode::config::standard());
+    check(&blobs, &payloads, bincode::config::legacy());
+
+    // `BufferedWriter` passes large slices on to the wrapped writer, and buffers small ones
+    let config = bincode::config::standard();
+    let mut inner = BlobWriter::default();
+    let mut writer = bincode::enc::write::BufferedWriter::new(&mut inner);
+    bincode::encode_into_writer(&blobs, &mut writer, config).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        inner.output,
+        bincode::encode_to_vec(&blobs, config).unwrap()
+    );
+    let lens: Vec<usize> = inner.blobs.iter().map(|(_, len)| *len).collect();
+    assert_eq!(lens, [300, 70_000]);
+    for (start, len) in inner.blobs {
+        assert!(inner.output[start..start + len]
+            .iter()
+            .all(|b| *b == inner.output[start]));
+    }
+}
+
+#[test]
+fn test_bulk_string_invalid_utf8() {
+    let config = bincode::config::legacy();
+    let mut bytes = "a".repeat(1000).into_bytes();
+    bytes[500] = 0xFF;
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+    let err = bincode::decode_from_slice::<String, _>(&encoded, config).unwrap_err();
+    match err.inner() {
+        bincode::error::DecodeError::Utf8 {
+            inner,
+            valid_up_to,
+            byte,
+        } => {
+            assert_eq!(inner.valid_up_to(), 500);
+            assert_eq!(*valid_up_to, 500);
+            assert_eq!(*byte, 0xFF);
+        }
+        e => panic!("Expected Utf8, got {e:?}"),
+    }
+}
+
+#[test]
+fn test_invalid_utf8() {
+    use bincode::error::DecodeError;
+
+    let strict = bincode::config::standard();
+    let lossy = bincode::config::standard().with_lossy_utf8_strings();
+    let cases: [(&[u8], usize, u8, &str); 4] = [
+        (b"\xFFabc", 0, 0xFF, "\u{FFFD}abc"),
+        (b"ab\xC0\xAFcd", 2, 0xC0, "ab\u{FFFD}\u{FFFD}cd"),
+        (b"abc\xFE", 3, 0xFE, "abc\u{FFFD}"),
+        // the string ends in the middle of a character
+        (b"abc\xE2\x82", 3, 0xE2, "abc\u{FFFD}"),
+    ];
+
+    for (bytes, index, invalid, replaced) in cases {
+        let encoded = bincode::encode_to_vec(bytes, strict).unwrap();
+
+        let err = bincode::decode_from_slice::<String, _>(&encoded, strict).unwrap_err();
+        match err.inner() {
+            DecodeError::Utf8 {
+                valid_up_to, byte, ..
+            } => {
+                assert_eq!(*valid_up_to, index);
+                assert_eq!(*byte, invalid);
+            }
+            e => panic!("Expected Utf8, got {e:?}"),
+        }
+        assert_eq!(
+            err.into_inner().to_string(),
+            format!(
+                "invalid UTF-8 at index {index} of the string, starting with byte {invalid:#04x}"
+            )
+        );
+
+        // Owned strings replace the invalid UTF-8
+        let (decoded, len): (String, usize) = bincode::decode_from_slice(&encoded, lossy).unwrap();
+        assert_eq!(decoded, replaced);
+        assert_eq!(len, encoded.len());
+        let (decoded, _): (Box<str>, usize) = bincode::decode_from_slice(&encoded, lossy).unwrap();
+        assert_eq!(&*decoded, replaced);
+        let (decoded, _): (Cow<'_, str>, usize) =
+            bincode::decode_from_slice(&encoded, lossy).unwrap();
+        assert_eq!(decoded, replaced);
+
+        // Borrowed strings can not be modified, so they still fail
+        let err = bincode::borrow_decode_from_slice::<&str, _>(&encoded, lossy).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            DecodeError::Utf8 { valid_up_to, .. } if valid_up_to == index
+        ));
+        let err =
+            bincode::borrow_decode_from_slice::<Cow<'_, str>, _>(&encoded, lossy).unwrap_err();
+        assert!(matches!(err.into_inner(), DecodeError::Utf8 { .. }));
+    }
+
+    // Valid strings are decoded the same in both modes
+    let encoded = bincode::encode_to_vec("Hello \u{1F600}", strict).unwrap();
+    let (decoded, _): (String, usize) = bincode::decode_from_slice(&encoded, lossy).unwrap();
+    assert_eq!(decoded, "Hello \u{1F600}");
+}
+
+std::thread_local! {
+    /// The ids of the `Tracked` values that are alive on this thread
+    static LIVE: core::cell::RefCell<Vec<u32>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// A value without a `Default` that keeps track of being alive, and fails to decode for some ids
+#[derive(Debug, PartialEq, bincode::Encode)]
+struct Tracked(u32);
+
+impl Tracked {
+    const FAIL: u32 = u32::MAX;
+    const PANIC: u32 = u32::MAX - 1;
+
+    fn live() -> Vec<u32> {
+        LIVE.with(|live| live.borrow().clone())
+    }
+}
+
+impl<Context> bincode::Decode<Context> for Tracked {
+    fn decode<D: bincode::de::Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        let id = u32::decode(decoder)?;
+        match id {
+            Self::FAIL => Err(bincode::error::DecodeError::Other("failing element")),
+            Self::PANIC => panic!("panicking element"),
+            id => {
+                LIVE.with(|live| live.borrow_mut().push(id));
+                Ok(Self(id))
+            }
+        }
+    }
+}
+bincode::impl_borrow_decode!(Tracked);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        LIVE.with(|live| {
+            let mut live = live.borrow_mut();
+            let index = live.iter().position(|id| *id == self.0);
+            // a value that is not alive is dropped twice
+            live.remove(index.expect("dropped twice"));
+        });
+    }
+}
+
+#[test]
+fn test_array_element_fails() {
+    let config = bincode::config::standard();
+    for k in 0..8 {
+        let mut ids: [u32; 8] = core::array::from_fn(|i| i as u32);
+        ids[k] = Tracked::FAIL;
+        let encoded = bincode::encode_to_vec(ids, config).unwrap();
+
+        let err = bincode::decode_from_slice::<[Tracked; 8], _>(&encoded, config).unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            bincode::error::DecodeError::Other("failing element")
+        ));
+        // the elements before `k` were created and all of them were dropped once
+        assert_eq!(Tracked::live(), []);
+
+        let err =
+            bincode::borrow_decode_from_slice::<[Tracked; 8], _>(&encoded, config).unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            bincode::error::DecodeError::Other("failing element")
+        ));
+        assert_eq!(Tracked::live(), []);
+
+        // running out of input drops the elements as well
+        let ids: [u32; 8] = core::array::from_fn(|i| i as u32);
+        let encoded = bincode::encode_to_vec(ids, config).unwrap();
+        let err = bincode::decode_from_slice::<[Tracked; 8], _>(&encoded[..k], config).unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            bincode::error::DecodeError::UnexpectedEnd { .. }
+        ));
+        assert_eq!(Tracked::live(), []);
+    }
+
+    // without failures, every element is alive exactly once
+    let ids: [u32; 8] = core::array::from_fn(|i| i as u32 * 3);
+    let encoded = bincode::encode_to_vec(ids, config).unwrap();
+    let (decoded, _): ([Tracked; 8], _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded.each_ref().map(|t| t.0), ids);
+    assert_eq!(Tracked::live(), [0, 3, 6, 9, 12, 15, 18, 21]);
+    drop(decoded);
+    assert_eq!(Tracked::live(), []);
+}
+
+#[test]
+fn test_array_element_panics() {
+    let config = bincode::config::standard();
+    for k in 0..8 {
+        let mut ids: [u32; 8] = core::array::from_fn(|i| i as u32);
+        ids[k] = Tracked::PANIC;
+        let encoded = bincode::encode_to_vec(ids, config).unwrap();
+
+        let result = std::panic::catch_unwind(|| {
+            bincode::decode_from_slice::<[Tracked; 8], _>(&encoded, config)
+        });
+        assert!(result.is_err());
+        assert_eq!(Tracked::live(), []);
+    }
+}
+
+#[test]
+fn test_large_string_array() {
+    let config = bincode::config::standard();
+    let array: [String; 1000] = core::array::from_fn(|i| "x".repeat(i % 50));
+    let encoded = bincode::encode_to_vec(&array, config).unwrap();
+
+    let (decoded, len): ([String; 1000], _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(decoded, array);
+
+    let (borrowed, _): ([&str; 1000], _) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(borrowed, array.each_ref().map(String::as_str));
+
+    // fails in the middle, after the first strings were allocated
+    let err =
+        bincode::decode_from_slice::<[String; 1000], _>(&encoded[..encoded.len() / 2], config)
+            .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        bincode::error::DecodeError::UnexpectedEnd { .. }
+    ));
+}
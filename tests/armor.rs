@@ -0,0 +1,258 @@
+#![cfg(all(feature = "armor", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::armor::{
+    base64_len, decode_from_base64_str, decode_from_hex_str, encode_to_base64_string,
+    encode_to_hex_string, Base64, Base64Reader, Base64Writer, FmtWriter, HexReader, HexWriter,
+};
+use bincode::de::read::{Reader, SliceReader};
+use bincode::enc::write::Writer;
+use bincode::error::DecodeError;
+use bincode::IoWriter;
+
+const ALL: [Base64; 4] = [
+    Base64::STANDARD,
+    Base64::STANDARD_NO_PAD,
+    Base64::URL_SAFE,
+    Base64::URL_SAFE_NO_PAD,
+];
+
+/// Base64-encodes `payload` with a reference implementation that works on the whole payload
+fn reference_base64(payload: &[u8], base64: Base64) -> String {
+    let alphabet: Vec<u8> = if base64 == Base64::URL_SAFE || base64 == Base64::URL_SAFE_NO_PAD {
+        (b'A'..=b'Z')
+            .chain(b'a'..=b'z')
+            .chain(b'0'..=b'9')
+            .chain(*b"-_")
+            .collect()
+    } else {
+        (b'A'..=b'Z')
+            .chain(b'a'..=b'z')
+            .chain(b'0'..=b'9')
+            .chain(*b"+/")
+            .collect()
+    };
+    let mut output = String::new();
+    for quantum in payload.chunks(3) {
+        let mut bytes = [0; 3];
+        bytes[..quantum.len()].copy_from_slice(quantum);
+        let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for i in 0..=quantum.len() {
+            output.push(char::from(alphabet[(bits >> (18 - 6 * i)) as usize & 0x3F]));
+        }
+        if base64 == Base64::STANDARD || base64 == Base64::URL_SAFE {
+            for _ in quantum.len()..3 {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn reference_hex(payload: &[u8]) -> String {
+    payload.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Writes `payload` with a `Base64Writer`, in chunks of `chunk` bytes
+fn base64_encode(payload: &[u8], chunk: usize, base64: Base64) -> String {
+    let mut writer = Base64Writer::new(FmtWriter::new(String::new()), base64);
+    for bytes in payload.chunks(chunk) {
+        writer.write(bytes).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// Reads the payload of the text with a `Base64Reader`, in chunks of `chunk` bytes
+fn base64_decode(text: &str, len: usize, chunk: usize, base64: Base64) -> Vec<u8> {
+    let mut reader = Base64Reader::new(SliceReader::new(text.as_bytes()), base64);
+    let mut payload = vec![0; len];
+    for bytes in payload.chunks_mut(chunk) {
+        reader.read(bytes).unwrap();
+    }
+    let reader = reader.finish().unwrap();
+    assert!(reader.remaining().is_empty());
+    payload
+}
+
+#[test]
+fn test_known_text() {
+    // the test vectors of RFC 4648
+    for (payload, text) in [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ] {
+        assert_eq!(base64_encode(payload.as_bytes(), 1, Base64::STANDARD), text);
+        assert_eq!(
+            base64_encode(payload.as_bytes(), 2, Base64::STANDARD_NO_PAD),
+            text.trim_end_matches('=')
+        );
+        assert_eq!(
+            base64_decode(text, payload.len(), 1, Base64::STANDARD),
+            payload.as_bytes()
+        );
+    }
+    assert_eq!(
+        base64_encode(&[0xFB, 0xFF, 0xBF], 3, Base64::STANDARD),
+        "+/+/"
+    );
+    assert_eq!(
+        base64_encode(&[0xFB, 0xFF, 0xBF], 3, Base64::URL_SAFE),
+        "-_-_"
+    );
+    assert_eq!(base64_len(4, Base64::STANDARD), Some(8));
+    assert_eq!(base64_len(4, Base64::URL_SAFE_NO_PAD), Some(6));
+    assert_eq!(
+        base64_len(usize::MAX / 4 * 3, Base64::STANDARD),
+        Some(usize::MAX / 4 * 4)
+    );
+    assert_eq!(base64_len(usize::MAX, Base64::STANDARD), None);
+    assert_eq!(base64_len(usize::MAX, Base64::URL_SAFE_NO_PAD), None);
+}
+
+#[test]
+fn test_chunk_boundaries() {
+    for len in 0..100 {
+        let payload: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+        for base64 in ALL {
+            let expected = reference_base64(&payload, base64);
+            assert_eq!(Some(expected.len()), base64_len(len, base64));
+            for chunk in [1, 2, 3, 4, 5, 47, 48, 49, 100] {
+                let text = base64_encode(&payload, chunk, base64);
+                assert_eq!(text, expected, "{len} bytes in chunks of {chunk}");
+                assert_eq!(base64_decode(&text, len, chunk, base64), payload);
+            }
+        }
+
+        let expected = reference_hex(&payload);
+        for chunk in [1, 2, 48, 49, 100] {
+            let mut writer = HexWriter::new(FmtWriter::new(String::new()));
+            for bytes in payload.chunks(chunk) {
+                writer.write(bytes).unwrap();
+            }
+            assert_eq!(writer.into_inner().into_inner(), expected);
+
+            let mut reader = HexReader::new(SliceReader::new(expected.as_bytes()));
+            let mut decoded = vec![0; len];
+            for bytes in decoded.chunks_mut(chunk) {
+                reader.read(bytes).unwrap();
+            }
+            assert_eq!(decoded, payload);
+            assert_eq!(reader.chars_read(), len * 2);
+        }
+    }
+}
+
+#[test]
+fn test_values() {
+    let config = bincode::config::standard();
+    let value = (
+        vec![0u64, 1, u64::MAX],
+        String::from("armored text"),
+        [7u8; 5],
+        Some(-3i32),
+    );
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+
+    for base64 in ALL {
+        let text = encode_to_base64_string(&value, config, base64).unwrap();
+        assert_eq!(text, reference_base64(&encoded, base64));
+        let (decoded, len) = decode_from_base64_str(&text, config, base64).unwrap();
+        assert_eq!((decoded, len), (value.clone(), text.len()));
+    }
+
+    let text = encode_to_hex_string(&value, config).unwrap();
+    assert_eq!(text, reference_hex(&encoded));
+    let (decoded, len) = decode_from_hex_str(&text, config).unwrap();
+    assert_eq!((decoded, len), (value.clone(), text.len()));
+    let (decoded, len) = decode_from_hex_str(&text.to_uppercase(), config).unwrap();
+    assert_eq!((decoded, len), (value.clone(), text.len()));
+
+    // armored text written to a byte writer
+    let mut output = Vec::new();
+    let mut writer = Base64Writer::new(IoWriter::new(&mut output), Base64::URL_SAFE);
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        output,
+        reference_base64(&encoded, Base64::URL_SAFE).as_bytes()
+    );
+}
+
+#[test]
+fn test_text_after_value() {
+    let config = bincode::config::standard();
+    let text = format!(
+        "{},{}",
+        encode_to_base64_string(1000u32, config, Base64::STANDARD).unwrap(),
+        encode_to_base64_string("next", config, Base64::STANDARD).unwrap()
+    );
+    assert_eq!(text, "++gD,BG5leHQ=");
+    let (decoded, len): (u32, usize) =
+        decode_from_base64_str(&text, config, Base64::STANDARD).unwrap();
+    assert_eq!((decoded, len), (1000, 4));
+    let (decoded, len): (String, usize) =
+        decode_from_base64_str(&text[len + 1..], config, Base64::STANDARD).unwrap();
+    assert_eq!((decoded.as_str(), len), ("next", 8));
+
+    let (decoded, len): (u8, usize) = decode_from_hex_str("2aff", config).unwrap();
+    assert_eq!((decoded, len), (42, 2));
+}
+
+fn invalid_character(err: &DecodeError) -> (u8, usize) {
+    match err.inner() {
+        DecodeError::InvalidArmorCharacter { found, offset } => (*found, *offset),
+        inner => panic!("expected InvalidArmorCharacter, found {inner:?}"),
+    }
+}
+
+#[test]
+fn test_invalid_characters() {
+    let config = bincode::config::standard();
+
+    // a character of the other alphabet
+    let err =
+        decode_from_base64_str::<String, _>("BGFi_2Q=", config, Base64::STANDARD).unwrap_err();
+    assert_eq!(invalid_character(&err), (b'_', 4));
+    assert_eq!(
+        err.inner().to_string(),
+        "invalid character '_' at offset 4 of the armored text"
+    );
+    let err =
+        decode_from_base64_str::<String, _>("BGFi+2Q=", config, Base64::URL_SAFE).unwrap_err();
+    assert_eq!(invalid_character(&err), (b'+', 4));
+
+    // missing or wrong padding
+    let err = decode_from_base64_str::<u8, _>("Kg", config, Base64::STANDARD).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+    let err = decode_from_base64_str::<u8, _>("Kg=A", config, Base64::STANDARD).unwrap_err();
+    assert_eq!(invalid_character(&err), (b'A', 3));
+    assert_eq!(
+        decode_from_base64_str::<u8, _>("Kg", config, Base64::STANDARD_NO_PAD).unwrap(),
+        (42, 2)
+    );
+
+    // padding where the value needs more characters, or padding without padding
+    let err = decode_from_base64_str::<u16, _>("+w==", config, Base64::STANDARD).unwrap_err();
+    assert_eq!(invalid_character(&err), (b'=', 2));
+    assert_eq!(
+        decode_from_base64_str::<u32, _>("++gD=", config, Base64::STANDARD_NO_PAD).unwrap(),
+        (1000, 4)
+    );
+    let err = decode_from_base64_str::<(u32, u8), _>("++gD=A", config, Base64::STANDARD_NO_PAD)
+        .unwrap_err();
+    assert_eq!(invalid_character(&err), (b'=', 4));
+
+    let err = decode_from_hex_str::<u16, _>("fb0g03", config).unwrap_err();
+    assert_eq!(invalid_character(&err), (b'g', 3));
+    let err = decode_from_hex_str::<u8, _>(" 2a", config).unwrap_err();
+    assert_eq!(invalid_character(&err), (b' ', 0));
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "arrayvec")]
+
+extern crate bincode_next as bincode;
+
+use arrayvec::{ArrayString, ArrayVec};
+use bincode::error::DecodeError;
+
+#[test]
+fn test_arrayvec() {
+    let config = bincode::config::standard();
+    let mut vec = ArrayVec::<u8, 32>::new();
+    vec.try_extend_from_slice(&[1, 2, 3, 4, 5]).unwrap();
+    let encoded = bincode::encode_to_vec(&vec, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(vec.to_vec(), config).unwrap()
+    );
+
+    let (decoded, len): (ArrayVec<u8, 32>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, encoded.len());
+
+    // exactly at capacity
+    let full: ArrayVec<u64, 4> = [1, 2, 3, u64::MAX].into();
+    let encoded = bincode::encode_to_vec(&full, config).unwrap();
+    let (decoded, _): (ArrayVec<u64, 4>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, full);
+}
+
+#[test]
+fn test_arrayvec_borrowed() {
+    let config = bincode::config::standard();
+    let vec: ArrayVec<&str, 3> = ["a", "bc", "def"].into();
+    let encoded = bincode::encode_to_vec(&vec, config).unwrap();
+    let (decoded, _): (ArrayVec<&str, 3>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+}
+
+#[test]
+fn test_arrayvec_capacity_exceeded() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(vec![0u8; 33], config).unwrap();
+    let err = bincode::decode_from_slice::<ArrayVec<u8, 32>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { len: 33, cap: 32 }
+    ));
+
+    // the length is checked before any element is read
+    let encoded = bincode::encode_to_vec(u64::from(u32::MAX), config).unwrap();
+    let err = bincode::decode_from_slice::<ArrayVec<u8, 32>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded {
+            len: 4_294_967_295,
+            cap: 32
+        }
+    ));
+}
+
+#[test]
+fn test_arraystring() {
+    let config = bincode::config::standard();
+    let string = ArrayString::<16>::from("hello ü").unwrap();
+    let encoded = bincode::encode_to_vec(string, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(String::from("hello ü"), config).unwrap()
+    );
+
+    let (decoded, len): (ArrayString<16>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, string);
+    assert_eq!(len, encoded.len());
+
+    let err = bincode::decode_from_slice::<ArrayString<4>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { len: 8, cap: 4 }
+    ));
+
+    let encoded = bincode::encode_to_vec(&[0xFFu8, 0xFE][..], config).unwrap();
+    let err = bincode::decode_from_slice::<ArrayString<4>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::Utf8 { .. }));
+}
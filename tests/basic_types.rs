@@ -4,6 +4,7 @@ use bincode::error::DecodeError;
 use core::cell::{Cell, RefCell};
 use core::cmp::Reverse;
 use core::ops::Bound;
+use core::task::Poll;
 use core::time::Duration;
 use std::num::*;
 use utils::{the_same, the_same_with_comparer};
@@ -177,6 +178,40 @@ fn test_slice() {
     assert_eq!(len, 8);
 }
 
+#[test]
+fn test_slice_writer_interleaved() {
+    use bincode::enc::{
+        write::{SliceWriter, Writer},
+        Encode, Encoder, EncoderImpl,
+    };
+
+    let mut buffer = [0xFFu8; 16];
+    let mut encoder = EncoderImpl::new(SliceWriter::new(&mut buffer), bincode::config::legacy());
+    encoder.writer().write(&[0xAB, 0xCD]).unwrap();
+    assert_eq!(encoder.writer().position(), 2);
+    7u16.encode(&mut encoder).unwrap();
+    encoder.writer().write(b"|").unwrap();
+    (true, 300u32).encode(&mut encoder).unwrap();
+    assert_eq!(encoder.writer().position(), 10);
+    assert_eq!(encoder.writer().remaining(), 6);
+
+    // the trailing `u64` does not fit, so nothing of it is written
+    let err = u64::MAX.encode(&mut encoder).unwrap_err();
+    assert!(matches!(err, bincode::error::EncodeError::UnexpectedEnd));
+    assert_eq!(encoder.writer().remaining(), 6);
+    encoder.writer().write(&[1, 2, 3, 4, 5, 6]).unwrap();
+    assert!(matches!(
+        encoder.writer().write(&[7]),
+        Err(bincode::error::EncodeError::UnexpectedEnd)
+    ));
+
+    let written = encoder.into_writer().into_written();
+    assert_eq!(
+        written,
+        &[0xAB, 0xCD, 7, 0, b'|', 1, 44, 1, 0, 0, 1, 2, 3, 4, 5, 6]
+    );
+}
+
 #[test]
 fn test_option_slice() {
     let mut buffer = [0u8; 32];
@@ -310,3 +345,213 @@ fn test_duration_wrapping() {
 
     assert_eq!(result.subsec_nanos(), 294967295);
 }
+
+#[test]
+fn test_char_utf8_bytes() {
+    let cases: [(char, &[u8]); 8] = [
+        ('\0', &[0x00]),
+        ('a', &[0x61]),
+        ('\u{7F}', &[0x7F]),
+        ('é', &[0xC3, 0xA9]),
+        ('\u{7FF}', &[0xDF, 0xBF]),
+        ('文', &[0xE6, 0x96, 0x87]),
+        ('😀', &[0xF0, 0x9F, 0x98, 0x80]),
+        (char::MAX, &[0xF4, 0x8F, 0xBF, 0xBF]),
+    ];
+    for (c, bytes) in cases {
+        // the same bytes with every config
+        assert_eq!(
+            bincode::encode_to_vec(c, bincode::config::standard()).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            bincode::encode_to_vec(c, bincode::config::legacy()).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            bincode::encode_to_vec(c, bincode::config::standard().with_big_endian()).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            bincode::encode_to_vec(
+                c,
+                bincode::config::standard()
+                    .with_scalar_char_encoding()
+                    .with_utf8_char_encoding()
+            )
+            .unwrap(),
+            bytes
+        );
+        let (decoded, len): (char, _) =
+            bincode::decode_from_slice(bytes, bincode::config::standard()).unwrap();
+        assert_eq!((decoded, len), (c, bytes.len()));
+    }
+}
+
+#[test]
+fn test_char_invalid() {
+    let invalid: [(&[u8], [u8; 4]); 6] = [
+        // a continuation byte
+        (&[0x80], [0x80, 0, 0, 0]),
+        // bytes that never appear in UTF-8
+        (&[0xFF], [0xFF, 0, 0, 0]),
+        (&[0xF5, 0x80, 0x80, 0x80], [0xF5, 0, 0, 0]),
+        // an overlong encoding of 'a'
+        (&[0xC1, 0xA1], [0xC1, 0, 0, 0]),
+        // a lead byte without its continuation bytes
+        (&[0xE6, 0x61, 0x61], [0xE6, 0x61, 0x61, 0]),
+        // an overlong encoding of the surrogate 0xD800
+        (&[0xF0, 0x8D, 0xA0, 0x80], [0xF0, 0x8D, 0xA0, 0x80]),
+    ];
+    for (bytes, expected) in invalid {
+        let err =
+            bincode::decode_from_slice::<char, _>(bytes, bincode::config::standard()).unwrap_err();
+        match err.inner() {
+            DecodeError::InvalidCharEncoding(found) => assert_eq!(*found, expected),
+            err => panic!("{bytes:?}: {err:?}"),
+        }
+    }
+
+    // well-formed UTF-8 of values that are not a `char`
+    let invalid: [(&[u8], u32); 3] = [
+        // the surrogates 0xD800 and 0xDFFF
+        (&[0xED, 0xA0, 0x80], 0xD800),
+        (&[0xED, 0xBF, 0xBF], 0xDFFF),
+        // 0x110000, above `char::MAX`
+        (&[0xF4, 0x90, 0x80, 0x80], 0x11_0000),
+    ];
+    for (bytes, expected) in invalid {
+        let err =
+            bincode::decode_from_slice::<char, _>(bytes, bincode::config::standard()).unwrap_err();
+        match err.inner() {
+            DecodeError::InvalidCharValue { found } => assert_eq!(*found, expected),
+            err => panic!("{bytes:?}: {err:?}"),
+        }
+    }
+
+    // a sequence that is cut off
+    let err = bincode::decode_from_slice::<char, _>(&[0xF0, 0x9F], bincode::config::standard())
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedEnd { additional: 2 }
+    ));
+}
+
+#[test]
+fn test_char_scalar_bytes() {
+    let little = bincode::config::standard().with_scalar_char_encoding();
+    let big = little.with_big_endian();
+    let cases: [(char, u32); 6] = [
+        ('\0', 0),
+        ('a', 0x61),
+        ('é', 0xE9),
+        ('文', 0x6587),
+        ('😀', 0x1_F600),
+        (char::MAX, 0x10_FFFF),
+    ];
+    for (c, scalar) in cases {
+        let encoded = bincode::encode_to_vec(c, little).unwrap();
+        assert_eq!(encoded, scalar.to_le_bytes());
+        let (decoded, len): (char, _) = bincode::decode_from_slice(&encoded, little).unwrap();
+        assert_eq!((decoded, len), (c, 4));
+
+        // with the endianness, but not the int encoding of the config
+        let encoded = bincode::encode_to_vec(c, big).unwrap();
+        assert_eq!(encoded, scalar.to_be_bytes());
+        assert_eq!(
+            bincode::encode_to_vec(c, big.with_fixed_int_encoding()).unwrap(),
+            encoded
+        );
+        let (decoded, len): (char, _) = bincode::decode_from_slice(&encoded, big).unwrap();
+        assert_eq!((decoded, len), (c, 4));
+    }
+
+    let text: Vec<char> = "aé文😀".chars().collect();
+    let encoded = bincode::encode_to_vec(&text, little).unwrap();
+    assert_eq!(encoded.len(), 1 + 4 * text.len());
+    let (decoded, _): (Vec<char>, _) = bincode::decode_from_slice(&encoded, little).unwrap();
+    assert_eq!(decoded, text);
+}
+
+#[test]
+fn test_char_scalar_invalid() {
+    let config = bincode::config::standard().with_scalar_char_encoding();
+    for found in [0xD800, 0xDFFF, 0x11_0000, u32::MAX] {
+        let err = bincode::decode_from_slice::<char, _>(&found.to_le_bytes(), config).unwrap_err();
+        assert!(
+            matches!(err.inner(), DecodeError::InvalidCharValue { found: f } if *f == found),
+            "{err:?}"
+        );
+    }
+    assert_eq!(
+        DecodeError::InvalidCharValue { found: 0xD800 }.to_string(),
+        "invalid char 0xd800, which is not a Unicode scalar value"
+    );
+
+    let err = bincode::decode_from_slice::<char, _>(&[0x61, 0, 0], config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+}
+
+#[test]
+fn test_std_enum_tags() {
+    fn check<T>(value: T, standard: &[u8], legacy: &[u8])
+    where
+        T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    {
+        let encoded = bincode::encode_to_vec(&value, bincode::config::standard()).unwrap();
+        assert_eq!(encoded, standard, "{value:?}");
+        let encoded = bincode::encode_to_vec(&value, bincode::config::legacy()).unwrap();
+        assert_eq!(encoded, legacy, "{value:?}");
+
+        let (decoded, _): (T, usize) =
+            bincode::decode_from_slice(standard, bincode::config::standard()).unwrap();
+        assert_eq!(decoded, value);
+        let (decoded, _): (T, usize) =
+            bincode::decode_from_slice(legacy, bincode::config::legacy()).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    // these tags are part of the format, and must never change
+    check(Option::<u8>::None, &[0], &[0]);
+    check(Some(7u8), &[1, 7], &[1, 7]);
+    check(Result::<u8, u8>::Ok(7), &[0, 7], &[0, 0, 0, 0, 7]);
+    check(Result::<u8, u8>::Err(7), &[1, 7], &[1, 0, 0, 0, 7]);
+    check(Bound::<u8>::Unbounded, &[0], &[0, 0, 0, 0]);
+    check(Bound::Included(7u8), &[1, 7], &[1, 0, 0, 0, 7]);
+    check(Bound::Excluded(7u8), &[2, 7], &[2, 0, 0, 0, 7]);
+    check(Poll::Ready(7u8), &[0, 7], &[0, 0, 0, 0, 7]);
+    check(Poll::<u8>::Pending, &[1], &[1, 0, 0, 0]);
+
+    // any other tag is rejected, listing the allowed ones
+    fn invalid<T: bincode::Decode<()> + core::fmt::Debug>(tag: u8) -> String {
+        let err =
+            bincode::decode_from_slice::<T, _>(&[tag, 0], bincode::config::standard()).unwrap_err();
+        let DecodeError::UnexpectedVariant { found, allowed, .. } = err.into_inner() else {
+            panic!("expected UnexpectedVariant");
+        };
+        assert_eq!(found, u64::from(tag));
+        allowed.to_string()
+    }
+    assert_eq!(invalid::<Option<u8>>(2), "one of None(0), Some(1)");
+    assert_eq!(invalid::<Result<u8, u8>>(2), "one of Ok(0), Err(1)");
+    assert_eq!(
+        invalid::<Bound<u8>>(3),
+        "one of Unbounded(0), Included(1), Excluded(2)"
+    );
+    assert_eq!(invalid::<Poll<u8>>(2), "one of Ready(0), Pending(1)");
+
+    // the borrowed impls read the same tags
+    let (decoded, _): (Poll<&str>, usize) =
+        bincode::borrow_decode_from_slice(&[0, 1, b'a'], bincode::config::standard()).unwrap();
+    assert_eq!(decoded, Poll::Ready("a"));
+    let err = bincode::borrow_decode_from_slice::<Poll<&str>, _>(&[5], bincode::config::standard())
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedVariant { found: 5, .. }
+    ));
+}
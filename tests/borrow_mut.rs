@@ -0,0 +1,108 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{
+        read::{BorrowReader, SliceReader, SliceReaderMut, TakeReader},
+        BorrowDecode, DecoderImpl,
+    },
+    error::DecodeError,
+};
+
+#[derive(bincode::Encode, bincode::BorrowDecode, Debug)]
+struct Packet<'a> {
+    id: u16,
+    payload: &'a mut [u8],
+    name: &'a mut str,
+    checksum: u8,
+}
+
+#[test]
+fn test_mutate_in_place() {
+    let config = bincode::config::legacy();
+    let mut buffer = bincode::encode_to_vec(
+        Packet {
+            id: 7,
+            payload: &mut [1, 2, 3],
+            name: &mut String::from("abc"),
+            checksum: 0xAA,
+        },
+        config,
+    )
+    .unwrap();
+    let original = buffer.clone();
+
+    let (packet, len): (Packet, usize) =
+        bincode::borrow_decode_from_slice_mut(&mut buffer, config).unwrap();
+    assert_eq!(len, original.len());
+    assert_eq!(packet.id, 7);
+    assert_eq!(packet.payload, [1, 2, 3]);
+    assert_eq!(packet.name, "abc");
+    packet.payload.reverse();
+    packet.name.make_ascii_uppercase();
+
+    // id, then the length and bytes of the payload, then the length and bytes of the name
+    let payload = 2 + 8..2 + 8 + 3;
+    let name = payload.end + 8..payload.end + 8 + 3;
+    assert_eq!(buffer[payload.clone()], [3, 2, 1]);
+    assert_eq!(&buffer[name.clone()], b"ABC");
+    for (idx, (new, old)) in buffer.iter().zip(&original).enumerate() {
+        if !payload.contains(&idx) && !name.contains(&idx) {
+            assert_eq!(new, old);
+        }
+    }
+}
+
+#[test]
+fn test_mutable_borrow_unsupported() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec([1u8, 2, 3].as_slice(), config).unwrap();
+    let err = bincode::borrow_decode_from_slice::<&mut [u8], _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::MutableBorrowUnsupported
+    ));
+
+    let mut reader = SliceReader::new(&encoded);
+    assert!(matches!(
+        reader.take_bytes_mut(1),
+        Err(DecodeError::MutableBorrowUnsupported)
+    ));
+}
+
+#[test]
+fn test_slice_reader_mut() {
+    let mut buffer = [1u8, 2, 3, 4, 5];
+    let mut reader = SliceReaderMut::new(&mut buffer);
+    let first = reader.take_bytes_mut(2).unwrap();
+    let second = reader.take_bytes_mut(2).unwrap();
+    first[0] = 10;
+    second[0] = 30;
+    assert!(matches!(
+        reader.take_bytes_mut(2),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    assert_eq!(buffer, [10, 2, 30, 4, 5]);
+
+    let mut buffer = [1u8, 2, 3, 4, 5];
+    let mut reader = TakeReader::new(SliceReaderMut::new(&mut buffer), 3);
+    reader.take_bytes_mut(3).unwrap().fill(0);
+    assert!(matches!(
+        reader.take_bytes_mut(1),
+        Err(DecodeError::TakeLimitExceeded { .. })
+    ));
+    assert_eq!(buffer, [0, 0, 0, 4, 5]);
+
+    let config = bincode::config::standard();
+    let mut encoded = bincode::encode_to_vec([0xFFu8, 0xFE].as_slice(), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReaderMut::new(&mut encoded), config, ());
+    assert!(matches!(
+        <&mut str>::borrow_decode(&mut decoder),
+        Err(DecodeError::Utf8 {
+            valid_up_to: 0,
+            byte: 0xFF,
+            ..
+        })
+    ));
+}
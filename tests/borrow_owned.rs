@@ -0,0 +1,126 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use std::borrow::Cow;
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct Mixed<'a> {
+    #[bincode(borrow)]
+    name: Cow<'a, str>,
+    #[bincode(owned)]
+    comment: Cow<'a, str>,
+    data: &'a [u8],
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct OwnedOnly<'a> {
+    id: u32,
+    #[bincode(owned)]
+    name: Cow<'a, str>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum OwnedEnum<'a> {
+    Empty,
+    Named {
+        #[bincode(owned)]
+        name: Cow<'a, str>,
+    },
+    Pair(u8, #[bincode(owned)] Cow<'a, [u8]>),
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum MixedEnum<'a> {
+    Borrowed(#[bincode(borrow)] Cow<'a, str>),
+    Owned(#[bincode(owned)] Cow<'a, str>),
+}
+
+#[allow(clippy::ptr_arg)]
+const fn is_borrowed<T: ToOwned + ?Sized>(cow: &Cow<'_, T>) -> bool {
+    matches!(cow, Cow::Borrowed(_))
+}
+
+#[test]
+fn test_borrow_and_owned_fields() {
+    let config = bincode::config::standard();
+    let mixed = Mixed {
+        name: Cow::Borrowed("name"),
+        comment: Cow::Borrowed("comment"),
+        data: &[1, 2, 3],
+    };
+    let encoded = bincode::encode_to_vec(&mixed, config).unwrap();
+    let (decoded, len): (Mixed, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, mixed);
+    assert_eq!(len, encoded.len());
+    assert!(is_borrowed(&decoded.name));
+    assert!(!is_borrowed(&decoded.comment));
+
+    let owned = OwnedOnly {
+        id: 5,
+        name: Cow::Borrowed("owned"),
+    };
+    let encoded = bincode::encode_to_vec(&owned, config).unwrap();
+    let (decoded, _): (OwnedOnly, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, owned);
+    assert!(!is_borrowed(&decoded.name));
+    let (decoded, _): (OwnedOnly, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, owned);
+    assert!(!is_borrowed(&decoded.name));
+}
+
+#[test]
+fn test_owned_fields_outlive_input() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(
+        OwnedOnly {
+            id: 1,
+            name: Cow::Borrowed("outlives"),
+        },
+        config,
+    )
+    .unwrap();
+
+    // owned fields are not tied to the input, so they can be `'static` when borrow decoded
+    let decoded: OwnedOnly<'static> = bincode::borrow_decode_from_slice(&encoded, config)
+        .unwrap()
+        .0;
+    drop(encoded);
+    assert_eq!(decoded.name, "outlives");
+}
+
+#[test]
+fn test_borrow_and_owned_enums() {
+    let config = bincode::config::standard();
+    for value in [
+        OwnedEnum::Empty,
+        OwnedEnum::Named {
+            name: Cow::Borrowed("named"),
+        },
+        OwnedEnum::Pair(3, Cow::Borrowed(&[4, 5])),
+    ] {
+        let encoded = bincode::encode_to_vec(&value, config).unwrap();
+        let (decoded, _): (OwnedEnum, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+        let (decoded, _): (OwnedEnum, usize) =
+            bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+        match &decoded {
+            OwnedEnum::Empty => {}
+            OwnedEnum::Named { name } => assert!(!is_borrowed(name)),
+            OwnedEnum::Pair(_, bytes) => assert!(!is_borrowed(bytes)),
+        }
+    }
+
+    let encoded = bincode::encode_to_vec(MixedEnum::Borrowed("a".into()), config).unwrap();
+    let (decoded, _): (MixedEnum, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert!(matches!(decoded, MixedEnum::Borrowed(Cow::Borrowed("a"))));
+    let encoded = bincode::encode_to_vec(MixedEnum::Owned("b".into()), config).unwrap();
+    let (decoded, _): (MixedEnum, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert!(matches!(decoded, MixedEnum::Owned(Cow::Owned(ref s)) if s == "b"));
+}
@@ -0,0 +1,212 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::bounded::{BoundedString, BoundedVec, CapacityError};
+use bincode::config;
+use bincode::error::DecodeError;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+struct Login {
+    user: BoundedString<16>,
+    roles: BoundedVec<BoundedString<8>, 4>,
+    pin: BoundedVec<u8, 6>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct OwnedLogin {
+    user: String,
+    roles: Vec<String>,
+    pin: Vec<u8>,
+}
+
+fn login() -> Login {
+    let mut roles = BoundedVec::new();
+    roles
+        .try_push(BoundedString::try_from("admin").unwrap())
+        .unwrap();
+    roles
+        .try_push(BoundedString::try_from("dev").unwrap())
+        .unwrap();
+    Login {
+        user: BoundedString::try_from("ann").unwrap(),
+        roles,
+        pin: BoundedVec::try_from(&[1, 2, 3, 4][..]).unwrap(),
+    }
+}
+
+fn owned_login() -> OwnedLogin {
+    OwnedLogin {
+        user: String::from("ann"),
+        roles: vec![String::from("admin"), String::from("dev")],
+        pin: vec![1, 2, 3, 4],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    fn check(config: impl config::Config) {
+        let encoded = bincode::encode_to_vec(login(), config).unwrap();
+        let (decoded, len): (Login, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, login());
+        assert_eq!(len, encoded.len());
+
+        let mut decoder = bincode::de::DecoderImpl::new(
+            bincode::de::read::SliceReader::new(&encoded),
+            config,
+            (),
+        );
+        bincode::skip_value::<Login, _>(&mut decoder).unwrap();
+    }
+    check(config::standard());
+    check(config::legacy());
+    check(config::standard().with_big_endian());
+
+    let empty: BoundedVec<u32, 0> = BoundedVec::new();
+    let encoded = bincode::encode_to_vec(&empty, config::standard()).unwrap();
+    assert_eq!(encoded, [0]);
+    let (decoded, _): (BoundedVec<u32, 0>, _) =
+        bincode::decode_from_slice(&encoded, config::standard()).unwrap();
+    assert!(decoded.is_empty());
+}
+
+#[test]
+fn test_same_bytes_as_string_and_vec() {
+    fn check(config: impl config::Config) {
+        assert_eq!(
+            bincode::encode_to_vec(login(), config).unwrap(),
+            bincode::encode_to_vec(owned_login(), config).unwrap()
+        );
+    }
+    check(config::standard());
+    check(config::standard().with_big_endian());
+
+    let config = config::legacy();
+    let encoded = bincode::encode_to_vec(owned_login(), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(login(), config).unwrap());
+    let (decoded, _): (Login, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, login());
+    let encoded = bincode::encode_to_vec(login(), config).unwrap();
+    let (decoded, _): (OwnedLogin, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, owned_login());
+}
+
+#[test]
+fn test_over_capacity() {
+    let config = config::standard();
+
+    let encoded = bincode::encode_to_vec("a name that is far too long", config).unwrap();
+    let err = bincode::decode_from_slice::<BoundedString<16>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::ArrayCapacityExceeded { len: 27, cap: 16 }
+    ));
+
+    // the elements are not read, so the input does not need to have them
+    let encoded = bincode::encode_to_vec(vec![7u64; 1000], config).unwrap();
+    let err =
+        bincode::decode_from_slice::<BoundedVec<u64, 8>, _>(&encoded[..3], config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::ArrayCapacityExceeded { len: 1000, cap: 8 }
+    ));
+
+    // in a nested field, the error has the path of the field
+    let mut owned = owned_login();
+    owned.roles.push(String::from("a role that is too long"));
+    let encoded = bincode::encode_to_vec(&owned, config).unwrap();
+    let err = bincode::decode_from_slice::<Login, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::ArrayCapacityExceeded { len: 23, cap: 8 }
+    ));
+
+    assert_eq!(
+        BoundedString::<2>::try_from("abc"),
+        Err(CapacityError { len: 3, cap: 2 })
+    );
+    assert_eq!(
+        BoundedVec::<u8, 2>::try_from(&[1, 2, 3][..]),
+        Err(CapacityError { len: 3, cap: 2 })
+    );
+    let mut string = BoundedString::<4>::try_from("ab").unwrap();
+    assert_eq!(string.try_push('é'), Ok(()));
+    assert_eq!(string.try_push('é'), Err(CapacityError { len: 6, cap: 4 }));
+    assert_eq!(string, "abé");
+    let mut vec = BoundedVec::<u8, 1>::new();
+    assert_eq!(vec.try_push(1), Ok(()));
+    assert!(vec.is_full());
+    assert_eq!(vec.try_push(2), Err(2));
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let encoded = bincode::encode_to_vec(&[b'a', 0xFF][..], config::standard()).unwrap();
+    let err = bincode::decode_from_slice::<BoundedString<4>, _>(&encoded, config::standard())
+        .unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Utf8 { .. }));
+}
+
+#[test]
+fn test_map_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(BoundedString::<8>::try_from("b").unwrap(), 2u8);
+    map.insert(BoundedString::<8>::try_from("a").unwrap(), 1u8);
+    assert_eq!(map.get("a"), Some(&1));
+
+    let config = config::standard().with_strict_map_order();
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    let std_map: BTreeMap<String, u8> = [(String::from("a"), 1), (String::from("b"), 2)].into();
+    assert_eq!(encoded, bincode::encode_to_vec(&std_map, config).unwrap());
+    let (decoded, _): (BTreeMap<BoundedString<8>, u8>, _) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, map);
+
+    let (decoded, _): (HashMap<BoundedVec<u8, 2>, BoundedString<8>>, _) =
+        bincode::decode_from_slice(
+            &bincode::encode_to_vec(HashMap::from([(vec![1u8, 2], "x")]), config::standard())
+                .unwrap(),
+            config::standard(),
+        )
+        .unwrap();
+    assert_eq!(decoded[&[1u8, 2][..]], "x");
+}
+
+#[test]
+fn test_borrow_decode() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(["a", "bc"], config).unwrap();
+    let encoded = [&[2][..], &encoded].concat();
+    let (decoded, _): (BoundedVec<&str, 4>, _) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded.as_slice(), ["a", "bc"]);
+}
+
+#[test]
+fn test_drops_elements() {
+    let value = Rc::new(());
+    let mut vec = BoundedVec::<Rc<()>, 4>::new();
+    for _ in 0..3 {
+        vec.try_push(Rc::clone(&value)).unwrap();
+    }
+    let clone = vec.clone();
+    assert_eq!(Rc::strong_count(&value), 7);
+    assert!(vec.pop().is_some());
+    assert_eq!(Rc::strong_count(&value), 6);
+    drop(vec);
+    assert_eq!(Rc::strong_count(&value), 4);
+    drop(clone);
+    assert_eq!(Rc::strong_count(&value), 1);
+
+    // the elements that were decoded before an error are dropped
+    let encoded = bincode::encode_to_vec(
+        (3u8, String::from("a"), String::from("b")),
+        config::standard(),
+    )
+    .unwrap();
+    let err = bincode::decode_from_slice::<BoundedVec<String, 4>, _>(&encoded, config::standard())
+        .unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::UnexpectedEnd { .. }));
+}
@@ -0,0 +1,237 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::Decoder,
+    error::DecodeError,
+    Decode,
+};
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+/// Runs `f` on a thread with a stack that is far too small for the decoded values
+fn with_small_stack<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R {
+    std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+fn check_large_array<T, C>(config: C)
+where
+    T: Decode<()> + bincode::Encode + Copy + PartialEq + Default + Send + 'static,
+    C: Config + Send + 'static,
+{
+    let values: Box<[T; 1 << 20]> = vec![T::default(); 1 << 20]
+        .into_boxed_slice()
+        .try_into()
+        .unwrap_or_else(|_| unreachable!());
+    let encoded = bincode::encode_to_vec(&values, config).unwrap();
+
+    with_small_stack(move || {
+        let (boxed, len): (Box<[T; 1 << 20]>, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(len, encoded.len());
+        assert!(boxed.iter().all(|value| *value == T::default()));
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // too large for miri
+fn test_large_boxed_arrays() {
+    with_small_stack(|| {
+        let encoded = vec![7u8; 8_000_000];
+        let (boxed, len): (Box<[u8; 8_000_000]>, usize) =
+            bincode::decode_from_slice(&encoded, config::standard()).unwrap();
+        assert_eq!(len, 8_000_000);
+        assert!(boxed.iter().all(|byte| *byte == 7));
+
+        // nested arrays are decoded in place too
+        let encoded: Vec<u8> = (0..1024 * 1024 * 8).map(|i| (i % 8) as u8).collect();
+        let (boxed, _): (Box<[[u64; 1024]; 1024]>, usize) =
+            bincode::decode_from_slice(&encoded, config::legacy()).unwrap();
+        assert!(boxed
+            .iter()
+            .flatten()
+            .all(|value| *value == 0x0706_0504_0302_0100));
+        let (boxed, _): (Box<[[u64; 1024]; 1024]>, usize) =
+            bincode::decode_from_slice(&encoded, config::legacy().with_big_endian()).unwrap();
+        assert!(boxed
+            .iter()
+            .flatten()
+            .all(|value| *value == 0x0001_0203_0405_0607));
+
+        let (rc, _): (Rc<[u16; 1 << 20]>, usize) =
+            bincode::decode_from_slice(&encoded, config::legacy()).unwrap();
+        assert!(rc.iter().step_by(4).all(|value| *value == 0x0100));
+        let (arc, _): (Arc<[u16; 1 << 20]>, usize) =
+            bincode::decode_from_slice(&encoded, config::legacy().with_big_endian()).unwrap();
+        assert!(arc.iter().step_by(4).all(|value| *value == 0x0001));
+    });
+
+    // elements that are decoded one by one
+    check_large_array::<bool, _>(config::standard());
+    check_large_array::<u32, _>(config::standard());
+    check_large_array::<char, _>(config::legacy());
+}
+
+#[test]
+fn test_boxed_array_limit() {
+    let encoded = vec![0u8; 4 * 1024];
+    let config = config::legacy().with_limit::<1024>();
+    let err = bincode::decode_from_slice::<Box<[u32; 1024]>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+
+    let config = config::legacy().with_limit::<4096>();
+    let (boxed, _): (Box<[u32; 1024]>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(*boxed, [0; 1024]);
+}
+
+thread_local! {
+    static DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A byte that fails to decode if it is 255, and counts how often it is dropped
+#[derive(Debug)]
+struct Tracked(u8);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.set(dropped.get() + 1));
+    }
+}
+
+impl<Context> Decode<Context> for Tracked {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u8::decode(decoder)? {
+            255 => Err(DecodeError::Other("tracked byte 255")),
+            byte => Ok(Self(byte)),
+        }
+    }
+}
+
+fn dropped() -> usize {
+    DROPPED.with(|dropped| dropped.replace(0))
+}
+
+#[test]
+fn test_drop_after_error() {
+    dropped();
+
+    // the 10th element fails, so the 9 elements before it are dropped once
+    let mut encoded = vec![1u8; 16];
+    encoded[9] = 255;
+    let err = bincode::decode_from_slice::<Box<[[Tracked; 4]; 4]>, _>(&encoded, config::standard())
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::Other("tracked byte 255")
+    ));
+    assert_eq!(dropped(), 9);
+
+    // the input ends in the middle of the array
+    let err = bincode::decode_from_slice::<Rc<[Tracked; 16]>, _>(&[1; 5], config::standard())
+        .unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+    assert_eq!(dropped(), 5);
+
+    // the first element fails
+    encoded[0] = 255;
+    assert!(
+        bincode::decode_from_slice::<Arc<[Tracked; 16]>, _>(&encoded, config::standard()).is_err()
+    );
+    assert_eq!(dropped(), 0);
+
+    // every element is dropped once with the box
+    let (boxed, _): (Box<[[Tracked; 4]; 4]>, usize) =
+        bincode::decode_from_slice(&[3; 16], config::standard()).unwrap();
+    assert!(boxed.iter().flatten().all(|tracked| tracked.0 == 3));
+    assert_eq!(dropped(), 0);
+    drop(boxed);
+    assert_eq!(dropped(), 16);
+}
+
+/// A type that only implements `decode` and `borrow_decode`, like any type outside of bincode
+#[derive(Debug, PartialEq)]
+struct Named<'a> {
+    id: u32,
+    name: &'a str,
+}
+
+impl<'de, Context> bincode::BorrowDecode<'de, Context> for Named<'de> {
+    fn borrow_decode<D: bincode::de::BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Ok(Self {
+            id: u32::decode(decoder)?,
+            name: bincode::BorrowDecode::borrow_decode(decoder)?,
+        })
+    }
+}
+
+#[test]
+fn test_user_types_in_place() {
+    let config = config::standard();
+
+    // `Tracked` only implements `decode`, so it is decoded and moved into the allocation
+    let (boxed, _): (Box<Tracked>, usize) = bincode::decode_from_slice(&[5], config).unwrap();
+    assert_eq!(boxed.0, 5);
+    let (rc, _): (Rc<Tracked>, usize) = bincode::decode_from_slice(&[6], config).unwrap();
+    assert_eq!(rc.0, 6);
+    let (arc, _): (Arc<Tracked>, usize) = bincode::decode_from_slice(&[7], config).unwrap();
+    assert_eq!(arc.0, 7);
+    let err = bincode::decode_from_slice::<Box<Tracked>, _>(&[255], config).unwrap_err();
+    assert!(matches!(err, DecodeError::Other("tracked byte 255")));
+    drop((boxed, rc, arc));
+    dropped();
+
+    let encoded = bincode::encode_to_vec((3u32, "three"), config).unwrap();
+    let (boxed, _): (Box<Named>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        *boxed,
+        Named {
+            id: 3,
+            name: "three"
+        }
+    );
+    let (rc, _): (Rc<[Named; 1]>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        rc[0],
+        Named {
+            id: 3,
+            name: "three"
+        }
+    );
+    let (arc, _): (Arc<Named>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(arc.name, "three");
+    let err =
+        bincode::borrow_decode_from_slice::<Box<Named>, _>(&encoded[..3], config).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { .. }), "{err:?}");
+}
+
+#[test]
+fn test_strings_in_place() {
+    let strings = [String::from("a"), String::from("bc"), String::new()];
+    let encoded = bincode::encode_to_vec(&strings, config::standard()).unwrap();
+    let (boxed, _): (Box<[String; 3]>, usize) =
+        bincode::decode_from_slice(&encoded, config::standard()).unwrap();
+    assert_eq!(*boxed, strings);
+
+    // the last string is not valid UTF-8
+    let mut invalid = encoded.clone();
+    invalid.truncate(invalid.len() - 1);
+    invalid.extend([1, 0xFF]);
+    let err = bincode::decode_from_slice::<Box<[String; 3]>, _>(&invalid, config::standard())
+        .unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Utf8 { .. }), "{err:?}");
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "bytes")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use bytes::{Bytes, BytesMut};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Packet {
+    id: u32,
+    header: Bytes,
+    payload: Bytes,
+}
+
+fn assert_same_format<C: bincode::config::Config>(data: &[u8], config: C) {
+    let vec_encoded = bincode::encode_to_vec(data.to_vec(), config).unwrap();
+    let bytes_encoded = bincode::encode_to_vec(Bytes::copy_from_slice(data), config).unwrap();
+    let bytes_mut_encoded = bincode::encode_to_vec(BytesMut::from(data), config).unwrap();
+    assert_eq!(vec_encoded, bytes_encoded);
+    assert_eq!(vec_encoded, bytes_mut_encoded);
+}
+
+#[test]
+fn test_bytes_format_matches_vec() {
+    let data: Vec<u8> = (0..=255).collect();
+    for len in [0, 1, 250, 256] {
+        assert_same_format(&data[..len], bincode::config::standard());
+        assert_same_format(&data[..len], bincode::config::legacy());
+    }
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let config = bincode::config::standard();
+    let bytes = Bytes::from_static(b"hello world");
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+
+    let (decoded, len): (Bytes, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, bytes);
+    assert_eq!(len, encoded.len());
+
+    let (decoded, len): (BytesMut, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, bytes);
+    assert_eq!(len, encoded.len());
+
+    let (decoded, len): (Vec<u8>, usize) =
+        bincode::decode_from_bytes(Bytes::from(encoded.clone()), config).unwrap();
+    assert_eq!(decoded, bytes);
+    assert_eq!(len, encoded.len());
+}
+
+#[test]
+fn test_bytes_zero_copy() {
+    let config = bincode::config::standard();
+    let packet = Packet {
+        id: 5,
+        header: Bytes::from_static(b"header"),
+        payload: Bytes::from(vec![0xAB; 1024]),
+    };
+    let source = Bytes::from(bincode::encode_to_vec(&packet, config).unwrap());
+    let source_range = source.as_ptr_range();
+
+    let (decoded, len): (Packet, usize) =
+        bincode::decode_from_bytes(source.clone(), config).unwrap();
+    assert_eq!(decoded, packet);
+    assert_eq!(len, source.len());
+
+    for field in [&decoded.header, &decoded.payload] {
+        let field_range = field.as_ptr_range();
+        assert!(source_range.start <= field_range.start);
+        assert!(field_range.end <= source_range.end);
+    }
+}
+
+#[test]
+fn test_bytes_unexpected_end() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(Bytes::from_static(b"hello"), config).unwrap();
+    let truncated = Bytes::copy_from_slice(&encoded[..encoded.len() - 1]);
+    let err = bincode::decode_from_bytes::<Bytes, _>(truncated, config).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { additional: 1 }));
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Frame {
+    id: u8,
+    #[bincode(remaining_bytes)]
+    payload: Bytes,
+}
+
+#[test]
+fn test_bytes_remaining() {
+    let config = bincode::config::standard();
+    let frame = Frame {
+        id: 3,
+        payload: Bytes::from_static(b"payload"),
+    };
+    let encoded = Bytes::from(bincode::encode_to_vec(&frame, config).unwrap());
+    assert_eq!(&encoded[..], b"\x03payload");
+
+    let (decoded, len): (Frame, usize) =
+        bincode::decode_from_bytes(encoded.clone(), config).unwrap();
+    assert_eq!(decoded, frame);
+    assert_eq!(len, encoded.len());
+    // the payload is a slice of the input
+    assert_eq!(decoded.payload.as_ptr(), encoded[1..].as_ptr());
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "chrono")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+
+fn the_same_with_config<T, C>(value: &T, config: C)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    C: bincode::config::Config,
+{
+    let mut buffer = [0u8; 64];
+    let len = bincode::encode_into_slice(value, &mut buffer, config).unwrap();
+    let (decoded, decoded_len): (T, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(value, &decoded);
+    assert_eq!(len, decoded_len);
+}
+
+fn the_same<T>(value: T)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+{
+    the_same_with_config(&value, bincode::config::standard());
+    the_same_with_config(&value, bincode::config::standard().with_big_endian());
+    the_same_with_config(&value, bincode::config::legacy());
+    the_same_with_config(&value, bincode::config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_date_time_utc() {
+    the_same(DateTime::<Utc>::UNIX_EPOCH);
+    the_same(DateTime::<Utc>::MIN_UTC);
+    the_same(DateTime::<Utc>::MAX_UTC);
+    the_same(DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap());
+    // pre-1970
+    the_same(DateTime::from_timestamp(-1, 999_999_999).unwrap());
+    the_same(DateTime::from_timestamp(-86_400 * 365 * 100, 1).unwrap());
+    // leap second
+    the_same(DateTime::from_timestamp(1_483_228_799, 1_500_000_000).unwrap());
+}
+
+#[test]
+fn test_date_time_utc_format() {
+    let date_time = DateTime::from_timestamp(-2, 5).unwrap();
+    let encoded = bincode::encode_to_vec(date_time, bincode::config::legacy()).unwrap();
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&(-2i64).to_le_bytes());
+    expected.extend_from_slice(&5u32.to_le_bytes());
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_naive_date_time() {
+    the_same(NaiveDateTime::MIN);
+    the_same(NaiveDateTime::MAX);
+    the_same(
+        NaiveDate::from_ymd_opt(1969, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 999_999_999)
+            .unwrap(),
+    );
+    the_same(
+        NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 1_999)
+            .unwrap(),
+    );
+}
+
+#[test]
+fn test_naive_date() {
+    the_same(NaiveDate::MIN);
+    the_same(NaiveDate::MAX);
+    the_same(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
+    the_same(NaiveDate::from_ymd_opt(1600, 2, 29).unwrap());
+
+    let encoded = bincode::encode_to_vec(
+        NaiveDate::from_ymd_opt(1970, 1, 2).unwrap(),
+        bincode::config::legacy(),
+    )
+    .unwrap();
+    assert_eq!(encoded, 1i32.to_le_bytes());
+}
+
+#[test]
+fn test_time_delta() {
+    the_same(TimeDelta::zero());
+    the_same(TimeDelta::MIN);
+    the_same(TimeDelta::MAX);
+    the_same(TimeDelta::nanoseconds(-1));
+    the_same(TimeDelta::nanoseconds(1));
+    the_same(TimeDelta::seconds(-5) + TimeDelta::milliseconds(250));
+}
+
+#[test]
+fn test_out_of_range() {
+    let config = bincode::config::legacy();
+
+    let encoded = bincode::encode_to_vec((i64::MAX, 0u32), config).unwrap();
+    let err = bincode::decode_from_slice::<DateTime<Utc>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+    let err = bincode::decode_from_slice::<NaiveDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+    let err = bincode::decode_from_slice::<TimeDelta, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    // nanoseconds out of range
+    let encoded = bincode::encode_to_vec((0i64, 2_000_000_000u32), config).unwrap();
+    let err = bincode::decode_from_slice::<DateTime<Utc>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+    let err = bincode::decode_from_slice::<TimeDelta, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    // leap second nanoseconds on a second that is not the last second of a minute
+    let encoded = bincode::encode_to_vec((0i64, 1_500_000_000u32), config).unwrap();
+    let err = bincode::decode_from_slice::<DateTime<Utc>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    for days in [i32::MIN, i32::MAX] {
+        let encoded = bincode::encode_to_vec(days, config).unwrap();
+        let err = bincode::decode_from_slice::<NaiveDate, _>(&encoded, config).unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+    }
+}
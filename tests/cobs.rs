@@ -0,0 +1,263 @@
+#![cfg(all(feature = "cobs", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::cobs::{decode_from_cobs_slice, max_encoded_len, CobsReader, CobsWriter};
+use bincode::de::read::{Reader, SliceReader};
+use bincode::enc::write::{SliceWriter, Writer};
+use bincode::error::{DecodeError, EncodeError};
+use bincode::IoWriter;
+
+/// COBS-encodes `payload` with a reference implementation that works on the whole payload
+fn reference_encode(payload: &[u8]) -> Vec<u8> {
+    let mut output = vec![0];
+    let mut code_index = Some(0);
+    let mut code = 1u8;
+    for (i, &byte) in payload.iter().enumerate() {
+        if byte != 0 {
+            output.push(byte);
+            code += 1;
+        }
+        if byte == 0 || code == 0xFF {
+            output[code_index.unwrap()] = code;
+            code = 1;
+            // a full block at the end does not need an empty block after it
+            code_index = (byte == 0 || i + 1 < payload.len()).then_some(output.len());
+            if code_index.is_some() {
+                output.push(0);
+            }
+        }
+    }
+    if let Some(code_index) = code_index {
+        output[code_index] = code;
+    }
+    output.push(0);
+    output
+}
+
+/// Writes `payload` with a `CobsWriter`, in chunks of `chunk` bytes
+fn cobs_encode(payload: &[u8], chunk: usize) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut writer = CobsWriter::new(IoWriter::new(&mut output));
+    for bytes in payload.chunks(chunk) {
+        writer.write(bytes).unwrap();
+    }
+    writer.finish().unwrap();
+    output
+}
+
+/// Reads the payload of the frame with a `CobsReader`, in chunks of `chunk` bytes
+fn cobs_decode(frame: &[u8], len: usize, chunk: usize) -> Vec<u8> {
+    let mut reader = CobsReader::new(SliceReader::new(frame));
+    let mut payload = vec![0; len];
+    for bytes in payload.chunks_mut(chunk) {
+        reader.read(bytes).unwrap();
+    }
+    assert!(matches!(
+        reader.read(&mut [0]).unwrap_err(),
+        DecodeError::CobsFrameTruncated
+    ));
+    reader.finish().unwrap();
+    payload
+}
+
+fn check_payload(payload: &[u8]) {
+    let expected = reference_encode(payload);
+    for chunk in [1, 3, 254, 1000] {
+        let frame = cobs_encode(payload, chunk);
+        assert_eq!(frame, expected, "chunks of {chunk}");
+        assert_eq!(frame.iter().filter(|&&byte| byte == 0).count(), 1);
+        assert!(frame.len() <= max_encoded_len(payload.len()));
+        assert_eq!(cobs_decode(&frame, payload.len(), chunk), payload);
+    }
+}
+
+#[test]
+fn test_known_frames() {
+    assert_eq!(cobs_encode(&[], 1), [1, 0]);
+    assert_eq!(cobs_encode(&[0], 1), [1, 1, 0]);
+    assert_eq!(cobs_encode(&[0, 0], 1), [1, 1, 1, 0]);
+    assert_eq!(
+        cobs_encode(&[0x11, 0x22, 0, 0x33], 1),
+        [3, 0x11, 0x22, 2, 0x33, 0]
+    );
+    assert_eq!(cobs_encode(&[0x11, 0, 0, 0], 4), [2, 0x11, 1, 1, 1, 0]);
+
+    let payload: Vec<u8> = (1..=254).collect();
+    let mut expected = vec![0xFF];
+    expected.extend_from_slice(&payload);
+    expected.push(0);
+    assert_eq!(cobs_encode(&payload, 7), expected);
+
+    let payload: Vec<u8> = (1..=255).collect();
+    let mut expected = vec![0xFF];
+    expected.extend(1..=254);
+    expected.extend([2, 255, 0]);
+    assert_eq!(cobs_encode(&payload, 7), expected);
+}
+
+#[test]
+fn test_many_zero_bytes() {
+    check_payload(&[0; 1000]);
+    let payload: Vec<u8> = (0..2000)
+        .map(|i| if i % 3 == 0 { 0 } else { i as u8 | 1 })
+        .collect();
+    check_payload(&payload);
+
+    let config = bincode::config::legacy();
+    let value = (vec![0u64; 100], 0u32, [0u16; 50]);
+    let mut frame = Vec::new();
+    let mut writer = CobsWriter::new(IoWriter::new(&mut frame));
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        frame,
+        reference_encode(&bincode::encode_to_vec(&value, config).unwrap())
+    );
+
+    let (decoded, len): ((Vec<u64>, u32, [u16; 50]), usize) =
+        decode_from_cobs_slice(&frame, config).unwrap();
+    assert_eq!((decoded, len), (value, frame.len()));
+}
+
+#[test]
+fn test_worst_case_overhead() {
+    for len in [253usize, 254, 255, 508, 509, 1000, 4096] {
+        let payload: Vec<u8> = (0..len).map(|i| (i % 255) as u8 + 1).collect();
+        check_payload(&payload);
+        let frame = cobs_encode(&payload, 100);
+        // no zero bytes, so the overhead is the most it can be
+        assert_eq!(frame.len() - payload.len(), len.div_ceil(254) + 1, "{len}");
+    }
+
+    let config = bincode::config::standard();
+    let value: Vec<u8> = (0..1000).map(|i| (i % 255) as u8 + 1).collect();
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    let mut output = vec![0u8; max_encoded_len(encoded.len())];
+    let mut writer = CobsWriter::new(SliceWriter::new(&mut output));
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    let len = writer.finish().unwrap().bytes_written();
+    assert_eq!(output[..len], reference_encode(&encoded));
+
+    let (decoded, frame_len): (Vec<u8>, usize) = decode_from_cobs_slice(&output, config).unwrap();
+    assert_eq!((decoded, frame_len), (value, len));
+}
+
+#[test]
+fn test_consecutive_frames() {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    for value in ["first", "", "third\0frame"] {
+        let mut writer = CobsWriter::new(IoWriter::new(&mut stream));
+        bincode::encode_into_writer(value, &mut writer, config).unwrap();
+        writer.finish().unwrap();
+    }
+
+    let mut rest = &stream[..];
+    for expected in ["first", "", "third\0frame"] {
+        let (decoded, len): (String, usize) = decode_from_cobs_slice(rest, config).unwrap();
+        assert_eq!(decoded, expected);
+        rest = &rest[len..];
+    }
+    assert!(rest.is_empty());
+
+    // the rest of a frame is skipped
+    let (decoded, len): (u8, usize) = decode_from_cobs_slice(&stream, config).unwrap();
+    assert_eq!((decoded, len), (5, 8));
+
+    let mut reader = SliceReader::new(&stream);
+    for expected in ["first", "", "third\0frame"] {
+        let mut frame = CobsReader::new(&mut reader);
+        let decoded: String = bincode::decode_from_reader(&mut frame, config).unwrap();
+        assert_eq!(decoded, expected);
+        frame.finish().unwrap();
+    }
+    assert!(reader.peek_read(1).is_none());
+}
+
+#[test]
+fn test_corrupted_stuffing() {
+    let config = bincode::config::standard();
+    let frame = cobs_encode(
+        &bincode::encode_to_vec((1u8, "abcdef"), config).unwrap(),
+        16,
+    );
+    assert_eq!(frame, [9, 1, 6, b'a', b'b', b'c', b'd', b'e', b'f', 0]);
+
+    // a zero byte inside of a block
+    let mut corrupted = frame.clone();
+    corrupted[4] = 0;
+    let result: Result<((u8, String), usize), _> = decode_from_cobs_slice(&corrupted, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::CobsInvalidStuffing { offset: 4 }
+    ));
+
+    // a code byte that points past the delimiter, into the next frame
+    let mut corrupted = frame.clone();
+    corrupted[0] = 12;
+    corrupted.extend([1, 0]);
+    let result: Result<((u8, String), usize), _> = decode_from_cobs_slice(&corrupted, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::CobsInvalidStuffing { offset: 9 }
+    ));
+
+    // a code byte that ends the frame too early
+    let mut corrupted = frame.clone();
+    corrupted[0] = 4;
+    corrupted[4] = 0;
+    let result: Result<((u8, String), usize), _> = decode_from_cobs_slice(&corrupted, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::CobsFrameTruncated
+    ));
+
+    // the corruption is found after the value is decoded
+    let result: Result<(u8, usize), _> = decode_from_cobs_slice(&corrupted[..4], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::CobsFrameTruncated
+    ));
+    let mut corrupted = frame.clone();
+    corrupted[7] = 0;
+    let result: Result<(u8, usize), _> = decode_from_cobs_slice(&corrupted, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::CobsInvalidStuffing { offset: 7 }
+    ));
+}
+
+#[test]
+fn test_truncated_frames() {
+    let config = bincode::config::standard();
+    let frame = cobs_encode(&bincode::encode_to_vec((0u8, 300u32), config).unwrap(), 16);
+    assert_eq!(frame, [1, 4, 251, 44, 1, 0]);
+
+    for len in 0..frame.len() {
+        let result: Result<((u8, u32), usize), _> = decode_from_cobs_slice(&frame[..len], config);
+        assert!(
+            matches!(
+                result.unwrap_err().into_inner(),
+                DecodeError::CobsFrameTruncated
+            ),
+            "{len}"
+        );
+    }
+
+    // an empty frame
+    let result: Result<(u8, usize), _> = decode_from_cobs_slice(&[0, 5], config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::CobsFrameTruncated
+    ));
+}
+
+#[test]
+fn test_writer_errors() {
+    let config = bincode::config::standard();
+    let mut output = [0u8; 4];
+    let mut writer = CobsWriter::new(SliceWriter::new(&mut output));
+    bincode::encode_into_writer([0u8; 3], &mut writer, config).unwrap();
+    assert!(matches!(writer.finish(), Err(EncodeError::UnexpectedEnd)));
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "alloc")]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::{string::String, vec, vec::Vec};
+use bincode::{
+    config::{self, Config},
+    error::DecodeError,
+};
+
+fn value() -> (u32, String, Vec<u64>) {
+    (300, String::from("peer"), vec![1, u64::MAX])
+}
+
+#[test]
+fn test_round_trip() {
+    let config = config::standard();
+    let encoded = bincode::encode_with_config_header(value(), config).unwrap();
+    assert_eq!(encoded[..4], config.wire_id());
+    assert_eq!(
+        encoded[4..],
+        bincode::encode_to_vec(value(), config).unwrap()
+    );
+
+    let (decoded, len): ((u32, String, Vec<u64>), usize) =
+        bincode::decode_checking_config_header(&encoded, config).unwrap();
+    assert_eq!(decoded, value());
+    assert_eq!(len, encoded.len());
+
+    // the settings that only change what is accepted may differ
+    let (decoded, _): ((u32, String, Vec<u64>), usize) =
+        bincode::decode_checking_config_header(&encoded, config::untrusted(1024)).unwrap();
+    assert_eq!(decoded, value());
+    let encoded =
+        bincode::encode_with_config_header(value(), config::untrusted(1024).with_big_endian())
+            .unwrap();
+    assert!(
+        bincode::decode_checking_config_header::<(u32, String, Vec<u64>), _>(
+            &encoded,
+            config::standard().with_big_endian()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_mismatch() {
+    let encoded = bincode::encode_with_config_header(value(), config::standard()).unwrap();
+    let err = bincode::decode_checking_config_header::<(u32, String, Vec<u64>), _>(
+        &encoded,
+        config::legacy(),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DecodeError::ConfigMismatch { expected, found }
+                if expected == config::legacy().wire_id() && found == config::standard().wire_id()
+        ),
+        "{err:?}"
+    );
+    assert_eq!(
+        err.to_string(),
+        "the data was encoded with a different config: variable int encoding instead of fixed int encoding"
+    );
+
+    let encoded = bincode::encode_with_config_header(
+        value(),
+        config::standard()
+            .with_big_endian()
+            .with_fixed_u32_lengths(),
+    )
+    .unwrap();
+    let err = bincode::decode_checking_config_header::<(u32, String, Vec<u64>), _>(
+        &encoded,
+        config::standard(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the data was encoded with a different config: big endian instead of little endian, fixed u32 lengths instead of lengths like a u64"
+    );
+
+    // data without a header, or with a header of a later version
+    let err =
+        bincode::decode_checking_config_header::<u32, _>(&[1, 0], config::standard()).unwrap_err();
+    assert!(
+        matches!(err, DecodeError::UnexpectedEnd { additional: 2 }),
+        "{err:?}"
+    );
+    let err =
+        bincode::decode_checking_config_header::<u32, _>(&[2, 0, 0, 0, 1], config::standard())
+            .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the data has an unknown config header [02, 00, 00, 00]"
+    );
+}
+
+/// The wire ids are written into data, so they must never change
+#[test]
+fn test_golden_wire_ids() {
+    assert_eq!(config::standard().wire_id(), [1, 0b0_0000, 0b00_0000, 0]);
+    assert_eq!(config::legacy().wire_id(), [1, 0b0_0010, 0b00_0000, 0]);
+    assert_eq!(
+        config::untrusted(1024).wire_id(),
+        [1, 0b0_0000, 0b11_0011, 0]
+    );
+    assert_eq!(
+        config::standard()
+            .with_big_endian()
+            .with_fixed_u32_lengths()
+            .with_lossless_paths()
+            .wire_id(),
+        [1, 0b1_1001, 0b00_0000, 0]
+    );
+    assert_eq!(
+        config::legacy()
+            .with_varint_lengths()
+            .with_lossy_utf8_strings()
+            .wire_id(),
+        [1, 0b0_0110, 0b00_0100, 0]
+    );
+    assert_eq!(
+        config::standard()
+            .with_fixed_u64_lengths()
+            .with_exact_system_time()
+            .wire_id(),
+        [1, 0b0_1100, 0b00_1000, 0]
+    );
+    assert_eq!(
+        config::standard().with_scalar_char_encoding().wire_id(),
+        [1, 0b10_0000, 0b00_0000, 0]
+    );
+
+    let encoded = bincode::encode_with_config_header(7u8, config::legacy()).unwrap();
+    assert_eq!(encoded, [1, 2, 0, 0, 7]);
+}
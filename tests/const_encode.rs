@@ -0,0 +1,137 @@
+#![cfg(feature = "derive")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{config::Config, enc::write::ConstWriter, Encode};
+
+#[derive(bincode::Encode, bincode::ConstEncode)]
+struct Version {
+    major: u16,
+    minor: u8,
+}
+
+#[derive(bincode::Encode, bincode::ConstEncode)]
+struct Header {
+    magic: [u8; 4],
+    version: Version,
+    flags: [[bool; 2]; 3],
+    checksum: u128,
+    offset: isize,
+    scale: f64,
+}
+
+#[derive(bincode::Encode, bincode::ConstEncode)]
+struct Pair(i32, f32);
+
+#[derive(bincode::Encode, bincode::ConstEncode)]
+struct Empty;
+
+const HEADER: Header = Header {
+    magic: *b"BNC1",
+    version: Version { major: 2, minor: 7 },
+    flags: [[true, false], [false, false], [true, true]],
+    checksum: 0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10,
+    offset: -12_345,
+    scale: 1.5,
+};
+
+const HEADER_LE: [u8; Header::ENCODED_SIZE] = HEADER.encoded(bincode::config::legacy());
+const HEADER_BE: [u8; Header::ENCODED_SIZE] =
+    HEADER.encoded(bincode::config::legacy().with_big_endian());
+const PAIR: [u8; Pair::ENCODED_SIZE] =
+    Pair(-2, 0.25).encoded(bincode::config::standard().with_fixed_int_encoding());
+const EMPTY: [u8; Empty::ENCODED_SIZE] = Empty.encoded(bincode::config::legacy());
+
+fn check<T: Encode>(value: &T, encoded: &[u8], config: impl Config) {
+    assert_eq!(bincode::encode_to_vec(value, config).unwrap(), encoded);
+}
+
+#[test]
+fn test_const_encoded_matches_runtime() {
+    assert_eq!(Header::ENCODED_SIZE, 4 + 3 + 6 + 16 + 8 + 8);
+    check(&HEADER, &HEADER_LE, bincode::config::legacy());
+    check(
+        &HEADER,
+        &HEADER_BE,
+        bincode::config::legacy().with_big_endian(),
+    );
+    check(
+        &Pair(-2, 0.25),
+        &PAIR,
+        bincode::config::standard().with_fixed_int_encoding(),
+    );
+    check(&Empty, &EMPTY, bincode::config::legacy());
+    assert_eq!(EMPTY.len(), 0);
+}
+
+const PRIMITIVES: [u8; 46] = {
+    let mut writer = ConstWriter::new(bincode::config::legacy().with_big_endian());
+    writer.write_u8(1);
+    writer.write_i8(-2);
+    writer.write_bool(true);
+    writer.write_u16(0x0304);
+    writer.write_i16(-5);
+    writer.write_u32(6);
+    writer.write_i32(-7);
+    writer.write_u64(8);
+    writer.write_i64(-9);
+    writer.write_usize(10);
+    writer.write_f32(-11.5);
+    writer.write_bytes(b"xyz");
+    writer.finish()
+};
+
+#[test]
+fn test_const_writer_matches_runtime() {
+    let value = (
+        (1u8, -2i8, true, 0x0304u16, -5i16),
+        (6u32, -7i32, 8u64, -9i64, 10usize),
+        -11.5f32,
+        [b'x', b'y', b'z'],
+    );
+    check(
+        &value,
+        &PRIMITIVES,
+        bincode::config::legacy().with_big_endian(),
+    );
+
+    let mut writer = ConstWriter::<24>::new(bincode::config::legacy());
+    writer.write_u128(u128::MAX - 1);
+    writer.write_isize(-3);
+    assert_eq!(writer.bytes_written(), 24);
+    check(
+        &(u128::MAX - 1, -3isize),
+        &writer.finish(),
+        bincode::config::legacy(),
+    );
+}
+
+#[test]
+fn test_const_writer_as_writer() {
+    let config = bincode::config::legacy();
+    let mut writer = ConstWriter::<15>::new(config);
+    bincode::encode_into_writer((5u32, "abc"), &mut writer, config).unwrap();
+    assert!(bincode::encode_into_writer(0u8, &mut writer, config).is_err());
+    check(&(5u32, "abc"), &writer.finish(), config);
+}
+
+#[test]
+#[should_panic(expected = "ConstWriter only supports fixint configs")]
+fn test_const_writer_varint() {
+    let _ = ConstWriter::<4>::new(bincode::config::standard());
+}
+
+#[test]
+#[should_panic(expected = "ConstWriter does not have enough room left")]
+fn test_const_writer_full() {
+    let mut writer = ConstWriter::<3>::new(bincode::config::legacy());
+    writer.write_u32(1);
+}
+
+#[test]
+#[should_panic(expected = "ConstWriter was not filled")]
+fn test_const_writer_not_filled() {
+    let mut writer = ConstWriter::<3>::new(bincode::config::legacy());
+    writer.write_u16(1);
+    let _ = writer.finish();
+}
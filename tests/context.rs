@@ -105,3 +105,316 @@ fn decode_with_context() {
     let self_referencing: SelfReferencing = decode_from_slice(&bytes, config).unwrap().0;
     self_referencing.with_container(|c| assert_eq!(&container, c))
 }
+
+/// Borrows from the input and allocates in the context
+#[derive(Encode, BorrowDecode, PartialEq, Eq, Debug)]
+#[bincode(decode_context = "&'bump Bump")]
+struct Named<'a, 'bump> {
+    name: &'a str,
+    // not tied to the lifetime of the input, only to the bump
+    #[bincode(owned)]
+    vec: CodableVec<'bump, u32>,
+}
+
+/// Is multiplied by the context when it is decoded
+#[derive(Encode, PartialEq, Eq, Debug)]
+struct Scaled(u32);
+
+impl Decode<u32> for Scaled {
+    fn decode<D: bincode::de::Decoder<Context = u32>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Ok(Self(u32::decode(decoder)? * *decoder.context()))
+    }
+}
+bincode::impl_borrow_decode_with_context!(Scaled, u32);
+
+#[derive(Encode, Decode, PartialEq, Eq, Debug)]
+#[bincode(decode_context = "u32", prefix = "ScaledHeader: first")]
+struct ScaledPair {
+    first: Scaled,
+    second: Scaled,
+}
+
+fn container(bump: &Bump) -> Container<'_> {
+    Container {
+        vec: CodableVec(vec![in bump; 1, 2, 3]),
+    }
+}
+
+#[test]
+fn decode_with_context_from_other_sources() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let container = container(&bump);
+    let bytes = encode_to_vec(&container, config).unwrap();
+
+    let (first, second) = bytes.split_at(2);
+    let (decoded, len): (Container, _) =
+        bincode::decode_from_segments_with_context(&[first, second], config, &bump).unwrap();
+    assert_eq!((decoded, len), (container, bytes.len()));
+
+    let container = self::container(&bump);
+    let decoded: Container = bincode::decode_from_reader_with_context(
+        bincode::de::read::SliceReader::new(&bytes),
+        config,
+        &bump,
+    )
+    .unwrap();
+    assert_eq!(decoded, container);
+}
+
+#[test]
+fn borrow_decode_with_context() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let named = Named {
+        name: "numbers",
+        vec: CodableVec(vec![in &bump; 4, 5, 6]),
+    };
+    let mut bytes = encode_to_vec(&named, config).unwrap();
+
+    // the value borrows from the input, and allocates in a bump that outlives it
+    let decoded = {
+        let (decoded, len): (Named, _) =
+            bincode::borrow_decode_from_slice_with_context(&bytes, config, &bump).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(decoded.name, "numbers");
+        decoded.vec
+    };
+    assert_eq!(decoded, named.vec);
+
+    let (decoded, _): (Named, _) =
+        bincode::borrow_decode_from_slice_mut_with_context(&mut bytes, config, &bump).unwrap();
+    assert_eq!(decoded, named);
+
+    let bytes = encode_to_vec(
+        ScaledPair {
+            first: Scaled(2),
+            second: Scaled(3),
+        },
+        config,
+    )
+    .unwrap();
+    let (header, len) = ScaledHeader::decode_prefix_with_context(&bytes, config, 10).unwrap();
+    assert_eq!((header.first, len), (Scaled(20), 1));
+    let (pair, _): (ScaledPair, _) =
+        bincode::decode_from_slice_with_context(&bytes, config, 10).unwrap();
+    assert_eq!(pair.second, Scaled(30));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn decode_with_context_from_std_read() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let container = container(&bump);
+    let mut stream = encode_to_vec(&container, config).unwrap();
+    let len = stream.len();
+    stream.extend_from_slice(&stream.clone());
+    // a third container, whose entries are past the single byte that may be read
+    stream.push(5);
+
+    let mut reader = stream.as_slice();
+    let decoded: Container =
+        bincode::decode_from_std_read_with_context(&mut reader, config, &bump).unwrap();
+    assert_eq!(decoded, container);
+    let (decoded, read): (Container, _) =
+        bincode::decode_from_std_read_with_bytes_read_with_context(&mut reader, config, &bump)
+            .unwrap();
+    assert_eq!((decoded, read), (self::container(&bump), len));
+    let err = bincode::decode_from_std_read_take_with_context::<_, Container, _, _>(
+        &mut reader,
+        1,
+        config,
+        &bump,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TakeLimitExceeded { .. }),
+        "{err:?}"
+    );
+
+    let named = Named {
+        name: "streamed",
+        vec: CodableVec(vec![in &bump; 7]),
+    };
+    let stream = encode_to_vec(&named, config).unwrap();
+    let mut reader = bincode::BufferedIoReader::with_capacity(64, stream.as_slice());
+    let (decoded, read): (Named, _) =
+        bincode::decode_borrowed_from_buf_read_with_context(&mut reader, config, &bump).unwrap();
+    assert_eq!((decoded, read), (named, stream.len()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn decode_with_context_take() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let container = container(&bump);
+    let bytes = encode_to_vec(&container, config).unwrap();
+    let mut stream = bytes.clone();
+    stream.push(0xFF);
+
+    let mut reader = stream.as_slice();
+    let (decoded, read): (Container, _) =
+        bincode::decode_from_std_read_take_with_context(&mut reader, bytes.len(), config, &bump)
+            .unwrap();
+    assert_eq!((decoded, read), (container, bytes.len()));
+    assert_eq!(reader, [0xFF]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn decode_with_context_from_bytes() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let container = container(&bump);
+    let bytes = encode_to_vec(&container, config).unwrap();
+    let (decoded, len): (Container, _) =
+        bincode::decode_from_bytes_with_context(bytes.clone().into(), config, &bump).unwrap();
+    assert_eq!((decoded, len), (container, bytes.len()));
+}
+
+#[cfg(feature = "memmap2")]
+#[test]
+#[cfg_attr(miri, ignore)] // miri does not like `tempfile`
+fn decode_with_context_from_mmap() {
+    use std::io::Write;
+
+    let config = config::standard();
+    let bump = Bump::new();
+    let mut file = tempfile::tempfile().unwrap();
+    for (name, number) in [("one", 1), ("two", 2)] {
+        let named = Named {
+            name,
+            vec: CodableVec(vec![in &bump; number]),
+        };
+        file.write_all(&encode_to_vec(&named, config).unwrap())
+            .unwrap();
+    }
+    // Safety: the file is not modified while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+
+    let (first, len): (Named, _) =
+        bincode::decode_from_mmap_with_context(&mmap, config, &bump).unwrap();
+    assert_eq!((first.name, len), ("one", 6));
+
+    let records: std::vec::Vec<(usize, Named)> =
+        bincode::decode_records_from_mmap_with_context(&mmap, config, &bump)
+            .collect::<Result<_, _>>()
+            .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!((records[1].0, records[1].1.name), (6, "two"));
+    assert_eq!(records[1].1.vec.0.as_slice(), [2]);
+}
+
+/// Allocates its strings and slices in the bump, so it outlives the input
+#[cfg(feature = "bumpalo")]
+#[derive(Encode, Decode, PartialEq, Eq, Debug)]
+#[bincode(decode_context = "&'bump Bump")]
+struct Document<'bump> {
+    title: &'bump str,
+    tags: &'bump [&'bump str],
+    sections: &'bump [Section<'bump>],
+}
+
+#[cfg(feature = "bumpalo")]
+#[derive(Encode, Decode, PartialEq, Eq, Debug)]
+#[bincode(decode_context = "&'bump Bump")]
+enum Section<'bump> {
+    Text(&'bump str),
+    Numbers(&'bump [u32]),
+}
+
+/// Borrows its name from the input, and copies its note into the bump
+#[cfg(feature = "bumpalo")]
+#[derive(Encode, BorrowDecode, PartialEq, Eq, Debug)]
+#[bincode(decode_context = "&'bump Bump")]
+struct Annotated<'a, 'bump> {
+    name: &'a str,
+    #[bincode(owned)]
+    note: &'bump str,
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn decode_in_bump() {
+    let config = config::standard();
+    let bump = Bump::new();
+    let document = Document {
+        title: "notes",
+        tags: &["a", "bc"],
+        sections: &[
+            Section::Text("first"),
+            Section::Numbers(&[1, 2, 300]),
+            Section::Text(""),
+        ],
+    };
+
+    let decoded = {
+        let input = encode_to_vec(&document, config).unwrap();
+        let (decoded, len): (Document, _) =
+            bincode::decode_from_slice_in(&input, config, &bump).unwrap();
+        assert_eq!(len, input.len());
+        decoded
+    };
+    // the input is dropped, the decoded value lives in the bump
+    assert_eq!(decoded, document);
+    assert!(bump.allocated_bytes() > 0);
+
+    // the same bytes as owned types
+    let owned = (
+        String::from("notes"),
+        std::vec![String::from("a")],
+        std::vec::Vec::<u8>::new(),
+    );
+    let input = encode_to_vec(&owned, config).unwrap();
+    let (decoded, _): (Document, _) = bincode::decode_from_slice_in(&input, config, &bump).unwrap();
+    assert_eq!((decoded.title, decoded.tags), ("notes", &["a"][..]));
+
+    let annotated = Annotated {
+        name: "n",
+        note: "copied",
+    };
+    let note = {
+        let input = encode_to_vec(&annotated, config).unwrap();
+        let (decoded, _): (Annotated, _) =
+            bincode::borrow_decode_from_slice_with_context(&input, config, &bump).unwrap();
+        assert_eq!(decoded, annotated);
+        decoded.note
+    };
+    assert_eq!(note, "copied");
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn decode_in_bump_errors() {
+    let config = config::standard();
+    let bump = Bump::new();
+
+    let input = encode_to_vec(("a", &[0xFFu8][..]), config).unwrap();
+    let err = bincode::decode_from_slice_in::<(&str, &str), _>(&input, config, &bump).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Utf8 { .. }), "{err:?}");
+    let (decoded, _): (&str, _) =
+        bincode::decode_from_slice_in(&input[2..], config.with_lossy_utf8_strings(), &bump)
+            .unwrap();
+    assert_eq!(decoded, "\u{FFFD}");
+
+    // a length that the input does not have is not allocated up front
+    let input = encode_to_vec(u64::MAX / 2, config).unwrap();
+    let err = bincode::decode_from_slice_in::<&str, _>(&input, config, &bump).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+    let err = bincode::decode_from_slice_in::<&[u64], _>(&input, config, &bump).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+    let input = encode_to_vec("too long", config).unwrap();
+    let err = bincode::decode_from_slice_in::<&str, _>(&input, config.with_limit::<4>(), &bump)
+        .unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+}
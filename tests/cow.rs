@@ -0,0 +1,137 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use std::borrow::Cow;
+
+#[derive(bincode::Encode, bincode::Decode, Clone, PartialEq, Debug)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Shape<'a> {
+    origin: Cow<'a, Point>,
+    values: Cow<'a, [u32]>,
+    points: Cow<'a, [Point]>,
+    bytes: Cow<'a, [u8]>,
+    name: Cow<'a, str>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Drawing<'a> {
+    Empty,
+    Shape(Shape<'a>),
+    Path { points: Cow<'a, [Point]> },
+    Marker(Cow<'a, Point>, Cow<'a, str>),
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Generic<'a, T: Clone> {
+    value: Cow<'a, T>,
+    list: Cow<'a, [T]>,
+}
+
+#[allow(clippy::ptr_arg)]
+const fn is_borrowed<T: ToOwned + ?Sized>(cow: &Cow<'_, T>) -> bool {
+    matches!(cow, Cow::Borrowed(_))
+}
+
+fn shape() -> Shape<'static> {
+    Shape {
+        origin: Cow::Owned(Point { x: 1, y: 2 }),
+        values: Cow::Borrowed(&[3, 4, 500]),
+        points: Cow::Owned(vec![Point { x: 5, y: 6 }]),
+        bytes: Cow::Borrowed(&[7, 8]),
+        name: Cow::Borrowed("shape"),
+    }
+}
+
+#[test]
+fn test_cow_owned_and_borrowed() {
+    let config = bincode::config::standard();
+    let shape = shape();
+    let encoded = bincode::encode_to_vec(&shape, config).unwrap();
+    // encoded like the borrowed values
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(
+            (
+                Point { x: 1, y: 2 },
+                [3u32, 4, 500].as_slice(),
+                vec![Point { x: 5, y: 6 }],
+                [7u8, 8].as_slice(),
+                "shape"
+            ),
+            config
+        )
+        .unwrap()
+    );
+
+    let (decoded, _): (Shape, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, shape);
+    assert!(!is_borrowed(&decoded.bytes));
+    assert!(!is_borrowed(&decoded.name));
+
+    let (decoded, _): (Shape, usize) = bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, shape);
+    assert!(!is_borrowed(&decoded.origin));
+    assert!(!is_borrowed(&decoded.values));
+    assert!(!is_borrowed(&decoded.points));
+    assert!(is_borrowed(&decoded.bytes));
+    assert!(is_borrowed(&decoded.name));
+}
+
+#[test]
+fn test_cow_in_enums() {
+    let config = bincode::config::standard();
+    let drawings = vec![
+        Drawing::Empty,
+        Drawing::Shape(shape()),
+        Drawing::Path {
+            points: Cow::Borrowed(&[Point { x: 9, y: 10 }, Point { x: 11, y: 12 }]),
+        },
+        Drawing::Marker(Cow::Owned(Point { x: 13, y: 14 }), Cow::Borrowed("marker")),
+    ];
+    let encoded = bincode::encode_to_vec(&drawings, config).unwrap();
+
+    let (decoded, _): (Vec<Drawing>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, drawings);
+    let (decoded, _): (Vec<Drawing>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, drawings);
+    assert!(
+        matches!(&decoded[3], Drawing::Marker(point, name) if !is_borrowed(point) && is_borrowed(name))
+    );
+}
+
+#[test]
+fn test_cow_generic() {
+    let config = bincode::config::standard();
+    let generic = Generic {
+        value: Cow::Owned(String::from("value")),
+        list: Cow::Borrowed(&[String::from("a"), String::from("b")]),
+    };
+    let encoded = bincode::encode_to_vec(&generic, config).unwrap();
+    let (decoded, _): (Generic<String>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, generic);
+    let (decoded, _): (Generic<String>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, generic);
+    assert!(!is_borrowed(&decoded.value));
+    assert!(!is_borrowed(&decoded.list));
+
+    // the derived impls are bounded on `T`, not on `Cow<'a, T>`
+    fn round_trip<T>(value: &Generic<'_, T>) -> Generic<'static, T>
+    where
+        T: Clone + bincode::Encode + bincode::Decode<()>,
+    {
+        let encoded = bincode::encode_to_vec(value, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&encoded, bincode::config::standard())
+            .unwrap()
+            .0
+    }
+    assert_eq!(round_trip(&generic), generic);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use bincode::{BufferedIoReader, DeadlineReader};
+use std::io::{ErrorKind, Read};
+use std::time::{Duration, Instant};
+
+/// A peer that sends the bytes of `data` one at a time, sleeping before every read
+struct SlowLoris {
+    data: Vec<u8>,
+    position: usize,
+    delay: Duration,
+}
+
+impl SlowLoris {
+    fn new(data: Vec<u8>, delay: Duration) -> Self {
+        Self {
+            data,
+            position: 0,
+            delay,
+        }
+    }
+}
+
+impl Read for SlowLoris {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        std::thread::sleep(self.delay);
+        let Some(&byte) = self.data.get(self.position) else {
+            return Ok(0);
+        };
+        buf[0] = byte;
+        self.position += 1;
+        Ok(1)
+    }
+}
+
+/// A socket with a read timeout, that times out every other read
+struct Flaky {
+    data: std::io::Cursor<Vec<u8>>,
+    timed_out: bool,
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.timed_out = !self.timed_out;
+        if self.timed_out {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+        self.data.read(buf)
+    }
+}
+
+/// A socket with a read timeout that never sends anything
+struct Silent;
+
+impl Read for Silent {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        std::thread::sleep(Duration::from_millis(10));
+        Err(ErrorKind::TimedOut.into())
+    }
+}
+
+fn payload() -> Vec<u8> {
+    bincode::encode_to_vec(vec![7u8; 1000], bincode::config::standard()).unwrap()
+}
+
+#[test]
+fn test_deadline() {
+    let config = bincode::config::standard();
+    let start = Instant::now();
+    let mut reader = DeadlineReader::new(
+        SlowLoris::new(payload(), Duration::from_millis(5)),
+        start + Duration::from_millis(100),
+    );
+    let err = bincode::decode_from_std_read::<Vec<u8>, _, _>(&mut reader, config).unwrap_err();
+
+    // without the deadline, this would take 5 seconds
+    assert!(start.elapsed() < Duration::from_secs(2));
+    let DecodeError::TimedOut { elapsed } = err.inner() else {
+        panic!("{err:?}");
+    };
+    assert!(*elapsed >= Duration::from_millis(100));
+    assert!(reader.time_spent() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_budget() {
+    let config = bincode::config::standard();
+    let start = Instant::now();
+    let reader = DeadlineReader::with_budget(
+        SlowLoris::new(payload(), Duration::from_millis(5)),
+        Duration::from_millis(100),
+    );
+    let mut reader = BufferedIoReader::new(reader);
+    let err = bincode::decode_from_reader::<Vec<u8>, _, _>(&mut reader, config).unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(2));
+    let DecodeError::TimedOut { elapsed } = err.inner() else {
+        panic!("{err:?}");
+    };
+    assert!(*elapsed >= Duration::from_millis(100));
+
+    // the budget is not used up by a peer that sends its bytes in time
+    let mut reader =
+        DeadlineReader::with_budget(std::io::Cursor::new(payload()), Duration::from_secs(10));
+    let decoded: Vec<u8> = bincode::decode_from_std_read(&mut reader, config).unwrap();
+    assert_eq!(decoded, vec![7u8; 1000]);
+}
+
+#[test]
+fn test_read_timeouts_are_retried() {
+    let config = bincode::config::standard();
+    let reader = Flaky {
+        data: std::io::Cursor::new(payload()),
+        timed_out: false,
+    };
+    let mut reader = DeadlineReader::new(reader, Instant::now() + Duration::from_secs(10));
+    let decoded: Vec<u8> = bincode::decode_from_std_read(&mut reader, config).unwrap();
+    assert_eq!(decoded, vec![7u8; 1000]);
+
+    // a socket that never sends anything times out on its own read timeout, until the budget is used up
+    let mut reader = DeadlineReader::with_budget(Silent, Duration::from_millis(50));
+    let err = bincode::decode_from_std_read::<u8, _, _>(&mut reader, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TimedOut { .. }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_io_error() {
+    let mut reader = DeadlineReader::new(&[1u8, 2, 3][..], Instant::now());
+    let err = reader.read(&mut [0; 3]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+    assert!(matches!(
+        DecodeError::from_io_error(err),
+        DecodeError::TimedOut { .. }
+    ));
+}
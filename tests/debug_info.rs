@@ -0,0 +1,161 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::debug_info::{
+    append_debug_info, encode_to_vec_with_debug_text, strip_debug_info, MAGIC,
+};
+use bincode::error::DecodeError;
+use bincode::{debug_info_from_slice, encode_to_vec_with_debug_info};
+use std::collections::BTreeMap;
+
+type Orders = BTreeMap<u64, (String, Vec<f32>, Option<bool>)>;
+
+fn orders(len: u64) -> Orders {
+    (0..len)
+        .map(|id| {
+            let item = (
+                format!("item {id}"),
+                vec![id as f32 / 4.0; 3],
+                Some(id % 3 == 0),
+            );
+            (id * 1000, item)
+        })
+        .collect()
+}
+
+#[test]
+fn test_decode_ignores_trailer() {
+    let orders = orders(20);
+    let config = config::standard();
+    let plain = bincode::encode_to_vec(&orders, config).unwrap();
+    let encoded = encode_to_vec_with_debug_info(&orders, config).unwrap();
+    assert_eq!(&encoded[..plain.len()], plain);
+
+    let (decoded, len): (Orders, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (orders.clone(), plain.len()));
+    let decoded: Orders = bincode::decode_from_std_read(&mut &encoded[..], config).unwrap();
+    assert_eq!(decoded, orders);
+
+    // a config that rejects trailing bytes decodes the bytes without the trailer
+    let config = config.with_no_trailing_bytes();
+    let err = bincode::decode_from_slice::<Orders, _>(&encoded, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TrailingBytes { .. }),
+        "{err:?}"
+    );
+    assert_eq!(strip_debug_info(&encoded).unwrap(), plain);
+    let (decoded, _): (Orders, _) =
+        bincode::decode_from_slice(strip_debug_info(&encoded).unwrap(), config).unwrap();
+    assert_eq!(decoded, orders);
+}
+
+/// Asserts that the debug info of `orders` is extracted from their encoding with `config`, and returns the size of the trailer
+fn check_extract<C: Config>(orders: &Orders, config: C) -> usize {
+    let encoded = encode_to_vec_with_debug_info(orders, config).unwrap();
+    assert_eq!(
+        debug_info_from_slice(&encoded).unwrap().unwrap(),
+        format!("{orders:#?}")
+    );
+    assert!(encoded.ends_with(&MAGIC));
+    encoded.len() - bincode::encode_to_vec(orders, config).unwrap().len()
+}
+
+#[test]
+fn test_extract() {
+    let orders = orders(50);
+    check_extract(&orders, config::standard());
+    check_extract(&orders, config::standard().with_big_endian());
+    let trailer = check_extract(&orders, config::legacy());
+
+    // the repeated field names and formatting compress well
+    let text_len = format!("{orders:#?}").len();
+    assert!(trailer < text_len / 3, "{trailer} of {text_len}");
+
+    let encoded =
+        encode_to_vec_with_debug_text(7u8, config::standard(), "seven, from sensor 3").unwrap();
+    assert_eq!(encoded[0], 7);
+    assert_eq!(
+        debug_info_from_slice(&encoded).unwrap().unwrap(),
+        "seven, from sensor 3"
+    );
+}
+
+#[test]
+fn test_no_overhead_without_debug_info() {
+    let orders = orders(5);
+    let encoded = bincode::encode_to_vec(&orders, config::standard()).unwrap();
+    assert_eq!(debug_info_from_slice(&encoded).unwrap(), None);
+    assert_eq!(strip_debug_info(&encoded).unwrap(), encoded);
+    assert_eq!(debug_info_from_slice(&[]).unwrap(), None);
+    assert_eq!(debug_info_from_slice(&MAGIC[1..]).unwrap(), None);
+}
+
+#[test]
+fn test_compression_round_trip() {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let random: String = (0..5000)
+        .map(|_| char::from(b'a' + (next() % 26) as u8))
+        .collect();
+    let texts = [
+        String::new(),
+        "x".into(),
+        "aaaa".into(),
+        "ab".repeat(1000),
+        "z".repeat(100_000),
+        "grüße, 世界 🦀 ".repeat(40),
+        random.clone(),
+        // a repetition further back than a match can reach
+        format!("{random}{}{random}", "-".repeat(70_000)),
+    ];
+    for text in texts {
+        let mut bytes = vec![1, 2, 3];
+        append_debug_info(&mut bytes, &text).unwrap();
+        assert_eq!(debug_info_from_slice(&bytes).unwrap().unwrap(), text);
+        assert_eq!(strip_debug_info(&bytes).unwrap(), [1, 2, 3]);
+    }
+}
+
+#[test]
+fn test_invalid_trailer() {
+    let encoded = encode_to_vec_with_debug_info(orders(10), config::standard()).unwrap();
+    let footer = encoded.len() - 16;
+    let is_invalid = |bytes: &[u8]| {
+        matches!(
+            debug_info_from_slice(bytes),
+            Err(DecodeError::InvalidDebugInfo)
+        )
+    };
+
+    // the front of the payload is cut off, so the compressed text is longer than the bytes
+    let compressed_len = u32::from_le_bytes(encoded[footer + 4..footer + 8].try_into().unwrap());
+    let cut = &encoded[encoded.len() - 16 - compressed_len as usize + 1..];
+    assert!(is_invalid(cut));
+    assert!(strip_debug_info(cut).is_err());
+
+    // a wrong length of the text
+    let mut bytes = encoded.clone();
+    bytes[footer] ^= 1;
+    assert!(is_invalid(&bytes));
+
+    // a match that starts before the text
+    let mut bytes = vec![0x80, 5, 0, 0x00, b'a'];
+    bytes.extend(5u32.to_le_bytes());
+    bytes.extend(5u32.to_le_bytes());
+    bytes.extend(MAGIC);
+    assert!(is_invalid(&bytes));
+
+    // text that is not UTF-8
+    let mut bytes = vec![0x00, 0xFF];
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(2u32.to_le_bytes());
+    bytes.extend(MAGIC);
+    assert!(is_invalid(&bytes));
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::{DecodeExtend, Extended},
+    error::DecodeError,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+fn extended(inserted: usize, overwritten: usize) -> Extended {
+    Extended {
+        inserted,
+        overwritten,
+    }
+}
+
+/// Extends `target` with `batch`, and checks that the result is the same as decoding `batch` and then extending `target` with it
+fn check_extend<M, C>(mut target: M, batch: &M, config: C) -> (M, Extended)
+where
+    M: DecodeExtend<()>
+        + bincode::Decode<()>
+        + bincode::Encode
+        + IntoIterator
+        + Extend<M::Item>
+        + Clone
+        + PartialEq
+        + core::fmt::Debug,
+    C: Config,
+{
+    let encoded = bincode::encode_to_vec(batch, config).unwrap();
+
+    let mut merged = target.clone();
+    let (decoded, _): (M, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    merged.extend(decoded);
+
+    let (extended, len) = bincode::decode_extend_from_slice(&mut target, &encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(target, merged);
+    (target, extended)
+}
+
+#[test]
+fn test_extend_populated_map() {
+    let state = HashMap::from([(1u32, String::from("idle")), (2, String::from("idle"))]);
+    let batch = HashMap::from([
+        (2u32, String::from("busy")),
+        (3, String::from("idle")),
+        (4, String::from("busy")),
+    ]);
+    let (state, extended) = check_extend(state, &batch, config::standard());
+    assert_eq!(extended, self::extended(2, 1));
+    assert_eq!(state.len(), 4);
+    assert_eq!(state[&1], "idle");
+    assert_eq!(state[&2], "busy");
+
+    let tree = BTreeMap::from([(1u8, 1u8), (5, 5)]);
+    let (tree, extended) = check_extend(
+        tree,
+        &BTreeMap::from([(0u8, 0u8), (5, 6), (9, 9)]),
+        config::legacy(),
+    );
+    assert_eq!(extended, self::extended(2, 1));
+    assert_eq!(tree, BTreeMap::from([(0, 0), (1, 1), (5, 6), (9, 9)]));
+
+    let (_, extended) = check_extend(
+        HashSet::from([1u64, 2]),
+        &HashSet::from([2u64, 3]),
+        config::standard(),
+    );
+    assert_eq!(extended, self::extended(1, 1));
+    let (_, extended) = check_extend(
+        BTreeSet::from([String::from("a")]),
+        &BTreeSet::from([String::from("a"), String::from("b")]),
+        config::standard().with_strict_map_order(),
+    );
+    assert_eq!(extended, self::extended(1, 1));
+
+    let (vec, extended) = check_extend(vec![1u32, 2], &vec![3, 4, 5], config::standard());
+    assert_eq!(vec, [1, 2, 3, 4, 5]);
+    assert_eq!(extended, self::extended(3, 0));
+    let (vec, _) = check_extend(vec![1u8], &vec![2, 3], config::legacy());
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[test]
+fn test_extend_string() {
+    let mut string = String::from("hello");
+    let encoded = bincode::encode_to_vec(", world", config::standard()).unwrap();
+    let (extended, _) =
+        bincode::decode_extend_from_slice(&mut string, &encoded, config::standard()).unwrap();
+    assert_eq!(string, "hello, world");
+    assert_eq!(extended, self::extended(7, 0));
+
+    let err =
+        bincode::decode_extend_from_slice(&mut string, &[1, 0xFF], config::standard()).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Utf8 { .. }), "{err:?}");
+    assert_eq!(string, "hello, world");
+}
+
+#[test]
+fn test_overwrite_or_reject_duplicates() {
+    // a batch with the key 2 twice, and the keys out of order
+    let pairs = [(1u32, 10u32), (2, 20), (2, 21), (0, 1)];
+    let mut encoded = bincode::encode_to_vec(pairs.len() as u64, config::standard()).unwrap();
+    encoded.extend(bincode::encode_to_vec(pairs, config::standard()).unwrap());
+
+    // without a strict map order, later entries overwrite earlier ones
+    let mut tree = BTreeMap::from([(1u32, 0u32)]);
+    let (extended, _) =
+        bincode::decode_extend_from_slice(&mut tree, &encoded, config::standard()).unwrap();
+    assert_eq!(extended, self::extended(2, 2));
+    assert_eq!(tree, BTreeMap::from([(0, 1), (1, 10), (2, 21)]));
+
+    let mut map = HashMap::from([(1u32, 0u32)]);
+    let (extended, _) =
+        bincode::decode_extend_from_slice(&mut map, &encoded, config::standard()).unwrap();
+    assert_eq!(extended, self::extended(2, 2));
+    assert_eq!(map, HashMap::from([(0, 1), (1, 10), (2, 21)]));
+
+    // with a strict map order, the duplicate key is rejected like `decode` rejects it
+    let config = config::standard().with_strict_map_order();
+    let err = bincode::decode_from_slice::<BTreeMap<u32, u32>, _>(&encoded, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::NonCanonicalMapOrder { index: 2 }),
+        "{err:?}"
+    );
+    let mut tree = BTreeMap::from([(1u32, 0u32)]);
+    let err = bincode::decode_extend_from_slice(&mut tree, &encoded, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::NonCanonicalMapOrder { index: 2 }),
+        "{err:?}"
+    );
+    // the keys of the target itself may be overwritten
+    assert_eq!(tree, BTreeMap::from([(1, 10)]));
+}
+
+#[test]
+fn test_extend_limit() {
+    let batch: Vec<u32> = (0..100).collect();
+    let encoded = bincode::encode_to_vec(&batch, config::standard()).unwrap();
+
+    // the same limit as decoding the batch on its own
+    let config = config::standard().with_limit::<100>();
+    let decoded = bincode::decode_from_slice::<Vec<u32>, _>(&encoded, config).unwrap_err();
+    assert!(
+        matches!(decoded.inner(), DecodeError::LimitExceeded),
+        "{decoded:?}"
+    );
+    let mut target = vec![1u32; 1000];
+    let extended = bincode::decode_extend_from_slice(&mut target, &encoded, config).unwrap_err();
+    assert_eq!(
+        format!("{:?}", extended.inner()),
+        format!("{:?}", decoded.inner())
+    );
+    assert_eq!(target.len(), 1000);
+
+    let config = config::standard().with_limit::<1000>();
+    assert!(bincode::decode_from_slice::<Vec<u32>, _>(&encoded, config).is_ok());
+    let (extended, _) = bincode::decode_extend_from_slice(&mut target, &encoded, config).unwrap();
+    assert_eq!(extended, self::extended(100, 0));
+    assert_eq!(target.len(), 1100);
+
+    // a length that is far larger than the input
+    let mut encoded = bincode::encode_to_vec(u64::MAX / 2, config::standard()).unwrap();
+    encoded.extend([1, 2, 3]);
+    let mut map = HashMap::<u8, u8>::new();
+    let err =
+        bincode::decode_extend_from_slice(&mut map, &encoded, config::untrusted(1024)).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+    assert!(map.is_empty());
+}
@@ -24,6 +24,41 @@ fn test_encode() {
     assert_eq!(bytes_written, 3);
     assert_eq!(&slice[..bytes_written], &[10, 10, 20]);
 }
+
+#[test]
+fn test_encode_into_slice_too_small() {
+    let start = Test {
+        a: 500i32,
+        b: 100_000u32,
+        c: 20u8,
+    };
+    let mut buffer = [0u8; 32];
+    let required =
+        bincode::encode_into_slice(&start, &mut buffer, bincode::config::standard()).unwrap();
+    assert_eq!(required, 9);
+
+    for capacity in [0, required - 1] {
+        let mut slice = vec![0u8; capacity];
+        let err = bincode::encode_into_slice(&start, &mut slice, bincode::config::standard())
+            .unwrap_err();
+        match err {
+            bincode::error::EncodeError::SliceTooSmall {
+                capacity: c,
+                required: r,
+            } => {
+                assert_eq!(c, capacity);
+                assert_eq!(r, required);
+            }
+            e => panic!("Expected SliceTooSmall, got {e:?}"),
+        }
+    }
+
+    let mut slice = vec![0u8; required];
+    let bytes_written =
+        bincode::encode_into_slice(&start, &mut slice, bincode::config::standard()).unwrap();
+    assert_eq!(bytes_written, required);
+    assert_eq!(slice, buffer[..required]);
+}
 #[derive(PartialEq, Debug, Eq)]
 pub struct Test2<T> {
     a: T,
@@ -297,9 +332,12 @@ fn test_c_style_enum() {
             }
             Err(DecodeError::UnexpectedVariant {
                 type_name: "CStyleEnum",
-                allowed: &bincode::error::AllowedEnumVariants::Allowed(&[0, 1, 2, 3, 4]),
+                allowed:
+                    &bincode::error::AllowedEnumVariants::Named(
+                        &[("A", 0), ("B", 1), ("C", 2), ("D", 3), ("E", 4)],
+                    ),
                 found,
-            }) if found == num as u32 => {}
+            }) if found == u64::from(num) => {}
             Err(e) => panic!("Expected DecodeError::UnexpectedVariant, got {e:?}"),
         }
     }
@@ -467,3 +505,548 @@ mod zoxide {
         assert!(matches!(decoded[1].path, Cow::Borrowed("Bar")));
     }
 }
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+enum ByteTagged {
+    Push(u32),
+    Pop,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u16")]
+enum U16Tagged {
+    A,
+    B(u8),
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u32")]
+enum U32Tagged {
+    A,
+    B(u8),
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "varint")]
+enum VarintTagged {
+    A,
+    B(u8),
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+enum BorrowedTagged<'a> {
+    Empty,
+    Name(&'a str),
+}
+
+/// The most variants that a `u8` tag can tell apart
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+enum FullByteTagged {
+    V0,
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    V9,
+    V10,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+    V18,
+    V19,
+    V20,
+    V21,
+    V22,
+    V23,
+    V24,
+    V25,
+    V26,
+    V27,
+    V28,
+    V29,
+    V30,
+    V31,
+    V32,
+    V33,
+    V34,
+    V35,
+    V36,
+    V37,
+    V38,
+    V39,
+    V40,
+    V41,
+    V42,
+    V43,
+    V44,
+    V45,
+    V46,
+    V47,
+    V48,
+    V49,
+    V50,
+    V51,
+    V52,
+    V53,
+    V54,
+    V55,
+    V56,
+    V57,
+    V58,
+    V59,
+    V60,
+    V61,
+    V62,
+    V63,
+    V64,
+    V65,
+    V66,
+    V67,
+    V68,
+    V69,
+    V70,
+    V71,
+    V72,
+    V73,
+    V74,
+    V75,
+    V76,
+    V77,
+    V78,
+    V79,
+    V80,
+    V81,
+    V82,
+    V83,
+    V84,
+    V85,
+    V86,
+    V87,
+    V88,
+    V89,
+    V90,
+    V91,
+    V92,
+    V93,
+    V94,
+    V95,
+    V96,
+    V97,
+    V98,
+    V99,
+    V100,
+    V101,
+    V102,
+    V103,
+    V104,
+    V105,
+    V106,
+    V107,
+    V108,
+    V109,
+    V110,
+    V111,
+    V112,
+    V113,
+    V114,
+    V115,
+    V116,
+    V117,
+    V118,
+    V119,
+    V120,
+    V121,
+    V122,
+    V123,
+    V124,
+    V125,
+    V126,
+    V127,
+    V128,
+    V129,
+    V130,
+    V131,
+    V132,
+    V133,
+    V134,
+    V135,
+    V136,
+    V137,
+    V138,
+    V139,
+    V140,
+    V141,
+    V142,
+    V143,
+    V144,
+    V145,
+    V146,
+    V147,
+    V148,
+    V149,
+    V150,
+    V151,
+    V152,
+    V153,
+    V154,
+    V155,
+    V156,
+    V157,
+    V158,
+    V159,
+    V160,
+    V161,
+    V162,
+    V163,
+    V164,
+    V165,
+    V166,
+    V167,
+    V168,
+    V169,
+    V170,
+    V171,
+    V172,
+    V173,
+    V174,
+    V175,
+    V176,
+    V177,
+    V178,
+    V179,
+    V180,
+    V181,
+    V182,
+    V183,
+    V184,
+    V185,
+    V186,
+    V187,
+    V188,
+    V189,
+    V190,
+    V191,
+    V192,
+    V193,
+    V194,
+    V195,
+    V196,
+    V197,
+    V198,
+    V199,
+    V200,
+    V201,
+    V202,
+    V203,
+    V204,
+    V205,
+    V206,
+    V207,
+    V208,
+    V209,
+    V210,
+    V211,
+    V212,
+    V213,
+    V214,
+    V215,
+    V216,
+    V217,
+    V218,
+    V219,
+    V220,
+    V221,
+    V222,
+    V223,
+    V224,
+    V225,
+    V226,
+    V227,
+    V228,
+    V229,
+    V230,
+    V231,
+    V232,
+    V233,
+    V234,
+    V235,
+    V236,
+    V237,
+    V238,
+    V239,
+    V240,
+    V241,
+    V242,
+    V243,
+    V244,
+    V245,
+    V246,
+    V247,
+    V248,
+    V249,
+    V250,
+    V251,
+    V252,
+    V253,
+    V254,
+    V255,
+}
+
+fn check_tagged<T>(value: T, legacy: &[u8], big_endian: &[u8], standard: &[u8])
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+{
+    let legacy_config = bincode::config::legacy();
+    let big_endian_config = bincode::config::legacy().with_big_endian();
+    let standard_config = bincode::config::standard();
+
+    assert_eq!(
+        bincode::encode_to_vec(&value, legacy_config).unwrap(),
+        legacy
+    );
+    assert_eq!(
+        bincode::encode_to_vec(&value, big_endian_config).unwrap(),
+        big_endian
+    );
+    assert_eq!(
+        bincode::encode_to_vec(&value, standard_config).unwrap(),
+        standard
+    );
+
+    let (decoded, len): (T, usize) = bincode::decode_from_slice(legacy, legacy_config).unwrap();
+    assert_eq!((decoded, len), (value, legacy.len()));
+    let (decoded, _): (T, usize) =
+        bincode::decode_from_slice(big_endian, big_endian_config).unwrap();
+    let (decoded_standard, _): (T, usize) =
+        bincode::decode_from_slice(standard, standard_config).unwrap();
+    assert_eq!(decoded, decoded_standard);
+}
+
+#[test]
+fn test_tag_type() {
+    // the tag has the same width with every config
+    check_tagged(ByteTagged::Pop, &[1], &[1], &[1]);
+    check_tagged(
+        ByteTagged::Push(5),
+        &[0, 5, 0, 0, 0],
+        &[0, 0, 0, 0, 5],
+        &[0, 5],
+    );
+    check_tagged(U16Tagged::B(7), &[1, 0, 7], &[0, 1, 7], &[1, 0, 7]);
+    check_tagged(
+        U32Tagged::B(7),
+        &[1, 0, 0, 0, 7],
+        &[0, 0, 0, 1, 7],
+        &[1, 0, 0, 0, 7],
+    );
+    check_tagged(VarintTagged::B(7), &[1, 7], &[1, 7], &[1, 7]);
+    check_tagged(FullByteTagged::V255, &[255], &[255], &[255]);
+
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(BorrowedTagged::Name("ab"), config).unwrap();
+    assert_eq!(encoded, [1, 2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+    let (decoded, _): (BorrowedTagged, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, BorrowedTagged::Name("ab"));
+}
+
+#[test]
+fn test_tag_type_unexpected_variant() {
+    let config = bincode::config::legacy();
+    let err = bincode::decode_from_slice::<ByteTagged, _>(&[2], config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedVariant {
+            found: 2,
+            type_name: "ByteTagged",
+            allowed: &bincode::error::AllowedEnumVariants::Named(&[("Push", 0), ("Pop", 1)]),
+        }
+    ));
+
+    let err = bincode::decode_from_slice::<U16Tagged, _>(&[0, 1], config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedVariant { found: 256, .. }
+    ));
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Message {
+    Ping,
+    Pong(u8),
+    Data { payload: Vec<u8> },
+}
+
+#[test]
+fn test_unexpected_variant_display() {
+    let config = bincode::config::standard();
+    let err = bincode::decode_from_slice::<Message, _>(&[7], config)
+        .unwrap_err()
+        .into_inner();
+    assert_eq!(
+        err.to_string(),
+        "unexpected variant of Message: expected one of Ping(0), Pong(1), Data(2), found 7"
+    );
+
+    // the tags of an enum with explicit discriminants are still their positions
+    let err = bincode::decode_from_slice::<CStyleEnum, _>(&[5], config)
+        .unwrap_err()
+        .into_inner();
+    assert_eq!(
+        err.to_string(),
+        "unexpected variant of CStyleEnum: expected one of A(0), B(1), C(2), D(3), E(4), found 5"
+    );
+
+    let err = bincode::decode_from_slice::<ByteTagged, _>(&[2], config).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected variant of ByteTagged: expected one of Push(0), Pop(1), found 2"
+    );
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum HashTagged<'a> {
+    #[bincode(discriminant = 0x9E37_79B9_7F4A_7C15)]
+    Ping,
+    // continues from the tag before it
+    Pong(u8),
+    #[bincode(discriminant = 3)]
+    Name(&'a str),
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum SparseTagged {
+    Start,
+    #[bincode(discriminant = 1000)]
+    Middle(u16),
+    End,
+    #[bincode(discriminant = 0xFFFF_FFFFu64)]
+    Last,
+}
+
+#[test]
+fn test_u64_discriminants() {
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(HashTagged::Ping, config).unwrap();
+    assert_eq!(encoded, 0x9E37_79B9_7F4A_7C15u64.to_le_bytes());
+    let encoded = bincode::encode_to_vec(HashTagged::Pong(7), config).unwrap();
+    assert_eq!(encoded, [0x16, 0x7C, 0x4A, 0x7F, 0xB9, 0x79, 0x37, 0x9E, 7]);
+    // every tag is a `u64` once one of them does not fit in a `u32`
+    let encoded = bincode::encode_to_vec(HashTagged::Name("a"), config).unwrap();
+    assert_eq!(
+        encoded,
+        [3, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'a']
+    );
+
+    fn roundtrip(config: impl bincode::config::Config) {
+        for value in [HashTagged::Ping, HashTagged::Pong(7), HashTagged::Name("a")] {
+            let encoded = bincode::encode_to_vec(&value, config).unwrap();
+            let (decoded, len): (HashTagged<'_>, usize) =
+                bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+            assert_eq!((decoded, len), (value, encoded.len()));
+        }
+    }
+    roundtrip(bincode::config::standard());
+    roundtrip(bincode::config::legacy().with_big_endian());
+
+    // with varint encoding, the tag is only as long as it needs to be
+    let encoded =
+        bincode::encode_to_vec(HashTagged::Name("a"), bincode::config::standard()).unwrap();
+    assert_eq!(encoded, [3, 1, b'a']);
+}
+
+#[test]
+fn test_u32_discriminants() {
+    // tags that fit in a `u32` are still encoded as a `u32`
+    let config = bincode::config::legacy();
+    for (value, tag) in [
+        (SparseTagged::Start, 0u32),
+        (SparseTagged::End, 1001),
+        (SparseTagged::Last, u32::MAX),
+    ] {
+        let encoded = bincode::encode_to_vec(&value, config).unwrap();
+        assert_eq!(encoded, tag.to_le_bytes());
+        let (decoded, _): (SparseTagged, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+    let encoded = bincode::encode_to_vec(SparseTagged::Middle(5), config).unwrap();
+    assert_eq!(encoded, [0xE8, 3, 0, 0, 5, 0]);
+
+    let err = bincode::decode_from_slice::<SparseTagged, _>(&[1, 0, 0, 0], config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedVariant {
+            found: 1,
+            type_name: "SparseTagged",
+            allowed: &bincode::error::AllowedEnumVariants::Named(&[
+                ("Start", 0),
+                ("Middle", 1000),
+                ("End", 1001),
+                ("Last", 0xFFFF_FFFF),
+            ]),
+        }
+    ));
+}
+
+#[test]
+fn test_u64_unexpected_variant() {
+    let config = bincode::config::legacy();
+    let found = u64::from(u32::MAX) + 5;
+    let err = bincode::borrow_decode_from_slice::<HashTagged<'_>, _>(&found.to_le_bytes(), config)
+        .unwrap_err();
+    let err = err.into_inner();
+    assert!(matches!(
+        err,
+        DecodeError::UnexpectedVariant {
+            found: 0x1_0000_0004,
+            type_name: "HashTagged",
+            allowed: &bincode::error::AllowedEnumVariants::Named(&[
+                ("Ping", 0x9E37_79B9_7F4A_7C15),
+                ("Pong", 0x9E37_79B9_7F4A_7C16),
+                ("Name", 3),
+            ]),
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "unexpected variant of HashTagged: expected one of Ping(11400714819323198485), Pong(11400714819323198486), Name(3), found 4294967300"
+    );
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+enum ByteDiscriminants {
+    #[bincode(discriminant = 0x10)]
+    A,
+    B,
+    #[bincode(discriminant = 0xFF)]
+    C,
+}
+
+#[test]
+fn test_tag_type_discriminants() {
+    let config = bincode::config::standard();
+    for (value, tag) in [
+        (ByteDiscriminants::A, 0x10u8),
+        (ByteDiscriminants::B, 0x11),
+        (ByteDiscriminants::C, 0xFF),
+    ] {
+        let encoded = bincode::encode_to_vec(&value, config).unwrap();
+        assert_eq!(encoded, [tag]);
+        let (decoded, _): (ByteDiscriminants, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+}
@@ -0,0 +1,318 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+use std::marker::PhantomData;
+
+/// A type that implements none of the traits
+#[derive(Debug, PartialEq)]
+struct Foreign;
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Id<T> {
+    raw: u64,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Encode, BorrowDecode, Debug, PartialEq)]
+struct Tagged<'a, T, U> {
+    name: &'a str,
+    _marker: core::marker::PhantomData<(T, U)>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Reference<T> {
+    Id(Id<T>),
+    Name(String),
+}
+
+/// Only encodes the amount of values it holds, so it does not need `T: Encode`
+#[derive(Debug, PartialEq)]
+struct Counter<T>(Vec<T>);
+
+impl<T> Encode for Counter<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.len().encode(encoder)
+    }
+}
+
+impl<T, Context> Decode<Context> for Counter<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = usize::decode(decoder)?;
+        if len == 0 {
+            Ok(Self(Vec::new()))
+        } else {
+            Err(DecodeError::Other("only empty counters can be decoded"))
+        }
+    }
+}
+
+impl<'de, T, Context> BorrowDecode<'de, Context> for Counter<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Wrapped<T> {
+    counter: Counter<T>,
+    id: Id<T>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Tree<T> {
+    Leaf(T),
+    Node(Box<Tree<T>>, Box<Tree<T>>),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct List<T> {
+    value: T,
+    next: Option<Box<Self>>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Node<T> {
+    value: T,
+    next: Option<Box<Node<T>>>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Graph<T> {
+    nodes: Vec<Node<T>>,
+    edges: Vec<(usize, usize)>,
+}
+
+/// `Expr` and `Block` contain each other, so their bounds can not require each other
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Expr<T> {
+    Value(T),
+    Block(Block<T>),
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Block<T> {
+    exprs: Vec<Expr<T>>,
+    last: Option<Box<Expr<T>>>,
+}
+
+#[derive(Encode, BorrowDecode, Debug, PartialEq)]
+enum Doc<'a, T> {
+    Text(&'a str),
+    Section(Section<'a, T>),
+}
+
+#[derive(Encode, BorrowDecode, Debug, PartialEq)]
+struct Section<'a, T> {
+    title: &'a str,
+    meta: T,
+    children: Vec<Doc<'a, T>>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Defaulted<Bar = ()> {
+    x: Bar,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Pair<D, E> {
+    data: (D, E),
+}
+
+/// The bounds can still be written by hand
+#[derive(Encode, Decode, Debug, PartialEq)]
+#[bincode(
+    encode_bounds = "T: Encode + Clone",
+    decode_bounds = "T: Decode<__Context> + Clone"
+)]
+struct Explicit<T> {
+    value: T,
+}
+
+/// Only implements `Encode`, like a value that is sent but never received
+#[derive(Debug, PartialEq)]
+struct Outgoing(u8);
+
+impl Encode for Outgoing {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+/// Only implements `Decode`, like a value that is received but never sent
+#[derive(Debug, PartialEq)]
+struct Incoming(u8);
+
+impl<Context> Decode<Context> for Incoming {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(Self)
+    }
+}
+
+impl<'de, Context> BorrowDecode<'de, Context> for Incoming {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+#[derive(Encode)]
+#[bincode(encode_only)]
+struct Request<T> {
+    id: u32,
+    body: Vec<T>,
+}
+
+#[derive(Decode, Debug, PartialEq)]
+#[bincode(decode_only)]
+enum Response<T> {
+    Ok(T),
+    Err(String),
+}
+
+fn round_trip<T: Encode + Decode<()> + PartialEq + core::fmt::Debug>(value: &T) {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(value, config).unwrap();
+    let (decoded, len): (T, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((&decoded, len), (value, encoded.len()));
+}
+
+#[test]
+fn test_phantom_data() {
+    let id = Id::<Foreign> {
+        raw: 7,
+        _marker: PhantomData,
+    };
+    round_trip(&id);
+    round_trip(&Reference::<Foreign>::Id(id));
+    round_trip(&Reference::<Foreign>::Name(String::from("name")));
+
+    let tagged = Tagged::<Foreign, fn()> {
+        name: "tagged",
+        _marker: PhantomData,
+    };
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&tagged, config).unwrap();
+    let (decoded, _): (Tagged<Foreign, fn()>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, tagged);
+}
+
+#[test]
+fn test_wrapper() {
+    round_trip(&Wrapped::<Foreign> {
+        counter: Counter(Vec::new()),
+        id: Id {
+            raw: 1,
+            _marker: PhantomData,
+        },
+    });
+
+    let encoded = bincode::encode_to_vec(
+        Wrapped::<Foreign> {
+            counter: Counter(vec![Foreign, Foreign]),
+            id: Id {
+                raw: 1,
+                _marker: PhantomData,
+            },
+        },
+        bincode::config::standard(),
+    )
+    .unwrap();
+    assert_eq!(encoded, [2, 1]);
+}
+
+#[test]
+fn test_recursive() {
+    round_trip(&Tree::Node(
+        Box::new(Tree::Leaf(1u8)),
+        Box::new(Tree::Node(Box::new(Tree::Leaf(2)), Box::new(Tree::Leaf(3)))),
+    ));
+    round_trip(&List {
+        value: String::from("a"),
+        next: Some(Box::new(List {
+            value: String::from("b"),
+            next: None,
+        })),
+    });
+    round_trip(&Defaulted { x: () });
+    round_trip(&Defaulted { x: 5u16 });
+    round_trip(&Pair {
+        data: (1u8, String::from("e")),
+    });
+    round_trip(&Explicit { value: 3u32 });
+}
+
+#[test]
+fn test_mutually_recursive() {
+    round_trip(&Graph {
+        nodes: vec![
+            Node {
+                value: 1u8,
+                next: Some(Box::new(Node {
+                    value: 2,
+                    next: None,
+                })),
+            },
+            Node {
+                value: 3,
+                next: None,
+            },
+        ],
+        edges: vec![(0, 1)],
+    });
+    round_trip(&Expr::Block(Block {
+        exprs: vec![
+            Expr::Value(String::from("a")),
+            Expr::Block(Block {
+                exprs: Vec::new(),
+                last: Some(Box::new(Expr::Value(String::from("b")))),
+            }),
+        ],
+        last: None,
+    }));
+
+    let doc = Doc::Section(Section {
+        title: "title",
+        meta: 7u32,
+        children: vec![
+            Doc::Text("text"),
+            Doc::Section(Section {
+                title: "nested",
+                meta: 8,
+                children: Vec::new(),
+            }),
+        ],
+    });
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&doc, config).unwrap();
+    let (decoded, _): (Doc<u32>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, doc);
+}
+
+#[test]
+fn test_encode_only_and_decode_only() {
+    let config = bincode::config::standard();
+    let request = Request {
+        id: 3,
+        body: vec![Outgoing(1), Outgoing(2)],
+    };
+    let encoded = bincode::encode_to_vec(&request, config).unwrap();
+    assert_eq!(encoded, [3, 2, 1, 2]);
+
+    let encoded = bincode::encode_to_vec((0u32, 9u8), config).unwrap();
+    let (decoded, _): (Response<Incoming>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Response::Ok(Incoming(9)));
+    let (decoded, _): (Response<Incoming>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Response::Ok(Incoming(9)));
+}
@@ -0,0 +1,236 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::de::read::{Reader, SliceReader};
+use bincode::de::{BorrowDecode, DecoderImpl};
+use bincode::digest::{
+    decode_and_verify_digest, encode_and_digest, DigestReader, DigestWriter, Finalize, Update,
+};
+use bincode::error::DecodeError;
+use bincode::IoWriter;
+use std::io::{Read, Seek};
+
+/// FNV-1a, as a simple hash function for the tests
+#[derive(Clone)]
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Update for Fnv {
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl Finalize for Fnv {
+    type Output = u64;
+
+    fn finalize(self) -> u64 {
+        self.0
+    }
+}
+
+fn fnv(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv::default();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct Transfer<'a> {
+    from: &'a str,
+    to: &'a str,
+    amount: u64,
+    memo: Option<&'a [u8]>,
+}
+
+fn transfer() -> Transfer<'static> {
+    Transfer {
+        from: "ann",
+        to: "bob",
+        amount: 1_000_000,
+        memo: Some(b"rent"),
+    }
+}
+
+#[test]
+fn test_digest_of_encoded_bytes() {
+    fn check(config: impl Config) {
+        let mut message = Vec::new();
+        let digest = encode_and_digest(
+            transfer(),
+            IoWriter::new(&mut message),
+            config,
+            Fnv::default(),
+        )
+        .unwrap();
+        assert_eq!(message, bincode::encode_to_vec(transfer(), config).unwrap());
+        assert_eq!(digest, fnv(&message));
+
+        let mut written = Vec::new();
+        let mut writer = DigestWriter::new(IoWriter::new(&mut written), Fnv::default());
+        bincode::encode_into_writer(transfer(), &mut writer, config).unwrap();
+        assert_eq!(writer.hasher().0, digest);
+        let (_, hasher) = writer.into_parts();
+        assert_eq!(hasher.finalize(), digest);
+        assert_eq!(written, message);
+    }
+    check(config::standard());
+    check(config::legacy());
+    check(config::standard().with_big_endian());
+}
+
+#[test]
+fn test_streaming() {
+    let config = config::standard();
+    let mut file = tempfile::tempfile().unwrap();
+    let mut hasher = Fnv::default();
+    // the hasher is borrowed, so the digest covers both values
+    let mut writer = DigestWriter::new(IoWriter::new(&mut file), &mut hasher);
+    bincode::encode_into_writer(transfer(), &mut writer, config).unwrap();
+    bincode::encode_into_writer(vec![7u32; 500], &mut writer, config).unwrap();
+
+    let mut written = Vec::new();
+    file.rewind().unwrap();
+    file.read_to_end(&mut written).unwrap();
+    assert_eq!(hasher.finalize(), fnv(&written));
+
+    // a length prefix collects its section in a `Vec` first
+    let mut written = Vec::new();
+    let mut writer = DigestWriter::new(IoWriter::new(&mut written), Fnv::default());
+    bincode::enc::Encoder::with_length_prefix(
+        &mut bincode::enc::EncoderImpl::new(&mut writer, config),
+        |encoder| bincode::Encode::encode(&transfer(), encoder),
+    )
+    .unwrap();
+    let (_, hasher) = writer.into_parts();
+    assert_eq!(hasher.finalize(), fnv(&written));
+}
+
+#[test]
+fn test_verify() {
+    let config = config::standard();
+    let message = bincode::encode_to_vec((transfer(), 5u8), config).unwrap();
+    let digest = fnv(&message);
+    let (decoded, n): (Transfer, u8) = bincode::borrow_decode_from_slice(&message, config)
+        .unwrap()
+        .0;
+    assert_eq!((decoded, n), (transfer(), 5));
+
+    let mut reader = SliceReader::new(&message);
+    let decoded: (String, String, u64, Option<Vec<u8>>, u8) =
+        decode_and_verify_digest(&mut reader, config, Fnv::default(), &digest).unwrap();
+    assert_eq!(decoded.2, 1_000_000);
+    assert_eq!(reader.remaining_hint(), Some(0));
+
+    // borrowed bytes are hashed as well
+    let mut reader = DigestReader::new(SliceReader::new(&message), Fnv::default());
+    let mut decoder = DecoderImpl::new(&mut reader, config, ());
+    let decoded = <(Transfer, u8)>::borrow_decode(&mut decoder).unwrap();
+    assert_eq!(decoded, (transfer(), 5));
+    assert_eq!(reader.into_parts().1.finalize(), digest);
+
+    // only the bytes that were decoded are hashed
+    let mut reader = SliceReader::new(&message);
+    let from: String =
+        decode_and_verify_digest(&mut reader, config, Fnv::default(), &fnv(&message[..4])).unwrap();
+    assert_eq!(from, "ann");
+    assert_eq!(reader.remaining_hint(), Some(message.len() - 4));
+}
+
+#[test]
+fn test_tampered() {
+    let config = config::standard();
+    let message = bincode::encode_to_vec(transfer(), config).unwrap();
+    let digest = fnv(&message);
+
+    // every changed byte that still decodes is caught
+    for index in 0..message.len() {
+        let mut tampered = message.clone();
+        tampered[index] ^= 1;
+        let result = decode_and_verify_digest::<(String, String, u64, Option<Vec<u8>>), _, _, _>(
+            SliceReader::new(&tampered),
+            config,
+            Fnv::default(),
+            &digest,
+        );
+        assert!(result.is_err(), "byte {index}");
+    }
+    let mut tampered = message.clone();
+    *tampered.last_mut().unwrap() = b'x';
+    let err = decode_and_verify_digest::<(String, String, u64, Option<Vec<u8>>), _, _, _>(
+        SliceReader::new(&tampered),
+        config,
+        Fnv::default(),
+        &digest,
+    )
+    .unwrap_err();
+    assert!(matches!(err, DecodeError::DigestMismatch), "{err:?}");
+
+    // invalid input fails before the digest is checked
+    let err = decode_and_verify_digest::<(String, String, u64, Option<Vec<u8>>), _, _, _>(
+        SliceReader::new(&message[..message.len() - 1]),
+        config,
+        Fnv::default(),
+        &digest,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest_crate() {
+    use bincode::digest::DigestAdapter;
+
+    /// FNV-1a behind the traits of the `digest` crate
+    #[derive(Default)]
+    struct FnvDigest(Fnv);
+
+    impl digest::OutputSizeUser for FnvDigest {
+        type OutputSize = digest::typenum::U8;
+    }
+
+    impl digest::Update for FnvDigest {
+        fn update(&mut self, data: &[u8]) {
+            self.0.update(data);
+        }
+    }
+
+    impl digest::FixedOutput for FnvDigest {
+        fn finalize_into(self, out: &mut digest::Output<Self>) {
+            out.copy_from_slice(&self.0.finalize().to_be_bytes());
+        }
+    }
+
+    let config = config::standard();
+    let mut message = Vec::new();
+    let digest = encode_and_digest(
+        transfer(),
+        IoWriter::new(&mut message),
+        config,
+        DigestAdapter(FnvDigest::default()),
+    )
+    .unwrap();
+    assert_eq!(digest.as_slice(), fnv(&message).to_be_bytes());
+
+    let decoded: (String, String, u64, Option<Vec<u8>>) = decode_and_verify_digest(
+        SliceReader::new(&message),
+        config,
+        DigestAdapter(FnvDigest::default()),
+        &digest,
+    )
+    .unwrap();
+    assert_eq!(decoded.0, "ann");
+}
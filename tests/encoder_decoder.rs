@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+use bincode::enc::{write::SliceWriter, Encode, EncoderImpl};
+use bincode::error::DecodeError;
+
+#[derive(Debug, PartialEq)]
+enum Body {
+    Text(String),
+    Numbers(Vec<u32>),
+}
+
+fn encode_session(buffer: &mut [u8]) -> usize {
+    let config = bincode::config::standard();
+    let mut encoder = EncoderImpl::new(SliceWriter::new(buffer), config);
+    1u8.encode(&mut encoder).unwrap();
+    "text".encode(&mut encoder).unwrap();
+    vec![1u32, 2, 3].encode(&mut encoder).unwrap();
+    encoder.into_writer().bytes_written()
+}
+
+fn decode_body<D: Decoder<Context = ()>>(kind: u8, decoder: &mut D) -> Result<Body, DecodeError> {
+    match kind {
+        0 => Ok(Body::Text(String::decode(decoder)?)),
+        _ => Ok(Body::Numbers(Vec::decode(decoder)?)),
+    }
+}
+
+#[test]
+fn test_one_encoder_for_several_values() {
+    let mut buffer = [0u8; 32];
+    let len = encode_session(&mut buffer);
+    let expected =
+        bincode::encode_to_vec((1u8, "text", vec![1u32, 2, 3]), bincode::config::standard())
+            .unwrap();
+    assert_eq!(buffer[..len], expected);
+}
+
+#[test]
+fn test_one_decoder_for_several_values() {
+    let mut buffer = [0u8; 32];
+    let len = encode_session(&mut buffer);
+
+    let config = bincode::config::standard();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&buffer[..len]), config, ());
+    let kind = u8::decode(&mut decoder).unwrap();
+    let text = decode_body(0, &mut decoder).unwrap();
+    let numbers = decode_body(kind, &mut decoder).unwrap();
+    assert_eq!(text, Body::Text(String::from("text")));
+    assert_eq!(numbers, Body::Numbers(vec![1, 2, 3]));
+    assert_eq!(decoder.bytes_claimed(), 0);
+    assert!(decoder.into_reader().remaining().is_empty());
+}
+
+#[test]
+fn test_limit_is_shared_between_values() {
+    let mut buffer = [0u8; 32];
+    let len = encode_session(&mut buffer);
+    let bytes = &buffer[..len];
+
+    // every value fits in the limit on its own
+    let config = bincode::config::standard().with_limit::<24>();
+    let (_, read): (u8, usize) = bincode::decode_from_slice(bytes, config).unwrap();
+    let (_, read_text): (String, usize) =
+        bincode::decode_from_slice(&bytes[read..], config).unwrap();
+    let (_, _): (Vec<u32>, usize) =
+        bincode::decode_from_slice(&bytes[read + read_text..], config).unwrap();
+
+    // but not all of them together
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config, ());
+    u8::decode(&mut decoder).unwrap();
+    assert_eq!(decoder.limit_remaining(), Some(23));
+    String::decode(&mut decoder).unwrap();
+    assert_eq!(decoder.limit_remaining(), Some(11));
+    assert!(matches!(
+        Vec::<u32>::decode(&mut decoder).unwrap_err().into_inner(),
+        DecodeError::LimitExceeded
+    ));
+
+    let config = bincode::config::standard().with_limit::<33>();
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config, ());
+    u8::decode(&mut decoder).unwrap();
+    String::decode(&mut decoder).unwrap();
+    assert_eq!(Vec::<u32>::decode(&mut decoder).unwrap(), [1, 2, 3]);
+    assert_eq!(decoder.limit_remaining(), Some(0));
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "derive")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::Config;
+use std::borrow::Cow;
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Mixed {
+    default: u32,
+    #[bincode(endian = "big")]
+    big: u32,
+    #[bincode(endian = "little")]
+    little: i16,
+    #[bincode(endian = "big")]
+    float: f64,
+    #[bincode(endian = "big")]
+    matrix: [[u16; 2]; 2],
+    tail: u16,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[bincode(endian = "big")]
+    id: u64,
+    text: Cow<'a, str>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Message {
+    Ping(#[bincode(endian = "big")] u16),
+    Move {
+        #[bincode(endian = "little")]
+        x: i32,
+        y: i32,
+    },
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum BorrowedMessage<'a> {
+    Text(&'a str, #[bincode(endian = "big")] u32),
+}
+
+fn mixed() -> Mixed {
+    Mixed {
+        default: 0x0102_0304,
+        big: 0x0506_0708,
+        little: -2,
+        float: 1.5,
+        matrix: [[0x0910, 0x1112], [0x1314, 0x1516]],
+        tail: 0x1718,
+    }
+}
+
+fn round_trip<T>(value: &T, config: impl Config) -> Vec<u8>
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + std::fmt::Debug,
+{
+    let encoded = bincode::encode_to_vec(value, config).unwrap();
+    let (decoded, len): (T, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(&decoded, value);
+    assert_eq!(len, encoded.len());
+    encoded
+}
+
+#[test]
+fn test_endian_fields_fixint() {
+    let little = round_trip(&mixed(), bincode::config::legacy());
+    let big = round_trip(&mixed(), bincode::config::legacy().with_big_endian());
+
+    let mut expected = Vec::new();
+    expected.extend(0x0102_0304u32.to_le_bytes());
+    expected.extend(0x0506_0708u32.to_be_bytes());
+    expected.extend((-2i16).to_le_bytes());
+    expected.extend(1.5f64.to_be_bytes());
+    for value in [0x0910u16, 0x1112, 0x1314, 0x1516] {
+        expected.extend(value.to_be_bytes());
+    }
+    expected.extend(0x1718u16.to_le_bytes());
+    assert_eq!(little, expected);
+
+    // only the fields without an override follow the config
+    let mut expected = Vec::new();
+    expected.extend(0x0102_0304u32.to_be_bytes());
+    expected.extend(0x0506_0708u32.to_be_bytes());
+    expected.extend((-2i16).to_le_bytes());
+    expected.extend(1.5f64.to_be_bytes());
+    for value in [0x0910u16, 0x1112, 0x1314, 0x1516] {
+        expected.extend(value.to_be_bytes());
+    }
+    expected.extend(0x1718u16.to_be_bytes());
+    assert_eq!(big, expected);
+}
+
+#[test]
+fn test_endian_fields_varint() {
+    // varints of more than a byte are written in the endianness as well
+    let value = Message::Ping(300);
+    assert_eq!(
+        round_trip(&value, bincode::config::standard()),
+        [0, 251, 0x01, 0x2C]
+    );
+    assert_eq!(
+        round_trip(&value, bincode::config::standard().with_big_endian()),
+        [0, 251, 0x01, 0x2C]
+    );
+
+    let value = Message::Move { x: 1000, y: 1000 };
+    assert_eq!(
+        round_trip(&value, bincode::config::standard()),
+        [1, 251, 0xD0, 0x07, 251, 0xD0, 0x07]
+    );
+    assert_eq!(
+        round_trip(&value, bincode::config::standard().with_big_endian()),
+        [1, 251, 0xD0, 0x07, 251, 0x07, 0xD0]
+    );
+}
+
+#[test]
+fn test_endian_fields_borrowed() {
+    for big_endian in [false, true] {
+        let value = Borrowed {
+            name: "name",
+            id: 0x0102_0304_0506_0708,
+            text: Cow::Borrowed("text"),
+        };
+        let config = bincode::config::legacy();
+        let encoded = if big_endian {
+            bincode::encode_to_vec(&value, config.with_big_endian()).unwrap()
+        } else {
+            bincode::encode_to_vec(&value, config).unwrap()
+        };
+        assert_eq!(encoded[12..20], 0x0102_0304_0506_0708u64.to_be_bytes());
+        let decoded: Borrowed = if big_endian {
+            bincode::borrow_decode_from_slice(&encoded, config.with_big_endian())
+                .unwrap()
+                .0
+        } else {
+            bincode::borrow_decode_from_slice(&encoded, config)
+                .unwrap()
+                .0
+        };
+        assert_eq!(decoded, value);
+    }
+
+    let value = BorrowedMessage::Text("hi", 7);
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded[14..], [0, 0, 0, 7]);
+    let (decoded, _): (BorrowedMessage, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_endian_fields_limit() {
+    let encoded = bincode::encode_to_vec(mixed(), bincode::config::legacy()).unwrap();
+    // the limit is exceeded by `float`, which is read with the endianness of its field
+    let config = bincode::config::legacy().with_limit::<12>();
+    let err = bincode::decode_from_slice::<Mixed, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        bincode::error::DecodeError::LimitExceeded
+    ));
+}
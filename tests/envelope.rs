@@ -0,0 +1,149 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::de::read::SliceReader;
+use bincode::envelope::{decode_file, encode_file, read_header, write_header, HEADER_SIZE};
+use bincode::error::DecodeError;
+use bincode::{Decode, Encode, IoWriter};
+
+const MAGIC: [u8; 4] = *b"MAPS";
+
+/// The first version of the format
+#[derive(Encode, Decode)]
+struct MapV1 {
+    name: String,
+    tiles: Vec<u8>,
+}
+
+/// The current version, which added the size of the map
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Map {
+    name: String,
+    width: u32,
+    tiles: Vec<u8>,
+}
+
+impl From<MapV1> for Map {
+    fn from(map: MapV1) -> Self {
+        Self {
+            name: map.name,
+            width: map.tiles.len() as u32,
+            tiles: map.tiles,
+        }
+    }
+}
+
+fn decode_map<C: Config>(bytes: &[u8], config: C) -> Result<Map, DecodeError> {
+    decode_file(
+        SliceReader::new(bytes),
+        MAGIC,
+        config,
+        |version, decoder| match version {
+            1 => MapV1::decode(decoder).map(Into::into),
+            2 => Map::decode(decoder),
+            _ => Err(DecodeError::UnsupportedVersion { version }),
+        },
+    )
+}
+
+fn encode<T: Encode, C: Config>(value: &T, version: u16, config: C) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    encode_file(value, IoWriter::new(&mut bytes), MAGIC, version, config).unwrap();
+    bytes
+}
+
+#[test]
+fn test_header_round_trip() {
+    let mut bytes = Vec::new();
+    write_header(IoWriter::new(&mut bytes), MAGIC, 0x0102).unwrap();
+    assert_eq!(bytes, b"MAPS\x02\x01");
+    assert_eq!(bytes.len(), HEADER_SIZE);
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(read_header(&mut reader, MAGIC).unwrap(), 0x0102);
+    assert_eq!(reader.position(), HEADER_SIZE);
+}
+
+fn check_round_trip<C: Config>(config: C) {
+    let map = Map {
+        name: "harbor".into(),
+        width: 3,
+        tiles: vec![1, 2, 3, 4, 5, 6],
+    };
+    let bytes = encode(&map, 2, config);
+    // the header does not depend on the config
+    assert_eq!(bytes[..HEADER_SIZE], *b"MAPS\x02\x00");
+    assert_eq!(
+        bytes[HEADER_SIZE..],
+        bincode::encode_to_vec(&map, config).unwrap()
+    );
+    assert_eq!(decode_map(&bytes, config).unwrap(), map);
+}
+
+#[test]
+fn test_round_trip() {
+    check_round_trip(config::standard());
+    check_round_trip(config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_migration() {
+    let config = config::standard();
+    let old = MapV1 {
+        name: "cave".into(),
+        tiles: vec![9; 4],
+    };
+    let bytes = encode(&old, 1, config);
+    assert_eq!(
+        decode_map(&bytes, config).unwrap(),
+        Map {
+            name: "cave".into(),
+            width: 4,
+            tiles: vec![9; 4],
+        }
+    );
+}
+
+#[test]
+fn test_wrong_magic() {
+    let config = config::standard();
+    let mut bytes = encode(&"not a map", 2, config);
+    bytes[..4].copy_from_slice(b"PNG\0");
+    let err = decode_map(&bytes, config).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DecodeError::WrongMagic {
+                expected: MAGIC,
+                found: [b'P', b'N', b'G', 0]
+            }
+        ),
+        "{err:?}"
+    );
+    assert_eq!(
+        err.to_string(),
+        "not a file of the expected format: expected magic bytes b\"MAPS\", found b\"PNG\\x00\""
+    );
+
+    // a file that is shorter than the magic
+    let err = read_header(SliceReader::new(b"MAP"), MAGIC).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { .. }), "{err:?}");
+    assert!(matches!(
+        read_header(SliceReader::new(b"XAPS"), MAGIC),
+        Err(DecodeError::WrongMagic { .. })
+    ));
+}
+
+#[test]
+fn test_future_version() {
+    let config = config::standard();
+    let bytes = encode(&"from the future", 3, config);
+    let err = decode_map(&bytes, config).unwrap_err();
+    assert!(
+        matches!(err, DecodeError::UnsupportedVersion { version: 3 }),
+        "{err:?}"
+    );
+    assert_eq!(err.to_string(), "unsupported format version 3");
+}
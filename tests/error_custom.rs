@@ -0,0 +1,128 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::{Decode, Decoder};
+use bincode::enc::{Encode, Encoder};
+use bincode::error::{DecodeError, EncodeError};
+use std::error::Error;
+use std::fmt;
+
+/// A version number that only accepts the versions this crate knows about
+#[derive(Debug, PartialEq)]
+struct Version(u32);
+
+#[derive(Debug, PartialEq)]
+struct UnsupportedVersion {
+    found: u32,
+    supported: std::ops::RangeInclusive<u32>,
+}
+
+impl fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "version {} is not supported, expected {} to {}",
+            self.found,
+            self.supported.start(),
+            self.supported.end()
+        )
+    }
+}
+
+impl Error for UnsupportedVersion {}
+
+const SUPPORTED: std::ops::RangeInclusive<u32> = 1..=3;
+
+impl Encode for Version {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if !SUPPORTED.contains(&self.0) {
+            return Err(EncodeError::custom(UnsupportedVersion {
+                found: self.0,
+                supported: SUPPORTED,
+            }));
+        }
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for Version {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let version = u32::decode(decoder)?;
+        if !SUPPORTED.contains(&version) {
+            return Err(DecodeError::custom(UnsupportedVersion {
+                found: version,
+                supported: SUPPORTED,
+            }));
+        }
+        Ok(Self(version))
+    }
+}
+bincode::impl_borrow_decode!(Version);
+
+#[test]
+fn test_custom_decode_error() {
+    let config = bincode::config::standard();
+    let (version, _): (Version, usize) = bincode::decode_from_slice(&[2], config).unwrap();
+    assert_eq!(version, Version(2));
+
+    let err = bincode::decode_from_slice::<Version, _>(&[7], config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Custom(_)));
+    assert_eq!(
+        err.to_string(),
+        "version 7 is not supported, expected 1 to 3"
+    );
+    assert_eq!(
+        err.inner().to_string(),
+        "version 7 is not supported, expected 1 to 3"
+    );
+
+    // the source is the original error
+    let source = err.source().unwrap();
+    assert_eq!(
+        source.downcast_ref::<UnsupportedVersion>(),
+        Some(&UnsupportedVersion {
+            found: 7,
+            supported: SUPPORTED
+        })
+    );
+
+    // the boxed error can be taken out of the error
+    let DecodeError::Custom(inner) = err.into_inner() else {
+        panic!("expected a custom error");
+    };
+    let inner = inner.downcast::<UnsupportedVersion>().unwrap();
+    assert_eq!(inner.found, 7);
+}
+
+#[test]
+fn test_custom_encode_error() {
+    let config = bincode::config::standard();
+    assert_eq!(bincode::encode_to_vec(Version(3), config).unwrap(), vec![3]);
+
+    let err = bincode::encode_to_vec(Version(4), config).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "version 4 is not supported, expected 1 to 3"
+    );
+    let source = err.source().unwrap();
+    assert_eq!(
+        source.downcast_ref::<UnsupportedVersion>().unwrap().found,
+        4
+    );
+}
+
+#[test]
+fn test_custom_fmt() {
+    let id = 12;
+    let err = DecodeError::custom_fmt(format_args!("unknown id {id}"));
+    assert_eq!(err.to_string(), "unknown id 12");
+    assert_eq!(err.source().unwrap().to_string(), "unknown id 12");
+
+    let err = EncodeError::custom_fmt(format_args!("unknown id {id}"));
+    assert_eq!(err.to_string(), "unknown id 12");
+
+    // messages work as well as error types
+    let err = DecodeError::custom("bad magic");
+    assert_eq!(err.to_string(), "bad magic");
+}
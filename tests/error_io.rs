@@ -0,0 +1,135 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::{DecodeError, EncodeError};
+use std::io::{self, ErrorKind};
+
+/// A writer that fails every write with the given kind
+struct FailingWriter(ErrorKind);
+
+impl io::Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(self.0, "failing writer"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader that fails every read with the given kind
+struct FailingReader(ErrorKind);
+
+impl io::Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(self.0, "failing reader"))
+    }
+}
+
+fn decode_io<D: bincode::Decode<()>>(bytes: &[u8]) -> io::Result<D> {
+    let (value, _) = bincode::decode_from_slice_with_offset(bytes, bincode::config::standard())?;
+    Ok(value)
+}
+
+#[test]
+fn test_decode_error_kind() {
+    let config = bincode::config::standard();
+
+    let err = decode_io::<u32>(&[]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    let err = decode_io::<bool>(&[5]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = decode_io::<String>(&[1, 0xFF]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let err: io::Error =
+        bincode::decode_from_slice::<Vec<u8>, _>(&[100; 200], config.with_limit::<10>())
+            .unwrap_err()
+            .into();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let err: io::Error = bincode::decode_from_std_read::<u32, _, _>(&mut [].as_slice(), config)
+        .unwrap_err()
+        .into();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let err: io::Error = DecodeError::AllocationFailed { requested: 1 }.into();
+    assert_eq!(err.kind(), ErrorKind::OutOfMemory);
+    let err: io::Error = DecodeError::RemainingBytesUnknown.into();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+    // io errors keep their own kind, without being wrapped
+    let err: io::Error = bincode::decode_from_std_read::<u32, _, _>(
+        &mut FailingReader(ErrorKind::ConnectionReset),
+        config,
+    )
+    .unwrap_err()
+    .into();
+    assert_eq!(err.kind(), ErrorKind::ConnectionReset);
+    assert!(err
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<DecodeError>()
+        .is_none());
+    assert_eq!(err.to_string(), "failing reader");
+
+    // also when the io error is wrapped in context
+    let err: io::Error = bincode::decode_from_std_read_with_offset::<(u8, u32), _, _>(
+        &mut io::Read::chain(&[1u8][..], FailingReader(ErrorKind::ConnectionReset)),
+        config,
+    )
+    .unwrap_err()
+    .into();
+    assert_eq!(err.kind(), ErrorKind::ConnectionReset);
+    assert_eq!(err.to_string(), "failing reader");
+}
+
+#[test]
+fn test_encode_error_kind() {
+    let config = bincode::config::standard();
+
+    let err: io::Error = bincode::encode_into_slice(5000u32, &mut [0u8; 1], config)
+        .unwrap_err()
+        .into();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+
+    let err: io::Error =
+        bincode::encode_into_std_write(5u32, &mut FailingWriter(ErrorKind::BrokenPipe), config)
+            .unwrap_err()
+            .into();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(err.to_string(), "failing writer");
+
+    let err: io::Error = EncodeError::custom("not encodable").into();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_io_error_roundtrip() {
+    let err = decode_io::<bool>(&[5]).unwrap_err();
+    assert!(err.get_ref().unwrap().is::<DecodeError>());
+    let err = DecodeError::from_io_error(err);
+    assert_eq!(err.offset(), Some(1));
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::InvalidBooleanValue(5)
+    ));
+
+    let err = DecodeError::from_io_error(io::Error::new(ErrorKind::TimedOut, "timed out"));
+    match err {
+        DecodeError::Io { inner, additional } => {
+            assert_eq!(inner.kind(), ErrorKind::TimedOut);
+            assert_eq!(additional, 0);
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+
+    let err: io::Error = EncodeError::Other("other").into();
+    assert!(matches!(
+        EncodeError::from_io_error(err),
+        EncodeError::Other("other")
+    ));
+    let err = EncodeError::from_io_error(ErrorKind::BrokenPipe.into());
+    assert!(matches!(err, EncodeError::Io { index: 0, .. }));
+}
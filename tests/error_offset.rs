@@ -0,0 +1,251 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Kind {
+    A,
+    B(u16),
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Header {
+    id: u32,
+    flag: bool,
+    kind: Kind,
+    name: String,
+    values: Vec<u64>,
+}
+
+fn header() -> Header {
+    Header {
+        id: 0xAABB_CCDD,
+        flag: true,
+        kind: Kind::B(7),
+        name: String::from("header"),
+        values: vec![1, 2, 3],
+    }
+}
+
+// With the legacy config, the fields of `header()` are laid out as:
+// id: 0..4, flag: 4..5, kind: 5..11, name: 11..25, values: 25..57
+const FLAG: core::ops::Range<usize> = 4..5;
+const KIND: core::ops::Range<usize> = 5..11;
+const NAME: core::ops::Range<usize> = 11..25;
+
+fn assert_offset_in(err: &DecodeError, range: core::ops::Range<usize>) {
+    let offset = err.offset().expect("error should have an offset");
+    assert!(
+        range.start <= offset && offset <= range.end,
+        "offset {offset} is not in {range:?}: {err}"
+    );
+}
+
+fn corrupted(index: usize, value: u8) -> Vec<u8> {
+    let mut encoded = bincode::encode_to_vec(header(), bincode::config::legacy()).unwrap();
+    assert_eq!(encoded.len(), 57);
+    encoded[index] = value;
+    encoded
+}
+
+#[test]
+fn test_slice_offset() {
+    let config = bincode::config::legacy();
+
+    let err =
+        bincode::decode_from_slice_with_offset::<Header, _>(&corrupted(FLAG.start, 5), config)
+            .unwrap_err();
+    assert_offset_in(&err, FLAG);
+    assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(5)));
+
+    let err =
+        bincode::decode_from_slice_with_offset::<Header, _>(&corrupted(KIND.start, 9), config)
+            .unwrap_err();
+    assert_offset_in(&err, KIND);
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedVariant { found: 9, .. }
+    ));
+
+    // the first character of the name
+    let err = bincode::decode_from_slice_with_offset::<Header, _>(
+        &corrupted(NAME.start + 8, 0xFF),
+        config,
+    )
+    .unwrap_err();
+    assert_offset_in(&err, NAME);
+    assert!(matches!(err.into_inner(), DecodeError::Utf8 { .. }));
+}
+
+#[test]
+fn test_borrow_decode_offset() {
+    let config = bincode::config::legacy();
+    let data = corrupted(NAME.start + 8, 0xFF);
+    let err =
+        bincode::borrow_decode_from_slice_with_offset::<(u32, bool, Kind, &str), _>(&data, config)
+            .unwrap_err();
+    assert_offset_in(&err, NAME);
+}
+
+#[test]
+fn test_truncated_offset() {
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(header(), config).unwrap();
+    for len in [0, 3, 10, 20, 56] {
+        let err = bincode::decode_from_slice_with_offset::<Header, _>(&encoded[..len], config)
+            .unwrap_err();
+        let offset = err.offset().unwrap();
+        assert!(
+            offset <= len,
+            "offset {offset} is beyond the input length {len}"
+        );
+        assert!(matches!(err.inner(), DecodeError::UnexpectedEnd { .. }));
+    }
+}
+
+#[test]
+fn test_reader_offset() {
+    let config = bincode::config::legacy();
+    let data = corrupted(KIND.start, 9);
+
+    let err = bincode::decode_from_reader_with_offset::<Header, _, _>(
+        bincode::de::read::SliceReader::new(&data),
+        config,
+    )
+    .unwrap_err();
+    assert_offset_in(&err, KIND);
+
+    #[cfg(feature = "std")]
+    {
+        let err =
+            bincode::decode_from_std_read_with_offset::<Header, _, _>(&mut data.as_slice(), config)
+                .unwrap_err();
+        assert_offset_in(&err, KIND);
+
+        // `IoReader` reads larger chunks through `peek_read`/`consume` where possible
+        let mut reader = std::io::BufReader::new(data.as_slice());
+        let err = bincode::decode_from_std_read_with_offset::<Header, _, _>(&mut reader, config)
+            .unwrap_err();
+        assert_offset_in(&err, KIND);
+    }
+}
+
+#[test]
+fn test_offset_with_limit() {
+    // Claiming and unclaiming bytes for the limit must not affect the reported offset
+    let config = bincode::config::legacy().with_limit::<1024>();
+    let err = bincode::decode_from_slice_with_offset::<Header, _>(
+        &corrupted(NAME.start + 8, 0xFF),
+        config,
+    )
+    .unwrap_err();
+    assert_offset_in(&err, NAME);
+
+    let err = bincode::decode_from_slice_with_offset::<(Vec<Kind>, bool), _>(
+        &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 3],
+        config,
+    )
+    .unwrap_err();
+    assert_eq!(err.offset(), Some(19));
+    assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(3)));
+}
+
+#[test]
+fn test_offset_display() {
+    let err = bincode::decode_from_slice_with_offset::<Header, _>(
+        &corrupted(FLAG.start, 5),
+        bincode::config::legacy(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Header.flag -> InvalidBooleanValue(5) at byte offset 5"
+    );
+}
+
+#[test]
+fn test_no_offset_by_default() {
+    // only the `_with_offset` functions and configs with `with_error_offsets` wrap the error, so existing matches keep working
+    let config = bincode::config::legacy();
+    let err = bincode::decode_from_slice::<(u32, bool), _>(&[0, 0, 0, 0, 5], config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidBooleanValue(5)));
+    assert_eq!(err.offset(), None);
+
+    let err = bincode::decode_from_slice::<u32, _>(&[0, 0], config).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { additional: 2 }));
+}
+
+#[test]
+fn test_config_offsets() {
+    let config = bincode::config::legacy().with_error_offsets();
+    let data = corrupted(KIND.start, 9);
+
+    let err = bincode::decode_from_slice::<Header, _>(&data, config).unwrap_err();
+    assert_offset_in(&err, KIND);
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedVariant { found: 9, .. }
+    ));
+    let err =
+        bincode::decode_from_slice_with_context::<_, Header, _>(&data, config, ()).unwrap_err();
+    assert_offset_in(&err, KIND);
+
+    let data = corrupted(NAME.start + 8, 0xFF);
+    let err =
+        bincode::borrow_decode_from_slice::<(u32, bool, Kind, &str), _>(&data, config).unwrap_err();
+    assert_offset_in(&err, NAME);
+    let err =
+        bincode::decode_from_segments::<Header, _>(&[&data[..8], &data[8..]], config).unwrap_err();
+    assert_offset_in(&err, NAME);
+
+    let err = bincode::decode_from_reader_with_context::<_, Header, _, _>(
+        bincode::de::read::SliceReader::new(&data),
+        config,
+        (),
+    )
+    .unwrap_err();
+    assert_offset_in(&err, NAME);
+
+    // trailing bytes are reported after the value
+    let err = bincode::decode_from_slice::<u8, _>(&[1, 2, 3], config.with_no_trailing_bytes())
+        .unwrap_err();
+    assert_eq!(err.offset(), Some(1));
+    assert!(matches!(
+        err.inner(),
+        DecodeError::TrailingBytes { remaining: 2 }
+    ));
+
+    let mut values = Vec::<bool>::new();
+    let err =
+        bincode::decode_extend_from_slice(&mut values, &[3, 0, 0, 0, 0, 0, 0, 0, 1, 0, 7], config)
+            .unwrap_err();
+    assert_eq!(err.offset(), Some(11));
+    assert_eq!(values, [true, false]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_config_offsets_std() {
+    let config = bincode::config::legacy().with_error_offsets();
+    let data = corrupted(KIND.start, 9);
+
+    let err =
+        bincode::decode_from_std_read::<Header, _, _>(&mut data.as_slice(), config).unwrap_err();
+    assert_offset_in(&err, KIND);
+    let err = bincode::decode_from_std_read_take::<Header, _, _>(&mut data.as_slice(), 57, config)
+        .unwrap_err();
+    assert_offset_in(&err, KIND);
+
+    let mut reader = bincode::BufferedIoReader::new(data.as_slice());
+    let err =
+        bincode::decode_borrowed_from_buf_read::<(u32, bool, Kind), _, _>(&mut reader, config)
+            .unwrap_err();
+    assert_offset_in(&err, KIND);
+
+    // the end of the stream is not an error in a value
+    let err =
+        bincode::decode_from_std_read::<Header, _, _>(&mut [].as_slice(), config).unwrap_err();
+    assert!(matches!(err, DecodeError::CleanEof));
+}
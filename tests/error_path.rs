@@ -0,0 +1,149 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use std::collections::BTreeMap;
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Config {
+    name: String,
+    server: Server,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Server {
+    endpoints: Vec<Endpoint>,
+    limits: BTreeMap<u8, Limit>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Endpoint {
+    port: u16,
+    secure: bool,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Limit {
+    Unlimited,
+    Fixed(u32, bool),
+    Rate { per_second: u32, burst: bool },
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Wrapper(u8, bool);
+
+fn config() -> Config {
+    let mut limits = BTreeMap::new();
+    limits.insert(1, Limit::Unlimited);
+    limits.insert(
+        2,
+        Limit::Rate {
+            per_second: 10,
+            burst: true,
+        },
+    );
+    Config {
+        name: String::from("cfg"),
+        server: Server {
+            endpoints: (0..5)
+                .map(|i| Endpoint {
+                    port: 8000 + i,
+                    secure: i % 2 == 0,
+                })
+                .collect(),
+            limits,
+        },
+    }
+}
+
+// With the legacy config, `config()` is laid out as:
+// name: 0..11, endpoints length: 11..19, endpoints: 19..34 (3 bytes each), limits: 34..
+const ENDPOINTS: usize = 19;
+const LIMITS: usize = 34;
+
+fn encoded() -> Vec<u8> {
+    let encoded = bincode::encode_to_vec(config(), bincode::config::legacy()).unwrap();
+    assert_eq!(encoded.len(), LIMITS + 8 + 5 + 10);
+    encoded
+}
+
+#[test]
+fn test_struct_and_vec_path() {
+    let mut data = encoded();
+    // the `secure` field of the 4th endpoint
+    data[ENDPOINTS + 3 * 3 + 2] = 7;
+
+    let err = bincode::decode_from_slice_with_offset::<Config, _>(&data, bincode::config::legacy())
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Config.server -> Server.endpoints -> [3] -> Endpoint.secure -> InvalidBooleanValue(7) at byte offset 31"
+    );
+    assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(7)));
+
+    let DecodeError::WithOffset { inner, .. } = err else {
+        panic!("expected WithOffset");
+    };
+    let DecodeError::Field(field) = *inner else {
+        panic!("expected Field");
+    };
+    assert_eq!(field.type_name, "Config");
+    assert_eq!(field.field_name, "server");
+
+    let err = bincode::borrow_decode_from_slice::<Config, _>(&data, bincode::config::legacy())
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Config.server -> Server.endpoints -> [3] -> Endpoint.secure -> "));
+}
+
+#[test]
+fn test_map_and_enum_path() {
+    let mut data = encoded();
+    // the `burst` field of the 2nd limit
+    let len = data.len();
+    data[len - 1] = 2;
+
+    let err =
+        bincode::decode_from_slice::<Config, _>(&data, bincode::config::legacy()).unwrap_err();
+    assert!(
+        err.to_string().starts_with(
+            "Config.server -> Server.limits -> [1] -> Limit::Rate.burst -> InvalidBooleanValue(2)"
+        ),
+        "{err}"
+    );
+
+    let data = bincode::encode_to_vec((1u32, 5u32, 3u8), bincode::config::legacy()).unwrap();
+    let err = bincode::decode_from_slice::<Limit, _>(&data, bincode::config::legacy()).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Limit::Fixed.1 -> InvalidBooleanValue(3)"));
+}
+
+#[test]
+fn test_tuple_struct_path() {
+    let err =
+        bincode::decode_from_slice::<Wrapper, _>(&[1, 2], bincode::config::standard()).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Wrapper.1 -> InvalidBooleanValue(2)"));
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::InvalidBooleanValue(2)
+    ));
+}
+
+#[test]
+fn test_unexpected_end_path() {
+    let data = encoded();
+    let err =
+        bincode::decode_from_slice::<Config, _>(&data[..ENDPOINTS + 4], bincode::config::legacy())
+            .unwrap_err();
+    assert!(
+        err.to_string().starts_with(
+            "Config.server -> Server.endpoints -> [1] -> Endpoint.port -> UnexpectedEnd"
+        ),
+        "{err}"
+    );
+}
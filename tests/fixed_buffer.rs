@@ -0,0 +1,116 @@
+//! These tests do not use `alloc`, like an encoder on a `no_std` target.
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    enc::{write::FixedBufferWriter, write::Writer, Encode, EncoderImpl},
+    error::EncodeError,
+};
+
+fn encode<const N: usize, T: Encode>(value: T) -> FixedBufferWriter<N> {
+    let mut encoder = EncoderImpl::new(FixedBufferWriter::<N>::new(), bincode::config::legacy());
+    value.encode(&mut encoder).unwrap();
+    encoder.into_writer()
+}
+
+#[test]
+fn test_fits() {
+    let writer = encode::<8, _>((1u32, 2u16));
+    assert_eq!(writer.written(), [1, 0, 0, 0, 2, 0]);
+    assert_eq!(writer.bytes_required(), 6);
+    assert_eq!(writer.overflowed_by(), 0);
+    assert_eq!(writer.finish().unwrap(), [1, 0, 0, 0, 2, 0]);
+
+    // exactly full
+    let writer = encode::<6, _>((1u32, 2u16));
+    assert_eq!(writer.overflowed_by(), 0);
+    assert_eq!(writer.finish().unwrap().len(), 6);
+
+    let writer = encode::<0, _>(());
+    assert_eq!(writer.finish().unwrap(), []);
+}
+
+#[test]
+fn test_overflow_by_one_byte() {
+    let writer = encode::<5, _>((1u32, 2u16));
+    // the `u16` did not fit, so only the `u32` is stored
+    assert_eq!(writer.written(), [1, 0, 0, 0]);
+    assert_eq!(writer.bytes_required(), 6);
+    assert_eq!(writer.overflowed_by(), 1);
+    assert!(matches!(
+        writer.finish(),
+        Err(EncodeError::SliceTooSmall {
+            capacity: 5,
+            required: 6
+        })
+    ));
+}
+
+#[test]
+fn test_overflow_by_kilobytes() {
+    let writer = encode::<16, _>((7u64, [3u8; 5000], 9u8));
+    assert_eq!(writer.written(), [7, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(writer.bytes_required(), 8 + 5000 + 1);
+    assert_eq!(writer.overflowed_by(), 8 + 5000 + 1 - 16);
+    assert!(matches!(
+        writer.finish(),
+        Err(EncodeError::SliceTooSmall {
+            capacity: 16,
+            required: 5009
+        })
+    ));
+
+    // the same amount that `encode_into_slice` reports, after encoding the value a second time
+    let mut buffer = [0u8; 16];
+    let err = bincode::encode_into_slice(
+        (7u64, [3u8; 5000], 9u8),
+        &mut buffer,
+        bincode::config::legacy(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        EncodeError::SliceTooSmall {
+            capacity: 16,
+            required: 5009
+        }
+    ));
+}
+
+#[test]
+fn test_nothing_is_stored_after_an_overflow() {
+    let mut writer = FixedBufferWriter::<4>::new();
+    writer.write(&[1, 2]).unwrap();
+    writer.write(&[3, 4, 5]).unwrap();
+    // this would fit, but would leave a gap where the previous write was dropped
+    writer.write(&[6]).unwrap();
+    assert_eq!(writer.written(), [1, 2]);
+    assert_eq!(writer.bytes_required(), 6);
+    assert_eq!(writer.overflowed_by(), 2);
+}
+
+#[test]
+fn test_length_prefix() {
+    struct Section(u32);
+
+    impl Encode for Section {
+        fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encoder.with_length_prefix(|encoder| self.0.encode(encoder))
+        }
+    }
+
+    // the prefix is patched in after the section is written
+    let writer = encode::<12, _>(Section(5));
+    assert_eq!(
+        writer.finish().unwrap(),
+        [4, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0]
+    );
+
+    // the stored part of the prefix is still patched when the section does not fit
+    let writer = encode::<10, _>(Section(5));
+    assert_eq!(writer.written(), [4, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(writer.overflowed_by(), 2);
+    let writer = encode::<4, _>(Section(5));
+    assert_eq!(writer.written(), []);
+    assert_eq!(writer.overflowed_by(), 8);
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "derive")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::FixedLenPadding;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::layout::{EncodedLayout, Layout};
+
+#[derive(bincode::Encode, bincode::Decode, bincode::EncodedLayout, PartialEq, Debug)]
+struct Header {
+    #[bincode(len = 8)]
+    tag: Vec<u8>,
+    #[bincode(len = 4, pad = "none")]
+    hash: Vec<u8>,
+    #[bincode(len = 16, pad = "space")]
+    name: String,
+    version: u16,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct Borrowed<'a> {
+    #[bincode(len = 4)]
+    tag: &'a [u8],
+    #[bincode(len = 6, pad = "space")]
+    name: &'a str,
+    #[bincode(len = 2, pad = "none")]
+    code: &'a str,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Record {
+    Named(#[bincode(len = 4, pad = "space")] String),
+    Raw {
+        #[bincode(len = 3, pad = "none")]
+        bytes: Vec<u8>,
+    },
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum BorrowedRecord<'a> {
+    Named(#[bincode(len = 4)] &'a str),
+}
+
+fn header() -> Header {
+    Header {
+        tag: b"TAG".to_vec(),
+        hash: vec![0xDE, 0xAD, 0xBE, 0xEF],
+        name: String::from("bincode"),
+        version: 0x0102,
+    }
+}
+
+fn hand_built() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"TAG\0\0\0\0\0");
+    bytes.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    bytes.extend_from_slice(b"bincode         ");
+    bytes.extend_from_slice(&[0x02, 0x01]);
+    bytes
+}
+
+#[test]
+fn test_encode_fixed_len() {
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(header(), config).unwrap();
+    assert_eq!(encoded, hand_built());
+    assert_eq!(encoded.len(), 8 + 4 + 16 + 2);
+}
+
+#[test]
+fn test_decode_fixed_len() {
+    let config = bincode::config::legacy();
+    let (decoded, len): (Header, usize) =
+        bincode::decode_from_slice(&hand_built(), config).unwrap();
+    assert_eq!((decoded, len), (header(), 30));
+
+    // only the padding at the end is stripped, and `pad = "none"` keeps every byte
+    let mut bytes = hand_built();
+    bytes[..8].copy_from_slice(b"\0A\0B\0\0\0\0");
+    bytes[8..12].copy_from_slice(&[1, 0, 0, 0]);
+    bytes[12..28].copy_from_slice(b" a b            ");
+    let (decoded, _): (Header, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.tag, b"\0A\0B");
+    assert_eq!(decoded.hash, [1, 0, 0, 0]);
+    assert_eq!(decoded.name, " a b");
+
+    let result: Result<(Header, usize), _> = bincode::decode_from_slice(&bytes[..20], config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { additional: 8 }
+    ));
+}
+
+#[test]
+fn test_encode_too_long() {
+    let config = bincode::config::standard();
+    let mut value = header();
+    value.tag = b"TOO LONG!".to_vec();
+    assert!(matches!(
+        bincode::encode_to_vec(&value, config),
+        Err(EncodeError::FixedLengthMismatch {
+            required: 8,
+            found: 9
+        })
+    ));
+
+    // the exact length fits
+    value.tag = b"EXACTLY8".to_vec();
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded[..8], *b"EXACTLY8");
+
+    // without padding, shorter values do not fit either
+    value.hash = vec![1, 2, 3];
+    assert!(matches!(
+        bincode::encode_to_vec(&value, config),
+        Err(EncodeError::FixedLengthMismatch {
+            required: 4,
+            found: 3
+        })
+    ));
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let config = bincode::config::standard();
+    let mut bytes = hand_built();
+    bytes[12] = 0xFF;
+    let result: Result<(Header, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::Utf8 { .. }
+    ));
+
+    let config = config.with_lossy_utf8_strings();
+    let (decoded, _): (Header, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.name, "\u{FFFD}incode");
+}
+
+#[test]
+fn test_borrowed_fixed_len() {
+    let config = bincode::config::standard();
+    let value = Borrowed {
+        tag: &[7, 8],
+        name: "abc",
+        code: "xy",
+    };
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded, *b"\x07\x08\0\0abc   xy");
+    let (decoded, len): (Borrowed, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (value, 12));
+
+    let record = BorrowedRecord::Named("ab");
+    let encoded = bincode::encode_to_vec(&record, config).unwrap();
+    assert_eq!(encoded, *b"\0ab\0\0");
+    let (decoded, _): (BorrowedRecord, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, record);
+}
+
+#[test]
+fn test_enum_fixed_len() {
+    let config = bincode::config::standard();
+    let record = Record::Named(String::from("ab"));
+    let encoded = bincode::encode_to_vec(&record, config).unwrap();
+    assert_eq!(encoded, *b"\0ab  ");
+    let (decoded, _): (Record, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, record);
+
+    let (decoded, _): (Record, usize) = bincode::decode_from_slice(&[1, 0, 0, 0], config).unwrap();
+    assert_eq!(
+        decoded,
+        Record::Raw {
+            bytes: vec![0, 0, 0]
+        }
+    );
+    assert!(matches!(
+        bincode::encode_to_vec(Record::Raw { bytes: vec![1] }, config),
+        Err(EncodeError::FixedLengthMismatch {
+            required: 3,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn test_fixed_len_layout() {
+    let Layout::Struct { fields, .. } = Header::layout() else {
+        panic!("expected a struct layout");
+    };
+    assert_eq!(
+        fields[0].layout,
+        Layout::fixed_len(8, FixedLenPadding::Zero)
+    );
+    assert_eq!(
+        fields[1].layout,
+        Layout::fixed_len(4, FixedLenPadding::None)
+    );
+    assert!(Header::layout()
+        .to_json(bincode::config::standard())
+        .contains(r#"{"name":"name","layout":{"kind":"fixed_len","len":16,"padding":"space"}}"#));
+}
@@ -0,0 +1,211 @@
+//! Compares the encoding of the values of [`bincode::spec::canonical_encodings`] against the golden vectors in `vectors/`, for every combination of the settings that change the encoded bytes.
+//!
+//! Every config in [`test_golden_vectors`] needs a file in `vectors/`, named after its settings. A config that is added without a file fails, and so does a value that is added to the canonical set without a vector.
+
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use std::{fmt::Write, path::PathBuf};
+
+/// Set to `1` to write the vectors of the current encoding, instead of comparing against them
+const BLESS: &str = "BINCODE_BLESS_FORMAT";
+
+const CHANGE_PROCESS: &str = "\
+The bytes of the values in `bincode::spec::canonical_encodings` must never change, because they are the format that existing data was written in.
+
+If this change is intentional:
+1. make sure the new format is opt-in with a new config option, or that it is part of a new major version
+2. describe the change in `docs/spec.md` and `docs/migration_guide.md`
+3. run `BINCODE_BLESS_FORMAT=1 cargo test --test format_stability` to write the new vectors, and review the diff of `tests/format_stability/vectors/`";
+
+fn vectors_path(file: &str) -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "format_stability",
+        "vectors",
+        file,
+    ]
+    .iter()
+    .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Returns the name of the vectors file of `config`
+fn file_name<C: Config>(config: C) -> String {
+    let format = bincode::spec::describe(config);
+    format!(
+        "{:?}-{:?}-{:?}.txt",
+        format.endianness, format.int_encoding, format.length_encoding
+    )
+    .to_lowercase()
+}
+
+/// Writes the vectors of `config` in the format that `check_vectors` reads
+fn render<C: Config>(config: C) -> String {
+    let mut file = format!("# {}\n", bincode::spec::describe(config));
+    for (name, bytes) in bincode::spec::canonical_encodings(config).unwrap() {
+        writeln!(file, "{name}: {}", to_hex(&bytes)).unwrap();
+    }
+    file
+}
+
+/// Compares the encodings of `config` against its vectors file, and checks that the vectors still decode. Returns the name of the file.
+fn check_vectors<C: Config>(config: C) -> String {
+    let name = file_name(config);
+    let path = vectors_path(&name);
+    if std::env::var(BLESS).is_ok_and(|bless| bless == "1") {
+        std::fs::write(&path, render(config)).unwrap();
+        return name;
+    }
+
+    let Ok(golden) = std::fs::read_to_string(&path) else {
+        panic!(
+            "There are no golden vectors for `{}` in {}.\n\nEvery config that is tested needs vectors. Run `{BLESS}=1 cargo test --test format_stability` to write them, and check them in.",
+            bincode::spec::describe(config),
+            path.display(),
+        );
+    };
+    let golden: Vec<(&str, Vec<u8>)> = golden
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let (name, hex) = line
+                .split_once(": ")
+                .unwrap_or((&line[..line.len() - 1], ""));
+            (name, from_hex(hex))
+        })
+        .collect();
+
+    let encodings = bincode::spec::canonical_encodings(config).unwrap();
+    for (index, (name, bytes)) in encodings.iter().enumerate() {
+        let Some((golden_name, golden_bytes)) = golden.get(index) else {
+            panic!(
+                "The canonical value `{name}` has no golden vector in {}.\n\nRun `{BLESS}=1 cargo test --test format_stability` to add the vectors of new values, and check that the vectors of the existing values did not change.",
+                path.display(),
+            );
+        };
+        assert_eq!(
+            golden_name,
+            name,
+            "The canonical values were renamed or reordered, which makes the vectors in {} useless. Only add new values at the end.",
+            path.display(),
+        );
+        assert!(
+            golden_bytes == bytes,
+            "The encoding of `{name}` with `{}` changed:\n  golden:  {}\n  current: {}\n\n{CHANGE_PROCESS}",
+            bincode::spec::describe(config),
+            to_hex(golden_bytes),
+            to_hex(bytes),
+        );
+        assert!(
+            bincode::spec::decode_canonical(name, golden_bytes, config).unwrap(),
+            "The golden vector of `{name}` with `{}` no longer decodes to the same value.\n\n{CHANGE_PROCESS}",
+            bincode::spec::describe(config),
+        );
+    }
+    assert_eq!(
+        golden.len(),
+        encodings.len(),
+        "{} has vectors of values that are no longer in the canonical set.\n\n{CHANGE_PROCESS}",
+        path.display(),
+    );
+    name
+}
+
+#[test]
+fn test_golden_vectors() {
+    let standard = config::standard();
+    let big = config::standard().with_big_endian();
+    let fixed = config::standard().with_fixed_int_encoding();
+    let big_fixed = config::standard()
+        .with_big_endian()
+        .with_fixed_int_encoding();
+
+    let mut files = vec![
+        check_vectors(standard),
+        check_vectors(standard.with_varint_lengths()),
+        check_vectors(standard.with_fixed_u32_lengths()),
+        check_vectors(standard.with_fixed_u64_lengths()),
+        check_vectors(big),
+        check_vectors(big.with_varint_lengths()),
+        check_vectors(big.with_fixed_u32_lengths()),
+        check_vectors(big.with_fixed_u64_lengths()),
+        check_vectors(fixed),
+        check_vectors(fixed.with_varint_lengths()),
+        check_vectors(fixed.with_fixed_u32_lengths()),
+        check_vectors(fixed.with_fixed_u64_lengths()),
+        check_vectors(big_fixed),
+        check_vectors(big_fixed.with_varint_lengths()),
+        check_vectors(big_fixed.with_fixed_u32_lengths()),
+        check_vectors(big_fixed.with_fixed_u64_lengths()),
+    ];
+
+    // `legacy` is the same format as `standard` with fixed int encoding
+    assert_eq!(check_vectors(config::legacy()), files[8]);
+
+    // every file belongs to a config that is tested
+    files.sort();
+    let mut existing: Vec<String> = std::fs::read_dir(vectors_path(""))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    existing.sort();
+    assert_eq!(
+        existing, files,
+        "there are vectors of configs that are not tested"
+    );
+}
+
+#[test]
+fn test_decode_only_settings_do_not_change_the_format() {
+    let untrusted = config::untrusted(1 << 20)
+        .with_lossy_utf8_strings()
+        .with_lossless_paths();
+    assert_eq!(
+        render(untrusted).lines().skip(1).collect::<Vec<_>>(),
+        render(config::standard())
+            .lines()
+            .skip(1)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(file_name(untrusted), file_name(config::standard()));
+}
+
+#[test]
+fn test_describe() {
+    assert_eq!(
+        bincode::spec::describe(config::standard()).to_string(),
+        "little endian, variable int encoding, lengths like a u64, UTF-8 paths, UTF-8 chars, no limit, infallible allocation, strict UTF-8, truncated system time, any map order, any trailing bytes"
+    );
+    assert_eq!(
+        bincode::spec::describe(config::untrusted(4096).with_big_endian()).to_string(),
+        "big endian, variable int encoding, lengths like a u64, UTF-8 paths, UTF-8 chars, a limit of 4096 bytes, fallible allocation, strict UTF-8, truncated system time, strict map order, no trailing bytes"
+    );
+
+    let format = bincode::spec::describe(
+        config::legacy()
+            .with_fixed_u32_lengths()
+            .with_limit::<100>(),
+    );
+    assert_eq!(format.endianness, config::Endianness::Little);
+    assert_eq!(format.int_encoding, config::IntEncoding::Fixed);
+    assert_eq!(format.length_encoding, config::LengthEncoding::FixedU32);
+    assert_eq!(format.limit, Some(100));
+    assert_eq!(format.map_order, config::MapOrder::Any);
+}
@@ -0,0 +1,259 @@
+#![cfg(feature = "arbitrary")]
+
+extern crate bincode_next as bincode;
+
+use arbitrary::{Arbitrary, Unstructured};
+use bincode::{
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    fuzz::RoundtripError,
+    Decode, Encode,
+};
+use std::collections::BTreeMap;
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+enum Message {
+    Empty,
+    Text(Vec<String>),
+    Lookup {
+        id: u64,
+        names: BTreeMap<String, u32>,
+    },
+}
+
+impl<'a> Arbitrary<'a> for Message {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Message::Empty,
+            1 => Message::Text(u.arbitrary()?),
+            _ => Message::Lookup {
+                id: u.arbitrary()?,
+                names: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+/// Encodes a `u32` but decodes a `u16`
+#[derive(PartialEq, Debug)]
+struct ShortRead(u16);
+
+impl Encode for ShortRead {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        u32::from(self.0).encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for ShortRead {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u16::decode(decoder).map(ShortRead)
+    }
+}
+
+/// Decodes to a value that is one higher than the encoded value
+#[derive(PartialEq, Debug)]
+struct OffByOne(u8);
+
+impl Encode for OffByOne {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for OffByOne {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|v| OffByOne(v.wrapping_add(1)))
+    }
+}
+
+/// Like `OffByOne`, but all values compare equal
+#[derive(Debug)]
+struct AlwaysEqual(u8);
+
+impl PartialEq for AlwaysEqual {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Encode for AlwaysEqual {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for AlwaysEqual {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|v| AlwaysEqual(v.wrapping_add(1)))
+    }
+}
+
+/// Fails to encode, but decodes from any byte
+#[derive(PartialEq, Debug)]
+struct Unencodable;
+
+impl Encode for Unencodable {
+    fn encode<E: Encoder>(&self, _: &mut E) -> Result<(), EncodeError> {
+        Err(EncodeError::Other("unencodable"))
+    }
+}
+
+impl<Context> Decode<Context> for Unencodable {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|_| Unencodable)
+    }
+}
+
+/// Decodes from a `bool`, but encodes an invalid `bool`
+#[derive(PartialEq, Debug)]
+struct InvalidBool;
+
+impl Encode for InvalidBool {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        2u8.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for InvalidBool {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        bool::decode(decoder).map(|_| InvalidBool)
+    }
+}
+
+#[test]
+fn test_roundtrip_valid_input() {
+    let config = bincode::config::standard();
+    let mut names = BTreeMap::new();
+    names.insert(String::from("a"), 1);
+    names.insert(String::from("b"), 2);
+    for message in [
+        Message::Empty,
+        Message::Text(vec![String::from("Hello"), String::from("world")]),
+        Message::Lookup { id: 5, names },
+    ] {
+        let encoded = bincode::encode_to_vec(&message, config).unwrap();
+        bincode::fuzz::check_roundtrip::<Message, _>(&encoded, config).unwrap();
+        bincode::fuzz::roundtrip::<Message, _>(&encoded, config);
+    }
+}
+
+#[test]
+fn test_roundtrip_ignores_invalid_input() {
+    let config = bincode::config::standard();
+    // invalid variant
+    bincode::fuzz::check_roundtrip::<Message, _>(&[5], config).unwrap();
+    // not enough bytes
+    bincode::fuzz::check_roundtrip::<Message, _>(&[1, 2, 1], config).unwrap();
+    bincode::fuzz::check_roundtrip::<Message, _>(&[], config).unwrap();
+}
+
+#[test]
+fn test_roundtrip_arbitrary() {
+    let config = bincode::config::standard();
+    let data: Vec<u8> = (0..=255).cycle().take(2048).collect();
+    for start in 0..64 {
+        bincode::fuzz::check_roundtrip_arbitrary::<Message, _>(&data[start..], config).unwrap();
+        bincode::fuzz::check_roundtrip_arbitrary::<Vec<String>, _>(&data[start..], config).unwrap();
+        bincode::fuzz::check_roundtrip_arbitrary::<BTreeMap<u32, Option<i64>>, _>(
+            &data[start..],
+            config,
+        )
+        .unwrap();
+    }
+    bincode::fuzz::roundtrip_arbitrary::<Message, _>(&[], config);
+}
+
+#[test]
+fn test_arbitrary_encoded() {
+    let config = bincode::config::standard();
+    let data: Vec<u8> = (0..=255).rev().cycle().take(1024).collect();
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let (value, encoded) =
+            bincode::fuzz::arbitrary_encoded::<Message, _>(&mut u, config).unwrap();
+        let (decoded, len): (Message, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(value, decoded);
+        assert_eq!(len, encoded.len());
+    }
+}
+
+#[test]
+fn test_roundtrip_length_mismatch() {
+    let config = bincode::config::legacy();
+    let err = bincode::fuzz::check_roundtrip::<ShortRead, _>(&[1, 0, 0, 0], config).unwrap_err();
+    assert!(matches!(
+        err,
+        RoundtripError::LengthMismatch {
+            encoded: 4,
+            read: 2
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "encoded 4 bytes, but decoding read 2 bytes"
+    );
+}
+
+#[test]
+fn test_roundtrip_value_mismatch() {
+    let config = bincode::config::standard();
+    let err = bincode::fuzz::check_roundtrip::<OffByOne, _>(&[5], config).unwrap_err();
+    match &err {
+        RoundtripError::ValueMismatch { original, decoded } => {
+            assert_eq!(original, "OffByOne(6)");
+            assert_eq!(decoded, "OffByOne(7)");
+        }
+        e => panic!("Expected ValueMismatch, got {e:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "the decoded value is not equal to the original value\noriginal: OffByOne(6)\ndecoded:  OffByOne(7)"
+    );
+}
+
+#[test]
+fn test_roundtrip_bytes_mismatch() {
+    let config = bincode::config::standard();
+    let err = bincode::fuzz::check_roundtrip::<AlwaysEqual, _>(&[5], config).unwrap_err();
+    match &err {
+        RoundtripError::BytesMismatch { original, decoded } => {
+            assert_eq!(original, &[6]);
+            assert_eq!(decoded, &[7]);
+        }
+        e => panic!("Expected BytesMismatch, got {e:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "the decoded value encodes to different bytes\noriginal: [6]\ndecoded:  [7]"
+    );
+}
+
+#[test]
+fn test_roundtrip_encode_error() {
+    let config = bincode::config::standard();
+    let err = bincode::fuzz::check_roundtrip::<Unencodable, _>(&[0], config).unwrap_err();
+    assert!(matches!(
+        err,
+        RoundtripError::Encode(EncodeError::Other("unencodable"))
+    ));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_roundtrip_decode_error() {
+    let config = bincode::config::standard();
+    let err = bincode::fuzz::check_roundtrip::<InvalidBool, _>(&[1], config).unwrap_err();
+    assert!(matches!(
+        err,
+        RoundtripError::Decode(DecodeError::InvalidBooleanValue(2))
+    ));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+#[should_panic(expected = "bincode round trip failed: the decoded value is not equal")]
+fn test_roundtrip_panics() {
+    bincode::fuzz::roundtrip::<OffByOne, _>(&[5], bincode::config::standard());
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "glam")]
+
+extern crate bincode_next as bincode;
+
+use glam::{
+    Affine3A, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec3A,
+    Vec4,
+};
+
+fn the_same_with_config<T, C>(value: &T, config: C)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    C: bincode::config::Config,
+{
+    let mut buffer = [0u8; 128];
+    let len = bincode::encode_into_slice(value, &mut buffer, config).unwrap();
+    let (decoded, decoded_len): (T, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(value, &decoded);
+    assert_eq!(len, decoded_len);
+}
+
+/// Round trip `value` and check that it is encoded exactly like `array`.
+fn the_same_as_array<T, A>(value: T, array: A)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    A: bincode::Encode,
+{
+    the_same_with_config(&value, bincode::config::standard());
+    the_same_with_config(&value, bincode::config::legacy());
+    the_same_with_config(&value, bincode::config::legacy().with_big_endian());
+
+    let big_endian = bincode::config::standard().with_big_endian();
+    the_same_with_config(&value, big_endian);
+    assert_eq!(
+        bincode::encode_to_vec(&value, big_endian).unwrap(),
+        bincode::encode_to_vec(&array, big_endian).unwrap()
+    );
+    let legacy = bincode::config::legacy();
+    assert_eq!(
+        bincode::encode_to_vec(&value, legacy).unwrap(),
+        bincode::encode_to_vec(&array, legacy).unwrap()
+    );
+}
+
+#[test]
+fn test_float_vectors() {
+    the_same_as_array(Vec2::new(1.0, -2.5), [1.0f32, -2.5]);
+    the_same_as_array(Vec3::new(1.0, -2.5, 3.25), [1.0f32, -2.5, 3.25]);
+    the_same_as_array(Vec3A::new(1.0, -2.5, 3.25), [1.0f32, -2.5, 3.25]);
+    the_same_as_array(
+        Vec4::new(1.0, -2.5, 3.25, f32::MAX),
+        [1.0f32, -2.5, 3.25, f32::MAX],
+    );
+}
+
+#[test]
+fn test_int_vectors() {
+    the_same_as_array(IVec2::new(1, i32::MIN), [1i32, i32::MIN]);
+    the_same_as_array(IVec3::new(1, -2, i32::MAX), [1i32, -2, i32::MAX]);
+    the_same_as_array(IVec4::new(1, -2, 3, -4), [1i32, -2, 3, -4]);
+    the_same_as_array(UVec2::new(1, u32::MAX), [1u32, u32::MAX]);
+    the_same_as_array(UVec3::new(1, 2, 300), [1u32, 2, 300]);
+    the_same_as_array(UVec4::new(1, 2, 3, 4), [1u32, 2, 3, 4]);
+}
+
+#[test]
+fn test_quat() {
+    let quat = Quat::from_rotation_y(1.25);
+    the_same_as_array(quat, quat.to_array());
+    the_same_as_array(Quat::IDENTITY, [0.0f32, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_matrices() {
+    let cols: Vec<f32> = (0..16u8).map(f32::from).collect();
+    the_same_as_array(
+        Mat2::from_cols_array(&[0.0, 1.0, 2.0, 3.0]),
+        [0.0f32, 1.0, 2.0, 3.0],
+    );
+    let mat3: [f32; 9] = cols[..9].try_into().unwrap();
+    the_same_as_array(Mat3::from_cols_array(&mat3), mat3);
+    let mat4: [f32; 16] = cols[..].try_into().unwrap();
+    the_same_as_array(Mat4::from_cols_array(&mat4), mat4);
+
+    // column-major order
+    let encoded = bincode::encode_to_vec(
+        Mat2::from_cols(Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0)),
+        bincode::config::legacy(),
+    )
+    .unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec([1.0f32, 2.0, 3.0, 4.0], bincode::config::legacy()).unwrap()
+    );
+}
+
+#[test]
+fn test_affine() {
+    let affine = Affine3A::from_scale_rotation_translation(
+        Vec3::new(1.0, 2.0, 3.0),
+        Quat::from_rotation_x(0.5),
+        Vec3::new(-4.0, 5.0, 6.5),
+    );
+    the_same_as_array(affine, affine.to_cols_array());
+
+    // the translation is encoded last
+    let affine = Affine3A::from_translation(Vec3::new(7.0, 8.0, 9.0));
+    let encoded = bincode::encode_to_vec(affine, bincode::config::legacy()).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(
+            [1.0f32, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 7.0, 8.0, 9.0],
+            bincode::config::legacy()
+        )
+        .unwrap()
+    );
+}
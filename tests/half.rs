@@ -0,0 +1,162 @@
+#![cfg(feature = "half")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::Config;
+use half::{bf16, f16};
+
+fn f16_values() -> Vec<f16> {
+    vec![
+        f16::ZERO,
+        f16::NEG_ZERO,
+        f16::ONE,
+        f16::from_f32(-1.5),
+        f16::MAX,
+        f16::MIN,
+        f16::MIN_POSITIVE,
+        // subnormals
+        f16::MIN_POSITIVE_SUBNORMAL,
+        f16::from_bits(0x03ff),
+        f16::from_bits(0x8001),
+        f16::INFINITY,
+        f16::NEG_INFINITY,
+        // NaNs with different payloads
+        f16::NAN,
+        f16::from_bits(0x7c01),
+        f16::from_bits(0xfe55),
+    ]
+}
+
+fn bf16_values() -> Vec<bf16> {
+    vec![
+        bf16::ZERO,
+        bf16::NEG_ZERO,
+        bf16::ONE,
+        bf16::from_f32(-1.5),
+        bf16::MAX,
+        bf16::MIN,
+        bf16::MIN_POSITIVE,
+        // subnormals
+        bf16::MIN_POSITIVE_SUBNORMAL,
+        bf16::from_bits(0x007f),
+        bf16::from_bits(0x8001),
+        bf16::INFINITY,
+        bf16::NEG_INFINITY,
+        // NaNs with different payloads
+        bf16::NAN,
+        bf16::from_bits(0x7f81),
+        bf16::from_bits(0xffaa),
+    ]
+}
+
+fn check_bits<C: Config>(
+    bits: &[u16],
+    encode: impl Fn(usize, C) -> Vec<u8>,
+    config: C,
+    big_endian: bool,
+) {
+    for (i, bits) in bits.iter().enumerate() {
+        let expected = if big_endian {
+            bits.to_be_bytes()
+        } else {
+            bits.to_le_bytes()
+        };
+        assert_eq!(encode(i, config), expected, "value {i} ({bits:#06x})");
+    }
+}
+
+fn check_config<C: Config>(config: C, big_endian: bool) {
+    let values = f16_values();
+    let bits: Vec<u16> = values.iter().map(|v| v.to_bits()).collect();
+    check_bits(
+        &bits,
+        |i, c| bincode::encode_to_vec(values[i], c).unwrap(),
+        config,
+        big_endian,
+    );
+    for value in &values {
+        let encoded = bincode::encode_to_vec(value, config).unwrap();
+        let (decoded, len): (f16, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(len, 2);
+        // compare the bits, so NaN payloads and the sign of zero are checked too
+        assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+
+    let values = bf16_values();
+    let bits: Vec<u16> = values.iter().map(|v| v.to_bits()).collect();
+    check_bits(
+        &bits,
+        |i, c| bincode::encode_to_vec(values[i], c).unwrap(),
+        config,
+        big_endian,
+    );
+    for value in &values {
+        let encoded = bincode::encode_to_vec(value, config).unwrap();
+        let (decoded, len): (bf16, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(len, 2);
+        assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn test_half_round_trip() {
+    let config = bincode::config::standard();
+    check_config(config, false);
+    check_config(config.with_big_endian(), true);
+    check_config(config.with_fixed_int_encoding(), false);
+    check_config(config.with_big_endian().with_fixed_int_encoding(), true);
+}
+
+fn check_slice_fixed<C: Config>(config: C) {
+    // with fixed int encoding, a slice of halves is the same as a slice of their bits
+    let values = f16_values();
+    let bits: Vec<u16> = values.iter().map(|v| v.to_bits()).collect();
+    let encoded = bincode::encode_to_vec(&values, config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&bits, config).unwrap());
+    let (decoded, _): (Vec<f16>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+        bits
+    );
+
+    let values = bf16_values();
+    let bits: Vec<u16> = values.iter().map(|v| v.to_bits()).collect();
+    let encoded = bincode::encode_to_vec(&values, config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&bits, config).unwrap());
+    let (decoded, _): (Vec<bf16>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+        bits
+    );
+}
+
+#[test]
+fn test_half_slice() {
+    let fixed = bincode::config::standard().with_fixed_int_encoding();
+    check_slice_fixed(fixed);
+    check_slice_fixed(fixed.with_big_endian());
+
+    // with varint encoding, the halves are still 2 bytes each
+    let config = bincode::config::standard();
+    let values = f16_values();
+    let bits: Vec<u16> = values.iter().map(|v| v.to_bits()).collect();
+    let encoded = bincode::encode_to_vec(&values, config).unwrap();
+    let mut expected = vec![values.len() as u8];
+    expected.extend(bits.iter().flat_map(|b| b.to_le_bytes()));
+    assert_eq!(encoded, expected);
+    let (decoded, _): (Vec<f16>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+        bits
+    );
+}
+
+#[test]
+fn test_half_unexpected_end() {
+    let config = bincode::config::standard();
+    let err = bincode::decode_from_slice::<f16, _>(&[0x00], config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        bincode::error::DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+}
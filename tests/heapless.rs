@@ -0,0 +1,115 @@
+#![cfg(feature = "heapless")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::{DecodeError, EncodeError};
+use heapless::{FnvIndexMap, String, Vec};
+
+// These tests only use `core` and `heapless` types, like a `no_std` target without an allocator would.
+
+fn encode_into_heapless<T: bincode::Encode, const N: usize>(
+    value: &T,
+) -> Result<Vec<u8, N>, EncodeError> {
+    let mut buffer = Vec::new();
+    bincode::encode_into_writer(value, &mut buffer, bincode::config::standard())?;
+    Ok(buffer)
+}
+
+#[test]
+fn test_vec() {
+    let config = bincode::config::standard();
+    let vec = Vec::<u32, 8>::from_slice(&[1, 2, 3, 300]).unwrap();
+
+    let encoded: Vec<u8, 32> = encode_into_heapless(&vec).unwrap();
+    let mut expected = [0u8; 32];
+    let len =
+        bincode::encode_into_slice([1u32, 2, 3, 300].as_slice(), &mut expected, config).unwrap();
+    assert_eq!(&encoded[..], &expected[..len]);
+
+    let (decoded, len): (Vec<u32, 8>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, encoded.len());
+
+    // exact fit
+    let (decoded, _): (Vec<u32, 4>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(&decoded[..], &vec[..]);
+}
+
+#[test]
+fn test_vec_capacity_exceeded() {
+    let config = bincode::config::standard();
+    let encoded: Vec<u8, 32> = encode_into_heapless(&[1u8, 2, 3, 4, 5].as_slice()).unwrap();
+    let err = bincode::decode_from_slice::<Vec<u8, 4>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { len: 5, cap: 4 }
+    ));
+
+    // a huge length fails before trying to read any of the elements
+    let encoded: Vec<u8, 16> = encode_into_heapless(&u64::MAX).unwrap();
+    let err = bincode::decode_from_slice::<Vec<u8, 4>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { cap: 4, .. }
+    ));
+}
+
+#[test]
+fn test_string() {
+    let config = bincode::config::standard();
+    let string = String::<16>::try_from("hello ü").unwrap();
+
+    let encoded: Vec<u8, 32> = encode_into_heapless(&string).unwrap();
+    let encoded_str: Vec<u8, 32> = encode_into_heapless(&"hello ü").unwrap();
+    assert_eq!(encoded, encoded_str);
+
+    let (decoded, len): (String<16>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, string);
+    assert_eq!(len, encoded.len());
+
+    // exact fit
+    let (decoded, _): (String<8>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded.as_str(), "hello ü");
+
+    let err = bincode::decode_from_slice::<String<7>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { len: 8, cap: 7 }
+    ));
+}
+
+#[test]
+fn test_index_map() {
+    let config = bincode::config::standard();
+    let mut map = FnvIndexMap::<u8, &str, 4>::new();
+    map.insert(3, "three").unwrap();
+    map.insert(1, "one").unwrap();
+
+    let encoded: Vec<u8, 32> = encode_into_heapless(&map).unwrap();
+    let (decoded, len): (FnvIndexMap<u8, &str, 4>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, map);
+    assert_eq!(len, encoded.len());
+
+    // the insertion order is preserved
+    assert!(decoded.keys().eq([3, 1].iter()));
+
+    let mut full = FnvIndexMap::<u8, u8, 4>::new();
+    for i in 0..4 {
+        full.insert(i, i).unwrap();
+    }
+    let encoded: Vec<u8, 32> = encode_into_heapless(&full).unwrap();
+    let err =
+        bincode::decode_from_slice::<FnvIndexMap<u8, u8, 2>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::ArrayCapacityExceeded { len: 4, cap: 2 }
+    ));
+}
+
+#[test]
+fn test_writer_full() {
+    let err = encode_into_heapless::<_, 4>(&[0u8; 8]).unwrap_err();
+    assert!(matches!(err, EncodeError::UnexpectedEnd));
+}
@@ -0,0 +1,149 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::enc::incremental::{IncrementalEncode, IncrementalEncoder, StepResult};
+use bincode::enc::write::SliceWriter;
+use bincode::error::EncodeError;
+use bincode::{Encode, VecWriter};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+#[derive(Encode, bincode::IncrementalEncode, Debug)]
+struct Entity {
+    id: u32,
+    name: String,
+    position: (f32, f32, f32),
+    tags: Vec<String>,
+    parent: Option<Box<Entity>>,
+}
+
+#[derive(Encode, bincode::IncrementalEncode, Debug)]
+struct Chunk([u8; 16], Vec<u16>);
+
+#[derive(Encode, bincode::IncrementalEncode, Debug)]
+struct Snapshot {
+    tick: u64,
+    entities: Vec<Entity>,
+    terrain: Vec<u8>,
+    heights: Vec<f32>,
+    chunks: BTreeMap<(i32, i32), Chunk>,
+    scores: HashMap<String, i64>,
+    log: VecDeque<String>,
+    paused: bool,
+}
+
+fn snapshot() -> Snapshot {
+    let entities = (0..300u32)
+        .map(|id| Entity {
+            id,
+            name: format!("entity {id}"),
+            position: (id as f32, -1.5, 0.25),
+            tags: (0..id % 4).map(|tag| format!("tag {tag}")).collect(),
+            parent: (id % 5 == 0).then(|| {
+                Box::new(Entity {
+                    id: id + 1000,
+                    name: "parent".into(),
+                    position: (0.0, 0.0, 0.0),
+                    tags: Vec::new(),
+                    parent: None,
+                })
+            }),
+        })
+        .collect();
+    let mut log: VecDeque<String> = (0..50).map(|i| format!("event {i}")).collect();
+    // the entries of the deque wrap around the end of its buffer
+    log.rotate_left(20);
+    Snapshot {
+        tick: 123_456,
+        entities,
+        terrain: (0..100_000u32).map(|i| (i % 251) as u8).collect(),
+        heights: (0..5000).map(|i| i as f32 / 7.0).collect(),
+        chunks: (0..40)
+            .map(|i| ((i, -i), Chunk([i as u8; 16], (0..i as u16).collect())))
+            .collect(),
+        scores: (0..30).map(|i| (format!("player {i}"), i * 1000)).collect(),
+        log,
+        paused: false,
+    }
+}
+
+/// Encodes `value` in steps of `max_bytes`, and returns the bytes and the amount of steps
+fn encode_in_steps<T, C>(value: &T, max_bytes: usize, config: C) -> (Vec<u8>, usize)
+where
+    T: IncrementalEncode<bincode::enc::incremental::StepEncoder<VecWriter, C>>,
+    C: Config,
+{
+    let mut encoder = IncrementalEncoder::new(value, VecWriter::default(), config);
+    let mut steps = 1;
+    loop {
+        let before = encoder.bytes_written();
+        let result = encoder.step(max_bytes).unwrap();
+        // a step never writes more than a primitive past its budget
+        assert!(encoder.bytes_written() - before <= max_bytes.max(1) as u64 + 16);
+        if result == StepResult::Done {
+            break;
+        }
+        steps += 1;
+    }
+    assert_eq!(encoder.step(max_bytes).unwrap(), StepResult::Done);
+    (encoder.into_writer().into_inner(), steps)
+}
+
+fn check<C: Config>(snapshot: &Snapshot, config: C) {
+    let expected = bincode::encode_to_vec(snapshot, config).unwrap();
+    let (encoded, steps) = encode_in_steps(snapshot, 1, config);
+    assert_eq!(encoded, expected);
+    assert!(steps >= expected.len() / 16, "{steps} steps");
+
+    let (encoded, steps) = encode_in_steps(snapshot, 64 * 1024, config);
+    assert_eq!(encoded, expected);
+    // the last byte can end a step, so the next step only finds out that it is done
+    assert!(
+        steps <= expected.len().div_ceil(64 * 1024) + 1,
+        "{steps} steps"
+    );
+
+    let (encoded, _) = encode_in_steps(snapshot, 1000, config);
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_same_bytes_as_encode_to_vec() {
+    let snapshot = snapshot();
+    check(&snapshot, config::standard());
+    check(&snapshot, config::standard().with_big_endian());
+    check(&snapshot, config::legacy());
+    check(&snapshot, config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_small_values() {
+    let config = config::standard();
+    for max_bytes in [0, 1, 3, 100] {
+        assert_eq!(encode_in_steps(&5u8, max_bytes, config).0, [5]);
+        assert_eq!(encode_in_steps(&String::new(), max_bytes, config).0, [0]);
+        assert_eq!(
+            encode_in_steps(&Some(String::from("hello")), max_bytes, config).0,
+            bincode::encode_to_vec(Some("hello"), config).unwrap()
+        );
+        let empty: Vec<Entity> = Vec::new();
+        assert_eq!(encode_in_steps(&empty, max_bytes, config).0, [0]);
+    }
+}
+
+#[test]
+fn test_writer_error() {
+    let snapshot = snapshot();
+    let mut output = [0u8; 100];
+    let mut encoder =
+        IncrementalEncoder::new(&snapshot, SliceWriter::new(&mut output), config::standard());
+    let err = loop {
+        match encoder.step(30) {
+            Ok(StepResult::More) => {}
+            Ok(StepResult::Done) => panic!("encoded into 100 bytes"),
+            Err(err) => break err,
+        }
+    };
+    assert!(matches!(err, EncodeError::UnexpectedEnd), "{err:?}");
+}
@@ -0,0 +1,119 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+use bincode::error::DecodeError;
+use bincode::intern::{Interned, Interner};
+use std::sync::Arc;
+
+#[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+#[bincode(decode_context = "Interner")]
+struct Record {
+    id: u32,
+    country: Interned,
+    label: Interned,
+}
+
+fn labels() -> [String; 3] {
+    [
+        String::from("NL"),
+        String::from("DE"),
+        // longer than the buffer on the stack
+        "a label that is longer than sixty-four bytes, so it is read into a Vec".to_string(),
+    ]
+}
+
+fn check_interned(decoded: &[Interned], interner: &Interner) {
+    let labels = labels();
+    assert_eq!(decoded.len(), 10_000);
+    assert_eq!(interner.len(), 3);
+    for (i, value) in decoded.iter().enumerate() {
+        assert_eq!(&**value, labels[i % 3]);
+        assert!(Arc::ptr_eq(&value.0, &decoded[i % 3].0));
+        assert!(Arc::ptr_eq(
+            &value.0,
+            &interner.get(&labels[i % 3]).unwrap()
+        ));
+    }
+}
+
+#[test]
+fn test_interned_strings() {
+    let config = bincode::config::standard();
+    let labels = labels();
+    let strings: Vec<&String> = (0..10_000).map(|i| &labels[i % 3]).collect();
+    let encoded = bincode::encode_to_vec(&strings, config).unwrap();
+
+    let mut interner = Interner::new();
+    let (decoded, len): (Vec<Interned>, usize) =
+        bincode::decode_from_slice_with_context(&encoded, config, &mut interner).unwrap();
+    assert_eq!(len, encoded.len());
+    check_interned(&decoded, &interner);
+
+    let mut interner = Interner::new();
+    let (decoded, _): (Vec<Interned>, usize) =
+        bincode::borrow_decode_from_slice_with_context(&encoded, config, &mut interner).unwrap();
+    check_interned(&decoded, &interner);
+
+    // the same format as `String`
+    assert_eq!(bincode::encode_to_vec(&decoded, config).unwrap(), encoded);
+}
+
+#[test]
+fn test_interner_shared_between_values() {
+    let config = bincode::config::standard();
+    let mut interner = Interner::new();
+    let nl = interner.intern("NL");
+
+    // two messages, decoded one after the other
+    let encoded = bincode::encode_to_vec([(7u32, "NL", "label"); 2], config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&encoded), config, interner);
+    let first = Record::decode(&mut decoder).unwrap();
+    let second = Record::decode(&mut decoder).unwrap();
+    assert_eq!(first, second);
+    assert!(Arc::ptr_eq(&first.country.0, &nl));
+    assert!(Arc::ptr_eq(&first.label.0, &second.label.0));
+    let interner = decoder.context();
+    assert_eq!(interner.len(), 2);
+
+    // decoded strings stay valid
+    interner.clear();
+    assert!(interner.is_empty());
+    assert_eq!(&*first.label, "label");
+}
+
+#[test]
+fn test_interned_invalid_utf8() {
+    let encoded = [2, 0xC3, 0x28];
+    let result: Result<(Interned, usize), _> = bincode::decode_from_slice_with_context(
+        &encoded,
+        bincode::config::standard(),
+        Interner::new(),
+    );
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::Utf8 { .. }
+    ));
+
+    let mut interner = Interner::new();
+    let (decoded, _): (Interned, usize) = bincode::decode_from_slice_with_context(
+        &encoded,
+        bincode::config::standard().with_lossy_utf8_strings(),
+        &mut interner,
+    )
+    .unwrap();
+    assert_eq!(&*decoded, "\u{FFFD}(");
+    assert!(interner.get("\u{FFFD}(").is_some());
+
+    // the limit counts the bytes of the string
+    let result: Result<(Interned, usize), _> = bincode::decode_from_slice_with_context(
+        &[3, b'a', b'b', b'c'],
+        bincode::config::standard().with_limit::<3>(),
+        Interner::new(),
+    );
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::LimitExceeded
+    ));
+}
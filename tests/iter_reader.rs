@@ -0,0 +1,132 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::read::{IterReader, Reader, TryIterReader};
+use bincode::error::DecodeError;
+use std::collections::VecDeque;
+
+/// The error of an input source that can fail
+#[derive(Debug)]
+struct Disconnected {
+    after: usize,
+}
+
+impl From<Disconnected> for DecodeError {
+    fn from(e: Disconnected) -> Self {
+        DecodeError::custom(format!("disconnected after {} bytes", e.after))
+    }
+}
+
+fn value() -> (u32, String, Vec<u16>) {
+    (300, String::from("iterator"), vec![1, 2, 1000])
+}
+
+#[test]
+fn test_plain_iterator() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(value(), config).unwrap();
+
+    let decoded: (u32, String, Vec<u16>) =
+        bincode::decode_from_iter(encoded.iter().copied(), config).unwrap();
+    assert_eq!(decoded, value());
+
+    // e.g. the drain of a ring buffer, which is left with the bytes after the value
+    let mut ring: VecDeque<u8> = encoded.iter().copied().collect();
+    ring.extend([7, 8]);
+    let mut drain = ring.drain(..);
+    let decoded: (u32, String, Vec<u16>) = bincode::decode_from_iter(&mut drain, config).unwrap();
+    assert_eq!(decoded, value());
+    assert_eq!(drain.collect::<Vec<_>>(), [7, 8]);
+
+    // an iterator without a known length
+    let decoded: (u32, String, Vec<u16>) =
+        bincode::decode_from_iter(encoded.iter().copied().filter(|_| true), config).unwrap();
+    assert_eq!(decoded, value());
+}
+
+#[test]
+fn test_multi_byte_reads_and_skips() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = IterReader::new(data.iter().copied());
+    assert_eq!(reader.remaining_hint(), Some(256));
+
+    let mut buffer = [0u8; 100];
+    reader.read(&mut buffer).unwrap();
+    assert_eq!(buffer[..], data[..100]);
+    reader.skip(50).unwrap();
+    assert_eq!(reader.remaining_hint(), Some(106));
+    reader.read(&mut buffer[..6]).unwrap();
+    assert_eq!(buffer[..6], [150, 151, 152, 153, 154, 155]);
+
+    assert!(matches!(
+        reader.skip(101),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    assert_eq!(reader.into_inner().next(), None);
+
+    // skipping without a size hint
+    let mut reader = IterReader::new(data.iter().copied().filter(|_| true));
+    assert_eq!(reader.remaining_hint(), None);
+    reader.skip(255).unwrap();
+    assert!(matches!(
+        reader.skip(3),
+        Err(DecodeError::UnexpectedEnd { additional: 2 })
+    ));
+}
+
+#[test]
+fn test_fallible_iterator_errors_mid_value() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(value(), config).unwrap();
+
+    let bytes = encoded.iter().enumerate().map(|(index, &byte)| {
+        if index < 6 {
+            Ok(byte)
+        } else {
+            Err(Disconnected { after: index })
+        }
+    });
+    let result: Result<(u32, String, Vec<u16>), _> =
+        bincode::decode_from_reader_with_offset(TryIterReader::new(bytes), config);
+    let err = result.unwrap_err();
+    assert_eq!(err.offset(), Some(4));
+    let DecodeError::Custom(inner) = err.into_inner() else {
+        panic!("expected a custom error");
+    };
+    assert_eq!(inner.to_string(), "disconnected after 6 bytes");
+
+    let bytes = encoded.iter().map(|&byte| Ok::<_, Disconnected>(byte));
+    let decoded: (u32, String, Vec<u16>) =
+        bincode::decode_from_reader(TryIterReader::new(bytes), config).unwrap();
+    assert_eq!(decoded, value());
+}
+
+#[test]
+fn test_exhausted_early() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(value(), config).unwrap();
+
+    let result: Result<(u32, String, Vec<u16>), _> =
+        bincode::decode_from_iter(encoded[..10].iter().copied(), config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { additional: 2 }
+    ));
+
+    let bytes = encoded[..10]
+        .iter()
+        .map(|&byte| Ok::<_, Disconnected>(byte));
+    let result: Result<(u32, String, Vec<u16>), _> =
+        bincode::decode_from_reader(TryIterReader::new(bytes), config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { additional: 2 }
+    ));
+
+    let result: Result<u32, _> = bincode::decode_from_iter(core::iter::empty(), config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+}
@@ -0,0 +1,271 @@
+#![cfg(all(feature = "derive", feature = "alloc"))]
+#![allow(dead_code)]
+
+extern crate bincode_next as bincode;
+
+use bincode::layout::{EncodedLayout, Field, Layout, Variant};
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+struct Header {
+    version: u8,
+    flags: Option<u16>,
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+enum Shape {
+    Empty,
+    Circle(f32),
+    Polygon { points: Vec<(i32, i32)> },
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+struct Message {
+    header: Header,
+    name: String,
+    shapes: Vec<Shape>,
+    checksum: [u8; 4],
+}
+
+mod reordered {
+    use super::{Header, Shape};
+
+    #[derive(bincode::Encode, bincode::EncodedLayout)]
+    pub struct Message {
+        pub name: String,
+        pub header: Header,
+        pub shapes: Vec<Shape>,
+        pub checksum: [u8; 4],
+    }
+}
+
+#[test]
+fn test_layout_json() {
+    let json = Message::layout().to_json(bincode::config::standard());
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"endian":"little","layout":{"kind":"struct","name":"Message","fields":["#,
+            r#"{"name":"header","layout":{"kind":"struct","name":"Header","fields":["#,
+            r#"{"name":"version","layout":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}},"#,
+            r#"{"name":"flags","layout":{"kind":"option","value":{"kind":"int","signed":false,"bits":16,"encoding":"varint"}}}"#,
+            r#"]}},"#,
+            r#"{"name":"name","layout":{"kind":"str","len":{"kind":"int","signed":false,"bits":64,"encoding":"varint"}}},"#,
+            r#"{"name":"shapes","layout":{"kind":"seq","len":{"kind":"int","signed":false,"bits":64,"encoding":"varint"},"element":"#,
+            r#"{"kind":"enum","name":"Shape","tag":{"kind":"int","signed":false,"bits":32,"encoding":"varint"},"variants":["#,
+            r#"{"name":"Empty","tag":0,"fields":[]},"#,
+            r#"{"name":"Circle","tag":1,"fields":[{"name":"0","layout":{"kind":"float","bits":32}}]},"#,
+            r#"{"name":"Polygon","tag":2,"fields":[{"name":"points","layout":{"kind":"seq","len":{"kind":"int","signed":false,"bits":64,"encoding":"varint"},"element":"#,
+            r#"{"kind":"tuple","elements":[{"kind":"int","signed":true,"bits":32,"encoding":"varint"},{"kind":"int","signed":true,"bits":32,"encoding":"varint"}]}}}]}"#,
+            r#"]}}},"#,
+            r#"{"name":"checksum","layout":{"kind":"array","len":4,"element":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}}}"#,
+            r#"]}}"#,
+        )
+    );
+}
+
+#[test]
+fn test_layout_config() {
+    let config = bincode::config::standard()
+        .with_big_endian()
+        .with_fixed_int_encoding();
+    let json = Header::layout().to_json(config);
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"endian":"big","layout":{"kind":"struct","name":"Header","fields":["#,
+            r#"{"name":"version","layout":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}},"#,
+            r#"{"name":"flags","layout":{"kind":"option","value":{"kind":"int","signed":false,"bits":16,"encoding":"fixed"}}}"#,
+            r#"]}}"#,
+        )
+    );
+}
+
+#[test]
+fn test_layout_length_encoding() {
+    let layout = Layout::Seq(Box::new(Layout::Str));
+    let len = |bits, encoding| {
+        format!(r#"{{"kind":"int","signed":false,"bits":{bits},"encoding":"{encoding}"}}"#)
+    };
+    let seq = |len: String| {
+        format!(
+            r#"{{"endian":"little","layout":{{"kind":"seq","len":{len},"element":{{"kind":"str","len":{len}}}}}}}"#
+        )
+    };
+
+    let standard = bincode::config::standard();
+    assert_eq!(layout.to_json(standard), seq(len(64, "varint")));
+    assert_eq!(
+        layout.to_json(standard.with_fixed_u32_lengths()),
+        seq(len(32, "fixed"))
+    );
+    assert_eq!(
+        layout.to_json(standard.with_fixed_u64_lengths()),
+        seq(len(64, "fixed"))
+    );
+    let legacy = bincode::config::legacy();
+    assert_eq!(layout.to_json(legacy), seq(len(64, "fixed")));
+    assert_eq!(
+        layout.to_json(legacy.with_varint_lengths()),
+        seq(len(64, "varint"))
+    );
+}
+
+#[test]
+fn test_layout_reordered_fields() {
+    let config = bincode::config::standard();
+    assert_ne!(
+        Message::layout().to_json(config),
+        reordered::Message::layout().to_json(config)
+    );
+
+    let Layout::Struct { name, fields } = reordered::Message::layout() else {
+        panic!("expected a struct");
+    };
+    assert_eq!(name, "Message");
+    let names: Vec<_> = fields.iter().map(|f| f.name).collect();
+    assert_eq!(names, ["name", "header", "shapes", "checksum"]);
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+struct Wrapper<'a, T>(&'a str, T);
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+struct Unit;
+
+#[derive(bincode::EncodedLayout)]
+enum Empty {}
+
+#[test]
+fn test_layout_tree() {
+    assert_eq!(
+        <Wrapper<'_, Unit>>::layout(),
+        Layout::Struct {
+            name: "Wrapper",
+            fields: vec![
+                Field::new("0", Layout::Str),
+                Field::new(
+                    "1",
+                    Layout::Struct {
+                        name: "Unit",
+                        fields: vec![]
+                    }
+                ),
+            ],
+        }
+    );
+    assert_eq!(
+        Empty::layout(),
+        Layout::Enum {
+            name: "Empty",
+            tag: None,
+            variants: vec![]
+        }
+    );
+    assert_eq!(
+        <Result<u64, ()>>::layout(),
+        Layout::Enum {
+            name: "Result",
+            tag: None,
+            variants: vec![
+                Variant::new(
+                    "Ok",
+                    0,
+                    vec![Field::new(
+                        "0",
+                        Layout::Int {
+                            signed: false,
+                            bits: 64
+                        }
+                    )]
+                ),
+                Variant::new("Err", 1, vec![Field::new("0", Layout::Unit)]),
+            ],
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_layout_with_serde() {
+    #[derive(bincode::Encode, bincode::EncodedLayout)]
+    struct WithSerde<'a> {
+        #[bincode(with_serde)]
+        value: &'a str,
+    }
+
+    assert_eq!(
+        WithSerde::layout(),
+        Layout::Struct {
+            name: "WithSerde",
+            fields: vec![Field::new("value", Layout::Opaque { name: "&'a str" })],
+        }
+    );
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+#[bincode(tag_type = "u16")]
+enum Tagged {
+    A,
+    B(u8),
+}
+
+#[test]
+fn test_tag_type_layout() {
+    // the tag is fixed, regardless of the int encoding of the config
+    for json in [
+        Tagged::layout().to_json(bincode::config::standard()),
+        Tagged::layout().to_json(bincode::config::legacy()),
+    ] {
+        assert!(
+            json.contains(r#""tag":{"kind":"int","signed":false,"bits":16,"encoding":"fixed"}"#)
+        );
+    }
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+struct Frame {
+    id: u8,
+    #[bincode(remaining_bytes)]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn test_remaining_bytes_layout() {
+    assert_eq!(
+        Frame::layout(),
+        Layout::Struct {
+            name: "Frame",
+            fields: vec![
+                Field::new(
+                    "id",
+                    Layout::Int {
+                        signed: false,
+                        bits: 8
+                    }
+                ),
+                Field::new("payload", Layout::RemainingBytes),
+            ],
+        }
+    );
+}
+
+#[derive(bincode::Encode, bincode::EncodedLayout)]
+enum HashTagged {
+    #[bincode(discriminant = 0x1_0000_0000)]
+    A,
+    B(u8),
+}
+
+#[test]
+fn test_u64_tag_layout() {
+    let json = HashTagged::layout().to_json(bincode::config::legacy());
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"endian":"little","layout":{"kind":"enum","name":"HashTagged","tag":{"kind":"int","signed":false,"bits":64,"encoding":"fixed"},"variants":["#,
+            r#"{"name":"A","tag":4294967296,"fields":[]},"#,
+            r#"{"name":"B","tag":4294967297,"fields":[{"name":"0","layout":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}}]}"#,
+            r#"]}}"#,
+        )
+    );
+}
@@ -0,0 +1,310 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use bincode::{
+    config::{self, Config, LengthEncoding},
+    error::{DecodeError, EncodeError},
+};
+
+/// A `Vec` of 300 elements, whose length does not fit in a single varint byte
+fn long_vec() -> Vec<u8> {
+    vec![7; 300]
+}
+
+fn check<T, C>(value: &T, config: C, expected: &[u8])
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    C: Config,
+{
+    let encoded = bincode::encode_to_vec(value, config).unwrap();
+    assert_eq!(encoded, expected);
+    let (decoded, read): (T, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(read, encoded.len());
+    assert_eq!(&decoded, value);
+}
+
+#[test]
+fn test_length_encoding_of_config() {
+    let standard = config::standard();
+    assert_eq!(standard.length_encoding(), LengthEncoding::Int);
+    assert_eq!(
+        standard.with_varint_lengths().length_encoding(),
+        LengthEncoding::Varint
+    );
+    assert_eq!(
+        standard.with_fixed_u32_lengths().length_encoding(),
+        LengthEncoding::FixedU32
+    );
+    assert_eq!(
+        standard
+            .with_fixed_u64_lengths()
+            .with_int_encoded_lengths()
+            .length_encoding(),
+        LengthEncoding::Int
+    );
+}
+
+#[test]
+fn test_int_encoded_lengths() {
+    let standard = config::standard();
+    check(&String::from("ab"), standard, &[2, b'a', b'b']);
+    check(
+        &String::from("ab"),
+        config::legacy(),
+        &[2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b'],
+    );
+    let mut expected = vec![251, 44, 1];
+    expected.extend(long_vec());
+    check(&long_vec(), standard, &expected);
+}
+
+#[test]
+fn test_varint_lengths() {
+    let config = config::legacy().with_varint_lengths();
+    check(&String::from("ab"), config, &[2, b'a', b'b']);
+    check(&vec![1u16, 2], config, &[2, 1, 0, 2, 0]);
+
+    let mut expected = vec![251, 44, 1];
+    expected.extend(long_vec());
+    check(&long_vec(), config, &expected);
+    let mut expected = vec![251, 1, 44];
+    expected.extend(long_vec());
+    check(&long_vec(), config.with_big_endian(), &expected);
+}
+
+#[test]
+fn test_fixed_u32_lengths() {
+    let config = config::standard().with_fixed_u32_lengths();
+    check(&String::from("ab"), config, &[2, 0, 0, 0, b'a', b'b']);
+    check(&Vec::<u8>::new(), config, &[0, 0, 0, 0]);
+    check(&BTreeMap::from([(1u8, 2u8)]), config, &[1, 0, 0, 0, 1, 2]);
+
+    let mut expected = vec![44, 1, 0, 0];
+    expected.extend(long_vec());
+    check(&long_vec(), config, &expected);
+    let mut expected = vec![0, 0, 1, 44];
+    expected.extend(long_vec());
+    check(&long_vec(), config.with_big_endian(), &expected);
+
+    // a length that is cut off
+    let err = bincode::decode_from_slice::<Vec<u8>, _>(&[1, 0, 0], config).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
+
+#[test]
+fn test_fixed_u64_lengths() {
+    let config = config::standard().with_fixed_u64_lengths();
+    check(
+        &String::from("ab"),
+        config,
+        &[2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b'],
+    );
+    check(
+        &vec![String::from("a")],
+        config.with_big_endian(),
+        &[0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, b'a'],
+    );
+
+    let err = bincode::decode_from_slice::<Vec<u8>, _>(&[u8::MAX; 8], config).unwrap_err();
+    #[cfg(target_pointer_width = "64")]
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+    #[cfg(not(target_pointer_width = "64"))]
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::OutsideUsizeRange(u64::MAX)
+    ));
+}
+
+#[test]
+fn test_lengths_are_independent_of_ints() {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    struct Packet {
+        id: u32,
+        values: Vec<u64>,
+        name: String,
+    }
+
+    let packet = Packet {
+        id: 300,
+        values: vec![1, 1000],
+        name: String::from("hi"),
+    };
+
+    // varint ints with fixed u32 lengths
+    let config = config::standard().with_fixed_u32_lengths();
+    check(
+        &packet,
+        config,
+        &[
+            251, 44, 1, 2, 0, 0, 0, 1, 251, 232, 3, 2, 0, 0, 0, b'h', b'i',
+        ],
+    );
+
+    // fixed ints with varint lengths
+    let config = config::legacy().with_varint_lengths();
+    check(
+        &packet,
+        config,
+        &[
+            44, 1, 0, 0, 2, 1, 0, 0, 0, 0, 0, 0, 0, 232, 3, 0, 0, 0, 0, 0, 0, 2, b'h', b'i',
+        ],
+    );
+
+    // `usize` values are not lengths
+    check(
+        &300usize,
+        config::standard().with_fixed_u32_lengths(),
+        &[251, 44, 1],
+    );
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_length_too_large() {
+    /// A sequence that claims more entries than a `u32` can count, without any of them
+    struct Huge;
+
+    impl bincode::Encode for Huge {
+        fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            bincode::enc::encode_collection(encoder, u32::MAX as usize + 1, [0u8; 0])
+        }
+    }
+
+    let config = config::standard().with_fixed_u32_lengths();
+    let err = bincode::encode_to_vec(Huge, config).unwrap_err();
+    assert!(matches!(
+        err,
+        EncodeError::LengthTooLarge {
+            len: 0x1_0000_0000,
+            max: 0xFFFF_FFFF,
+        }
+    ));
+}
+
+/// The varint encoding of `len`, built by hand
+fn varint_len(len: u64) -> Vec<u8> {
+    if let Ok(len) = u32::try_from(len) {
+        [&[252][..], &len.to_le_bytes()].concat()
+    } else {
+        [&[253][..], &len.to_le_bytes()].concat()
+    }
+}
+
+/// Decodes a `Vec<u8>` whose length prefix is `prefix`, followed by a few bytes that are far less than the length
+fn decode_huge_len<C: Config>(prefix: &[u8], config: C) -> DecodeError {
+    let input = [prefix, &[1, 2, 3]].concat();
+    bincode::decode_from_slice::<Vec<u8>, _>(&input, config)
+        .unwrap_err()
+        .into_inner()
+}
+
+/// Checks the error of a length that claims more entries than there are
+fn check_huge_len<C: Config>(len: u64, prefix: &[u8], config: C) {
+    let err = decode_huge_len(prefix, config);
+    if usize::try_from(len).is_err() {
+        assert!(
+            matches!(err, DecodeError::OutsideUsizeRange(found) if found == len),
+            "{len}: {err:?}"
+        );
+    } else if config.limit().is_some() {
+        assert!(matches!(err, DecodeError::LimitExceeded), "{len}: {err:?}");
+    } else {
+        assert!(
+            matches!(err, DecodeError::UnexpectedEnd { .. }),
+            "{len}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn test_huge_length_prefixes() {
+    for len in [u64::from(u32::MAX), 1 << 32, 1 << 63] {
+        let varint = varint_len(len);
+        check_huge_len(len, &varint, config::standard());
+        check_huge_len(len, &varint, config::standard().with_limit::<1024>());
+        let config = config::legacy().with_varint_lengths();
+        check_huge_len(len, &varint, config);
+        check_huge_len(len, &varint, config.with_limit::<1024>());
+
+        let config = config::legacy();
+        check_huge_len(len, &len.to_le_bytes(), config);
+        check_huge_len(len, &len.to_le_bytes(), config.with_limit::<1024>());
+        let config = config::standard()
+            .with_fixed_u64_lengths()
+            .with_big_endian();
+        check_huge_len(len, &len.to_be_bytes(), config);
+        check_huge_len(len, &len.to_be_bytes(), config.with_limit::<1024>());
+
+        if let Ok(len) = u32::try_from(len) {
+            let config = config::standard().with_fixed_u32_lengths();
+            check_huge_len(len.into(), &len.to_le_bytes(), config);
+            check_huge_len(len.into(), &len.to_le_bytes(), config.with_limit::<1024>());
+        }
+    }
+
+    // the same prefixes are written for lengths that fit in a `usize`
+    #[cfg(target_pointer_width = "64")]
+    {
+        /// Encodes the length prefix of a collection of `len` entries
+        fn encode_prefix<C: Config>(len: usize, config: C) -> Vec<u8> {
+            struct Prefix(Vec<u8>);
+
+            impl bincode::enc::write::Writer for Prefix {
+                fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+                    self.0.extend_from_slice(bytes);
+                    Ok(())
+                }
+            }
+
+            struct Claim(usize);
+
+            impl bincode::Encode for Claim {
+                fn encode<E: bincode::enc::Encoder>(
+                    &self,
+                    encoder: &mut E,
+                ) -> Result<(), EncodeError> {
+                    bincode::enc::encode_collection(encoder, self.0, [0u8; 0])
+                }
+            }
+
+            // the collection fails after its length, as it has no entries
+            let mut prefix = Prefix(Vec::new());
+            bincode::encode_into_writer(Claim(len), &mut prefix, config).unwrap_err();
+            prefix.0
+        }
+
+        for len in [u32::MAX as usize, 1 << 32, 1 << 63] {
+            let wire_len = len as u64;
+            assert_eq!(encode_prefix(len, config::standard()), varint_len(wire_len));
+            assert_eq!(encode_prefix(len, config::legacy()), wire_len.to_le_bytes());
+            assert_eq!(
+                encode_prefix(len, config::standard().with_fixed_u64_lengths()),
+                wire_len.to_le_bytes()
+            );
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_lengths() {
+    let config = config::standard().with_fixed_u32_lengths();
+    let value = (String::from("ab"), vec![1u8], BTreeMap::from([(1u8, 2u8)]));
+    let encoded = bincode::serde::encode_to_vec(&value, config).unwrap();
+    assert_eq!(
+        encoded,
+        [2, 0, 0, 0, b'a', b'b', 1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 2]
+    );
+    assert_eq!(encoded, bincode::encode_to_vec(&value, config).unwrap());
+    let decoded = bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, (value, encoded.len()));
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    enc::{
+        write::{BufferedWriter, SeekWriter, SizeWriter, SliceWriter, Writer},
+        Encode, Encoder, EncoderImpl,
+    },
+    error::EncodeError,
+    shared::SharedRc,
+    IoWriter,
+};
+use std::{io::Cursor, rc::Rc};
+
+/// A section with a length prefix, which contains its own sections
+struct Section {
+    name: &'static str,
+    children: Vec<Section>,
+}
+
+impl Encode for Section {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.with_length_prefix(|encoder| {
+            self.name.encode(encoder)?;
+            (self.children.len() as u32).encode(encoder)?;
+            for child in &self.children {
+                child.encode(encoder)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn tree() -> Section {
+    let leaf = |name| Section {
+        name,
+        children: Vec::new(),
+    };
+    Section {
+        name: "root",
+        children: vec![
+            Section {
+                name: "branch",
+                children: vec![leaf("leaf one"), leaf("leaf two")],
+            },
+            leaf(""),
+        ],
+    }
+}
+
+/// Checks the length prefixes of a section encoded by `tree`, collects the names of the sections in order, and returns the length of the section including its prefix
+fn check_sections(bytes: &[u8], names: &mut Vec<String>) -> usize {
+    let len = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+    let section = &bytes[8..8 + len];
+    let (name, mut offset): (String, usize) =
+        bincode::decode_from_slice(section, bincode::config::standard()).unwrap();
+    let (children, read): (u32, usize) =
+        bincode::decode_from_slice(&section[offset..], bincode::config::standard()).unwrap();
+    offset += read;
+    names.push(name);
+    for _ in 0..children {
+        offset += check_sections(&section[offset..], names);
+    }
+    assert_eq!(offset, len);
+    8 + len
+}
+
+fn expected_names() -> Vec<String> {
+    ["root", "branch", "leaf one", "leaf two", ""]
+        .map(String::from)
+        .to_vec()
+}
+
+#[test]
+fn test_length_prefix_vec() {
+    let encoded = bincode::encode_to_vec(tree(), bincode::config::standard()).unwrap();
+    let mut names = Vec::new();
+    assert_eq!(check_sections(&encoded, &mut names), encoded.len());
+    assert_eq!(names, expected_names());
+
+    let encoded =
+        bincode::encode_to_vec(tree(), bincode::config::legacy().with_big_endian()).unwrap();
+    let len = u64::from_be_bytes(encoded[..8].try_into().unwrap());
+    assert_eq!(len as usize, encoded.len() - 8);
+}
+
+#[test]
+fn test_length_prefix_writers() {
+    let config = bincode::config::standard();
+    let expected = bincode::encode_to_vec(tree(), config).unwrap();
+
+    // written in place
+    let mut cursor = Cursor::new(vec![0xAA; 3]);
+    cursor.set_position(3);
+    let mut writer = IoWriter::new_seekable(&mut cursor);
+    assert!(writer.as_seek_writer().is_some());
+    bincode::encode_into_writer(tree(), &mut writer, config).unwrap();
+    assert_eq!(writer.bytes_written(), expected.len());
+    assert_eq!(cursor.position() as usize, 3 + expected.len());
+    assert_eq!(cursor.get_ref()[..3], [0xAA; 3]);
+    assert_eq!(cursor.get_ref()[3..], expected);
+
+    let mut buffer = [0u8; 128];
+    let mut writer = BufferedWriter::<_, 16>::with_buffer_size(SliceWriter::new(&mut buffer));
+    bincode::encode_into_writer(tree(), &mut writer, config).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buffer[..expected.len()], expected);
+
+    let mut writer = SizeWriter::default();
+    bincode::encode_into_writer(tree(), &mut writer, config).unwrap();
+    assert_eq!(writer.bytes_written, expected.len());
+
+    // buffered, because the writer is not a `SeekWriter`
+    let mut output = Vec::new();
+    let mut writer = IoWriter::new(&mut output);
+    assert!(writer.as_seek_writer().is_none());
+    bincode::encode_into_writer(tree(), &mut writer, config).unwrap();
+    assert_eq!(output, expected);
+    let mut output = Vec::new();
+    bincode::encode_into_std_write(tree(), &mut output, config).unwrap();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_write_at() {
+    let mut buffer = [0u8; 8];
+    let mut writer = SliceWriter::new(&mut buffer);
+    writer.write(&[1, 2, 3, 4]).unwrap();
+    SeekWriter::write_at(&mut writer, 1, &[5, 6]).unwrap();
+    assert!(SeekWriter::write_at(&mut writer, 3, &[7, 8]).is_err());
+    assert_eq!(SeekWriter::position(&writer), 4);
+    assert_eq!(writer.into_written(), [1, 5, 6, 4]);
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = IoWriter::new_seekable(&mut cursor);
+    writer.write(&[1, 2, 3, 4]).unwrap();
+    writer.write_at(0, &[9]).unwrap();
+    assert!(writer.write_at(4, &[9]).is_err());
+    writer.write(&[5]).unwrap();
+    assert_eq!(cursor.into_inner(), [9, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_length_prefix_shared_values() {
+    let config = bincode::config::standard();
+    let shared = SharedRc(Rc::new(String::from("shared")));
+    let mut buffer = [0u8; 32];
+    let mut encoder = EncoderImpl::new(SliceWriter::new(&mut buffer), config);
+    shared.encode(&mut encoder).unwrap();
+    encoder
+        .with_length_prefix(|encoder| shared.encode(encoder))
+        .unwrap();
+    shared.encode(&mut encoder).unwrap();
+    let encoded = encoder.into_writer().into_written();
+
+    // the section refers to the value that was encoded before it
+    let first = 1 + 1 + "shared".len();
+    assert_eq!(encoded[first..first + 8], 1u64.to_le_bytes());
+    assert_eq!(encoded[first + 8..], [1, 1]);
+}
+
+#[test]
+fn test_length_prefix_error() {
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 10];
+    let err = bincode::encode_into_slice(tree(), &mut buffer, config).unwrap_err();
+    assert!(matches!(err, EncodeError::SliceTooSmall { .. }));
+}
@@ -0,0 +1,628 @@
+#![cfg(feature = "alloc")]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::vec::Vec;
+use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+use bincode::error::DecodeError;
+
+/// A container that checks the limit before claiming anything, like a custom container would
+#[derive(Debug, PartialEq, bincode::Encode)]
+struct Bag<T>(Vec<T>);
+
+const TOO_LARGE: &str = "bag is too large for the limit";
+
+impl<Context, T: Decode<Context>> Decode<Context> for Bag<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = usize::decode(decoder)?;
+        if let Some(remaining) = decoder.limit_remaining() {
+            if len.saturating_mul(core::mem::size_of::<T>()) > remaining {
+                return Err(DecodeError::Other(TOO_LARGE));
+            }
+        }
+        decoder.claim_container_read::<T>(len)?;
+
+        let mut entries = Vec::with_capacity(len);
+        for _ in 0..len {
+            decoder.unclaim_container_element::<T>();
+            entries.push(T::decode(decoder)?);
+        }
+        Ok(Self(entries))
+    }
+}
+
+fn decode<T: Decode<()>, C: bincode::config::Config>(
+    bytes: &[u8],
+    config: C,
+) -> Result<(T, usize), DecodeError> {
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config, ());
+    let value = T::decode(&mut decoder)?;
+    Ok((value, decoder.bytes_claimed()))
+}
+
+#[test]
+fn test_limit_remaining_bails_early() {
+    let bag = Bag((0..100u32).collect());
+    let encoded = bincode::encode_to_vec(&bag, bincode::config::standard()).unwrap();
+
+    // the limit is too small for the claimed length, so decoding fails before reading any entries
+    let config = bincode::config::standard().with_limit::<64>();
+    let err = decode::<Bag<u32>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::Other(TOO_LARGE)), "{err:?}");
+
+    // the length prefix counts against the limit as well
+    let config = bincode::config::standard().with_limit::<400>();
+    let err = decode::<Bag<u32>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::Other(TOO_LARGE)), "{err:?}");
+
+    let config = bincode::config::standard().with_limit::<408>();
+    let (decoded, claimed) = decode::<Bag<u32>, _>(&encoded, config).unwrap();
+    assert_eq!(decoded, bag);
+    // the claims of the container are balanced, and match those of a `Vec`
+    let (_, vec_claimed) = decode::<Vec<u32>, _>(&encoded, config).unwrap();
+    assert_eq!(claimed, vec_claimed);
+
+    // without a limit nothing is counted, and the bag is always decoded
+    let config = bincode::config::standard();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&encoded), config, ());
+    assert_eq!(decoder.limit_remaining(), None);
+    assert_eq!(Bag::<u32>::decode(&mut decoder).unwrap(), bag);
+    assert_eq!(decoder.bytes_claimed(), 0);
+}
+
+#[test]
+fn test_limit_remaining_nested() {
+    let bags = Bag((0..10u16).map(|i| Bag((0..i).collect())).collect());
+    let encoded = bincode::encode_to_vec(&bags, bincode::config::standard()).unwrap();
+
+    // the outer bag claims room for 10 inner bags, which the inner bags give back while they are decoded
+    let config = bincode::config::standard().with_limit::<1000>();
+    let (decoded, claimed) = decode::<Bag<Bag<u16>>, _>(&encoded, config).unwrap();
+    assert_eq!(decoded, bags);
+    let (_, vec_claimed) = decode::<Vec<Vec<u16>>, _>(&encoded, config).unwrap();
+    assert_eq!(claimed, vec_claimed);
+
+    // 10 inner bags do not fit in the limit
+    let config = bincode::config::standard().with_limit::<100>();
+    let err = decode::<Bag<Bag<u16>>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::Other(TOO_LARGE)), "{err:?}");
+
+    // a limit that is exhausted reports 0 remaining, not an underflow
+    let mut decoder = DecoderImpl::new(
+        SliceReader::new(&encoded),
+        bincode::config::standard().with_limit::<4>(),
+        (),
+    );
+    assert!(decoder.claim_bytes_read(5).is_err());
+    assert_eq!(decoder.limit_remaining(), Some(0));
+}
+
+/// A third-party collection that is implemented only with the public helpers
+#[derive(Debug, PartialEq)]
+struct Stack<T>(Vec<T>);
+
+impl<T: bincode::Encode> bincode::Encode for Stack<T> {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        bincode::enc::encode_collection(encoder, self.0.len(), self.0.iter().rev())
+    }
+}
+
+impl<Context, T: Decode<Context>> Decode<Context> for Stack<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut entries: Vec<T> =
+            bincode::de::CollectionDecoder::<_, T>::new(decoder)?.collect::<Result<_, _>>()?;
+        entries.reverse();
+        Ok(Self(entries))
+    }
+}
+
+impl<'de, Context, T: bincode::BorrowDecode<'de, Context>> bincode::BorrowDecode<'de, Context>
+    for Stack<T>
+{
+    fn borrow_decode<D: bincode::de::BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let mut entries = bincode::de::CollectionDecoder::<_, T>::new_borrowed(decoder)?;
+        let mut stack = Vec::with_capacity(entries.initial_capacity());
+        while let Some(entry) = entries.next_borrowed_entry()? {
+            stack.insert(0, entry);
+        }
+        Ok(Self(stack))
+    }
+}
+
+/// Decodes `bytes` as a `Stack<T>` and as a `Vec<T>`, and checks that both behave the same
+fn check_same_as_vec<T, C>(bytes: &[u8], config: C)
+where
+    T: Decode<()> + for<'de> bincode::BorrowDecode<'de, ()> + core::fmt::Debug + PartialEq,
+    C: bincode::config::Config,
+{
+    let stack = decode::<Stack<T>, _>(bytes, config);
+    let vec = decode::<Vec<T>, _>(bytes, config);
+    match (stack, vec) {
+        (Ok((mut stack, stack_claimed)), Ok((vec, vec_claimed))) => {
+            stack.0.reverse();
+            assert_eq!(stack.0, vec);
+            assert_eq!(stack_claimed, vec_claimed);
+        }
+        (Err(stack), Err(vec)) => assert_eq!(stack.to_string(), vec.to_string()),
+        (stack, vec) => panic!("{stack:?} != {vec:?}"),
+    }
+
+    let stack = bincode::borrow_decode_from_slice::<Stack<T>, _>(bytes, config);
+    let vec = bincode::borrow_decode_from_slice::<Vec<T>, _>(bytes, config);
+    match (stack, vec) {
+        (Ok((mut stack, stack_len)), Ok((vec, vec_len))) => {
+            stack.0.reverse();
+            assert_eq!((stack.0, stack_len), (vec, vec_len));
+        }
+        (Err(stack), Err(vec)) => assert_eq!(stack.to_string(), vec.to_string()),
+        (stack, vec) => panic!("{stack:?} != {vec:?}"),
+    }
+}
+
+#[test]
+fn test_collection_helpers_same_as_vec() {
+    fn check<T, const LIMIT: usize>(bytes: &[u8])
+    where
+        T: Decode<()> + for<'de> bincode::BorrowDecode<'de, ()> + core::fmt::Debug + PartialEq,
+    {
+        check_same_as_vec::<T, _>(bytes, bincode::config::standard().with_limit::<LIMIT>());
+        check_same_as_vec::<T, _>(bytes, bincode::config::standard());
+    }
+
+    let strings: Vec<String> = (0..20).map(|i| "s".repeat(i)).collect();
+    let encoded = bincode::encode_to_vec(&strings, bincode::config::standard()).unwrap();
+    // the stack encodes its entries in reverse
+    let stack = Stack(strings.iter().rev().cloned().collect());
+    assert_eq!(
+        bincode::encode_to_vec(&stack, bincode::config::standard()).unwrap(),
+        encoded
+    );
+
+    for cut in [0, 1, 10, encoded.len() / 2, encoded.len()] {
+        let bytes = &encoded[..cut];
+        check::<String, 0>(bytes);
+        check::<String, 100>(bytes);
+        check::<String, 480>(bytes);
+        check::<String, 500>(bytes);
+        check::<String, 1000>(bytes);
+    }
+
+    let numbers: Vec<u64> = (0..100).map(|i| i * 1_000_003).collect();
+    let encoded = bincode::encode_to_vec(&numbers, bincode::config::standard()).unwrap();
+    for cut in [0, 1, encoded.len() / 2, encoded.len()] {
+        let bytes = &encoded[..cut];
+        check::<u64, 100>(bytes);
+        check::<u64, 800>(bytes);
+        check::<u64, 808>(bytes);
+        check::<u64, 1000>(bytes);
+        check::<(), 100>(bytes);
+    }
+
+    // the errors of the entries have the same index
+    let mut invalid = bincode::encode_to_vec(&strings[..3], bincode::config::standard()).unwrap();
+    invalid.extend_from_slice(&[1, 0xFF]);
+    invalid[0] = 4;
+    check::<String, 1000>(&invalid);
+    let err = decode::<Stack<String>, _>(&invalid, bincode::config::standard()).unwrap_err();
+    assert!(err.to_string().contains("[3]"), "{err}");
+}
+
+/// Decodes a `Vec<T>` with a length of `len` (at least 1) and no entries after it. Returns whether the collection accepted the length, in which case decoding fails at its first entry instead.
+fn entries_fit<T: Decode<()>, C: bincode::config::Config>(len: u64, config: C) -> bool {
+    let encoded = bincode::encode_to_vec(len, config).unwrap();
+    match decode::<Vec<T>, _>(&encoded, config).map(|_| ()) {
+        Err(DecodeError::LimitExceeded) => false,
+        // vecs of bytes are read at once, without the index of an entry
+        Err(DecodeError::Index { index: 0, .. } | DecodeError::UnexpectedEnd { .. }) => true,
+        other => panic!(
+            "{len} entries of {}: {other:?}",
+            core::any::type_name::<T>()
+        ),
+    }
+}
+
+#[test]
+fn test_limit_same_for_entry_types() {
+    let config = bincode::config::standard().with_limit::<64>();
+    // the length claims 8 bytes, and every entry is at least a single byte, whether it is a `u8`, a pointer to one or a string
+    for len in 1..100 {
+        let fits = 8 + len <= 64;
+        assert_eq!(entries_fit::<u8, _>(len, config), fits, "{len}");
+        assert_eq!(entries_fit::<Box<u8>, _>(len, config), fits, "{len}");
+        assert_eq!(entries_fit::<String, _>(len, config), fits, "{len}");
+        assert_eq!(entries_fit::<Option<u64>, _>(len, config), fits, "{len}");
+        // zero-sized entries need no input, but count as a byte each
+        let encoded = bincode::encode_to_vec(len, config).unwrap();
+        assert_eq!(
+            decode::<Vec<()>, _>(&encoded, config).is_ok(),
+            fits,
+            "{len}"
+        );
+    }
+
+    // types that are always encoded in more bytes claim more
+    for len in 1..20 {
+        assert_eq!(entries_fit::<f64, _>(len, config), 8 + len * 8 <= 64);
+        assert_eq!(entries_fit::<[u8; 4], _>(len, config), 8 + len * 4 <= 64);
+        assert_eq!(entries_fit::<(u8, f32), _>(len, config), 8 + len * 5 <= 64);
+        assert_eq!(
+            entries_fit::<Box<[u16; 3]>, _>(len, config),
+            8 + len * 3 <= 64
+        );
+    }
+    assert_eq!(<[u8; 1024] as Decode<()>>::MIN_ENCODED_SIZE, 1024);
+    assert_eq!(<[(); 1024] as Decode<()>>::MIN_ENCODED_SIZE, 0);
+    assert_eq!(<([u8; 64], f64) as Decode<()>>::MIN_ENCODED_SIZE, 72);
+
+    // with their entries present, a `Vec<u8>` and a `Vec<Box<u8>>` of the same bytes are accepted and rejected alike
+    for len in 50..60 {
+        let bytes: Vec<u8> = (0..len).collect();
+        let encoded = bincode::encode_to_vec(&bytes, bincode::config::standard()).unwrap();
+        let vec = decode::<Vec<u8>, _>(&encoded, config);
+        let boxed = decode::<Vec<Box<u8>>, _>(&encoded, config);
+        match (vec, boxed) {
+            (Ok((vec, vec_claimed)), Ok((boxed, boxed_claimed))) => {
+                assert_eq!(vec, boxed.into_iter().map(|b| *b).collect::<Vec<_>>());
+                assert_eq!(vec_claimed, boxed_claimed);
+            }
+            (Err(vec), Err(boxed)) => assert_eq!(vec.to_string(), boxed.to_string()),
+            (vec, boxed) => panic!("{vec:?} != {boxed:?}"),
+        }
+    }
+
+    // the fixed int fast path still counts the full size of every entry
+    let numbers: Vec<u32> = (0..100).collect();
+    let config = bincode::config::legacy().with_limit::<1000>();
+    let encoded = bincode::encode_to_vec(&numbers, config).unwrap();
+    let (_, claimed) = decode::<Vec<u32>, _>(&encoded, config).unwrap();
+    assert_eq!(claimed, encoded.len());
+    let config = bincode::config::legacy().with_limit::<407>();
+    assert!(matches!(
+        decode::<Vec<u32>, _>(&encoded, config),
+        Err(DecodeError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn test_encode_collection_wrong_len() {
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+
+    let err = bincode::encode_into_slice(WrongLen(2, &[1, 2, 3]), &mut buffer, config).unwrap_err();
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::Other("collection has more entries than its length")
+    ));
+    let err = bincode::encode_into_slice(WrongLen(4, &[1, 2, 3]), &mut buffer, config).unwrap_err();
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::Other("collection has fewer entries than its length")
+    ));
+    let len = bincode::encode_into_slice(WrongLen(3, &[1, 2, 3]), &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], [3, 1, 2, 3]);
+}
+
+/// Claims a length that may not match its entries
+struct WrongLen(usize, &'static [u8]);
+
+impl bincode::Encode for WrongLen {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        bincode::enc::encode_collection(encoder, self.0, self.1)
+    }
+}
+
+#[test]
+fn test_runtime_limit_same_as_const() {
+    use std::collections::{BTreeMap, HashMap, VecDeque};
+
+    fn check<T, const LIMIT: usize>(bytes: &[u8])
+    where
+        T: Decode<()> + core::fmt::Debug + PartialEq,
+    {
+        let config = bincode::config::standard();
+        let runtime = decode::<T, _>(bytes, config.with_runtime_limit(LIMIT));
+        let constant = decode::<T, _>(bytes, config.with_limit::<LIMIT>());
+        match (runtime, constant) {
+            (Ok(runtime), Ok(constant)) => assert_eq!(runtime, constant),
+            (Err(runtime), Err(constant)) => assert_eq!(runtime.to_string(), constant.to_string()),
+            (runtime, constant) => panic!("{runtime:?} != {constant:?}"),
+        }
+
+        // without a limit, nothing is counted
+        let (_, claimed) = decode::<T, _>(bytes, config).unwrap();
+        assert_eq!(claimed, 0);
+    }
+
+    let config = bincode::config::standard();
+    let numbers: Vec<u32> = (0..100).collect();
+    let encoded = bincode::encode_to_vec(&numbers, config).unwrap();
+    check::<Vec<u32>, 0>(&encoded);
+    check::<Vec<u32>, 399>(&encoded);
+    check::<Vec<u32>, 401>(&encoded);
+    check::<Vec<u32>, 1000>(&encoded);
+    check::<VecDeque<u32>, 399>(&encoded);
+    check::<VecDeque<u32>, 401>(&encoded);
+    check::<Bag<u32>, 400>(&encoded);
+    check::<Bag<u32>, 408>(&encoded);
+
+    let map: HashMap<u32, String> = (0..20).map(|i| (i, "s".repeat(i as usize))).collect();
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    check::<HashMap<u32, String>, 100>(&encoded);
+    check::<HashMap<u32, String>, 1000>(&encoded);
+    check::<BTreeMap<u32, String>, 100>(&encoded);
+    check::<BTreeMap<u32, String>, 1000>(&encoded);
+
+    // the limit is part of the value, not the type
+    let limits: Vec<_> = [10, 1000]
+        .into_iter()
+        .map(|limit| config.with_runtime_limit(limit))
+        .collect();
+    assert!(decode::<Vec<u32>, _>(
+        &bincode::encode_to_vec(&numbers, config).unwrap(),
+        limits[0]
+    )
+    .is_err());
+    assert!(decode::<Vec<u32>, _>(
+        &bincode::encode_to_vec(&numbers, config).unwrap(),
+        limits[1]
+    )
+    .is_ok());
+    assert_eq!(bincode::config::Config::limit(&limits[0]), Some(10));
+
+    // the builder keeps the limit
+    let config = config
+        .with_runtime_limit(12)
+        .with_fixed_int_encoding()
+        .with_big_endian();
+    assert_eq!(bincode::config::Config::limit(&config), Some(12));
+    let mut decoder = DecoderImpl::new(SliceReader::new(&[]), config, ());
+    assert_eq!(decoder.limit_remaining(), Some(12));
+    assert!(matches!(
+        decoder.claim_bytes_read(13),
+        Err(DecodeError::LimitExceeded)
+    ));
+}
+
+#[derive(Debug, PartialEq, bincode::Encode, bincode::Decode)]
+struct Header {
+    name: alloc::string::String,
+    flags: Vec<u8>,
+}
+
+fn message(header_flags: usize) -> Vec<u8> {
+    let header = Header {
+        name: "message".into(),
+        flags: alloc::vec![1; header_flags],
+    };
+    let body: Vec<u32> = (0..100).collect();
+    bincode::encode_to_vec((header, body), bincode::config::standard()).unwrap()
+}
+
+fn decode_message<C: bincode::config::Config>(
+    bytes: &[u8],
+    config: C,
+    header_limit: usize,
+) -> Result<(Header, Vec<u32>, usize), DecodeError> {
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config, ());
+    let header = decoder.with_sub_limit("header", header_limit, |d| Header::decode(d))?;
+    let after_header = decoder.bytes_claimed();
+    let body = Vec::decode(&mut decoder)?;
+    Ok((header, body, after_header))
+}
+
+#[test]
+fn test_sub_limit() {
+    let config = bincode::config::standard().with_limit::<1000>();
+    let bytes = message(10);
+
+    let (header, body, after_header) = decode_message(&bytes, config, 100).unwrap();
+    assert_eq!(header.flags.len(), 10);
+    assert_eq!(body.len(), 100);
+    // the claims of the header stay claimed, and the body claims the rest
+    assert!(after_header > 0 && after_header <= 100);
+
+    // the header exceeds its limit, while the whole message would fit
+    let bytes = message(200);
+    assert!(decode::<(Header, Vec<u32>), _>(&bytes, config).is_ok());
+    let err = decode_message(&bytes, config, 100).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::SubLimitExceeded {
+            scope: "header",
+            limit: 100
+        }
+    ));
+
+    // the header fits in its limit, but the body does not fit in what is left
+    let err = decode_message(&bytes, config.with_limit::<600>(), 300).unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::LimitExceeded));
+    assert!(decode_message(&message(10), config.with_limit::<600>(), 300).is_ok());
+}
+
+#[test]
+fn test_sub_limit_without_config_limit() {
+    let config = bincode::config::standard();
+
+    // the claims of the scope are checked, but not counted once the scope is left
+    let (header, body, after_header) = decode_message(&message(10), config, 100).unwrap();
+    assert_eq!(header.flags.len(), 10);
+    assert_eq!(body.len(), 100);
+    assert_eq!(after_header, 0);
+
+    let bytes = message(200);
+    assert!(decode::<(Header, Vec<u32>), _>(&bytes, config).is_ok());
+    let err = decode_message(&bytes, config, 100).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::SubLimitExceeded {
+            scope: "header",
+            limit: 100
+        }
+    ));
+
+    let bytes = bincode::encode_to_vec((1u8, [2u8; 20]), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    assert_eq!(decoder.limit_remaining(), None);
+    let err = decoder
+        .with_sub_limit("scope", 10, |d| {
+            assert_eq!(d.limit_remaining(), Some(10));
+            assert_eq!(u8::decode(d)?, 1);
+            assert_eq!((d.bytes_claimed(), d.limit_remaining()), (1, Some(9)));
+            <[u8; 20]>::decode(d)
+        })
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::SubLimitExceeded {
+            scope: "scope",
+            limit: 10
+        }
+    ));
+}
+
+#[test]
+fn test_nested_sub_limits() {
+    let config = bincode::config::standard().with_limit::<100>();
+    let bytes = bincode::encode_to_vec((1u8, [2u8; 20], 3u8), config).unwrap();
+
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    let value = decoder
+        .with_sub_limit("outer", 30, |d| {
+            let first = u8::decode(d)?;
+            assert_eq!(d.limit_remaining(), Some(29));
+            let array: [u8; 20] = d.with_sub_limit("inner", 20, |d| {
+                let array = <[u8; 20]>::decode(d)?;
+                assert_eq!((d.bytes_claimed(), d.limit_remaining()), (20, Some(0)));
+                Ok(array)
+            })?;
+            assert_eq!((d.bytes_claimed(), d.limit_remaining()), (21, Some(9)));
+            Ok((first, array))
+        })
+        .unwrap();
+    assert_eq!(value, (1, [2; 20]));
+    assert_eq!(
+        (decoder.bytes_claimed(), decoder.limit_remaining()),
+        (21, Some(79))
+    );
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 3);
+
+    // the inner scope fails first
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    let err = decoder
+        .with_sub_limit("outer", 30, |d| {
+            d.with_sub_limit("inner", 19, |d| <(u8, [u8; 20])>::decode(d))
+        })
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::SubLimitExceeded {
+            scope: "inner",
+            limit: 19
+        }
+    ));
+
+    // the outer scope is smaller than the inner one
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config, ());
+    let err = decoder
+        .with_sub_limit("outer", 10, |d| {
+            d.with_sub_limit("inner", 50, |d| <(u8, [u8; 20])>::decode(d))
+        })
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::SubLimitExceeded {
+            scope: "outer",
+            limit: 10
+        }
+    ));
+}
+
+#[derive(Debug, PartialEq, bincode::Encode, bincode::Decode)]
+enum Nested {
+    Leaf,
+    Node(Box<Nested>),
+}
+
+fn nested(depth: usize) -> Nested {
+    (0..depth).fold(Nested::Leaf, |inner, _| Nested::Node(Box::new(inner)))
+}
+
+/// Decodes `bytes`, and returns the result without the path of the error and how many bytes were read
+fn decode_and_count<T: Decode<()>, C: bincode::config::Config>(
+    bytes: &[u8],
+    config: C,
+) -> (Result<T, DecodeError>, usize) {
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config, ());
+    let result = T::decode(&mut decoder).map_err(DecodeError::into_inner);
+    let remaining = bincode::de::read::Reader::remaining_hint(decoder.reader()).unwrap();
+    (result, bytes.len() - remaining)
+}
+
+#[test]
+fn test_limit_nested_enums() {
+    // with fixed int encoding, every tag claims exactly the 4 bytes that it takes
+    let config = bincode::config::legacy();
+    let value = nested(100);
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded.len(), 404);
+    let (decoded, _) = decode_and_count::<Nested, _>(&encoded, config.with_runtime_limit(404));
+    assert_eq!(decoded.unwrap(), value);
+    for limit in [0, 1, 100, 403] {
+        let (result, read) =
+            decode_and_count::<Nested, _>(&encoded, config.with_runtime_limit(limit));
+        assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+        assert!(read <= limit, "read {read} bytes with a limit of {limit}");
+    }
+
+    // a varint tag claims more than it takes, so the limit is never exceeded by the input that is read
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded.len(), 101);
+    for limit in [0, 50, 100, 403] {
+        let (result, read) =
+            decode_and_count::<Nested, _>(&encoded, config.with_runtime_limit(limit));
+        assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+        assert!(read <= limit, "read {read} bytes with a limit of {limit}");
+    }
+
+    // the enums of the standard library claim their tags and their payload
+    type Deep = Option<Result<Result<Option<std::net::IpAddr>, u8>, u8>>;
+    let value: Deep = Some(Ok(Ok(Some(std::net::Ipv6Addr::LOCALHOST.into()))));
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(value, config).unwrap();
+    assert_eq!(encoded.len(), 1 + 4 + 4 + 1 + 4 + 16);
+    let (decoded, _) =
+        decode_and_count::<Deep, _>(&encoded, config.with_runtime_limit(encoded.len()));
+    assert_eq!(decoded.unwrap(), value);
+    for limit in 0..encoded.len() {
+        let (result, read) =
+            decode_and_count::<Deep, _>(&encoded, config.with_runtime_limit(limit));
+        assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+        assert!(read <= limit, "read {read} bytes with a limit of {limit}");
+    }
+
+    // a container of addresses reaches the limit as soon as its entries could not fit
+    let addresses = vec![std::net::Ipv6Addr::LOCALHOST; 50];
+    let encoded = bincode::encode_to_vec(&addresses, config).unwrap();
+    assert_eq!(encoded.len(), 808);
+    let (result, read) =
+        decode_and_count::<Vec<std::net::Ipv6Addr>, _>(&encoded, config.with_runtime_limit(807));
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+    assert_eq!(read, 8);
+    let (decoded, _) =
+        decode_and_count::<Vec<std::net::Ipv6Addr>, _>(&encoded, config.with_runtime_limit(808));
+    assert_eq!(decoded.unwrap(), addresses);
+
+    let (result, read) = decode_and_count::<char, _>("ü".as_bytes(), config.with_runtime_limit(1));
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)));
+    assert_eq!(read, 1);
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::error::DecodeError;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Decodes `entries`, which are encoded like a map in the order they are given
+fn decode_map<C: Config>(
+    entries: &[(u32, &str)],
+    config: C,
+) -> Result<BTreeMap<u32, String>, DecodeError> {
+    let encoded = bincode::encode_to_vec(entries, config).unwrap();
+    bincode::decode_from_slice(&encoded, config).map(|(map, _)| map)
+}
+
+/// The map that inserting `entries` one by one results in
+fn inserted(entries: &[(u32, &str)]) -> BTreeMap<u32, String> {
+    let mut map = BTreeMap::new();
+    for (key, value) in entries {
+        map.insert(*key, value.to_string());
+    }
+    map
+}
+
+#[test]
+fn test_sorted() {
+    fn check(config: impl Config) {
+        let map: BTreeMap<u32, String> = (0..1000).map(|i| (i * 3, i.to_string())).collect();
+        let set: BTreeSet<String> = map.values().cloned().collect();
+        let encoded = bincode::encode_to_vec(&map, config).unwrap();
+        let (decoded, _): (BTreeMap<u32, String>, _) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, map);
+
+        let encoded = bincode::encode_to_vec(&set, config).unwrap();
+        let (decoded, _): (BTreeSet<String>, _) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, set);
+    }
+    check(config::standard());
+    check(config::standard().with_strict_map_order());
+    check(config::legacy().with_strict_map_order());
+
+    let empty = decode_map(&[], config::standard().with_strict_map_order()).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_unsorted_permissive() {
+    let config = config::standard();
+    for entries in [
+        &[(3, "c"), (1, "a"), (2, "b")][..],
+        &[(1, "a"), (2, "b"), (5, "e"), (4, "d"), (6, "f"), (0, "z")],
+        &[(9, "i"), (8, "h"), (7, "g")],
+    ] {
+        assert_eq!(decode_map(entries, config).unwrap(), inserted(entries));
+    }
+
+    let encoded = bincode::encode_to_vec(vec![5u8, 1, 3], config).unwrap();
+    let (set, _): (BTreeSet<u8>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(set, BTreeSet::from([1, 3, 5]));
+}
+
+#[test]
+fn test_unsorted_strict() {
+    let config = config::standard().with_strict_map_order();
+    let err = decode_map(&[(1, "a"), (2, "b"), (5, "e"), (4, "d")], config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 3 }
+    ));
+
+    let err = decode_map(&[(3, "c"), (1, "a")], config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 1 }
+    ));
+
+    let encoded = bincode::encode_to_vec(vec!["b", "a"], config).unwrap();
+    let err = bincode::decode_from_slice::<BTreeSet<String>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 1 }
+    ));
+
+    // a `HashMap` has no order
+    let encoded = bincode::encode_to_vec(vec![(3u32, 0u8), (1, 0)], config).unwrap();
+    let (map, _): (HashMap<u32, u8>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_duplicate_keys() {
+    // the value of the last duplicate wins, like with `insert`
+    let entries = [(1, "a"), (2, "b"), (2, "second"), (3, "c"), (1, "third")];
+    let map = decode_map(&entries, config::standard()).unwrap();
+    assert_eq!(map, inserted(&entries));
+    assert_eq!(map[&2], "second");
+    assert_eq!(map[&1], "third");
+
+    let err = decode_map(&entries, config::standard().with_strict_map_order()).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 2 }
+    ));
+
+    let encoded = bincode::encode_to_vec(vec![1u8, 1, 2], config::standard()).unwrap();
+    let (set, _): (BTreeSet<u8>, _) =
+        bincode::decode_from_slice(&encoded, config::standard()).unwrap();
+    assert_eq!(set, BTreeSet::from([1, 2]));
+    let config = config::standard().with_strict_map_order();
+    let err = bincode::decode_from_slice::<BTreeSet<u8>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 1 }
+    ));
+}
+
+#[test]
+fn test_borrowed() {
+    let config = config::standard().with_strict_map_order();
+    let map = BTreeMap::from([("a", "1"), ("b", "2")]);
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    let (decoded, _): (BTreeMap<&str, &str>, _) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, map);
+
+    let encoded = bincode::encode_to_vec(vec![("b", "2"), ("a", "1")], config).unwrap();
+    let err =
+        bincode::borrow_decode_from_slice::<BTreeMap<&str, &str>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::NonCanonicalMapOrder { index: 1 }
+    ));
+
+    let encoded = bincode::encode_to_vec(vec!["b", "a"], config::standard()).unwrap();
+    let (decoded, _): (BTreeSet<&str>, _) =
+        bincode::borrow_decode_from_slice(&encoded, config::standard()).unwrap();
+    assert_eq!(decoded, BTreeSet::from(["a", "b"]));
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "memmap2")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct Record<'a> {
+    id: u32,
+    name: &'a str,
+    tags: Vec<&'a str>,
+}
+
+fn records() -> Vec<Record<'static>> {
+    vec![
+        Record {
+            id: 1,
+            name: "first",
+            tags: vec!["a", "b"],
+        },
+        Record {
+            id: 2,
+            name: "second",
+            tags: vec![],
+        },
+        Record {
+            id: 300,
+            name: "a somewhat longer third record",
+            tags: vec!["c"],
+        },
+    ]
+}
+
+fn map(records: &[Record], config: impl bincode::config::Config) -> (memmap2::Mmap, Vec<usize>) {
+    let mut file = tempfile::tempfile().unwrap();
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+    for record in records {
+        offsets.push(offset);
+        offset += bincode::encode_into_std_write(record, &mut file, config).unwrap();
+    }
+    // Safety: the file is not modified while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+    assert_eq!(mmap.len(), offset);
+    (mmap, offsets)
+}
+
+fn assert_borrowed_from(value: &str, mmap: &memmap2::Mmap) {
+    let range = mmap.as_ptr_range();
+    let ptr = value.as_ptr();
+    assert!(range.start <= ptr && ptr < range.end);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // miri does not like `tempfile`
+fn test_decode_from_mmap() {
+    let config = bincode::config::standard();
+    let expected = records();
+    let mut file = tempfile::tempfile().unwrap();
+    let len = bincode::encode_into_std_write(&expected, &mut file, config).unwrap();
+    // Safety: the file is not modified while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+
+    let (decoded, read): (Vec<Record>, usize) = bincode::decode_from_mmap(&mmap, config).unwrap();
+    assert_eq!(decoded, expected);
+    assert_eq!(read, len);
+    for record in &decoded {
+        assert_borrowed_from(record.name, &mmap);
+        for tag in &record.tags {
+            assert_borrowed_from(tag, &mmap);
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // miri does not like `tempfile`
+fn test_decode_records_from_mmap() {
+    let config = bincode::config::standard();
+    let expected = records();
+    let (mmap, offsets) = map(&expected, config);
+
+    let mut records = bincode::decode_records_from_mmap::<Record, _>(&mmap, config);
+    for (record, offset) in expected.iter().zip(&offsets) {
+        let (decoded_offset, decoded) = records.next().unwrap().unwrap();
+        assert_eq!(decoded_offset, *offset);
+        assert_eq!(&decoded, record);
+        assert_borrowed_from(decoded.name, &mmap);
+    }
+    assert!(records.next().is_none());
+    assert_eq!(records.offset(), mmap.len());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // miri does not like `tempfile`
+fn test_decode_records_limit_per_record() {
+    // every record fits in the limit on its own, but all of them together do not
+    let config = bincode::config::standard().with_limit::<80>();
+    let expected = records();
+    let (mmap, _) = map(&expected, config);
+    let all: Result<((Record, Record, Record), usize), _> =
+        bincode::decode_from_mmap(&mmap, config);
+    assert!(matches!(
+        all.unwrap_err().inner(),
+        DecodeError::LimitExceeded
+    ));
+
+    let decoded = bincode::decode_records_from_mmap::<Record, _>(&mmap, config)
+        .map(|record| record.map(|(_, record)| record))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(decoded, expected);
+
+    // the long record does not fit in a smaller limit
+    let config = bincode::config::standard().with_limit::<56>();
+    let mut records = bincode::decode_records_from_mmap::<Record, _>(&mmap, config);
+    assert_eq!(records.next().unwrap().unwrap().1, expected[0]);
+    assert_eq!(records.next().unwrap().unwrap().1, expected[1]);
+    let err = records.next().unwrap().unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded));
+    assert!(records.next().is_none());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // miri does not like `tempfile`
+fn test_decode_records_truncated() {
+    let config = bincode::config::standard();
+    let expected = records();
+    let mut file = tempfile::tempfile().unwrap();
+    bincode::encode_into_std_write(&expected[0], &mut file, config).unwrap();
+    bincode::encode_into_std_write(&expected[1], &mut file, config).unwrap();
+    // a record that was cut off while writing
+    std::io::Write::write_all(&mut file, &[5, 3]).unwrap();
+    // Safety: the file is not modified while it is mapped
+    let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+
+    let results: Vec<_> = bincode::decode_records_from_mmap::<Record, _>(&mmap, config).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    let err = results[2].as_ref().unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::UnexpectedEnd { .. }));
+}
@@ -0,0 +1,204 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::de::read::{Reader, SliceReader};
+use bincode::error::DecodeError;
+use bincode::message::{decode_dispatch, encode_message, MessageSet};
+use bincode::IoWriter;
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Ping {
+    seq: u32,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Chat {
+    from: String,
+    text: String,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Move {
+    x: i32,
+    y: i32,
+}
+
+#[derive(PartialEq, Debug)]
+enum Event {
+    Ping(Ping),
+    Chat(Chat),
+    Move(Move),
+}
+
+const PING: u16 = 1;
+const CHAT: u16 = 2;
+const MOVE: u16 = 300;
+
+fn events<'a, C: Config>() -> MessageSet<'a, SliceReader<'a>, C, Event> {
+    let mut set = MessageSet::new();
+    set.on(PING, Event::Ping)
+        .on(CHAT, Event::Chat)
+        .on(MOVE, Event::Move);
+    set
+}
+
+fn chat() -> Chat {
+    Chat {
+        from: String::from("ann"),
+        text: String::from("hello"),
+    }
+}
+
+#[test]
+fn test_dispatch() {
+    fn check(config: impl Config) {
+        let mut stream = Vec::new();
+        encode_message(PING, &Ping { seq: 1 }, IoWriter::new(&mut stream), config).unwrap();
+        encode_message(CHAT, &chat(), IoWriter::new(&mut stream), config).unwrap();
+        encode_message(
+            MOVE,
+            &Move { x: -3, y: 4 },
+            IoWriter::new(&mut stream),
+            config,
+        )
+        .unwrap();
+        encode_message(PING, &Ping { seq: 2 }, IoWriter::new(&mut stream), config).unwrap();
+
+        let mut set = events();
+        let mut reader = SliceReader::new(&stream);
+        let mut decoded = Vec::new();
+        while reader.remaining_hint() != Some(0) {
+            decoded.push(decode_dispatch(&mut reader, config, &mut set).unwrap());
+        }
+        assert_eq!(
+            decoded,
+            [
+                Event::Ping(Ping { seq: 1 }),
+                Event::Chat(chat()),
+                Event::Move(Move { x: -3, y: 4 }),
+                Event::Ping(Ping { seq: 2 }),
+            ]
+        );
+    }
+    check(config::standard());
+    check(config::legacy());
+    check(config::standard().with_big_endian());
+}
+
+#[test]
+fn test_format() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    encode_message(PING, &Ping { seq: 5 }, IoWriter::new(&mut stream), config).unwrap();
+    // the id as 2 bytes, the length of the payload as 8 bytes, and the payload
+    assert_eq!(stream, [1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 5]);
+
+    // the header has a fixed size, also for ids that would be larger as a varint
+    let mut header = Vec::new();
+    encode_message(MOVE, &(), IoWriter::new(&mut header), config).unwrap();
+    assert_eq!(header, [44, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let mut header = Vec::new();
+    encode_message(
+        MOVE,
+        &(),
+        IoWriter::new(&mut header),
+        config.with_big_endian(),
+    )
+    .unwrap();
+    assert_eq!(header, [1, 44, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    // handlers can keep state of their own
+    let mut pings = Vec::new();
+    let mut set = MessageSet::new();
+    set.on(PING, |ping: Ping| pings.push(ping.seq));
+    assert!(set.contains(PING));
+    assert!(!set.contains(CHAT));
+    decode_dispatch(&mut SliceReader::new(&stream), config, &mut set).unwrap();
+    drop(set);
+    assert_eq!(pings, [5]);
+}
+
+#[test]
+fn test_unknown_message() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    encode_message(PING, &Ping { seq: 1 }, IoWriter::new(&mut stream), config).unwrap();
+    encode_message(7, &"not for us", IoWriter::new(&mut stream), config).unwrap();
+    encode_message(CHAT, &chat(), IoWriter::new(&mut stream), config).unwrap();
+
+    let mut set = events();
+    let mut reader = SliceReader::new(&stream);
+    assert_eq!(
+        decode_dispatch(&mut reader, config, &mut set).unwrap(),
+        Event::Ping(Ping { seq: 1 })
+    );
+    let err = decode_dispatch(&mut reader, config, &mut set).unwrap_err();
+    assert!(
+        matches!(err, DecodeError::UnknownMessage { id: 7, len: 11 }),
+        "{err:?}"
+    );
+    // the payload was skipped, so the stream continues with the next message
+    assert_eq!(
+        decode_dispatch(&mut reader, config, &mut set).unwrap(),
+        Event::Chat(chat())
+    );
+    assert_eq!(reader.remaining_hint(), Some(0));
+}
+
+#[test]
+fn test_payload_errors() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    // a `Chat` with a string that is not UTF-8
+    encode_message(
+        CHAT,
+        &("ann", &[0xFFu8][..]),
+        IoWriter::new(&mut stream),
+        config,
+    )
+    .unwrap();
+    // a payload that is too short for a `Move`
+    encode_message(MOVE, &1u8, IoWriter::new(&mut stream), config).unwrap();
+    // a newer `Ping` with a field at the end
+    encode_message(PING, &(9u32, 10u32), IoWriter::new(&mut stream), config).unwrap();
+    encode_message(PING, &Ping { seq: 11 }, IoWriter::new(&mut stream), config).unwrap();
+
+    let mut set = events();
+    let mut reader = SliceReader::new(&stream);
+    let err = decode_dispatch(&mut reader, config, &mut set).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Utf8 { .. }), "{err:?}");
+    let err = decode_dispatch(&mut reader, config, &mut set).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TakeLimitExceeded { limit: 1, .. }),
+        "{err:?}"
+    );
+    assert_eq!(
+        decode_dispatch(&mut reader, config, &mut set).unwrap(),
+        Event::Ping(Ping { seq: 9 })
+    );
+    assert_eq!(
+        decode_dispatch(&mut reader, config, &mut set).unwrap(),
+        Event::Ping(Ping { seq: 11 })
+    );
+    assert_eq!(reader.remaining_hint(), Some(0));
+
+    // the errors of the handler are passed on as they are
+    let mut stream = Vec::new();
+    encode_message(PING, &Ping { seq: 0 }, IoWriter::new(&mut stream), config).unwrap();
+    let mut set = MessageSet::new();
+    set.on(PING, |ping: Ping| {
+        if ping.seq == 0 {
+            Err("sequence numbers start at 1")
+        } else {
+            Ok(ping.seq)
+        }
+    });
+    let result = decode_dispatch(&mut SliceReader::new(&stream), config, &mut set).unwrap();
+    assert_eq!(result, Err("sequence numbers start at 1"));
+
+    // the message is cut off
+    let err = decode_dispatch(&mut SliceReader::new(&stream[..5]), config, &mut set).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { .. }), "{err:?}");
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{
+        read::{BorrowReader, Reader, SliceReader, TakeReader},
+        BorrowDecode, Decode, DecoderImpl,
+    },
+    enc::{
+        write::{BufferedWriter, SliceWriter, Writer},
+        Encode, EncoderImpl,
+    },
+    error::{DecodeError, EncodeError},
+    IoWriter,
+};
+
+/// Sums the bytes that are written to the inner writer
+struct ChecksumWriter<W> {
+    inner: W,
+    sum: u32,
+}
+
+impl<W: Writer> Writer for ChecksumWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.sum = bytes
+            .iter()
+            .fold(self.sum, |sum, &byte| sum.wrapping_add(u32::from(byte)));
+        self.inner.write(bytes)
+    }
+}
+
+/// Encodes `value` with a writer that is taken by value, like helper functions often do
+fn encode_with<W: Writer>(value: impl Encode, writer: W) -> Result<(), EncodeError> {
+    bincode::encode_into_writer(value, writer, bincode::config::standard())
+}
+
+/// Decodes a `T` with a reader that is taken by value
+fn decode_with<T: Decode<()>, R: Reader>(reader: R) -> Result<T, DecodeError> {
+    let mut decoder = DecoderImpl::new(reader, bincode::config::standard(), ());
+    T::decode(&mut decoder)
+}
+
+#[test]
+fn test_mut_slice_reader() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec((5u32, "text", [1u8, 2, 3]), config).unwrap();
+
+    let mut reader = SliceReader::new(&encoded);
+    let first: u32 = bincode::decode_from_reader(&mut reader, config).unwrap();
+    let second: String = decode_with(&mut reader).unwrap();
+    assert_eq!((first, second.as_str()), (5, "text"));
+
+    // the reader can be wrapped in an adapter, and used again afterwards
+    let mut take = TakeReader::new(&mut reader, 2);
+    let too_large: Result<[u8; 3], _> = decode_with(&mut take);
+    assert!(matches!(
+        too_large,
+        Err(DecodeError::TakeLimitExceeded { limit: 2, .. })
+    ));
+    let start: [u8; 2] = decode_with(&mut take).unwrap();
+    assert_eq!((start, take.remaining()), ([1, 2], 0));
+    let last: u8 = decode_with(&mut reader).unwrap();
+    assert_eq!(last, 3);
+    assert!(reader.peek_read(1).is_none());
+}
+
+#[test]
+fn test_mut_borrow_reader() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(("first", "second"), config).unwrap();
+
+    // both borrow from `encoded`, not from the reference to the reader
+    let (first, second): (&str, &str) = {
+        let mut reader = SliceReader::new(&encoded);
+        let mut decoder = DecoderImpl::new(&mut reader, config, ());
+        let first = BorrowDecode::borrow_decode(&mut decoder).unwrap();
+        let mut decoder = DecoderImpl::new(&mut reader, config, ());
+        (first, BorrowDecode::borrow_decode(&mut decoder).unwrap())
+    };
+    assert_eq!((first, second), ("first", "second"));
+
+    let mut reader = SliceReader::new(&encoded);
+    let mut by_ref = &mut reader;
+    assert_eq!((&mut by_ref).take_bytes(1).unwrap(), [5]);
+    assert_eq!(by_ref.take_bytes(5).unwrap(), b"first");
+}
+
+#[test]
+fn test_peek_read_through_references() {
+    let data = [1, 2, 3, 4];
+    let mut reader = SliceReader::new(&data);
+    let mut by_ref = &mut reader;
+    let mut by_ref_ref = &mut by_ref;
+    assert_eq!(Reader::peek_read(&mut by_ref_ref, 2), Some(&[1, 2][..]));
+    Reader::consume(&mut by_ref_ref, 2);
+    assert_eq!(Reader::peek_read(&mut by_ref_ref, 3), None);
+
+    let dyn_reader: &mut dyn Reader = &mut reader;
+    let mut by_ref = dyn_reader;
+    assert_eq!(Reader::peek_read(&mut by_ref, 2), Some(&[3, 4][..]));
+    let value: u8 = decode_with(&mut by_ref).unwrap();
+    assert_eq!(value, 3);
+    assert_eq!(Reader::peek_read(&mut by_ref, 1), Some(&[4][..]));
+}
+
+#[test]
+fn test_dyn_reader() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec((1000u64, vec![7u8; 3]), config).unwrap();
+
+    let mut slice_reader = SliceReader::new(&encoded);
+    let reader: &mut dyn Reader = &mut slice_reader;
+    let number: u64 = bincode::decode_from_reader(&mut *reader, config).unwrap();
+    let bytes: Vec<u8> = decode_with(reader).unwrap();
+    assert_eq!((number, bytes), (1000, vec![7; 3]));
+}
+
+#[test]
+fn test_mut_writers() {
+    let config = bincode::config::standard();
+    let expected = bincode::encode_to_vec((5u32, "text"), config).unwrap();
+
+    let mut buffer = [0u8; 16];
+    let mut writer = SliceWriter::new(&mut buffer);
+    bincode::encode_into_writer(5u32, &mut writer, config).unwrap();
+    encode_with("text", &mut writer).unwrap();
+    assert_eq!(writer.bytes_written(), expected.len());
+    assert_eq!(writer.into_written(), expected);
+
+    let mut output = Vec::new();
+    let mut writer = IoWriter::new(&mut output);
+    encode_with(5u32, &mut writer).unwrap();
+    bincode::encode_into_writer("text", &mut writer, config).unwrap();
+    assert_eq!(writer.bytes_written(), expected.len());
+    assert_eq!(output, expected);
+
+    // adapters over a writer that is still needed afterwards
+    let mut buffer = [0u8; 16];
+    let mut writer = SliceWriter::new(&mut buffer);
+    let mut checksum = ChecksumWriter {
+        inner: &mut writer,
+        sum: 0,
+    };
+    encode_with((5u32, "text"), &mut checksum).unwrap();
+    let sum = checksum.sum;
+    let mut buffered = BufferedWriter::<_, 4>::with_buffer_size(&mut writer);
+    encode_with(sum, &mut buffered).unwrap();
+    buffered.finish().unwrap();
+    let written = writer.into_written();
+    assert_eq!(written[..expected.len()], expected);
+    let (decoded_sum, _): (u32, usize) =
+        bincode::decode_from_slice(&written[expected.len()..], config).unwrap();
+    assert_eq!(
+        decoded_sum,
+        expected.iter().map(|&byte| u32::from(byte)).sum::<u32>()
+    );
+}
+
+#[test]
+fn test_dyn_writer() {
+    let config = bincode::config::standard();
+    let expected = bincode::encode_to_vec((5u32, "text"), config).unwrap();
+
+    let mut output = Vec::new();
+    let mut io_writer = IoWriter::new(&mut output);
+    let writer: &mut dyn Writer = &mut io_writer;
+    encode_with(5u32, &mut *writer).unwrap();
+    let mut encoder = EncoderImpl::new(writer, config);
+    "text".encode(&mut encoder).unwrap();
+    assert_eq!(output, expected);
+
+    // the writer still supports writing length prefixes in place
+    let mut buffer = [0u8; 32];
+    let mut slice_writer = SliceWriter::new(&mut buffer);
+    let writer: &mut dyn Writer = &mut slice_writer;
+    assert!(writer.as_seek_writer().is_some());
+    let mut encoder = EncoderImpl::new(writer, config);
+    bincode::enc::Encoder::with_length_prefix(&mut encoder, |encoder| {
+        (5u32, "text").encode(encoder)
+    })
+    .unwrap();
+    let written = slice_writer.into_written();
+    assert_eq!(written[..8], (expected.len() as u64).to_le_bytes());
+    assert_eq!(written[8..], expected);
+}
@@ -0,0 +1,262 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::{read::Reader, Decode, Decoder, DecoderImpl},
+    error::DecodeError,
+};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// A reader whose `peek_read` returns at most 1 byte, however many bytes are asked for
+struct ShortPeekReader<'a>(&'a [u8]);
+
+impl Reader for ShortPeekReader<'_> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        bincode::de::read::SliceReader::new(self.0).read(bytes)?;
+        self.0 = &self.0[bytes.len()..];
+        Ok(())
+    }
+
+    fn peek_read(&mut self, _: usize) -> Option<&[u8]> {
+        self.0.get(..1)
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.0 = &self.0[n.min(self.0.len())..];
+    }
+}
+
+fn check_short_peek_read<C: Config>(config: C) {
+    let value = (
+        7u8,
+        1000u16,
+        70_000u32,
+        u64::MAX,
+        u128::MAX,
+        300usize,
+        -5_000i64,
+    );
+    let encoded = bincode::encode_to_vec(value, config).unwrap();
+    let decoded: (u8, u16, u32, u64, u128, usize, i64) =
+        bincode::decode_from_reader(ShortPeekReader(&encoded), config).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_short_peek_read() {
+    check_short_peek_read(config::standard());
+    check_short_peek_read(config::standard().with_big_endian());
+
+    let encoded = bincode::encode_to_vec("Second", config::standard()).unwrap();
+    let mut decoder = DecoderImpl::new(ShortPeekReader(&encoded), config::standard(), ());
+    let index =
+        bincode::de::decode_variant_name(&mut decoder, "Tag", &["First", "Second"]).unwrap();
+    assert_eq!(index, 1);
+}
+
+/// A value that un-claims more bytes than it claimed
+struct OverUnclaim;
+
+impl<Context> Decode<Context> for OverUnclaim {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.claim_bytes_read(1)?;
+        u8::decode(decoder)?;
+        decoder.unclaim_bytes_read(100);
+        Ok(Self)
+    }
+}
+
+#[test]
+fn test_over_unclaim() {
+    let config = config::standard().with_limit::<8>();
+    let (decoded, _): ((OverUnclaim, u32), usize) =
+        bincode::decode_from_slice(&[1, 2], config).unwrap();
+    assert_eq!(decoded.1, 2);
+
+    let mut decoder = DecoderImpl::new(bincode::de::read::SliceReader::new(&[1; 16]), config, ());
+    let (_, claimed) = decoder
+        .with_sub_limit("scope", 2, |d| {
+            OverUnclaim::decode(d)?;
+            Ok((u8::decode(d)?, d.bytes_claimed()))
+        })
+        .unwrap();
+    assert_eq!(claimed, 1);
+    // the limit still applies after the scope
+    assert!(matches!(
+        <[u8; 8]>::decode(&mut decoder),
+        Err(DecodeError::LimitExceeded)
+    ));
+}
+
+/// Decodes `T` from a length of `len` followed by a few bytes, from a slice and from a `std::io::Read`, and asserts that both fail
+fn check_huge_len<T: Decode<()> + std::fmt::Debug, C: Config>(len: u64, config: C) {
+    let mut bytes = bincode::encode_to_vec(len, config).unwrap();
+    bytes.extend([1, 2, 3]);
+    bincode::decode_from_slice::<T, _>(&bytes, config).unwrap_err();
+    bincode::decode_from_std_read::<T, _, _>(&mut &bytes[..], config).unwrap_err();
+}
+
+fn check_huge_lens<C: Config>(config: C) {
+    for len in [u64::MAX, usize::MAX as u64, isize::MAX as u64 + 1, 1 << 40] {
+        check_huge_len::<Vec<u64>, _>(len, config);
+        check_huge_len::<Vec<u8>, _>(len, config);
+        check_huge_len::<String, _>(len, config);
+        check_huge_len::<Box<[u32]>, _>(len, config);
+        check_huge_len::<Rc<[u16]>, _>(len, config);
+        check_huge_len::<Arc<str>, _>(len, config);
+        check_huge_len::<VecDeque<u128>, _>(len, config);
+        check_huge_len::<BinaryHeap<i8>, _>(len, config);
+        check_huge_len::<HashMap<u32, String>, _>(len, config);
+        check_huge_len::<HashSet<u64>, _>(len, config);
+        check_huge_len::<BTreeMap<u8, Vec<u8>>, _>(len, config);
+    }
+}
+
+#[test]
+fn test_huge_lengths_without_limit() {
+    check_huge_lens(config::standard());
+    check_huge_lens(config::legacy());
+}
+
+#[test]
+fn test_invalid_durations() {
+    let config = config::legacy();
+    for (secs, nanos) in [
+        (u64::MAX, 1_000_000_000),
+        (u64::MAX, u32::MAX),
+        (u64::MAX - 3, 4_000_000_000),
+    ] {
+        let bytes = bincode::encode_to_vec((secs, nanos), config).unwrap();
+        let err = bincode::decode_from_slice::<Duration, _>(&bytes, config).unwrap_err();
+        assert!(
+            matches!(err.inner(), DecodeError::InvalidDuration { .. }),
+            "{err:?}"
+        );
+        let err = bincode::decode_from_slice::<SystemTime, _>(&bytes, config).unwrap_err();
+        assert!(
+            matches!(
+                err.inner(),
+                DecodeError::InvalidDuration { .. } | DecodeError::InvalidSystemTime { .. }
+            ),
+            "{err:?}"
+        );
+    }
+
+    // the largest duration is valid, but is not a valid `SystemTime`
+    let bytes = bincode::encode_to_vec((u64::MAX, 999_999_999u32), config).unwrap();
+    assert_eq!(
+        bincode::decode_from_slice::<Duration, _>(&bytes, config)
+            .unwrap()
+            .0,
+        Duration::MAX
+    );
+    let err = bincode::decode_from_slice::<SystemTime, _>(&bytes, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::InvalidSystemTime { .. }),
+        "{err:?}"
+    );
+    let err = bincode::decode_from_slice::<SystemTime, _>(&bytes, config.with_exact_system_time())
+        .unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::InvalidSystemTime { .. }),
+        "{err:?}"
+    );
+}
+
+fn check_truncated<T: Decode<()> + std::fmt::Debug, C: Config>(bytes: &[u8], config: C) {
+    let _ = bincode::decode_from_slice::<T, _>(bytes, config);
+    let _ = bincode::decode_from_std_read::<T, _, _>(&mut &bytes[..], config);
+    let _ = bincode::decode_from_reader::<T, _, _>(ShortPeekReader(bytes), config);
+}
+
+fn check_truncated_varints<C: Config>(config: C) {
+    for discriminant in 0xFB..=0xFF {
+        for len in 0..17 {
+            let mut bytes = vec![discriminant];
+            bytes.extend(std::iter::repeat_n(0xFF, len));
+            check_truncated::<u16, _>(&bytes, config);
+            check_truncated::<u32, _>(&bytes, config);
+            check_truncated::<u64, _>(&bytes, config);
+            check_truncated::<u128, _>(&bytes, config);
+            check_truncated::<usize, _>(&bytes, config);
+            check_truncated::<i16, _>(&bytes, config);
+            check_truncated::<i32, _>(&bytes, config);
+            check_truncated::<i64, _>(&bytes, config);
+            check_truncated::<i128, _>(&bytes, config);
+            check_truncated::<isize, _>(&bytes, config);
+            check_truncated::<char, _>(&bytes, config);
+            check_truncated::<Vec<u8>, _>(&bytes, config);
+        }
+    }
+}
+
+#[test]
+fn test_truncated_varints() {
+    check_truncated_varints(config::standard());
+    check_truncated_varints(config::standard().with_big_endian());
+}
+
+type Owned = (
+    (Vec<String>, BTreeMap<u8, Vec<u16>>, HashMap<u32, String>),
+    (
+        Box<[u8]>,
+        Rc<str>,
+        Arc<[u32]>,
+        std::ffi::CString,
+        std::path::PathBuf,
+    ),
+    (
+        SystemTime,
+        Duration,
+        std::net::SocketAddr,
+        Option<f64>,
+        char,
+    ),
+    (
+        std::num::NonZeroU32,
+        Result<u8, i8>,
+        std::ops::Range<u64>,
+        Box<[u64; 3]>,
+    ),
+);
+type Borrowed<'a> = (&'a str, &'a [u8], std::borrow::Cow<'a, str>, Vec<&'a str>);
+
+fn decode_all<C: Config>(data: &[u8], config: C) {
+    let _ = bincode::decode_from_slice::<Owned, _>(data, config);
+    let _ = bincode::decode_from_slice::<Vec<Owned>, _>(data, config);
+    let _ = bincode::borrow_decode_from_slice::<Borrowed, _>(data, config);
+    let _ = bincode::decode_from_std_read::<Owned, _, _>(&mut &data[..], config);
+    let _ = bincode::decode_from_reader::<Owned, _, _>(ShortPeekReader(data), config);
+}
+
+/// A quick version of the `decode` fuzz target, over inputs that favor the bytes that start varints
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_arbitrary_bytes() {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for _ in 0..2000 {
+        let len = (next() % 64) as usize;
+        let data: Vec<u8> = (0..len)
+            .map(|_| match next() % 4 {
+                0 => 0xFB + (next() % 5) as u8,
+                1 => (next() % 4) as u8,
+                _ => next() as u8,
+            })
+            .collect();
+        decode_all(&data, config::standard());
+        decode_all(&data, config::legacy().with_big_endian());
+        decode_all(&data, config::standard().with_limit::<64>());
+        decode_all(&data, config::untrusted(256));
+    }
+}
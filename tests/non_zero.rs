@@ -0,0 +1,205 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    error::{DecodeError, IntegerType},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::{
+        NonZero, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+        NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+};
+
+/// Asserts that `result` failed because a `NonZero` of `non_zero_type` was zero
+fn assert_zero<T: core::fmt::Debug>(
+    result: Result<(T, usize), DecodeError>,
+    non_zero_type: IntegerType,
+) {
+    let err = result.unwrap_err();
+    match err.inner() {
+        DecodeError::NonZeroTypeIsZero {
+            non_zero_type: found,
+        } => {
+            assert_eq!(*found, non_zero_type);
+        }
+        inner => panic!("expected NonZeroTypeIsZero, found {inner:?}"),
+    }
+}
+
+fn decode<T: bincode::Decode<()>, C: Config>(
+    bytes: &[u8],
+    config: C,
+) -> Result<(T, usize), DecodeError> {
+    bincode::decode_from_slice(bytes, config)
+}
+
+fn borrow_decode<'a, T: bincode::BorrowDecode<'a, ()>, C: Config>(
+    bytes: &'a [u8],
+    config: C,
+) -> Result<(T, usize), DecodeError> {
+    bincode::borrow_decode_from_slice(bytes, config)
+}
+
+/// Checks the zero and valid values of a `NonZero` type, on its own, in an `Option` and in collections, with the given config
+fn check<T, C>(
+    values: &[T],
+    zero: impl Fn(C) -> Vec<u8>,
+    non_zero_type: fn() -> IntegerType,
+    config: C,
+) where
+    T: bincode::Encode
+        + bincode::Decode<()>
+        + for<'a> bincode::BorrowDecode<'a, ()>
+        + Copy
+        + Ord
+        + core::hash::Hash
+        + core::fmt::Debug,
+    C: Config,
+{
+    let zero = zero(config);
+
+    // zero on its own
+    assert_zero(decode::<T, _>(&zero, config), non_zero_type());
+    assert_zero(borrow_decode::<T, _>(&zero, config), non_zero_type());
+
+    // valid values
+    for value in values {
+        let encoded = bincode::encode_to_vec(value, config).unwrap();
+        assert_eq!(
+            encoded,
+            bincode::encode_to_vec(Some(*value), config).unwrap()[1..]
+        );
+        assert_eq!(
+            decode::<T, _>(&encoded, config).unwrap(),
+            (*value, encoded.len())
+        );
+        assert_eq!(
+            borrow_decode::<T, _>(&encoded, config).unwrap(),
+            (*value, encoded.len())
+        );
+    }
+
+    // inside an `Option`, `None` is a tag of 0 and `Some(0)` is rejected
+    assert_eq!(decode::<Option<T>, _>(&[0], config).unwrap(), (None, 1));
+    let some_zero: Vec<u8> = [1].into_iter().chain(zero.iter().copied()).collect();
+    assert_zero(decode::<Option<T>, _>(&some_zero, config), non_zero_type());
+    assert_zero(
+        borrow_decode::<Option<T>, _>(&some_zero, config),
+        non_zero_type(),
+    );
+
+    // inside collections, a zero after valid elements is rejected
+    let mut vec = bincode::encode_to_vec(values.len() as u64 + 1, config).unwrap();
+    for value in values {
+        vec.extend(bincode::encode_to_vec(value, config).unwrap());
+    }
+    vec.extend(&zero);
+    assert_zero(decode::<Vec<T>, _>(&vec, config), non_zero_type());
+    assert_zero(borrow_decode::<Vec<T>, _>(&vec, config), non_zero_type());
+    assert_zero(decode::<[T; 1], _>(&zero, config), non_zero_type());
+
+    let map: BTreeMap<T, T> = values.iter().map(|value| (*value, *value)).collect();
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    assert_eq!(
+        decode::<BTreeMap<T, T>, _>(&encoded, config).unwrap().0,
+        map
+    );
+    let map: HashMap<T, T> = map.into_iter().collect();
+    assert_eq!(decode::<HashMap<T, T>, _>(&encoded, config).unwrap().0, map);
+
+    // a zero key
+    let mut keys = bincode::encode_to_vec(1u64, config).unwrap();
+    keys.extend(&zero);
+    keys.extend(bincode::encode_to_vec(values[0], config).unwrap());
+    assert_zero(decode::<HashMap<T, T>, _>(&keys, config), non_zero_type());
+    assert_zero(decode::<BTreeMap<T, T>, _>(&keys, config), non_zero_type());
+}
+
+macro_rules! check_non_zero {
+    ($($non_zero:ty => $int:ty, $integer_type:ident;)*) => {
+        fn check_all<C: Config>(config: C) {
+            $(
+                check::<$non_zero, C>(
+                    &[
+                        <$non_zero>::new(1).unwrap(),
+                        <$non_zero>::new(<$int>::MAX).unwrap(),
+                        <$non_zero>::new(<$int>::MIN).unwrap_or(<$non_zero>::MAX),
+                        <$non_zero>::new(<$int>::MAX / 3).unwrap(),
+                    ],
+                    |config| bincode::encode_to_vec(0 as $int, config).unwrap(),
+                    || IntegerType::$integer_type,
+                    config,
+                );
+            )*
+        }
+    };
+}
+
+check_non_zero! {
+    NonZeroU8 => u8, U8;
+    NonZeroU16 => u16, U16;
+    NonZeroU32 => u32, U32;
+    NonZeroU64 => u64, U64;
+    NonZeroU128 => u128, U128;
+    NonZeroUsize => usize, Usize;
+    NonZeroI8 => i8, I8;
+    NonZeroI16 => i16, I16;
+    NonZeroI32 => i32, I32;
+    NonZeroI64 => i64, I64;
+    NonZeroI128 => i128, I128;
+    NonZeroIsize => isize, Isize;
+}
+
+#[test]
+fn test_varint() {
+    check_all(config::standard());
+    check_all(config::standard().with_big_endian());
+}
+
+#[test]
+fn test_fixint() {
+    check_all(config::legacy());
+    check_all(config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_generic_non_zero() {
+    // `NonZero<T>` is the same type as the aliases
+    let value = NonZero::<u32>::new(300).unwrap();
+    let encoded = bincode::encode_to_vec(value, config::standard()).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(300u32, config::standard()).unwrap()
+    );
+    assert_eq!(
+        decode::<NonZero<u32>, _>(&encoded, config::standard())
+            .unwrap()
+            .0,
+        value
+    );
+    assert_zero(
+        decode::<NonZero<i128>, _>(&[0; 16], config::legacy()),
+        IntegerType::I128,
+    );
+}
+
+#[test]
+fn test_display() {
+    let err = decode::<NonZeroU32, _>(&[0], config::standard()).unwrap_err();
+    assert_eq!(err.inner().to_string(), "NonZero<u32> can not be zero");
+    let err = decode::<NonZeroIsize, _>(&[0; 8], config::legacy()).unwrap_err();
+    assert_eq!(err.inner().to_string(), "NonZero<isize> can not be zero");
+}
+
+#[test]
+fn test_skip_zero() {
+    // the bytes of a zero are skipped like any other integer, as skipping never decodes the value
+    use bincode::de::{read::SliceReader, DecoderImpl, SkipDecode};
+    let mut decoder = DecoderImpl::new(SliceReader::new(&[0, 0, 0, 0, 7]), config::legacy(), ());
+    NonZeroU32::skip(&mut decoder).unwrap();
+    assert_eq!(decoder.into_reader().remaining(), [7]);
+}
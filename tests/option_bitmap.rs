@@ -0,0 +1,218 @@
+#![cfg(feature = "derive")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use bincode::layout::{EncodedLayout, Layout};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Default, Clone)]
+#[bincode(option_bitmap)]
+struct Settings {
+    version: u16,
+    a: Option<u8>,
+    b: Option<u32>,
+    c: Option<String>,
+    d: core::option::Option<Vec<u8>>,
+    e: Option<bool>,
+    name: String,
+    f: Option<u8>,
+    g: Option<u64>,
+    h: std::option::Option<i16>,
+    i: Option<(u8, u8)>,
+    checksum: u32,
+}
+
+/// The same fields, without the bitmap
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Default, Clone)]
+struct PlainSettings {
+    version: u16,
+    a: Option<u8>,
+    b: Option<u32>,
+    c: Option<String>,
+    d: Option<Vec<u8>>,
+    e: Option<bool>,
+    name: String,
+    f: Option<u8>,
+    g: Option<u64>,
+    h: Option<i16>,
+    i: Option<(u8, u8)>,
+    checksum: u32,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(option_bitmap, pad_struct_to = 16)]
+struct Borrowed<'a> {
+    title: Option<&'a str>,
+    #[bincode(pad_to = 3)]
+    padded: Option<u8>,
+    #[bincode(owned)]
+    owned: Option<String>,
+    bytes: Option<&'a [u8]>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::EncodedLayout, PartialEq, Debug)]
+#[bincode(option_bitmap)]
+struct Small(Option<u8>, u8, #[bincode(pad_to = 2)] Option<u8>);
+
+fn all_some() -> Settings {
+    Settings {
+        version: 2,
+        a: Some(1),
+        b: Some(2),
+        c: Some(String::from("c")),
+        d: Some(vec![4]),
+        e: Some(true),
+        name: String::from("n"),
+        f: Some(6),
+        g: Some(7),
+        h: Some(-8),
+        i: Some((9, 9)),
+        checksum: 10,
+    }
+}
+
+fn plain(settings: &Settings) -> PlainSettings {
+    let Settings {
+        version,
+        a,
+        b,
+        c,
+        d,
+        e,
+        name,
+        f,
+        g,
+        h,
+        i,
+        checksum,
+    } = settings.clone();
+    PlainSettings {
+        version,
+        a,
+        b,
+        c,
+        d,
+        e,
+        name,
+        f,
+        g,
+        h,
+        i,
+        checksum,
+    }
+}
+
+fn round_trip(settings: &Settings) -> Vec<u8> {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(settings, config).unwrap();
+    let (decoded, len): (Settings, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(&decoded, settings);
+    assert_eq!(len, encoded.len());
+    encoded
+}
+
+#[test]
+fn test_all_none() {
+    let settings = Settings {
+        version: 1,
+        name: String::from("n"),
+        checksum: 3,
+        ..Settings::default()
+    };
+    let encoded = round_trip(&settings);
+    // 9 options in 2 bytes, followed by the fields that are not options
+    assert_eq!(encoded, [0, 0, 1, 1, b'n', 3]);
+
+    let plain = bincode::encode_to_vec(plain(&settings), bincode::config::standard()).unwrap();
+    assert_eq!(plain.len(), encoded.len() + 7);
+}
+
+#[test]
+fn test_all_some() {
+    let settings = all_some();
+    let encoded = round_trip(&settings);
+    assert_eq!(encoded[..2], [0xFF, 0x01]);
+    assert_eq!(
+        encoded[2..],
+        [2, 1, 2, 1, b'c', 1, 4, 1, 1, b'n', 6, 7, 15, 9, 9, 10]
+    );
+
+    // the bitmap takes one byte more than the 9 tags if every option is `Some`
+    let plain = bincode::encode_to_vec(plain(&settings), bincode::config::standard()).unwrap();
+    assert_eq!(plain.len() + 2, encoded.len() + 9);
+}
+
+#[test]
+fn test_alternating() {
+    let mut settings = all_some();
+    settings.b = None;
+    settings.d = None;
+    settings.f = None;
+    settings.h = None;
+    let encoded = round_trip(&settings);
+    assert_eq!(encoded[..2], [0b0101_0101, 0b1]);
+    assert_eq!(encoded[2..], [2, 1, 1, b'c', 1, 1, b'n', 7, 9, 9, 10]);
+
+    let mut settings = all_some();
+    settings.a = None;
+    settings.c = None;
+    settings.e = None;
+    settings.g = None;
+    settings.i = None;
+    let encoded = round_trip(&settings);
+    assert_eq!(encoded[..2], [0b1010_1010, 0b0]);
+    assert_eq!(encoded[2..], [2, 2, 1, 4, 1, b'n', 6, 15, 10]);
+}
+
+#[test]
+fn test_invalid_bitmap() {
+    let config = bincode::config::standard();
+    let result: Result<(Settings, usize), _> =
+        bincode::decode_from_slice(&[0, 0b10, 1, 1, b'n', 3], config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::InvalidOptionBitmap {
+            options: 9,
+            found: 0b10
+        }
+    ));
+}
+
+#[test]
+fn test_borrowed_and_attributes() {
+    let config = bincode::config::standard();
+    let value = Borrowed {
+        title: Some("t"),
+        padded: Some(5),
+        owned: None,
+        bytes: Some(&[1, 2]),
+    };
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    // `padded` has an attribute, so it keeps its tag and is not in the bitmap
+    assert_eq!(
+        encoded,
+        [0b101, 1, b't', 1, 5, 0, 2, 1, 2, 0, 0, 0, 0, 0, 0, 0]
+    );
+    let (decoded, len): (Borrowed, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (value, 16));
+
+    let value = Small(None, 4, Some(5));
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded, [0, 4, 1, 5]);
+    let (decoded, _): (Small, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_option_bitmap_layout() {
+    let Layout::Struct { fields, .. } = Small::layout() else {
+        panic!("expected a struct layout");
+    };
+    assert_eq!(fields[0].layout, Layout::bitmap_option(u8::layout()));
+    assert_eq!(fields[1].layout, u8::layout());
+    assert_eq!(fields[2].layout, Layout::padded(2, Option::<u8>::layout()));
+    assert!(Small::layout()
+        .to_json(bincode::config::legacy())
+        .contains(r#""layout":{"kind":"bitmap_option","value":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}}"#));
+}
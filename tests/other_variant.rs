@@ -0,0 +1,220 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::de::{read::SliceReader, DecoderImpl, SkipDecode};
+use bincode::error::DecodeError;
+use bincode::layout::{EncodedLayout, Layout};
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+    #[bincode(other)]
+    Unknown,
+}
+
+/// A newer version of `Level`
+#[derive(bincode::Encode)]
+enum NewLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    #[bincode(discriminant = 100)]
+    Fatal,
+}
+
+#[derive(
+    bincode::Encode, bincode::Decode, bincode::SkipDecode, bincode::EncodedLayout, PartialEq, Debug,
+)]
+#[bincode(length_prefixed)]
+enum Event {
+    Start {
+        id: u32,
+        name: String,
+    },
+    Stop(u32),
+    Ping,
+    #[bincode(other)]
+    Unknown(u32),
+}
+
+/// A newer version of `Event`
+#[derive(bincode::Encode)]
+#[bincode(length_prefixed)]
+enum NewEvent {
+    Start {
+        id: u32,
+        name: String,
+    },
+    Stop(u32),
+    Ping,
+    Pause {
+        seconds: u64,
+        reason: String,
+    },
+    #[bincode(discriminant = 0xFFFF_FFFF)]
+    Resume,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(length_prefixed, tag_type = "u8")]
+enum Borrowed<'a> {
+    Name(&'a str),
+    #[bincode(other)]
+    Other {
+        tag: u32,
+    },
+}
+
+#[derive(bincode::Encode)]
+#[bincode(length_prefixed, tag_type = "u8")]
+enum NewBorrowed<'a> {
+    Name(&'a str),
+    Bytes(&'a [u8]),
+}
+
+fn check<C: Config>(config: C) {
+    // known variants are encoded as before
+    for (level, new) in [
+        (Level::Debug, NewLevel::Debug),
+        (Level::Info, NewLevel::Info),
+        (Level::Warn, NewLevel::Warn),
+    ] {
+        let encoded = bincode::encode_to_vec(&level, config).unwrap();
+        assert_eq!(encoded, bincode::encode_to_vec(new, config).unwrap());
+        let (decoded, _): (Level, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, level);
+    }
+    for new in [NewLevel::Error, NewLevel::Fatal] {
+        let encoded = bincode::encode_to_vec(new, config).unwrap();
+        let (decoded, len): (Level, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, Level::Unknown);
+        assert_eq!(len, encoded.len());
+    }
+
+    let events = [
+        Event::Start {
+            id: 1,
+            name: "boot".into(),
+        },
+        Event::Stop(7),
+        Event::Ping,
+    ];
+    let new_events = [
+        NewEvent::Start {
+            id: 1,
+            name: "boot".into(),
+        },
+        NewEvent::Stop(7),
+        NewEvent::Ping,
+    ];
+    for (event, new) in events.into_iter().zip(new_events) {
+        let encoded = bincode::encode_to_vec(&event, config).unwrap();
+        assert_eq!(encoded, bincode::encode_to_vec(new, config).unwrap());
+        let (decoded, _): (Event, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    // the fields of the unknown variants are skipped, so the values after them are decoded
+    let encoded = bincode::encode_to_vec(
+        (
+            NewEvent::Pause {
+                seconds: 30,
+                reason: "lunch".into(),
+            },
+            NewEvent::Resume,
+            NewEvent::Stop(3),
+        ),
+        config,
+    )
+    .unwrap();
+    let (decoded, len): ((Event, Event, Event), _) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded,
+        (
+            Event::Unknown(3),
+            Event::Unknown(0xFFFF_FFFF),
+            Event::Stop(3)
+        )
+    );
+    assert_eq!(len, encoded.len());
+
+    // an unknown variant is encoded with its tag again
+    let encoded = bincode::encode_to_vec(NewEvent::Resume, config).unwrap();
+    assert_eq!(
+        bincode::encode_to_vec(Event::Unknown(0xFFFF_FFFF), config).unwrap(),
+        encoded
+    );
+    let (decoded, _): (Event, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Event::Unknown(0xFFFF_FFFF));
+}
+
+#[test]
+fn test_unknown_variants() {
+    check(config::standard());
+    check(config::legacy());
+    check(
+        config::standard()
+            .with_big_endian()
+            .with_fixed_int_encoding(),
+    );
+}
+
+#[test]
+fn test_skip() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(
+        (
+            NewLevel::Fatal,
+            NewEvent::Pause {
+                seconds: 1,
+                reason: "nap".into(),
+            },
+            5u8,
+        ),
+        config,
+    )
+    .unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&encoded), config, ());
+    Level::skip(&mut decoder).unwrap();
+    Event::skip(&mut decoder).unwrap();
+    let after: u8 = bincode::Decode::decode(&mut decoder).unwrap();
+    assert_eq!(after, 5);
+}
+
+#[test]
+fn test_borrow_decode() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(NewBorrowed::Bytes(&[1, 2, 3]), config).unwrap();
+    let (decoded, _): (Borrowed, _) = bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Borrowed::Other { tag: 1 });
+
+    let encoded = bincode::encode_to_vec(NewBorrowed::Name("ada"), config).unwrap();
+    let (decoded, _): (Borrowed, _) = bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Borrowed::Name("ada"));
+
+    // the tag must still fit in the tag type to be encoded
+    assert!(bincode::encode_to_vec(Borrowed::Other { tag: 256 }, config).is_err());
+}
+
+#[test]
+fn test_truncated() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(NewEvent::Resume, config).unwrap();
+    let err =
+        bincode::decode_from_slice::<Event, _>(&encoded[..encoded.len() - 1], config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { additional: 1 }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_layout() {
+    assert!(matches!(Event::layout(), Layout::LengthPrefixed(_)));
+}
@@ -0,0 +1,258 @@
+#![cfg(feature = "derive")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::{DecodeError, EncodeError};
+use bincode::layout::{EncodedLayout, Layout};
+
+#[derive(bincode::Encode, bincode::Decode, bincode::EncodedLayout, PartialEq, Debug)]
+struct Entry {
+    id: u8,
+    #[bincode(pad_to = 8)]
+    name: String,
+    flags: u8,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Strict {
+    #[bincode(pad_to = 4, strict_padding)]
+    value: u16,
+    tail: u8,
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::EncodedLayout, PartialEq, Debug)]
+#[bincode(pad_struct_to = 16, strict_padding)]
+struct Record {
+    id: u32,
+    #[bincode(pad_to = 6)]
+    tags: Vec<u8>,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(pad_struct_to = 12)]
+struct Borrowed<'a> {
+    #[bincode(pad_to = 8)]
+    name: &'a str,
+    bytes: &'a [u8],
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Message {
+    Name(#[bincode(pad_to = 4)] String, u8),
+    Point {
+        #[bincode(pad_to = 3, strict_padding)]
+        x: u8,
+        y: u8,
+    },
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum BorrowedMessage<'a> {
+    Text(#[bincode(pad_to = 4)] &'a str),
+}
+
+fn entry(name: &str) -> Entry {
+    Entry {
+        id: 1,
+        name: String::from(name),
+        flags: 2,
+    }
+}
+
+#[test]
+fn test_field_shorter_than_padding() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(entry("abc"), config).unwrap();
+    assert_eq!(encoded, [1, 3, b'a', b'b', b'c', 0, 0, 0, 0, 2]);
+
+    let (decoded, len): (Entry, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (entry("abc"), 10));
+}
+
+#[test]
+fn test_field_exactly_padding() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(entry("abcdefg"), config).unwrap();
+    assert_eq!(encoded, [1, 7, b'a', b'b', b'c', b'd', b'e', b'f', b'g', 2]);
+
+    let (decoded, len): (Entry, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (entry("abcdefg"), 10));
+}
+
+#[test]
+fn test_field_exceeds_padding() {
+    let config = bincode::config::standard();
+    let result = bincode::encode_to_vec(entry("abcdefgh"), config);
+    assert!(matches!(
+        result,
+        Err(EncodeError::PaddingExceeded { pad_to: 8, size: 9 })
+    ));
+
+    let mut buffer = [0u8; 16];
+    let result = bincode::encode_into_slice(entry("abcdefgh"), &mut buffer, config);
+    assert!(matches!(
+        result,
+        Err(EncodeError::PaddingExceeded { pad_to: 8, size: 9 })
+    ));
+
+    // a length that does not fit in the padded size
+    let encoded = [1, 8, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', 2];
+    let result: Result<(Entry, usize), _> = bincode::decode_from_slice(&encoded, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
+
+#[test]
+fn test_decode_hand_built_buffer() {
+    let config = bincode::config::standard();
+    // the padding is skipped without looking at it
+    let encoded = [1, 2, b'h', b'i', 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 2];
+    let (decoded, len): (Entry, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (entry("hi"), 10));
+
+    let encoded = [5, 0, 0, 0, 9];
+    let (decoded, len): (Strict, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (Strict { value: 5, tail: 9 }, 5));
+
+    let encoded = [5, 0, 1, 0, 9];
+    let result: Result<(Strict, usize), _> = bincode::decode_from_slice(&encoded, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::NonZeroPadding { pad_to: 4, size: 1 }
+    ));
+
+    // the padding is part of the input
+    let encoded = [5, 0, 0];
+    let result: Result<(Strict, usize), _> = bincode::decode_from_slice(&encoded, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+}
+
+#[test]
+fn test_struct_padding() {
+    let config = bincode::config::standard();
+    let record = Record {
+        id: 7,
+        tags: vec![],
+    };
+    let encoded = bincode::encode_to_vec(&record, config).unwrap();
+    assert_eq!(encoded, [7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let (decoded, len): (Record, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (record, 16));
+
+    let record = Record {
+        id: 300,
+        tags: vec![1, 2, 3, 4, 5],
+    };
+    let encoded = bincode::encode_to_vec(&record, config).unwrap();
+    assert_eq!(encoded, [251, 44, 1, 5, 1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0]);
+    let (decoded, len): (Record, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (record, 16));
+
+    let mut corrupted = encoded.clone();
+    corrupted[15] = 1;
+    let result: Result<(Record, usize), _> = bincode::decode_from_slice(&corrupted, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::NonZeroPadding {
+            pad_to: 16,
+            size: 9
+        }
+    ));
+
+    let record = Record {
+        id: u32::MAX,
+        tags: vec![1, 2, 3, 4, 5],
+    };
+    assert!(matches!(
+        bincode::encode_to_vec(&record, bincode::config::legacy()),
+        Err(EncodeError::PaddingExceeded {
+            pad_to: 6,
+            size: 13
+        })
+    ));
+}
+
+#[test]
+fn test_borrowed_padding() {
+    let config = bincode::config::standard();
+    let value = Borrowed {
+        name: "name",
+        bytes: &[1, 2],
+    };
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(encoded, [4, b'n', b'a', b'm', b'e', 0, 0, 0, 2, 1, 2, 0]);
+    let (decoded, len): (Borrowed, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (value, 12));
+
+    let message = BorrowedMessage::Text("ab");
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+    assert_eq!(encoded, [0, 2, b'a', b'b', 0]);
+    let (decoded, len): (BorrowedMessage, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (message, 5));
+}
+
+#[test]
+fn test_enum_padding() {
+    let config = bincode::config::standard();
+    let message = Message::Name(String::from("ab"), 3);
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+    assert_eq!(encoded, [0, 2, b'a', b'b', 0, 3]);
+    let (decoded, len): (Message, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (message, 6));
+
+    let message = Message::Point { x: 1, y: 2 };
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+    assert_eq!(encoded, [1, 1, 0, 0, 2]);
+    let (decoded, len): (Message, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (message, 5));
+
+    let result: Result<(Message, usize), _> = bincode::decode_from_slice(&[1, 1, 0, 4, 2], config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::NonZeroPadding { pad_to: 3, size: 1 }
+    ));
+
+    assert!(matches!(
+        bincode::encode_to_vec(Message::Name(String::from("abcd"), 3), config),
+        Err(EncodeError::PaddingExceeded { pad_to: 4, size: 5 })
+    ));
+}
+
+#[test]
+fn test_padding_limit() {
+    // the padding counts towards the limit
+    let config = bincode::config::standard().with_limit::<9>();
+    let encoded = bincode::encode_to_vec(entry("abc"), bincode::config::standard()).unwrap();
+    let result: Result<(Entry, usize), _> = bincode::decode_from_slice(&encoded, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::LimitExceeded
+    ));
+}
+
+#[test]
+fn test_padding_layout() {
+    assert_eq!(
+        Entry::layout(),
+        Layout::Struct {
+            name: "Entry",
+            fields: vec![
+                bincode::layout::Field::new("id", u8::layout()),
+                bincode::layout::Field::new("name", Layout::padded(8, Layout::Str)),
+                bincode::layout::Field::new("flags", u8::layout()),
+            ],
+        }
+    );
+    let json = Record::layout().to_json(bincode::config::legacy());
+    assert_eq!(
+        json,
+        r#"{"endian":"little","layout":{"kind":"padded","size":16,"layout":{"kind":"struct","name":"Record","fields":[{"name":"id","layout":{"kind":"int","signed":false,"bits":32,"encoding":"fixed"}},{"name":"tags","layout":{"kind":"padded","size":6,"layout":{"kind":"seq","len":{"kind":"int","signed":false,"bits":64,"encoding":"fixed"},"element":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}}}}]}}}"#
+    );
+}
@@ -0,0 +1,121 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use bincode::{
+    config::{self, Config},
+    error::EncodeError,
+    pre_encoded::PreEncoded,
+};
+
+#[derive(bincode::Encode, bincode::Decode, Clone, PartialEq, Debug)]
+struct Manifest {
+    version: u32,
+    assets: BTreeMap<String, Vec<u8>>,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Message<M> {
+    id: u64,
+    manifest: M,
+    tail: String,
+}
+
+fn manifest() -> Manifest {
+    Manifest {
+        version: 300,
+        assets: BTreeMap::from([
+            (String::from("map"), vec![1, 2, 3]),
+            (String::from("sky"), vec![0; 400]),
+        ]),
+    }
+}
+
+/// Encodes a message with a pre-encoded manifest, and with the manifest itself
+fn check_same_bytes<C: Config>(config: C) {
+    let manifest = manifest();
+    let pre_encoded = Message {
+        id: 1,
+        manifest: PreEncoded::new(&manifest, config).unwrap(),
+        tail: String::from("end"),
+    };
+    let plain = Message {
+        id: 1,
+        manifest: &manifest,
+        tail: String::from("end"),
+    };
+    assert_eq!(
+        bincode::encode_to_vec(&pre_encoded, config).unwrap(),
+        bincode::encode_to_vec(&plain, config).unwrap()
+    );
+    assert_eq!(
+        pre_encoded.manifest.as_bytes(),
+        bincode::encode_to_vec(&manifest, config).unwrap()
+    );
+    assert_eq!(pre_encoded.manifest.get(), &manifest);
+}
+
+#[test]
+fn test_same_bytes_as_value() {
+    check_same_bytes(config::standard());
+    check_same_bytes(config::legacy());
+    check_same_bytes(
+        config::standard()
+            .with_big_endian()
+            .with_fixed_u32_lengths(),
+    );
+}
+
+#[test]
+fn test_config_mismatch() {
+    let pre_encoded = PreEncoded::new(&manifest(), config::standard()).unwrap();
+
+    for err in [
+        bincode::encode_to_vec(&pre_encoded, config::standard().with_big_endian()),
+        bincode::encode_to_vec(&pre_encoded, config::standard().with_fixed_int_encoding()),
+        bincode::encode_to_vec(&pre_encoded, config::standard().with_fixed_u32_lengths()),
+        bincode::encode_to_vec(&pre_encoded, config::legacy()),
+    ] {
+        assert!(
+            matches!(err, Err(EncodeError::PreEncodedConfigMismatch)),
+            "{err:?}"
+        );
+    }
+
+    // options that only affect decoding can differ
+    let config = config::untrusted(4096).with_any_trailing_bytes();
+    assert_eq!(
+        bincode::encode_to_vec(&pre_encoded, config).unwrap(),
+        pre_encoded.as_bytes()
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let config = config::standard().with_big_endian();
+    let message = Message {
+        id: 7,
+        manifest: PreEncoded::new(&manifest(), config).unwrap(),
+        tail: String::from("end"),
+    };
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+
+    let (decoded, len): (Message<PreEncoded<Manifest>>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(decoded, message);
+    assert_eq!(decoded.manifest.as_bytes(), message.manifest.as_bytes());
+    assert_eq!(bincode::encode_to_vec(&decoded, config).unwrap(), encoded);
+
+    // the bytes are those of the plain value
+    let (plain, _): (Message<Manifest>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(plain.manifest, manifest());
+
+    // a decoded value is cached for the config it was decoded with
+    let err = bincode::encode_to_vec(&decoded, config::standard()).unwrap_err();
+    assert!(matches!(err, EncodeError::PreEncodedConfigMismatch));
+    assert_eq!(decoded.manifest.into_inner(), manifest());
+}
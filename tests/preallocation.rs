@@ -0,0 +1,167 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{config::Config, de::read::SliceReader, error::DecodeError, Decode};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+
+/// Keeps track of the largest allocation on every thread, so the tests can run in parallel
+struct LargestAllocation;
+
+thread_local! {
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for LargestAllocation {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.with(|largest| largest.set(largest.get().max(layout.size())));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.with(|largest| largest.set(largest.get().max(new_size)));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAllocation = LargestAllocation;
+
+/// Runs `f` and returns the largest allocation it made
+fn largest_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    LARGEST.with(|largest| largest.set(0));
+    let result = f();
+    (result, LARGEST.with(Cell::get))
+}
+
+fn is_unexpected_end(err: &DecodeError) -> bool {
+    matches!(err.inner(), DecodeError::UnexpectedEnd { .. })
+}
+
+/// A length prefix of 100 million elements, followed by a few bytes
+fn huge_len<C: Config>(config: C) -> Vec<u8> {
+    let mut data = bincode::encode_to_vec(100_000_000u64, config).unwrap();
+    data.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    data
+}
+
+/// Decodes `T` from a short input that claims to have 100 million elements, with every kind of reader
+fn check<T: Decode<()> + Debug, C: Config>(config: C, max: usize) {
+    let data = huge_len(config);
+    let name = std::any::type_name::<T>();
+
+    let (result, largest) =
+        largest_allocation(|| bincode::decode_from_slice::<T, _>(&data, config));
+    assert!(is_unexpected_end(&result.unwrap_err()), "{name}");
+    assert!(
+        largest <= max,
+        "{name} from a slice allocated {largest} bytes"
+    );
+
+    let (result, largest) = largest_allocation(|| {
+        bincode::decode_from_reader::<T, _, _>(SliceReader::new(&data), config)
+    });
+    assert!(is_unexpected_end(&result.unwrap_err()), "{name}");
+    assert!(
+        largest <= max,
+        "{name} from a SliceReader allocated {largest} bytes"
+    );
+
+    // the std reader does not know how much input is left
+    let (result, largest) =
+        largest_allocation(|| bincode::decode_from_std_read::<T, _, _>(&mut &data[..], config));
+    assert!(is_unexpected_end(&result.unwrap_err()), "{name}");
+    assert!(
+        largest <= 2 * 1024 * 1024,
+        "{name} from a std reader allocated {largest} bytes"
+    );
+}
+
+#[test]
+fn test_huge_len_small_input() {
+    let config = bincode::config::standard();
+    // a few bytes of input only allow for a few elements, plus the bytes used for the error
+    check::<Vec<u8>, _>(config, 1024);
+    check::<Vec<u64>, _>(config, 1024);
+    check::<Vec<String>, _>(config, 1024);
+    check::<Vec<(u8, u8)>, _>(config, 1024);
+    check::<Box<[u32]>, _>(config, 1024);
+    check::<VecDeque<u16>, _>(config, 1024);
+    check::<String, _>(config, 1024);
+    check::<HashMap<u8, u8>, _>(config, 1024);
+    check::<HashSet<u32>, _>(config, 1024);
+    check::<BTreeMap<u8, u8>, _>(config, 1024);
+
+    let config = bincode::config::legacy();
+    check::<Vec<u8>, _>(config, 1024);
+    check::<Vec<u64>, _>(config, 1024);
+    check::<Vec<f32>, _>(config, 1024);
+    check::<Vec<String>, _>(config, 1024);
+    check::<HashMap<u32, String>, _>(config, 1024);
+}
+
+#[test]
+fn test_huge_len_shortfall() {
+    // the error still reports how many bytes are missing for the whole value
+    let config = bincode::config::legacy();
+    let data = huge_len(config);
+    let expected = 8 * 100_000_000 - 12;
+
+    let err = bincode::decode_from_slice::<Vec<u64>, _>(&data, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedEnd { additional } if *additional == expected
+    ));
+    let err = bincode::decode_from_std_read::<Vec<u64>, _, _>(&mut &data[..], config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::UnexpectedEnd { additional } if *additional == expected
+    ));
+}
+
+#[test]
+fn test_large_input_still_decodes() {
+    let config = bincode::config::standard();
+    let bytes: Vec<u8> = (0..3 * 1024 * 1024).map(|i| i as u8).collect();
+    let strings: Vec<String> = (0..100_000).map(|i| i.to_string()).collect();
+    let encoded_bytes = bincode::encode_to_vec(&bytes, config).unwrap();
+    let encoded_strings = bincode::encode_to_vec(&strings, config).unwrap();
+
+    // slices know the input is large enough, so the elements are allocated at once
+    let (decoded, largest) = largest_allocation(|| {
+        bincode::decode_from_slice::<Vec<u8>, _>(&encoded_bytes, config)
+            .unwrap()
+            .0
+    });
+    assert_eq!(decoded, bytes);
+    assert_eq!(largest, bytes.len());
+
+    // std readers grow as the elements arrive
+    let decoded: Vec<u8> = bincode::decode_from_std_read(&mut &encoded_bytes[..], config).unwrap();
+    assert_eq!(decoded, bytes);
+    let decoded: Vec<String> =
+        bincode::decode_from_std_read(&mut &encoded_strings[..], config).unwrap();
+    assert_eq!(decoded, strings);
+    let decoded: HashSet<String> =
+        bincode::decode_from_std_read(&mut &encoded_strings[..], config).unwrap();
+    assert_eq!(decoded.len(), strings.len());
+}
+
+#[test]
+fn test_len_near_usize_max() {
+    let config = bincode::config::legacy();
+    let mut data = bincode::encode_to_vec(u64::MAX / 2, config).unwrap();
+    data.extend_from_slice(&[0; 16]);
+
+    let err = bincode::decode_from_slice::<Vec<u64>, _>(&data, config).unwrap_err();
+    assert!(is_unexpected_end(&err));
+    let err = bincode::decode_from_std_read::<Vec<u64>, _, _>(&mut &data[..], config).unwrap_err();
+    assert!(is_unexpected_end(&err));
+}
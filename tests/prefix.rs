@@ -0,0 +1,95 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use std::borrow::Cow;
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(prefix = "MessageHeader: version, msg_type, tenant")]
+pub struct Message {
+    pub version: u8,
+    pub msg_type: u16,
+    pub tenant: u64,
+    pub body: Vec<u8>,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(prefix = "RecordHeader: id, name")]
+struct Record<'a, T> {
+    id: u32,
+    #[bincode(borrow)]
+    name: Cow<'a, str>,
+    payload: T,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(prefix = "FixedHeader: kind")]
+struct Fixed<'a> {
+    #[bincode(owned)]
+    kind: Cow<'a, str>,
+    values: [u32; 2],
+}
+
+#[test]
+fn test_decode_prefix() {
+    let config = bincode::config::standard();
+    let message = Message {
+        version: 2,
+        msg_type: 300,
+        tenant: 1 << 40,
+        body: vec![7; 100],
+    };
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+
+    let (header, len) = MessageHeader::decode_prefix(&encoded, config).unwrap();
+    assert_eq!(header.version, 2);
+    assert_eq!(header.msg_type, 300);
+    assert_eq!(header.tenant, 1 << 40);
+    // the rest of the input is the body
+    let (body, _): (Vec<u8>, usize) = bincode::decode_from_slice(&encoded[len..], config).unwrap();
+    assert_eq!(body, message.body);
+
+    let (header, header_len): (MessageHeader, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(header_len, len);
+    assert_eq!(header.tenant, message.tenant);
+
+    let (decoded, full_len): (Message, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, message);
+    assert_eq!(full_len, encoded.len());
+}
+
+#[test]
+fn test_borrow_decode_prefix() {
+    let config = bincode::config::legacy();
+    let record = Record {
+        id: 9,
+        name: Cow::Borrowed("name"),
+        payload: [1u64, 2, 3],
+    };
+    let encoded = bincode::encode_to_vec(&record, config).unwrap();
+
+    let (header, len) = RecordHeader::decode_prefix(&encoded, config).unwrap();
+    assert_eq!(header.id, 9);
+    assert!(matches!(header.name, Cow::Borrowed("name")));
+    assert_eq!(len, 4 + 8 + 4);
+
+    let (decoded, _): (Record<[u64; 3]>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, record);
+}
+
+#[test]
+fn test_prefix_field_attributes() {
+    let config = bincode::config::standard();
+    let fixed = Fixed {
+        kind: Cow::Borrowed("kind"),
+        values: [5, 6],
+    };
+    let encoded = bincode::encode_to_vec(&fixed, config).unwrap();
+    // the header is decoded with the attributes of the fields
+    let (header, len) = FixedHeader::decode_prefix(&encoded, config).unwrap();
+    assert!(matches!(header.kind, Cow::Owned(ref kind) if kind == "kind"));
+    assert_eq!(&encoded[len..], [5, 6]);
+}
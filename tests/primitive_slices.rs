@@ -0,0 +1,224 @@
+#![cfg(feature = "alloc")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::Config,
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+/// Wraps a primitive, so slices of it are always encoded and decoded element by element
+#[derive(Clone, Copy, Debug)]
+struct Slow<T>(T);
+
+impl<T: Encode> Encode for Slow<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context, T: Decode<Context>> Decode<Context> for Slow<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        T::decode(decoder).map(Slow)
+    }
+}
+
+/// A primitive that can be generated from random bits
+trait Primitive: Encode + Decode<()> + Copy + Debug + 'static {
+    fn from_random(bits: u128) -> Self;
+    /// Compares by bits, so NaNs with the same payload are equal
+    fn bits(self) -> u128;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {$(
+        impl Primitive for $ty {
+            fn from_random(bits: u128) -> Self {
+                // mix small values in, which are encoded differently with varint encoding
+                if bits % 3 == 0 { (bits >> 120) as $ty } else { bits as $ty }
+            }
+            fn bits(self) -> u128 {
+                self as u128
+            }
+        }
+    )*};
+}
+impl_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_float {
+    ($($ty:ty => $bits:ty),*) => {$(
+        impl Primitive for $ty {
+            fn from_random(bits: u128) -> Self {
+                <$ty>::from_bits(bits as $bits)
+            }
+            fn bits(self) -> u128 {
+                self.to_bits() as u128
+            }
+        }
+    )*};
+}
+impl_float!(f32 => u32, f64 => u64);
+
+/// A small deterministic xorshift generator
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u128 {
+        let mut half = || {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        };
+        (u128::from(half()) << 64) | u128::from(half())
+    }
+
+    fn values<T: Primitive>(&mut self, len: usize) -> Vec<T> {
+        (0..len).map(|_| T::from_random(self.next())).collect()
+    }
+}
+
+fn bits<T: Primitive>(values: impl IntoIterator<Item = T>) -> Vec<u128> {
+    values.into_iter().map(T::bits).collect()
+}
+
+fn check_vec<T: Primitive, C: Config>(values: &[T], config: C) {
+    let slow: Vec<Slow<T>> = values.iter().copied().map(Slow).collect();
+    let encoded = bincode::encode_to_vec(values, config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&slow, config).unwrap());
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(values.to_vec(), config).unwrap()
+    );
+
+    // `VecDeque` that wraps around, so it consists of two slices
+    let mut deque = VecDeque::with_capacity(values.len());
+    if let Some(first) = values.first() {
+        for _ in 0..deque.capacity() - values.len() / 2 {
+            deque.push_back(*first);
+            deque.pop_front();
+        }
+    }
+    deque.extend(values.iter().copied());
+    assert_eq!(deque.as_slices().1.is_empty(), values.len() < 2);
+    assert_eq!(encoded, bincode::encode_to_vec(&deque, config).unwrap());
+
+    let (decoded, len): (Vec<T>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(bits(decoded.iter().copied()), bits(values.iter().copied()));
+    let (decoded, _): (Box<[T]>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(bits(decoded.iter().copied()), bits(values.iter().copied()));
+    let (decoded, _): (Vec<Slow<T>>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        bits(decoded.iter().map(|v| v.0)),
+        bits(values.iter().copied())
+    );
+
+    if !values.is_empty() {
+        let truncated = &encoded[..encoded.len() - 1];
+        let fast = bincode::decode_from_slice::<Vec<T>, _>(truncated, config).unwrap_err();
+        let slow = bincode::decode_from_slice::<Vec<Slow<T>>, _>(truncated, config).unwrap_err();
+        match (fast.inner(), slow.inner()) {
+            (
+                DecodeError::UnexpectedEnd { additional: fast },
+                DecodeError::UnexpectedEnd { additional: slow },
+            ) => assert_eq!(fast, slow),
+            e => panic!("Expected UnexpectedEnd, got {e:?}"),
+        }
+    }
+}
+
+fn check_array<T: Primitive, C: Config>(random: &mut Random, config: C) {
+    let values: [T; 37] = core::array::from_fn(|_| T::from_random(random.next()));
+    let encoded = bincode::encode_to_vec(values, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(values.map(Slow), config).unwrap()
+    );
+
+    let (decoded, len): ([T; 37], usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(bits(decoded), bits(values));
+    let (decoded, _): ([Slow<T>; 37], usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(bits(decoded.map(|v| v.0)), bits(values));
+}
+
+fn check<T: Primitive>(random: &mut Random) {
+    fn with_config<T: Primitive, C: Config>(random: &mut Random, config: C) {
+        // 3000 elements are more than fit in the buffer that is used to swap bytes
+        for len in [0, 1, 2, 3, 7, 64, 100, 3000] {
+            check_vec(&random.values::<T>(len), config);
+        }
+        check_array::<T, C>(random, config);
+    }
+    with_config::<T, _>(random, bincode::config::standard());
+    with_config::<T, _>(random, bincode::config::legacy());
+    with_config::<T, _>(random, bincode::config::standard().with_big_endian());
+    with_config::<T, _>(random, bincode::config::legacy().with_big_endian());
+    with_config::<T, _>(
+        random,
+        bincode::config::legacy().with_limit::<{ 1024 * 1024 }>(),
+    );
+}
+
+#[test]
+fn test_primitive_slices_match_element_wise() {
+    let mut random = Random(0x2545_F491_4F6C_DD1D);
+    check::<u8>(&mut random);
+    check::<u16>(&mut random);
+    check::<u32>(&mut random);
+    check::<u64>(&mut random);
+    check::<u128>(&mut random);
+    check::<usize>(&mut random);
+    check::<i8>(&mut random);
+    check::<i16>(&mut random);
+    check::<i32>(&mut random);
+    check::<i64>(&mut random);
+    check::<i128>(&mut random);
+    check::<isize>(&mut random);
+    check::<f32>(&mut random);
+    check::<f64>(&mut random);
+}
+
+#[test]
+fn test_primitive_slices_fixint_layout() {
+    let values = [0x0102_0304u32, 0x0506_0708];
+    let little = bincode::encode_to_vec(values, bincode::config::legacy()).unwrap();
+    assert_eq!(little, [4, 3, 2, 1, 8, 7, 6, 5]);
+    let big = bincode::encode_to_vec(values, bincode::config::legacy().with_big_endian()).unwrap();
+    assert_eq!(big, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let floats = vec![1.5f32, -2.0];
+    let encoded = bincode::encode_to_vec(&floats, bincode::config::standard()).unwrap();
+    let mut expected = vec![2];
+    expected.extend_from_slice(&1.5f32.to_le_bytes());
+    expected.extend_from_slice(&(-2.0f32).to_le_bytes());
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_bool_and_char_slices_are_validated() {
+    let config = bincode::config::legacy();
+    let err = bincode::decode_from_slice::<Vec<bool>, _>(&[2, 0, 0, 0, 0, 0, 0, 0, 1, 2], config)
+        .unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::InvalidBooleanValue(2)));
+    let err = bincode::decode_from_slice::<[char; 2], _>(&[b'a', 0xFF], config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::InvalidCharEncoding(_)));
+}
+
+#[test]
+fn test_primitive_slices_limit() {
+    let config = bincode::config::legacy().with_limit::<64>();
+    let encoded = bincode::encode_to_vec(vec![0u64; 8], bincode::config::legacy()).unwrap();
+    let err = bincode::decode_from_slice::<Vec<u64>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded));
+    let encoded = bincode::encode_to_vec(vec![0u64; 7], bincode::config::legacy()).unwrap();
+    let (decoded, _): (Vec<u64>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, [0; 7]);
+}
@@ -0,0 +1,152 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::read::{ProgressReader, SliceReader, TakeReader};
+use bincode::de::{BorrowDecode, DecoderImpl};
+use bincode::enc::write::{ProgressWriter, SizeWriter};
+use bincode::error::{DecodeError, EncodeError};
+use core::ops::ControlFlow;
+
+fn snapshot() -> Vec<(u64, String)> {
+    (0..500u64).map(|i| (i, format!("entry {i}"))).collect()
+}
+
+#[test]
+fn test_reported_counts_match_sizes() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(snapshot(), config).unwrap();
+
+    let mut reports = Vec::new();
+    let mut reader = ProgressReader::new(SliceReader::new(&encoded), 0, |read| {
+        reports.push(read);
+        ControlFlow::Continue(())
+    });
+    let decoded: Vec<(u64, String)> = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(decoded, snapshot());
+    assert_eq!(reader.bytes_read(), encoded.len() as u64);
+    assert!(!reader.is_cancelled());
+    assert!(reports.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(reports.last(), Some(&(encoded.len() as u64)));
+
+    let mut reports = Vec::new();
+    let mut output = Vec::new();
+    let mut writer = ProgressWriter::from_std_write(&mut output, 0, |written| {
+        reports.push(written);
+        ControlFlow::Continue(())
+    });
+    bincode::encode_into_writer(snapshot(), &mut writer, config).unwrap();
+    assert_eq!(writer.bytes_written(), encoded.len() as u64);
+    assert_eq!(output, encoded);
+    assert_eq!(reports.last(), Some(&(encoded.len() as u64)));
+
+    // borrowed data is counted as well
+    let encoded = bincode::encode_to_vec(("borrowed", [1u8; 4]), config).unwrap();
+    let mut reader =
+        ProgressReader::new(SliceReader::new(&encoded), 0, |_| ControlFlow::Continue(()));
+    let decoded: (&str, [u8; 4]) =
+        BorrowDecode::borrow_decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!(decoded, ("borrowed", [1; 4]));
+    assert_eq!(reader.bytes_read(), encoded.len() as u64);
+}
+
+#[test]
+fn test_throttling() {
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec([(7u32, 8u32); 50], config).unwrap();
+
+    let mut reports = Vec::new();
+    let reader = ProgressReader::from_std_read(&encoded[..], 64, |read| {
+        reports.push(read);
+        ControlFlow::Continue(())
+    });
+    let decoded: [(u32, u32); 50] = bincode::decode_from_reader(reader, config).unwrap();
+    assert_eq!(decoded, [(7, 8); 50]);
+    assert_eq!(reports, [64, 128, 192, 256, 320, 384]);
+
+    // the callback is called once per interval, also when a read spans several of them
+    let mut reports = Vec::new();
+    let mut writer = ProgressWriter::new(SizeWriter::default(), 10, |written| {
+        reports.push(written);
+        ControlFlow::Continue(())
+    });
+    bincode::encode_into_writer((1u8, [0u8; 35].as_slice(), 2u8, 3u8), &mut writer, config)
+        .unwrap();
+    assert_eq!(writer.into_inner().bytes_written, 46);
+    assert_eq!(reports, [44]);
+
+    // through a framing adapter
+    let mut data = encoded.clone();
+    data.extend_from_slice(&[1, 2, 3]);
+    let mut calls = 0;
+    let mut reader = ProgressReader::new(
+        TakeReader::new(SliceReader::new(&data), encoded.len()),
+        100,
+        |_| {
+            calls += 1;
+            ControlFlow::Continue(())
+        },
+    );
+    let _: [(u32, u32); 50] = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(reader.into_inner().remaining(), 0);
+    assert_eq!(calls, 4);
+}
+
+#[test]
+fn test_cancel_decoding() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(snapshot(), config).unwrap();
+
+    let mut calls = Vec::new();
+    let mut reader = ProgressReader::new(SliceReader::new(&encoded), 1000, |read| {
+        calls.push(read);
+        if read < 2000 {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    let result: Result<Vec<(u64, String)>, _> = bincode::decode_from_reader(&mut reader, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::Cancelled
+    ));
+    assert!(reader.is_cancelled());
+    let read = reader.bytes_read();
+    assert!((2000..2010).contains(&read), "{read}");
+
+    // the reader stays cancelled, without reading or calling the callback again
+    let result: Result<u8, _> = bincode::decode_from_reader(&mut reader, config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        DecodeError::Cancelled
+    ));
+    assert_eq!(reader.bytes_read(), read);
+    assert_eq!(calls.len(), 2);
+
+    let err = std::io::Error::from(DecodeError::Cancelled);
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+}
+
+#[test]
+fn test_cancel_encoding() {
+    let config = bincode::config::standard();
+    let mut output = Vec::new();
+    let mut writer = ProgressWriter::from_std_write(&mut output, 100, |written| {
+        if written < 300 {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    let result = bincode::encode_into_writer(snapshot(), &mut writer, config);
+    assert!(matches!(result, Err(EncodeError::Cancelled)));
+    assert!(writer.is_cancelled());
+    let written = writer.bytes_written();
+    assert!(matches!(
+        bincode::encode_into_writer(1u8, &mut writer, config),
+        Err(EncodeError::Cancelled)
+    ));
+    assert_eq!(writer.bytes_written(), written);
+    assert_eq!(output.len() as u64, written);
+}
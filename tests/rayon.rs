@@ -0,0 +1,110 @@
+#![cfg(all(feature = "rayon", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::Config,
+    enc::{Encode, Encoder},
+    error::EncodeError,
+    shared::SharedArc,
+};
+use std::sync::Arc;
+
+#[derive(bincode::Encode, PartialEq, Debug)]
+struct Record {
+    id: u64,
+    name: String,
+    scores: Vec<i32>,
+    flag: Option<bool>,
+}
+
+fn records(len: u64) -> Vec<Record> {
+    (0..len)
+        .map(|id| Record {
+            id: id * 7919,
+            name: "record".repeat(id as usize % 5),
+            scores: (0..id % 7).map(|i| i as i32 - 3).collect(),
+            flag: (id % 3 != 0).then_some(id % 2 == 0),
+        })
+        .collect()
+}
+
+fn check_parallel<T: Encode + Sync, C: Config + Sync>(slice: &[T], config: C) {
+    let sequential = bincode::encode_to_vec(slice, config).unwrap();
+    assert_eq!(
+        bincode::encode_to_vec_parallel(slice, config).unwrap(),
+        sequential
+    );
+
+    let mut written = Vec::new();
+    let len = bincode::encode_into_std_write_parallel(slice, &mut written, config).unwrap();
+    assert_eq!(len, sequential.len());
+    assert_eq!(written, sequential);
+}
+
+#[test]
+fn test_parallel_matches_sequential() {
+    let records = records(50_000);
+    let numbers: Vec<u64> = (0..100_000).map(|i| i * i).collect();
+    let bytes: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+    for slice_len in [0, 1, 17, 50_000] {
+        check_parallel(&records[..slice_len], bincode::config::standard());
+        check_parallel(&records[..slice_len], bincode::config::legacy());
+    }
+    check_parallel(&numbers, bincode::config::standard());
+    check_parallel(&numbers, bincode::config::legacy().with_big_endian());
+    check_parallel(&bytes, bincode::config::standard());
+}
+
+/// Fails to encode when its value is `u32::MAX`
+struct Fallible(u32);
+
+impl Encode for Fallible {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if self.0 == u32::MAX {
+            return Err(EncodeError::Other("fallible"));
+        }
+        self.0.encode(encoder)
+    }
+}
+
+#[test]
+fn test_parallel_error() {
+    let config = bincode::config::standard();
+    let mut values: Vec<Fallible> = (0..100_000).map(Fallible).collect();
+    values[60_000].0 = u32::MAX;
+    values[99_999].0 = u32::MAX;
+
+    assert!(matches!(
+        bincode::encode_to_vec(&values, config),
+        Err(EncodeError::Other("fallible"))
+    ));
+    assert!(matches!(
+        bincode::encode_to_vec_parallel(&values, config),
+        Err(EncodeError::Other("fallible"))
+    ));
+
+    let mut written = Vec::new();
+    assert!(matches!(
+        bincode::encode_into_std_write_parallel(&values, &mut written, config),
+        Err(EncodeError::Other("fallible"))
+    ));
+    // only the length and the elements before the failing one may have been written
+    let mut sequential = bincode::encode_to_vec(100_000u64, config).unwrap();
+    for value in &values[..60_000] {
+        sequential.extend(bincode::encode_to_vec(value, config).unwrap());
+    }
+    assert!(written.len() <= sequential.len());
+    assert_eq!(written, sequential[..written.len()]);
+}
+
+#[test]
+fn test_parallel_shared_values() {
+    let config = bincode::config::standard();
+    let shared = SharedArc(Arc::new(String::from("shared")));
+    let mut values: Vec<Option<SharedArc<String>>> = vec![None; 40_000];
+    values[20_000] = Some(shared.clone());
+    values[30_000] = Some(SharedArc(Arc::new(String::from("other"))));
+    values[39_999] = Some(shared);
+    check_parallel(&values, config);
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::de::read::{Reader, SliceReader};
+use bincode::error::DecodeError;
+use bincode::{Decode, Encode};
+use core::mem::MaybeUninit;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// A reader that only implements `read`, so it uses the default `read_uninit`, which zeroes the bytes first
+struct ZeroingReader<'a>(SliceReader<'a>);
+
+impl Reader for ZeroingReader<'_> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.0.read(bytes)
+    }
+}
+
+/// A reader that returns other bytes from `read_uninit` than it was given
+struct WrongSliceReader;
+
+impl Reader for WrongSliceReader {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        bytes.fill(7);
+        Ok(())
+    }
+
+    fn read_uninit<'a>(
+        &mut self,
+        bytes: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], DecodeError> {
+        Ok(vec![7; bytes.len()].leak())
+    }
+}
+
+/// Decodes `encoded` with every reader, and returns the results, which must all be the same
+fn decode_all<T, C>(encoded: &[u8], config: C) -> Result<T, String>
+where
+    T: Decode<()> + PartialEq + Debug,
+    C: Config,
+{
+    let results = [
+        bincode::decode_from_slice::<T, _>(encoded, config).map(|(value, _)| value),
+        bincode::decode_from_reader(SliceReader::new(encoded), config),
+        bincode::decode_from_std_read(&mut &encoded[..], config),
+        bincode::decode_from_reader(ZeroingReader(SliceReader::new(encoded)), config),
+    ]
+    .map(|result| result.map_err(|e| format!("{:?}", e.into_inner())));
+    for result in &results[1..] {
+        assert_eq!(*result, results[0]);
+    }
+    let [first, ..] = results;
+    first
+}
+
+/// Asserts that `value` round trips through every reader, and that every truncation of its encoding fails the same with every reader
+fn check<T, C>(value: T, config: C)
+where
+    T: Encode + Decode<()> + PartialEq + Debug,
+    C: Config,
+{
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(decode_all::<T, _>(&encoded, config).unwrap(), value);
+    // an empty input is a clean EOF for an `IoReader`
+    for len in [1, encoded.len() / 3, encoded.len() / 2, encoded.len() - 1] {
+        if len > 0 && len < encoded.len() {
+            decode_all::<T, _>(&encoded[..len], config).unwrap_err();
+        }
+    }
+}
+
+fn check_all<C: Config>(config: C) {
+    // more than one chunk of an `IoReader`
+    let bytes: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    for len in [1, 100, bytes.len()] {
+        check(bytes[..len].to_vec(), config);
+        check(Box::<[u8]>::from(&bytes[..len]), config);
+        check(Rc::<[u8]>::from(&bytes[..len]), config);
+    }
+    check(Vec::<u8>::new(), config);
+    check("grüße 🦀 ".repeat(10_000), config);
+    check(String::new(), config);
+    check((0..50_000u32).collect::<Vec<_>>(), config);
+    check(
+        (0..1000).map(|i| i as f64 / 3.0).collect::<Vec<_>>(),
+        config,
+    );
+    check(
+        core::array::from_fn::<u16, 64, _>(|i| i as u16 * 1000),
+        config,
+    );
+    check(Box::new([-1i64; 300]), config);
+}
+
+#[test]
+fn test_same_as_read() {
+    check_all(config::standard());
+    check_all(config::legacy());
+    check_all(config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_truncated() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(vec![1u8; 300_000], config).unwrap();
+    // 5 bytes of length and 99_998 of the 300_000 bytes
+    let err = decode_all::<Vec<u8>, _>(&encoded[..100_003], config).unwrap_err();
+    assert_eq!(
+        err,
+        format!(
+            "{:?}",
+            DecodeError::UnexpectedEnd {
+                additional: 200_002
+            }
+        )
+    );
+}
+
+#[test]
+fn test_wrong_slice() {
+    let config = config::legacy();
+    let err = bincode::decode_from_reader::<Vec<u8>, _, _>(WrongSliceReader, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Other(_)), "{err:?}");
+    let err = bincode::decode_from_reader::<[u32; 4], _, _>(WrongSliceReader, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::Other(_)), "{err:?}");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // too large for miri
+fn test_100_mb() {
+    let config = config::standard();
+    let bytes: Vec<u8> = (0..100 << 20).map(|i: u32| (i >> 12) as u8).collect();
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+    let (decoded, _): (Vec<u8>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert!(decoded == bytes);
+    let decoded: Vec<u8> = bincode::decode_from_std_read(&mut &encoded[..], config).unwrap();
+    assert!(decoded == bytes);
+}
@@ -0,0 +1,147 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{read::SliceReader, Decoder, DecoderImpl},
+    error::DecodeError,
+};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Message {
+    kind: u8,
+    #[bincode(remaining_bytes)]
+    payload: Vec<u8>,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+struct BorrowedMessage<'a> {
+    id: u16,
+    #[bincode(remaining_bytes)]
+    payload: &'a [u8],
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Packet {
+    Ping,
+    Data(u8, #[bincode(remaining_bytes)] Vec<u8>),
+}
+
+#[test]
+fn test_remaining_bytes_roundtrip() {
+    let config = bincode::config::legacy();
+    for payload in [vec![], vec![1, 2, 3], vec![0xFF; 1000]] {
+        let message = Message {
+            kind: 9,
+            payload: payload.clone(),
+        };
+        let encoded = bincode::encode_to_vec(&message, config).unwrap();
+        // no length in front of the payload
+        assert_eq!(encoded.len(), 1 + payload.len());
+        assert_eq!(&encoded[1..], payload.as_slice());
+
+        let (decoded, len): (Message, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(len, encoded.len());
+    }
+
+    let message = BorrowedMessage {
+        id: 0x0102,
+        payload: b"rest",
+    };
+    let encoded = bincode::encode_to_vec(&message, config).unwrap();
+    assert_eq!(encoded, [2, 1, b'r', b'e', b's', b't']);
+    let (decoded, _): (BorrowedMessage, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, message);
+
+    for packet in [Packet::Ping, Packet::Data(4, vec![5, 6])] {
+        let encoded = bincode::encode_to_vec(&packet, bincode::config::standard()).unwrap();
+        let (decoded, _): (Packet, usize) =
+            bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+        assert_eq!(decoded, packet);
+    }
+    assert_eq!(
+        bincode::encode_to_vec(Packet::Data(4, vec![5, 6]), bincode::config::standard()).unwrap(),
+        [1, 4, 5, 6]
+    );
+}
+
+#[test]
+fn test_remaining_bytes_framing() {
+    let config = bincode::config::standard();
+    let messages = [
+        Message {
+            kind: 1,
+            payload: vec![10, 11],
+        },
+        Message {
+            kind: 2,
+            payload: vec![20, 21, 22],
+        },
+    ];
+
+    // every message is prefixed with its length
+    let mut stream = Vec::new();
+    for message in &messages {
+        let encoded = bincode::encode_to_vec(message, config).unwrap();
+        stream.push(encoded.len() as u8);
+        stream.extend(encoded);
+    }
+
+    let mut reader = stream.as_slice();
+    for message in &messages {
+        let len = usize::from(reader[0]);
+        reader = &reader[1..];
+        let (decoded, bytes_read): (Message, usize) =
+            bincode::decode_from_std_read_take(&mut reader, len, config).unwrap();
+        assert_eq!(&decoded, message);
+        assert_eq!(bytes_read, len);
+    }
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn test_remaining_bytes_unknown() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(
+        Message {
+            kind: 1,
+            payload: vec![1, 2, 3],
+        },
+        config,
+    )
+    .unwrap();
+
+    // a plain stream does not know where the message ends
+    let err = bincode::decode_from_std_read::<Message, _, _>(&mut encoded.as_slice(), config)
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::RemainingBytesUnknown
+    ));
+    let err = bincode::decode_from_reader::<Message, _, _>(
+        bincode::BufferedIoReader::new(encoded.as_slice()),
+        config,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::RemainingBytesUnknown
+    ));
+}
+
+#[test]
+fn test_remaining_bytes_limit() {
+    let encoded = [1u8; 10];
+
+    let config = bincode::config::standard().with_limit::<4>();
+    let err = bincode::decode_from_slice::<Message, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::LimitExceeded));
+
+    let config = bincode::config::standard().with_limit::<10>();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&encoded), config, ());
+    assert_eq!(decoder.take_remaining_bytes().unwrap(), encoded);
+    assert_eq!(decoder.limit_remaining(), Some(0));
+}
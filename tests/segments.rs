@@ -0,0 +1,159 @@
+#![cfg(feature = "alloc")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{
+        read::{BorrowReader, Reader, SegmentedSliceReader},
+        BorrowDecode, DecoderImpl,
+    },
+    error::DecodeError,
+};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Packet {
+    id: u64,
+    flags: u8,
+    name: String,
+    payload: Vec<u8>,
+    values: Vec<i32>,
+    checksum: u32,
+}
+
+fn packet() -> Packet {
+    Packet {
+        id: 0x1234_5678_9abc,
+        flags: 3,
+        name: String::from("segmented"),
+        payload: (0..40).collect(),
+        values: vec![-1, 300, 70_000, i32::MIN],
+        checksum: u32::MAX,
+    }
+}
+
+fn split(data: &[u8], size: usize) -> Vec<&[u8]> {
+    data.chunks(size).collect()
+}
+
+fn check_every_size<C: bincode::config::Config>(config: C) {
+    let packet = packet();
+    let data = bincode::encode_to_vec(&packet, config).unwrap();
+    for size in 1..=data.len() {
+        let segments = split(&data, size);
+        let (decoded, len): (Packet, usize) =
+            bincode::decode_from_segments(&segments, config).unwrap();
+        assert_eq!(decoded, packet, "segment size {size}");
+        assert_eq!(len, data.len());
+    }
+}
+
+#[test]
+fn test_decode_from_segments_every_size() {
+    check_every_size(bincode::config::standard());
+    check_every_size(bincode::config::legacy());
+    check_every_size(bincode::config::standard().with_big_endian());
+}
+
+#[test]
+fn test_decode_from_segments_empty_segments() {
+    let config = bincode::config::standard();
+    let data = bincode::encode_to_vec(packet(), config).unwrap();
+    let mut segments = Vec::new();
+    for byte in &data {
+        segments.push(&[][..]);
+        segments.push(std::slice::from_ref(byte));
+        segments.push(&[][..]);
+    }
+    let (decoded, len): (Packet, usize) = bincode::decode_from_segments(&segments, config).unwrap();
+    assert_eq!(decoded, packet());
+    assert_eq!(len, data.len());
+}
+
+#[test]
+fn test_decode_from_segments_unexpected_end() {
+    let config = bincode::config::legacy();
+    let data = bincode::encode_to_vec(5u64, config).unwrap();
+    for size in 1..data.len() {
+        let segments = split(&data[..6], size);
+        let err = bincode::decode_from_segments::<u64, _>(&segments, config).unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            DecodeError::UnexpectedEnd { additional: 2 }
+        ));
+    }
+}
+
+#[test]
+fn test_segmented_reader_byte_segments() {
+    let data: Vec<u8> = (0..10).collect();
+    let segments = split(&data, 1);
+    let mut reader = SegmentedSliceReader::new(&segments);
+    assert_eq!(reader.remaining(), 10);
+
+    // reads span any amount of segments
+    let mut buf = [0u8; 4];
+    reader.read(&mut buf).unwrap();
+    assert_eq!(buf, [0, 1, 2, 3]);
+    assert_eq!(reader.bytes_read(), 4);
+
+    // peeking only sees the current segment
+    assert_eq!(reader.peek_read(1), Some(&[4][..]));
+    assert_eq!(reader.peek_read(2), None);
+    reader.consume(1);
+    assert_eq!(reader.bytes_read(), 5);
+
+    // borrowing only works within the current segment
+    assert_eq!(reader.take_bytes(1).unwrap(), &[5]);
+    assert!(matches!(
+        reader.take_bytes(2),
+        Err(DecodeError::BorrowSpansSegments {
+            length: 2,
+            available: 1
+        })
+    ));
+    assert!(matches!(
+        reader.take_bytes(5),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    assert_eq!(reader.bytes_read(), 6);
+
+    let mut buf = [0u8; 5];
+    assert!(matches!(
+        reader.read(&mut buf),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    // a failed read does not consume anything
+    reader.read(&mut buf[..4]).unwrap();
+    assert_eq!(buf[..4], [6, 7, 8, 9]);
+    assert_eq!(reader.remaining(), 0);
+    assert_eq!(reader.peek_read(0), Some(&[][..]));
+}
+
+#[test]
+fn test_segmented_reader_borrow() {
+    let config = bincode::config::standard();
+    let data = bincode::encode_to_vec((7u8, "hello", "world"), config).unwrap();
+    // `7`, `5hello` and `5world` each in their own segment
+    let segments = [&data[..1], &data[1..7], &data[7..]];
+    let mut decoder = DecoderImpl::new(SegmentedSliceReader::new(&segments), config, ());
+    let (id, hello, world) = <(u8, &str, &str)>::borrow_decode(&mut decoder).unwrap();
+    assert_eq!((id, hello, world), (7, "hello", "world"));
+    assert_eq!(hello.as_ptr(), data[2..].as_ptr());
+
+    // `hello` spans two segments
+    let segments = [&data[..4], &data[4..]];
+    let mut decoder = DecoderImpl::new(SegmentedSliceReader::new(&segments), config, ());
+    let err = <(u8, &str, &str)>::borrow_decode(&mut decoder).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::BorrowSpansSegments {
+            length: 5,
+            available: 2
+        }
+    ));
+
+    // owned strings are copied across the boundary
+    let (decoded, _): ((u8, String, String), usize) =
+        bincode::decode_from_segments(&segments, config).unwrap();
+    assert_eq!(decoded, (7, String::from("hello"), String::from("world")));
+}
@@ -94,6 +94,87 @@ fn test_serialize_deserialize_borrowed_data() {
         output
     );
     assert_eq!(len, 13);
+
+    let mut result = [0u8; 12];
+    let err = bincode::serde::encode_into_slice(&input, &mut result, bincode::config::standard())
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::SliceTooSmall {
+            capacity: 12,
+            required: 13
+        }
+    ));
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct SerdeLog<'a> {
+    pub level: u8,
+    pub msg: &'a str,
+    pub payload: &'a [u8],
+    #[serde(borrow)]
+    pub source: Option<&'a str>,
+    #[serde(borrow)]
+    pub tags: Vec<&'a str>,
+    #[serde(borrow)]
+    pub cow: std::borrow::Cow<'a, str>,
+}
+
+fn assert_borrowed_from(input: &[u8], borrowed: &[u8]) {
+    let range = input.as_ptr_range();
+    let borrowed_range = borrowed.as_ptr_range();
+    assert!(range.start <= borrowed_range.start && borrowed_range.end <= range.end);
+}
+
+fn check_serde_borrowed<C: bincode::config::Config>(input: &SerdeLog, config: C) {
+    let encoded = bincode::serde::encode_to_vec(input, config).unwrap();
+    let (output, len): (SerdeLog, usize) =
+        bincode::serde::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(&output, input);
+    assert_eq!(len, encoded.len());
+
+    assert_borrowed_from(&encoded, output.msg.as_bytes());
+    assert_borrowed_from(&encoded, output.payload);
+    assert_borrowed_from(&encoded, output.source.unwrap().as_bytes());
+    for tag in &output.tags {
+        assert_borrowed_from(&encoded, tag.as_bytes());
+    }
+    match &output.cow {
+        std::borrow::Cow::Borrowed(cow) => assert_borrowed_from(&encoded, cow.as_bytes()),
+        std::borrow::Cow::Owned(_) => panic!("Expected a borrowed Cow"),
+    }
+}
+
+#[test]
+fn test_serde_borrowed_str_and_bytes() {
+    let input = SerdeLog {
+        level: 3,
+        msg: "disk full",
+        payload: &[1, 2, 3, 4],
+        source: Some("storage"),
+        tags: vec!["a", "bc"],
+        cow: std::borrow::Cow::Borrowed("cow"),
+    };
+    check_serde_borrowed(&input, bincode::config::standard());
+    check_serde_borrowed(&input, bincode::config::legacy());
+}
+
+#[test]
+fn test_serde_borrowed_same_format_as_borrow_decode() {
+    let config = bincode::config::standard();
+    let msg = "Hello world";
+    let payload: &[u8] = &[0, 1, 2, 255];
+    let native = bincode::encode_to_vec((msg, payload), config).unwrap();
+    let serde = bincode::serde::encode_to_vec((msg, payload), config).unwrap();
+    assert_eq!(native, serde);
+
+    let ((msg, payload), len): ((&str, &[u8]), usize) =
+        bincode::serde::borrow_decode_from_slice(&native, config).unwrap();
+    assert_eq!(msg, "Hello world");
+    assert_eq!(payload, &[0, 1, 2, 255]);
+    assert_eq!(len, native.len());
+    assert_borrowed_from(&native, msg.as_bytes());
+    assert_borrowed_from(&native, payload);
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
@@ -202,6 +283,50 @@ mod derive {
         );
     }
 
+    #[derive(bincode::BorrowDecode, Encode, PartialEq, Eq, Debug)]
+    pub struct StructWithBorrowedSerde<'a> {
+        pub id: u32,
+        #[bincode(with_serde)]
+        pub log: super::SerdeLog<'a>,
+    }
+
+    #[derive(bincode::BorrowDecode, Encode, PartialEq, Eq, Debug)]
+    pub enum EnumWithBorrowedSerde<'a> {
+        Log(#[bincode(with_serde)] super::SerdeLog<'a>),
+    }
+
+    #[test]
+    fn test_serde_derive_borrowed() {
+        let log = super::SerdeLog {
+            level: 1,
+            msg: "message",
+            payload: &[5, 6, 7],
+            source: None,
+            tags: vec!["tag"],
+            cow: std::borrow::Cow::Borrowed("cow"),
+        };
+        let config = bincode::config::standard();
+
+        let start = StructWithBorrowedSerde { id: 5, log };
+        let encoded = bincode::encode_to_vec(&start, config).unwrap();
+        let (result, len): (StructWithBorrowedSerde, usize) =
+            bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(start, result);
+        assert_eq!(len, encoded.len());
+        super::assert_borrowed_from(&encoded, result.log.msg.as_bytes());
+        super::assert_borrowed_from(&encoded, result.log.payload);
+
+        let start = EnumWithBorrowedSerde::Log(start.log);
+        let encoded = bincode::encode_to_vec(&start, config).unwrap();
+        let (result, len): (EnumWithBorrowedSerde, usize) =
+            bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(start, result);
+        assert_eq!(len, encoded.len());
+        let EnumWithBorrowedSerde::Log(log) = result;
+        super::assert_borrowed_from(&encoded, log.msg.as_bytes());
+        super::assert_borrowed_from(&encoded, log.payload);
+    }
+
     #[test]
     fn test_vec_compat_debug() {
         let compat = Compat(vec![0, 1, 2, 3]);
@@ -252,3 +377,537 @@ mod derive {
         assert_eq!(debug_view, "Hello World!")
     }
 }
+
+mod self_describing {
+    use serde_derive::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Untagged {
+        Number(u64),
+        Text(String),
+        Pair { a: i32, b: Option<bool> },
+        List(Vec<Untagged>),
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Inner {
+        x: f32,
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Flattened {
+        id: u32,
+        #[serde(flatten)]
+        inner: Inner,
+        #[serde(flatten)]
+        extra: BTreeMap<String, u8>,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Tagged {
+        Unit,
+        Newtype(i16),
+        Tuple(u8, char),
+        Struct { bytes: Vec<u8>, skipped: Option<()> },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(tag = "type")]
+    enum Internal {
+        Ping,
+        Data { value: i64 },
+    }
+
+    fn the_same<T>(value: T)
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + core::fmt::Debug,
+    {
+        let config = bincode::config::standard();
+        let encoded = bincode::serde::encode_to_vec_self_describing(&value, config).unwrap();
+        let (decoded, len): (T, usize) =
+            bincode::serde::decode_from_slice_self_describing(&encoded, config).unwrap();
+        assert_eq!(value, decoded);
+        assert_eq!(len, encoded.len());
+
+        let config = bincode::config::legacy().with_big_endian();
+        let mut slice = [0u8; 256];
+        let len =
+            bincode::serde::encode_into_slice_self_describing(&value, &mut slice, config).unwrap();
+        let (decoded, decoded_len): (T, usize) =
+            bincode::serde::decode_from_slice_self_describing(&slice[..len], config).unwrap();
+        assert_eq!(value, decoded);
+        assert_eq!(len, decoded_len);
+
+        let err = bincode::serde::encode_into_slice_self_describing(
+            &value,
+            &mut slice[..len - 1],
+            config,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            bincode::error::EncodeError::SliceTooSmall { required, .. } if required == len
+        ));
+    }
+
+    #[test]
+    fn test_self_describing_untagged_enum() {
+        the_same(Untagged::Number(5));
+        the_same(Untagged::Text(String::from("Hello")));
+        the_same(Untagged::Pair { a: -3, b: None });
+        the_same(Untagged::Pair {
+            a: 3,
+            b: Some(true),
+        });
+        the_same(Untagged::List(vec![
+            Untagged::Number(u64::MAX),
+            Untagged::Text(String::new()),
+        ]));
+    }
+
+    #[test]
+    fn test_self_describing_flatten() {
+        let mut extra = BTreeMap::new();
+        extra.insert(String::from("first"), 1);
+        extra.insert(String::from("second"), 2);
+        the_same(Flattened {
+            id: 10,
+            inner: Inner {
+                x: 1.5,
+                name: String::from("inner"),
+            },
+            extra,
+        });
+    }
+
+    #[test]
+    fn test_self_describing_enums() {
+        the_same(Tagged::Unit);
+        the_same(Tagged::Newtype(-300));
+        the_same(Tagged::Tuple(5, 'ß'));
+        the_same(Tagged::Struct {
+            bytes: vec![1, 2, 3],
+            skipped: Some(()),
+        });
+        the_same(vec![Internal::Ping, Internal::Data { value: -1 }]);
+    }
+
+    #[test]
+    fn test_self_describing_json_value() {
+        let config = bincode::config::standard();
+        let json = serde_json::json!({
+            "null": null,
+            "bool": true,
+            "int": -5,
+            "uint": 12345678901u64,
+            "float": 0.25,
+            "string": "text",
+            "array": [1, "two", [3.0], {}],
+            "object": { "nested": { "deep": [] } }
+        });
+
+        let encoded = bincode::serde::encode_to_vec_self_describing(&json, config).unwrap();
+        let (value, _): (serde_json::Value, usize) =
+            bincode::serde::decode_from_slice_self_describing(&encoded, config).unwrap();
+        assert_eq!(value, json);
+
+        // A typed value can be decoded as a `Value`, and decoded from its re-encoded `Value`
+        let original = Untagged::Pair {
+            a: 7,
+            b: Some(false),
+        };
+        let encoded = bincode::serde::encode_to_vec_self_describing(&original, config).unwrap();
+        let (value, _): (serde_json::Value, usize) =
+            bincode::serde::decode_from_slice_self_describing(&encoded, config).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": 7, "b": false }));
+        let encoded = bincode::serde::encode_to_vec_self_describing(&value, config).unwrap();
+        let (decoded, _): (Untagged, usize) =
+            bincode::serde::decode_from_slice_self_describing(&encoded, config).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_self_describing_format() {
+        let config = bincode::config::standard();
+        let encoded =
+            bincode::serde::encode_to_vec_self_describing((1u8, "a", Some(-1i32)), config).unwrap();
+        assert_eq!(encoded, &[20, 8, 1, 16, 1, b'a', 19, 5, 1, 22]);
+    }
+
+    #[test]
+    fn test_self_describing_invalid_tag() {
+        let config = bincode::config::standard();
+        let err = bincode::serde::decode_from_slice_self_describing::<serde_json::Value, _>(
+            &[20, 8, 1, 200],
+            config,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            bincode::error::DecodeError::Serde(bincode::serde::DecodeError::InvalidTypeTag(200))
+        ));
+    }
+}
+
+/// The even numbers below the given number, serialized from an iterator that doesn't know its length
+struct Evens(u32);
+
+impl serde::Serialize for Evens {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.0).filter(|i| i % 2 == 0))
+    }
+}
+
+/// Groups of even numbers, so the sequences of unknown length are nested
+struct EvenGroups(u32);
+
+impl serde::Serialize for EvenGroups {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.0).filter(|i| i % 2 == 1).map(Evens))
+    }
+}
+
+#[test]
+fn test_serialize_seq_without_len() {
+    let config = bincode::config::standard();
+    let eager: Vec<u32> = (0..300).filter(|i| i % 2 == 0).collect();
+
+    let encoded = bincode::serde::encode_to_vec(Evens(300), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&eager, config).unwrap());
+    let (decoded, len): (Vec<u32>, usize) =
+        bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, eager);
+    assert_eq!(len, encoded.len());
+
+    let eager: Vec<Vec<u32>> = (0..6)
+        .filter(|i| i % 2 == 1)
+        .map(|n| (0..n).filter(|i| i % 2 == 0).collect())
+        .collect();
+    let encoded = bincode::serde::encode_to_vec(EvenGroups(6), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&eager, config).unwrap());
+
+    // the output is the same for encoders that don't allocate
+    let mut slice = [0u8; 256];
+    let written = bincode::serde::encode_into_slice(Evens(300), &mut slice, config).unwrap();
+    assert_eq!(
+        &slice[..written],
+        bincode::encode_to_vec(
+            (0..300).filter(|i| i % 2 == 0).collect::<Vec<u32>>(),
+            config
+        )
+        .unwrap()
+    );
+}
+
+#[derive(Serialize)]
+struct Flattened {
+    id: u32,
+    #[serde(flatten)]
+    extra: std::collections::BTreeMap<String, u32>,
+}
+
+#[test]
+fn test_serialize_map_without_len() {
+    let config = bincode::config::standard();
+    let value = Flattened {
+        id: 7,
+        extra: [("a".to_string(), 1), ("b".to_string(), 500)].into(),
+    };
+    let encoded = bincode::serde::encode_to_vec(&value, config).unwrap();
+
+    // a flattened struct is serialized as a map of all fields
+    let eager: Vec<(String, u32)> = vec![
+        ("id".to_string(), 7),
+        ("a".to_string(), 1),
+        ("b".to_string(), 500),
+    ];
+    assert_eq!(encoded, bincode::encode_to_vec(&eager, config).unwrap());
+    let (decoded, _): (std::collections::BTreeMap<String, u32>, usize) =
+        bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, eager.into_iter().collect());
+}
+
+/// Serializes the `Display` output of its value with `collect_str`
+struct Collected<T>(T);
+
+impl<T: core::fmt::Display> serde::Serialize for Collected<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// A `Display` implementation that writes something longer every time it is called
+struct Unstable(core::cell::Cell<usize>);
+
+impl core::fmt::Display for Unstable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let n = self.0.get() + 1;
+        self.0.set(n);
+        for _ in 0..n {
+            f.write_str("ab")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_serialize_collect_str() {
+    let config = bincode::config::standard();
+    let value = std::net::Ipv4Addr::new(192, 168, 1, 20);
+    let encoded = bincode::serde::encode_to_vec(Collected(value), config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(value.to_string(), config).unwrap()
+    );
+    let (decoded, _): (String, usize) =
+        bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, "192.168.1.20");
+
+    let long = format_args!("{:é>200}", "").to_string();
+    let encoded = bincode::serde::encode_to_vec(Collected(&long), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&long, config).unwrap());
+
+    let err =
+        bincode::serde::encode_to_vec(Collected(Unstable(Default::default())), config).unwrap_err();
+    assert!(matches!(err, bincode::error::EncodeError::Other(_)));
+}
+
+/// Like `serde_bytes`, serializes the bytes with `serialize_bytes` instead of as a sequence
+mod as_bytes {
+    pub fn serialize<S: serde::Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        deserializer
+            .deserialize_bytes(super::BytesVisitor)
+            .map(|bytes| bytes.data)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Visited {
+    Borrowed,
+    Bytes,
+    ByteBuf,
+}
+
+/// Remembers which method of the visitor got the bytes
+#[derive(Debug, PartialEq)]
+struct VisitedBytes {
+    data: Vec<u8>,
+    visited: Visited,
+}
+
+struct BytesVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+    type Value = VisitedBytes;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(VisitedBytes {
+            data: v.to_vec(),
+            visited: Visited::Borrowed,
+        })
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(VisitedBytes {
+            data: v.to_vec(),
+            visited: Visited::Bytes,
+        })
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(VisitedBytes {
+            data: v,
+            visited: Visited::ByteBuf,
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VisitedBytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Blob {
+    id: u32,
+    #[serde(with = "as_bytes")]
+    data: Vec<u8>,
+}
+
+#[test]
+fn test_serde_bytes_round_trip() {
+    let blob = Blob {
+        id: 7,
+        data: (0..=255).collect(),
+    };
+    let config = bincode::config::standard();
+    let encoded = bincode::serde::encode_to_vec(&blob, config).unwrap();
+    let (decoded, len): (Blob, usize) =
+        bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded, len), (blob, encoded.len()));
+
+    let decoded: Blob =
+        bincode::serde::decode_from_std_read(&mut encoded.as_slice(), config).unwrap();
+    assert_eq!(decoded.data.len(), 256);
+
+    let config = bincode::config::legacy();
+    let encoded = bincode::serde::encode_to_vec(&decoded, config).unwrap();
+    let (decoded, _): (Blob, usize) = bincode::serde::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded.id, 7);
+}
+
+#[test]
+fn test_serde_bytes_same_format_as_vec() {
+    let config = bincode::config::standard();
+    let data: Vec<u8> = (0..300).map(|i| (i * 7) as u8).collect();
+    let blob = Blob { id: 1, data };
+
+    let encoded = bincode::serde::encode_to_vec(&blob, config).unwrap();
+    let native = bincode::encode_to_vec((1u32, &blob.data), config).unwrap();
+    assert_eq!(encoded, native);
+    // the same as a `Vec<u8>` that serde serializes as a sequence
+    assert_eq!(
+        encoded,
+        bincode::serde::encode_to_vec((1u32, &blob.data), config).unwrap()
+    );
+
+    let ((id, data), _): ((u32, Vec<u8>), usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((id, data), (blob.id, blob.data));
+}
+
+#[test]
+fn test_serde_bytes_visitor() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&[1u8, 2, 3][..], config).unwrap();
+
+    // borrowed from the slice
+    let (bytes, _): (VisitedBytes, usize) =
+        bincode::serde::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(bytes.visited, Visited::Borrowed);
+    assert_eq!(bytes.data, [1, 2, 3]);
+
+    // lent by a reader that can peek, without a `Vec`
+    let bytes: VisitedBytes =
+        bincode::serde::decode_from_reader(bincode::de::read::SliceReader::new(&encoded), config)
+            .unwrap();
+    assert_eq!(bytes.visited, Visited::Bytes);
+    assert_eq!(bytes.data, [1, 2, 3]);
+
+    // read into a `Vec` otherwise
+    let bytes: VisitedBytes =
+        bincode::serde::decode_from_std_read(&mut encoded.as_slice(), config).unwrap();
+    assert_eq!(bytes.visited, Visited::ByteBuf);
+    assert_eq!(bytes.data, [1, 2, 3]);
+
+    let result: Result<VisitedBytes, _> =
+        bincode::serde::decode_from_std_read(&mut &encoded[..3], config);
+    assert!(matches!(
+        result.unwrap_err().into_inner(),
+        bincode::error::DecodeError::UnexpectedEnd { additional: 1 }
+    ));
+}
+
+/// A `u16` between 0 and 100
+#[derive(Debug)]
+struct Percentage(u16);
+
+impl<'de> serde::Deserialize<'de> for Percentage {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u16::deserialize(deserializer)?;
+        if value > 100 {
+            return Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(value.into()),
+                &"a percentage",
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// Rejects every value with a custom message
+#[derive(Debug)]
+struct Rejected;
+
+impl<'de> serde::Deserialize<'de> for Rejected {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = u32::deserialize(deserializer)?;
+        Err(serde::de::Error::custom(format_args!(
+            "id {id} was revoked"
+        )))
+    }
+}
+
+impl serde::Serialize for Rejected {
+    fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom(
+            "cannot serialize a rejected value",
+        ))
+    }
+}
+
+#[allow(dead_code)] // Never decoded successfully
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Untagged {
+    Number(u32),
+    Text(String),
+}
+
+#[test]
+fn test_serde_error_messages() {
+    use bincode::error::DecodeError;
+    use bincode::serde::DecodeError as SerdeDecodeError;
+
+    let config = bincode::config::standard();
+
+    let result: Result<(Rejected, usize), _> = bincode::serde::decode_from_slice(&[7], config);
+    let err = result.unwrap_err();
+    assert_eq!(err.to_string(), "id 7 was revoked");
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::Serde(SerdeDecodeError::Custom(message)) if &*message == "id 7 was revoked"
+    ));
+
+    let encoded = bincode::encode_to_vec(300u16, config).unwrap();
+    let result: Result<(Percentage, usize), _> =
+        bincode::serde::decode_from_slice(&encoded, config);
+    assert_eq!(
+        result.unwrap_err().into_inner().to_string(),
+        "invalid value: integer `300`, expected a percentage"
+    );
+    let (percentage, _): (Percentage, usize) =
+        bincode::serde::decode_from_slice(&[42], config).unwrap();
+    assert_eq!(percentage.0, 42);
+
+    let result: Result<(Untagged, usize), _> = bincode::serde::decode_from_slice(&[1], config);
+    let err = result.unwrap_err().into_inner();
+    assert_eq!(
+        err.to_string(),
+        "serde's `deserialize_any` is not supported by bincode, because its format is not self-describing"
+    );
+    let DecodeError::Serde(err) = err else {
+        panic!("{err:?}");
+    };
+    assert_eq!(err.unsupported_method(), Some("deserialize_any"));
+
+    let err: DecodeError = serde::de::Error::missing_field("id");
+    assert_eq!(err.to_string(), "missing field `id`");
+
+    let err = bincode::serde::encode_to_vec(Rejected, config).unwrap_err();
+    assert_eq!(err.to_string(), "cannot serialize a rejected value");
+}
@@ -0,0 +1,235 @@
+#![cfg(all(feature = "serde", feature = "alloc", feature = "derive"))]
+extern crate bincode_next as bincode;
+
+use bincode::serde::legacy;
+use bincode_1::Options;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Record {
+    id: u32,
+    name: String,
+    score: i16,
+    ratio: f32,
+    flag: bool,
+    initial: char,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Event {
+    Start,
+    Move(i32, i32),
+    Rename { from: String, to: String },
+    Stop,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Nested {
+    inner: Option<Option<u8>>,
+    list: Option<Vec<Option<u16>>>,
+}
+
+fn record() -> Record {
+    Record {
+        id: 7,
+        name: String::from("bob"),
+        score: -2,
+        ratio: 1.5,
+        flag: true,
+        initial: 'é',
+    }
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event::Start,
+        Event::Move(-1, 300),
+        Event::Rename {
+            from: String::from("a"),
+            to: String::from("b"),
+        },
+        Event::Stop,
+    ]
+}
+
+fn map() -> BTreeMap<String, u64> {
+    let mut map = BTreeMap::new();
+    map.insert(String::from("one"), 1);
+    map.insert(String::from("two"), 1000);
+    map
+}
+
+fn nested() -> Vec<Nested> {
+    vec![
+        Nested {
+            inner: None,
+            list: None,
+        },
+        Nested {
+            inner: Some(None),
+            list: Some(vec![]),
+        },
+        Nested {
+            inner: Some(Some(5)),
+            list: Some(vec![None, Some(258)]),
+        },
+    ]
+}
+
+#[test]
+fn test_legacy_struct() {
+    // bincode_1::serialize(&record())
+    #[rustfmt::skip]
+    let fixint = [
+        7, 0, 0, 0, // id
+        3, 0, 0, 0, 0, 0, 0, 0, b'b', b'o', b'b', // name
+        254, 255, // score
+        0, 0, 192, 63, // ratio
+        1, // flag
+        195, 169, // initial
+    ];
+    // bincode_1::options().serialize(&record())
+    #[rustfmt::skip]
+    let varint = [
+        7, // id
+        3, b'b', b'o', b'b', // name
+        3, // score, zigzag encoded
+        0, 0, 192, 63, // ratio
+        1, // flag
+        195, 169, // initial
+    ];
+    assert_eq!(bincode_1::serialize(&record()).unwrap(), fixint);
+    assert_eq!(bincode_1::options().serialize(&record()).unwrap(), varint);
+
+    let decoded: Record = legacy::deserialize(&fixint, bincode::config::legacy()).unwrap();
+    assert_eq!(decoded, record());
+    let decoded: Record = legacy::deserialize(&varint, bincode::config::standard()).unwrap();
+    assert_eq!(decoded, record());
+}
+
+#[test]
+fn test_legacy_enum() {
+    // bincode_1::serialize(&events())
+    #[rustfmt::skip]
+    let fixint = [
+        4, 0, 0, 0, 0, 0, 0, 0, // length
+        0, 0, 0, 0, // Start
+        1, 0, 0, 0, 255, 255, 255, 255, 44, 1, 0, 0, // Move(-1, 300)
+        2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'a', 1, 0, 0, 0, 0, 0, 0, 0, b'b', // Rename
+        3, 0, 0, 0, // Stop
+    ];
+    // bincode_1::options().serialize(&events())
+    #[rustfmt::skip]
+    let varint = [
+        4, // length
+        0, // Start
+        1, 1, 251, 88, 2, // Move(-1, 300)
+        2, 1, b'a', 1, b'b', // Rename
+        3, // Stop
+    ];
+    assert_eq!(bincode_1::serialize(&events()).unwrap(), fixint);
+    assert_eq!(bincode_1::options().serialize(&events()).unwrap(), varint);
+
+    let decoded: Vec<Event> = legacy::deserialize(&fixint, bincode::config::legacy()).unwrap();
+    assert_eq!(decoded, events());
+    let decoded: Vec<Event> = legacy::deserialize(&varint, bincode::config::standard()).unwrap();
+    assert_eq!(decoded, events());
+}
+
+#[test]
+fn test_legacy_map() {
+    // bincode_1::serialize(&map())
+    #[rustfmt::skip]
+    let fixint = [
+        2, 0, 0, 0, 0, 0, 0, 0, // length
+        3, 0, 0, 0, 0, 0, 0, 0, b'o', b'n', b'e', 1, 0, 0, 0, 0, 0, 0, 0,
+        3, 0, 0, 0, 0, 0, 0, 0, b't', b'w', b'o', 232, 3, 0, 0, 0, 0, 0, 0,
+    ];
+    // bincode_1::options().serialize(&map())
+    #[rustfmt::skip]
+    let varint = [
+        2, // length
+        3, b'o', b'n', b'e', 1,
+        3, b't', b'w', b'o', 251, 232, 3,
+    ];
+    assert_eq!(bincode_1::serialize(&map()).unwrap(), fixint);
+    assert_eq!(bincode_1::options().serialize(&map()).unwrap(), varint);
+
+    let decoded: BTreeMap<String, u64> =
+        legacy::deserialize(&fixint, bincode::config::legacy()).unwrap();
+    assert_eq!(decoded, map());
+    let decoded: BTreeMap<String, u64> =
+        legacy::deserialize(&varint, bincode::config::standard()).unwrap();
+    assert_eq!(decoded, map());
+}
+
+#[test]
+fn test_legacy_nested_options() {
+    // bincode_1::serialize(&nested())
+    #[rustfmt::skip]
+    let fixint = [
+        3, 0, 0, 0, 0, 0, 0, 0, // length
+        0, 0, // None, None
+        1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, // Some(None), Some([])
+        1, 1, 5, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 1, // Some(Some(5)), Some([None, Some(258)])
+    ];
+    // bincode_1::options().serialize(&nested())
+    let varint = [3, 0, 0, 1, 0, 1, 0, 1, 1, 5, 1, 2, 0, 1, 251, 2, 1];
+    // bincode_1::options().with_big_endian().serialize(&nested())
+    let varint_be = [3, 0, 0, 1, 0, 1, 0, 1, 1, 5, 1, 2, 0, 1, 251, 1, 2];
+    assert_eq!(bincode_1::serialize(&nested()).unwrap(), fixint);
+    assert_eq!(bincode_1::options().serialize(&nested()).unwrap(), varint);
+    assert_eq!(
+        bincode_1::options()
+            .with_big_endian()
+            .serialize(&nested())
+            .unwrap(),
+        varint_be
+    );
+
+    let decoded: Vec<Nested> = legacy::deserialize(&fixint, bincode::config::legacy()).unwrap();
+    assert_eq!(decoded, nested());
+    let decoded: Vec<Nested> = legacy::deserialize(&varint, bincode::config::standard()).unwrap();
+    assert_eq!(decoded, nested());
+    let decoded: Vec<Nested> =
+        legacy::deserialize(&varint_be, bincode::config::standard().with_big_endian()).unwrap();
+    assert_eq!(decoded, nested());
+}
+
+#[test]
+fn test_legacy_trailing_bytes() {
+    let mut bytes = bincode_1::serialize(&record()).unwrap();
+    let len = bytes.len();
+    bytes.extend_from_slice(&[1, 2, 3]);
+
+    // bincode::deserialize ignores trailing bytes
+    let decoded: Record = legacy::deserialize(&bytes, bincode::config::legacy()).unwrap();
+    assert_eq!(decoded, record());
+
+    let mut deserializer = legacy::Deserializer::from_slice(&bytes, bincode::config::legacy());
+    let decoded: Record = serde::Deserialize::deserialize(deserializer.as_deserializer()).unwrap();
+    assert_eq!(decoded, record());
+    assert_eq!(deserializer.bytes_read(), len);
+    assert!(matches!(
+        deserializer.end(),
+        Err(bincode::error::DecodeError::Serde(
+            bincode::serde::DecodeError::TrailingBytes(3)
+        ))
+    ));
+
+    let mut deserializer =
+        legacy::Deserializer::from_slice(&bytes[..len], bincode::config::legacy());
+    let decoded: Record = serde::Deserialize::deserialize(deserializer.as_deserializer()).unwrap();
+    assert_eq!(decoded, record());
+    deserializer.end().unwrap();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_legacy_deserialize_from() {
+    let bytes = bincode_1::options().serialize(&events()).unwrap();
+    let decoded: Vec<Event> =
+        legacy::deserialize_from(&mut bytes.as_slice(), bincode::config::standard()).unwrap();
+    assert_eq!(decoded, events());
+}
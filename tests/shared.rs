@@ -0,0 +1,155 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    error::{DecodeError, EncodeError},
+    shared::{SharedArc, SharedRc, SharedTable},
+};
+use std::{
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, PartialEq, bincode::Encode, bincode::Decode)]
+#[bincode(decode_context = "SharedTable")]
+struct Node {
+    name: String,
+    children: Vec<SharedArc<Node>>,
+}
+
+fn node(name: &str, children: Vec<SharedArc<Node>>) -> SharedArc<Node> {
+    SharedArc(Arc::new(Node {
+        name: name.to_string(),
+        children,
+    }))
+}
+
+#[test]
+fn test_diamond() {
+    let config = bincode::config::standard();
+
+    //   top
+    //  /   \
+    // left right
+    //  \   /
+    //  bottom
+    let bottom = node("bottom", vec![]);
+    let left = node("left", vec![bottom.clone()]);
+    let right = node("right", vec![bottom.clone()]);
+    let top = node("top", vec![left, right]);
+
+    let encoded = bincode::encode_to_vec(&top, config).unwrap();
+    let (decoded, len): (SharedArc<Node>, usize) =
+        bincode::decode_from_slice_with_context(&encoded, config, SharedTable::new()).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(decoded, top);
+
+    let left = &decoded.0.children[0].0;
+    let right = &decoded.0.children[1].0;
+    assert!(!Arc::ptr_eq(left, right));
+    assert!(Arc::ptr_eq(&left.children[0].0, &right.children[0].0));
+}
+
+#[test]
+fn test_size_independent_of_references() {
+    let config = bincode::config::standard();
+    let mesh = SharedArc(Arc::new(vec![7u8; 1000]));
+
+    let encode = |references: usize| {
+        let meshes: Vec<SharedArc<Vec<u8>>> = (0..references).map(|_| mesh.clone()).collect();
+        bincode::encode_to_vec(&meshes, config).unwrap()
+    };
+
+    // every further reference is a single byte, and the mesh is encoded once
+    let few = encode(2);
+    let many = encode(200);
+    assert_eq!(many.len() - few.len(), 198);
+    assert!(many.len() < 1300);
+
+    let (decoded, _): (Vec<SharedArc<Vec<u8>>>, usize) =
+        bincode::decode_from_slice_with_context(&many, config, SharedTable::new()).unwrap();
+    assert_eq!(decoded.len(), 200);
+    assert!(decoded.iter().all(|m| Arc::ptr_eq(&m.0, &decoded[0].0)));
+    assert_eq!(*decoded[0].0, vec![7u8; 1000]);
+}
+
+#[test]
+fn test_shared_rc() {
+    let config = bincode::config::standard();
+    let first = SharedRc(Rc::new(String::from("first")));
+    let second = SharedRc(Rc::new(String::from("second")));
+    let value = vec![first.clone(), second.clone(), first, second];
+
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    let mut table = SharedTable::new();
+    let (decoded, _): (Vec<SharedRc<String>>, usize) =
+        bincode::decode_from_slice_with_context(&encoded, config, &mut table).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(table.len(), 2);
+    assert!(Rc::ptr_eq(&decoded[0].0, &decoded[2].0));
+    assert!(Rc::ptr_eq(&decoded[1].0, &decoded[3].0));
+    assert!(!Rc::ptr_eq(&decoded[0].0, &decoded[1].0));
+}
+
+#[derive(bincode::Encode)]
+struct Cyclic {
+    next: Mutex<Option<SharedArc<Cyclic>>>,
+}
+
+#[test]
+fn test_cycle() {
+    let config = bincode::config::standard();
+    let cyclic = SharedArc(Arc::new(Cyclic {
+        next: Mutex::new(None),
+    }));
+    *cyclic.0.next.lock().unwrap() = Some(cyclic.clone());
+
+    let err = bincode::encode_to_vec(&cyclic, config).unwrap_err();
+    assert!(matches!(err, EncodeError::SharedCycle));
+
+    // break the cycle, so the value can be dropped
+    cyclic.0.next.lock().unwrap().take();
+
+    // a reference to the value that is being decoded
+    let encoded = [0, 1, 1];
+    let err = bincode::decode_from_slice_with_context::<_, SharedArc<(u8, SharedArc<u8>)>, _>(
+        &encoded,
+        config,
+        SharedTable::new(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::SharedCycle { id: 0 }
+    ));
+}
+
+#[test]
+fn test_invalid_shared_id() {
+    let config = bincode::config::standard();
+
+    // a reference to a value that was never decoded
+    let err = bincode::decode_from_slice_with_context::<_, SharedArc<u8>, _>(
+        &[3],
+        config,
+        SharedTable::new(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::InvalidSharedId { id: 2 }
+    ));
+
+    // a reference to a value of another type
+    let err = bincode::decode_from_slice_with_context::<_, (SharedArc<u8>, SharedArc<u16>), _>(
+        &[0, 5, 1],
+        config,
+        SharedTable::new(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::InvalidSharedId { id: 0 }
+    ));
+}
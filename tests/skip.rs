@@ -0,0 +1,109 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{
+        read::{Reader, SegmentedSliceReader, SliceReader, TakeReader},
+        Decode, Decoder, DecoderImpl,
+    },
+    error::DecodeError,
+    BufferedIoReader, IoReader, SeekReader,
+};
+use std::io::{BufReader, Cursor};
+
+const SKIPPED: usize = 1000;
+
+/// A `u32`, a section of `SKIPPED` bytes that is skipped, and a `String`
+fn input() -> Vec<u8> {
+    let config = bincode::config::standard();
+    let mut input = bincode::encode_to_vec(7u32, config).unwrap();
+    input.extend((0..SKIPPED).map(|i| i as u8));
+    input.extend(bincode::encode_to_vec(String::from("after"), config).unwrap());
+    input
+}
+
+fn skip_between_decodes<R: Reader>(reader: R) {
+    let mut decoder = DecoderImpl::new(reader, bincode::config::standard(), ());
+    assert_eq!(u32::decode(&mut decoder).unwrap(), 7);
+    decoder.skip_bytes(SKIPPED).unwrap();
+    assert_eq!(String::decode(&mut decoder).unwrap(), "after");
+
+    // 6 bytes left
+    let err = decoder.skip_bytes(0).and_then(|()| decoder.skip_bytes(10));
+    assert!(matches!(
+        err,
+        Err(DecodeError::UnexpectedEnd { additional: 10 })
+    ));
+}
+
+#[test]
+fn test_skip_readers() {
+    let input = input();
+    skip_between_decodes(SliceReader::new(&input));
+    skip_between_decodes(IoReader::new(input.as_slice()));
+    skip_between_decodes(SeekReader::new(Cursor::new(&input)));
+    // buffers that are much smaller than the skipped bytes
+    skip_between_decodes(BufReader::with_capacity(16, input.as_slice()));
+    skip_between_decodes(BufferedIoReader::with_capacity(16, input.as_slice()));
+
+    let segments: Vec<&[u8]> = input.chunks(7).collect();
+    skip_between_decodes(SegmentedSliceReader::new(&segments));
+}
+
+#[test]
+fn test_skip_past_end() {
+    let input = [0u8; 300];
+
+    let mut reader = SliceReader::new(&input);
+    assert!(matches!(
+        reader.skip(301),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    // the default implementation reports the bytes of the chunks that it did not get to as well
+    let mut reader = IoReader::new(input.as_slice());
+    assert!(matches!(
+        reader.skip(1000),
+        Err(DecodeError::UnexpectedEnd { additional: 700 })
+    ));
+    let mut reader = BufferedIoReader::with_capacity(64, input.as_slice());
+    assert!(matches!(
+        reader.skip(1000),
+        Err(DecodeError::UnexpectedEnd { additional: 700 })
+    ));
+
+    // a failed seek leaves the position as it was
+    let mut reader = SeekReader::new(Cursor::new(&input));
+    reader.skip(100).unwrap();
+    assert!(matches!(
+        reader.skip(1000),
+        Err(DecodeError::UnexpectedEnd { additional: 800 })
+    ));
+    reader.skip(200).unwrap();
+    assert_eq!(reader.bytes_read(), 300);
+}
+
+#[test]
+fn test_skip_claims_bytes() {
+    let input = [0u8; 300];
+
+    let config = bincode::config::standard().with_limit::<100>();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&input), config, ());
+    decoder.skip_bytes(60).unwrap();
+    assert_eq!(decoder.limit_remaining(), Some(40));
+    assert!(matches!(
+        decoder.skip_bytes(60),
+        Err(DecodeError::LimitExceeded)
+    ));
+
+    let mut reader = TakeReader::new(SliceReader::new(&input), 100);
+    reader.skip(60).unwrap();
+    assert_eq!(reader.remaining(), 40);
+    assert!(matches!(
+        reader.skip(60),
+        Err(DecodeError::TakeLimitExceeded {
+            limit: 100,
+            additional: 20
+        })
+    ));
+}
@@ -0,0 +1,255 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::{
+        read::{Reader, SliceReader},
+        Decode, Decoder, DecoderImpl, SkipDecode,
+    },
+    error::DecodeError,
+};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+struct Point {
+    x: u32,
+    y: u32,
+    visible: bool,
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+enum Shape {
+    Empty,
+    Circle { center: Point, radius: f32 },
+    Polygon(Vec<Point>, Option<String>),
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+struct Scene<T> {
+    points: Vec<Point>,
+    shapes: Vec<Shape>,
+    name: T,
+}
+
+fn scene() -> Scene<String> {
+    Scene {
+        points: (0..1000)
+            .map(|i| Point {
+                x: i,
+                y: i * 1000,
+                visible: i % 2 == 0,
+            })
+            .collect(),
+        shapes: vec![
+            Shape::Empty,
+            Shape::Circle {
+                center: Point {
+                    x: 1,
+                    y: 2,
+                    visible: true,
+                },
+                radius: 1.5,
+            },
+            Shape::Polygon(
+                vec![Point {
+                    x: 3,
+                    y: 300,
+                    visible: false,
+                }],
+                Some(String::from("triangle")),
+            ),
+        ],
+        name: String::from("scene"),
+    }
+}
+
+fn slice_decoder<C: Config>(bytes: &[u8], config: C) -> DecoderImpl<SliceReader<'_>, C, ()> {
+    DecoderImpl::new(SliceReader::new(bytes), config, ())
+}
+
+#[test]
+fn test_skip_to_third_field() {
+    fn check(config: impl Config) {
+        let bytes = bincode::encode_to_vec(scene(), config).unwrap();
+        let mut decoder = slice_decoder(&bytes, config);
+        // `Point` is fixed-size with a fixint config, `Shape` never is
+        bincode::skip_value::<Vec<Point>, _>(&mut decoder).unwrap();
+        bincode::skip_value::<Vec<Shape>, _>(&mut decoder).unwrap();
+        assert_eq!(String::decode(&mut decoder).unwrap(), "scene");
+        assert_eq!(decoder.reader().remaining_hint(), Some(0));
+    }
+    check(config::standard());
+    check(config::legacy());
+    check(
+        config::standard()
+            .with_big_endian()
+            .with_fixed_int_encoding(),
+    );
+}
+
+#[test]
+fn test_skip_whole_values() {
+    let config = config::standard();
+    let value = (scene(), 7u8);
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let mut decoder = slice_decoder(&bytes, config);
+    bincode::skip_value::<Scene<String>, _>(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 7);
+
+    let mut decoder = slice_decoder(&bytes, config);
+    <(Scene<String>, u8)>::skip(&mut decoder).unwrap();
+    assert_eq!(decoder.reader().remaining_hint(), Some(0));
+}
+
+fn fixed_size<T: SkipDecode + ?Sized, C: Config>(_: C) -> Option<usize> {
+    T::fixed_size::<C>()
+}
+
+#[test]
+fn test_fixed_size() {
+    assert_eq!(fixed_size::<Point, _>(config::legacy()), Some(9));
+    assert_eq!(fixed_size::<Point, _>(config::standard()), None);
+    assert_eq!(fixed_size::<[Point; 3], _>(config::legacy()), Some(27));
+    assert_eq!(
+        fixed_size::<(u8, bool, f64), _>(config::standard()),
+        Some(10)
+    );
+    assert_eq!(fixed_size::<Shape, _>(config::legacy()), None);
+    assert_eq!(fixed_size::<Vec<u8>, _>(config::legacy()), None);
+}
+
+#[test]
+fn test_skipped_bytes_are_not_validated() {
+    // 2 is not a valid `bool`, and the string is not UTF-8
+    let config = config::standard();
+    let bytes = [2, 2, 0xFF, 0xFE, 9];
+    let mut decoder = slice_decoder(&bytes, config);
+    <(bool, String)>::skip(&mut decoder).unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 9);
+}
+
+#[test]
+fn test_skip_collections() {
+    let config = config::standard();
+    let value = (
+        BTreeMap::from([(1u16, String::from("one")), (2, String::from("two"))]),
+        HashMap::from([(String::from("a"), vec![1u64, u64::MAX])]),
+        Some(Box::new(Shape::Empty)),
+        Ok::<char, ()>('é'),
+        [Some(300u32), None],
+        std::time::Duration::from_secs(5),
+    );
+    let mut bytes = bincode::encode_to_vec(&value, config).unwrap();
+    bytes.push(42);
+    let mut decoder = slice_decoder(&bytes, config);
+    bincode::skip_value::<
+        (
+            BTreeMap<u16, String>,
+            HashMap<String, Vec<u64>>,
+            Option<Box<Shape>>,
+            Result<char, ()>,
+            [Option<u32>; 2],
+            std::time::Duration,
+        ),
+        _,
+    >(&mut decoder)
+    .unwrap();
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 42);
+}
+
+#[test]
+fn test_skip_errors() {
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(7u32, config).unwrap();
+    let err = bincode::skip_value::<Shape, _>(&mut slice_decoder(&bytes, config)).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::UnexpectedVariant {
+            found: 7,
+            type_name: "Shape",
+            ..
+        }
+    ));
+
+    // the length claims more points than there are
+    let bytes = bincode::encode_to_vec(vec![0u8; 10], config).unwrap();
+    let err =
+        bincode::skip_value::<Vec<[u8; 4]>, _>(&mut slice_decoder(&bytes, config)).unwrap_err();
+    assert!(matches!(err, DecodeError::UnexpectedEnd { additional: 30 }));
+
+    let err = bincode::skip_value::<Option<u8>, _>(&mut slice_decoder(&[2], config)).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::UnexpectedVariant { found: 2, .. }
+    ));
+
+    // the errors of derived types have the field they happened in
+    let bytes = bincode::encode_to_vec(
+        (
+            vec![Point {
+                x: 1,
+                y: 2,
+                visible: true,
+            }],
+            1u8,
+        ),
+        config,
+    )
+    .unwrap();
+    let err =
+        bincode::skip_value::<Scene<String>, _>(&mut slice_decoder(&bytes, config)).unwrap_err();
+    assert!(err.to_string().contains("shapes"), "{err}");
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, bincode::SkipDecode, PartialEq, Debug)]
+#[bincode(option_bitmap)]
+struct Attributes<'a> {
+    first: Option<u32>,
+    #[bincode(len = 8)]
+    code: String,
+    #[bincode(pad_to = 16)]
+    label: String,
+    second: Option<String>,
+    borrowed: &'a str,
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::SkipDecode, PartialEq, Debug)]
+#[bincode(pad_struct_to = 12)]
+struct Padded(u8, u16);
+
+#[derive(bincode::Encode, bincode::SkipDecode)]
+struct Trailer {
+    id: u8,
+    #[bincode(remaining_bytes)]
+    rest: Vec<u8>,
+}
+
+#[test]
+fn test_skip_with_attributes() {
+    let config = config::standard();
+    let value = (
+        Attributes {
+            first: None,
+            code: String::from("abc"),
+            label: String::from("label"),
+            second: Some(String::from("second")),
+            borrowed: "borrowed",
+        },
+        Padded(1, 2),
+        Trailer {
+            id: 1,
+            rest: vec![1, 2, 3],
+        },
+    );
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let mut decoder = slice_decoder(&bytes, config);
+    bincode::skip_value::<Attributes<'_>, _>(&mut decoder).unwrap();
+    assert_eq!(Padded::decode(&mut decoder).unwrap(), Padded(1, 2));
+    bincode::skip_value::<Trailer, _>(&mut decoder).unwrap();
+    assert_eq!(decoder.reader().remaining_hint(), Some(0));
+
+    assert_eq!(fixed_size::<Padded, _>(config::standard()), Some(12));
+    assert_eq!(fixed_size::<Attributes, _>(config::legacy()), None);
+}
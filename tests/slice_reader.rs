@@ -0,0 +1,73 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::de::read::{BorrowReader, Reader, SliceReader};
+use bincode::de::{BorrowDecode, Decode, DecoderImpl};
+use bincode::error::DecodeError;
+
+const MAGIC: [u8; 4] = *b"BNC2";
+
+fn message() -> Vec<u8> {
+    let config = bincode::config::standard();
+    let mut bytes = bincode::encode_to_vec(300u32, config).unwrap();
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend(bincode::encode_to_vec(("name", vec![1u8, 2, 3]), config).unwrap());
+    bytes.extend_from_slice(b"tail");
+    bytes
+}
+
+#[test]
+fn test_interleaved_decoding() {
+    let config = bincode::config::standard();
+    let bytes = message();
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(reader.position(), 0);
+    assert_eq!(reader.remaining(), &bytes[..]);
+
+    let id = u32::decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!((id, reader.position()), (300, 3));
+
+    assert_eq!(reader.remaining()[..4], MAGIC);
+    assert_eq!(reader.take_bytes(4).unwrap(), MAGIC);
+    assert_eq!(reader.position(), 7);
+
+    let (name, data): (&str, Vec<u8>) =
+        BorrowDecode::borrow_decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!((name, data.as_slice()), ("name", &[1, 2, 3][..]));
+    assert_eq!(reader.position(), 16);
+
+    // the remainder outlives the reader
+    let tail = reader.into_remainder();
+    assert_eq!(tail, b"tail");
+    assert_eq!(tail.as_ptr(), bytes[16..].as_ptr());
+}
+
+#[test]
+fn test_position_after_errors_and_skips() {
+    let config = bincode::config::standard();
+    let bytes = message();
+
+    let mut reader = SliceReader::new(&bytes);
+    reader.skip(3).unwrap();
+    let result = u64::decode(&mut DecoderImpl::new(
+        &mut reader,
+        config.with_fixed_int_encoding(),
+        (),
+    ));
+    assert!(result.is_ok());
+    assert_eq!(reader.position(), 11);
+
+    let result = <[u8; 10]>::decode(&mut DecoderImpl::new(&mut reader, config, ()));
+    assert!(matches!(
+        result,
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+    // a failed read does not advance the reader
+    assert_eq!((reader.position(), reader.remaining().len()), (11, 9));
+
+    reader.consume(5);
+    assert_eq!(reader.position(), 16);
+    assert_eq!(reader.into_remainder(), b"tail");
+}
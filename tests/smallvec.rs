@@ -0,0 +1,52 @@
+#![cfg(feature = "smallvec")]
+
+extern crate bincode_next as bincode;
+
+use smallvec::{smallvec, SmallVec};
+
+#[test]
+fn test_smallvec_inline() {
+    let config = bincode::config::standard();
+    let vec: SmallVec<[u8; 16]> = smallvec![1, 2, 3, 4];
+    let encoded = bincode::encode_to_vec(&vec, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(vec.to_vec(), config).unwrap()
+    );
+
+    let (decoded, len): (SmallVec<[u8; 16]>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, encoded.len());
+    assert!(!decoded.spilled());
+}
+
+#[test]
+fn test_smallvec_spilled() {
+    let config = bincode::config::legacy();
+    let vec: SmallVec<[u32; 4]> = (0..100).collect();
+    assert!(vec.spilled());
+    let encoded = bincode::encode_to_vec(&vec, config).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(vec.to_vec(), config).unwrap()
+    );
+
+    let (decoded, len): (SmallVec<[u32; 4]>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, encoded.len());
+    assert!(decoded.spilled());
+}
+
+#[test]
+fn test_smallvec_borrowed() {
+    let config = bincode::config::standard();
+    let vec: SmallVec<[&str; 2]> = smallvec!["hello", "world", "!"];
+    let encoded = bincode::encode_to_vec(&vec, config).unwrap();
+
+    let (decoded, len): (SmallVec<[&str; 2]>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(len, encoded.len());
+}
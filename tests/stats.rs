@@ -0,0 +1,202 @@
+#![cfg(all(feature = "stats", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{read::SliceReader, BorrowDecode, Decode, DecoderImpl},
+    enc::write::SizeWriter,
+    stats::{StatsReader, StatsWriter},
+};
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Shape {
+    Point,
+    Circle(Position, u16),
+    Polygon { corners: Vec<Position> },
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct Scene {
+    id: u64,
+    name: String,
+    shapes: Vec<Shape>,
+    origin: Position,
+}
+
+fn scene() -> Scene {
+    Scene {
+        id: 7,
+        name: String::from("scene"),
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle(Position { x: 1.0, y: 2.0 }, 3),
+            Shape::Polygon {
+                corners: vec![Position { x: 0.0, y: 0.0 }, Position { x: 1.0, y: 1.0 }],
+            },
+        ],
+        origin: Position { x: 0.0, y: 0.0 },
+    }
+}
+
+fn stats(entries: &[(&str, usize)]) -> Vec<(String, usize)> {
+    entries
+        .iter()
+        .map(|(path, bytes)| (path.to_string(), *bytes))
+        .collect()
+}
+
+#[test]
+fn test_encode_stats() {
+    let config = bincode::config::legacy();
+    let mut writer = StatsWriter::new(SizeWriter::default());
+    bincode::encode_into_writer(scene(), &mut writer, config).unwrap();
+
+    let total = bincode::encode_to_vec(scene(), config).unwrap().len();
+    assert_eq!(writer.bytes_written(), total);
+    let (inner, entries) = writer.into_parts();
+    assert_eq!(inner.bytes_written, total);
+    assert_eq!(entries.iter().map(|(_, bytes)| bytes).sum::<usize>(), total);
+    assert_eq!(
+        entries,
+        stats(&[
+            ("Scene.id", 8),
+            // the length and the bytes
+            ("Scene.name", 8 + 5),
+            // the length of the `Vec` and the tags of the variants
+            ("Scene.shapes", 8 + 3 * 4),
+            ("Scene.shapes/Shape::Circle.0/Position.x", 4),
+            ("Scene.shapes/Shape::Circle.0/Position.y", 4),
+            ("Scene.shapes/Shape::Circle.1", 2),
+            ("Scene.shapes/Shape::Polygon.corners", 8),
+            ("Scene.shapes/Shape::Polygon.corners/Position.x", 2 * 4),
+            ("Scene.shapes/Shape::Polygon.corners/Position.y", 2 * 4),
+            ("Scene.origin/Position.x", 4),
+            ("Scene.origin/Position.y", 4),
+        ])
+    );
+}
+
+#[test]
+fn test_top_level_enum_tag() {
+    let mut writer = StatsWriter::new(SizeWriter::default());
+    bincode::encode_into_writer(
+        Shape::Circle(Position { x: 1.0, y: 2.0 }, 3),
+        &mut writer,
+        bincode::config::standard(),
+    )
+    .unwrap();
+    assert_eq!(
+        writer.stats(),
+        stats(&[
+            ("", 1),
+            ("Shape::Circle.0/Position.x", 4),
+            ("Shape::Circle.0/Position.y", 4),
+            ("Shape::Circle.1", 1),
+        ])
+    );
+}
+
+#[test]
+fn test_decode_stats() {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(scene(), config).unwrap();
+    let mut writer = StatsWriter::new(SizeWriter::default());
+    bincode::encode_into_writer(scene(), &mut writer, config).unwrap();
+
+    let mut reader = StatsReader::new(SliceReader::new(&bytes));
+    let decoded = Scene::decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!(decoded, scene());
+    assert_eq!(reader.bytes_read(), bytes.len());
+    assert_eq!(reader.stats(), writer.stats());
+
+    let mut reader = StatsReader::new(SliceReader::new(&bytes));
+    let decoded = Scene::borrow_decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!(decoded, scene());
+    assert_eq!(reader.into_parts().1, writer.into_parts().1);
+}
+
+#[test]
+fn test_decode_stats_with_offset() {
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(scene(), config).unwrap();
+    let mut writer = StatsWriter::new(SizeWriter::default());
+    bincode::encode_into_writer(scene(), &mut writer, config).unwrap();
+
+    let mut reader = StatsReader::new(SliceReader::new(&bytes));
+    let decoded: Scene = bincode::decode_from_reader_with_offset(&mut reader, config).unwrap();
+    assert_eq!(decoded, scene());
+    assert_eq!(reader.stats(), writer.stats());
+
+    // the tag of the first shape is the byte after the id, the name and the length of the shapes
+    let mut corrupted = bytes.clone();
+    corrupted[1 + 1 + 5 + 1] = 9;
+    let mut reader = StatsReader::new(SliceReader::new(&corrupted));
+    let err =
+        bincode::decode_from_reader_with_offset::<Scene, _, _>(&mut reader, config).unwrap_err();
+    assert_eq!(err.offset(), Some(9));
+    assert_eq!(
+        reader.stats(),
+        stats(&[
+            ("Scene.id", 1),
+            ("Scene.name", 1 + 5),
+            ("Scene.shapes", 1 + 1)
+        ])
+    );
+}
+
+#[test]
+fn test_borrowed_stats() {
+    #[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+    struct Borrowed<'a> {
+        key: &'a str,
+        value: &'a [u8],
+    }
+
+    let config = bincode::config::standard();
+    let value = Borrowed {
+        key: "key",
+        value: &[1, 2, 3, 4],
+    };
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let mut reader = StatsReader::new(SliceReader::new(&bytes));
+    let decoded = Borrowed::borrow_decode(&mut DecoderImpl::new(&mut reader, config, ())).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(
+        reader.stats(),
+        stats(&[("Borrowed.key", 1 + 3), ("Borrowed.value", 1 + 4)])
+    );
+}
+
+#[test]
+fn test_length_prefix_stats() {
+    struct Section(Position);
+
+    impl bincode::Encode for Section {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            encoder.with_length_prefix(|encoder| self.0.encode(encoder))
+        }
+    }
+
+    // the length is written in place, and not counted again
+    let mut writer = StatsWriter::new(SizeWriter::default());
+    bincode::encode_into_writer(
+        Section(Position { x: 1.0, y: 2.0 }),
+        &mut writer,
+        bincode::config::standard(),
+    )
+    .unwrap();
+    assert_eq!(writer.bytes_written(), 16);
+    assert_eq!(
+        writer.stats(),
+        stats(&[("", 8), ("Position.x", 4), ("Position.y", 4)])
+    );
+}
@@ -5,7 +5,7 @@ mod utils;
 
 use bincode::error::DecodeError;
 use std::{
-    ffi::CString,
+    ffi::{CStr, CString},
     io::{Cursor, Seek, SeekFrom},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     path::{Path, PathBuf},
@@ -53,6 +53,595 @@ fn test_std_cursor() {
     assert_eq!(foo.b, 10);
 }
 
+#[test]
+fn test_std_read_bytes_read() {
+    let config = bincode::config::standard();
+    let values = [
+        Foo { a: 1, b: 2 },
+        Foo { a: 300, b: 4 },
+        Foo {
+            a: u32::MAX,
+            b: 70_000,
+        },
+    ];
+    let mut log = Vec::new();
+    let mut offsets = Vec::new();
+    for value in &values {
+        offsets.push(log.len());
+        bincode::encode_into_std_write(value, &mut log, config).unwrap();
+    }
+    offsets.push(log.len());
+
+    let mut cursor = Cursor::new(&log);
+    let mut total = 0;
+    for (value, offsets) in values.iter().zip(offsets.windows(2)) {
+        let (foo, bytes_read): (Foo, usize) =
+            bincode::decode_from_std_read_with_bytes_read(&mut cursor, config).unwrap();
+        assert_eq!((foo.a, foo.b), (value.a, value.b));
+        assert_eq!(bytes_read, offsets[1] - offsets[0]);
+        total += bytes_read;
+        assert_eq!(total, offsets[1]);
+        assert_eq!(cursor.position() as usize, total);
+    }
+
+    let mut reader = bincode::IoReader::new(&log[..]);
+    for offsets in offsets.windows(2) {
+        let _: Foo = bincode::decode_from_reader(&mut reader, config).unwrap();
+        assert_eq!(reader.bytes_read(), offsets[1]);
+    }
+}
+
+/// A `std::io::Read` that returns at most `chunk` bytes per call, and fails with `WouldBlock` when it has no more data, like a non-blocking socket
+struct Chunked<'a> {
+    data: &'a [u8],
+    chunk: usize,
+}
+
+impl std::io::Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.data.is_empty() {
+            return Err(std::io::ErrorKind::WouldBlock.into());
+        }
+        let n = buf.len().min(self.chunk).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_buffered_io_reader() {
+    fn check<C: bincode::config::Config>(config: C) {
+        type Values = (u64, String, Vec<u64>, i128, Vec<String>, u16);
+        let values: Values = (
+            u64::MAX,
+            String::from("Hello world, this string is longer than the small buffers"),
+            vec![0, 250, 251, 65_536, u64::MAX / 3, u64::MAX],
+            i128::MIN,
+            vec![String::new(), String::from("a"), String::from("bc")],
+            u16::MAX,
+        );
+        let encoded = bincode::encode_to_vec(&values, config).unwrap();
+        // every capacity makes refills land at a different place, e.g. in the middle of a varint or string
+        for capacity in 1..=encoded.len() + 1 {
+            for chunk in [1, 3, 64] {
+                let mut reader = bincode::BufferedIoReader::with_capacity(
+                    capacity,
+                    Chunked {
+                        data: &encoded,
+                        chunk,
+                    },
+                );
+                let decoded: Values = bincode::decode_from_reader(&mut reader, config).unwrap();
+                assert_eq!(decoded, values, "capacity {capacity}, chunk {chunk}");
+                assert_eq!(reader.bytes_read(), encoded.len());
+                assert!(reader.buffer().is_empty());
+            }
+        }
+    }
+
+    check(bincode::config::standard());
+    check(bincode::config::standard().with_big_endian());
+    check(bincode::config::legacy());
+}
+
+#[test]
+fn test_buffered_io_reader_sequential() {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    for i in 0..100u64 {
+        bincode::encode_into_std_write((i * 1_000_003, format!("record {i}")), &mut stream, config)
+            .unwrap();
+    }
+    stream.extend_from_slice(b"trailer");
+
+    let mut reader = bincode::BufferedIoReader::with_capacity(64, Cursor::new(&stream));
+    for i in 0..100u64 {
+        let (number, text): (u64, String) =
+            bincode::decode_from_reader(&mut reader, config).unwrap();
+        assert_eq!(number, i * 1_000_003);
+        assert_eq!(text, format!("record {i}"));
+    }
+    let consumed = reader.bytes_read();
+    assert_eq!(
+        &stream[consumed..consumed + reader.buffer().len()],
+        reader.buffer()
+    );
+    assert_eq!(&stream[consumed..], b"trailer");
+}
+
+#[test]
+fn test_buffered_io_reader_unexpected_end() {
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec((1u32, String::from("Hello")), config).unwrap();
+    for cut in 0..encoded.len() {
+        for capacity in [1, 4, 1024] {
+            let mut reader = bincode::BufferedIoReader::with_capacity(capacity, &encoded[..cut]);
+            let err = bincode::decode_from_reader::<(u32, String), _, _>(&mut reader, config)
+                .unwrap_err();
+            let expected = bincode::decode_from_slice::<(u32, String), _>(&encoded[..cut], config)
+                .unwrap_err();
+            assert_eq!(err.to_string(), expected.to_string());
+        }
+    }
+}
+
+#[test]
+fn test_decode_borrowed_from_buf_read() {
+    #[derive(bincode::Encode, bincode::BorrowDecode, Debug, PartialEq)]
+    struct Record<'a> {
+        id: u32,
+        name: &'a str,
+        owned: String,
+        tags: Vec<&'a str>,
+        data: &'a [u8],
+    }
+
+    let config = bincode::config::standard();
+    let records: Vec<Record> = (0..20u32)
+        .map(|i| Record {
+            id: i * 1_000_003,
+            name: ["a", "record name", "a longer record name than most"][i as usize % 3],
+            owned: "owned".repeat(i as usize % 5),
+            tags: vec!["x", "yz"],
+            data: &[7; 16],
+        })
+        .collect();
+    let mut stream = Vec::new();
+    for record in &records {
+        bincode::encode_into_std_write(record, &mut stream, config).unwrap();
+    }
+    stream.extend_from_slice(b"trailer");
+
+    // every record fits in the buffer, even when the refills land in the middle of a field
+    for capacity in [128, 200, 1024] {
+        for chunk in [1, 3, 64] {
+            let mut reader = bincode::BufferedIoReader::with_capacity(
+                capacity,
+                Chunked {
+                    data: &stream,
+                    chunk,
+                },
+            );
+            let mut offset = 0;
+            for expected in &records {
+                let (record, len): (Record, _) =
+                    bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+                assert_eq!(&record, expected, "capacity {capacity}, chunk {chunk}");
+                assert_eq!(len, bincode::encode_to_vec(expected, config).unwrap().len());
+                offset += len;
+            }
+            assert_eq!(reader.bytes_read(), offset);
+            // decoding owned values from the same reader continues after the borrowed ones
+            let mut trailer = [0u8; 7];
+            bincode::de::read::Reader::read(&mut reader, &mut trailer).unwrap();
+            assert_eq!(&trailer, b"trailer");
+        }
+    }
+
+    // owned data does not need to fit in the buffer
+    let long = "long".repeat(100);
+    let encoded = bincode::encode_to_vec((&long, "short", &long), config).unwrap();
+    let mut reader = bincode::BufferedIoReader::with_capacity(32, encoded.as_slice());
+    let ((first, short, last), len): ((String, &str, String), _) =
+        bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+    assert_eq!(
+        (first.as_str(), short, last.as_str()),
+        (&*long, "short", &*long)
+    );
+    assert_eq!(len, encoded.len());
+}
+
+#[test]
+fn test_decode_borrowed_from_buf_read_too_large() {
+    let config = bincode::config::standard();
+    let long = "long".repeat(100);
+
+    // a borrowed field larger than the buffer
+    let encoded = bincode::encode_to_vec((1u8, long.as_str()), config).unwrap();
+    let mut reader = bincode::BufferedIoReader::with_capacity(64, encoded.as_slice());
+    let err = bincode::decode_borrowed_from_buf_read::<(u8, &str), _, _>(&mut reader, config)
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        bincode::error::DecodeError::BorrowedDataTooLarge {
+            requested: 400,
+            capacity: 64
+        }
+    ));
+
+    // the same field fits in a larger buffer
+    let mut reader = bincode::BufferedIoReader::with_capacity(404, encoded.as_slice());
+    let ((_, decoded), _): ((u8, &str), _) =
+        bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+    assert_eq!(decoded, long);
+
+    // borrowed fields that fit in the buffer on their own, but not together
+    let encoded = bincode::encode_to_vec((&long[..300], &long[..200]), config).unwrap();
+    let mut reader = bincode::BufferedIoReader::with_capacity(405, encoded.as_slice());
+    let err = bincode::decode_borrowed_from_buf_read::<(&str, &str), _, _>(&mut reader, config)
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        bincode::error::DecodeError::BorrowedDataTooLarge {
+            requested: 200,
+            capacity: 405
+        }
+    ));
+    let mut reader = bincode::BufferedIoReader::with_capacity(510, encoded.as_slice());
+    let ((first, second), _): ((&str, &str), _) =
+        bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+    assert_eq!((first.len(), second.len()), (300, 200));
+
+    // owned bytes between them do not take up room
+    let encoded = bincode::encode_to_vec((&long[..300], &long, &long[..200]), config).unwrap();
+    let mut reader = bincode::BufferedIoReader::with_capacity(510, encoded.as_slice());
+    let ((first, owned, second), _): ((&str, String, &str), _) =
+        bincode::decode_borrowed_from_buf_read(&mut reader, config).unwrap();
+    assert_eq!((first.len(), owned, second.len()), (300, long.clone(), 200));
+
+    // running out of input
+    for cut in 0..encoded.len() {
+        let mut reader = bincode::BufferedIoReader::with_capacity(1024, &encoded[..cut]);
+        let err = bincode::decode_borrowed_from_buf_read::<(&str, String, &str), _, _>(
+            &mut reader,
+            config,
+        )
+        .unwrap_err();
+        let expected =
+            bincode::borrow_decode_from_slice::<(&str, String, &str), _>(&encoded[..cut], config)
+                .unwrap_err();
+        assert_eq!(err.to_string(), expected.to_string());
+    }
+}
+
+/// A `std::io::Write` that counts how often it is called, and writes at most `max` bytes per call
+#[derive(Default)]
+struct CountingWriter {
+    output: Vec<u8>,
+    calls: usize,
+    flushes: usize,
+    max: Option<usize>,
+}
+
+impl std::io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_vectored(&[std::io::IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        self.calls += 1;
+        let mut written = 0;
+        for buf in bufs {
+            let n = buf.len().min(self.max.unwrap_or(usize::MAX) - written);
+            self.output.extend_from_slice(&buf[..n]);
+            written += n;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[derive(bincode::Encode)]
+struct Blobs {
+    id: u64,
+    name: String,
+    blobs: Vec<Vec<u8>>,
+    tags: Vec<u32>,
+}
+
+fn blobs() -> Blobs {
+    Blobs {
+        id: 12_345,
+        name: String::from("blobs"),
+        blobs: (0..10u8)
+            .map(|i| vec![i; 20_000 + usize::from(i)])
+            .collect(),
+        tags: (0..50).collect(),
+    }
+}
+
+#[test]
+fn test_buffered_io_writer_output() {
+    let config = bincode::config::standard();
+    let value = blobs();
+    let mut expected = Vec::new();
+    let len = bincode::encode_into_std_write(&value, &mut expected, config).unwrap();
+
+    for capacity in [0, 1, 7, 64, 8 * 1024, 64 * 1024, 1024 * 1024] {
+        for max in [None, Some(1), Some(5_000)] {
+            let mut output = CountingWriter {
+                max,
+                ..CountingWriter::default()
+            };
+            let mut writer = bincode::BufferedIoWriter::with_capacity(capacity, &mut output);
+            bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+            assert_eq!(writer.bytes_written(), len);
+            writer.flush().unwrap();
+            drop(writer);
+            assert_eq!(output.output, expected, "capacity {capacity}, max {max:?}");
+        }
+    }
+
+    // dropping the writer writes the buffered bytes too
+    let mut output = Vec::new();
+    let mut writer = bincode::BufferedIoWriter::new(&mut output);
+    bincode::encode_into_writer((1u8, "small"), &mut writer, config).unwrap();
+    drop(writer);
+    assert_eq!(
+        output,
+        bincode::encode_to_vec((1u8, "small"), config).unwrap()
+    );
+}
+
+#[test]
+fn test_buffered_io_writer_calls() {
+    let config = bincode::config::standard();
+    let value = blobs();
+
+    let mut unbuffered = CountingWriter::default();
+    bincode::encode_into_std_write(&value, &mut unbuffered, config).unwrap();
+
+    let mut buffered = CountingWriter::default();
+    let mut writer = bincode::BufferedIoWriter::new(&mut buffered);
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    assert_eq!(unbuffered.output, buffered.output);
+    // id: 1, name: 2, blobs: 1 + 10 * 3 (the u16 varint lengths are 2 writes), tags: 1 + 50
+    assert_eq!(unbuffered.calls, 86);
+    // one call per blob, which also writes the small values before it, and one for the rest
+    assert_eq!(buffered.calls, 11);
+}
+
+#[test]
+fn test_buffered_io_writer_error() {
+    struct Full;
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Ok(0)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let mut full = Full;
+    let mut writer = bincode::BufferedIoWriter::with_capacity(16, &mut full);
+    // buffered, so this does not fail yet
+    bincode::encode_into_writer(5u32, &mut writer, config).unwrap();
+    let err = writer.flush().unwrap_err();
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::Io { ref inner, index: 0 } if inner.kind() == std::io::ErrorKind::WriteZero
+    ));
+}
+
+#[derive(bincode::Encode)]
+struct SmallFields {
+    records: Vec<(u8, u16, u32, u64, bool, char)>,
+    blob: Vec<u8>,
+    names: Vec<(i32, String)>,
+}
+
+/// Many small fields, with a blob in between that is larger than the buffer
+fn small_fields() -> SmallFields {
+    SmallFields {
+        records: (0..40u8)
+            .map(|i| {
+                (
+                    i,
+                    u16::from(i) * 300,
+                    u32::from(i) << 20,
+                    u64::from(i),
+                    i % 2 == 0,
+                    'é',
+                )
+            })
+            .collect(),
+        blob: vec![7; 1000],
+        names: (0..40).map(|i| (-i, i.to_string())).collect(),
+    }
+}
+
+#[test]
+fn test_buffered_writer_output() {
+    use bincode::enc::write::BufferedWriter;
+
+    fn check<const N: usize>(expected: &[u8], value: &impl bincode::Encode) {
+        let config = bincode::config::standard();
+        let mut output = CountingWriter::default();
+        let mut writer =
+            BufferedWriter::<_, N>::with_buffer_size(bincode::IoWriter::new(&mut output));
+        bincode::encode_into_writer(value, &mut writer, config).unwrap();
+        assert_eq!(writer.bytes_written(), expected.len());
+        assert_eq!(writer.finish().unwrap().bytes_written(), expected.len());
+        assert_eq!(output.output, expected, "buffer size {N}");
+    }
+
+    let config = bincode::config::standard();
+    let value = small_fields();
+    let mut unbuffered = CountingWriter::default();
+    bincode::encode_into_std_write(&value, &mut unbuffered, config).unwrap();
+
+    check::<1>(&unbuffered.output, &value);
+    check::<7>(&unbuffered.output, &value);
+    check::<64>(&unbuffered.output, &value);
+    check::<256>(&unbuffered.output, &value);
+    check::<4096>(&unbuffered.output, &value);
+
+    // most writes only go into the buffer
+    let mut buffered = CountingWriter::default();
+    let mut writer = BufferedWriter::new(bincode::IoWriter::new(&mut buffered));
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    bincode::enc::write::Writer::flush(&mut writer).unwrap();
+    assert_eq!(writer.buffered(), &[]);
+    drop(writer);
+    assert_eq!(buffered.output, unbuffered.output);
+    assert_eq!(buffered.flushes, 1);
+    assert!(unbuffered.calls > 300, "{}", unbuffered.calls);
+    assert!(buffered.calls < 10, "{}", buffered.calls);
+
+    // dropping the writer writes the buffered bytes too
+    let mut output = Vec::new();
+    let mut writer = BufferedWriter::new(bincode::IoWriter::new(&mut output));
+    bincode::encode_into_writer((1u8, "small"), &mut writer, config).unwrap();
+    assert_eq!(writer.buffered().len(), 7);
+    drop(writer);
+    assert_eq!(
+        output,
+        bincode::encode_to_vec((1u8, "small"), config).unwrap()
+    );
+}
+
+#[test]
+fn test_buffered_writer_error() {
+    use bincode::enc::write::BufferedWriter;
+
+    /// Accepts `limit` bytes and fails after that
+    struct Failing {
+        output: Vec<u8>,
+        limit: usize,
+    }
+    impl std::io::Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.limit - self.output.len());
+            if n == 0 {
+                return Err(std::io::Error::other("full"));
+            }
+            self.output.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let value = small_fields();
+    let expected = bincode::encode_to_vec(&value, config).unwrap();
+
+    for limit in [0, 1, 100, 255, 256, 257, 700, 1500, expected.len() - 1] {
+        let mut failing = Failing {
+            output: Vec::new(),
+            limit,
+        };
+        let mut writer = BufferedWriter::new(bincode::IoWriter::new(&mut failing));
+        let err = bincode::encode_into_writer(&value, &mut writer, config)
+            .and_then(|()| bincode::enc::write::Writer::flush(&mut writer))
+            .unwrap_err();
+        // the index is where the bytes that failed start, counted from the start of the output
+        let bincode::error::EncodeError::Io { index, .. } = err else {
+            panic!("{err:?}");
+        };
+        assert!(index <= limit, "limit {limit}, index {index}");
+        // a failing write is at most the buffer or the 1000 byte blob
+        assert!(limit - index <= 1000, "limit {limit}, index {index}");
+        drop(writer);
+        // the bytes are written in order and never twice
+        assert_eq!(failing.output, expected[..limit], "limit {limit}");
+    }
+
+    // a failing write of the buffered bytes is reported by `finish`
+    let mut failing = Failing {
+        output: Vec::new(),
+        limit: 3,
+    };
+    let mut writer = BufferedWriter::new(bincode::IoWriter::new(&mut failing));
+    bincode::encode_into_writer(5u32, &mut writer, config).unwrap();
+    bincode::encode_into_writer(u32::MAX, &mut writer, config).unwrap();
+    let Err(err) = writer.finish() else {
+        panic!("finish should fail");
+    };
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::Io { ref inner, index: 0 } if inner.kind() == std::io::ErrorKind::Other
+    ));
+    assert_eq!(failing.output, [5, 0xFC, 0xFF]);
+}
+
+#[test]
+fn test_std_write_flush() {
+    let config = bincode::config::standard();
+
+    let mut output = CountingWriter::default();
+    let len = bincode::encode_into_std_write(Foo { a: 1, b: 2 }, &mut output, config).unwrap();
+    assert_eq!(output.flushes, 0);
+    let len = len
+        + bincode::encode_into_std_write_flushed(Foo { a: 3, b: 4 }, &mut output, config).unwrap();
+    assert_eq!(output.flushes, 1);
+    assert_eq!(output.output, [1, 2, 3, 4]);
+    assert_eq!(len, 4);
+
+    // through the `Writer` trait
+    let mut writer = bincode::BufferedIoWriter::new(&mut output);
+    bincode::enc::write::Writer::write(&mut writer, &[5]).unwrap();
+    bincode::enc::write::Writer::flush(&mut &mut writer).unwrap();
+    drop(writer);
+    assert_eq!(output.flushes, 2);
+    assert_eq!(output.output, [1, 2, 3, 4, 5]);
+
+    // a `BufWriter` only passes the bytes on when it is flushed
+    let mut buffered = std::io::BufWriter::new(CountingWriter::default());
+    bincode::encode_into_std_write(Foo { a: 1, b: 2 }, &mut buffered, config).unwrap();
+    assert!(buffered.get_ref().output.is_empty());
+    bincode::encode_into_std_write_flushed(Foo { a: 3, b: 4 }, &mut buffered, config).unwrap();
+    assert_eq!(buffered.get_ref().output, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_std_write_flush_error() {
+    struct FailingFlush(Vec<u8>);
+    impl std::io::Write for FailingFlush {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::Error::other("flush failed"))
+        }
+    }
+
+    let mut output = FailingFlush(Vec::new());
+    let err = bincode::encode_into_std_write_flushed(
+        (String::from("Hello"), 5u8),
+        &mut output,
+        bincode::config::standard(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        bincode::error::EncodeError::Io { ref inner, index: 7 } if inner.to_string() == "flush failed"
+    ));
+    assert_eq!(output.0.len(), 7);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)] // miri does not like `tempfile`
 fn test_std_file() {
@@ -146,6 +735,17 @@ fn test_std_commons() {
     assert_eq!(len, 21);
 }
 
+#[test]
+fn test_ip_addr_unexpected_variant() {
+    let err = bincode::decode_from_slice::<IpAddr, _>(&[2], bincode::config::standard())
+        .unwrap_err()
+        .into_inner();
+    assert_eq!(
+        err.to_string(),
+        "unexpected variant of core::net::ip_addr::IpAddr: expected one of V4(0), V6(1), found 2"
+    );
+}
+
 #[test]
 fn test_system_time_out_of_range() {
     let input = [0xfd, 0x90, 0x0c, 0xfd, 0xfd, 0x90, 0x0c, 0xfd, 0x90, 0x90];
@@ -165,6 +765,121 @@ fn test_system_time_out_of_range() {
     }
 }
 
+#[test]
+fn test_system_time_wire_format() {
+    use std::time::{Duration, SystemTime};
+
+    let config = bincode::config::legacy();
+    // 1_700_000_000 seconds and 123_456_700 nanoseconds, which every platform can represent
+    let bytes = [
+        0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00, 0xbc, 0xcc, 0x5b, 0x07,
+    ];
+    let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_700);
+    assert_eq!(bincode::encode_to_vec(time, config).unwrap(), bytes);
+    let (decoded, _): (SystemTime, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, time);
+    let (decoded, _): (SystemTime, usize) =
+        bincode::decode_from_slice(&bytes, config.with_exact_system_time()).unwrap();
+    assert_eq!(decoded, time);
+
+    // 1_700_000_000 seconds and 123_456_789 nanoseconds
+    let bytes = [
+        0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00, 0x15, 0xcd, 0x5b, 0x07,
+    ];
+    let exact = Duration::new(1_700_000_000, 123_456_789);
+    let (truncated, _): (SystemTime, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    let exact_result: Result<(SystemTime, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_exact_system_time());
+    if cfg!(windows) {
+        assert_eq!(
+            truncated,
+            SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_700)
+        );
+        assert!(matches!(
+            exact_result.unwrap_err().into_inner(),
+            DecodeError::InvalidSystemTime { duration } if duration == exact
+        ));
+    } else {
+        assert_eq!(truncated, SystemTime::UNIX_EPOCH + exact);
+        assert_eq!(exact_result.unwrap().0, truncated);
+    }
+}
+
+#[test]
+fn test_system_time_max() {
+    use std::time::{Duration, SystemTime};
+
+    // The latest time that can be represented, and how much later the next second is
+    #[cfg(windows)]
+    let (max, exact_nanos) = (Duration::new(910_692_730_085, 477_580_799), 477_580_700);
+    #[cfg(not(windows))]
+    let (max, exact_nanos) = (Duration::new(i64::MAX as u64, 999_999_999), 999_999_999);
+
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(max, config).unwrap();
+    let (decoded, _): (SystemTime, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded.duration_since(SystemTime::UNIX_EPOCH).unwrap(),
+        Duration::new(max.as_secs(), exact_nanos)
+    );
+    let (reencoded, _): (Duration, usize) =
+        bincode::decode_from_slice(&bincode::encode_to_vec(decoded, config).unwrap(), config)
+            .unwrap();
+    assert_eq!(reencoded, Duration::new(max.as_secs(), exact_nanos));
+
+    let exact = bincode::encode_to_vec(Duration::new(max.as_secs(), exact_nanos), config).unwrap();
+    let (decoded, _): (SystemTime, usize) =
+        bincode::decode_from_slice(&exact, config.with_exact_system_time()).unwrap();
+    assert_eq!(
+        decoded.duration_since(SystemTime::UNIX_EPOCH).unwrap(),
+        Duration::new(max.as_secs(), exact_nanos)
+    );
+
+    let next = Duration::new(max.as_secs() + 1, 0);
+    let encoded = bincode::encode_to_vec(next, config).unwrap();
+    for result in [
+        bincode::decode_from_slice::<SystemTime, _>(&encoded, config),
+        bincode::decode_from_slice::<SystemTime, _>(&encoded, config.with_exact_system_time()),
+    ] {
+        assert!(matches!(
+            result.unwrap_err().into_inner(),
+            DecodeError::InvalidSystemTime { duration } if duration == next
+        ));
+    }
+}
+
+#[test]
+fn test_borrow_cstr() {
+    let config = bincode::config::standard();
+    let cstring = CString::new("Hello world").unwrap();
+
+    // Encoded with the terminator, the bytes can be borrowed, or decoded as a byte string
+    let encoded = bincode::encode_to_vec(cstring.as_bytes_with_nul(), config).unwrap();
+    let (decoded, len): (&CStr, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, cstring.as_c_str());
+    assert_eq!(decoded.to_bytes().as_ptr(), encoded[1..].as_ptr());
+    assert_eq!(len, 13);
+
+    // The encoding of `CString` leaves out the terminator, so it can not be borrowed
+    let encoded = bincode::encode_to_vec(&cstring, config).unwrap();
+    let result: Result<(&CStr, usize), _> = bincode::borrow_decode_from_slice(&encoded, config);
+    match result.map_err(DecodeError::into_inner) {
+        Err(DecodeError::CStringNulError { position }) => assert_eq!(position, 11),
+        other => panic!("Expected DecodeError::CStringNulError, got {other:?}"),
+    }
+    let (decoded, _): (CString, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, cstring);
+
+    // A 0 byte before the terminator
+    let encoded = bincode::encode_to_vec(&b"Hello\0world\0"[..], config).unwrap();
+    let result: Result<(&CStr, usize), _> = bincode::borrow_decode_from_slice(&encoded, config);
+    match result.map_err(DecodeError::into_inner) {
+        Err(DecodeError::CStringNulError { position }) => assert_eq!(position, 5),
+        other => panic!("Expected DecodeError::CStringNulError, got {other:?}"),
+    }
+}
+
 /// Simple example of user-defined hasher to test encoding/decoding HashMap and HashSet with custom hash algorithms.
 #[derive(Copy, Clone, Default)]
 pub struct ExampleCustomHasher {
@@ -203,3 +918,219 @@ fn test_decode_borrow_str_in_array() {
     .unwrap();
     assert_eq!(strs, ["abc", "def", "ghi"]);
 }
+
+/// A hasher that must be constructed with explicit keys, so it has no `Default` implementation
+#[derive(Clone)]
+struct KeyedState {
+    keys: (u64, u64),
+}
+
+struct KeyedHasher {
+    state: u64,
+    key: u64,
+}
+
+impl std::hash::Hasher for KeyedHasher {
+    fn finish(&self) -> u64 {
+        self.state ^ self.key
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.state = (self.state ^ u64::from(*b)).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+impl std::hash::BuildHasher for KeyedState {
+    type Hasher = KeyedHasher;
+
+    fn build_hasher(&self) -> KeyedHasher {
+        KeyedHasher {
+            state: self.keys.0,
+            key: self.keys.1,
+        }
+    }
+}
+
+/// Holds maps with a keyed hasher, which the `Decode` implementation of `HashMap` can not create
+struct Keyed<'a> {
+    map: std::collections::HashMap<String, u32, KeyedState>,
+    set: std::collections::HashSet<u16, KeyedState>,
+    borrowed: std::collections::HashMap<&'a str, &'a str, KeyedState>,
+}
+
+impl<'de, Context> bincode::BorrowDecode<'de, Context> for Keyed<'de> {
+    fn borrow_decode<D: bincode::de::BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let hasher = KeyedState {
+            keys: (0x0123_4567, 0x89ab_cdef),
+        };
+        Ok(Self {
+            map: bincode::decode_hash_map_with_hasher(decoder, hasher.clone())?,
+            set: bincode::decode_hash_set_with_hasher(decoder, hasher.clone())?,
+            borrowed: bincode::borrow_decode_hash_map_with_hasher(decoder, hasher)?,
+        })
+    }
+}
+
+#[test]
+fn test_decode_with_hasher() {
+    let config = bincode::config::standard();
+    let map: std::collections::HashMap<String, u32> =
+        [("a".to_string(), 1), ("b".to_string(), 2)].into();
+    let set: std::collections::HashSet<u16> = [3, 4, 5].into();
+    let borrowed: std::collections::HashMap<&str, &str> = [("key", "value")].into();
+    let encoded = bincode::encode_to_vec((&map, &set, &borrowed), config).unwrap();
+
+    let (keyed, len): (Keyed<'_>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(keyed.map.hasher().keys, (0x0123_4567, 0x89ab_cdef));
+    assert_eq!(keyed.map.len(), 2);
+    assert_eq!(keyed.map["a"], 1);
+    assert_eq!(keyed.map["b"], 2);
+    assert_eq!(keyed.set.len(), 3);
+    assert!([3, 4, 5].iter().all(|k| keyed.set.contains(k)));
+    assert_eq!(keyed.borrowed["key"], "value");
+
+    // the map can be encoded again like any other map
+    assert_eq!(
+        bincode::encode_to_vec((&keyed.map, &keyed.set, &keyed.borrowed), config)
+            .unwrap()
+            .len(),
+        encoded.len()
+    );
+
+    // a set with the hasher, decoded on its own
+    let encoded = bincode::encode_to_vec(&set, config).unwrap();
+    let mut decoder =
+        bincode::de::DecoderImpl::new(bincode::de::read::SliceReader::new(&encoded), config, ());
+    let decoded = bincode::borrow_decode_hash_set_with_hasher::<_, u16, _>(
+        &mut decoder,
+        KeyedState { keys: (1, 2) },
+    )
+    .unwrap();
+    assert_eq!(decoded.len(), 3);
+}
+
+/// Maps that borrow their keys and values from the input
+#[derive(bincode::Encode, bincode::BorrowDecode)]
+struct BorrowedMaps<'de, S: std::hash::BuildHasher + Default> {
+    files: std::collections::HashMap<&'de str, &'de [u8]>,
+    counts: std::collections::HashMap<&'de str, u64, S>,
+    index: std::collections::BTreeMap<&'de str, std::collections::HashSet<&'de str>>,
+}
+
+#[test]
+fn test_borrowed_map_keys() {
+    let config = bincode::config::standard();
+    let maps = BorrowedMaps::<std::hash::RandomState> {
+        files: [("a.txt", &b"abc"[..]), ("empty", &[][..])].into(),
+        counts: [("a", 1), ("b", u64::MAX)].into(),
+        index: [("x", ["y", "z"].into()), ("w", Default::default())].into(),
+    };
+    let encoded = bincode::encode_to_vec(&maps, config).unwrap();
+
+    let (decoded, len): (BorrowedMaps<std::hash::RandomState>, usize) =
+        bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(decoded.files, maps.files);
+    assert_eq!(decoded.counts, maps.counts);
+    assert_eq!(decoded.index, maps.index);
+
+    // the keys and values point into the input instead of being copied
+    let input = encoded.as_ptr_range();
+    for (key, value) in &decoded.files {
+        assert!(input.contains(&key.as_ptr()));
+        assert!(value.is_empty() || input.contains(&value.as_ptr()));
+    }
+    assert!(decoded.counts.keys().all(|k| input.contains(&k.as_ptr())));
+
+    // a key that is not valid UTF-8 fails like any other borrowed string
+    let map: std::collections::HashMap<&[u8], u8> = [(&b"\xFF"[..], 1)].into();
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    let err = bincode::borrow_decode_from_slice::<std::collections::HashMap<&str, u8>, _>(
+        &encoded, config,
+    )
+    .unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::Utf8 { .. }));
+}
+
+#[test]
+fn test_lossless_paths() {
+    let utf8 = bincode::config::standard();
+    let lossless = bincode::config::standard().with_lossless_paths();
+    let path = PathBuf::from("/home/user/file.txt");
+
+    // paths that are valid UTF-8 are a string, after the tag
+    let encoded = bincode::encode_to_vec(&path, utf8).unwrap();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec("/home/user/file.txt", utf8).unwrap()
+    );
+    let encoded = bincode::encode_to_vec(&path, lossless).unwrap();
+    let mut expected = vec![0];
+    expected.extend(bincode::encode_to_vec("/home/user/file.txt", utf8).unwrap());
+    assert_eq!(encoded, expected);
+
+    let (decoded, len): (PathBuf, usize) = bincode::decode_from_slice(&encoded, lossless).unwrap();
+    assert_eq!(decoded, path);
+    assert_eq!(len, encoded.len());
+    let (decoded, _): (&Path, usize) =
+        bincode::borrow_decode_from_slice(&encoded, lossless).unwrap();
+    assert_eq!(decoded, path);
+
+    // an unknown tag
+    let err = bincode::decode_from_slice::<PathBuf, _>(&[3, 0], lossless).unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::UnexpectedVariant { found: 3, .. }
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_lossless_paths_invalid_utf8() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let utf8 = bincode::config::standard();
+    let lossless = bincode::config::standard().with_lossless_paths();
+    let bytes: &[u8] = b"/home/\xff\xfeuser";
+    let path = Path::new(std::ffi::OsStr::from_bytes(bytes));
+
+    // the default config can only encode UTF-8
+    assert!(matches!(
+        bincode::encode_to_vec(path, utf8).unwrap_err(),
+        bincode::error::EncodeError::InvalidPathCharacters
+    ));
+
+    let encoded = bincode::encode_to_vec(path, lossless).unwrap();
+    let mut expected = vec![1, bytes.len() as u8];
+    expected.extend_from_slice(bytes);
+    assert_eq!(encoded, expected);
+
+    let (decoded, len): (PathBuf, usize) = bincode::decode_from_slice(&encoded, lossless).unwrap();
+    assert_eq!(decoded, path);
+    assert_eq!(decoded.as_os_str().as_bytes(), bytes);
+    assert_eq!(len, encoded.len());
+    let (decoded, _): (&Path, usize) =
+        bincode::borrow_decode_from_slice(&encoded, lossless).unwrap();
+    assert_eq!(decoded, path);
+
+    // a path that was encoded on Windows, with an unpaired surrogate
+    let units: Vec<u16> = vec![b'C' as u16, b':' as u16, 0xd800, b'x' as u16];
+    let mut encoded = vec![2];
+    encoded.extend(bincode::encode_to_vec(&units, lossless).unwrap());
+    let err = bincode::decode_from_slice_with_offset::<PathBuf, _>(&encoded, lossless).unwrap_err();
+    assert_eq!(err.offset(), Some(encoded.len()));
+    match err.into_inner() {
+        DecodeError::ForeignPath(foreign) => {
+            assert_eq!(*foreign, bincode::error::ForeignPath::Windows(units));
+        }
+        err => panic!("expected ForeignPath, got {err:?}"),
+    }
+    let err = bincode::borrow_decode_from_slice::<&Path, _>(&encoded, lossless).unwrap_err();
+    assert!(matches!(err.into_inner(), DecodeError::ForeignPath(_)));
+}
@@ -0,0 +1,188 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config;
+use bincode::error::DecodeError;
+use bincode::layout::EncodedLayout;
+
+#[derive(
+    bincode::Encode, bincode::Decode, bincode::SkipDecode, bincode::EncodedLayout, PartialEq, Debug,
+)]
+#[bincode(string_tags)]
+enum Command {
+    Ping,
+    Move { x: u8, y: u8 },
+    Say(String),
+    r#Loop,
+    AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesA,
+    AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesB(u8),
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(string_tags)]
+enum Borrowed<'a> {
+    Name(&'a str),
+    Bytes(&'a [u8]),
+}
+
+fn commands() -> Vec<Command> {
+    vec![
+        Command::Ping,
+        Command::Move { x: 1, y: 2 },
+        Command::Say(String::from("hello")),
+        Command::Loop,
+        Command::AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesA,
+        Command::AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesB(3),
+    ]
+}
+
+#[test]
+fn test_round_trip() {
+    fn check(config: impl config::Config) {
+        for command in commands() {
+            let bytes = bincode::encode_to_vec(&command, config).unwrap();
+            let (decoded, len): (Command, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+            assert_eq!(decoded, command);
+            assert_eq!(len, bytes.len());
+
+            // a reader that can not peek, so the name is compared in parts
+            let decoded: Command =
+                bincode::decode_from_iter(bytes.iter().copied(), config).unwrap();
+            assert_eq!(decoded, command);
+        }
+    }
+    check(config::standard());
+    check(config::legacy());
+    check(
+        config::standard()
+            .with_big_endian()
+            .with_fixed_int_encoding(),
+    );
+}
+
+#[test]
+fn test_byte_format() {
+    let bytes = bincode::encode_to_vec(Command::Ping, config::standard()).unwrap();
+    assert_eq!(bytes, [4, b'P', b'i', b'n', b'g']);
+
+    let bytes = bincode::encode_to_vec(Command::Move { x: 1, y: 2 }, config::standard()).unwrap();
+    assert_eq!(bytes, [4, b'M', b'o', b'v', b'e', 1, 2]);
+
+    // the `r#` of a raw identifier is not part of the name
+    let bytes = bincode::encode_to_vec(Command::Loop, config::standard()).unwrap();
+    assert_eq!(bytes, [4, b'L', b'o', b'o', b'p']);
+
+    let bytes = bincode::encode_to_vec(Command::Say(String::from("hi")), config::legacy()).unwrap();
+    assert_eq!(
+        bytes,
+        [3, 0, 0, 0, 0, 0, 0, 0, b'S', b'a', b'y', 2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i']
+    );
+}
+
+#[test]
+fn test_unknown_name() {
+    let config = config::standard();
+    for name in [
+        "ping",
+        "Pong",
+        "Pin",
+        "",
+        "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesC",
+        "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytes",
+    ] {
+        let bytes = bincode::encode_to_vec((name, 7u8), config).unwrap();
+        let expected = bincode::error::UnexpectedVariantName {
+            type_name: "Command",
+            allowed: &[
+                "Ping",
+                "Move",
+                "Say",
+                "Loop",
+                "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesA",
+                "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesB",
+            ],
+            found: String::from(name),
+        };
+
+        let err = bincode::decode_from_slice::<Command, _>(&bytes, config).unwrap_err();
+        assert!(matches!(err.inner(), DecodeError::UnexpectedVariantName(e) if **e == expected));
+
+        let err =
+            bincode::decode_from_iter::<Command, _, _>(bytes.iter().copied(), config).unwrap_err();
+        assert!(matches!(err.inner(), DecodeError::UnexpectedVariantName(e) if **e == expected));
+
+        let mut decoder =
+            bincode::de::DecoderImpl::new(bincode::de::read::SliceReader::new(&bytes), config, ());
+        let err = bincode::skip_value::<Command, _>(&mut decoder).unwrap_err();
+        assert!(matches!(err.inner(), DecodeError::UnexpectedVariantName(e) if **e == expected));
+    }
+
+    let bytes = bincode::encode_to_vec("Teleport", config).unwrap();
+    let err = bincode::decode_from_slice::<Command, _>(&bytes, config).unwrap_err();
+    assert_eq!(
+        err.inner().to_string(),
+        r#"unexpected variant of Command: expected one of "Ping", "Move", "Say", "Loop", "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesA", "AVariantWithAVeryLongNameThatDoesNotFitInTheBufferOfSixtyFourBytesB", found "Teleport""#
+    );
+
+    // the name is not UTF-8
+    let bytes = [2, 0xFF, b'a'];
+    let err = bincode::decode_from_slice::<Command, _>(&bytes, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::UnexpectedVariantName(e) if e.found == "\u{FFFD}a"));
+
+    let err = bincode::decode_from_slice::<Command, _>(&[4, b'P', b'i'], config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::UnexpectedEnd { .. }));
+}
+
+#[test]
+fn test_borrow_decode() {
+    let config = config::standard();
+    let value = Borrowed::Name("borrowed");
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(&bytes[..5], [4, b'N', b'a', b'm', b'e']);
+    let (decoded, _): (Borrowed, _) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, value);
+
+    let value = Borrowed::Bytes(&[1, 2, 3]);
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let (decoded, _): (Borrowed, _) = bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, value);
+
+    let bytes = bincode::encode_to_vec("Str", config).unwrap();
+    let err = bincode::borrow_decode_from_slice::<Borrowed, _>(&bytes, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::UnexpectedVariantName(e) if e.found == "Str"));
+}
+
+#[test]
+fn test_skip() {
+    let config = config::standard();
+    for command in commands() {
+        let mut bytes = bincode::encode_to_vec(&command, config).unwrap();
+        bytes.push(42);
+        let mut decoder =
+            bincode::de::DecoderImpl::new(bincode::de::read::SliceReader::new(&bytes), config, ());
+        bincode::skip_value::<Command, _>(&mut decoder).unwrap();
+        let trailer: u8 = bincode::Decode::decode(&mut decoder).unwrap();
+        assert_eq!(trailer, 42);
+    }
+}
+
+#[test]
+fn test_layout() {
+    #[derive(bincode::Encode, bincode::EncodedLayout)]
+    #[bincode(string_tags)]
+    #[allow(dead_code)]
+    enum Small {
+        A,
+        B(u8),
+    }
+
+    assert_eq!(
+        Small::layout().to_json(config::standard()),
+        r#"{"endian":"little","layout":{"kind":"string_tagged_enum","name":"Small","len":{"kind":"int","signed":false,"bits":64,"encoding":"varint"},"variants":[{"name":"A","fields":[]},{"name":"B","fields":[{"name":"0","layout":{"kind":"int","signed":false,"bits":8,"encoding":"fixed"}}]}]}}"#
+    );
+    assert!(matches!(
+        Command::layout(),
+        bincode::layout::Layout::StringTaggedEnum { name: "Command", ref variants } if variants.len() == 6
+    ));
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::{
+        read::{SliceReader, TakeReader},
+        BorrowDecode, DecoderImpl,
+    },
+    error::DecodeError,
+};
+use std::io::Cursor;
+
+/// Two messages, each prefixed with their length as a `u32`
+fn stream() -> Vec<u8> {
+    let config = bincode::config::standard();
+    let mut stream = Vec::new();
+    for message in [
+        bincode::encode_to_vec((5u64, String::from("first")), config).unwrap(),
+        bincode::encode_to_vec((6u64, String::from("second")), config).unwrap(),
+    ] {
+        stream.extend_from_slice(&(message.len() as u32).to_le_bytes());
+        stream.extend_from_slice(&message);
+    }
+    stream
+}
+
+fn read_len(cursor: &mut Cursor<&Vec<u8>>) -> usize {
+    let mut len = [0u8; 4];
+    std::io::Read::read_exact(cursor, &mut len).unwrap();
+    u32::from_le_bytes(len) as usize
+}
+
+#[test]
+fn test_take_exact() {
+    let config = bincode::config::standard();
+    let stream = stream();
+    let mut cursor = Cursor::new(&stream);
+
+    for expected in [(5u64, "first"), (6, "second")] {
+        let len = read_len(&mut cursor);
+        let ((number, text), bytes_read): ((u64, String), usize) =
+            bincode::decode_from_std_read_take(&mut cursor, len, config).unwrap();
+        assert_eq!((number, text.as_str()), expected);
+        assert_eq!(bytes_read, len);
+    }
+    assert_eq!(cursor.position() as usize, stream.len());
+}
+
+#[test]
+fn test_take_under_consumption() {
+    let config = bincode::config::standard();
+    let stream = stream();
+    let mut cursor = Cursor::new(&stream);
+
+    // only decode the number of the first message
+    let len = read_len(&mut cursor);
+    let (number, bytes_read): (u64, usize) =
+        bincode::decode_from_std_read_take(&mut cursor, len, config).unwrap();
+    assert_eq!(number, 5);
+    assert_eq!(bytes_read, 1);
+    assert!(bytes_read < len);
+
+    let mut reader = TakeReader::new(SliceReader::new(&stream[4..]), len);
+    let _: u64 = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(reader.remaining(), len - 1);
+}
+
+#[test]
+fn test_take_over_read() {
+    let config = bincode::config::standard();
+    let stream = stream();
+    let mut cursor = Cursor::new(&stream);
+
+    // decode the first message as something bigger than it is
+    let len = read_len(&mut cursor);
+    let err =
+        bincode::decode_from_std_read_take::<(u64, String, u64), _, _>(&mut cursor, len, config)
+            .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::TakeLimitExceeded {
+            limit,
+            additional: 1,
+        } if *limit == len
+    ));
+    // nothing past the boundary was read, so the second message is intact
+    assert_eq!(cursor.position() as usize, 4 + len);
+    let len = read_len(&mut cursor);
+    let ((number, text), _): ((u64, String), usize) =
+        bincode::decode_from_std_read_take(&mut cursor, len, config).unwrap();
+    assert_eq!((number, text.as_str()), (6, "second"));
+
+    // a length prefix that claims more bytes than the limit
+    let data = bincode::encode_to_vec(vec![0u8; 100], config).unwrap();
+    let mut reader = TakeReader::new(SliceReader::new(&data), 10);
+    let err = bincode::decode_from_reader::<Vec<u8>, _, _>(&mut reader, config).unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::TakeLimitExceeded {
+            limit: 10,
+            additional: 91,
+        }
+    ));
+    assert_eq!(reader.remaining(), 9);
+    // the bytes past the length prefix were not read
+    let rest: [u8; 100] = bincode::decode_from_reader(reader.into_inner(), config).unwrap();
+    assert_eq!(rest, [0u8; 100]);
+}
+
+#[test]
+fn test_take_borrowed() {
+    let config = bincode::config::standard();
+    let data = bincode::encode_to_vec(("hello", "world"), config).unwrap();
+
+    let mut decoder = DecoderImpl::new(TakeReader::new(SliceReader::new(&data), 6), config, ());
+    let first: &str = BorrowDecode::borrow_decode(&mut decoder).unwrap();
+    assert_eq!(first, "hello");
+    let err = <&str>::borrow_decode(&mut decoder).unwrap_err();
+    assert!(matches!(
+        err,
+        DecodeError::TakeLimitExceeded {
+            limit: 6,
+            additional: 1,
+        }
+    ));
+}
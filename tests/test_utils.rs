@@ -0,0 +1,172 @@
+#![cfg(all(feature = "test-utils", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    test_utils::{
+        assert_roundtrip, assert_roundtrip_by, assert_roundtrip_with_config,
+        assert_roundtrip_with_config_by,
+    },
+    Decode, Encode,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+enum Message {
+    Empty,
+    Text(Vec<String>),
+    Lookup {
+        id: u64,
+        names: BTreeMap<String, u32>,
+    },
+}
+
+/// Encodes a `u32` but decodes a `u16`
+#[derive(PartialEq, Debug)]
+struct ShortRead(u16);
+
+impl Encode for ShortRead {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        u32::from(self.0).encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for ShortRead {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u16::decode(decoder).map(ShortRead)
+    }
+}
+
+/// Decodes to a value that is one higher than the encoded value
+#[derive(PartialEq, Debug)]
+struct OffByOne(u8);
+
+impl Encode for OffByOne {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for OffByOne {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|v| OffByOne(v.wrapping_add(1)))
+    }
+}
+
+/// Like `OffByOne`, but all values compare equal
+#[derive(Debug)]
+struct AlwaysEqual(u8);
+
+impl PartialEq for AlwaysEqual {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Encode for AlwaysEqual {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for AlwaysEqual {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|v| AlwaysEqual(v.wrapping_add(1)))
+    }
+}
+
+/// Decodes a missing byte as 0
+#[derive(PartialEq, Debug)]
+struct Lenient(u8);
+
+impl Encode for Lenient {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for Lenient {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Lenient(u8::decode(decoder).unwrap_or(0)))
+    }
+}
+
+/// Fails to encode
+#[derive(PartialEq, Debug)]
+struct Unencodable;
+
+impl Encode for Unencodable {
+    fn encode<E: Encoder>(&self, _: &mut E) -> Result<(), EncodeError> {
+        Err(EncodeError::Other("unencodable"))
+    }
+}
+
+impl<Context> Decode<Context> for Unencodable {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder).map(|_| Unencodable)
+    }
+}
+
+#[test]
+fn test_valid_values() {
+    let mut names = BTreeMap::new();
+    names.insert(String::from("a"), 1);
+    names.insert(String::from("b"), 2);
+    assert_roundtrip(&Message::Empty);
+    assert_roundtrip(&Message::Text(vec![
+        String::from("Hello"),
+        String::from("world"),
+    ]));
+    assert_roundtrip(&Message::Lookup { id: 300, names });
+
+    assert_roundtrip(&());
+    assert_roundtrip(&String::new());
+    assert_roundtrip(&(u128::MAX, i64::MIN, Some('ü')));
+    assert_roundtrip(&PathBuf::from("some/path"));
+    assert_roundtrip(&std::time::SystemTime::UNIX_EPOCH);
+    assert_roundtrip_by(&f32::NAN, |a, b| a.to_bits() == b.to_bits());
+
+    let config = bincode::config::legacy().with_limit::<100>();
+    assert_roundtrip_with_config(&vec![1u32, 2, 3], config);
+    assert_roundtrip_with_config_by(&f64::NAN, config, |a, b| a.is_nan() && b.is_nan());
+}
+
+#[test]
+#[should_panic(expected = "decoding read 2 bytes")]
+fn test_short_read() {
+    assert_roundtrip_with_config(&ShortRead(1), bincode::config::legacy());
+}
+
+#[test]
+#[should_panic(expected = "the decoded value is not equal to the original value")]
+fn test_value_mismatch() {
+    assert_roundtrip(&OffByOne(1));
+}
+
+#[test]
+#[should_panic(expected = "the decoded value encodes to different bytes")]
+fn test_bytes_mismatch() {
+    assert_roundtrip(&AlwaysEqual(1));
+}
+
+#[test]
+#[should_panic(expected = "decoding the first 0 of 1 bytes of Lenient(5)")]
+fn test_prefix_decoded() {
+    assert_roundtrip(&Lenient(5));
+}
+
+#[test]
+#[should_panic(expected = "failed to encode Unencodable")]
+fn test_unencodable() {
+    assert_roundtrip(&Unencodable);
+}
+
+#[test]
+#[should_panic(expected = "the decoded value is not equal to the original value")]
+fn test_nan_is_not_equal() {
+    assert_roundtrip(&f32::NAN);
+}
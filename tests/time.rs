@@ -0,0 +1,139 @@
+#![cfg(feature = "time")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+fn the_same_with_config<T, C>(value: &T, config: C)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+    C: bincode::config::Config,
+{
+    let mut buffer = [0u8; 64];
+    let len = bincode::encode_into_slice(value, &mut buffer, config).unwrap();
+    let (decoded, decoded_len): (T, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(value, &decoded);
+    assert_eq!(len, decoded_len);
+}
+
+fn the_same<T>(value: T)
+where
+    T: bincode::Encode + bincode::Decode<()> + PartialEq + core::fmt::Debug,
+{
+    the_same_with_config(&value, bincode::config::standard());
+    the_same_with_config(&value, bincode::config::standard().with_big_endian());
+    the_same_with_config(&value, bincode::config::legacy());
+    the_same_with_config(&value, bincode::config::legacy().with_big_endian());
+}
+
+fn date(year: i32, month: Month, day: u8) -> Date {
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+#[test]
+fn test_offset_date_time() {
+    the_same(OffsetDateTime::UNIX_EPOCH);
+    the_same(OffsetDateTime::from_unix_timestamp(-1).unwrap());
+    the_same(
+        OffsetDateTime::from_unix_timestamp(1_700_000_000)
+            .unwrap()
+            .replace_nanosecond(123_456_789)
+            .unwrap(),
+    );
+    the_same(PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT).assume_offset(UtcOffset::UTC));
+    the_same(PrimitiveDateTime::new(Date::MAX, Time::MAX).assume_offset(UtcOffset::UTC));
+    // the offset is preserved
+    let with_offset = PrimitiveDateTime::new(date(1900, Month::March, 4), Time::MIDNIGHT)
+        .assume_offset(UtcOffset::from_hms(-5, -30, 0).unwrap());
+    let encoded = bincode::encode_to_vec(with_offset, bincode::config::standard()).unwrap();
+    let (decoded, _): (OffsetDateTime, usize) =
+        bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+    assert_eq!(decoded.offset(), with_offset.offset());
+    assert_eq!(decoded.date(), with_offset.date());
+    assert_eq!(decoded.time(), with_offset.time());
+}
+
+#[test]
+fn test_offset_date_time_format() {
+    let date_time = OffsetDateTime::from_unix_timestamp(-2)
+        .unwrap()
+        .replace_nanosecond(5)
+        .unwrap()
+        .to_offset(UtcOffset::from_whole_seconds(3600).unwrap());
+    let encoded = bincode::encode_to_vec(date_time, bincode::config::legacy()).unwrap();
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&(-2i64).to_le_bytes());
+    expected.extend_from_slice(&5u32.to_le_bytes());
+    expected.extend_from_slice(&3600i32.to_le_bytes());
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_primitive_date_time() {
+    the_same(PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT));
+    the_same(PrimitiveDateTime::new(Date::MAX, Time::MAX));
+    the_same(PrimitiveDateTime::new(
+        date(1969, Month::December, 31),
+        Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(),
+    ));
+}
+
+#[test]
+fn test_duration() {
+    the_same(Duration::ZERO);
+    the_same(Duration::MIN);
+    the_same(Duration::MAX);
+    the_same(Duration::nanoseconds(-1));
+    the_same(Duration::new(-5, -250_000_000));
+    the_same(Duration::new(5, 250_000_000));
+}
+
+#[test]
+fn test_out_of_range() {
+    let config = bincode::config::legacy();
+
+    let encoded = bincode::encode_to_vec((i64::MAX, 0u32, 0i32), config).unwrap();
+    let err = bincode::decode_from_slice::<OffsetDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+    let err = bincode::decode_from_slice::<PrimitiveDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    // nanoseconds out of range
+    let encoded = bincode::encode_to_vec((0i64, 1_000_000_000u32, 0i32), config).unwrap();
+    let err = bincode::decode_from_slice::<OffsetDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    // offset out of range
+    let encoded = bincode::encode_to_vec((0i64, 0u32, i32::MAX), config).unwrap();
+    let err = bincode::decode_from_slice::<OffsetDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    // applying the offset pushes the date out of range
+    let max = PrimitiveDateTime::new(Date::MAX, Time::MAX).assume_utc();
+    let encoded =
+        bincode::encode_to_vec((max.unix_timestamp(), max.nanosecond(), 3600i32), config).unwrap();
+    let err = bincode::decode_from_slice::<OffsetDateTime, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDateTime { .. }));
+
+    for (secs, nanos) in [
+        (i64::MAX, 1_000_000_000i32),
+        (0, -1_000_000_000),
+        (1, -1),
+        (-1, 1),
+        (i64::MIN, -999_999_999),
+    ] {
+        let encoded = bincode::encode_to_vec((secs, nanos), config).unwrap();
+        let result = bincode::decode_from_slice::<Duration, _>(&encoded, config);
+        if secs == i64::MIN {
+            // this is the minimum representable duration
+            assert_eq!(result.unwrap().0, Duration::MIN);
+        } else {
+            assert!(matches!(
+                result.unwrap_err(),
+                DecodeError::InvalidDateTime { .. }
+            ));
+        }
+    }
+}
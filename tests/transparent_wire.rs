@@ -0,0 +1,182 @@
+#![cfg(all(feature = "derive", feature = "std"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::read::{Reader, SliceReader},
+    error::DecodeError,
+    TransparentWire,
+};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+#[derive(TransparentWire, Clone, Copy, PartialEq, Debug)]
+#[repr(transparent)]
+struct Meters(f32);
+
+#[derive(TransparentWire, Clone, Copy, PartialEq, Debug)]
+#[repr(transparent)]
+struct Ticks(u64);
+
+#[derive(TransparentWire, Clone, Copy, PartialEq, Debug)]
+#[repr(transparent)]
+struct Celsius {
+    value: f64,
+}
+
+/// A newtype around another newtype
+#[derive(TransparentWire, Clone, Copy, PartialEq, Debug)]
+#[repr(transparent)]
+struct Distance(Meters);
+
+#[derive(TransparentWire, Clone, Copy, PartialEq, Debug)]
+#[repr(transparent)]
+struct Offset(i16);
+
+/// A reader that counts the calls to `read`
+struct CountingReader<'a> {
+    reader: SliceReader<'a>,
+    reads: usize,
+}
+
+impl Reader for CountingReader<'_> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reads += 1;
+        self.reader.read(bytes)
+    }
+}
+
+/// Asserts that `values` are encoded exactly like `wire`, and round trip through every collection
+fn check<T, W, C>(values: &[T], wire: &[W], config: C)
+where
+    T: bincode::Encode
+        + bincode::Decode<()>
+        + for<'de> bincode::BorrowDecode<'de, ()>
+        + Copy
+        + PartialEq
+        + Debug
+        + 'static,
+    W: bincode::Encode,
+    C: Config,
+{
+    let encoded = bincode::encode_to_vec(values, config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(wire, config).unwrap());
+
+    let (decoded, len): (Vec<T>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!((decoded.as_slice(), len), (values, encoded.len()));
+    let (decoded, _): (Box<[T]>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(&*decoded, values);
+    let (decoded, _): (VecDeque<T>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert!(decoded.iter().eq(values));
+    let (decoded, _): (Vec<T>, _) = bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, values);
+    let mut reader = &encoded[..];
+    let decoded: Vec<T> = bincode::decode_from_std_read(&mut reader, config).unwrap();
+    assert_eq!(decoded, values);
+
+    let deque: VecDeque<T> = values.iter().copied().collect();
+    assert_eq!(bincode::encode_to_vec(&deque, config).unwrap(), encoded);
+
+    // arrays have no length
+    let array: [T; 3] = [values[0], values[1], values[2]];
+    let encoded = bincode::encode_to_vec(array, config).unwrap();
+    let len = bincode::encode_to_vec(3u64, config).unwrap().len();
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec(&wire[..3], config).unwrap()[len..]
+    );
+    let (decoded, _): ([T; 3], _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, array);
+    let (decoded, _): (Box<[T; 3]>, _) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(*decoded, array);
+}
+
+fn check_all<C: Config>(config: C) {
+    let floats = [1.5f32, -0.25, f32::MAX, f32::MIN_POSITIVE, 0.0];
+    check(&floats.map(Meters), &floats, config);
+    check(&floats.map(|f| Distance(Meters(f))), &floats, config);
+
+    let doubles = [2.5f64, f64::MIN, -0.0, 1e300];
+    check(&doubles.map(|value| Celsius { value }), &doubles, config);
+
+    let ints = [0u64, 1, 250, 251, u64::MAX];
+    check(&ints.map(Ticks), &ints, config);
+
+    let shorts = [0i16, -1, i16::MIN, i16::MAX];
+    check(&shorts.map(Offset), &shorts, config);
+}
+
+#[test]
+fn test_same_bytes_as_wire() {
+    check_all(config::standard());
+    check_all(config::standard().with_big_endian());
+    check_all(config::legacy());
+    check_all(config::legacy().with_big_endian());
+}
+
+#[test]
+fn test_single_values() {
+    let config = config::standard();
+    let encoded = bincode::encode_to_vec(Ticks(300), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(300u64, config).unwrap());
+    assert_eq!(
+        bincode::decode_from_slice::<Ticks, _>(&encoded, config).unwrap(),
+        (Ticks(300), 3)
+    );
+    assert_eq!(
+        bincode::decode_from_slice::<Option<Distance>, _>(&[1, 0, 0, 0xC0, 0x3F], config).unwrap(),
+        (Some(Distance(Meters(1.5))), 5)
+    );
+}
+
+#[test]
+fn test_fast_path() {
+    // a slice of newtypes is read with the same single call as a slice of the wire type
+    let config = config::legacy();
+    let floats: Vec<f32> = (0..1000).map(|i| i as f32 / 3.0).collect();
+    let encoded = bincode::encode_to_vec(&floats, config).unwrap();
+
+    let mut reader = CountingReader {
+        reader: SliceReader::new(&encoded),
+        reads: 0,
+    };
+    let decoded: Vec<Distance> = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert!(decoded.iter().map(|d| d.0 .0).eq(floats.iter().copied()));
+    assert!(reader.reads < 5, "{} reads", reader.reads);
+
+    let mut reader = CountingReader {
+        reader: SliceReader::new(&encoded[8..]),
+        reads: 0,
+    };
+    let decoded: [Meters; 1000] = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert!(decoded.iter().map(|m| m.0).eq(floats.iter().copied()));
+    assert!(reader.reads < 5, "{} reads", reader.reads);
+}
+
+#[test]
+fn test_limit() {
+    // the fast path claims the full size of every element against the limit
+    let config = config::legacy().with_limit::<100>();
+    let encoded = bincode::encode_to_vec(vec![0u64; 20], config::legacy()).unwrap();
+    let err = bincode::decode_from_slice::<Vec<Ticks>, _>(&encoded, config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+    let encoded = bincode::encode_to_vec(vec![0u64; 10], config::legacy()).unwrap();
+    assert_eq!(
+        bincode::decode_from_slice::<Vec<Ticks>, _>(&encoded, config)
+            .unwrap()
+            .0,
+        vec![Ticks(0); 10]
+    );
+}
+
+#[test]
+fn test_truncated() {
+    let config = config::legacy();
+    let encoded = bincode::encode_to_vec(vec![1.0f64; 4], config).unwrap();
+    let err = bincode::decode_from_slice::<Vec<Celsius>, _>(&encoded[..20], config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::UnexpectedEnd { .. }),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "std")]
+
+extern crate bincode_next as bincode;
+
+use bincode::{config::Config, de::read::SliceReader, error::DecodeError, Decode, Encode};
+use std::fmt::Debug;
+
+/// A reader that returns at most one byte per call
+struct Trickle<'a>(&'a [u8]);
+
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.0.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+fn additional(err: DecodeError) -> usize {
+    match err.into_inner() {
+        DecodeError::UnexpectedEnd { additional } => additional,
+        e => panic!("Expected UnexpectedEnd, got {e:?}"),
+    }
+}
+
+/// Decodes `value` from every prefix of its encoding, with every reader, and checks that the reported shortfall matches `expected(cut)`
+fn check_truncated<T, C>(value: T, config: C, expected: impl Fn(usize) -> usize)
+where
+    T: Encode + Decode<()> + PartialEq + Debug,
+    C: Config,
+{
+    let encoded = bincode::encode_to_vec(&value, config).unwrap();
+    for cut in 0..encoded.len() {
+        let data = &encoded[..cut];
+        let expected = expected(cut);
+
+        let err = bincode::decode_from_slice::<T, _>(data, config).unwrap_err();
+        assert_eq!(additional(err), expected, "slice, cut at {cut}");
+
+        let err =
+            bincode::decode_from_reader::<T, _, _>(SliceReader::new(data), config).unwrap_err();
+        assert_eq!(additional(err), expected, "SliceReader, cut at {cut}");
+
+        // without any bytes, the stream ended cleanly before the value
+        let err = bincode::decode_from_std_read::<T, _, _>(&mut &data[..], config).unwrap_err();
+        if cut == 0 {
+            assert!(matches!(err, DecodeError::CleanEof), "IoReader: {err:?}");
+        } else {
+            assert_eq!(additional(err), expected, "IoReader, cut at {cut}");
+        }
+
+        let err = bincode::decode_from_std_read::<T, _, _>(&mut Trickle(data), config).unwrap_err();
+        if cut == 0 {
+            assert!(matches!(err, DecodeError::CleanEof), "IoReader: {err:?}");
+        } else {
+            assert_eq!(
+                additional(err),
+                expected,
+                "IoReader (trickle), cut at {cut}"
+            );
+        }
+
+        let err = bincode::decode_from_reader::<T, _, _>(std::io::BufReader::new(data), config)
+            .unwrap_err();
+        assert_eq!(additional(err), expected, "BufReader, cut at {cut}");
+    }
+    let (decoded, len): (T, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(len, encoded.len());
+}
+
+#[test]
+fn test_fixed_int_shortfall() {
+    check_truncated(u64::MAX, bincode::config::legacy(), |cut| 8 - cut);
+    check_truncated(
+        (1u16, 2u32),
+        bincode::config::legacy().with_big_endian(),
+        |cut| if cut < 2 { 2 - cut } else { 6 - cut },
+    );
+}
+
+#[test]
+fn test_varint_shortfall() {
+    // the first byte is read on its own, then the rest of the varint
+    let varint = |len: usize| move |cut: usize| if cut == 0 { 1 } else { len - cut };
+    check_truncated(u16::MAX, bincode::config::standard(), varint(3));
+    check_truncated(u32::MAX, bincode::config::standard(), varint(5));
+    check_truncated(u64::MAX, bincode::config::standard(), varint(9));
+    check_truncated(u128::MAX, bincode::config::standard(), varint(17));
+    check_truncated(i64::MIN, bincode::config::standard(), varint(9));
+}
+
+#[test]
+fn test_string_shortfall() {
+    let value = String::from("hello world");
+    check_truncated(value.clone(), bincode::config::legacy(), |cut| {
+        if cut < 8 {
+            8 - cut
+        } else {
+            19 - cut
+        }
+    });
+    check_truncated(value, bincode::config::standard(), |cut| {
+        if cut == 0 {
+            1
+        } else {
+            12 - cut
+        }
+    });
+}
+
+#[test]
+fn test_large_shortfall() {
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(vec![7u8; 2 * 1024 * 1024], config).unwrap();
+    // 5 bytes of length prefix
+    assert_eq!(encoded.len(), 5 + 2 * 1024 * 1024);
+
+    for cut in [5, 6, 1024, encoded.len() - 1] {
+        let data = &encoded[..cut];
+        let expected = encoded.len() - cut;
+
+        let err = bincode::decode_from_slice::<Vec<u8>, _>(data, config).unwrap_err();
+        assert_eq!(additional(err), expected);
+
+        let err =
+            bincode::decode_from_std_read::<Vec<u8>, _, _>(&mut &data[..], config).unwrap_err();
+        assert_eq!(additional(err), expected);
+    }
+}
+
+#[test]
+fn test_io_error_is_not_unexpected_end() {
+    struct Failing;
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("failing"))
+        }
+    }
+
+    let err = bincode::decode_from_std_read::<u32, _, _>(&mut Failing, bincode::config::legacy())
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner(),
+        DecodeError::Io { additional: 4, .. }
+    ));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_clean_eof() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Message {
+        id: u32,
+        text: String,
+    }
+
+    let config = bincode::config::legacy();
+    let messages = [
+        Message {
+            id: 1,
+            text: String::from("first"),
+        },
+        Message {
+            id: 2,
+            text: String::from("second"),
+        },
+    ];
+    let mut stream = Vec::new();
+    for message in &messages {
+        bincode::encode_into_std_write(message, &mut stream, config).unwrap();
+    }
+
+    // two full messages, and then nothing
+    let mut reader = stream.as_slice();
+    for message in &messages {
+        let decoded: Message = bincode::decode_from_std_read(&mut reader, config).unwrap();
+        assert_eq!(&decoded, message);
+    }
+    let err = bincode::decode_from_std_read::<Message, _, _>(&mut reader, config).unwrap_err();
+    assert!(matches!(err, DecodeError::CleanEof));
+
+    // the second message is cut off, even in the middle of its first field
+    let first_len = stream.len() / 2;
+    for cut in [first_len + 2, first_len + 6, stream.len() - 1] {
+        let mut reader = Trickle(&stream[..cut]);
+        let _: Message = bincode::decode_from_std_read(&mut reader, config).unwrap();
+        let err = bincode::decode_from_std_read_with_offset::<Message, _, _>(&mut reader, config)
+            .unwrap_err();
+        assert_eq!(err.offset(), Some(cut - first_len), "cut at {cut}");
+        assert!(matches!(
+            err.into_inner(),
+            DecodeError::UnexpectedEnd { .. }
+        ));
+    }
+}
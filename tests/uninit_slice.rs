@@ -0,0 +1,121 @@
+#![cfg(feature = "alloc")]
+
+extern crate alloc;
+extern crate bincode_next as bincode;
+
+use alloc::{string::String, vec, vec::Vec};
+use bincode::{
+    config,
+    enc::{
+        write::{SeekWriter, UninitSliceWriter, Writer},
+        Encode, Encoder,
+    },
+    error::EncodeError,
+};
+use core::mem::MaybeUninit;
+
+fn value() -> (u32, String, Vec<u64>, Option<[u8; 3]>) {
+    (
+        300,
+        String::from("ring buffer"),
+        vec![1, u64::MAX, 1 << 40],
+        Some([7, 8, 9]),
+    )
+}
+
+fn uninit(len: usize) -> Vec<MaybeUninit<u8>> {
+    (0..len).map(|_| MaybeUninit::uninit()).collect()
+}
+
+#[test]
+fn test_exact_and_oversized() {
+    let config = config::standard();
+    let expected = bincode::encode_to_vec(value(), config).unwrap();
+
+    let mut exact = uninit(expected.len());
+    let written = bincode::encode_into_uninit_slice(value(), &mut exact, config).unwrap();
+    assert_eq!(written, expected);
+
+    let mut oversized = uninit(expected.len() * 4);
+    let written = bincode::encode_into_uninit_slice(value(), &mut oversized, config).unwrap();
+    assert_eq!(written, expected);
+
+    // the returned bytes can be changed like any other slice
+    written[0] = 0;
+    assert_eq!(written[1..], expected[1..]);
+
+    let mut empty = uninit(0);
+    assert_eq!(
+        bincode::encode_into_uninit_slice((), &mut empty, config).unwrap(),
+        []
+    );
+}
+
+#[test]
+fn test_undersized() {
+    let config = config::legacy();
+    let expected = bincode::encode_to_vec(value(), config).unwrap();
+
+    for len in [0, 1, expected.len() / 2, expected.len() - 1] {
+        let mut buffer = uninit(len);
+        let err = bincode::encode_into_uninit_slice(value(), &mut buffer, config).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                EncodeError::SliceTooSmall { capacity, required }
+                    if capacity == len && required == expected.len()
+            ),
+            "{len}: {err:?}"
+        );
+
+        // the same error as `encode_into_slice`
+        let mut zeroed = vec![0u8; len];
+        let slice_err = bincode::encode_into_slice(value(), &mut zeroed, config).unwrap_err();
+        assert_eq!(format!("{err:?}"), format!("{slice_err:?}"));
+    }
+}
+
+#[test]
+fn test_writer() {
+    let mut buffer = uninit(6);
+    let mut writer = UninitSliceWriter::new(&mut buffer);
+    writer.write(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(writer.bytes_written(), 4);
+    assert_eq!(writer.remaining(), 2);
+
+    // a write that does not fit writes nothing
+    assert!(matches!(
+        writer.write(&[5, 6, 7]),
+        Err(EncodeError::UnexpectedEnd)
+    ));
+    assert_eq!(writer.bytes_written(), 4);
+
+    // only the bytes that were written can be overwritten
+    writer.write_at(1, &[9, 9]).unwrap();
+    assert!(writer.write_at(3, &[9, 9]).is_err());
+    assert!(writer.write_at(usize::MAX, &[9]).is_err());
+
+    writer.write(&[5, 6]).unwrap();
+    assert_eq!(writer.remaining(), 0);
+    assert_eq!(writer.into_written(), [1, 9, 9, 4, 5, 6]);
+}
+
+/// A string with a length prefix, which is written after the string
+struct Prefixed(&'static str);
+
+impl Encode for Prefixed {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.with_length_prefix(|encoder| self.0.encode(encoder))
+    }
+}
+
+#[test]
+fn test_length_prefix() {
+    let config = config::standard();
+    let value = (Prefixed("first"), 3u8, Prefixed("second"));
+    let expected = bincode::encode_to_vec(&value, config).unwrap();
+
+    let mut buffer = uninit(64);
+    let written = bincode::encode_into_uninit_slice(&value, &mut buffer, config).unwrap();
+    assert_eq!(written, expected);
+}
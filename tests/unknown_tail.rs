@@ -0,0 +1,149 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::{
+    config::{self, Config},
+    de::{read::SliceReader, DecoderImpl, SkipDecode},
+    error::DecodeError,
+    layout::{EncodedLayout, Layout},
+};
+
+/// The version of an order that the proxy was built with
+#[derive(
+    bincode::Encode, bincode::Decode, bincode::SkipDecode, bincode::EncodedLayout, PartialEq, Debug,
+)]
+#[bincode(length_prefixed)]
+struct OrderV1 {
+    id: u32,
+    item: String,
+    #[bincode(unknown_tail)]
+    unknown: Vec<u8>,
+}
+
+/// A later version of the order, with fields that the proxy does not know about
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(length_prefixed)]
+struct OrderV2 {
+    id: u32,
+    item: String,
+    priority: Option<u8>,
+    notes: Vec<String>,
+    #[bincode(unknown_tail)]
+    unknown: Vec<u8>,
+}
+
+/// The first version, without a field for the unknown bytes
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(length_prefixed)]
+struct OrderSummary {
+    id: u32,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(length_prefixed)]
+struct BorrowedOrder<'a> {
+    id: u32,
+    item: &'a str,
+    #[bincode(unknown_tail)]
+    unknown: &'a [u8],
+}
+
+fn order_v2() -> OrderV2 {
+    OrderV2 {
+        id: 7,
+        item: String::from("widget"),
+        priority: Some(3),
+        notes: vec![String::from("fragile")],
+        unknown: Vec::new(),
+    }
+}
+
+#[test]
+fn test_proxy_round_trip() {
+    let config = config::standard();
+    let produced = bincode::encode_to_vec(order_v2(), config).unwrap();
+    assert_eq!(produced[..8], (produced.len() as u64 - 8).to_le_bytes());
+
+    // the proxy keeps the fields it does not know about
+    let (mut proxied, len): (OrderV1, usize) =
+        bincode::decode_from_slice(&produced, config).unwrap();
+    assert_eq!(len, produced.len());
+    assert_eq!(proxied.id, 7);
+    assert_eq!(proxied.item, "widget");
+    assert_eq!(
+        proxied.unknown,
+        bincode::encode_to_vec((Some(3u8), vec!["fragile"]), config).unwrap()
+    );
+    assert_eq!(bincode::encode_to_vec(&proxied, config).unwrap(), produced);
+
+    // and writes them back after the fields it changed
+    proxied.item = String::from("gadget");
+    let forwarded = bincode::encode_to_vec(&proxied, config).unwrap();
+    let (consumed, len): (OrderV2, usize) = bincode::decode_from_slice(&forwarded, config).unwrap();
+    assert_eq!(len, forwarded.len());
+    assert_eq!(
+        consumed,
+        OrderV2 {
+            item: String::from("gadget"),
+            ..order_v2()
+        }
+    );
+
+    let (borrowed, _): (BorrowedOrder, usize) =
+        bincode::borrow_decode_from_slice(&produced, config).unwrap();
+    assert_eq!(borrowed.item, "widget");
+    assert_eq!(borrowed.unknown, proxied.unknown);
+    assert_eq!(bincode::encode_to_vec(&borrowed, config).unwrap(), produced);
+}
+
+fn fixed_size<T: SkipDecode, C: Config>(_: C) -> Option<usize> {
+    T::fixed_size::<C>()
+}
+
+#[test]
+fn test_skip_unknown_fields() {
+    let config = config::legacy().with_big_endian();
+    let produced = bincode::encode_to_vec((order_v2(), 9u8, order_v2()), config).unwrap();
+
+    // without an `unknown_tail`, the bytes after the known fields are skipped
+    let (decoded, len): ((OrderSummary, u8, OrderV1), usize) =
+        bincode::decode_from_slice(&produced, config).unwrap();
+    assert_eq!(len, produced.len());
+    assert_eq!(decoded.0, OrderSummary { id: 7 });
+    assert_eq!(decoded.1, 9);
+    assert_eq!(decoded.2.item, "widget");
+
+    let mut decoder = DecoderImpl::new(SliceReader::new(&produced), config, ());
+    OrderV1::skip(&mut decoder).unwrap();
+    assert_eq!(decoder.into_reader().remaining()[0], 9);
+    assert_eq!(fixed_size::<OrderV1, _>(config), None);
+}
+
+#[test]
+fn test_section_too_short() {
+    let config = config::standard();
+    let mut produced = bincode::encode_to_vec(order_v2(), config).unwrap();
+    // the section ends in the middle of the item
+    produced[0] = 4;
+    let err = bincode::decode_from_slice::<OrderV2, _>(&produced, config).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TakeLimitExceeded { limit: 4, .. }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_layout() {
+    let Layout::LengthPrefixed(layout) = OrderV1::layout() else {
+        panic!("{:?}", OrderV1::layout());
+    };
+    let Layout::Struct { name, fields } = *layout else {
+        panic!("{layout:?}");
+    };
+    assert_eq!(name, "OrderV1");
+    assert_eq!(fields[2].layout, Layout::RemainingBytes);
+    assert!(OrderV1::layout().to_json(config::standard()).starts_with(
+        r#"{"endian":"little","layout":{"kind":"length_prefixed","layout":{"kind":"struct""#
+    ));
+}
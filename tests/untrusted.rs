@@ -0,0 +1,219 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+extern crate bincode_next as bincode;
+
+use bincode::config::{self, Config};
+use bincode::error::DecodeError;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+/// Keeps track of the largest allocation on every thread, so the tests can run in parallel
+struct LargestAllocation;
+
+thread_local! {
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for LargestAllocation {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.with(|largest| largest.set(largest.get().max(layout.size())));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.with(|largest| largest.set(largest.get().max(new_size)));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAllocation = LargestAllocation;
+
+/// Runs `f` and returns the largest allocation it made
+fn largest_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    LARGEST.with(|largest| largest.set(0));
+    let result = f();
+    (result, LARGEST.with(Cell::get))
+}
+
+const LIMIT: usize = 512;
+
+#[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+enum Nested {
+    Leaf,
+    Node(Box<Nested>),
+}
+
+#[derive(bincode::Encode, bincode::Decode, Debug, PartialEq)]
+struct Request {
+    user: String,
+    scores: BTreeMap<u32, u32>,
+    flags: Vec<bool>,
+}
+
+fn decode<T: bincode::Decode<()>>(input: &[u8], config: impl Config) -> Result<T, DecodeError> {
+    let (result, largest) =
+        largest_allocation(|| bincode::decode_from_slice::<T, _>(input, config));
+    if config.limit().is_some() {
+        assert!(largest <= LIMIT, "allocated {largest} bytes");
+    }
+    result.map(|(value, _)| value)
+}
+
+/// A length prefix of `len`, followed by `data`
+fn with_len(len: u64, data: &[u8]) -> Vec<u8> {
+    let mut input = bincode::encode_to_vec(len, config::standard()).unwrap();
+    input.extend_from_slice(data);
+    input
+}
+
+#[test]
+fn test_huge_lengths() {
+    let untrusted = config::untrusted(LIMIT);
+
+    // more data than the limit allows
+    let input = bincode::encode_to_vec(vec![7u8; 10_000], config::standard()).unwrap();
+    assert_eq!(
+        decode::<Vec<u8>>(&input, config::standard()).unwrap().len(),
+        10_000
+    );
+    let err = decode::<Vec<u8>>(&input, untrusted).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+
+    // a length that is far larger than the input is rejected before anything is allocated for it
+    for len in [1 << 20, 1 << 40, u64::MAX] {
+        let input = with_len(len, &[1, 2, 3, 4]);
+        assert!(decode::<Vec<u64>>(&input, config::standard()).is_err());
+        let err = decode::<Vec<u64>>(&input, untrusted).unwrap_err();
+        assert!(
+            matches!(err.inner(), DecodeError::LimitExceeded),
+            "{len}: {err:?}"
+        );
+        let err = decode::<String>(&input, untrusted).unwrap_err();
+        assert!(
+            matches!(err.inner(), DecodeError::LimitExceeded),
+            "{len}: {err:?}"
+        );
+    }
+
+    // readers that do not know how much input is left allocate up to the limit, not up to the length
+    let input = with_len(1 << 40, &[0; 64]);
+    let (result, largest) = largest_allocation(|| {
+        bincode::decode_from_std_read::<Vec<u8>, _, _>(&mut &input[..], untrusted)
+    });
+    let err = result.unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+    assert!(largest <= LIMIT, "allocated {largest} bytes");
+}
+
+#[test]
+fn test_deep_nesting() {
+    let shallow = [[1u8; 16].as_slice(), &[0]].concat();
+    assert!(decode::<Nested>(&shallow, config::untrusted(LIMIT)).is_ok());
+
+    // `standard()` would decode this until the stack overflows
+    let input = [vec![1u8; 1_000_000], vec![0]].concat();
+    let err = decode::<Nested>(&input, config::untrusted(LIMIT)).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded), "{err:?}");
+}
+
+#[test]
+fn test_duplicate_keys() {
+    let pairs = bincode::encode_to_vec([(1u32, 1u32), (1, 2)], config::standard()).unwrap();
+    let input = with_len(2, &pairs);
+    let map = decode::<BTreeMap<u32, u32>>(&input, config::standard()).unwrap();
+    assert_eq!(map, BTreeMap::from([(1, 2)]));
+    let err = decode::<BTreeMap<u32, u32>>(&input, config::untrusted(LIMIT)).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::NonCanonicalMapOrder { index: 1 }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_trailing_bytes() {
+    let request = Request {
+        user: String::from("ann"),
+        scores: BTreeMap::from([(1, 10), (2, 20)]),
+        flags: vec![true, false],
+    };
+    let mut input = bincode::encode_to_vec(&request, config::untrusted(LIMIT)).unwrap();
+    assert_eq!(
+        decode::<Request>(&input, config::untrusted(LIMIT)).unwrap(),
+        request
+    );
+
+    input.extend_from_slice(b"smuggled");
+    assert_eq!(
+        decode::<Request>(&input, config::standard()).unwrap(),
+        request
+    );
+    let err = decode::<Request>(&input, config::untrusted(LIMIT)).unwrap_err();
+    assert!(
+        matches!(err.inner(), DecodeError::TrailingBytes { remaining: 8 }),
+        "{err:?}"
+    );
+
+    // the other functions that decode from a slice check it as well
+    let err = bincode::borrow_decode_from_slice::<&str, _>(&[1, b'a', 0], config::untrusted(LIMIT))
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::TrailingBytes { remaining: 1 }
+    ));
+    let err = bincode::decode_from_segments::<u16, _>(&[&[1], &[2, 3]], config::untrusted(LIMIT))
+        .unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        DecodeError::TrailingBytes { remaining: 2 }
+    ));
+}
+
+#[test]
+fn test_invalid_values() {
+    // these are rejected by every config
+    fn check(config: impl Config) {
+        let err = decode::<Request>(&[1, 0xFF, 0, 0], config).unwrap_err();
+        assert!(matches!(err.inner(), DecodeError::Utf8 { .. }), "{err:?}");
+        let err = decode::<Request>(&[0, 0, 1, 2], config).unwrap_err();
+        assert!(
+            matches!(err.inner(), DecodeError::InvalidBooleanValue(2)),
+            "{err:?}"
+        );
+    }
+    check(config::standard());
+    check(config::untrusted(LIMIT));
+}
+
+#[test]
+fn test_overrides() {
+    let config = config::untrusted(LIMIT);
+    assert_eq!(config.limit(), Some(LIMIT));
+    assert_eq!(config.map_order(), config::MapOrder::Strict);
+    assert_eq!(config.trailing_bytes(), config::TrailingBytes::Reject);
+    assert_eq!(config.allocation(), config::Allocation::Fallible);
+    assert_eq!(config.utf8_decoding(), config::Utf8Decoding::Strict);
+
+    // the encoding is the same as that of `standard()`
+    let value = (
+        String::from("a"),
+        vec![1u64, 300],
+        BTreeMap::from([(1u8, 2u8)]),
+    );
+    assert_eq!(
+        bincode::encode_to_vec(&value, config).unwrap(),
+        bincode::encode_to_vec(&value, config::standard()).unwrap()
+    );
+
+    let config = config.with_any_trailing_bytes().with_runtime_limit(4);
+    assert_eq!(config.trailing_bytes(), config::TrailingBytes::Any);
+    let (value, len) = bincode::decode_from_slice::<u8, _>(&[1, 2], config).unwrap();
+    assert_eq!((value, len), (1, 1));
+    let err = bincode::decode_from_slice::<Vec<u8>, _>(&[5, 1, 2, 3, 4, 5], config).unwrap_err();
+    assert!(matches!(err.inner(), DecodeError::LimitExceeded));
+}
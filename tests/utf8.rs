@@ -0,0 +1,92 @@
+#![cfg(feature = "alloc")]
+
+extern crate bincode_next as bincode;
+
+use bincode::error::DecodeError;
+use std::borrow::Cow;
+
+/// Strings of a couple of KB, so the SIMD validation of the `simdutf8` feature handles full blocks and a tail
+fn valid_strings() -> Vec<String> {
+    vec![
+        String::new(),
+        "a".repeat(4096 + 13),
+        "héllo wörld ".repeat(500),
+        "日本語のテキスト".repeat(300),
+        "🦀 crab ".repeat(700),
+        format!("{}é", "x".repeat(8191)),
+    ]
+}
+
+/// Bytes that are not valid UTF-8, with the error at the start, in the middle and at the end
+fn invalid_strings() -> Vec<Vec<u8>> {
+    let ascii = "a".repeat(5000).into_bytes();
+    let mut strings = vec![vec![0xFF], b"abc\xC0\x80".to_vec()];
+    for (position, invalid) in [
+        (0, &[0x80][..]),
+        (2500, &[0xE6, 0x97]),
+        (4999, &[0xF0, 0x9F, 0xA6]),
+        (5000, &[0xED, 0xA0, 0x80]),
+    ] {
+        let mut bytes = ascii.clone();
+        bytes.splice(position..position, invalid.iter().copied());
+        strings.push(bytes);
+    }
+    let mut multibyte = "ü".repeat(2000).into_bytes();
+    multibyte[3001] = b'u';
+    strings.push(multibyte);
+    strings
+}
+
+#[test]
+fn test_decode_valid_strings() {
+    let config = bincode::config::standard();
+    for string in valid_strings() {
+        let encoded = bincode::encode_to_vec(&string, config).unwrap();
+
+        let (decoded, len): (String, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, string);
+        assert_eq!(len, encoded.len());
+        let (decoded, _): (&str, usize) =
+            bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, string);
+        let (decoded, _): (Cow<str>, usize) =
+            bincode::borrow_decode_from_slice(&encoded, config).unwrap();
+        assert!(matches!(decoded, Cow::Borrowed(s) if s == string));
+        let (decoded, _): (Box<str>, usize) =
+            bincode::decode_from_slice(&encoded, config.with_lossy_utf8_strings()).unwrap();
+        assert_eq!(&*decoded, string);
+    }
+}
+
+#[test]
+fn test_decode_invalid_strings() {
+    let config = bincode::config::standard();
+    for bytes in invalid_strings() {
+        let expected = core::str::from_utf8(&bytes).unwrap_err();
+        let encoded = bincode::encode_to_vec(bytes.as_slice(), config).unwrap();
+        let check = |err: DecodeError| match err.into_inner() {
+            DecodeError::Utf8 {
+                inner,
+                valid_up_to,
+                byte,
+            } => {
+                assert_eq!(inner, expected);
+                assert_eq!(valid_up_to, expected.valid_up_to());
+                assert_eq!(byte, bytes[valid_up_to]);
+            }
+            err => panic!("unexpected error {err:?}"),
+        };
+
+        check(bincode::decode_from_slice::<String, _>(&encoded, config).unwrap_err());
+        check(bincode::borrow_decode_from_slice::<&str, _>(&encoded, config).unwrap_err());
+        check(bincode::borrow_decode_from_slice::<Cow<str>, _>(&encoded, config).unwrap_err());
+        let mut buffer = encoded.clone();
+        check(
+            bincode::borrow_decode_from_slice_mut::<&mut str, _>(&mut buffer, config).unwrap_err(),
+        );
+
+        let (lossy, _): (String, usize) =
+            bincode::decode_from_slice(&encoded, config.with_lossy_utf8_strings()).unwrap();
+        assert_eq!(lossy, String::from_utf8_lossy(&bytes));
+    }
+}
@@ -126,6 +126,11 @@ where
             .with_variable_int_encoding(),
         &cmp,
     );
+    the_same_with_config(
+        &element,
+        bincode::config::standard().with_fallible_allocation(),
+        &cmp,
+    );
 }
 
 #[cfg(feature = "serde")]